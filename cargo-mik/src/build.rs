@@ -0,0 +1,315 @@
+//! `cargo mik build`: build the handler and compose it with the bridge.
+//!
+//! Replaces the manual steps:
+//!
+//! ```bash
+//! cargo component build --release
+//! curl -LO https://github.com/dufeutech/mik-sdk/releases/download/v0.1.2/mik-bridge.wasm
+//! wac plug mik-bridge.wasm --plug target/wasm32-wasip2/release/my_api.wasm -o service.wasm
+//! ```
+//!
+//! The bridge matches the mik-sdk version in `Cargo.lock`: a crates.io or
+//! git dependency downloads that release's `mik-bridge.wasm` (cached under
+//! `target/mik`), a path dependency builds `mik-bridge` from the same
+//! checkout.
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Written by the `__mik_write_schema` test `routes!` generates.
+const OPENAPI_FILE: &str = "openapi.json";
+
+/// Targets `cargo component` may build for, depending on its version.
+const TARGETS: &[&str] = &["wasm32-wasip2", "wasm32-wasip1"];
+
+/// Arguments of `cargo mik build`.
+#[derive(Args)]
+pub struct BuildArgs {
+    /// Composed component to write (default: dist/<lib>.wasm)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Use this bridge component instead of resolving one
+    #[arg(long, value_name = "FILE", conflicts_with = "bridge_source")]
+    bridge: Option<PathBuf>,
+
+    /// Build the bridge from a local mik-sdk checkout
+    #[arg(long, value_name = "DIR")]
+    bridge_source: Option<PathBuf>,
+}
+
+/// Where the bridge comes from.
+#[derive(Debug, PartialEq, Eq)]
+enum BridgeSource {
+    /// Download the `mik-bridge.wasm` asset of this release.
+    Release(String),
+    /// Build `mik-bridge` in this mik-sdk checkout.
+    Checkout(PathBuf),
+}
+
+/// Build, resolve the bridge and compose.
+pub fn run(args: &BuildArgs) -> Result<()> {
+    let output = compose(args)?;
+    println!();
+    println!("Run it with:");
+    println!("  wasmtime serve -S cli=y {}", output.display());
+    Ok(())
+}
+
+/// Build the handler and compose it with the bridge, returning the path of
+/// the composed component.
+pub fn compose(args: &BuildArgs) -> Result<PathBuf> {
+    let manifest = fs::read_to_string("Cargo.toml")
+        .context("no Cargo.toml here, run `cargo mik build` in the handler crate")?;
+    let lib = lib_name(&manifest)?;
+
+    exec(
+        Command::new("cargo").args(["component", "build", "--release"]),
+        "cargo install cargo-component --locked",
+    )?;
+    let handler = component(&target_dir()?, &lib)?;
+
+    let bridge = if let Some(bridge) = &args.bridge {
+        bridge.clone()
+    } else {
+        let source = match &args.bridge_source {
+            Some(dir) => BridgeSource::Checkout(dir.clone()),
+            None => sdk_source()?,
+        };
+        match source {
+            BridgeSource::Checkout(dir) => build_bridge(&dir)?,
+            BridgeSource::Release(version) => download_bridge(&target_dir()?, &version)?,
+        }
+    };
+
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| Path::new("dist").join(format!("{lib}.wasm")));
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("cannot create {}", parent.display()))?;
+    }
+    exec(
+        Command::new("wac")
+            .arg("plug")
+            .arg(&bridge)
+            .arg("--plug")
+            .arg(&handler)
+            .arg("-o")
+            .arg(&output),
+        "cargo install wac-cli --locked",
+    )?;
+
+    println!("Composed {}", output.display());
+    Ok(output)
+}
+
+/// The `OpenAPI` document of the handler crate, from the test `routes!`
+/// generates.
+pub fn openapi() -> Result<String> {
+    exec(
+        Command::new("cargo").args(["test", "--lib", "-q", "__mik_write_schema"]),
+        "rustup",
+    )?;
+    fs::read_to_string(OPENAPI_FILE)
+        .with_context(|| format!("{OPENAPI_FILE} not generated, does the crate use routes!?"))
+}
+
+/// Name of the `.wasm` file cargo writes: `[lib] name`, or the package
+/// name with `-` replaced by `_`.
+fn lib_name(manifest: &str) -> Result<String> {
+    let manifest: toml::Table = manifest.parse().context("invalid Cargo.toml")?;
+    let name = |section: &str| {
+        manifest
+            .get(section)
+            .and_then(|s| s.get("name"))
+            .and_then(toml::Value::as_str)
+    };
+    match name("lib").or_else(|| name("package")) {
+        Some(name) => Ok(name.replace('-', "_")),
+        None => bail!("Cargo.toml has no [package] name"),
+    }
+}
+
+/// The build directory: `CARGO_TARGET_DIR`, or `target` next to the
+/// workspace manifest.
+fn target_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    let manifest = capture(Command::new("cargo").args([
+        "locate-project",
+        "--workspace",
+        "--message-format",
+        "plain",
+    ]))?;
+    Ok(Path::new(&manifest).with_file_name("target"))
+}
+
+/// The most recently built release component named `lib`.
+fn component(target: &Path, lib: &str) -> Result<PathBuf> {
+    TARGETS
+        .iter()
+        .map(|t| target.join(t).join("release").join(format!("{lib}.wasm")))
+        .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+        .max()
+        .map(|(_, path)| path)
+        .with_context(|| format!("no {lib}.wasm under {}", target.display()))
+}
+
+/// Resolve the mik-sdk the handler was built against.
+fn sdk_source() -> Result<BridgeSource> {
+    let id = capture(Command::new("cargo").args(["pkgid", "mik-sdk"]))
+        .context("cannot resolve mik-sdk, is it a dependency of this crate?")?;
+    parse_pkgid(&id).with_context(|| format!("unexpected package id `{id}`"))
+}
+
+/// Parse `cargo pkgid` output, e.g.
+/// `registry+https://github.com/rust-lang/crates.io-index#mik-sdk@0.1.2` or
+/// `path+file:///src/mik-sdk/mik-sdk#0.1.2`.
+fn parse_pkgid(id: &str) -> Option<BridgeSource> {
+    let (source, fragment) = id.rsplit_once('#')?;
+    if let Some(path) = source.strip_prefix("path+file://") {
+        // The crate lives in <checkout>/mik-sdk
+        return Some(BridgeSource::Checkout(Path::new(path).parent()?.into()));
+    }
+    let version = fragment.rsplit_once('@').map_or(fragment, |(_, v)| v);
+    (!version.is_empty()).then(|| BridgeSource::Release(version.to_string()))
+}
+
+/// Build `mik-bridge` in a mik-sdk checkout.
+fn build_bridge(checkout: &Path) -> Result<PathBuf> {
+    let dir = checkout.join("mik-bridge");
+    if !dir.join("Cargo.toml").is_file() {
+        bail!("no mik-bridge crate in {}", checkout.display());
+    }
+    exec(
+        Command::new("cargo")
+            .args(["component", "build", "--release"])
+            .current_dir(&dir),
+        "cargo install cargo-component --locked",
+    )?;
+    // Standalone workspace, so it has its own target directory
+    let target =
+        std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| dir.join("target"), PathBuf::from);
+    component(&target, "mik_bridge")
+}
+
+/// Download the bridge of a release, or reuse a previous download.
+fn download_bridge(target: &Path, version: &str) -> Result<PathBuf> {
+    let path = target
+        .join("mik")
+        .join(format!("mik-bridge-{version}.wasm"));
+    if path.is_file() {
+        return Ok(path);
+    }
+    fs::create_dir_all(target.join("mik"))
+        .with_context(|| format!("cannot create {}", target.join("mik").display()))?;
+
+    let url = bridge_url(version);
+    println!("Downloading {url}");
+    // Write beside the cache entry so an interrupted download is not reused
+    let partial = path.with_extension("part");
+    exec(
+        Command::new("curl")
+            .args(["-fsSL", "-o"])
+            .arg(&partial)
+            .arg(&url),
+        "your package manager, or pass --bridge",
+    )?;
+    fs::rename(&partial, &path).with_context(|| format!("cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+/// The `mik-bridge.wasm` asset of a mik-sdk release.
+fn bridge_url(version: &str) -> String {
+    format!("https://github.com/dufeutech/mik-sdk/releases/download/v{version}/mik-bridge.wasm")
+}
+
+/// Run a tool, pointing at `install` when it is missing.
+pub fn exec(command: &mut Command, install: &str) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = match command.status() {
+        Ok(status) => status,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            bail!("`{program}` not found, install it with {install}")
+        },
+        Err(e) => return Err(e).with_context(|| format!("cannot run `{program}`")),
+    };
+    if !status.success() {
+        bail!("`{program}` failed ({status})");
+    }
+    Ok(())
+}
+
+/// Run a tool and return its trimmed stdout.
+fn capture(command: &mut Command) -> Result<String> {
+    let output = command.output().context("cannot run cargo")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lib_name() {
+        let package = "[package]\nname = \"my-api\"\nversion = \"0.1.0\"\n";
+        assert_eq!(lib_name(package).ok().as_deref(), Some("my_api"));
+
+        let renamed = format!("{package}\n[lib]\nname = \"service\"\n");
+        assert_eq!(lib_name(&renamed).ok().as_deref(), Some("service"));
+
+        assert!(lib_name("[workspace]\n").is_err());
+        assert!(lib_name("not toml").is_err());
+    }
+
+    #[test]
+    fn test_parse_pkgid() {
+        assert_eq!(
+            parse_pkgid("registry+https://github.com/rust-lang/crates.io-index#mik-sdk@0.1.2"),
+            Some(BridgeSource::Release("0.1.2".to_string()))
+        );
+        assert_eq!(
+            parse_pkgid("git+https://github.com/dufeutech/mik-sdk?branch=main#0.2.0"),
+            Some(BridgeSource::Release("0.2.0".to_string()))
+        );
+        assert_eq!(
+            parse_pkgid("path+file:///src/mik-sdk/mik-sdk#0.1.2"),
+            Some(BridgeSource::Checkout(PathBuf::from("/src/mik-sdk")))
+        );
+        assert_eq!(parse_pkgid("mik-sdk"), None);
+    }
+
+    #[test]
+    fn test_bridge_url() {
+        assert_eq!(
+            bridge_url("0.1.2"),
+            "https://github.com/dufeutech/mik-sdk/releases/download/v0.1.2/mik-bridge.wasm"
+        );
+    }
+
+    #[test]
+    fn test_component() {
+        let target = std::env::temp_dir().join(format!("cargo-mik-build-{}", std::process::id()));
+        let missing = component(&target, "api");
+
+        let release = target.join("wasm32-wasip1").join("release");
+        let written =
+            fs::create_dir_all(&release).and_then(|()| fs::write(release.join("api.wasm"), b""));
+        let found = component(&target, "api");
+        let _ = fs::remove_dir_all(&target);
+
+        assert!(missing.is_err());
+        assert!(written.is_ok(), "{written:?}");
+        assert_eq!(found.ok(), Some(release.join("api.wasm")));
+    }
+}
@@ -0,0 +1,427 @@
+//! `cargo mik client`: typed clients for a handler crate.
+//!
+//! Reads the `OpenAPI` document `routes!` generates (or `--spec`) and emits
+//! a client with a method per route, typed from the same schemas that
+//! validate requests on the server:
+//!
+//! ```bash
+//! cargo mik client typescript -o ../web/src/api.ts
+//! ```
+
+mod rust;
+mod typescript;
+
+use crate::build;
+use crate::generate::snake_case;
+use anyhow::{Context, Result, bail};
+use clap::{Args, ValueEnum};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// Operation keys of a path item.
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head", "options"];
+
+/// Arguments of `cargo mik client`.
+#[derive(Args)]
+pub struct ClientArgs {
+    /// Client language
+    #[arg(value_enum)]
+    language: Language,
+
+    /// File to write (default: dist/client.<ext>), `-` for stdout
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Read this `OpenAPI` document instead of generating it
+    #[arg(long, value_name = "FILE")]
+    spec: Option<PathBuf>,
+}
+
+/// A client language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Language {
+    /// `fetch`-based TypeScript client
+    Typescript,
+    /// Module using the mik-sdk HTTP client, for service-to-service calls
+    Rust,
+}
+
+/// Generate the client and write it.
+pub fn run(args: &ClientArgs) -> Result<()> {
+    let spec = match &args.spec {
+        Some(path) => {
+            fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?
+        },
+        None => build::openapi()?,
+    };
+    let doc: Value = serde_json::from_str(&spec).context("invalid openapi.json")?;
+    let api = Api::parse(&doc)?;
+    let (code, extension) = match args.language {
+        Language::Typescript => (typescript::render(&api), "ts"),
+        Language::Rust => (rust::render(&api), "rs"),
+    };
+
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("dist/client.{extension}")));
+    if output.as_os_str() == "-" {
+        print!("{code}");
+        return Ok(());
+    }
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("cannot create {}", parent.display()))?;
+    }
+    fs::write(&output, code).with_context(|| format!("cannot write {}", output.display()))?;
+    println!(
+        "Wrote {} ({} operations)",
+        output.display(),
+        api.operations.len()
+    );
+    Ok(())
+}
+
+/// The parts of an `OpenAPI` document a client needs.
+struct Api<'a> {
+    doc: &'a Value,
+    title: &'a str,
+    version: &'a str,
+    /// Component schemas, in document order.
+    schemas: Vec<(&'a str, &'a Value)>,
+    operations: Vec<Operation<'a>>,
+}
+
+/// One route.
+struct Operation<'a> {
+    /// `snake_case` name: the handler name from `operationId`.
+    name: String,
+    /// Upper-case HTTP method.
+    method: String,
+    path: &'a str,
+    summary: Option<&'a str>,
+    deprecated: bool,
+    path_params: Vec<Param<'a>>,
+    query: Vec<Param<'a>>,
+    headers: Vec<Param<'a>>,
+    body: Option<Body<'a>>,
+    /// Schema of the first 2xx JSON response.
+    response: Option<&'a Value>,
+}
+
+/// A path, query or header parameter.
+struct Param<'a> {
+    name: &'a str,
+    required: bool,
+    schema: &'a Value,
+}
+
+/// A JSON or form request body.
+struct Body<'a> {
+    form: bool,
+    schema: &'a Value,
+}
+
+impl<'a> Api<'a> {
+    fn parse(doc: &'a Value) -> Result<Self> {
+        if doc.get("openapi").and_then(Value::as_str).is_none() {
+            bail!("not an OpenAPI 3 document: no `openapi` version");
+        }
+        let mut api = Self {
+            doc,
+            title: doc
+                .pointer("/info/title")
+                .and_then(Value::as_str)
+                .unwrap_or("API"),
+            version: doc
+                .pointer("/info/version")
+                .and_then(Value::as_str)
+                .unwrap_or(""),
+            schemas: doc
+                .pointer("/components/schemas")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(name, schema)| (name.as_str(), schema))
+                .collect(),
+            operations: Vec::new(),
+        };
+
+        let mut names = BTreeSet::new();
+        for (path, item) in doc
+            .get("paths")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            for method in METHODS {
+                if let Some(operation) = item.get(*method) {
+                    let mut operation = api.operation(path, method, operation, item);
+                    // Handler names are unique per crate, not across merged documents
+                    let base = operation.name.clone();
+                    let mut n = 2;
+                    while !names.insert(operation.name.clone()) {
+                        operation.name = format!("{base}_{n}");
+                        n += 1;
+                    }
+                    api.operations.push(operation);
+                }
+            }
+        }
+        Ok(api)
+    }
+
+    fn operation(
+        &self,
+        path: &'a str,
+        method: &str,
+        operation: &'a Value,
+        item: &'a Value,
+    ) -> Operation<'a> {
+        // `routes!` writes `package.handler`
+        let name = operation
+            .get("operationId")
+            .and_then(Value::as_str)
+            .map(|id| id.rsplit('.').next().unwrap_or(id))
+            .map_or_else(|| snake_case(&format!("{method} {path}")), snake_case);
+
+        let mut params: Vec<&'a Value> = Vec::new();
+        for param in [item.get("parameters"), operation.get("parameters")]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_array)
+            .flatten()
+            .map(|p| self.resolve(p))
+        {
+            // Operation parameters override path-level ones
+            let key = |p: &Value| (p.get("name").cloned(), p.get("in").cloned());
+            params.retain(|p| key(p) != key(param));
+            params.push(param);
+        }
+        let located = |location: &str| -> Vec<Param<'a>> {
+            params
+                .iter()
+                .filter(|p| p.get("in").and_then(Value::as_str) == Some(location))
+                .filter_map(|p| {
+                    Some(Param {
+                        name: p.get("name")?.as_str()?,
+                        required: location == "path"
+                            || p.get("required").and_then(Value::as_bool) == Some(true),
+                        schema: p.get("schema").map_or(&Value::Null, |s| self.resolve(s)),
+                    })
+                })
+                .collect()
+        };
+
+        let content = operation
+            .get("requestBody")
+            .map(|b| self.resolve(b))
+            .and_then(|b| b.get("content"))
+            .and_then(Value::as_object);
+        let body = content.and_then(|content| {
+            content.iter().find_map(|(media, m)| {
+                let form = media == "application/x-www-form-urlencoded";
+                let schema = m.get("schema").unwrap_or(&Value::Null);
+                (form || is_json(media)).then(|| Body {
+                    form,
+                    // Form fields are listed one by one
+                    schema: if form { self.resolve(schema) } else { schema },
+                })
+            })
+        });
+
+        let response = operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(code, response)| Some((code.parse::<u16>().ok()?, response)))
+            .filter(|(code, _)| (200..300).contains(code))
+            .min_by_key(|(code, _)| *code)
+            .and_then(|(_, response)| self.resolve(response).get("content"))
+            .and_then(Value::as_object)
+            .and_then(|content| content.iter().find(|(media, _)| is_json(media)))
+            .and_then(|(_, m)| m.get("schema"));
+
+        Operation {
+            name,
+            method: method.to_ascii_uppercase(),
+            path,
+            summary: operation.get("summary").and_then(Value::as_str),
+            deprecated: operation.get("deprecated").and_then(Value::as_bool) == Some(true),
+            path_params: located("path"),
+            query: located("query"),
+            headers: located("header"),
+            body,
+            response,
+        }
+    }
+
+    /// Follow a local `$ref`, returning the value itself otherwise.
+    fn resolve(&self, value: &'a Value) -> &'a Value {
+        let mut value = value;
+        // Bounded, a reference cycle would otherwise never end
+        for _ in 0..8 {
+            match value
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix('#'))
+                .and_then(|pointer| self.doc.pointer(pointer))
+            {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+        value
+    }
+}
+
+fn is_json(media: &str) -> bool {
+    media == "application/json" || media.ends_with("+json")
+}
+
+/// The non-null `type` of a schema and whether it is nullable, from
+/// `nullable: true` (3.0) or a `null` type (3.1).
+fn schema_type(schema: &Value) -> (Option<&str>, bool) {
+    let nullable = schema.get("nullable").and_then(Value::as_bool) == Some(true);
+    match schema.get("type") {
+        Some(Value::String(ty)) => (Some(ty), nullable),
+        Some(Value::Array(types)) => (
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|t| *t != "null"),
+            nullable || types.iter().any(|t| t == "null"),
+        ),
+        _ => (None, nullable),
+    }
+}
+
+/// Names of the required properties of an object schema.
+fn required(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// `User` for `#/components/schemas/User`.
+fn component_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap_or(reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A document shaped like the ones `routes!` generates.
+    pub const SPEC: &str = r##"{
+        "openapi": "3.0.0",
+        "info": { "title": "users-api", "version": "0.2.0" },
+        "paths": {
+            "/users": {
+                "get": {
+                    "operationId": "users_api.list_users",
+                    "summary": "List users",
+                    "parameters": [
+                        { "name": "search", "in": "query", "required": false, "schema": { "type": "string" } },
+                        { "name": "page", "in": "query", "required": false, "schema": { "type": "integer", "default": 1 } }
+                    ],
+                    "responses": {
+                        "200": { "description": "Success", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/User" } } } } },
+                        "4XX": { "description": "Client Error", "content": { "application/problem+json": { "schema": { "$ref": "#/components/schemas/ProblemDetails" } } } }
+                    }
+                },
+                "post": {
+                    "operationId": "users_api.create_user",
+                    "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/CreateUser" } } } },
+                    "responses": { "201": { "description": "Created", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } } } }
+                }
+            },
+            "/users/{id}": {
+                "delete": {
+                    "operationId": "users_api.delete_user",
+                    "deprecated": true,
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } },
+                        { "name": "x-request-id", "in": "header", "required": false, "schema": { "type": "string" } }
+                    ],
+                    "responses": { "204": { "description": "No Content" } }
+                }
+            },
+            "/login": {
+                "post": {
+                    "operationId": "users_api.login",
+                    "requestBody": { "content": { "application/x-www-form-urlencoded": { "schema": { "type": "object", "required": ["user"], "properties": { "user": { "type": "string" }, "remember": { "type": "boolean" } } } } } },
+                    "responses": { "200": { "description": "Success" } }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "User": {
+                    "type": "object",
+                    "description": "A registered user",
+                    "required": ["id", "name", "role"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "name": { "type": "string", "description": "Display name" },
+                        "email": { "type": "string", "format": "email", "nullable": true },
+                        "role": { "$ref": "#/components/schemas/Role" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                        "created-at": { "type": "integer" },
+                        "meta": { "type": "object", "additionalProperties": { "type": "number" } }
+                    }
+                },
+                "CreateUser": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": { "name": { "type": "string", "minLength": 1 }, "role": { "$ref": "#/components/schemas/Role" } }
+                },
+                "Role": { "type": "string", "enum": ["admin", "member"] },
+                "ProblemDetails": {
+                    "type": "object",
+                    "required": ["type", "title", "status"],
+                    "properties": { "type": { "type": "string" }, "title": { "type": "string" }, "status": { "type": "integer" }, "detail": { "type": "string" } }
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn test_parse() {
+        let doc: Value = serde_json::from_str(SPEC).unwrap_or_default();
+        let Ok(api) = Api::parse(&doc) else {
+            panic!("SPEC should parse");
+        };
+
+        assert_eq!((api.title, api.version), ("users-api", "0.2.0"));
+        assert_eq!(api.schemas.len(), 4);
+        let names: Vec<&str> = api.operations.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, ["list_users", "create_user", "delete_user", "login"]);
+
+        let [list, create, delete, login] = api.operations.as_slice() else {
+            panic!("expected 4 operations");
+        };
+        assert_eq!(list.method, "GET");
+        assert_eq!(list.query.len(), 2);
+        assert!(list.query.iter().all(|p| !p.required));
+        assert!(list.response.is_some());
+        assert!(create.body.as_ref().is_some_and(|b| !b.form));
+
+        assert!(delete.deprecated && delete.response.is_none());
+        assert_eq!(delete.path_params.first().map(|p| p.name), Some("id"));
+        assert_eq!(delete.headers.first().map(|p| p.name), Some("x-request-id"));
+        assert!(login.body.as_ref().is_some_and(|b| b.form));
+    }
+
+    #[test]
+    fn test_rejects_non_openapi() {
+        assert!(Api::parse(&Value::Null).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! Rust client: `#[derive(Type)]` structs per schema and a `Client` with a
+//! method per route, sending through `mik_sdk::http_client`.
+//!
+//! Meant for one mik service calling another: the generated module goes in
+//! the calling crate and is regenerated when the called routes change.
+
+use super::{Api, Operation, Param, required};
+use crate::generate::{Generator, ident, pascal_case, scalar_type, snake_case};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Error type, `Client` and helpers every generated client shares.
+const RUNTIME: &str = r#"/// A failed call.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request could not be sent.
+    Http(http_client::Error),
+    /// The service answered with a non-2xx status, usually a problem body.
+    Status(Response),
+    /// The response body is not the documented type.
+    Parse(String),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http(e) => write!(f, "{e}"),
+            Self::Status(response) => write!(
+                f,
+                "HTTP {}: {}",
+                response.status,
+                response.text().unwrap_or_default()
+            ),
+            Self::Parse(message) => write!(f, "unexpected response: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<http_client::Error> for ClientError {
+    fn from(e: http_client::Error) -> Self {
+        Self::Http(e)
+    }
+}
+
+fn send(request: ClientRequest) -> Result<Response, ClientError> {
+    let response = request.send()?;
+    if response.is_success() {
+        Ok(response)
+    } else {
+        Err(ClientError::Status(response))
+    }
+}
+
+fn parse<T: FromJson>(response: &Response) -> Result<T, ClientError> {
+    let body = json::try_parse_full(response.bytes())
+        .ok_or_else(|| ClientError::Parse("invalid JSON".to_string()))?;
+    T::from_json(&body).map_err(|e| ClientError::Parse(e.to_string()))
+}
+
+fn encode(value: &str) -> String {
+    percent_encode(value.as_bytes())
+}
+
+fn form_body(pairs: &[(&str, String)]) -> String {
+    let pairs: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| format!("{}={}", encode(name), encode(value)))
+        .collect();
+    pairs.join("&")
+}
+
+fn query_string(pairs: &[(&str, String)]) -> String {
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", form_body(pairs))
+    }
+}
+"#;
+
+/// Render the client module for a document.
+pub fn render(api: &Api) -> String {
+    let mut types = Generator::new(api.doc);
+    types.components();
+    let mut inputs = Vec::new();
+    let mut methods = Vec::new();
+    for operation in &api.operations {
+        methods.push(method(&mut types, &mut inputs, operation));
+    }
+
+    let mut out = String::new();
+    let version = if api.version.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", api.version)
+    };
+    let _ = writeln!(out, "//! Client for {}{version}.", api.title);
+    out.push_str(
+        "//!\n//! Generated by `cargo mik client rust`. Do not edit: regenerate it when\n\
+         //! the routes change.\n\n",
+    );
+    out.push_str("#![allow(dead_code, unused_imports)]\n\n");
+    out.push_str("use mik_sdk::Type;\n");
+    out.push_str("use mik_sdk::encoding::percent_encode;\n");
+    out.push_str("use mik_sdk::http_client::{self, ClientRequest, Method, Response};\n");
+    out.push_str("use mik_sdk::json::{self, ToJson};\n");
+    out.push_str("use mik_sdk::typed::FromJson;\n");
+    if types.uses_hashmap() {
+        out.push_str("use std::collections::HashMap;\n");
+    }
+    for todo in types.todos() {
+        let _ = write!(out, "\n// TODO: {todo}");
+    }
+    if !types.todos().is_empty() {
+        out.push('\n');
+    }
+
+    section(&mut out, "TYPES");
+    let items: Vec<&str> = types
+        .types()
+        .iter()
+        .chain(&inputs)
+        .map(String::as_str)
+        .collect();
+    out.push_str(&items.join("\n"));
+
+    section(&mut out, "CLIENT");
+    let _ = write!(
+        out,
+        r"/// Client for {}.
+#[derive(Debug, Clone)]
+pub struct Client {{
+    base_url: String,
+    headers: Vec<(String, String)>,
+}}
+
+impl Client {{
+    /// A client for the service at `base_url`, e.g. `http://users:8080`.
+    pub fn new(base_url: &str) -> Self {{
+        Self {{
+            base_url: base_url.trim_end_matches('/').to_string(),
+            headers: Vec::new(),
+        }}
+    }}
+
+    /// Send a header with every request, e.g. `authorization`.
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> Self {{
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }}
+",
+        api.title
+    );
+    for method in &methods {
+        out.push('\n');
+        out.push_str(method);
+    }
+    out.push_str(
+        r#"
+    fn request(&self, method: Method, path: &str) -> ClientRequest {
+        let mut request = http_client::request(method, &format!("{}{path}", self.base_url));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+}
+
+"#,
+    );
+    out.push_str(RUNTIME);
+    out
+}
+
+/// A `Client` method, defining the structs for its query, headers or form.
+fn method<'a>(
+    types: &mut Generator<'a>,
+    inputs: &mut Vec<String>,
+    operation: &Operation<'a>,
+) -> String {
+    let base = pascal_case(&operation.name);
+    let mut params = vec!["&self".to_string()];
+    let (mut path, mut args) = path_format(operation, &mut params);
+
+    let mut chain = String::new();
+    match &operation.body {
+        Some(body) if body.form => {
+            let fields = form_fields(body.schema);
+            let ty = types.unique(&format!("{base}Form"));
+            inputs.push(params_struct(&ty, "Form body", &operation.name, &fields));
+            params.push(format!("form: &{ty}"));
+            chain.push_str(
+                "\n            .header(\"content-type\", \"application/x-www-form-urlencoded\")\
+                 \n            .body(form_body(&form.pairs()).as_bytes())",
+            );
+        },
+        Some(body) => {
+            let ty = types.rust_type(body.schema, &format!("{base}Body"));
+            params.push(format!("body: &{ty}"));
+            chain.push_str("\n            .json(&body.to_json().to_bytes())");
+        },
+        None => {},
+    }
+    if !operation.query.is_empty() {
+        let ty = types.unique(&format!("{base}Query"));
+        inputs.push(params_struct(
+            &ty,
+            "Query parameters",
+            &operation.name,
+            &operation.query,
+        ));
+        params.push(format!("query: &{ty}"));
+        path.push_str("{}");
+        args.push("query_string(&query.pairs())".to_string());
+    }
+    if !operation.headers.is_empty() {
+        let ty = types.unique(&format!("{base}Headers"));
+        inputs.push(params_struct(
+            &ty,
+            "Headers",
+            &operation.name,
+            &operation.headers,
+        ));
+        params.push(format!("headers: &{ty}"));
+    }
+    let returns = operation.response.map_or_else(
+        || "()".to_string(),
+        |s| types.rust_type(s, &format!("{base}Response")),
+    );
+
+    let mut out = String::new();
+    if let Some(summary) = operation.summary {
+        for line in summary.trim().lines() {
+            let _ = writeln!(out, "    /// {}", line.trim_end());
+        }
+    }
+    if operation.deprecated {
+        out.push_str("    #[deprecated]\n");
+    }
+    let _ = writeln!(
+        out,
+        "    pub fn {}({}) -> Result<{returns}, ClientError> {{",
+        ident(&operation.name),
+        params.join(", ")
+    );
+    let path = if args.is_empty() {
+        format!("{path:?}")
+    } else {
+        format!("&format!({path:?}, {})", args.join(", "))
+    };
+    let method = pascal_case(&operation.method.to_ascii_lowercase());
+    let mutable = if operation.headers.is_empty() {
+        ""
+    } else {
+        "mut "
+    };
+    let request = format!("self.request(Method::{method}, {path})");
+    if chain.is_empty() {
+        let _ = writeln!(out, "        let {mutable}request = {request};");
+    } else {
+        // Laid out the way rustfmt breaks a method chain
+        let request = request.replacen("self.", "self\n            .", 1);
+        let _ = writeln!(out, "        let {mutable}request = {request}{chain};");
+    }
+    if !operation.headers.is_empty() {
+        out.push_str("        for (name, value) in headers.pairs() {\n");
+        out.push_str("            request = request.header(name, &value);\n");
+        out.push_str("        }\n");
+    }
+    if operation.response.is_some() {
+        out.push_str("        parse(&send(request)?)\n");
+    } else {
+        out.push_str("        send(request).map(drop)\n");
+    }
+    out.push_str("    }\n");
+    out
+}
+
+/// The `format!` string and arguments of a path, adding its parameters:
+/// `/users/{id}` becomes `"/users/{}", encode(id)`.
+fn path_format(operation: &Operation, params: &mut Vec<String>) -> (String, Vec<String>) {
+    let mut path = String::new();
+    let mut args = Vec::new();
+    let mut rest = operation.path;
+    while let Some((before, after)) = rest.split_once('{') {
+        let Some((key, after)) = after.split_once('}') else {
+            break;
+        };
+        path.push_str(before);
+        path.push_str("{}");
+        let param = ident(&snake_case(key));
+        let ty = operation
+            .path_params
+            .iter()
+            .find(|p| p.name == key)
+            .and_then(|p| scalar_type(p.schema))
+            .unwrap_or("String");
+        if ty == "String" {
+            params.push(format!("{param}: &str"));
+            args.push(format!("encode({param})"));
+        } else {
+            params.push(format!("{param}: {ty}"));
+            args.push(format!("encode(&{param}.to_string())"));
+        }
+        rest = after;
+    }
+    path.push_str(rest);
+    (path, args)
+}
+
+/// The fields of a form body schema, as parameters.
+fn form_fields(schema: &Value) -> Vec<Param<'_>> {
+    let required = required(schema);
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(key, schema)| Param {
+            name: key,
+            required: required.contains(&key.as_str()),
+            schema,
+        })
+        .collect()
+}
+
+/// A plain struct for query, header or form fields, with the pairs to send.
+fn params_struct(name: &str, what: &str, operation: &str, params: &[Param]) -> String {
+    let mut fields = String::new();
+    let mut pairs = String::new();
+    for param in params {
+        let field = ident(&snake_case(param.name));
+        let ty = scalar_type(param.schema).unwrap_or("String");
+        if param.required {
+            let _ = writeln!(fields, "    pub {field}: {ty},");
+            let _ = writeln!(
+                pairs,
+                "        pairs.push(({:?}, self.{field}.to_string()));",
+                param.name
+            );
+        } else {
+            let _ = writeln!(fields, "    pub {field}: Option<{ty}>,");
+            let _ = writeln!(
+                pairs,
+                "        if let Some(value) = &self.{field} {{\n            pairs.push(({:?}, value.to_string()));\n        }}",
+                param.name
+            );
+        }
+    }
+    format!(
+        "/// {what} of `{operation}`.\n#[derive(Debug, Clone, Default)]\npub struct {name} {{\n{fields}}}\n\n\
+         impl {name} {{\n    fn pairs(&self) -> Vec<(&'static str, String)> {{\n        let mut pairs = Vec::new();\n{pairs}        pairs\n    }}\n}}\n"
+    )
+}
+
+fn section(out: &mut String, title: &str) {
+    let rule = "=".repeat(76);
+    let _ = write!(out, "\n// {rule}\n// {title}\n// {rule}\n\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::SPEC;
+    use super::*;
+
+    fn client() -> String {
+        let doc: Value = serde_json::from_str(SPEC).unwrap_or_default();
+        Api::parse(&doc).map(|api| render(&api)).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_render_types() {
+        let client = client();
+        assert!(client.starts_with("//! Client for users-api 0.2.0.\n"));
+        assert!(client.contains("#[derive(Type)]\npub struct User {\n    pub id: String,\n"));
+        assert!(client.contains("#[derive(Type)]\npub enum Role {\n    Admin,\n    Member,\n}\n"));
+        assert!(client.contains("use std::collections::HashMap;\n"));
+        assert!(client.contains(
+            "pub struct ListUsersQuery {\n    pub search: Option<String>,\n    pub page: Option<i64>,\n}\n"
+        ));
+        assert!(
+            client.contains("            pairs.push((\"x-request-id\", value.to_string()));\n")
+        );
+        assert!(client.contains("        pairs.push((\"user\", self.user.to_string()));\n"));
+    }
+
+    #[test]
+    fn test_render_methods() {
+        let client = client();
+        assert!(client.contains(
+            "    /// List users\n    pub fn list_users(&self, query: &ListUsersQuery) -> Result<Vec<User>, ClientError> {\n        \
+             let request = self.request(Method::Get, &format!(\"/users{}\", query_string(&query.pairs())));\n        \
+             parse(&send(request)?)\n"
+        ));
+        assert!(client.contains(
+            "        let request = self\n            .request(Method::Post, \"/users\")\n            .json(&body.to_json().to_bytes());\n"
+        ));
+        assert!(client.contains(
+            "    #[deprecated]\n    pub fn delete_user(&self, id: &str, headers: &DeleteUserHeaders) -> Result<(), ClientError> {\n        \
+             let mut request = self.request(Method::Delete, &format!(\"/users/{}\", encode(id)));\n"
+        ));
+        assert!(client.contains("            .body(form_body(&form.pairs()).as_bytes());\n        send(request).map(drop)\n"));
+    }
+
+    #[test]
+    fn test_path_format() {
+        let id = serde_json::json!({ "type": "integer" });
+        let operation = Operation {
+            name: "get_post".to_string(),
+            method: "GET".to_string(),
+            path: "/users/{user-id}/posts/{id}",
+            summary: None,
+            deprecated: false,
+            path_params: vec![Param {
+                name: "id",
+                required: true,
+                schema: &id,
+            }],
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: None,
+            response: None,
+        };
+        let mut params = Vec::new();
+        let (path, args) = path_format(&operation, &mut params);
+        assert_eq!(path, "/users/{}/posts/{}");
+        assert_eq!(args, ["encode(user_id)", "encode(&id.to_string())"]);
+        assert_eq!(params, ["user_id: &str", "id: i64"]);
+    }
+}
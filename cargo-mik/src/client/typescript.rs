@@ -0,0 +1,519 @@
+//! TypeScript client: an interface per schema and a `Client` class with a
+//! method per route, on top of `fetch`.
+//!
+//! No runtime dependencies, so it works in browsers, Node 18+, Deno, Bun
+//! and workers alike.
+
+use super::{Api, Operation, Param, component_name, required, schema_type};
+use crate::generate::pascal_case;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Helpers every generated client shares.
+const RUNTIME: &str = r#"/** An RFC 7807 problem, as returned by `error!`. */
+export interface ApiProblem {
+  type?: string;
+  title?: string;
+  status?: number;
+  detail?: string;
+  [key: string]: unknown;
+}
+
+/** A non-2xx response, with its problem body when there is one. */
+export class ApiError extends Error {
+  constructor(
+    readonly status: number,
+    readonly problem: ApiProblem | undefined,
+  ) {
+    super(problem?.detail ?? problem?.title ?? `HTTP ${status}`);
+    this.name = "ApiError";
+  }
+}
+
+export interface ClientOptions {
+  /** Where the service runs, e.g. `https://api.example.com`. */
+  baseUrl: string;
+  /** Headers sent with every request, e.g. `authorization`. */
+  headers?: Record<string, string>;
+  /** `fetch` implementation, the global one by default. */
+  fetch?: typeof fetch;
+}
+
+interface Init {
+  query?: object;
+  headers?: object;
+  body?: unknown;
+  form?: object;
+}
+
+/** Scalars as-is, arrays as repeated keys, `null` and `undefined` left out. */
+function encode(params: object | undefined): URLSearchParams {
+  const encoded = new URLSearchParams();
+  for (const [key, value] of Object.entries(params ?? {})) {
+    for (const item of Array.isArray(value) ? value : [value]) {
+      if (item !== undefined && item !== null) encoded.append(key, String(item));
+    }
+  }
+  return encoded;
+}
+"#;
+
+/// Render the client for a document.
+pub fn render(api: &Api) -> String {
+    let mut out = String::new();
+    let version = if api.version.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", api.version)
+    };
+    let _ = writeln!(
+        out,
+        "// Generated by `cargo mik client typescript` from {}{version}.",
+        api.title
+    );
+    out.push_str("// Do not edit: regenerate it when the routes change.\n\n");
+    out.push_str(RUNTIME);
+
+    for (name, schema) in &api.schemas {
+        out.push('\n');
+        declaration(&mut out, name, schema);
+    }
+
+    out.push_str(
+        r#"
+export class Client {
+  private readonly baseUrl: string;
+  private readonly headers: Record<string, string>;
+  private readonly fetch: typeof fetch;
+
+  constructor(options: ClientOptions) {
+    this.baseUrl = options.baseUrl.replace(/\/+$/, "");
+    this.headers = options.headers ?? {};
+    this.fetch = options.fetch ?? globalThis.fetch.bind(globalThis);
+  }
+"#,
+    );
+    for operation in &api.operations {
+        out.push('\n');
+        method(&mut out, operation);
+    }
+    out.push_str(
+        r#"
+  private async request<T>(method: string, path: string, init: Init): Promise<T> {
+    const headers: Record<string, string> = { accept: "application/json", ...this.headers };
+    for (const [key, value] of encode(init.headers)) headers[key] = value;
+    let body: string | undefined;
+    if (init.body !== undefined) {
+      headers["content-type"] = "application/json";
+      body = JSON.stringify(init.body);
+    } else if (init.form !== undefined) {
+      headers["content-type"] = "application/x-www-form-urlencoded";
+      body = encode(init.form).toString();
+    }
+
+    const query = encode(init.query).toString();
+    const url = this.baseUrl + path + (query ? `?${query}` : "");
+    const response = await this.fetch(url, { method, headers, body });
+    const text = await response.text();
+    let data: unknown;
+    try {
+      data = text ? JSON.parse(text) : undefined;
+    } catch {
+      data = undefined;
+    }
+    if (!response.ok) {
+      throw new ApiError(response.status, data as ApiProblem | undefined);
+    }
+    return data as T;
+  }
+}
+"#,
+    );
+    out
+}
+
+/// `export interface` for objects with properties, `export type` otherwise.
+fn declaration(out: &mut String, name: &str, schema: &Value) {
+    doc(out, "", description(schema), is_deprecated(schema));
+    let name = type_name(name);
+    let (ty, nullable) = schema_type(schema);
+    let properties = schema.get("properties").and_then(Value::as_object);
+    if properties.is_some() && !nullable && matches!(ty, None | Some("object")) {
+        let _ = writeln!(out, "export interface {name} {{");
+        properties_of(out, "  ", schema);
+        out.push_str("}\n");
+    } else {
+        let _ = writeln!(out, "export type {name} = {};", ts_type(schema));
+    }
+}
+
+/// One line per property, with its description as a comment.
+fn properties_of(out: &mut String, indent: &str, schema: &Value) {
+    let required = required(schema);
+    for (key, property) in schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        doc(out, indent, description(property), is_deprecated(property));
+        let optional = if required.contains(&key.as_str()) {
+            ""
+        } else {
+            "?"
+        };
+        let _ = writeln!(
+            out,
+            "{indent}{}{optional}: {};",
+            property_name(key),
+            ts_type(property)
+        );
+    }
+}
+
+/// A `Client` method.
+fn method(out: &mut String, operation: &Operation) {
+    doc(out, "  ", operation.summary, operation.deprecated);
+
+    let mut params = Vec::new();
+    let mut init = Vec::new();
+    if !operation.path_params.is_empty() {
+        params.push(format!("path: {}", params_type(&operation.path_params)));
+    }
+    if let Some(body) = &operation.body {
+        let name = if body.form { "form" } else { "body" };
+        params.push(format!("{name}: {}", ts_type(body.schema)));
+        init.push(name);
+    }
+    for (name, group) in [("query", &operation.query), ("headers", &operation.headers)] {
+        if group.is_empty() {
+            continue;
+        }
+        let default = if group.iter().any(|p| p.required) {
+            ""
+        } else {
+            " = {}"
+        };
+        params.push(format!("{name}: {}{default}", params_type(group)));
+        init.push(name);
+    }
+
+    let returns = operation
+        .response
+        .map_or_else(|| "void".to_string(), ts_type);
+    let _ = writeln!(
+        out,
+        "  {}({}): Promise<{returns}> {{",
+        camel_case(&operation.name),
+        params.join(", ")
+    );
+    let init = if init.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", init.join(", "))
+    };
+    let _ = writeln!(
+        out,
+        "    return this.request(\"{}\", {}, {init});",
+        operation.method,
+        path_template(operation.path),
+    );
+    out.push_str("  }\n");
+}
+
+/// `{ id: string; page?: number }` for a group of parameters.
+fn params_type(params: &[Param]) -> String {
+    let fields: Vec<String> = params
+        .iter()
+        .map(|p| {
+            let optional = if p.required { "" } else { "?" };
+            format!("{}{optional}: {}", property_name(p.name), ts_type(p.schema))
+        })
+        .collect();
+    format!("{{ {} }}", fields.join("; "))
+}
+
+/// `/users/{id}` becomes `` `/users/${encodeURIComponent(String(path.id))}` ``.
+fn path_template(path: &str) -> String {
+    let mut out = String::from("`");
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        literal(&mut out, &rest[..start]);
+        let name = &rest[start + 1..start + len];
+        let access = if is_identifier(name) {
+            format!("path.{name}")
+        } else {
+            format!("path[{}]", Value::from(name))
+        };
+        let _ = write!(out, "${{encodeURIComponent(String({access}))}}");
+        rest = &rest[start + len + 1..];
+    }
+    literal(&mut out, rest);
+    out.push('`');
+    out
+}
+
+/// Append template literal text, escaped.
+fn literal(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(c, '`' | '$' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// The TypeScript type of a schema.
+fn ts_type(schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return type_name(component_name(reference));
+    }
+    let (ty, nullable) = schema_type(schema);
+    let base = base_type(schema, ty);
+    if nullable && base != "null" {
+        format!("{} | null", group(base))
+    } else {
+        base
+    }
+}
+
+/// The type of a schema, ignoring nullability.
+fn base_type(schema: &Value, ty: Option<&str>) -> String {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        // JSON literals are TypeScript literals
+        let literals: Vec<String> = values.iter().map(Value::to_string).collect();
+        return literals.join(" | ");
+    }
+    if let Some(variants) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        let variants: Vec<String> = variants.iter().map(ts_type).collect();
+        return variants.join(" | ");
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        let parts: Vec<String> = parts.iter().map(|p| group(ts_type(p))).collect();
+        return parts.join(" & ");
+    }
+    match ty {
+        Some("string") => "string".to_string(),
+        Some("integer" | "number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("null") => "null".to_string(),
+        Some("array") => {
+            let items = schema
+                .get("items")
+                .map_or_else(|| "unknown".to_string(), ts_type);
+            if items.contains(' ') {
+                format!("Array<{items}>")
+            } else {
+                format!("{items}[]")
+            }
+        },
+        Some("object") | None if schema.get("properties").is_some() => inline_object(schema),
+        Some("object") => match schema.get("additionalProperties") {
+            Some(values @ Value::Object(_)) => format!("Record<string, {}>", ts_type(values)),
+            _ => "Record<string, unknown>".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+/// `{ a: string; b?: number }`, without the comments of an interface.
+fn inline_object(schema: &Value) -> String {
+    let required = required(schema);
+    let fields: Vec<String> = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(key, property)| {
+            let optional = if required.contains(&key.as_str()) {
+                ""
+            } else {
+                "?"
+            };
+            format!("{}{optional}: {}", property_name(key), ts_type(property))
+        })
+        .collect();
+    format!("{{ {} }}", fields.join("; "))
+}
+
+/// Parenthesize a union so it can be combined.
+fn group(ty: String) -> String {
+    if ty.contains(" | ") {
+        format!("({ty})")
+    } else {
+        ty
+    }
+}
+
+/// A `JSDoc` comment, if there is anything to say.
+fn doc(out: &mut String, indent: &str, text: Option<&str>, deprecated: bool) {
+    let mut lines: Vec<String> = text
+        .into_iter()
+        .flat_map(str::lines)
+        .map(|line| line.trim_end().replace("*/", "*\\/"))
+        .collect();
+    if deprecated {
+        lines.push("@deprecated".to_string());
+    }
+    match lines.as_slice() {
+        [] => {},
+        [line] => {
+            let _ = writeln!(out, "{indent}/** {line} */");
+        },
+        lines => {
+            let _ = writeln!(out, "{indent}/**");
+            for line in lines {
+                let _ = writeln!(out, "{}", format!("{indent} * {line}").trim_end());
+            }
+            let _ = writeln!(out, "{indent} */");
+        },
+    }
+}
+
+fn description(schema: &Value) -> Option<&str> {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .filter(|d| !d.is_empty())
+}
+
+fn is_deprecated(schema: &Value) -> bool {
+    schema.get("deprecated").and_then(Value::as_bool) == Some(true)
+}
+
+/// A component name usable as a TypeScript type name.
+fn type_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        pascal_case(name)
+    }
+}
+
+/// Property keys that are not identifiers are quoted.
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        Value::from(name).to_string()
+    }
+}
+
+/// `list_users` becomes `listUsers`.
+fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::SPEC;
+    use super::*;
+
+    fn client() -> String {
+        let doc: Value = serde_json::from_str(SPEC).unwrap_or_default();
+        Api::parse(&doc).map(|api| render(&api)).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_ts_type() {
+        let ty = |json: &str| ts_type(&serde_json::from_str(json).unwrap_or_default());
+        assert_eq!(ty(r##"{"$ref":"#/components/schemas/User"}"##), "User");
+        assert_eq!(ty(r#"{"type":"integer"}"#), "number");
+        assert_eq!(ty(r#"{"type":"string","nullable":true}"#), "string | null");
+        assert_eq!(ty(r#"{"type":["string","null"]}"#), "string | null");
+        assert_eq!(ty(r#"{"enum":["a","b"]}"#), "\"a\" | \"b\"");
+        assert_eq!(
+            ty(r#"{"type":"array","items":{"type":"string"}}"#),
+            "string[]"
+        );
+        assert_eq!(
+            ty(r#"{"type":"array","items":{"enum":[1,2]}}"#),
+            "Array<1 | 2>"
+        );
+        assert_eq!(
+            ty(r#"{"type":"object","additionalProperties":{"type":"boolean"}}"#),
+            "Record<string, boolean>"
+        );
+        assert_eq!(
+            ty(
+                r#"{"type":"object","required":["a"],"properties":{"a":{"type":"string"},"b-c":{"type":"number"}}}"#
+            ),
+            "{ a: string; \"b-c\"?: number }"
+        );
+        assert_eq!(
+            ty(r#"{"oneOf":[{"type":"string"},{"type":"integer"}],"nullable":true}"#),
+            "(string | number) | null"
+        );
+        assert_eq!(ty("{}"), "unknown");
+    }
+
+    #[test]
+    fn test_path_template() {
+        assert_eq!(path_template("/users"), "`/users`");
+        assert_eq!(
+            path_template("/users/{id}/posts/{post-id}"),
+            "`/users/${encodeURIComponent(String(path.id))}/posts/${encodeURIComponent(String(path[\"post-id\"]))}`"
+        );
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("list_users"), "listUsers");
+        assert_eq!(camel_case("get_user_by_id"), "getUserById");
+        assert_eq!(camel_case("health"), "health");
+    }
+
+    #[test]
+    fn test_render_types() {
+        let client = client();
+        assert!(
+            client
+                .starts_with("// Generated by `cargo mik client typescript` from users-api 0.2.0.")
+        );
+        assert!(
+            client.contains("/** A registered user */\nexport interface User {\n  id: string;\n")
+        );
+        assert!(client.contains("  /** Display name */\n  name: string;\n"));
+        assert!(client.contains("  email?: string | null;\n"));
+        assert!(client.contains("  role: Role;\n  tags?: string[];\n  \"created-at\"?: number;\n"));
+        assert!(client.contains("  meta?: Record<string, number>;\n"));
+        assert!(client.contains("export type Role = \"admin\" | \"member\";\n"));
+    }
+
+    #[test]
+    fn test_render_methods() {
+        let client = client();
+        assert!(client.contains(
+            "  /** List users */\n  listUsers(query: { search?: string; page?: number } = {}): Promise<User[]> {\n    return this.request(\"GET\", `/users`, { query });\n"
+        ));
+        assert!(client.contains(
+            "  createUser(body: CreateUser): Promise<User> {\n    return this.request(\"POST\", `/users`, { body });\n"
+        ));
+        assert!(client.contains(
+            "  /** @deprecated */\n  deleteUser(path: { id: string }, headers: { \"x-request-id\"?: string } = {}): Promise<void> {\n"
+        ));
+        assert!(client.contains(
+            "  login(form: { user: string; remember?: boolean }): Promise<void> {\n    return this.request(\"POST\", `/login`, { form });\n"
+        ));
+    }
+}
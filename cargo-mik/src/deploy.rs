@@ -0,0 +1,341 @@
+//! `cargo mik deploy`: generate a platform manifest and deploy.
+//!
+//! Builds like `cargo mik build`, then reads the routes from the `OpenAPI`
+//! document `routes!` generates (`cargo test __mik_write_schema`) and writes
+//! the manifest next to the composed component:
+//!
+//! | Target      | Manifest    | Deploys with                                   |
+//! | ----------- | ----------- | ---------------------------------------------- |
+//! | `spin`      | `spin.toml` | `spin deploy` (Fermyon Cloud)                  |
+//! | `wasmcloud` | `wadm.yaml` | `wash push` to `--registry`, `wash app deploy` |
+//!
+//! Spin gets one HTTP trigger per route. Health, metrics and docs endpoints
+//! are not part of the `OpenAPI` document; add them with `--route`.
+
+use crate::build::{self, BuildArgs};
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// wasmCloud HTTP server provider linked to the component.
+const HTTP_SERVER_IMAGE: &str = "ghcr.io/wasmcloud/http-server:0.23.2";
+
+/// Arguments of `cargo mik deploy`.
+#[derive(Args)]
+pub struct DeployArgs {
+    /// Platform to deploy to
+    #[arg(long, value_enum)]
+    target: Target,
+
+    #[command(flatten)]
+    build: BuildArgs,
+
+    /// OCI repository to push the component to, e.g. ghcr.io/my-org
+    /// (wasmCloud; default: reference the local file)
+    #[arg(long, value_name = "REPO")]
+    registry: Option<String>,
+
+    /// Extra route to serve, e.g. /healthz (repeatable)
+    #[arg(long = "route", value_name = "PATH")]
+    routes: Vec<String>,
+
+    /// Host the component may call, e.g. `https://api.example.com` (Spin,
+    /// repeatable)
+    #[arg(long, value_name = "HOST")]
+    allow_outbound: Vec<String>,
+
+    /// Write the manifest without deploying
+    #[arg(long)]
+    no_push: bool,
+}
+
+/// A deployment platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Target {
+    /// Spin, deployed to Fermyon Cloud
+    Spin,
+    /// wasmCloud, deployed with wadm
+    Wasmcloud,
+}
+
+/// `[package]` fields used in manifests.
+#[derive(Debug, PartialEq, Eq)]
+struct Package {
+    /// Kebab-case name, valid as a Spin component id and wadm name.
+    name: String,
+    version: String,
+    description: Option<String>,
+}
+
+/// Build, write the manifest and deploy.
+pub fn run(args: &DeployArgs) -> Result<()> {
+    let manifest = fs::read_to_string("Cargo.toml")
+        .context("no Cargo.toml here, run `cargo mik deploy` in the handler crate")?;
+    let package = package(&manifest)?;
+    let component = build::compose(&args.build)?;
+
+    let mut routes = openapi_routes(&build::openapi()?)?;
+    routes.extend(args.routes.iter().cloned());
+
+    let dir = component.parent().unwrap_or_else(|| Path::new("."));
+    match args.target {
+        Target::Spin => {
+            let manifest = dir.join("spin.toml");
+            let contents = spin_manifest(&package, &component, &routes, &args.allow_outbound);
+            write(&manifest, &contents)?;
+            if !args.no_push {
+                build::exec(
+                    Command::new("spin").arg("deploy").arg("-f").arg(&manifest),
+                    "https://developer.fermyon.com/spin/install",
+                )?;
+            }
+        },
+        Target::Wasmcloud => {
+            let image = if let Some(registry) = &args.registry {
+                let registry = registry.trim_end_matches('/');
+                let image = format!("{registry}/{}:{}", package.name, package.version);
+                if !args.no_push {
+                    build::exec(
+                        Command::new("wash").args(["push", &image]).arg(&component),
+                        "https://wasmcloud.com/docs/installation",
+                    )?;
+                }
+                image
+            } else {
+                let path = component
+                    .canonicalize()
+                    .with_context(|| format!("cannot resolve {}", component.display()))?;
+                format!("file://{}", path.display())
+            };
+            let manifest = dir.join("wadm.yaml");
+            write(&manifest, &wadm_manifest(&package, &image))?;
+            if !args.no_push {
+                build::exec(
+                    Command::new("wash").args(["app", "deploy"]).arg(&manifest),
+                    "https://wasmcloud.com/docs/installation",
+                )?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Read the `[package]` fields of a manifest.
+fn package(manifest: &str) -> Result<Package> {
+    let manifest: toml::Table = manifest.parse().context("invalid Cargo.toml")?;
+    let field = |name: &str| {
+        manifest
+            .get("package")
+            .and_then(|p| p.get(name))
+            .and_then(toml::Value::as_str)
+            .map(str::to_string)
+    };
+    Ok(Package {
+        name: field("name")
+            .context("Cargo.toml has no [package] name")?
+            .replace('_', "-"),
+        version: field("version").unwrap_or_else(|| "0.1.0".to_string()),
+        description: field("description"),
+    })
+}
+
+/// Route patterns of an `OpenAPI` document, in Spin syntax.
+///
+/// A set, since each path appears once however many methods it has.
+fn openapi_routes(openapi: &str) -> Result<BTreeSet<String>> {
+    let openapi: serde_json::Value =
+        serde_json::from_str(openapi).context("invalid openapi.json")?;
+    let paths = openapi
+        .get("paths")
+        .and_then(serde_json::Value::as_object)
+        .context("openapi.json has no paths")?;
+    Ok(paths.keys().map(|path| spin_route(path)).collect())
+}
+
+/// `/users/{id}` becomes `/users/:id`.
+fn spin_route(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            segment
+                .strip_prefix('{')
+                .and_then(|s| s.strip_suffix('}'))
+                .map_or_else(|| segment.to_string(), |name| format!(":{name}"))
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A Spin manifest with one HTTP trigger per route.
+fn spin_manifest(
+    package: &Package,
+    component: &Path,
+    routes: &BTreeSet<String>,
+    allow_outbound: &[String],
+) -> String {
+    let quote = |s: &str| toml::Value::String(s.to_string()).to_string();
+    let source = component
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+
+    let mut manifest = format!(
+        "spin_manifest_version = 2\n\n[application]\nname = {}\nversion = {}\n",
+        quote(&package.name),
+        quote(&package.version)
+    );
+    if let Some(description) = &package.description {
+        let _ = writeln!(manifest, "description = {}", quote(description));
+    }
+    for route in routes {
+        let _ = write!(
+            manifest,
+            "\n[[trigger.http]]\nroute = {}\ncomponent = {}\n",
+            quote(route),
+            quote(&package.name)
+        );
+    }
+    let hosts: Vec<String> = allow_outbound.iter().map(|h| quote(h)).collect();
+    let _ = write!(
+        manifest,
+        "\n[component.{}]\nsource = {}\nallowed_outbound_hosts = [{}]\n",
+        package.name,
+        quote(&source),
+        hosts.join(", ")
+    );
+    manifest
+}
+
+/// A wadm application running the component behind the HTTP server
+/// provider on port 8080.
+fn wadm_manifest(package: &Package, image: &str) -> String {
+    // YAML single-quoted scalars only escape `'`
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let description = package
+        .description
+        .as_deref()
+        .map(|d| format!("    description: {}\n", quote(d)))
+        .unwrap_or_default();
+    let name = &package.name;
+    let version = quote(&format!("v{}", package.version));
+    let image = quote(image);
+    format!(
+        "apiVersion: core.oam.dev/v1beta1
+kind: Application
+metadata:
+  name: {name}
+  annotations:
+    version: {version}
+{description}spec:
+  components:
+    - name: {name}
+      type: component
+      properties:
+        image: {image}
+      traits:
+        - type: spreadscaler
+          properties:
+            instances: 1
+    - name: httpserver
+      type: capability
+      properties:
+        image: {HTTP_SERVER_IMAGE}
+      traits:
+        - type: link
+          properties:
+            target: {name}
+            namespace: wasi
+            package: http
+            interfaces: [incoming-handler]
+            source:
+              config:
+                - name: {name}-http
+                  properties:
+                    address: 0.0.0.0:8080
+"
+    )
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("cannot write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api() -> Package {
+        Package {
+            name: "users-api".to_string(),
+            version: "0.2.0".to_string(),
+            description: Some("Bob's users".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_package() {
+        let manifest = "[package]\nname = \"users_api\"\nversion = \"0.2.0\"\n";
+        let parsed = package(manifest).ok();
+        assert_eq!(parsed.as_ref().map(|p| p.name.as_str()), Some("users-api"));
+        assert_eq!(parsed.map(|p| p.description), Some(None));
+        assert!(package("[workspace]\n").is_err());
+    }
+
+    #[test]
+    fn test_spin_route() {
+        assert_eq!(spin_route("/"), "/");
+        assert_eq!(spin_route("/users/{id}"), "/users/:id");
+        assert_eq!(
+            spin_route("/users/{id}/posts/{slug}"),
+            "/users/:id/posts/:slug"
+        );
+    }
+
+    #[test]
+    fn test_openapi_routes() {
+        let openapi = r#"{"openapi":"3.1.0","paths":{"/users":{"get":{},"post":{}},"/users/{id}":{"get":{}}}}"#;
+        assert_eq!(
+            openapi_routes(openapi).ok(),
+            Some(BTreeSet::from([
+                "/users".to_string(),
+                "/users/:id".to_string()
+            ]))
+        );
+        assert!(openapi_routes("{}").is_err());
+        assert!(openapi_routes("not json").is_err());
+    }
+
+    #[test]
+    fn test_spin_manifest() {
+        let routes = BTreeSet::from(["/users", "/healthz"].map(String::from));
+        let manifest = spin_manifest(
+            &api(),
+            Path::new("dist/users_api.wasm"),
+            &routes,
+            &["https://api.example.com".to_string()],
+        );
+
+        assert!(manifest.starts_with("spin_manifest_version = 2\n"));
+        assert!(manifest.contains("description = \"Bob's users\"\n"));
+        assert_eq!(manifest.matches("[[trigger.http]]").count(), 2);
+        assert!(manifest.find("\"/healthz\"") < manifest.find("\"/users\""));
+        assert!(manifest.contains("[component.users-api]\nsource = \"users_api.wasm\""));
+        assert!(manifest.contains("allowed_outbound_hosts = [\"https://api.example.com\"]"));
+    }
+
+    #[test]
+    fn test_wadm_manifest() {
+        let manifest = wadm_manifest(&api(), "ghcr.io/acme/users-api:0.2.0");
+
+        assert!(manifest.contains("  name: users-api\n"));
+        assert!(manifest.contains("    version: 'v0.2.0'\n"));
+        assert!(manifest.contains("    description: 'Bob''s users'\n"));
+        assert!(manifest.contains("        image: 'ghcr.io/acme/users-api:0.2.0'\n"));
+        assert!(manifest.contains("            target: users-api\n"));
+        assert!(manifest.ends_with("address: 0.0.0.0:8080\n"));
+    }
+}
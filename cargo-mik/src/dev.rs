@@ -0,0 +1,202 @@
+//! `cargo mik dev`: rebuild and restart on every change.
+//!
+//! Runs `cargo mik build`, serves the composed component, then polls
+//! `src`, `wit`, `Cargo.toml` and `build.rs` for changes. Each change
+//! rebuilds, re-composes and restarts the runtime. A failed build keeps the
+//! previous server running so the last good version stays reachable.
+
+use crate::build::{self, BuildArgs};
+use anyhow::{Context, Result, bail};
+use clap::{Args, ValueEnum};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Files and directories whose changes trigger a rebuild.
+const WATCHED: &[&str] = &["src", "wit", "Cargo.toml", "build.rs"];
+
+/// How often the watched files are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Arguments of `cargo mik dev`.
+#[derive(Args)]
+pub struct DevArgs {
+    #[command(flatten)]
+    build: BuildArgs,
+
+    /// Runtime serving the component
+    #[arg(long, value_enum, default_value_t = Runtime::Wasmtime)]
+    runtime: Runtime,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+}
+
+/// A `wasi:http` runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Runtime {
+    /// `wasmtime serve`
+    Wasmtime,
+    /// `spin up`, with a generated manifest
+    Spin,
+}
+
+/// Serve, watch and reload until interrupted.
+pub fn run(args: &DevArgs) -> Result<()> {
+    let mut server = None;
+    loop {
+        match build::compose(&args.build) {
+            Ok(component) => {
+                stop(server.take());
+                server = Some(serve(args.runtime, &component, &args.addr)?);
+                println!("Serving on http://{}", args.addr);
+            },
+            Err(e) if server.is_some() => {
+                eprintln!("error: {e:#}");
+                eprintln!("Still serving the previous build");
+            },
+            Err(e) => eprintln!("error: {e:#}"),
+        }
+        println!("Watching for changes (Ctrl+C to stop)");
+
+        // Snapshot after the build, which may rewrite src/bindings.rs
+        let before = snapshot(Path::new("."));
+        while snapshot(Path::new(".")) == before {
+            thread::sleep(POLL_INTERVAL);
+        }
+        println!();
+        println!("Change detected, rebuilding");
+    }
+}
+
+/// Start the runtime on a composed component.
+fn serve(runtime: Runtime, component: &Path, addr: &str) -> Result<Child> {
+    let mut command = match runtime {
+        Runtime::Wasmtime => {
+            let mut command = Command::new("wasmtime");
+            command
+                // cli: environment and stderr, http: outbound requests
+                .args(["serve", "-S", "cli=y", "-S", "http=y"])
+                .args(["-S", "inherit-network=y", "--addr", addr])
+                .arg(component);
+            command
+        },
+        Runtime::Spin => {
+            let manifest = component.with_extension("spin.toml");
+            fs::write(&manifest, spin_manifest(component))
+                .with_context(|| format!("cannot write {}", manifest.display()))?;
+            let mut command = Command::new("spin");
+            command
+                .args(["up", "-f"])
+                .arg(&manifest)
+                .args(["--listen", addr]);
+            command
+        },
+    };
+
+    let program = command.get_program().to_string_lossy().into_owned();
+    match command.spawn() {
+        Ok(child) => Ok(child),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            bail!("`{program}` not found, install it or pick another --runtime")
+        },
+        Err(e) => Err(e).with_context(|| format!("cannot run `{program}`")),
+    }
+}
+
+/// A Spin manifest serving `component` on every path, with outbound HTTP
+/// allowed like `wasmtime serve -S inherit-network=y`.
+fn spin_manifest(component: &Path) -> String {
+    let source = component
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+    format!(
+        r#"spin_manifest_version = 2
+
+[application]
+name = "mik-dev"
+version = "0.1.0"
+
+[[trigger.http]]
+route = "/..."
+component = "handler"
+
+[component.handler]
+source = "{source}"
+allowed_outbound_hosts = ["*://*:*"]
+"#
+    )
+}
+
+/// Stop a running server, if any.
+fn stop(server: Option<Child>) {
+    if let Some(mut server) = server {
+        let _ = server.kill();
+        let _ = server.wait();
+    }
+}
+
+/// Modification times of every watched file under `root`, sorted by path
+/// so added and removed files count as changes too.
+fn snapshot(root: &Path) -> Vec<(PathBuf, SystemTime)> {
+    let mut files = Vec::new();
+    for path in WATCHED {
+        collect(&root.join(path), &mut files);
+    }
+    files.sort();
+    files
+}
+
+fn collect(path: &Path, files: &mut Vec<(PathBuf, SystemTime)>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            collect(&entry.path(), files);
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        files.push((path.to_path_buf(), modified));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_tracks_changes() {
+        let root = std::env::temp_dir().join(format!("cargo-mik-dev-{}", std::process::id()));
+        let src = root.join("src");
+        let _ = fs::create_dir_all(&src);
+        let _ = fs::write(src.join("lib.rs"), "");
+        let _ = fs::write(root.join("README.md"), "");
+
+        let initial = snapshot(&root);
+        let _ = fs::write(root.join("README.md"), "ignored");
+        let unwatched = snapshot(&root);
+        let _ = fs::write(src.join("routes.rs"), "");
+        let added = snapshot(&root);
+        let _ = fs::remove_file(src.join("lib.rs"));
+        let removed = snapshot(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(initial.len(), 1);
+        assert_eq!(unwatched, initial);
+        assert_eq!(added.len(), 2);
+        assert_ne!(removed, added);
+        assert_eq!(removed.len(), 1);
+    }
+
+    #[test]
+    fn test_spin_manifest() {
+        let manifest = spin_manifest(Path::new("dist/my_api.wasm"));
+        assert!(manifest.contains("source = \"my_api.wasm\""));
+        assert!(manifest.contains("route = \"/...\""));
+        assert!(manifest.starts_with("spin_manifest_version = 2"));
+    }
+}
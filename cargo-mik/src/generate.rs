@@ -0,0 +1,889 @@
+//! `cargo mik generate`: `OpenAPI` document to `routes!` code.
+//!
+//! For spec-first teams: reads an `OpenAPI` 3 document (JSON) and writes a
+//! handler crate's `src/lib.rs` with
+//!
+//! - a `#[derive(Type)]` struct or enum per component schema,
+//! - `#[derive(Path)]` / `#[derive(Query)]` structs for parameters, and
+//!   `Type` or `Form` structs for inline request bodies,
+//! - a `routes!` block carrying summaries, tags, status codes and
+//!   deprecation from the operations,
+//! - a handler stub per operation answering 501.
+//!
+//! Constraints (`minLength`, `maximum`, `pattern`, `format`, ...),
+//! descriptions and examples become `#[field(...)]` attributes, so the
+//! document `routes!` serves matches the input. Schemas with no `routes!`
+//! equivalent (`oneOf`, `anyOf`, ...) are typed as `String` and listed as
+//! TODOs at the top of the file.
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+/// Operation keys of a path item, in `routes!` order.
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head", "options"];
+
+/// Rust keywords, which get a `_` suffix as field or handler names.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Arguments of `cargo mik generate`.
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// `OpenAPI` 3 document (JSON)
+    spec: PathBuf,
+
+    /// File to write, `-` for stdout
+    #[arg(short, long, value_name = "FILE", default_value = "src/lib.rs")]
+    output: PathBuf,
+
+    /// Overwrite the output file
+    #[arg(long)]
+    force: bool,
+}
+
+/// Generate the code and write it.
+pub fn run(args: &GenerateArgs) -> Result<()> {
+    let spec = fs::read_to_string(&args.spec)
+        .with_context(|| format!("cannot read {}", args.spec.display()))?;
+    let code = generate(&spec)?;
+
+    if args.output.as_os_str() == "-" {
+        print!("{code}");
+        return Ok(());
+    }
+    if args.output.exists() && !args.force {
+        bail!(
+            "`{}` already exists, pass --force to overwrite it",
+            args.output.display()
+        );
+    }
+    if let Some(parent) = args.output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("cannot create {}", parent.display()))?;
+    }
+    fs::write(&args.output, code)
+        .with_context(|| format!("cannot write {}", args.output.display()))?;
+    println!("Wrote {}", args.output.display());
+    Ok(())
+}
+
+/// Generate `src/lib.rs` for an `OpenAPI` document.
+fn generate(spec: &str) -> Result<String> {
+    let doc: Value =
+        serde_json::from_str(spec).context("invalid OpenAPI document (JSON expected)")?;
+    if doc.get("openapi").and_then(Value::as_str).is_none() {
+        bail!("not an OpenAPI 3 document: no `openapi` version");
+    }
+
+    let mut generator = Generator::new(&doc);
+    generator.components();
+    if let Some(paths) = doc.get("paths").and_then(Value::as_object) {
+        for (path, item) in paths {
+            for method in METHODS {
+                if let Some(operation) = item.get(*method) {
+                    generator.operation(path, method, operation, item.get("parameters"));
+                }
+            }
+        }
+    }
+    for todo in &generator.todos {
+        eprintln!("warning: {todo}");
+    }
+    Ok(generator.render())
+}
+
+/// Accumulates the generated items.
+pub struct Generator<'a> {
+    doc: &'a Value,
+    /// Rust type of each component schema that becomes a struct or enum.
+    components: BTreeMap<String, String>,
+    /// Names taken by types and handlers.
+    names: BTreeSet<String>,
+    types: Vec<String>,
+    routes: Vec<String>,
+    handlers: Vec<String>,
+    todos: Vec<String>,
+    hashmap: bool,
+    /// Nesting of inlined schema references.
+    depth: usize,
+}
+
+impl<'a> Generator<'a> {
+    pub const fn new(doc: &'a Value) -> Self {
+        Self {
+            doc,
+            components: BTreeMap::new(),
+            names: BTreeSet::new(),
+            types: Vec::new(),
+            routes: Vec::new(),
+            handlers: Vec::new(),
+            todos: Vec::new(),
+            hashmap: false,
+            depth: 0,
+        }
+    }
+
+    /// A struct or enum per object or string enum component schema.
+    pub fn components(&mut self) {
+        let Some(schemas) = self
+            .doc
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+        else {
+            return;
+        };
+        // Reserve every name first, schemas reference each other
+        for (name, schema) in schemas {
+            if is_object(schema) || is_string_enum(schema) {
+                let rust = self.unique(&pascal_case(name));
+                self.components.insert(name.clone(), rust);
+            }
+        }
+        for (name, schema) in schemas {
+            if let Some(rust) = self.components.get(name).cloned() {
+                if is_object(schema) {
+                    self.object(&rust, "Type", schema);
+                } else {
+                    self.enumeration(&rust, schema);
+                }
+            }
+        }
+    }
+
+    /// A route, its input types and its handler stub.
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        operation: &'a Value,
+        shared: Option<&'a Value>,
+    ) {
+        let handler = operation
+            .get("operationId")
+            .and_then(Value::as_str)
+            .map_or_else(|| snake_case(&format!("{method} {path}")), snake_case);
+        let handler = self.unique(&ident(&handler));
+        let base = pascal_case(&handler);
+        let mut inputs = Vec::new();
+
+        let parameters = self.parameters(shared, operation.get("parameters"));
+        for (location, derive, source) in [("path", "Path", "path"), ("query", "Query", "query")] {
+            let params: Vec<&Value> = parameters
+                .iter()
+                .copied()
+                .filter(|p| p.get("in").and_then(Value::as_str) == Some(location))
+                .collect();
+            if !params.is_empty() {
+                let name = self.unique(&format!("{base}{derive}"));
+                self.params(&name, derive, &params);
+                inputs.push((source, name));
+            }
+        }
+
+        if let Some(body) = operation.get("requestBody").map(|b| self.resolve(b)) {
+            let content = body.get("content").and_then(Value::as_object);
+            let json = content.and_then(|c| {
+                c.iter()
+                    .find(|(media, _)| is_json(media))
+                    .and_then(|(_, m)| m.get("schema"))
+            });
+            let form = content
+                .and_then(|c| c.get("application/x-www-form-urlencoded"))
+                .and_then(|m| m.get("schema"));
+            if let Some(schema) = json {
+                match self.named_type(schema, &format!("{base}Body")) {
+                    Some(name) => inputs.push(("body", name)),
+                    None => self.todo(format!(
+                        "{handler}: request body is not an object, read it from `req`"
+                    )),
+                }
+            } else if let Some(schema) = form {
+                let schema = self.resolve(schema);
+                let name = self.unique(&format!("{base}Form"));
+                self.object(&name, "Form", schema);
+                inputs.push(("form", name));
+            } else if content.is_some() {
+                self.todo(format!(
+                    "{handler}: request body media type is not JSON or form, read it from `req`"
+                ));
+            }
+        }
+
+        let (status, response) = self.response(operation, &base);
+
+        let mut route = String::new();
+        let summary = operation.get("summary").and_then(Value::as_str);
+        let description = operation.get("description").and_then(Value::as_str);
+        for (i, text) in [summary, description].into_iter().flatten().enumerate() {
+            if i > 0 {
+                route.push_str("    ///\n");
+            }
+            for line in text.trim().lines() {
+                let _ = writeln!(route, "    /// {}", line.trim_end());
+            }
+        }
+        if let Some(tag) = operation.pointer("/tags/0").and_then(Value::as_str) {
+            let _ = writeln!(route, "    #[tag({tag:?})]");
+        }
+        if status != 200 {
+            let _ = writeln!(route, "    #[status({status})]");
+        }
+        if operation.get("deprecated").and_then(Value::as_bool) == Some(true) {
+            route.push_str("    #[deprecated]\n");
+        }
+        let args: Vec<String> = inputs
+            .iter()
+            .map(|(source, ty)| format!("{source}: {ty}"))
+            .collect();
+        let _ = write!(
+            route,
+            "    {} {path:?} => {handler}",
+            method.to_ascii_uppercase()
+        );
+        if !args.is_empty() {
+            let _ = write!(route, "({})", args.join(", "));
+        }
+        if let Some(response) = response {
+            let _ = write!(route, " -> {response}");
+        }
+        route.push(',');
+        self.routes.push(route);
+
+        let mut params = String::new();
+        for (source, ty) in &inputs {
+            let _ = write!(params, "_{source}: {ty}, ");
+        }
+        self.handlers.push(format!(
+            "fn {handler}({params}_req: &Request) -> Response {{\n    \
+             error! {{ status: status::NOT_IMPLEMENTED, title: \"Not Implemented\", detail: {handler:?} }}\n}}\n"
+        ));
+    }
+
+    /// Path-level parameters overridden by operation-level ones.
+    fn parameters(&self, shared: Option<&'a Value>, own: Option<&'a Value>) -> Vec<&'a Value> {
+        let mut merged: Vec<&'a Value> = Vec::new();
+        for param in [shared, own]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_array)
+            .flatten()
+            .map(|p| self.resolve(p))
+        {
+            let key = |p: &Value| (p.get("name").cloned(), p.get("in").cloned());
+            merged.retain(|p| key(p) != key(param));
+            merged.push(param);
+        }
+        merged
+    }
+
+    /// A `Path` or `Query` struct with a field per parameter.
+    fn params(&mut self, name: &str, derive: &str, params: &[&'a Value]) {
+        let mut fields = String::new();
+        for param in params {
+            let Some(key) = param.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let schema = param
+                .get("schema")
+                .map_or(&Value::Null, |s| self.resolve(s));
+            let mut ty = scalar_type(schema).unwrap_or("String").to_string();
+            let mut attrs = constraints(schema);
+            if let Some(description) = param.get("description").and_then(Value::as_str) {
+                attrs.push(format!("docs = {description:?}"));
+            }
+            let required = param.get("required").and_then(Value::as_bool) == Some(true);
+            match schema
+                .get("default")
+                .filter(|d| d.is_number() || d.is_boolean())
+            {
+                // Defaults are expressions, only literals other than strings fit
+                Some(default) if !required && derive == "Query" => {
+                    attrs.insert(0, format!("default = {default}"));
+                },
+                _ if !required && derive == "Query" => ty = format!("Option<{ty}>"),
+                _ => {},
+            }
+            field(&mut fields, key, &ty, attrs);
+        }
+        self.types.push(format!(
+            "#[derive({derive})]\npub struct {name} {{\n{fields}}}\n"
+        ));
+    }
+
+    /// Status code of the first success response and its JSON type.
+    fn response(&mut self, operation: &'a Value, base: &str) -> (u16, Option<String>) {
+        let Some((status, response)) = operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(code, response)| Some((code.parse::<u16>().ok()?, response)))
+            .filter(|(code, _)| (200..300).contains(code))
+            .min_by_key(|(code, _)| *code)
+        else {
+            return (200, None);
+        };
+        let schema = self
+            .resolve(response)
+            .get("content")
+            .and_then(Value::as_object)
+            .and_then(|c| c.iter().find(|(media, _)| is_json(media)))
+            .and_then(|(_, m)| m.get("schema"));
+        let ty = schema.and_then(|s| self.named_type(s, &format!("{base}Response")));
+        (status, ty)
+    }
+
+    /// A named Rust type for a body, defining one for an inline object.
+    ///
+    /// `routes!` inputs and responses are type names, so arrays and scalars
+    /// have none.
+    fn named_type(&mut self, schema: &'a Value, name: &str) -> Option<String> {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return self.components.get(component_name(reference)).cloned();
+        }
+        is_object(schema).then(|| self.rust_type(schema, name))
+    }
+
+    /// A struct with a field per property.
+    fn object(&mut self, name: &str, derive: &str, schema: &'a Value) {
+        let required: BTreeSet<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let mut fields = String::new();
+        for (key, property) in schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let context = format!("{name}{}", pascal_case(key));
+            let mut ty = self.rust_type(property, &context);
+            if ty == "String" && !is_supported(property) {
+                self.todo(format!(
+                    "{name}.{key}: schema not supported, typed as String"
+                ));
+            }
+            if !required.contains(key.as_str()) && !ty.starts_with("Option<") {
+                ty = format!("Option<{ty}>");
+            }
+            let property = self.resolve(property);
+            let mut attrs = constraints(property);
+            if let Some(description) = property.get("description").and_then(Value::as_str) {
+                attrs.push(format!("docs = {description:?}"));
+            }
+            if property.get("deprecated").and_then(Value::as_bool) == Some(true) {
+                attrs.push("deprecated = true".to_string());
+            }
+            if let Some(example) = property.get("example").and_then(literal) {
+                attrs.push(format!("x_example = {example}"));
+            }
+            field(&mut fields, key, &ty, attrs);
+        }
+
+        let mut item = String::new();
+        if let Some(description) = schema.get("description").and_then(Value::as_str) {
+            for line in description.trim().lines() {
+                let _ = writeln!(item, "/// {}", line.trim_end());
+            }
+        }
+        let _ = write!(
+            item,
+            "#[derive({derive})]\npub struct {name} {{\n{fields}}}\n"
+        );
+        self.types.push(item);
+    }
+
+    /// A unit enum per string value.
+    fn enumeration(&mut self, name: &str, schema: &'a Value) {
+        let mut variants = String::new();
+        let mut seen = BTreeSet::new();
+        for value in schema
+            .get("enum")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            let mut variant = pascal_case(value);
+            if variant.is_empty() || variant.starts_with(|c: char| c.is_ascii_digit()) {
+                variant.insert(0, 'V');
+            }
+            if !seen.insert(variant.clone()) {
+                continue;
+            }
+            // `Type` names variants in snake_case unless renamed
+            if snake_case(&variant) != value {
+                let _ = writeln!(variants, "    #[field(rename = {value:?})]");
+            }
+            let _ = writeln!(variants, "    {variant},");
+        }
+        self.types.push(format!(
+            "#[derive(Type)]\npub enum {name} {{\n{variants}}}\n"
+        ));
+    }
+
+    /// The Rust type of a schema, defining structs and enums for inline
+    /// objects and string enums, named after `context`.
+    pub fn rust_type(&mut self, schema: &'a Value, context: &str) -> String {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = component_name(reference);
+            if let Some(rust) = self.components.get(name) {
+                return rust.clone();
+            }
+            // Arrays and scalars are inlined where they are used, up to a
+            // depth that stops self-referencing aliases
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.doc.pointer(pointer));
+            let Some(target) = target.filter(|_| self.depth < 8) else {
+                self.todo(format!("{reference}: cannot resolve, typed as String"));
+                return "String".to_string();
+            };
+            self.depth += 1;
+            let ty = self.rust_type(target, &pascal_case(name));
+            self.depth -= 1;
+            return ty;
+        }
+
+        let nullable = schema.get("nullable").and_then(Value::as_bool) == Some(true)
+            || schema
+                .get("type")
+                .and_then(Value::as_array)
+                .is_some_and(|types| types.iter().any(|t| t == "null"));
+        let ty = if is_string_enum(schema) {
+            let name = self.unique(context);
+            self.enumeration(&name, schema);
+            name
+        } else if let Some(scalar) = scalar_type(schema) {
+            scalar.to_string()
+        } else if type_of(schema) == Some("array") {
+            let items = schema.get("items").unwrap_or(&Value::Null);
+            format!("Vec<{}>", self.rust_type(items, &format!("{context}Item")))
+        } else if is_object(schema) {
+            let name = self.unique(context);
+            self.object(&name, "Type", schema);
+            name
+        } else if let Some(values) = schema.get("additionalProperties").filter(|v| v.is_object()) {
+            self.hashmap = true;
+            format!(
+                "HashMap<String, {}>",
+                self.rust_type(values, &format!("{context}Value"))
+            )
+        } else {
+            "String".to_string()
+        };
+        if nullable {
+            format!("Option<{ty}>")
+        } else {
+            ty
+        }
+    }
+
+    /// Follow a local `$ref`, returning the schema itself otherwise.
+    fn resolve(&self, value: &'a Value) -> &'a Value {
+        let mut value = value;
+        // Bounded, a reference cycle would otherwise never end
+        for _ in 0..8 {
+            match value
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix('#'))
+                .and_then(|pointer| self.doc.pointer(pointer))
+            {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+        value
+    }
+
+    /// `name`, or `name2`, `name3`, ... if taken.
+    pub fn unique(&mut self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut n = 2;
+        while !self.names.insert(candidate.clone()) {
+            candidate = format!("{name}{n}");
+            n += 1;
+        }
+        candidate
+    }
+
+    fn todo(&mut self, message: String) {
+        self.todos.push(message);
+    }
+
+    /// The structs and enums defined so far.
+    pub fn types(&self) -> &[String] {
+        &self.types
+    }
+
+    /// Schemas that could not be mapped.
+    pub fn todos(&self) -> &[String] {
+        &self.todos
+    }
+
+    /// Whether a type uses `HashMap`.
+    pub const fn uses_hashmap(&self) -> bool {
+        self.hashmap
+    }
+
+    /// The whole file.
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let title = self
+            .doc
+            .pointer("/info/title")
+            .and_then(Value::as_str)
+            .unwrap_or("API");
+        let _ = writeln!(out, "//! {title}");
+        if let Some(description) = self
+            .doc
+            .pointer("/info/description")
+            .and_then(Value::as_str)
+        {
+            out.push_str("//!\n");
+            for line in description.trim().lines() {
+                let _ = writeln!(out, "//! {}", line.trim_end());
+            }
+        }
+        out.push_str(
+            "//!\n//! Generated by `cargo mik generate`. Handlers answer 501 until implemented.\n\n",
+        );
+        out.push_str("#[allow(warnings, unsafe_code)]\nmod bindings;\n\n");
+        out.push_str("use bindings::exports::mik::core::handler::{self, Guest, Response};\n");
+        out.push_str("use mik_sdk::prelude::*;\n");
+        if self.hashmap {
+            out.push_str("use std::collections::HashMap;\n");
+        }
+        for todo in &self.todos {
+            let _ = write!(out, "\n// TODO: {todo}");
+        }
+        if !self.todos.is_empty() {
+            out.push('\n');
+        }
+
+        section(&mut out, "TYPES");
+        out.push_str(&self.types.join("\n"));
+        section(&mut out, "ROUTES");
+        out.push_str("routes! {\n");
+        out.push_str(&self.routes.join("\n\n"));
+        out.push_str("\n}\n");
+        section(&mut out, "HANDLERS");
+        out.push_str(&self.handlers.join("\n"));
+        out
+    }
+}
+
+fn section(out: &mut String, title: &str) {
+    let rule = "=".repeat(76);
+    let _ = write!(out, "\n// {rule}\n// {title}\n// {rule}\n\n");
+}
+
+/// Append a field with its `#[field(...)]` attributes.
+fn field(fields: &mut String, key: &str, ty: &str, mut attrs: Vec<String>) {
+    let name = ident(&snake_case(key));
+    if name != key {
+        attrs.insert(0, format!("rename = {key:?}"));
+    }
+    if !attrs.is_empty() {
+        let _ = writeln!(fields, "    #[field({})]", attrs.join(", "));
+    }
+    let _ = writeln!(fields, "    pub {name}: {ty},");
+}
+
+/// `#[field]` constraints of a schema: `min`, `max`, `format` and `pattern`.
+fn constraints(schema: &Value) -> Vec<String> {
+    let mut attrs = Vec::new();
+    let bound = |keys: [&str; 3]| {
+        keys.iter()
+            .find_map(|k| schema.get(*k).filter(|v| v.is_number()))
+    };
+    if let Some(min) = bound(["minLength", "minimum", "minItems"]) {
+        attrs.push(format!("min = {min}"));
+    }
+    if let Some(max) = bound(["maxLength", "maximum", "maxItems"]) {
+        attrs.push(format!("max = {max}"));
+    }
+    if type_of(schema) == Some("string") {
+        if let Some(format) = schema.get("format").and_then(Value::as_str) {
+            attrs.push(format!("format = {format:?}"));
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+            attrs.push(format!("pattern = {pattern:?}"));
+        }
+    }
+    attrs
+}
+
+/// A string, bool or number as a Rust literal.
+fn literal(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(format!("{s:?}")),
+        Value::Bool(_) | Value::Number(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// The non-null `type` of a schema.
+fn type_of(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(ty) => Some(ty),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        _ => None,
+    }
+}
+
+/// Rust type of a string, integer, number or boolean schema.
+pub fn scalar_type(schema: &Value) -> Option<&'static str> {
+    let format = schema.get("format").and_then(Value::as_str);
+    match type_of(schema)? {
+        "string" => Some("String"),
+        "integer" if format == Some("int32") => Some("i32"),
+        "integer" => Some("i64"),
+        "number" => Some("f64"),
+        "boolean" => Some("bool"),
+        _ => None,
+    }
+}
+
+fn is_object(schema: &Value) -> bool {
+    schema.get("properties").is_some_and(Value::is_object)
+        || (type_of(schema) == Some("object") && schema.get("additionalProperties").is_none())
+}
+
+fn is_string_enum(schema: &Value) -> bool {
+    type_of(schema) == Some("string") && schema.get("enum").is_some_and(Value::is_array)
+}
+
+/// Whether a schema maps to a Rust type, rather than the `String` fallback.
+fn is_supported(schema: &Value) -> bool {
+    schema.get("$ref").is_some() || type_of(schema).is_some() || schema.get("properties").is_some()
+}
+
+fn is_json(media: &str) -> bool {
+    media == "application/json" || media.ends_with("+json")
+}
+
+/// `User` for `#/components/schemas/User`.
+fn component_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap_or(reference)
+}
+
+/// `snake_case`, splitting on separators and case changes the way
+/// `#[derive(Type)]` does for enum variants (`HTTPRequest` is
+/// `http_request`).
+pub fn snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && !out.is_empty() && !out.ends_with('_') {
+            let prev_upper = i > 0 && chars.get(i - 1).is_some_and(char::is_ascii_uppercase);
+            let next_lower = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+            if !prev_upper || next_lower {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+/// `PascalCase` of the `snake_case` words.
+pub fn pascal_case(s: &str) -> String {
+    snake_case(s)
+        .split('_')
+        .filter_map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next()?;
+            Some(first.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect()
+}
+
+/// A valid Rust identifier for a `snake_case` name.
+pub fn ident(name: &str) -> String {
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("n_{name}")
+    } else if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"{
+        "openapi": "3.1.0",
+        "info": { "title": "Users API", "version": "1.0.0" },
+        "paths": {
+            "/users": {
+                "get": {
+                    "operationId": "listUsers",
+                    "summary": "List users",
+                    "tags": ["Users"],
+                    "parameters": [
+                        { "name": "page", "in": "query", "schema": { "type": "integer", "format": "int32", "default": 1 } },
+                        { "name": "search", "in": "query", "schema": { "type": "string" } }
+                    ],
+                    "responses": { "200": { "description": "ok", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/UserList" } } } } }
+                },
+                "post": {
+                    "operationId": "createUser",
+                    "summary": "Create a user",
+                    "description": "Sends a welcome email.",
+                    "requestBody": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/NewUser" } } } },
+                    "responses": { "201": { "description": "created", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } } } }
+                }
+            },
+            "/users/{userId}": {
+                "parameters": [ { "name": "userId", "in": "path", "required": true, "schema": { "type": "integer" } } ],
+                "delete": { "deprecated": true, "responses": { "204": { "description": "gone" } } }
+            }
+        },
+        "components": {
+            "schemas": {
+                "User": {
+                    "type": "object",
+                    "required": ["id", "name", "role"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "name": { "type": "string", "minLength": 1, "maxLength": 100, "example": "Alice" },
+                        "emailAddress": { "type": "string", "format": "email", "description": "Where to write" },
+                        "role": { "$ref": "#/components/schemas/Role" },
+                        "type": { "type": "string" },
+                        "settings": { "type": "object", "properties": { "theme": { "type": "string", "enum": ["light", "dark"] } } },
+                        "extra": { "oneOf": [{ "type": "string" }, { "type": "integer" }] }
+                    }
+                },
+                "NewUser": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": { "name": { "type": "string" }, "tags": { "type": "array", "items": { "type": "string" } } }
+                },
+                "UserList": {
+                    "type": "object",
+                    "properties": { "users": { "type": "array", "items": { "$ref": "#/components/schemas/User" } }, "next": { "type": ["string", "null"] } }
+                },
+                "Role": { "type": "string", "enum": ["admin", "READ_ONLY", "superUser"] }
+            }
+        }
+    }"##;
+
+    fn generated() -> String {
+        generate(SPEC).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(snake_case("emailAddress"), "email_address");
+        assert_eq!(snake_case("HTTPRequest"), "http_request");
+        assert_eq!(snake_case("get /users/{id}"), "get_users_id");
+        assert_eq!(snake_case("READ_ONLY"), "read_only");
+        assert_eq!(pascal_case("list_users"), "ListUsers");
+        assert_eq!(pascal_case("user-profile"), "UserProfile");
+        assert_eq!(ident("type"), "type_");
+        assert_eq!(ident("2fa"), "n_2fa");
+    }
+
+    #[test]
+    fn test_component_types() {
+        let code = generated();
+        assert!(code.contains("#[derive(Type)]\npub struct User {\n"));
+        assert!(code.contains("    pub id: i64,\n"));
+        assert!(code.contains(
+            "    #[field(min = 1, max = 100, x_example = \"Alice\")]\n    pub name: String,\n"
+        ));
+        assert!(code.contains(
+            "    #[field(rename = \"emailAddress\", format = \"email\", docs = \"Where to write\")]\n    pub email_address: Option<String>,\n"
+        ));
+        assert!(code.contains("    pub role: Role,\n"));
+        assert!(code.contains("    #[field(rename = \"type\")]\n    pub type_: Option<String>,\n"));
+        assert!(code.contains("    pub users: Option<Vec<User>>,\n"));
+        assert!(code.contains("    pub next: Option<String>,\n"));
+    }
+
+    #[test]
+    fn test_enums() {
+        let code = generated();
+        assert!(code.contains(
+            "pub enum Role {\n    Admin,\n    #[field(rename = \"READ_ONLY\")]\n    ReadOnly,\n    #[field(rename = \"superUser\")]\n    SuperUser,\n}"
+        ));
+        assert!(code.contains("pub settings: Option<UserSettings>,"));
+        assert!(code.contains("pub theme: Option<UserSettingsTheme>,"));
+        assert!(code.contains("pub enum UserSettingsTheme {\n    Light,\n    Dark,\n}"));
+    }
+
+    #[test]
+    fn test_routes() {
+        let code = generated();
+        assert!(code.contains(
+            "    /// List users\n    #[tag(\"Users\")]\n    GET \"/users\" => list_users(query: ListUsersQuery) -> UserList,"
+        ));
+        assert!(code.contains(
+            "    /// Create a user\n    ///\n    /// Sends a welcome email.\n    #[status(201)]\n    POST \"/users\" => create_user(body: NewUser) -> User,"
+        ));
+        assert!(code.contains(
+            "    #[status(204)]\n    #[deprecated]\n    DELETE \"/users/{userId}\" => delete_users_user_id(path: DeleteUsersUserIdPath),"
+        ));
+    }
+
+    #[test]
+    fn test_parameters() {
+        let code = generated();
+        assert!(code.contains(
+            "#[derive(Query)]\npub struct ListUsersQuery {\n    #[field(default = 1)]\n    pub page: i32,\n    pub search: Option<String>,\n}"
+        ));
+        assert!(code.contains(
+            "#[derive(Path)]\npub struct DeleteUsersUserIdPath {\n    #[field(rename = \"userId\")]\n    pub user_id: i64,\n}"
+        ));
+    }
+
+    #[test]
+    fn test_handler_stubs() {
+        let code = generated();
+        assert!(code.contains(
+            "fn create_user(_body: NewUser, _req: &Request) -> Response {\n    error! { status: status::NOT_IMPLEMENTED, title: \"Not Implemented\", detail: \"create_user\" }\n}"
+        ));
+        assert!(code.starts_with("//! Users API\n"));
+        assert!(code.contains("use mik_sdk::prelude::*;"));
+    }
+
+    #[test]
+    fn test_unsupported_schemas_are_todos() {
+        let code = generated();
+        assert!(code.contains("// TODO: User.extra: schema not supported, typed as String"));
+        assert!(code.contains("    pub extra: Option<String>,\n"));
+    }
+
+    #[test]
+    fn test_rejects_non_openapi() {
+        assert!(generate("{}").is_err());
+        assert!(generate("openapi: 3.1.0").is_err());
+    }
+}
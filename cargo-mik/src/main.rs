@@ -0,0 +1,57 @@
+//! `cargo mik` - scaffold, build and run mik-sdk handler components.
+//!
+//! ```bash
+//! cargo install --path cargo-mik
+//! cargo mik new my-api
+//! cd my-api && cargo mik dev
+//! ```
+
+mod build;
+mod client;
+mod deploy;
+mod dev;
+mod generate;
+mod new;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "cargo-mik", bin_name = "cargo mik", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a handler crate with routes!, WIT dependencies and an example
+    New(new::NewArgs),
+    /// Build the handler and compose it with the matching mik-bridge
+    Build(build::BuildArgs),
+    /// Serve the component and rebuild it on every change
+    Dev(dev::DevArgs),
+    /// Build, generate a Spin or wasmCloud manifest from the routes and deploy
+    Deploy(deploy::DeployArgs),
+    /// Generate types, routes! and handler stubs from an `OpenAPI` document
+    Generate(generate::GenerateArgs),
+    /// Generate a typed client from the routes
+    Client(client::ClientArgs),
+}
+
+fn main() -> Result<()> {
+    // Cargo runs `cargo mik new` as `cargo-mik mik new`
+    let args = std::env::args_os()
+        .enumerate()
+        .filter(|(i, arg)| !(*i == 1 && arg == "mik"))
+        .map(|(_, arg)| arg);
+
+    match Cli::parse_from(args).command {
+        Command::New(args) => new::run(&args),
+        Command::Build(args) => build::run(&args),
+        Command::Dev(args) => dev::run(&args),
+        Command::Deploy(args) => deploy::run(&args),
+        Command::Generate(args) => generate::run(&args),
+        Command::Client(args) => client::run(&args),
+    }
+}
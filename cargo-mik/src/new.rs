@@ -0,0 +1,241 @@
+//! `cargo mik new`: scaffold a handler crate.
+//!
+//! The generated crate builds with `cargo component build` as-is: it ships
+//! the `mik:core` and WASI WIT packages under `wit/deps`, a world exporting
+//! the handler, and a `routes!` example with tests.
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// WIT packages copied into `wit/deps`, keyed by destination.
+const WIT_DEPS: &[(&str, &str)] = &[
+    (
+        "wit/deps/core/core.wit",
+        include_str!("../../mik-wit/core/core.wit"),
+    ),
+    (
+        "wit/deps/cli/cli.wit",
+        include_str!("../../mik-wit/handler/deps/wasi/cli.wit"),
+    ),
+    (
+        "wit/deps/clocks/clocks.wit",
+        include_str!("../../mik-wit/handler/deps/wasi/clocks.wit"),
+    ),
+    (
+        "wit/deps/http/http.wit",
+        include_str!("../../mik-wit/handler/deps/wasi/http.wit"),
+    ),
+    (
+        "wit/deps/io/io.wit",
+        include_str!("../../mik-wit/handler/deps/wasi/io.wit"),
+    ),
+    (
+        "wit/deps/random/random.wit",
+        include_str!("../../mik-wit/handler/deps/wasi/random.wit"),
+    ),
+];
+
+/// Project templates with `{{placeholders}}`, keyed by destination.
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/new/Cargo.toml")),
+    ("README.md", include_str!("../templates/new/README.md")),
+    (".gitignore", include_str!("../templates/new/gitignore")),
+    ("src/lib.rs", include_str!("../templates/new/lib.rs")),
+    ("wit/world.wit", include_str!("../templates/new/world.wit")),
+];
+
+/// Arguments of `cargo mik new`.
+#[derive(Args)]
+pub struct NewArgs {
+    /// Crate name (lowercase letters, digits, `-` and `_`)
+    name: String,
+
+    /// Directory to create (default: ./<name>)
+    #[arg(long)]
+    path: Option<PathBuf>,
+
+    /// Depend on a local mik-sdk checkout instead of crates.io
+    #[arg(long, value_name = "DIR")]
+    sdk_path: Option<PathBuf>,
+}
+
+/// Create the project.
+pub fn run(args: &NewArgs) -> Result<()> {
+    validate_name(&args.name)?;
+    let dir = args
+        .path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(&args.name));
+    if dir.exists() {
+        bail!("destination `{}` already exists", dir.display());
+    }
+
+    let sdk = match &args.sdk_path {
+        Some(path) => {
+            let path = path
+                .canonicalize()
+                .with_context(|| format!("mik-sdk checkout `{}` not found", path.display()))?;
+            // TOML literal string: no escaping of Windows separators
+            format!("{{ path = '{}' }}", path.join("mik-sdk").display())
+        },
+        None => format!("\"{}\"", sdk_version()),
+    };
+
+    for (file, contents) in files(&args.name, &sdk) {
+        write(&dir, file, &contents)?;
+    }
+
+    println!("Created handler `{}` in {}", args.name, dir.display());
+    println!();
+    println!("Next steps:");
+    println!("  cd {}", dir.display());
+    println!("  cargo component build --release");
+    Ok(())
+}
+
+/// Every file of a new project, relative to its root.
+fn files(name: &str, sdk: &str) -> Vec<(&'static str, String)> {
+    let wit_name = name.replace('_', "-");
+    let lib_name = name.replace('-', "_");
+    let render = |template: &str| {
+        template
+            .replace("{{name}}", name)
+            .replace("{{wit_name}}", &wit_name)
+            .replace("{{lib_name}}", &lib_name)
+            .replace("{{sdk}}", sdk)
+    };
+
+    TEMPLATES
+        .iter()
+        .map(|(file, template)| (*file, render(template)))
+        .chain(
+            WIT_DEPS
+                .iter()
+                .map(|(file, wit)| (*file, (*wit).to_string())),
+        )
+        .collect()
+}
+
+/// The mik-sdk requirement for generated crates: this CLI's minor version.
+fn sdk_version() -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let mut parts = version.split('.');
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) => format!("{major}.{minor}"),
+        _ => version.to_string(),
+    }
+}
+
+/// Crate names double as WIT package and world names, so they must be
+/// valid in both: lowercase ASCII, starting with a letter.
+fn validate_name(name: &str) -> Result<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        && !name.ends_with(['-', '_'])
+        && !name.contains("--")
+        && !name.contains("__");
+    if !valid {
+        bail!(
+            "invalid name `{name}`: use lowercase letters, digits, `-` or `_`, starting with a letter"
+        );
+    }
+    Ok(())
+}
+
+fn write(dir: &Path, file: &str, contents: &str) -> Result<()> {
+    let path = dir.join(file);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("cannot create {}", parent.display()))?;
+    }
+    fs::write(&path, contents).with_context(|| format!("cannot write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        for name in ["my-api", "users_service", "api2"] {
+            assert!(validate_name(name).is_ok(), "{name}");
+        }
+        for name in [
+            "", "My-Api", "2api", "-api", "api-", "my--api", "my api", "my.api",
+        ] {
+            assert!(validate_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_files_render_placeholders() {
+        let files = files("users_api", "\"0.1\"");
+        let get = |name: &str| {
+            files
+                .iter()
+                .find(|(file, _)| *file == name)
+                .map(|(_, contents)| contents.as_str())
+                .unwrap_or_default()
+        };
+
+        let manifest = get("Cargo.toml");
+        assert!(manifest.contains("name = \"users_api\""));
+        assert!(manifest.contains("mik-sdk = \"0.1\""));
+        assert!(manifest.contains("package = \"mik:users-api\""));
+        assert!(get("wit/world.wit").contains("world users-api {"));
+        assert!(get("README.md").contains("users_api.wasm"));
+        assert!(get("wit/deps/core/core.wit").starts_with("package mik:core@"));
+        assert!(files.iter().all(|(_, contents)| !contents.contains("{{")));
+    }
+
+    #[test]
+    fn test_manifest_lists_every_wit_dep() {
+        let manifest = files("api", "\"0.1\"")
+            .into_iter()
+            .find(|(file, _)| *file == "Cargo.toml")
+            .map(|(_, contents)| contents)
+            .unwrap_or_default();
+        for (file, _) in WIT_DEPS {
+            let dir = file.rsplit_once('/').map_or(*file, |(dir, _)| dir);
+            assert!(manifest.contains(&format!("path = \"{dir}\"")), "{dir}");
+        }
+    }
+
+    #[test]
+    fn test_sdk_version() {
+        assert_eq!(sdk_version().matches('.').count(), 1);
+    }
+
+    #[test]
+    fn test_run_writes_project() {
+        let dir = std::env::temp_dir().join(format!("cargo-mik-new-{}", std::process::id()));
+        let args = NewArgs {
+            name: "api".to_string(),
+            path: Some(dir.clone()),
+            sdk_path: None,
+        };
+        let result = run(&args);
+        let written: Vec<bool> = files("api", "")
+            .iter()
+            .map(|(file, _)| dir.join(file).is_file())
+            .collect();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(result.is_ok(), "{result:?}");
+        assert!(written.iter().all(|&exists| exists));
+    }
+
+    #[test]
+    fn test_run_refuses_existing_dir() {
+        let args = NewArgs {
+            name: "api".to_string(),
+            path: Some(std::env::temp_dir()),
+            sdk_path: None,
+        };
+        assert!(run(&args).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+#[allow(warnings, unsafe_code)]
+mod bindings;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use mik_sdk::prelude::*;
+
+#[derive(Path)]
+pub struct HelloPath {
+    pub name: String,
+}
+
+#[derive(Type)]
+pub struct HelloResponse {
+    #[field(x_example = "Hello, Alice!")]
+    pub greeting: String,
+}
+
+#[derive(Type)]
+pub struct EchoInput {
+    #[field(min = 1, x_example = "Hello, World!")]
+    pub message: String,
+}
+
+routes! {
+    /// Greet someone by name
+    GET "/hello/{name}" => hello(path: HelloPath) -> HelloResponse,
+
+    /// Echo a message back
+    POST "/echo" => echo(body: EchoInput),
+}
+
+fn hello(path: HelloPath, _req: &Request) -> Response {
+    ok!({ "greeting": format!("Hello, {}!", path.name) })
+}
+
+fn echo(body: EchoInput, _req: &Request) -> Response {
+    ok!({ "echo": body.message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hello() {
+        test_client()
+            .get("/hello/Alice")
+            .send()
+            .assert_status(200)
+            .assert_json("/greeting", "Hello, Alice!");
+    }
+
+    #[test]
+    fn test_echo_rejects_empty_message() {
+        test_client()
+            .post("/echo")
+            .json(&json!({ "message": "" }))
+            .send()
+            .assert_status(422);
+    }
+}
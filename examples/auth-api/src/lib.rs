@@ -18,6 +18,7 @@
 mod bindings;
 
 use bindings::exports::mik::core::handler::{self, Guest, Response};
+use mik_sdk::encoding::{hex_decode, hex_encode};
 use mik_sdk::prelude::*;
 
 // ============================================================================
@@ -273,31 +274,3 @@ fn validate_demo_token(token: &str) -> Option<AuthUser> {
         role: if username == "admin" { "admin" } else { "user" }.to_string(),
     })
 }
-
-// ============================================================================
-// UTILITY FUNCTIONS
-// ============================================================================
-
-/// Simple hex encoding (demo purposes).
-fn hex_encode(bytes: &[u8]) -> String {
-    use std::fmt::Write;
-    bytes
-        .iter()
-        .fold(String::with_capacity(bytes.len() * 2), |mut acc, b| {
-            let _ = write!(acc, "{b:02x}");
-            acc
-        })
-}
-
-/// Simple hex decoding (demo purposes).
-#[allow(unknown_lints, clippy::manual_is_multiple_of)]
-fn hex_decode(s: &str) -> Option<Vec<u8>> {
-    if s.len() % 2 != 0 {
-        return None;
-    }
-
-    (0..s.len())
-        .step_by(2)
-        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
-        .collect()
-}
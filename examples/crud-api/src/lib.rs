@@ -28,7 +28,6 @@
 mod bindings;
 
 use bindings::exports::mik::core::handler::{self, Guest, Response};
-use bindings::wasi::clocks::wall_clock;
 use mik_sdk::prelude::*;
 use mik_sql::{Cursor, PageInfo, parse_filter, sql_create, sql_delete, sql_read, sql_update};
 
@@ -308,7 +307,7 @@ fn create_user(body: CreateUserInput, _req: &Request) -> Response {
     // In production: execute query, get returned id and created_at
     let new_id = "3"; // Mock generated ID
     let location = format!("/users/{new_id}");
-    let created_at = get_iso_time();
+    let created_at = time::now_iso();
 
     // Return 201 Created with the new resource
     Response {
@@ -382,7 +381,7 @@ fn update_user(path: UserPath, body: UpdateUserInput, _req: &Request) -> Respons
         email
     };
 
-    let updated_at = get_iso_time();
+    let updated_at = time::now_iso();
     ok!({
         "id": id,
         "name": final_name,
@@ -496,16 +495,6 @@ fn list_posts(query: ListPostsQuery, _req: &Request) -> Response {
     }
 }
 
-// ============================================================================
-// HELPERS
-// ============================================================================
-
-/// Get ISO time from WASI clock.
-fn get_iso_time() -> String {
-    let dt = wall_clock::now();
-    time::to_iso(dt.seconds, dt.nanoseconds)
-}
-
 #[cfg(test)]
 mod tests {
     #[test]
@@ -127,3 +127,43 @@ fn search(query: SearchQuery, _req: &Request) -> Response {
         "message": message
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hello() {
+        test_client()
+            .get("/hello/Bob")
+            .send()
+            .assert_status(200)
+            .assert_json("/greeting", "Hello, Bob!");
+    }
+
+    #[test]
+    fn test_echo_validates_body() {
+        let client = test_client();
+        client
+            .post("/echo")
+            .json(&json!({ "message": "hi" }))
+            .send()
+            .assert_status(200)
+            .assert_json("/length", 2);
+        client
+            .post("/echo")
+            .json(&json!({ "message": "" }))
+            .send()
+            .assert_status(422);
+    }
+
+    #[test]
+    fn test_search_defaults() {
+        test_client()
+            .get("/search")
+            .query("q", "rust wasm")
+            .send()
+            .assert_json("/page", 1)
+            .assert_json("/message", "Searching for 'rust wasm' on page 1");
+    }
+}
@@ -0,0 +1,502 @@
+//! Response compression and request decompression.
+//!
+//! The bridge compresses buffered response bodies with brotli (`br`) or
+//! `gzip`, whichever the client prefers in `Accept-Encoding`; brotli wins a
+//! tie, as it shrinks typical JSON responses further. DEFLATE comes from
+//! `miniz_oxide` and brotli from the `brotli` crate, both pure Rust.
+//!
+//! Request bodies sent with `Content-Encoding: gzip`, `deflate` or `br` are
+//! decompressed before they reach the handler. The decoders stop as soon as
+//! the output exceeds the size limit, so small "zip bombs" cannot expand
+//! into large allocations.
+
+// Narrowing casts are intentional: gzip stores the input size modulo 2^32.
+#![allow(clippy::cast_possible_truncation)]
+
+use std::io::Read;
+
+/// DEFLATE level for gzip responses: zlib's default speed/size trade-off.
+const DEFLATE_LEVEL: u8 = 6;
+
+/// Brotli quality for responses. Levels above 5 cost far more CPU per
+/// request for a few percent smaller output.
+const BROTLI_QUALITY: i32 = 5;
+
+/// Brotli window of 4 MiB (`2^22`), the encoder's default.
+const BROTLI_WINDOW_BITS: i32 = 22;
+
+/// Content encodings the bridge can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Brotli,
+    Gzip,
+}
+
+impl Encoding {
+    /// Token used in `Content-Encoding`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Brotli => "br",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    /// Compress `data` with this encoding.
+    pub fn encode(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Brotli => brotli(data),
+            Self::Gzip => gzip(data),
+        }
+    }
+}
+
+/// Pick an encoding from an `Accept-Encoding` header value.
+///
+/// Picks the encoding with the highest q-value, preferring brotli on a tie.
+/// Honours `q=0` exclusions and the `*` wildcard. Returns `None` when the
+/// client only accepts `identity` or encodings the bridge cannot produce.
+pub fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+    let mut br_q = None;
+    let mut gzip_q = None;
+    let mut wildcard_q = None;
+    for item in accept_encoding.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim();
+        let q = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if coding.eq_ignore_ascii_case("br") {
+            br_q = Some(q);
+        } else if coding.eq_ignore_ascii_case("gzip") || coding.eq_ignore_ascii_case("x-gzip") {
+            gzip_q = Some(q);
+        } else if coding == "*" {
+            wildcard_q = Some(q);
+        }
+    }
+    let br_q = br_q.or(wildcard_q).unwrap_or(0.0);
+    let gzip_q = gzip_q.or(wildcard_q).unwrap_or(0.0);
+    if br_q > 0.0 && br_q >= gzip_q {
+        Some(Encoding::Brotli)
+    } else {
+        (gzip_q > 0.0).then_some(Encoding::Gzip)
+    }
+}
+
+/// Whether a response with this `Content-Type` is worth compressing.
+///
+/// Media that is already compressed (images, audio, video, archives, fonts)
+/// is skipped, as is anything without a content type.
+pub fn is_compressible(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    if mime.starts_with("text/") {
+        return true;
+    }
+    let Some(subtype) = mime.strip_prefix("application/") else {
+        return mime == "image/svg+xml";
+    };
+    subtype == "json"
+        || subtype.ends_with("+json")
+        || subtype == "xml"
+        || subtype.ends_with("+xml")
+        || subtype == "javascript"
+        || subtype == "x-javascript"
+        || subtype == "ecmascript"
+        || subtype == "wasm"
+        || subtype == "x-ndjson"
+        || subtype == "graphql-response+json"
+        || subtype == "x-www-form-urlencoded"
+}
+
+// ============================================================================
+// GZIP (RFC 1952)
+// ============================================================================
+
+/// Compress `data` into a gzip member.
+pub fn gzip(data: &[u8]) -> Vec<u8> {
+    // ID1 ID2 CM=deflate FLG=0 MTIME=0 XFL=0 OS=unknown
+    let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255];
+    out.extend(miniz_oxide::deflate::compress_to_vec(data, DEFLATE_LEVEL));
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    // ISIZE is the input length modulo 2^32
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
+
+/// CRC-32 (IEEE 802.3), as used by gzip.
+fn crc32(data: &[u8]) -> u32 {
+    static TABLE: std::sync::OnceLock<[u32; 256]> = std::sync::OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        let mut table = [0u32; 256];
+        for (n, entry) in (0u32..).zip(table.iter_mut()) {
+            let mut c = n;
+            for _ in 0..8 {
+                c = if c & 1 == 1 {
+                    0xedb8_8320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                };
+            }
+            *entry = c;
+        }
+        table
+    });
+    !data.iter().fold(!0u32, |crc, &byte| {
+        table[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+// ============================================================================
+// BROTLI (RFC 7932)
+// ============================================================================
+
+/// Compress `data` into a brotli stream.
+pub fn brotli(data: &[u8]) -> Vec<u8> {
+    let params = brotli::enc::BrotliEncoderParams {
+        quality: BROTLI_QUALITY,
+        lgwin: BROTLI_WINDOW_BITS,
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    brotli::BrotliCompress(&mut &data[..], &mut out, &params)
+        .expect("writing to a Vec cannot fail");
+    out
+}
+
+/// Decode a brotli stream, failing once the output exceeds `max_size`.
+fn unbrotli(data: &[u8], max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut out = Vec::new();
+    let limit = u64::try_from(max_size)
+        .unwrap_or(u64::MAX)
+        .saturating_add(1);
+    brotli::Decompressor::new(data, 4096)
+        .take(limit)
+        .read_to_end(&mut out)
+        .map_err(|_| DecodeError::Invalid)?;
+    if out.len() > max_size {
+        return Err(DecodeError::TooLarge);
+    }
+    Ok(out)
+}
+
+// ============================================================================
+// DECOMPRESSION
+// ============================================================================
+
+/// Why a request body could not be decompressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The content coding is not supported.
+    Unsupported,
+    /// The body is not valid for its content coding.
+    Invalid,
+    /// The decompressed body exceeds the size limit.
+    TooLarge,
+}
+
+/// Decompress a body sent with the given `Content-Encoding` header value.
+///
+/// Codings are undone in reverse order, as listed codings were applied in
+/// order. `identity` is a no-op. The output never grows beyond `max_size`.
+pub fn decode(
+    content_encoding: &str,
+    data: &[u8],
+    max_size: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut body = data.to_vec();
+    for coding in content_encoding.rsplit(',').map(str::trim) {
+        body = if coding.eq_ignore_ascii_case("gzip") || coding.eq_ignore_ascii_case("x-gzip") {
+            gunzip(&body, max_size)?
+        } else if coding.eq_ignore_ascii_case("deflate") {
+            zlib_inflate(&body, max_size)?
+        } else if coding.eq_ignore_ascii_case("br") {
+            unbrotli(&body, max_size)?
+        } else if coding.is_empty() || coding.eq_ignore_ascii_case("identity") {
+            body
+        } else {
+            return Err(DecodeError::Unsupported);
+        };
+    }
+    Ok(body)
+}
+
+/// Decode one or more concatenated gzip members (RFC 1952).
+fn gunzip(data: &[u8], max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    let mut out = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let header = data.get(pos..pos + 10).ok_or(DecodeError::Invalid)?;
+        if header[..3] != [0x1f, 0x8b, 8] {
+            return Err(DecodeError::Invalid);
+        }
+        let flags = header[3];
+        pos += 10;
+        if flags & FEXTRA != 0 {
+            let len = data.get(pos..pos + 2).ok_or(DecodeError::Invalid)?;
+            pos += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+        }
+        for flag in [FNAME, FCOMMENT] {
+            if flags & flag != 0 {
+                let rest = data.get(pos..).ok_or(DecodeError::Invalid)?;
+                pos += rest
+                    .iter()
+                    .position(|&b| b == 0)
+                    .ok_or(DecodeError::Invalid)?
+                    + 1;
+            }
+        }
+        if flags & FHCRC != 0 {
+            pos += 2;
+        }
+
+        // Members are inflated separately: one may not refer to another
+        let input = data.get(pos..).ok_or(DecodeError::Invalid)?;
+        let (member, used) = inflate(input, max_size - out.len())?;
+        pos += used;
+
+        let trailer = data.get(pos..pos + 8).ok_or(DecodeError::Invalid)?;
+        let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+        let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+        if crc32(&member) != crc || member.len() as u32 != size {
+            return Err(DecodeError::Invalid);
+        }
+        out.extend_from_slice(&member);
+        pos += 8;
+    }
+    Ok(out)
+}
+
+/// Decode an HTTP `deflate` body: a zlib stream (RFC 1950), or raw DEFLATE
+/// as sent by some clients.
+fn zlib_inflate(data: &[u8], max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    let is_zlib = data.len() >= 2
+        && data[0] & 0x0f == 8
+        && (u16::from(data[0]) << 8 | u16::from(data[1])) % 31 == 0;
+    if !is_zlib {
+        return inflate(data, max_size).map(|(out, _)| out);
+    }
+    // Preset dictionaries are not supported
+    if data[1] & 0x20 != 0 {
+        return Err(DecodeError::Invalid);
+    }
+    let (out, used) = inflate(&data[2..], max_size)?;
+    let trailer = data
+        .get(2 + used..2 + used + 4)
+        .ok_or(DecodeError::Invalid)?;
+    if adler32(&out) != u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]) {
+        return Err(DecodeError::Invalid);
+    }
+    Ok(out)
+}
+
+/// Adler-32 checksum, as used by zlib.
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 bytes is the most that can be summed before `b` may overflow
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    b << 16 | a
+}
+
+/// Inflate one raw DEFLATE stream, returning its output and the number of
+/// input bytes consumed.
+///
+/// Each stream gets a fresh output buffer, so a back-reference can only
+/// reach bytes of its own stream: one pointing before its start, e.g. into
+/// the previous gzip member, is rejected as invalid.
+fn inflate(data: &[u8], max_size: usize) -> Result<(Vec<u8>, usize), DecodeError> {
+    use miniz_oxide::inflate::TINFLStatus;
+    use miniz_oxide::inflate::core::{DecompressorOxide, decompress, inflate_flags};
+
+    // The whole output stays in `out`, which is what lets the decompressor
+    // check distances against the start of the stream
+    let flags = inflate_flags::TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF;
+    let mut decompressor = Box::<DecompressorOxide>::default();
+    let mut out = vec![0; data.len().saturating_mul(4).max(1024).min(max_size)];
+    let (mut read, mut written) = (0, 0);
+    loop {
+        let (status, consumed, produced) =
+            decompress(&mut decompressor, &data[read..], &mut out, written, flags);
+        read += consumed;
+        written += produced;
+        match status {
+            TINFLStatus::Done => {
+                out.truncate(written);
+                return Ok((out, read));
+            },
+            TINFLStatus::HasMoreOutput if out.len() < max_size => {
+                out.resize(out.len().saturating_mul(2).min(max_size), 0);
+            },
+            TINFLStatus::HasMoreOutput => return Err(DecodeError::TooLarge),
+            _ => return Err(DecodeError::Invalid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate("gzip, deflate, br"), Some(Encoding::Brotli));
+        assert_eq!(negotiate("br;q=1.0, gzip;q=0.8"), Some(Encoding::Brotli));
+        assert_eq!(negotiate("br;q=0.5, gzip"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("gzip, deflate"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("br;q=0, *"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("*"), Some(Encoding::Brotli));
+        assert_eq!(negotiate("br;q=0, gzip;q=0, *"), None);
+        assert_eq!(negotiate("*;q=0"), None);
+        assert_eq!(negotiate("identity"), None);
+        assert_eq!(negotiate(""), None);
+    }
+
+    #[test]
+    fn test_is_compressible() {
+        assert!(is_compressible("application/json"));
+        assert!(is_compressible("application/problem+json; charset=utf-8"));
+        assert!(is_compressible("text/html"));
+        assert!(is_compressible("image/svg+xml"));
+        assert!(!is_compressible("image/png"));
+        assert!(!is_compressible("application/zip"));
+        assert!(!is_compressible("application/octet-stream"));
+        assert!(!is_compressible(""));
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_gzip_empty() {
+        // Same bytes as `gzip -n` on empty input, apart from XFL/OS
+        assert_eq!(
+            gzip(b""),
+            [
+                0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gzip_trailer() {
+        let out = gzip(b"a");
+        let trailer = out.len() - 8;
+        assert_eq!(&out[trailer..trailer + 4], &crc32(b"a").to_le_bytes());
+        assert_eq!(&out[trailer + 4..], &1u32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_decode_roundtrip() {
+        let json = r#"{"id":1,"name":"item","tags":["a","b"]},"#.repeat(200);
+        let compressed = gzip(json.as_bytes());
+        assert_eq!(
+            decode("gzip", &compressed, 1 << 20),
+            Ok(json.clone().into_bytes())
+        );
+        let compressed = brotli(json.as_bytes());
+        assert_eq!(decode("br", &compressed, 1 << 20), Ok(json.into_bytes()));
+        assert_eq!(decode("identity", b"raw", 10), Ok(b"raw".to_vec()));
+    }
+
+    #[test]
+    fn test_decode_zlib_and_raw_deflate() {
+        // zlib.compress(b"hello hello hello")
+        let zlib = [
+            0x78, 0x9c, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0xc8, 0x40, 0x90, 0x00, 0x3a, 0x2e,
+            0x06, 0x7d,
+        ];
+        assert_eq!(
+            decode("deflate", &zlib, 100),
+            Ok(b"hello hello hello".to_vec())
+        );
+        assert_eq!(
+            decode("deflate", &zlib[2..zlib.len() - 4], 100),
+            Ok(b"hello hello hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_decode_stored_block() {
+        // Single final stored block holding "abc"
+        let raw = [0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'];
+        assert_eq!(decode("deflate", &raw, 10), Ok(b"abc".to_vec()));
+        assert_eq!(decode("deflate", &raw, 2), Err(DecodeError::TooLarge));
+    }
+
+    #[test]
+    fn test_gunzip_members_are_independent() {
+        let mut two = gzip(b"first ");
+        two.extend(gzip(b"second"));
+        assert_eq!(decode("gzip", &two, 100), Ok(b"first second".to_vec()));
+
+        // Fixed-Huffman block that starts with a match (length 3, distance 1),
+        // i.e. copies from before its own start
+        let mut member = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255, 0x03, 0x02, 0x00];
+        member.extend_from_slice(&crc32(b"aaa").to_le_bytes());
+        member.extend_from_slice(&3u32.to_le_bytes());
+        let mut crossing = gzip(b"a");
+        crossing.extend_from_slice(&member);
+        assert_eq!(decode("gzip", &crossing, 100), Err(DecodeError::Invalid));
+        assert_eq!(decode("gzip", &member, 100), Err(DecodeError::Invalid));
+    }
+
+    #[test]
+    fn test_decode_limits_output() {
+        let bomb = gzip(&vec![0u8; 1 << 20]);
+        assert!(bomb.len() < 16 * 1024);
+        assert_eq!(decode("gzip", &bomb, 1000), Err(DecodeError::TooLarge));
+        let bomb = brotli(&vec![0u8; 1 << 20]);
+        assert!(bomb.len() < 1024);
+        assert_eq!(decode("br", &bomb, 1000), Err(DecodeError::TooLarge));
+        assert_eq!(decode("br", &bomb, 1 << 20).map(|b| b.len()), Ok(1 << 20));
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_input() {
+        let mut compressed = gzip(b"payload payload payload");
+        assert_eq!(
+            decode("zstd", &compressed, 100),
+            Err(DecodeError::Unsupported)
+        );
+        assert_eq!(decode("br", &compressed, 100), Err(DecodeError::Invalid));
+        assert_eq!(decode("gzip", b"plain", 100), Err(DecodeError::Invalid));
+        let crc = compressed.len() - 8;
+        compressed[crc] ^= 1;
+        assert_eq!(decode("gzip", &compressed, 100), Err(DecodeError::Invalid));
+        compressed.truncate(12);
+        assert_eq!(decode("gzip", &compressed, 100), Err(DecodeError::Invalid));
+    }
+
+    #[test]
+    fn test_encodings_shrink_repetitive_json() {
+        let json = r#"{"id":1,"name":"item","tags":["a","b"]},"#.repeat(200);
+        for encoding in [Encoding::Brotli, Encoding::Gzip] {
+            let out = encoding.encode(json.as_bytes());
+            assert!(
+                out.len() < json.len() / 10,
+                "{encoding:?}: {} bytes",
+                out.len()
+            );
+        }
+    }
+}
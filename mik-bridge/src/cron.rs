@@ -0,0 +1,25 @@
+//! Spin cron trigger adapter.
+//!
+//! Exports `handle-cron-event` from the `spin-cron-bridge` world and runs the
+//! handler's jobs that are due at the event's timestamp.
+
+use crate::bindings::fermyon::spin_cron::cron_types::{CronError, Metadata};
+use crate::bindings::mik::core::schedule;
+use crate::{Bridge, log_error};
+
+impl crate::bindings::Guest for Bridge {
+    fn handle_cron_event(metadata: Metadata) -> Result<(), CronError> {
+        let failed = schedule::run_due(metadata.timestamp);
+        if failed.is_empty() {
+            return Ok(());
+        }
+
+        let summary = failed
+            .iter()
+            .map(|(name, error)| format!("{name}: {error}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+        log_error(&format!("Scheduled jobs failed: {summary}"));
+        Err(CronError::Other(summary))
+    }
+}
@@ -12,6 +12,71 @@
 //!
 //! - `MIK_MAX_BODY_SIZE`: Maximum request body size in bytes (default: 10MB)
 //!   Example: `MIK_MAX_BODY_SIZE=52428800` for 50MB
+//! - `MIK_COMPRESSION`: Set to `off` (or `false`, `0`) to disable response
+//!   compression (default: on)
+//! - `MIK_COMPRESSION_MIN_SIZE`: Smallest response body, in bytes, that is
+//!   compressed (default: 1024)
+//! - `MIK_MAX_HEADERS`: Maximum number of request headers (default: 100)
+//! - `MIK_MAX_HEADER_SIZE`: Maximum combined size of request header names and
+//!   values in bytes (default: 64KB)
+//! - `MIK_REQUEST_TIMEOUT`: Request deadline in milliseconds, from the moment
+//!   the request arrives (default: none)
+//!   Example: `MIK_REQUEST_TIMEOUT=30000` for 30 seconds
+//!
+//! ## Body Modes
+//!
+//! Before reading the body, the bridge asks the handler how it wants it
+//! delivered (`select-body-mode`). Buffered requests are read in full and
+//! passed to `handle`. Streaming requests skip buffering: the raw
+//! `wasi:io` input stream is handed to `handle-streaming`, and the handler
+//! reads it chunk by chunk.
+//!
+//! Responses work the same way in reverse: after `handle` returns, the bridge
+//! calls `take-response-stream`. If the handler registered a streaming body,
+//! the response head is sent immediately and each chunk is flushed to the
+//! client as it is produced.
+//!
+//! ## Compression
+//!
+//! Buffered request bodies sent with `Content-Encoding: gzip`, `deflate` or
+//! `br` are decompressed before the handler sees them; the decompressed size
+//! is held to `MIK_MAX_BODY_SIZE`. Streamed request bodies are passed through
+//! unchanged.
+//!
+//! Buffered responses are compressed with brotli or gzip, whichever the
+//! client's `Accept-Encoding` prefers (brotli on a tie), when the body is at
+//! least `MIK_COMPRESSION_MIN_SIZE` bytes and the content type is text-like
+//! (JSON, HTML, XML, ...). Bodies that already carry a `Content-Encoding`,
+//! streamed bodies, and bodies that would not get smaller are sent as-is.
+//!
+//! ## WebSockets
+//!
+//! Built with the `websocket` feature (and the `http-ws-bridge` world), the
+//! bridge answers `GET` upgrade requests whose path the handler `accepts`
+//! with `101 Switching Protocols` and runs the session itself: client frames
+//! are read from the request body, reassembled, and passed to `on-message`;
+//! the actions the handler returns are written to the response body as
+//! frames. Messages are held to `MIK_MAX_BODY_SIZE`. This needs a runtime
+//! that keeps the connection open after the 101 and exposes the upgraded
+//! stream as the request and response bodies; plain `wasi:http` 0.2
+//! runtimes end the session right away.
+//!
+//! ## Scheduled Jobs
+//!
+//! Built with the `spin-cron` feature (and the `spin-cron-bridge` world), the
+//! bridge also handles Spin cron trigger events: each event calls the
+//! handler's `run-due` with the event's timestamp, so every job declared with
+//! `jobs!` whose schedule matches that second runs. Failed jobs are logged and
+//! reported to Spin as a `cron-error`. Hosts without a cron trigger can call
+//! the handler's `mik:core/schedule` export directly.
+//!
+//! ## Messaging
+//!
+//! Built with the `messaging` feature (and the `messaging-bridge` world), the
+//! bridge exports the `wasi:messaging` incoming handler: each delivered
+//! message is passed to the handler's `consumers!` with its topic, body and
+//! metadata (the content type included). A consumer error is logged and
+//! returned to the host, which decides whether to redeliver the message.
 //!
 //! ## Security Considerations
 //!
@@ -19,34 +84,84 @@
 //!
 //! **This component does not implement rate limiting.** Rate limiting should be
 //! handled at the infrastructure layer by your WASI HTTP runtime or a reverse proxy.
+//! Deployments without one can use the token-bucket hooks in
+//! `mik_sdk::rate_limit` on the handler side.
 //!
 //! For production deployments, always use a reverse proxy or API gateway that
 //! provides:
 //! - Rate limiting (requests per second/minute)
 //! - Connection limits
-//! - Request timeout enforcement
 //! - DDoS protection
 //!
+//! ### Request Timeouts
+//!
+//! With `MIK_REQUEST_TIMEOUT` set, buffered body reads wait for data only
+//! until the deadline, so a client trickling its body (slow-loris) gets a
+//! 504 instead of holding the instance. The handler call itself is
+//! synchronous and cannot be interrupted from the bridge: a handler that
+//! returns past the deadline has its response replaced by a 504, and a
+//! streamed response stops pulling chunks once the deadline passes. Hard
+//! CPU limits for runaway handlers (epoch interruption, fuel) belong to the
+//! runtime.
+//!
 //! ### Request Size Limits
 //!
 //! The bridge enforces `MIK_MAX_BODY_SIZE` to prevent memory exhaustion from
 //! large request bodies. Requests exceeding this limit receive a 413 response.
+//! For streamed bodies the bridge can only check `Content-Length` up front;
+//! the handler enforces the limit while reading. Compressed bodies are held
+//! to the same limit after decompression, and decompression stops as soon as
+//! it is exceeded.
+//!
+//! Request headers are held to `MIK_MAX_HEADERS` and `MIK_MAX_HEADER_SIZE`.
+//! Requests over either limit receive a 431 response before any header is
+//! converted or the body is read.
 
 #[allow(warnings)]
 mod bindings;
+mod compress;
+#[cfg(feature = "spin-cron")]
+mod cron;
+#[cfg(feature = "messaging")]
+mod messaging;
+#[cfg(any(feature = "websocket", test))]
+mod websocket;
 
 use bindings::exports::wasi::http::incoming_handler::Guest;
-use bindings::mik::core::handler::{self, Method, RequestData};
+use bindings::mik::core::handler::{
+    self, BodyMode, Method, RequestData, Response, ResponseStream,
+};
+#[cfg(feature = "websocket")]
+use bindings::mik::core::websocket::{self as ws, Action, Message};
 use bindings::wasi::cli::environment;
 use bindings::wasi::cli::stderr;
+use bindings::wasi::clocks::monotonic_clock::{self, Instant};
 use bindings::wasi::http::types::{
     Fields, IncomingRequest, OutgoingBody, OutgoingResponse, ResponseOutparam,
 };
+use bindings::wasi::io::poll;
+use bindings::wasi::io::streams::{InputStream, OutputStream};
+use compress::{DecodeError, Encoding};
 use std::sync::OnceLock;
 
 /// Default maximum request body size (10MB).
 const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
 
+/// Default maximum number of request headers.
+const DEFAULT_MAX_HEADERS: usize = 100;
+
+/// Default maximum combined size of request header names and values (64KB).
+const DEFAULT_MAX_HEADER_SIZE: usize = 64 * 1024;
+
+/// Bytes requested per body read.
+const READ_CHUNK: u64 = 64 * 1024;
+
+/// Maximum bytes accepted by a single `blocking-write-and-flush` call.
+const MAX_WRITE_CHUNK: usize = 4096;
+
+/// Default smallest response body that is compressed (1KB).
+const DEFAULT_COMPRESSION_MIN_SIZE: usize = 1024;
+
 // ============================================================================
 // HTTP CONSTANTS (centralized for consistency)
 // ============================================================================
@@ -67,12 +182,15 @@ const fn status_title(code: u16) -> &'static str {
         404 => "Not Found",
         405 => "Method Not Allowed",
         413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
         422 => "Unprocessable Entity",
         429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
         500 => "Internal Server Error",
         501 => "Not Implemented",
         502 => "Bad Gateway",
         503 => "Service Unavailable",
+        504 => "Gateway Timeout",
         _ => "Error",
     }
 }
@@ -107,6 +225,118 @@ fn get_max_body_size() -> usize {
     })
 }
 
+/// Request header limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HeaderLimits {
+    /// Maximum number of header fields.
+    max_count: usize,
+    /// Maximum combined size of header names and values in bytes.
+    max_size: usize,
+}
+
+impl HeaderLimits {
+    /// Returns `true` if the headers fit within both limits.
+    ///
+    /// Stops summing as soon as a limit is crossed.
+    fn allows(&self, entries: &[(String, Vec<u8>)]) -> bool {
+        if entries.len() > self.max_count {
+            return false;
+        }
+        let mut size: usize = 0;
+        for (name, value) in entries {
+            size = size.saturating_add(name.len()).saturating_add(value.len());
+            if size > self.max_size {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Cached header limits from environment.
+static HEADER_LIMITS: OnceLock<HeaderLimits> = OnceLock::new();
+
+/// Returns the request header limits.
+///
+/// Reads `MIK_MAX_HEADERS` and `MIK_MAX_HEADER_SIZE` on first call and
+/// caches the result, like [`get_max_body_size`]. Invalid values fall back
+/// to [`DEFAULT_MAX_HEADERS`] and [`DEFAULT_MAX_HEADER_SIZE`].
+fn get_header_limits() -> HeaderLimits {
+    *HEADER_LIMITS.get_or_init(|| {
+        let env = environment::get_environment();
+        let var = |name: &str| {
+            env.iter()
+                .find(|(k, _)| k == name)
+                .and_then(|(_, v)| v.trim().parse().ok())
+        };
+        HeaderLimits {
+            max_count: var("MIK_MAX_HEADERS").unwrap_or(DEFAULT_MAX_HEADERS),
+            max_size: var("MIK_MAX_HEADER_SIZE").unwrap_or(DEFAULT_MAX_HEADER_SIZE),
+        }
+    })
+}
+
+/// Cached compression threshold from environment (`None` = disabled).
+static COMPRESSION_MIN_SIZE: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Returns the smallest response body size to compress, or `None` if
+/// compression is disabled.
+///
+/// Reads `MIK_COMPRESSION` and `MIK_COMPRESSION_MIN_SIZE` on first call and
+/// caches the result, like [`get_max_body_size`].
+fn get_compression_min_size() -> Option<usize> {
+    *COMPRESSION_MIN_SIZE.get_or_init(|| {
+        let env = environment::get_environment();
+        let var = |name: &str| {
+            env.iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.trim().to_ascii_lowercase())
+        };
+        if var("MIK_COMPRESSION").is_some_and(|v| matches!(v.as_str(), "off" | "false" | "0")) {
+            return None;
+        }
+        Some(
+            var("MIK_COMPRESSION_MIN_SIZE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_COMPRESSION_MIN_SIZE),
+        )
+    })
+}
+
+/// Cached request timeout in milliseconds from environment (`None` = no deadline).
+static REQUEST_TIMEOUT: OnceLock<Option<u64>> = OnceLock::new();
+
+/// Returns the request timeout in milliseconds, or `None` if requests have
+/// no deadline.
+///
+/// Reads `MIK_REQUEST_TIMEOUT` on first call and caches the result, like
+/// [`get_max_body_size`].
+fn get_request_timeout() -> Option<u64> {
+    *REQUEST_TIMEOUT.get_or_init(|| {
+        environment::get_environment()
+            .into_iter()
+            .find(|(k, _)| k == "MIK_REQUEST_TIMEOUT")
+            .and_then(|(_, v)| parse_timeout(&v))
+    })
+}
+
+/// Parse a `MIK_REQUEST_TIMEOUT` value in milliseconds.
+///
+/// Zero, negative and non-numeric values disable the deadline.
+fn parse_timeout(value: &str) -> Option<u64> {
+    value.trim().parse::<u64>().ok().filter(|&ms| ms > 0)
+}
+
+/// Returns the monotonic instant `timeout_ms` milliseconds after `now`.
+const fn deadline_after(now: Instant, timeout_ms: u64) -> Instant {
+    now.saturating_add(timeout_ms.saturating_mul(1_000_000))
+}
+
+/// Returns `true` once the request deadline has passed.
+fn deadline_passed(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| monotonic_clock::now() >= deadline)
+}
+
 /// Bridge component implementing WASI HTTP to mik handler translation.
 ///
 /// This is the core component that enables portable HTTP handlers:
@@ -127,17 +357,37 @@ struct Bridge;
 /// ## Request Flow
 ///
 /// 1. Extract path, method, headers from WASI HTTP request
-/// 2. Read body with size limit enforcement (413 if exceeded)
-/// 3. Convert to `mik:core/handler::RequestData`
-/// 4. Call user's `handler::handle()` function
-/// 5. Convert response and send via WASI HTTP
+/// 2. Ask the handler for a body mode (`handler::select_body_mode()`)
+/// 3. Streaming: pass the body stream to `handler::handle_streaming()`
+/// 4. Buffered: read body with size limit enforcement (413 if exceeded)
+///    until the request deadline (504 if reached), decompress it if it has
+///    a `Content-Encoding`, convert to `mik:core/handler::RequestData` and
+///    call `handler::handle()`
+/// 5. Convert response and send via WASI HTTP, streaming the body if the
+///    handler returned a `response-stream`, or compressing it if the client
+///    accepts brotli or gzip
+/// 6. Call `handler::run_tasks()` so work the handler deferred with
+///    `mik_sdk::tasks` runs after the client has its response
+///
+/// With the `websocket` feature, accepted upgrade requests skip steps 2-6
+/// and run a WebSocket session instead (see the module docs).
 ///
 /// ## Error Handling
 ///
 /// - Unsupported HTTP methods (CONNECT, TRACE) → 501 Not Implemented
-/// - Body exceeds `MIK_MAX_BODY_SIZE` → 413 Payload Too Large
+/// - Headers exceed `MIK_MAX_HEADERS` or `MIK_MAX_HEADER_SIZE` → 431 Request
+///   Header Fields Too Large
+/// - Body exceeds `MIK_MAX_BODY_SIZE` → 413 Payload Too Large, also when
+///   decompressed
+/// - Unknown request `Content-Encoding` → 415 Unsupported Media Type
+/// - Corrupt compressed body → 400 Bad Request
+/// - Body read or handler exceeds `MIK_REQUEST_TIMEOUT` → 504 Gateway Timeout
 /// - Invalid status codes are clamped to 500 with error logging
 /// - Invalid UTF-8 headers are silently dropped with error logging
+/// - Handler panics are logged on the handler side (`mik_sdk::recover`)
+///   with the path and trace ID. Components build with `panic = "abort"`,
+///   so the panic then traps and tears down the whole composed component,
+///   bridge included; the runtime answers with its own 500.
 impl Guest for Bridge {
     fn handle(request: IncomingRequest, response_out: ResponseOutparam) {
         // 1. Extract data from WASI HTTP request
@@ -152,91 +402,486 @@ impl Guest for Bridge {
                 return;
             }
         };
-        let headers = extract_headers(&request);
+        let deadline = get_request_timeout()
+            .map(|timeout_ms| deadline_after(monotonic_clock::now(), timeout_ms));
+        let Some(headers) = extract_headers(&request, get_header_limits()) else {
+            send_error_response(response_out, 431, status_title(431), &path);
+            return;
+        };
+        let encoding = accepted_encoding(&headers);
+
+        // WebSocket upgrades accepted by the handler become a session
+        #[cfg(feature = "websocket")]
+        if method == Method::Get
+            && let Some(key) = websocket::upgrade_key(&headers)
+            && ws::accepts(&path)
+        {
+            let accept = websocket::accept_key(key);
+            let mik_request = RequestData {
+                method,
+                path,
+                headers,
+                body: None,
+            };
+            serve_websocket(request, response_out, &mik_request, &accept);
+            return;
+        }
+
+        // 2. Let the handler choose how the body is delivered
+        if handler::select_body_mode(method, &path) == BodyMode::Streaming {
+            // Reject declared oversize bodies before handing over the stream
+            if content_length(&request).is_some_and(|len| len > get_max_body_size()) {
+                send_error_response(response_out, 413, status_title(413), &path);
+                return;
+            }
+
+            // 3. Stream the body to the handler. The incoming body must outlive
+            // its stream, so it is kept alive until the handler returns.
+            let mik_request = RequestData {
+                method,
+                path,
+                headers,
+                body: None,
+            };
+            let mik_response = match request.consume() {
+                Ok(incoming_body) => match incoming_body.stream() {
+                    Ok(stream) => {
+                        let response = handler::handle_streaming(&mik_request, stream);
+                        drop(incoming_body);
+                        response
+                    }
+                    Err(()) => handler::handle(&mik_request),
+                },
+                Err(()) => handler::handle(&mik_request),
+            };
+            let body_stream = handler::take_response_stream();
+            if deadline_passed(deadline) {
+                send_timeout_response(response_out, &mik_request.path);
+                handler::run_tasks();
+                return;
+            }
+            send_response(response_out, mik_response, body_stream, encoding, deadline);
+            handler::run_tasks();
+            return;
+        }
 
-        // 2. Read body with size limit check
-        let body = match read_body(&request) {
+        // 4. Read body with size limit and deadline checks
+        let body = match read_body(&request, deadline) {
             BodyResult::Ok(body) => body,
             BodyResult::TooLarge => {
                 // Return 413 Payload Too Large
                 send_error_response(response_out, 413, status_title(413), &path);
                 return;
             }
+            BodyResult::TimedOut => {
+                send_timeout_response(response_out, &path);
+                return;
+            }
+        };
+
+        // 5. Undo any Content-Encoding so the handler sees the plain body
+        let (headers, body) = match decode_body(headers, body, get_max_body_size()) {
+            Ok(decoded) => decoded,
+            Err(status) => {
+                send_error_response(response_out, status, status_title(status), &path);
+                return;
+            }
         };
 
-        // 3. Build mik request-data
         let mik_request = RequestData {
             method,
             path,
             headers,
             body,
         };
-
-        // 4. Call the user's handler
         let mik_response = handler::handle(&mik_request);
+        let body_stream = handler::take_response_stream();
+
+        // 6. A response that arrives past the deadline is discarded
+        if deadline_passed(deadline) {
+            send_timeout_response(response_out, &mik_request.path);
+            handler::run_tasks();
+            return;
+        }
+
+        // 7. Convert to WASI HTTP response and send
+        send_response(response_out, mik_response, body_stream, encoding, deadline);
+
+        // 8. Run deferred work now that the client has its response
+        handler::run_tasks();
+    }
+}
+
+/// Run a WebSocket session for an accepted upgrade request.
+///
+/// Sends `101 Switching Protocols`, calls `on-open`, then decodes client
+/// frames from the request body and calls `on-message` for each complete
+/// message until either side closes. Actions returned by the handler are
+/// written to the response body as they come back. `on-close` is always
+/// called last, with 1006 when the connection dropped without a close frame.
+#[cfg(feature = "websocket")]
+fn serve_websocket(
+    request: IncomingRequest,
+    response_out: ResponseOutparam,
+    mik_request: &RequestData,
+    accept: &str,
+) {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use websocket::{Assembler, Event, FrameError};
+
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+    let Ok(incoming_body) = request.consume() else {
+        send_error_response(response_out, 400, status_title(400), &mik_request.path);
+        return;
+    };
+    let Ok(input) = incoming_body.stream() else {
+        send_error_response(response_out, 400, status_title(400), &mik_request.path);
+        return;
+    };
 
-        // 5. Convert to WASI HTTP response and send
-        // Note: Fields resource is consumed by OutgoingResponse::new().
-        // We scope it explicitly to ensure proper WASI resource lifecycle.
-        let outgoing = {
-            let headers = Fields::new();
-            for (name, value) in mik_response.headers {
-                let _ = headers.append(&name, &value.into_bytes());
+    let outgoing = {
+        let headers = Fields::new();
+        // Runtimes without upgrade support may refuse these headers
+        for (name, value) in [
+            ("upgrade", "websocket"),
+            ("connection", "Upgrade"),
+            ("sec-websocket-accept", accept),
+        ] {
+            let _ = headers.append(&name.to_string(), &value.as_bytes().to_vec());
+        }
+        OutgoingResponse::new(headers)
+    };
+    let _ = outgoing.set_status_code(101);
+    let Ok(body_handle) = outgoing.body() else {
+        log_error("Failed to get WebSocket body handle");
+        ResponseOutparam::set(response_out, Ok(outgoing));
+        return;
+    };
+    ResponseOutparam::set(response_out, Ok(outgoing));
+    let Ok(output) = body_handle.write() else {
+        log_error("Failed to get WebSocket write stream");
+        ws::on_close(id, websocket::CLOSE_ABNORMAL, "");
+        let _ = OutgoingBody::finish(body_handle, None);
+        return;
+    };
+
+    let max_message = get_max_body_size();
+    let mut assembler = Assembler::new(max_message);
+    let mut buffer: Vec<u8> = Vec::new();
+    let fail = |output: &OutputStream, error: FrameError| {
+        let code = error.close_code();
+        let _ = write_all(output, &websocket::encode_close(code, ""));
+        (code, String::new())
+    };
+
+    let (code, reason) = 'session: {
+        if let Some(closed) = run_actions(&output, ws::on_open(id, mik_request)) {
+            break 'session closed;
+        }
+        loop {
+            // Handle every complete frame before reading more
+            loop {
+                let frame = match websocket::decode(&buffer, max_message) {
+                    Ok(Some((frame, used))) => {
+                        buffer.drain(..used);
+                        frame
+                    }
+                    Ok(None) => break,
+                    Err(error) => break 'session fail(&output, error),
+                };
+                let message = match assembler.push(frame) {
+                    Ok(Some(Event::Text(text))) => Message::Text(text),
+                    Ok(Some(Event::Binary(bytes))) => Message::Binary(bytes),
+                    Ok(Some(Event::Ping(payload))) => {
+                        let _ =
+                            write_all(&output, &websocket::encode(websocket::OP_PONG, &payload));
+                        continue;
+                    }
+                    Ok(Some(Event::Close(code, reason))) => {
+                        // Echo the close; 1005 means no code was given
+                        let echo = if code == 1005 {
+                            websocket::CLOSE_NORMAL
+                        } else {
+                            code
+                        };
+                        let _ = write_all(&output, &websocket::encode_close(echo, ""));
+                        break 'session (code, reason);
+                    }
+                    Ok(None) => continue,
+                    Err(error) => break 'session fail(&output, error),
+                };
+                if let Some(closed) = run_actions(&output, ws::on_message(id, &message)) {
+                    break 'session closed;
+                }
             }
-            // Ownership of headers transfers to OutgoingResponse here
-            OutgoingResponse::new(headers)
+            match input.blocking_read(READ_CHUNK) {
+                Ok(chunk) => buffer.extend_from_slice(&chunk),
+                Err(_) => break 'session (websocket::CLOSE_ABNORMAL, String::new()),
+            }
+        }
+    };
+
+    ws::on_close(id, code, &reason);
+    drop(output);
+    if let Err(e) = OutgoingBody::finish(body_handle, None) {
+        log_error(&format!("Failed to finish WebSocket body: {:?}", e));
+    }
+    drop(input);
+    drop(incoming_body);
+}
+
+/// Write the frames for the handler's actions.
+///
+/// Returns the close code and reason once the handler closed the
+/// connection or the client went away.
+#[cfg(feature = "websocket")]
+fn run_actions(output: &OutputStream, actions: Vec<Action>) -> Option<(u16, String)> {
+    for action in actions {
+        let frame = match &action {
+            Action::Send(Message::Text(text)) => {
+                websocket::encode(websocket::OP_TEXT, text.as_bytes())
+            }
+            Action::Send(Message::Binary(bytes)) => websocket::encode(websocket::OP_BINARY, bytes),
+            Action::Close((code, reason)) => websocket::encode_close(*code, reason),
         };
-        // Validate and clamp status code to valid HTTP range (100-599)
-        let status_code = if mik_response.status < 100 {
-            log_error(&format!(
-                "Invalid HTTP status code {}: must be >= 100, using 500",
-                mik_response.status
-            ));
-            500
-        } else if mik_response.status >= 600 {
-            log_error(&format!(
-                "Invalid HTTP status code {}: must be < 600, using 500",
-                mik_response.status
-            ));
-            500
-        } else {
+        if write_all(output, &frame).is_err() {
+            return Some((websocket::CLOSE_ABNORMAL, String::new()));
+        }
+        if let Action::Close((code, reason)) = action {
+            return Some((code, reason));
+        }
+    }
+    None
+}
+
+/// Log a request that ran past `MIK_REQUEST_TIMEOUT` and answer 504.
+fn send_timeout_response(response_out: ResponseOutparam, path: &str) {
+    log_error(&format!("Request exceeded MIK_REQUEST_TIMEOUT: {}", path));
+    send_error_response(response_out, 504, status_title(504), path);
+}
+
+/// Convert a mik response to WASI HTTP and send it.
+///
+/// When the handler registered a response stream, the response head is sent
+/// first and each chunk is written and flushed as the handler produces it,
+/// until `deadline` passes. Buffered bodies are compressed with `encoding`
+/// when worthwhile.
+fn send_response(
+    response_out: ResponseOutparam,
+    mik_response: Response,
+    body_stream: Option<ResponseStream>,
+    encoding: Option<Encoding>,
+    deadline: Option<Instant>,
+) {
+    let mik_response = if body_stream.is_none() {
+        compress_response(mik_response, encoding, get_compression_min_size())
+    } else {
+        mik_response
+    };
+
+    // Note: Fields resource is consumed by OutgoingResponse::new().
+    // We scope it explicitly to ensure proper WASI resource lifecycle.
+    let outgoing = {
+        let headers = Fields::new();
+        for (name, value) in mik_response.headers {
+            let _ = headers.append(&name, &value.into_bytes());
+        }
+        // Ownership of headers transfers to OutgoingResponse here
+        OutgoingResponse::new(headers)
+    };
+    // Validate and clamp status code to valid HTTP range (100-599)
+    let status_code = if mik_response.status < 100 {
+        log_error(&format!(
+            "Invalid HTTP status code {}: must be >= 100, using 500",
             mik_response.status
-        };
-        let _ = outgoing.set_status_code(status_code);
-
-        // Get body handle and ensure it's always finished per WASI HTTP spec.
-        // OutgoingBody::finish() must be called whenever we successfully get a body handle.
-        match outgoing.body() {
-            Ok(body_handle) => {
-                // Write body bytes if present
-                if let Some(body_bytes) = mik_response.body.as_ref() {
-                    match body_handle.write() {
-                        Ok(stream) => {
-                            if let Err(e) = stream.blocking_write_and_flush(body_bytes) {
-                                log_error(&format!("Failed to write response body: {:?}", e));
-                            }
-                            // Explicitly drop stream before calling finish (WASI resource cleanup)
-                            drop(stream);
+        ));
+        500
+    } else if mik_response.status >= 600 {
+        log_error(&format!(
+            "Invalid HTTP status code {}: must be < 600, using 500",
+            mik_response.status
+        ));
+        500
+    } else {
+        mik_response.status
+    };
+    let _ = outgoing.set_status_code(status_code);
+
+    // Get body handle and ensure it's always finished per WASI HTTP spec.
+    // OutgoingBody::finish() must be called whenever we successfully get a body handle.
+    let body_handle = match outgoing.body() {
+        Ok(body_handle) => body_handle,
+        Err(e) => {
+            // body() failed - no body handle to finish, but log for debugging
+            log_error(&format!("Failed to get response body handle: {:?}", e));
+            ResponseOutparam::set(response_out, Ok(outgoing));
+            return;
+        }
+    };
+
+    match body_stream {
+        Some(body_stream) => {
+            // Streaming: send the response head now so chunks reach the client as written
+            ResponseOutparam::set(response_out, Ok(outgoing));
+            match body_handle.write() {
+                Ok(stream) => {
+                    while let Some(chunk) = body_stream.next_chunk() {
+                        if deadline_passed(deadline) {
+                            // The status is already sent; cut the body short
+                            log_error("Response stream exceeded MIK_REQUEST_TIMEOUT");
+                            break;
                         }
-                        Err(e) => {
-                            log_error(&format!("Failed to get body write stream: {:?}", e));
+                        if let Err(e) = write_all(&stream, &chunk) {
+                            // Client went away - stop pulling chunks from the handler
+                            log_error(&format!("Failed to write response chunk: {}", e));
+                            break;
                         }
                     }
+                    drop(stream);
                 }
-                // Always finish the body handle (required by WASI HTTP spec)
-                if let Err(e) = OutgoingBody::finish(body_handle, None) {
-                    log_error(&format!("Failed to finish response body: {:?}", e));
+                Err(e) => {
+                    log_error(&format!("Failed to get body write stream: {:?}", e));
                 }
             }
-            Err(e) => {
-                // body() failed - no body handle to finish, but log for debugging
-                log_error(&format!("Failed to get response body handle: {:?}", e));
+            drop(body_stream);
+            if let Err(e) = OutgoingBody::finish(body_handle, None) {
+                log_error(&format!("Failed to finish response body: {:?}", e));
             }
         }
+        None => {
+            // Write body bytes if present
+            if let Some(body_bytes) = mik_response.body.as_ref() {
+                match body_handle.write() {
+                    Ok(stream) => {
+                        if let Err(e) = write_all(&stream, body_bytes) {
+                            log_error(&format!("Failed to write response body: {}", e));
+                        }
+                        // Explicitly drop stream before calling finish (WASI resource cleanup)
+                        drop(stream);
+                    }
+                    Err(e) => {
+                        log_error(&format!("Failed to get body write stream: {:?}", e));
+                    }
+                }
+            }
+            // Always finish the body handle (required by WASI HTTP spec)
+            if let Err(e) = OutgoingBody::finish(body_handle, None) {
+                log_error(&format!("Failed to finish response body: {:?}", e));
+            }
+            // Set response exactly once at the end
+            ResponseOutparam::set(response_out, Ok(outgoing));
+        }
+    }
+}
+
+/// Decompress a request body sent with `Content-Encoding: gzip`, `deflate` or
+/// `br`.
+///
+/// On success the `Content-Encoding` header is dropped and `Content-Length`
+/// updated, so the handler sees a plain body. Returns the error status
+/// otherwise: 415 for unknown codings, 400 for corrupt data, and 413 if the
+/// decompressed body exceeds `max_size`.
+fn decode_body(
+    mut headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+    max_size: usize,
+) -> Result<(Vec<(String, String)>, Option<Vec<u8>>), u16> {
+    let Some(encoding) = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-encoding"))
+        .map(|(_, value)| value.clone())
+    else {
+        return Ok((headers, body));
+    };
+    let Some(body) = body else {
+        return Ok((headers, None));
+    };
+
+    let decoded = compress::decode(&encoding, &body, max_size).map_err(|e| -> u16 {
+        match e {
+            DecodeError::Unsupported => 415,
+            DecodeError::Invalid => 400,
+            DecodeError::TooLarge => 413,
+        }
+    })?;
+    headers.retain(|(name, _)| {
+        !name.eq_ignore_ascii_case("content-encoding")
+            && !name.eq_ignore_ascii_case("content-length")
+    });
+    headers.push(("content-length".to_string(), decoded.len().to_string()));
+    Ok((headers, (!decoded.is_empty()).then_some(decoded)))
+}
 
-        // Set response exactly once at the end
-        ResponseOutparam::set(response_out, Ok(outgoing));
+/// Returns the encoding to compress the response with, from `Accept-Encoding`.
+fn accepted_encoding(headers: &[(String, String)]) -> Option<Encoding> {
+    headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("accept-encoding"))
+        .and_then(|(_, value)| compress::negotiate(value))
+}
+
+/// Compress a buffered response body if the client accepts it and it pays off.
+///
+/// Skipped when compression is disabled (`min_size` is `None`), the body is
+/// smaller than `min_size`, the handler already set `Content-Encoding`, the
+/// content type is not compressible, or the result would not be smaller.
+fn compress_response(
+    mut response: Response,
+    encoding: Option<Encoding>,
+    min_size: Option<usize>,
+) -> Response {
+    let (Some(encoding), Some(min_size)) = (encoding, min_size) else {
+        return response;
+    };
+    let header = |name: &str| {
+        response
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    };
+    let eligible = header("content-encoding").is_none()
+        && header(HEADER_CONTENT_TYPE).is_some_and(compress::is_compressible)
+        && !matches!(response.status, 204 | 206 | 304);
+    let Some(body) = response
+        .body
+        .as_ref()
+        .filter(|b| eligible && b.len() >= min_size)
+    else {
+        return response;
+    };
+
+    let compressed = encoding.encode(body);
+    if compressed.len() >= body.len() {
+        return response;
     }
+    response
+        .headers
+        .retain(|(k, _)| !k.eq_ignore_ascii_case("content-length"));
+    response.headers.push((
+        "content-encoding".to_string(),
+        encoding.as_str().to_string(),
+    ));
+    response
+        .headers
+        .push(("vary".to_string(), "accept-encoding".to_string()));
+    response.body = Some(compressed);
+    response
+}
+
+/// Write bytes to an output stream, flushing after each piece.
+///
+/// `blocking-write-and-flush` accepts at most 4096 bytes per call, so
+/// larger buffers are split.
+fn write_all(stream: &OutputStream, bytes: &[u8]) -> Result<(), String> {
+    for piece in bytes.chunks(MAX_WRITE_CHUNK) {
+        stream
+            .blocking_write_and_flush(piece)
+            .map_err(|e| format!("{:?}", e))?;
+    }
+    Ok(())
 }
 
 /// Log an error message to stderr.
@@ -275,16 +920,25 @@ fn convert_method(m: bindings::wasi::http::types::Method) -> Option<Method> {
 /// - Header values must be valid UTF-8 (per HTTP semantics)
 /// - Invalid UTF-8 values are dropped with a single log message per request
 /// - Multiple headers with the same name are preserved as separate entries
+/// - Limits are checked before any value is converted
 ///
 /// # Returns
 ///
-/// Vector of (header_name, header_value) pairs with valid UTF-8 encoding.
-fn extract_headers(req: &IncomingRequest) -> Vec<(String, String)> {
-    let headers = req.headers();
-    let mut result = Vec::new();
+/// Vector of (header_name, header_value) pairs with valid UTF-8 encoding, or
+/// `None` if the headers exceed `limits` (the caller answers 431).
+fn extract_headers(req: &IncomingRequest, limits: HeaderLimits) -> Option<Vec<(String, String)>> {
+    let entries = req.headers().entries();
+    if !limits.allows(&entries) {
+        log_error(&format!(
+            "Request headers exceed MIK_MAX_HEADERS or MIK_MAX_HEADER_SIZE ({} fields)",
+            entries.len()
+        ));
+        return None;
+    }
+    let mut result = Vec::with_capacity(entries.len());
     let mut invalid_count = 0;
 
-    for (name, value) in headers.entries() {
+    for (name, value) in entries {
         match String::from_utf8(value) {
             Ok(v) => result.push((name, v)),
             Err(_) => invalid_count += 1,
@@ -299,7 +953,19 @@ fn extract_headers(req: &IncomingRequest) -> Vec<(String, String)> {
         ));
     }
 
-    result
+    Some(result)
+}
+
+/// Returns the declared `Content-Length` of a request, if present and valid.
+///
+/// Searches headers manually to avoid String allocation from `.get(&String)`.
+fn content_length(req: &IncomingRequest) -> Option<usize> {
+    req.headers()
+        .entries()
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| std::str::from_utf8(value).ok())
+        .and_then(|s| s.trim().parse::<usize>().ok())
 }
 
 /// Result of reading request body.
@@ -308,9 +974,49 @@ enum BodyResult {
     Ok(Option<Vec<u8>>),
     /// Body exceeded size limit.
     TooLarge,
+    /// The request deadline passed while waiting for body data.
+    TimedOut,
+}
+
+/// Result of reading one chunk of a body stream.
+enum ReadResult {
+    /// Bytes read (never empty).
+    Data(Vec<u8>),
+    /// The stream is closed or failed.
+    End,
+    /// The deadline passed before more data arrived.
+    TimedOut,
 }
 
-fn read_body(req: &IncomingRequest) -> BodyResult {
+/// Read the next chunk of a body stream, waiting no longer than `deadline`.
+///
+/// Without a deadline this is a plain `blocking-read`. With one, the stream
+/// and a clock pollable are polled together, so a client that stops sending
+/// cannot hold the request open past the deadline.
+fn read_chunk(stream: &InputStream, deadline: Option<Instant>) -> ReadResult {
+    let Some(deadline) = deadline else {
+        return match stream.blocking_read(READ_CHUNK) {
+            Ok(chunk) if !chunk.is_empty() => ReadResult::Data(chunk),
+            _ => ReadResult::End,
+        };
+    };
+    loop {
+        match stream.read(READ_CHUNK) {
+            Ok(chunk) if chunk.is_empty() => {}
+            Ok(chunk) => return ReadResult::Data(chunk),
+            Err(_) => return ReadResult::End,
+        }
+        // No data yet: wait for the stream or the deadline, whichever comes first.
+        // Pollables are child resources and are dropped before the stream.
+        let readable = stream.subscribe();
+        let timer = monotonic_clock::subscribe_instant(deadline);
+        if !poll::poll(&[&readable, &timer]).contains(&0) {
+            return ReadResult::TimedOut;
+        }
+    }
+}
+
+fn read_body(req: &IncomingRequest, deadline: Option<Instant>) -> BodyResult {
     let body = match req.consume() {
         Ok(b) => b,
         Err(_) => return BodyResult::Ok(None),
@@ -323,47 +1029,42 @@ fn read_body(req: &IncomingRequest) -> BodyResult {
 
     // Pre-allocate based on Content-Length header if available, otherwise use chunk size.
     // This avoids multiple Vec reallocations for large bodies.
-    // Search headers manually to avoid String allocation from .get(&String).
-    let content_length_hint = req
-        .headers()
-        .entries()
-        .iter()
-        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
-        .and_then(|(_, value)| std::str::from_utf8(value).ok())
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(64 * 1024); // Default to one chunk size
+    let content_length_hint = content_length(req).unwrap_or(64 * 1024); // Default to one chunk size
 
     // Cap pre-allocation at max_size to avoid memory exhaustion from malicious headers
     let initial_capacity = content_length_hint.min(max_size);
 
     let mut bytes = Vec::with_capacity(initial_capacity);
-    let mut too_large = false;
+    let mut failure = None;
     loop {
-        match stream.blocking_read(64 * 1024) {
-            Ok(chunk) if chunk.is_empty() => break,
-            Ok(chunk) => {
+        match read_chunk(&stream, deadline) {
+            ReadResult::End => break,
+            ReadResult::Data(chunk) => {
                 // Check size limit before extending (use checked arithmetic to prevent overflow)
                 let new_len = match bytes.len().checked_add(chunk.len()) {
                     Some(len) => len,
                     None => {
-                        too_large = true;
+                        failure = Some(BodyResult::TooLarge);
                         break;
                     }
                 };
                 if new_len > max_size {
-                    too_large = true;
+                    failure = Some(BodyResult::TooLarge);
                     break;
                 }
                 bytes.extend(chunk);
             }
-            Err(_) => break,
+            ReadResult::TimedOut => {
+                failure = Some(BodyResult::TimedOut);
+                break;
+            }
         }
     }
     // Explicitly drop stream to ensure WASI resource cleanup before returning
     drop(stream);
 
-    if too_large {
-        return BodyResult::TooLarge;
+    if let Some(failure) = failure {
+        return failure;
     }
 
     if bytes.is_empty() {
@@ -461,12 +1162,15 @@ mod tests {
         assert_eq!(status_title(404), "Not Found");
         assert_eq!(status_title(405), "Method Not Allowed");
         assert_eq!(status_title(413), "Payload Too Large");
+        assert_eq!(status_title(415), "Unsupported Media Type");
         assert_eq!(status_title(422), "Unprocessable Entity");
         assert_eq!(status_title(429), "Too Many Requests");
+        assert_eq!(status_title(431), "Request Header Fields Too Large");
         assert_eq!(status_title(500), "Internal Server Error");
         assert_eq!(status_title(501), "Not Implemented");
         assert_eq!(status_title(502), "Bad Gateway");
         assert_eq!(status_title(503), "Service Unavailable");
+        assert_eq!(status_title(504), "Gateway Timeout");
     }
 
     #[test]
@@ -478,6 +1182,131 @@ mod tests {
         assert_eq!(status_title(301), "Error");
     }
 
+    // ========================================================================
+    // compression tests
+    // ========================================================================
+
+    fn json_response(body: &[u8]) -> Response {
+        Response {
+            status: 200,
+            headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("content-length".to_string(), body.len().to_string()),
+            ],
+            body: Some(body.to_vec()),
+        }
+    }
+
+    #[test]
+    fn test_accepted_encoding() {
+        let headers = vec![("Accept-Encoding".to_string(), "gzip, br".to_string())];
+        assert_eq!(accepted_encoding(&headers), Some(Encoding::Brotli));
+        let headers = vec![("accept-encoding".to_string(), "gzip".to_string())];
+        assert_eq!(accepted_encoding(&headers), Some(Encoding::Gzip));
+        assert_eq!(accepted_encoding(&[]), None);
+    }
+
+    #[test]
+    fn test_decode_body_gzip() {
+        let headers = vec![
+            ("content-encoding".to_string(), "gzip".to_string()),
+            ("content-length".to_string(), "999".to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ];
+        let body = compress::gzip(br#"{"name":"value"}"#);
+        let (headers, body) = decode_body(headers, Some(body), 1024).unwrap();
+        assert_eq!(body.as_deref(), Some(&br#"{"name":"value"}"#[..]));
+        assert_eq!(
+            headers,
+            vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("content-length".to_string(), "16".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_body_errors() {
+        let gzip = vec![("Content-Encoding".to_string(), "gzip".to_string())];
+        let zstd = vec![("Content-Encoding".to_string(), "zstd".to_string())];
+        let bomb = compress::gzip(&[0u8; 4096]);
+        assert_eq!(decode_body(zstd, Some(b"x".to_vec()), 1024), Err(415));
+        assert_eq!(
+            decode_body(gzip.clone(), Some(b"x".to_vec()), 1024),
+            Err(400)
+        );
+        assert_eq!(decode_body(gzip, Some(bomb), 1024), Err(413));
+    }
+
+    #[test]
+    fn test_decode_body_passthrough() {
+        let plain = vec![("content-type".to_string(), "text/plain".to_string())];
+        assert_eq!(
+            decode_body(plain.clone(), Some(b"hi".to_vec()), 1024),
+            Ok((plain, Some(b"hi".to_vec())))
+        );
+    }
+
+    #[test]
+    fn test_compress_response_gzip() {
+        let body = br#"{"name":"value"},"#.repeat(100);
+        let response = compress_response(json_response(&body), Some(Encoding::Gzip), Some(1024));
+        assert!(response.body.as_ref().unwrap().len() < body.len());
+        assert!(
+            response
+                .headers
+                .contains(&("content-encoding".to_string(), "gzip".to_string()))
+        );
+        assert!(
+            response
+                .headers
+                .contains(&("vary".to_string(), "accept-encoding".to_string()))
+        );
+        assert!(!response.headers.iter().any(|(k, _)| k == "content-length"));
+    }
+
+    #[test]
+    fn test_compress_response_brotli() {
+        let body = br#"{"name":"value"},"#.repeat(100);
+        let response = compress_response(json_response(&body), Some(Encoding::Brotli), Some(1024));
+        assert!(
+            response
+                .headers
+                .contains(&("content-encoding".to_string(), "br".to_string()))
+        );
+        let compressed = response.body.unwrap();
+        assert_eq!(compress::decode("br", &compressed, 1 << 20), Ok(body));
+    }
+
+    #[test]
+    fn test_compress_response_skipped() {
+        let body = br#"{"name":"value"},"#.repeat(100);
+
+        // Client does not accept gzip, or compression disabled
+        let response = compress_response(json_response(&body), None, Some(1024));
+        assert_eq!(response.body.as_deref(), Some(&body[..]));
+        let response = compress_response(json_response(&body), Some(Encoding::Gzip), None);
+        assert_eq!(response.body.as_deref(), Some(&body[..]));
+
+        // Below the threshold
+        let response = compress_response(json_response(b"{}"), Some(Encoding::Gzip), Some(1024));
+        assert_eq!(response.body.as_deref(), Some(&b"{}"[..]));
+
+        // Already compressed content type
+        let mut image = json_response(&body);
+        image.headers[0].1 = "image/png".to_string();
+        let response = compress_response(image, Some(Encoding::Gzip), Some(1024));
+        assert_eq!(response.body.as_deref(), Some(&body[..]));
+
+        // Handler already set an encoding
+        let mut encoded = json_response(&body);
+        encoded
+            .headers
+            .push(("content-encoding".to_string(), "br".to_string()));
+        let response = compress_response(encoded, Some(Encoding::Gzip), Some(1024));
+        assert_eq!(response.body.as_deref(), Some(&body[..]));
+    }
+
     // ========================================================================
     // escape_json_string tests
     // ========================================================================
@@ -563,6 +1392,75 @@ mod tests {
         assert!(!escaped.contains(r#"""#) || escaped.contains(r#"\""#));
     }
 
+    // ========================================================================
+    // header limit tests
+    // ========================================================================
+
+    fn entries(count: usize, value_len: usize) -> Vec<(String, Vec<u8>)> {
+        (0..count)
+            .map(|i| (format!("x-h{i}"), vec![b'v'; value_len]))
+            .collect()
+    }
+
+    #[test]
+    fn test_header_limits_allow() {
+        let limits = HeaderLimits {
+            max_count: 3,
+            max_size: 64,
+        };
+        assert!(limits.allows(&[]));
+        // 3 x ("x-hN" + 10 bytes) = 42 bytes
+        assert!(limits.allows(&entries(3, 10)));
+        // Exactly at the size limit: 4 x 16 = 64 bytes
+        let limits = HeaderLimits {
+            max_count: 4,
+            max_size: 64,
+        };
+        assert!(limits.allows(&entries(4, 12)));
+    }
+
+    #[test]
+    fn test_header_limits_reject() {
+        let limits = HeaderLimits {
+            max_count: 3,
+            max_size: 64,
+        };
+        assert!(!limits.allows(&entries(4, 0)));
+        assert!(!limits.allows(&entries(1, 61)));
+        assert!(!limits.allows(&entries(3, 18)));
+    }
+
+    #[test]
+    fn test_default_header_limits() {
+        let limits = HeaderLimits {
+            max_count: DEFAULT_MAX_HEADERS,
+            max_size: DEFAULT_MAX_HEADER_SIZE,
+        };
+        assert!(limits.allows(&entries(100, 600)));
+        assert!(!limits.allows(&entries(101, 0)));
+        assert!(!limits.allows(&entries(1, 64 * 1024)));
+    }
+
+    // ========================================================================
+    // request timeout tests
+    // ========================================================================
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("30000"), Some(30_000));
+        assert_eq!(parse_timeout(" 250 "), Some(250));
+        assert_eq!(parse_timeout("0"), None);
+        assert_eq!(parse_timeout("-5"), None);
+        assert_eq!(parse_timeout("30s"), None);
+    }
+
+    #[test]
+    fn test_deadline_after() {
+        assert_eq!(deadline_after(1_000, 2), 2_001_000);
+        assert_eq!(deadline_after(u64::MAX - 1, 1), u64::MAX);
+        assert_eq!(deadline_after(0, u64::MAX), u64::MAX);
+    }
+
     // ========================================================================
     // DEFAULT_MAX_BODY_SIZE constant test
     // ========================================================================
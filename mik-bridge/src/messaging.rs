@@ -0,0 +1,32 @@
+//! wasi:messaging adapter.
+//!
+//! Exports `wasi:messaging/incoming-handler` from the `messaging-bridge`
+//! world and passes each delivered message to the handler's consumers.
+
+use crate::bindings::exports::wasi::messaging::incoming_handler::Guest;
+use crate::bindings::mik::core::consumer;
+use crate::bindings::wasi::messaging::types::{Error, Message};
+use crate::{Bridge, log_error};
+
+impl Guest for Bridge {
+    fn handle(message: Message) -> Result<(), Error> {
+        let mut metadata = message.metadata().unwrap_or_default();
+        if let Some(content_type) = message.content_type()
+            && !metadata
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+        {
+            metadata.push(("content-type".to_string(), content_type));
+        }
+
+        let msg = consumer::Message {
+            topic: message.topic().unwrap_or_default(),
+            body: message.data(),
+            metadata,
+        };
+        consumer::handle(&msg).map_err(|err| {
+            log_error(&format!("Message on '{}' failed: {}", msg.topic, err));
+            Error::Other(err)
+        })
+    }
+}
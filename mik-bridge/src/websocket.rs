@@ -0,0 +1,527 @@
+//! WebSocket handshake and framing (RFC 6455).
+//!
+//! Used by the `websocket` feature: the bridge answers an upgrade request
+//! with `101 Switching Protocols`, then reads client frames from the request
+//! body and writes server frames to the response body. Runtimes that close
+//! the body after the 101 end the session right away (the handler sees
+//! `on-close` with 1006).
+//!
+//! Fragmented messages are reassembled before they reach the handler, pings
+//! are answered with pongs, and protocol violations close the connection
+//! with the matching status code.
+
+// Frame lengths are checked against the message limit before narrowing.
+#![allow(clippy::cast_possible_truncation)]
+
+/// Magic value appended to the client key (RFC 6455 Section 1.3).
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+pub const OP_CONTINUATION: u8 = 0x0;
+pub const OP_TEXT: u8 = 0x1;
+pub const OP_BINARY: u8 = 0x2;
+pub const OP_CLOSE: u8 = 0x8;
+pub const OP_PING: u8 = 0x9;
+pub const OP_PONG: u8 = 0xA;
+
+/// Close code for a normal closure.
+pub const CLOSE_NORMAL: u16 = 1000;
+
+/// Close code reported when the connection dropped without a close frame.
+pub const CLOSE_ABNORMAL: u16 = 1006;
+
+/// The `Sec-WebSocket-Key` of a valid upgrade request, if it is one.
+///
+/// Requires `Upgrade: websocket`, `Connection: upgrade` and
+/// `Sec-WebSocket-Version: 13`.
+pub fn upgrade_key(headers: &[(String, String)]) -> Option<&str> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.trim())
+    };
+    let has_token = |name: &str, token: &str| {
+        header(name).is_some_and(|v| v.split(',').any(|t| t.trim().eq_ignore_ascii_case(token)))
+    };
+    if !has_token("upgrade", "websocket")
+        || !has_token("connection", "upgrade")
+        || header("sec-websocket-version") != Some("13")
+    {
+        return None;
+    }
+    header("sec-websocket-key").filter(|key| !key.is_empty())
+}
+
+/// The `Sec-WebSocket-Accept` value for a client key.
+pub fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{ACCEPT_GUID}").as_bytes()))
+}
+
+/// A decoded frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub fin: bool,
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+/// Why a connection is closed by the bridge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// Malformed or unexpected frame (1002).
+    Protocol,
+    /// Text message that is not valid UTF-8 (1007).
+    InvalidUtf8,
+    /// Message larger than the limit (1009).
+    TooLarge,
+}
+
+impl FrameError {
+    /// Close code sent to the client.
+    pub const fn close_code(self) -> u16 {
+        match self {
+            Self::Protocol => 1002,
+            Self::InvalidUtf8 => 1007,
+            Self::TooLarge => 1009,
+        }
+    }
+}
+
+/// Decode one client frame from the start of `buf`.
+///
+/// Returns the frame and the number of bytes used, or `None` when `buf`
+/// does not hold a complete frame yet. Client frames must be masked.
+pub fn decode(buf: &[u8], max_payload: usize) -> Result<Option<(Frame, usize)>, FrameError> {
+    let (Some(&b0), Some(&b1)) = (buf.first(), buf.get(1)) else {
+        return Ok(None);
+    };
+    let fin = b0 & 0x80 != 0;
+    let opcode = b0 & 0x0F;
+    if b0 & 0x70 != 0 || b1 & 0x80 == 0 {
+        // Reserved bits without an extension, or an unmasked client frame
+        return Err(FrameError::Protocol);
+    }
+    let is_control = opcode & 0x08 != 0;
+    if !matches!(
+        opcode,
+        OP_CONTINUATION | OP_TEXT | OP_BINARY | OP_CLOSE | OP_PING | OP_PONG
+    ) {
+        return Err(FrameError::Protocol);
+    }
+
+    let (len, mut pos) = match b1 & 0x7F {
+        126 => match buf.get(2..4) {
+            Some(b) => (u64::from(u16::from_be_bytes([b[0], b[1]])), 4),
+            None => return Ok(None),
+        },
+        127 => match buf.get(2..10) {
+            Some(b) => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(b);
+                (u64::from_be_bytes(bytes), 10)
+            }
+            None => return Ok(None),
+        },
+        n => (u64::from(n), 2),
+    };
+    if is_control && (!fin || len > 125) {
+        return Err(FrameError::Protocol);
+    }
+    if len > max_payload as u64 {
+        return Err(FrameError::TooLarge);
+    }
+    let len = len as usize;
+
+    let Some(mask) = buf.get(pos..pos + 4) else {
+        return Ok(None);
+    };
+    let mask = [mask[0], mask[1], mask[2], mask[3]];
+    pos += 4;
+    let Some(masked) = buf.get(pos..pos + len) else {
+        return Ok(None);
+    };
+    let payload = masked
+        .iter()
+        .zip(mask.iter().cycle())
+        .map(|(b, m)| b ^ m)
+        .collect();
+    Ok(Some((
+        Frame {
+            fin,
+            opcode,
+            payload,
+        },
+        pos + len,
+    )))
+}
+
+/// Encode an unmasked, unfragmented server frame.
+pub fn encode(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 10);
+    out.push(0x80 | opcode);
+    match payload.len() {
+        len @ 0..=125 => out.push(len as u8),
+        len @ 126..=0xFFFF => {
+            out.push(126);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            out.push(127);
+            out.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Encode a close frame. The reason is cut to fit a control frame.
+pub fn encode_close(code: u16, reason: &str) -> Vec<u8> {
+    let mut end = reason.len().min(123);
+    while !reason.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut payload = code.to_be_bytes().to_vec();
+    payload.extend_from_slice(&reason.as_bytes()[..end]);
+    encode(OP_CLOSE, &payload)
+}
+
+/// What a client frame means for the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Text(String),
+    Binary(Vec<u8>),
+    /// Answer with a pong carrying the same payload.
+    Ping(Vec<u8>),
+    /// The client closed the connection.
+    Close(u16, String),
+}
+
+/// Reassembles fragmented messages and interprets control frames.
+pub struct Assembler {
+    max_message: usize,
+    opcode: Option<u8>,
+    buffer: Vec<u8>,
+}
+
+impl Assembler {
+    /// Messages over `max_message` bytes close the connection with 1009.
+    pub const fn new(max_message: usize) -> Self {
+        Self {
+            max_message,
+            opcode: None,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Feed a frame; returns an event once a message or control frame is complete.
+    pub fn push(&mut self, frame: Frame) -> Result<Option<Event>, FrameError> {
+        match frame.opcode {
+            OP_PING => Ok(Some(Event::Ping(frame.payload))),
+            OP_PONG => Ok(None),
+            OP_CLOSE => Ok(Some(close_event(&frame.payload)?)),
+            OP_CONTINUATION => {
+                let opcode = self.opcode.ok_or(FrameError::Protocol)?;
+                self.append(&frame.payload)?;
+                if !frame.fin {
+                    return Ok(None);
+                }
+                self.opcode = None;
+                message_event(opcode, std::mem::take(&mut self.buffer)).map(Some)
+            }
+            opcode => {
+                if self.opcode.is_some() {
+                    // A new message started before the previous one finished
+                    return Err(FrameError::Protocol);
+                }
+                if frame.fin {
+                    return message_event(opcode, frame.payload).map(Some);
+                }
+                self.opcode = Some(opcode);
+                self.append(&frame.payload)?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn append(&mut self, payload: &[u8]) -> Result<(), FrameError> {
+        if self.buffer.len() + payload.len() > self.max_message {
+            return Err(FrameError::TooLarge);
+        }
+        self.buffer.extend_from_slice(payload);
+        Ok(())
+    }
+}
+
+fn message_event(opcode: u8, payload: Vec<u8>) -> Result<Event, FrameError> {
+    if opcode == OP_TEXT {
+        String::from_utf8(payload)
+            .map(Event::Text)
+            .map_err(|_| FrameError::InvalidUtf8)
+    } else {
+        Ok(Event::Binary(payload))
+    }
+}
+
+fn close_event(payload: &[u8]) -> Result<Event, FrameError> {
+    match payload {
+        [] => Ok(Event::Close(1005, String::new())),
+        [_] => Err(FrameError::Protocol),
+        [hi, lo, reason @ ..] => {
+            let reason = std::str::from_utf8(reason).map_err(|_| FrameError::InvalidUtf8)?;
+            Ok(Event::Close(
+                u16::from_be_bytes([*hi, *lo]),
+                reason.to_string(),
+            ))
+        }
+    }
+}
+
+/// SHA-1, only used for the handshake accept key.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [
+        0x6745_2301,
+        0xEFCD_AB89,
+        0x98BA_DCFE,
+        0x1032_5476,
+        0xC3D2_E1F0,
+    ];
+    let mut message = data.to_vec();
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ED9_EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut out = [0u8; 20];
+    for (chunk, word) in out.chunks_exact_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+/// Standard base64 with padding.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mask a payload the way a client would.
+    fn client_frame(b0: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x37, 0xFA, 0x21, 0x3D];
+        let mut out = vec![b0];
+        if payload.len() < 126 {
+            out.push(0x80 | payload.len() as u8);
+        } else {
+            out.push(0x80 | 126);
+            out.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        }
+        out.extend_from_slice(&mask);
+        out.extend(payload.iter().zip(mask.iter().cycle()).map(|(b, m)| b ^ m));
+        out
+    }
+
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_accept_key() {
+        // Example from RFC 6455 Section 1.3
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn test_sha1_and_base64() {
+        assert_eq!(base64(&sha1(b"")), "2jmj7l5rSw0yVb/vlWAYkK/YBwk=");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+    }
+
+    #[test]
+    fn test_upgrade_key() {
+        let upgrade = headers(&[
+            ("Upgrade", "websocket"),
+            ("Connection", "keep-alive, Upgrade"),
+            ("Sec-WebSocket-Version", "13"),
+            ("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="),
+        ]);
+        assert_eq!(upgrade_key(&upgrade), Some("dGhlIHNhbXBsZSBub25jZQ=="));
+
+        let mut old_version = upgrade.clone();
+        old_version[2].1 = "8".to_string();
+        assert_eq!(upgrade_key(&old_version), None);
+        assert_eq!(upgrade_key(&upgrade[1..]), None);
+        assert_eq!(upgrade_key(&upgrade[..3]), None);
+    }
+
+    #[test]
+    fn test_decode_masked_text() {
+        let bytes = client_frame(0x81, b"Hello");
+        let (frame, used) = decode(&bytes, 1024).unwrap().unwrap();
+        assert_eq!(used, bytes.len());
+        assert!(frame.fin);
+        assert_eq!(frame.opcode, OP_TEXT);
+        assert_eq!(frame.payload, b"Hello");
+
+        // Incomplete input waits for more bytes
+        assert_eq!(decode(&bytes[..bytes.len() - 1], 1024), Ok(None));
+        assert_eq!(decode(&bytes[..1], 1024), Ok(None));
+    }
+
+    #[test]
+    fn test_decode_extended_length() {
+        let payload = vec![b'x'; 300];
+        let bytes = client_frame(0x82, &payload);
+        let (frame, used) = decode(&bytes, 1024).unwrap().unwrap();
+        assert_eq!(used, 2 + 2 + 4 + 300);
+        assert_eq!(frame.payload, payload);
+        assert_eq!(decode(&bytes, 100), Err(FrameError::TooLarge));
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_frames() {
+        // Unmasked
+        assert_eq!(decode(&[0x81, 0x01, b'a'], 1024), Err(FrameError::Protocol));
+        // Reserved bits
+        assert_eq!(
+            decode(&client_frame(0xC1, b"a"), 1024),
+            Err(FrameError::Protocol)
+        );
+        // Unknown opcode
+        assert_eq!(
+            decode(&client_frame(0x83, b"a"), 1024),
+            Err(FrameError::Protocol)
+        );
+        // Fragmented ping
+        assert_eq!(
+            decode(&client_frame(0x09, b"a"), 1024),
+            Err(FrameError::Protocol)
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(OP_TEXT, b"Hi"), vec![0x81, 0x02, b'H', b'i']);
+        let long = encode(OP_BINARY, &[0; 200]);
+        assert_eq!(&long[..4], &[0x82, 126, 0, 200]);
+        assert_eq!(long.len(), 204);
+        assert_eq!(
+            encode_close(1000, "bye"),
+            vec![0x88, 0x05, 0x03, 0xE8, b'b', b'y', b'e']
+        );
+        assert_eq!(encode_close(1000, &"é".repeat(100)).len(), 2 + 2 + 122);
+    }
+
+    #[test]
+    fn test_assembler_fragments() {
+        let mut assembler = Assembler::new(10);
+        let frame = |fin, opcode, payload: &[u8]| Frame {
+            fin,
+            opcode,
+            payload: payload.to_vec(),
+        };
+        assert_eq!(assembler.push(frame(false, OP_TEXT, b"Hel")), Ok(None));
+        // Control frames may be interleaved
+        assert_eq!(
+            assembler.push(frame(true, OP_PING, b"p")),
+            Ok(Some(Event::Ping(b"p".to_vec())))
+        );
+        assert_eq!(
+            assembler.push(frame(true, OP_CONTINUATION, b"lo")),
+            Ok(Some(Event::Text("Hello".to_string())))
+        );
+        assert_eq!(
+            assembler.push(frame(true, OP_CONTINUATION, b"x")),
+            Err(FrameError::Protocol)
+        );
+        assert_eq!(
+            assembler.push(frame(true, OP_TEXT, &[0xFF])),
+            Err(FrameError::InvalidUtf8)
+        );
+        assert_eq!(assembler.push(frame(false, OP_BINARY, &[0; 8])), Ok(None));
+        assert_eq!(
+            assembler.push(frame(true, OP_CONTINUATION, &[0; 8])),
+            Err(FrameError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn test_close_event() {
+        let mut assembler = Assembler::new(10);
+        let close = |payload: &[u8]| Frame {
+            fin: true,
+            opcode: OP_CLOSE,
+            payload: payload.to_vec(),
+        };
+        assert_eq!(
+            assembler.push(close(&[0x03, 0xE8, b'o', b'k'])),
+            Ok(Some(Event::Close(1000, "ok".to_string())))
+        );
+        assert_eq!(
+            assembler.push(close(&[])),
+            Ok(Some(Event::Close(1005, String::new())))
+        );
+        assert_eq!(assembler.push(close(&[0x03])), Err(FrameError::Protocol));
+    }
+}
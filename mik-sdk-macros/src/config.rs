@@ -0,0 +1,157 @@
+//! #[derive(Config)] implementation for struct-based settings.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input};
+
+use crate::type_registry::{get_inner_type, is_option_type};
+
+/// Struct-level and field-level `#[config(...)]` options.
+#[derive(Default)]
+struct ConfigAttrs {
+    prefix: Option<String>,
+    rename: Option<String>,
+    default: Option<String>,
+}
+
+fn parse_config_attrs(attrs: &[syn::Attribute], on_struct: bool) -> syn::Result<ConfigAttrs> {
+    let mut parsed = ConfigAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("config")) {
+        attr.parse_nested_meta(|meta| {
+            if on_struct && meta.path.is_ident("prefix") {
+                let value: LitStr = meta.value()?.parse()?;
+                parsed.prefix = Some(value.value());
+                Ok(())
+            } else if !on_struct && meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                parsed.rename = Some(value.value());
+                Ok(())
+            } else if !on_struct && meta.path.is_ident("default") {
+                let value: LitStr = meta.value()?.parse()?;
+                parsed.default = Some(value.value());
+                Ok(())
+            } else if on_struct {
+                Err(meta.error("Unknown config attribute. Valid options: prefix = \"APP_\""))
+            } else {
+                Err(meta.error(
+                    "Unknown config attribute. Valid options: rename = \"KEY\", default = \"value\"",
+                ))
+            }
+        })?;
+    }
+    Ok(parsed)
+}
+
+// ============================================================================
+// DERIVE CONFIG
+// ============================================================================
+
+/// Generates a `mik_sdk::config::Config` implementation.
+///
+/// Keys default to the prefix plus the uppercased field name. `Option`
+/// fields may be missing; `#[config(default = "...")]` values are parsed
+/// like the variable would be.
+pub fn derive_config_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input.generics,
+            "#[derive(Config)] does not support generic structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    name,
+                    "#[derive(Config)] requires a struct with named fields",
+                )
+                .to_compile_error()
+                .into();
+            },
+        },
+        _ => {
+            return syn::Error::new_spanned(name, "#[derive(Config)] only supports structs")
+                .to_compile_error()
+                .into();
+        },
+    };
+
+    let prefix = match parse_config_attrs(&input.attrs, true) {
+        Ok(attrs) => attrs.prefix.unwrap_or_default(),
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut extractions = Vec::new();
+    for field in fields {
+        let Some(field_name) = field.ident.as_ref() else {
+            continue;
+        };
+        let attrs = match parse_config_attrs(&field.attrs, false) {
+            Ok(attrs) => attrs,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let key = attrs
+            .rename
+            .unwrap_or_else(|| format!("{prefix}{}", field_name.to_string().to_uppercase()));
+
+        extractions.push(if is_option_type(&field.ty) {
+            let inner_ty = get_inner_type(&field.ty).unwrap_or(&field.ty);
+            quote! {
+                #field_name: match __lookup(#key) {
+                    Some(__v) => Some(mik_sdk::config::parse_value::<#inner_ty>(#key, &__v)?),
+                    None => None,
+                }
+            }
+        } else if let Some(default) = attrs.default {
+            quote! {
+                #field_name: match __lookup(#key) {
+                    Some(__v) => mik_sdk::config::parse_value(#key, &__v)?,
+                    None => mik_sdk::config::parse_value(#key, #default)?,
+                }
+            }
+        } else {
+            quote! {
+                #field_name: match __lookup(#key) {
+                    Some(__v) => mik_sdk::config::parse_value(#key, &__v)?,
+                    None => {
+                        return Err(mik_sdk::config::ConfigError::Missing {
+                            key: #key.to_string(),
+                        });
+                    },
+                }
+            }
+        });
+    }
+
+    let tokens = quote! {
+        impl mik_sdk::config::Config for #name {
+            fn from_lookup(
+                __lookup: &dyn Fn(&str) -> Option<String>,
+            ) -> Result<Self, mik_sdk::config::ConfigError> {
+                Ok(Self {
+                    #(#extractions),*
+                })
+            }
+
+            fn load() -> Result<&'static Self, mik_sdk::config::ConfigError> {
+                static __CONFIG: ::std::sync::OnceLock<#name> = ::std::sync::OnceLock::new();
+                if let Some(__config) = __CONFIG.get() {
+                    return Ok(__config);
+                }
+                let __config = <Self as mik_sdk::config::Config>::from_lookup(
+                    &mik_sdk::config::lookup,
+                )?;
+                Ok(__CONFIG.get_or_init(|| __config))
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
@@ -0,0 +1,172 @@
+//! CONSUMERS! macro for queue and topic message handlers.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Ident, LitStr, Path, Result, Token, Type, parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+};
+
+// ============================================================================
+// CONSUMERS! MACRO - Export message handlers through mik:core/consumer
+// ============================================================================
+
+/// Declare message consumers, exported through `mik:core/consumer`.
+///
+/// # Example
+///
+/// ```ignore
+/// consumers! {
+///     "orders" => handle_order(body: OrderMsg),
+///     "audit" => record_audit,
+/// }
+/// ```
+///
+/// Must be used in the same module as `routes!`, which defines `Handler`.
+pub fn consumers_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ConsumersInput);
+
+    let topics: Vec<&LitStr> = input.consumers.iter().map(|c| &c.topic).collect();
+    let arms = input.consumers.iter().map(ConsumerDef::dispatch_arm);
+
+    let tokens = quote! {
+        impl bindings::exports::mik::core::consumer::Guest for Handler {
+            fn topics() -> Vec<String> {
+                vec![#(#topics.to_string()),*]
+            }
+
+            fn handle(
+                __mik_raw: bindings::exports::mik::core::consumer::Message,
+            ) -> Result<(), String> {
+                let __mik_msg = mik_sdk::consumer::Message::new(
+                    __mik_raw.topic,
+                    __mik_raw.body,
+                    __mik_raw.metadata,
+                );
+                mik_sdk::consumer::__handle(&__mik_msg, |__mik_msg| match __mik_msg.topic() {
+                    #(#arms)*
+                    __mik_topic => Err(format!("No consumer for topic '{}'", __mik_topic)),
+                })
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
+
+/// Where a consumer input comes from.
+enum InputSource {
+    Body,
+    Headers,
+}
+
+/// One `"topic" => handler(inputs...)` entry.
+struct ConsumerDef {
+    topic: LitStr,
+    handler: Path,
+    inputs: Vec<(InputSource, Type)>,
+}
+
+impl ConsumerDef {
+    /// The match arm that extracts the inputs and calls the handler.
+    fn dispatch_arm(&self) -> TokenStream2 {
+        let topic = &self.topic;
+        let handler = &self.handler;
+        let vars: Vec<Ident> = (0..self.inputs.len())
+            .map(|i| format_ident!("__mik_input_{}", i))
+            .collect();
+        let parsing = self
+            .inputs
+            .iter()
+            .zip(&vars)
+            .map(|((source, ty), var)| match source {
+                InputSource::Body => quote! {
+                    let #var = mik_sdk::consumer::__body::<#ty>(__mik_msg)?;
+                },
+                InputSource::Headers => quote! {
+                    let #var = mik_sdk::consumer::__headers::<#ty>(__mik_msg)?;
+                },
+            });
+        quote! {
+            #topic => {
+                #(#parsing)*
+                mik_sdk::consumer::IntoConsumerResult::into_consumer_result(
+                    #handler(#(#vars,)* __mik_msg),
+                )
+            }
+        }
+    }
+}
+
+impl Parse for ConsumerDef {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let topic: LitStr = input.parse()?;
+        if topic.value().is_empty() {
+            return Err(syn::Error::new(
+                topic.span(),
+                "Consumer topic cannot be empty",
+            ));
+        }
+        input.parse::<Token![=>]>()?;
+        let handler: Path = input.parse()?;
+
+        let mut inputs = Vec::new();
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            while !content.is_empty() {
+                let source: Ident = content.parse()?;
+                let source = match source.to_string().as_str() {
+                    "body" => InputSource::Body,
+                    "headers" => InputSource::Headers,
+                    other => {
+                        return Err(syn::Error::new(
+                            source.span(),
+                            format!(
+                                "Unknown consumer input '{other}': messages support \
+                                 `body:` (JSON) and `headers:` (metadata)"
+                            ),
+                        ));
+                    },
+                };
+                content.parse::<Token![:]>()?;
+                inputs.push((source, content.parse()?));
+                if !content.is_empty() {
+                    content.parse::<Token![,]>()?;
+                }
+            }
+        }
+
+        Ok(Self {
+            topic,
+            handler,
+            inputs,
+        })
+    }
+}
+
+/// Input for the consumers! macro.
+struct ConsumersInput {
+    consumers: Punctuated<ConsumerDef, Token![,]>,
+}
+
+impl Parse for ConsumersInput {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let consumers = Punctuated::<ConsumerDef, Token![,]>::parse_terminated(input)?;
+        let mut topics = Vec::new();
+        for consumer in &consumers {
+            let topic = consumer.topic.value();
+            if topics.contains(&topic) {
+                return Err(syn::Error::new(
+                    consumer.topic.span(),
+                    format!("Duplicate consumer for topic '{topic}'"),
+                ));
+            }
+            topics.push(topic);
+        }
+        Ok(Self { consumers })
+    }
+}
@@ -0,0 +1,201 @@
+//! #[derive(Form)] implementation for form-urlencoded body types.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, parse_macro_input};
+use utoipa::openapi::{ObjectBuilder, Schema};
+
+use super::query_derive::{default_to_json_value, scalar_schema_builder};
+use super::type_derive::validation::{
+    generate_struct_rules, generate_validate_impl, generate_validation_checks,
+};
+use super::{
+    DeriveContext, extract_named_fields, get_inner_type, is_option_type, parse_field_attrs,
+    rust_type_to_name,
+};
+use crate::openapi::utoipa::{FieldConstraints, apply_constraints, schema_to_json};
+
+// ============================================================================
+// DERIVE FORM
+// ============================================================================
+
+/// Generates `FromForm`, `Validate` and `OpenApiSchema` implementations.
+///
+/// Parsing follows HTML form semantics: empty values leave optional and
+/// defaulted fields unset, and `bool` fields are checkboxes (`on`/`true`/`1`
+/// when checked, `false` when absent).
+#[allow(clippy::too_many_lines)] // Complex derive with many field processing branches
+pub fn derive_form_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match extract_named_fields(&input, DeriveContext::Form) {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+    let struct_rules = match generate_struct_rules(&input.attrs, fields) {
+        Ok(rules) => rules,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut field_inits = Vec::new();
+    let mut field_matches = Vec::new();
+    let mut field_finals = Vec::new();
+    let mut field_checks = Vec::new();
+
+    let mut schema_builder = ObjectBuilder::new();
+
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_ty = &field.ty;
+        let attrs = match parse_field_attrs(&field.attrs) {
+            Ok(attrs) => attrs,
+            Err(e) => return e.to_compile_error().into(),
+        };
+
+        let form_key = attrs
+            .rename
+            .clone()
+            .unwrap_or_else(|| field_name.to_string());
+        let is_optional = is_option_type(field_ty);
+
+        let inner_ty = if is_optional {
+            get_inner_type(field_ty)
+        } else {
+            Some(field_ty)
+        };
+        let type_name = inner_ty.map_or("value", rust_type_to_name);
+        let is_bool = type_name == "boolean";
+
+        // Checkboxes submit "on" when checked and nothing when unchecked
+        let parse_value = if is_bool {
+            quote! {
+                match __v.as_str() {
+                    "on" | "true" | "1" | "yes" => true,
+                    "off" | "false" | "0" | "no" => false,
+                    _ => return Err(mik_sdk::typed::ParseError::type_mismatch(#form_key, #type_name)),
+                }
+            }
+        } else {
+            quote! {
+                __v.parse().map_err(|_|
+                    mik_sdk::typed::ParseError::type_mismatch(#form_key, #type_name)
+                )?
+            }
+        };
+
+        let (mut field_builder, is_string) = scalar_schema_builder(field_ty);
+        let default = attrs
+            .default
+            .clone()
+            .or_else(|| (is_bool && !is_optional).then(|| "false".to_string()));
+
+        if is_optional {
+            field_inits.push(quote! {
+                let mut #field_name: #field_ty = None;
+            });
+            field_matches.push(quote! {
+                #form_key => {
+                    #field_name = if __v.is_empty() { None } else { Some(#parse_value) };
+                }
+            });
+            field_finals.push(quote! { #field_name });
+        } else if let Some(ref default) = default {
+            let default_val: TokenStream2 = default
+                .parse()
+                .unwrap_or_else(|_| quote! { Default::default() });
+            field_inits.push(quote! {
+                let mut #field_name: #field_ty = #default_val;
+            });
+            field_matches.push(quote! {
+                #form_key => {
+                    if !__v.is_empty() {
+                        #field_name = #parse_value;
+                    }
+                }
+            });
+            field_finals.push(quote! { #field_name });
+
+            if let Some(default_json) = default_to_json_value(default) {
+                field_builder = field_builder.default(Some(default_json));
+            }
+        } else {
+            field_inits.push(quote! {
+                let mut #field_name: Option<#field_ty> = None;
+            });
+            field_matches.push(quote! {
+                #form_key => {
+                    #field_name = Some(#parse_value);
+                }
+            });
+            field_finals.push(quote! {
+                #field_name: #field_name.ok_or_else(|| mik_sdk::typed::ParseError::missing(#form_key))?
+            });
+            schema_builder = schema_builder.required(&form_key);
+        }
+
+        let constraints = FieldConstraints {
+            min: attrs.min,
+            max: attrs.max,
+            format: attrs.format.clone(),
+            pattern: attrs.pattern.clone(),
+            description: attrs.docs.clone(),
+            x_attrs: attrs.x_attrs.clone(),
+            deprecated: attrs.deprecated,
+        };
+        field_builder = apply_constraints(field_builder, &constraints, is_string);
+        let field_schema: Schema = field_builder.build().into();
+        schema_builder = schema_builder.property(&form_key, field_schema);
+
+        generate_validation_checks(
+            &attrs,
+            field_name,
+            is_optional,
+            if is_string { "string" } else { "number" },
+            &mut field_checks,
+        );
+    }
+
+    let schema: Schema = schema_builder.build().into();
+    let schema_json = schema_to_json(&schema);
+    let name_str = name.to_string();
+
+    let validate_impl = generate_validate_impl(name, &field_checks, &struct_rules);
+
+    let tokens = quote! {
+        impl mik_sdk::typed::FromForm for #name {
+            fn from_form(__params: &[(String, String)]) -> Result<Self, mik_sdk::typed::ParseError> {
+                #(#field_inits)*
+
+                for (__k, __v) in __params {
+                    match __k.as_str() {
+                        #(#field_matches)*
+                        _ => {}
+                    }
+                }
+
+                let __form = Self {
+                    #(#field_finals),*
+                };
+                mik_sdk::typed::Validate::validate(&__form)
+                    .map_err(|e| mik_sdk::typed::ParseError::custom(e.field(), e.message()))?;
+                Ok(__form)
+            }
+        }
+
+        #validate_impl
+
+        impl mik_sdk::typed::OpenApiSchema for #name {
+            fn openapi_schema() -> &'static str {
+                #schema_json
+            }
+
+            fn schema_name() -> &'static str {
+                #name_str
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
@@ -0,0 +1,173 @@
+//! #[derive(Headers)] implementation for request header types.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, parse_macro_input};
+use utoipa::openapi::{ObjectBuilder, Schema};
+
+use super::query_derive::{default_to_json_value, scalar_schema_builder};
+use super::{
+    DeriveContext, FieldAttrs, extract_named_fields, get_inner_type, is_option_type,
+    parse_field_attrs, rust_type_to_name,
+};
+use crate::openapi::utoipa::schema_to_json;
+
+// ============================================================================
+// DERIVE HEADERS
+// ============================================================================
+
+/// Generates `FromHeaders` and `OpenApiSchema` implementations.
+///
+/// Header names default to the field name with `_` replaced by `-`, and are
+/// matched case-insensitively.
+#[allow(clippy::too_many_lines)] // Complex derive with many field processing branches
+pub fn derive_headers_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match extract_named_fields(&input, DeriveContext::Headers) {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+
+    let mut field_extractions = Vec::new();
+    let mut schema_builder = ObjectBuilder::new();
+    let mut header_params: Vec<serde_json::Value> = Vec::new();
+
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_ty = &field.ty;
+        let attrs = match parse_field_attrs(&field.attrs) {
+            Ok(attrs) => attrs,
+            Err(e) => return e.to_compile_error().into(),
+        };
+
+        let header_name = attrs
+            .rename
+            .clone()
+            .unwrap_or_else(|| field_name.to_string().replace('_', "-"));
+        let is_optional = is_option_type(field_ty);
+
+        let inner_ty = if is_optional {
+            get_inner_type(field_ty)
+        } else {
+            Some(field_ty)
+        };
+        let type_name = inner_ty.map_or("value", rust_type_to_name);
+
+        let parse_value = quote! {
+            __v.parse().map_err(|_|
+                mik_sdk::typed::ParseError::type_mismatch(#header_name, #type_name)
+            )?
+        };
+
+        let (mut field_builder, _) = scalar_schema_builder(field_ty);
+        let required = !is_optional && attrs.default.is_none();
+
+        if is_optional {
+            field_extractions.push(quote! {
+                #field_name: match __header(#header_name) {
+                    Some(__v) => Some(#parse_value),
+                    None => None,
+                }
+            });
+        } else if let Some(ref default) = attrs.default {
+            let default_val: TokenStream2 = default
+                .parse()
+                .unwrap_or_else(|_| quote! { Default::default() });
+            field_extractions.push(quote! {
+                #field_name: match __header(#header_name) {
+                    Some(__v) => #parse_value,
+                    None => #default_val,
+                }
+            });
+
+            if let Some(default_json) = default_to_json_value(default) {
+                field_builder = field_builder.default(Some(default_json));
+            }
+        } else {
+            field_extractions.push(quote! {
+                #field_name: match __header(#header_name) {
+                    Some(__v) => #parse_value,
+                    None => return Err(mik_sdk::typed::ParseError::missing(#header_name)),
+                }
+            });
+            schema_builder = schema_builder.required(&header_name);
+        }
+
+        let field_schema: Schema = field_builder.build().into();
+        header_params.push(build_header_parameter(
+            &header_name,
+            required,
+            &field_schema,
+            &attrs,
+        ));
+        schema_builder = schema_builder.property(&header_name, field_schema);
+    }
+
+    let schema: Schema = schema_builder.build().into();
+    let schema_json = schema_to_json(&schema);
+    let name_str = name.to_string();
+    let header_params_json =
+        serde_json::to_string(&header_params).unwrap_or_else(|_| "[]".to_string());
+
+    let tokens = quote! {
+        impl mik_sdk::typed::FromHeaders for #name {
+            fn from_headers(__headers: &[(String, String)]) -> Result<Self, mik_sdk::typed::ParseError> {
+                let __header = |__name: &str| {
+                    __headers
+                        .iter()
+                        .find(|(__k, _)| __k.eq_ignore_ascii_case(__name))
+                        .map(|(_, __v)| __v.as_str())
+                };
+
+                Ok(Self {
+                    #(#field_extractions),*
+                })
+            }
+        }
+
+        impl mik_sdk::typed::OpenApiSchema for #name {
+            fn openapi_schema() -> &'static str {
+                #schema_json
+            }
+
+            fn schema_name() -> &'static str {
+                #name_str
+            }
+
+            fn openapi_header_params() -> &'static str {
+                #header_params_json
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
+
+/// Build an OpenAPI header parameter object, documented from
+/// `#[field(docs = "...")]` and `#[field(deprecated)]`.
+fn build_header_parameter(
+    name: &str,
+    required: bool,
+    schema: &Schema,
+    attrs: &FieldAttrs,
+) -> serde_json::Value {
+    let schema_json: serde_json::Value =
+        serde_json::from_str(&schema_to_json(schema)).unwrap_or_else(|_| serde_json::json!({}));
+
+    let mut param = serde_json::json!({
+        "name": name,
+        "in": "header",
+        "required": required,
+        "schema": schema_json
+    });
+    if let Some(ref docs) = attrs.docs {
+        param["description"] = serde_json::Value::String(docs.clone());
+    }
+    if attrs.deprecated {
+        param["deprecated"] = serde_json::Value::Bool(true);
+    }
+    param
+}
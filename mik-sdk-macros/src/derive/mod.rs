@@ -1,9 +1,12 @@
-//! Derive macros for typed inputs: Type, Query, Path.
+//! Derive macros for typed inputs: Type, Query, Form, Headers, Path.
 //!
-//! These generate implementations for FromJson, FromQuery, FromPath traits,
-//! along with OpenAPI schema generation and optional validation.
+//! These generate implementations for FromJson, FromQuery, FromForm, FromHeaders,
+//! FromPath traits, along with OpenAPI schema generation and optional validation.
 
+mod form_derive;
+mod headers_derive;
 mod path_derive;
+mod pattern;
 mod query_derive;
 mod type_derive;
 
@@ -27,6 +30,7 @@ const VALID_FIELD_ATTRS: &[&str] = &[
     "rename",
     "docs",
     "deprecated",
+    "redact",
 ];
 
 /// Value types for x-* extension attributes.
@@ -39,6 +43,8 @@ pub enum XAttrValue {
 }
 
 // Re-export the public entry points
+pub use form_derive::derive_form_impl;
+pub use headers_derive::derive_headers_impl;
 pub use path_derive::derive_path_impl;
 pub use query_derive::derive_query_impl;
 pub use type_derive::derive_type_impl;
@@ -85,6 +91,8 @@ pub struct FieldAttrs {
     pub(crate) x_attrs: Vec<(String, XAttrValue)>,
     /// Mark field as deprecated in OpenAPI schema
     pub(crate) deprecated: bool,
+    /// Mask the value in log output and parse errors
+    pub(crate) redact: bool,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -149,7 +157,11 @@ pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, syn::Error>
                 let value: Lit = meta.value()?.parse()?;
                 match value {
                     Lit::Str(lit) => {
-                        result.pattern = Some(lit.value());
+                        let pattern = lit.value();
+                        if let Err(msg) = self::pattern::compile(&pattern) {
+                            return Err(syn::Error::new_spanned(&lit, msg));
+                        }
+                        result.pattern = Some(pattern);
                     },
                     _ => {
                         return Err(syn::Error::new_spanned(
@@ -210,6 +222,8 @@ pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, syn::Error>
                         ));
                     },
                 }
+            } else if meta.path.is_ident("redact") {
+                result.redact = true;
             } else {
                 let path = &meta.path;
                 let attr_name = quote!(#path).to_string();
@@ -258,11 +272,12 @@ pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, syn::Error>
                              #[field(min = 1)]           // minimum value/length\n\
                              #[field(max = 100)]         // maximum value/length\n\
                              #[field(default = 10)]      // default value\n\
-                             #[field(format = \"email\")] // format hint (OpenAPI)\n\
-                             #[field(pattern = \"...\")]  // regex pattern (OpenAPI)\n\
+                             #[field(format = \"email\")] // string format\n\
+                             #[field(pattern = \"...\")]  // regex pattern\n\
                              #[field(rename = \"...\")]   // JSON key name\n\
                              #[field(docs = \"...\")]     // description\n\
                              #[field(deprecated = true)] // mark as deprecated\n\
+                             #[field(redact)]            // mask in logs and errors\n\
                              #[field(x_* = ...)]         // OpenAPI x-* extensions"
                         ),
                     ));
@@ -296,12 +311,14 @@ pub fn rust_type_to_name(ty: &Type) -> &'static str {
 // STRUCT FIELD EXTRACTION HELPER
 // ============================================================================
 
-/// Context for derive macro error messages (Query and Path only)
+/// Context for derive macro error messages (Query, Form and Path only)
 ///
 /// Note: Type derive handles its own error messages since it supports both structs and enums.
 #[derive(Clone, Copy)]
 pub enum DeriveContext {
     Query,
+    Form,
+    Headers,
     Path,
 }
 
@@ -309,6 +326,8 @@ impl DeriveContext {
     const fn name(self) -> &'static str {
         match self {
             Self::Query => "Query",
+            Self::Form => "Form",
+            Self::Headers => "Headers",
             Self::Path => "Path",
         }
     }
@@ -316,6 +335,8 @@ impl DeriveContext {
     const fn example(self) -> &'static str {
         match self {
             Self::Query => "struct MyQuery { page: u32, limit: u32 }",
+            Self::Form => "struct LoginForm { username: String, password: String }",
+            Self::Headers => "struct ApiHeaders { x_api_key: String }",
             Self::Path => "struct UserPath { org_id: String, id: String }",
         }
     }
@@ -323,6 +344,8 @@ impl DeriveContext {
     const fn purpose(self) -> &'static str {
         match self {
             Self::Query => "for query parameters",
+            Self::Form => "for form fields",
+            Self::Headers => "for request headers",
             Self::Path => "for URL path parameters",
         }
     }
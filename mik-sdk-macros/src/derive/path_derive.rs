@@ -57,12 +57,14 @@ pub fn derive_path_impl(input: TokenStream) -> TokenStream {
                     .clone()
             });
         } else {
+            // Redacted parameters don't echo the rejected value
+            let redact = attrs.redact.then(|| quote! { .redact() });
             field_extractions.push(quote! {
                 #field_name: __params.get(#path_key)
                     .ok_or_else(|| mik_sdk::typed::ParseError::missing(#path_key))?
                     .parse()
                     .map_err(|_| mik_sdk::typed::ParseError::invalid_format(#path_key,
-                        __params.get(#path_key).map(|s| s.as_str()).unwrap_or("")))?
+                        __params.get(#path_key).map(|s| s.as_str()).unwrap_or(""))#redact)?
             });
         }
 
@@ -0,0 +1,198 @@
+//! Compile `#[field(pattern = "...")]` regexes at expansion time.
+//!
+//! Patterns are parsed with `regex-syntax` and lowered to the instruction set
+//! run by `mik_sdk::typed::pattern::is_match`, so an invalid regex fails the
+//! build and no regex engine is needed at runtime.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use regex_syntax::hir::{Class, Hir, HirKind, Look};
+
+/// Upper bound on compiled instructions, so counted repetitions such as
+/// `\w{1000}` cannot blow up the generated code.
+const MAX_INSTS: usize = 4096;
+
+enum Inst {
+    Ranges(Vec<(char, char)>),
+    Split(usize, usize),
+    Jump(usize),
+    Assert(&'static str),
+    Match,
+}
+
+/// Compile `pattern` to a `&[mik_sdk::typed::pattern::Inst]` expression.
+pub fn compile(pattern: &str) -> Result<TokenStream2, String> {
+    let hir = regex_syntax::Parser::new()
+        .parse(pattern)
+        .map_err(|e| format!("invalid pattern regex: {e}"))?;
+    let mut program = Vec::new();
+    emit(&hir, &mut program)?;
+    check_size(&program)?;
+    program.push(Inst::Match);
+
+    let insts = program.iter().map(|inst| match inst {
+        Inst::Ranges(ranges) => {
+            let ranges = ranges.iter().map(|(start, end)| quote! { (#start, #end) });
+            quote! { mik_sdk::typed::pattern::Inst::Ranges(&[#(#ranges),*]) }
+        },
+        Inst::Split(first, second) => {
+            quote! { mik_sdk::typed::pattern::Inst::Split(#first, #second) }
+        },
+        Inst::Jump(to) => quote! { mik_sdk::typed::pattern::Inst::Jump(#to) },
+        Inst::Assert(look) => {
+            let look = quote::format_ident!("{}", look);
+            quote! {
+                mik_sdk::typed::pattern::Inst::Assert(mik_sdk::typed::pattern::Look::#look)
+            }
+        },
+        Inst::Match => quote! { mik_sdk::typed::pattern::Inst::Match },
+    });
+    Ok(quote! { &[#(#insts),*] })
+}
+
+fn check_size(program: &[Inst]) -> Result<(), String> {
+    if program.len() > MAX_INSTS {
+        return Err(format!(
+            "pattern is too large (more than {MAX_INSTS} instructions); \
+             reduce counted repetitions like {{1000}}"
+        ));
+    }
+    Ok(())
+}
+
+fn emit(hir: &Hir, program: &mut Vec<Inst>) -> Result<(), String> {
+    check_size(program)?;
+    match hir.kind() {
+        HirKind::Empty => {},
+        HirKind::Literal(literal) => {
+            let text = std::str::from_utf8(&literal.0)
+                .map_err(|_| "pattern literals must be valid UTF-8".to_string())?;
+            program.extend(text.chars().map(|c| Inst::Ranges(vec![(c, c)])));
+        },
+        HirKind::Class(Class::Unicode(class)) => {
+            program.push(Inst::Ranges(
+                class
+                    .ranges()
+                    .iter()
+                    .map(|r| (r.start(), r.end()))
+                    .collect(),
+            ));
+        },
+        HirKind::Class(Class::Bytes(class)) => {
+            let ranges = class
+                .ranges()
+                .iter()
+                .map(|r| {
+                    (r.start().is_ascii() && r.end().is_ascii())
+                        .then(|| (char::from(r.start()), char::from(r.end())))
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| "pattern byte classes must be ASCII".to_string())?;
+            program.push(Inst::Ranges(ranges));
+        },
+        HirKind::Look(look) => program.push(Inst::Assert(look_name(*look)?)),
+        HirKind::Repetition(repetition) => {
+            for _ in 0..repetition.min {
+                emit(&repetition.sub, program)?;
+            }
+            if let Some(max) = repetition.max {
+                // Each further copy is optional: x{1,3} becomes x(x(x)?)?
+                let mut splits = Vec::new();
+                for _ in repetition.min..max {
+                    splits.push(program.len());
+                    program.push(Inst::Split(0, 0));
+                    emit(&repetition.sub, program)?;
+                }
+                let end = program.len();
+                for split in splits {
+                    program[split] = Inst::Split(split + 1, end);
+                }
+            } else {
+                let split = program.len();
+                program.push(Inst::Split(0, 0));
+                emit(&repetition.sub, program)?;
+                program.push(Inst::Jump(split));
+                program[split] = Inst::Split(split + 1, program.len());
+            }
+        },
+        HirKind::Capture(capture) => emit(&capture.sub, program)?,
+        HirKind::Concat(hirs) => {
+            for hir in hirs {
+                emit(hir, program)?;
+            }
+        },
+        HirKind::Alternation(hirs) => {
+            let mut jumps = Vec::new();
+            for (i, hir) in hirs.iter().enumerate() {
+                if i + 1 == hirs.len() {
+                    emit(hir, program)?;
+                } else {
+                    let split = program.len();
+                    program.push(Inst::Split(0, 0));
+                    emit(hir, program)?;
+                    jumps.push(program.len());
+                    program.push(Inst::Jump(0));
+                    program[split] = Inst::Split(split + 1, program.len());
+                }
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jump(end);
+            }
+        },
+    }
+    Ok(())
+}
+
+fn look_name(look: Look) -> Result<&'static str, String> {
+    Ok(match look {
+        Look::Start => "Start",
+        Look::End => "End",
+        Look::StartLF => "StartLine",
+        Look::EndLF => "EndLine",
+        Look::WordUnicode => "WordBoundary",
+        Look::WordUnicodeNegate => "NotWordBoundary",
+        Look::WordAscii => "AsciiWordBoundary",
+        Look::WordAsciiNegate => "NotAsciiWordBoundary",
+        other => {
+            return Err(format!(
+                "pattern assertion {other:?} is not supported; use ^, $, \\b or \\B"
+            ));
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_valid_patterns() {
+        for pattern in [
+            r"^[a-z0-9-]+$",
+            r"(?i)^(draft|published)$",
+            r"^\d{3}-\d{4}$",
+            r"\bid\b",
+            "",
+        ] {
+            assert!(compile(pattern).is_ok(), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_regex() {
+        let err = compile("^[a-z+$").unwrap_err();
+        assert!(err.starts_with("invalid pattern regex"), "{err}");
+        assert!(compile("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_compile_rejects_oversized_and_unsupported() {
+        assert!(compile(r"^\w{5000}$").unwrap_err().contains("too large"));
+        assert!(
+            compile(r"\b{start}x")
+                .unwrap_err()
+                .contains("not supported")
+        );
+    }
+}
@@ -6,6 +6,9 @@ use quote::quote;
 use syn::{DeriveInput, Type, parse_macro_input};
 use utoipa::openapi::{ObjectBuilder, RefOr, Schema, schema::SchemaType};
 
+use super::type_derive::validation::{
+    FieldChecks, generate_string_checks, generate_struct_rules, generate_validate_impl,
+};
 use super::{
     DeriveContext, extract_named_fields, get_inner_type, is_option_type, parse_field_attrs,
     rust_type_to_name,
@@ -25,10 +28,15 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
         Ok(fields) => fields,
         Err(err) => return err,
     };
+    let struct_rules = match generate_struct_rules(&input.attrs, fields) {
+        Ok(rules) => rules,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let mut field_inits = Vec::new();
     let mut field_matches = Vec::new();
     let mut field_finals = Vec::new();
+    let mut field_checks = Vec::new();
 
     // Build the object schema using utoipa ObjectBuilder
     let mut schema_builder = ObjectBuilder::new();
@@ -57,6 +65,12 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
             Some(field_ty)
         };
         let type_name = inner_ty.map_or("value", rust_type_to_name);
+        if scalar_schema_builder(field_ty).1 {
+            field_checks.extend(
+                generate_string_checks(&attrs, field_name, is_optional)
+                    .map(|checks| FieldChecks::new(field_name, vec![checks])),
+            );
+        }
 
         if is_optional {
             field_inits.push(quote! {
@@ -141,6 +155,8 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
     let query_params_json =
         serde_json::to_string(&query_params_array).unwrap_or_else(|_| "[]".to_string());
 
+    let validate_impl = generate_validate_impl(name, &field_checks, &struct_rules);
+
     let tokens = quote! {
         impl mik_sdk::typed::FromQuery for #name {
             fn from_query(__params: &[(String, String)]) -> Result<Self, mik_sdk::typed::ParseError> {
@@ -159,6 +175,8 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
             }
         }
 
+        #validate_impl
+
         impl mik_sdk::typed::OpenApiSchema for #name {
             fn openapi_schema() -> &'static str {
                 #schema_json
@@ -179,7 +197,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
 
 /// Try to convert a Rust default value to a `serde_json::Value`.
 /// Returns None for complex expressions that can't be represented in JSON.
-fn default_to_json_value(default: &str) -> Option<serde_json::Value> {
+pub(super) fn default_to_json_value(default: &str) -> Option<serde_json::Value> {
     let trimmed = default.trim();
 
     // Boolean literals
@@ -212,10 +230,10 @@ fn default_to_json_value(default: &str) -> Option<serde_json::Value> {
     None
 }
 
-/// Build an OpenAPI schema for a query parameter type using utoipa.
-/// Returns a tuple of (schema, is_string_type) where is_string_type is used
+/// Start an OpenAPI schema builder for a scalar query or form field type.
+/// Returns a tuple of (builder, is_string_type) where is_string_type is used
 /// for constraint application.
-fn build_query_type_schema(ty: &Type) -> (RefOr<Schema>, bool) {
+pub(super) fn scalar_schema_builder(ty: &Type) -> (ObjectBuilder, bool) {
     let type_str = quote!(#ty).to_string().replace(' ', "");
 
     // Handle Option<T> - extract inner type
@@ -225,37 +243,28 @@ fn build_query_type_schema(ty: &Type) -> (RefOr<Schema>, bool) {
         &type_str
     };
 
-    match inner_type {
+    let (schema_type, is_string) = match inner_type {
         "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
-            let schema = ObjectBuilder::new()
-                .schema_type(SchemaType::Type(utoipa::openapi::Type::Integer))
-                .build()
-                .into();
-            (RefOr::T(schema), false)
-        },
-        "f32" | "f64" => {
-            let schema = ObjectBuilder::new()
-                .schema_type(SchemaType::Type(utoipa::openapi::Type::Number))
-                .build()
-                .into();
-            (RefOr::T(schema), false)
-        },
-        "bool" => {
-            let schema = ObjectBuilder::new()
-                .schema_type(SchemaType::Type(utoipa::openapi::Type::Boolean))
-                .build()
-                .into();
-            (RefOr::T(schema), false)
+            (utoipa::openapi::Type::Integer, false)
         },
+        "f32" | "f64" => (utoipa::openapi::Type::Number, false),
+        "bool" => (utoipa::openapi::Type::Boolean, false),
         // Default to string for String, &str, and unknown types
-        _ => {
-            let schema = ObjectBuilder::new()
-                .schema_type(SchemaType::Type(utoipa::openapi::Type::String))
-                .build()
-                .into();
-            (RefOr::T(schema), true)
-        },
-    }
+        _ => (utoipa::openapi::Type::String, true),
+    };
+
+    (
+        ObjectBuilder::new().schema_type(SchemaType::Type(schema_type)),
+        is_string,
+    )
+}
+
+/// Build an OpenAPI schema for a query parameter type using utoipa.
+/// Returns a tuple of (schema, is_string_type) where is_string_type is used
+/// for constraint application.
+fn build_query_type_schema(ty: &Type) -> (RefOr<Schema>, bool) {
+    let (builder, is_string) = scalar_schema_builder(ty);
+    (RefOr::T(builder.build().into()), is_string)
 }
 
 /// Build an OpenAPI schema for a query parameter type with an optional default value.
@@ -263,32 +272,7 @@ fn build_query_type_schema_with_default(
     ty: &Type,
     default_value: Option<serde_json::Value>,
 ) -> RefOr<Schema> {
-    let type_str = quote!(#ty).to_string().replace(' ', "");
-
-    // Handle Option<T> - extract inner type
-    let inner_type = if type_str.starts_with("Option<") && type_str.ends_with('>') {
-        &type_str[7..type_str.len() - 1]
-    } else {
-        &type_str
-    };
-
-    let mut builder = ObjectBuilder::new();
-
-    match inner_type {
-        "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
-            builder = builder.schema_type(SchemaType::Type(utoipa::openapi::Type::Integer));
-        },
-        "f32" | "f64" => {
-            builder = builder.schema_type(SchemaType::Type(utoipa::openapi::Type::Number));
-        },
-        "bool" => {
-            builder = builder.schema_type(SchemaType::Type(utoipa::openapi::Type::Boolean));
-        },
-        // Default to string for String, &str, and unknown types
-        _ => {
-            builder = builder.schema_type(SchemaType::Type(utoipa::openapi::Type::String));
-        },
-    }
+    let (mut builder, _) = scalar_schema_builder(ty);
 
     if let Some(default_val) = default_value {
         builder = builder.default(Some(default_val));
@@ -90,17 +90,19 @@ pub fn derive_enum_type_impl(input: &DeriveInput, data_enum: &syn::DataEnum) ->
     let schema = enum_schema(&valid_values);
     let openapi_schema = schema_to_json(&schema);
 
+    let into_response_impl = super::generate_into_response_impl(name);
+
     let tokens = quote! {
         impl mik_sdk::typed::FromJson for #name {
             fn from_json(__value: &mik_sdk::json::JsonValue) -> Result<Self, mik_sdk::typed::ParseError> {
                 let __s = __value.str().ok_or_else(|| {
-                    mik_sdk::typed::ParseError::type_mismatch("value", "string")
+                    mik_sdk::typed::ParseError::type_mismatch("", "string")
                 })?;
 
                 match __s.as_str() {
                     #(#from_json_arms)*
                     __other => Err(mik_sdk::typed::ParseError::custom(
-                        "value",
+                        "",
                         format!(
                             "unknown enum variant \"{}\". Valid values: {}",
                             __other,
@@ -119,6 +121,10 @@ pub fn derive_enum_type_impl(input: &DeriveInput, data_enum: &syn::DataEnum) ->
             }
         }
 
+        impl mik_sdk::typed::ToLogJson for #name {}
+
+        #into_response_impl
+
         impl mik_sdk::typed::Validate for #name {
             fn validate(&self) -> Result<(), mik_sdk::typed::ValidationError> {
                 // Enums are always valid if parsed successfully
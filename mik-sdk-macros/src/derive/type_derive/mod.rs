@@ -9,10 +9,12 @@
 mod case;
 mod enum_impl;
 mod struct_impl;
-mod validation;
+pub(super) mod validation;
 
 use proc_macro::TokenStream;
-use syn::{Data, DeriveInput, parse_macro_input};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Ident, parse_macro_input};
 
 /// Entry point for #[derive(Type)] macro.
 pub fn derive_type_impl(input: TokenStream) -> TokenStream {
@@ -37,3 +39,18 @@ pub fn derive_type_impl(input: TokenStream) -> TokenStream {
         .into(),
     }
 }
+
+/// Generate the `IntoResponse` impl that lets handlers return the type
+/// directly, as a `200` JSON response, and the `Returns` impl that lets
+/// routes declare it with `-> Type`.
+fn generate_into_response_impl(name: &Ident) -> TokenStream2 {
+    quote! {
+        impl<__R: mik_sdk::middleware::HttpResponse> mik_sdk::IntoResponse<__R> for #name {
+            fn into_response(self) -> __R {
+                mik_sdk::IntoResponse::into_response(mik_sdk::json::ToJson::to_json(&self))
+            }
+        }
+
+        impl mik_sdk::response::Returns<#name> for #name {}
+    }
+}
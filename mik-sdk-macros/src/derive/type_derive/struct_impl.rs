@@ -5,7 +5,10 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{DeriveInput, Fields, Ident};
 
-use super::validation::generate_validation_checks;
+use super::validation::{
+    generate_json_validation, generate_struct_rules, generate_validate_impl,
+    generate_validation_checks,
+};
 use crate::derive::{
     get_inner_type, is_option_type, parse_field_attrs, rust_type_to_json_getter, rust_type_to_name,
 };
@@ -13,7 +16,7 @@ use crate::openapi::utoipa::{
     FieldConstraints, JsonFieldDef, apply_constraints, object_schema_json, schema_to_json,
 };
 use crate::type_registry::{
-    get_inner_type as registry_get_inner_type, get_openapi_schema, lookup_type,
+    get_inner_type as registry_get_inner_type, get_map_value_type, get_openapi_schema, lookup_type,
 };
 
 /// Generate FromJson, Validate, and OpenApiSchema implementations for structs.
@@ -61,11 +64,18 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
         },
     };
 
+    let struct_rules = match generate_struct_rules(&input.attrs, fields) {
+        Ok(rules) => rules,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     // Generate from_json and to_json implementations
     let mut from_json_fields = Vec::new();
     let mut to_json_fields = Vec::new();
+    let mut log_json_fields = Vec::new();
     let mut field_defs: Vec<JsonFieldDef> = Vec::new();
-    let mut validation_checks: Vec<TokenStream2> = Vec::new();
+    let mut field_checks = Vec::new();
+    let mut json_validation_checks: Vec<TokenStream2> = Vec::new();
     let mut nested_types: Vec<Ident> = Vec::new();
 
     for field in fields {
@@ -88,6 +98,29 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
             .set(#json_key, mik_sdk::json::ToJson::to_json(&self.#field_name))
         });
 
+        // Log output masks redacted fields and redacts nested types
+        log_json_fields.push(if attrs.redact {
+            let redacted = quote! { mik_sdk::json::str(mik_sdk::typed::REDACTED) };
+            if is_optional {
+                quote! {
+                    .set(#json_key, if self.#field_name.is_some() { #redacted } else { mik_sdk::json::null() })
+                }
+            } else {
+                quote! { .set(#json_key, #redacted) }
+            }
+        } else {
+            quote! {
+                .set(#json_key, (&mik_sdk::typed::__LogJson(&self.#field_name)).__log_json())
+            }
+        });
+
+        // Errors of redacted fields don't echo the rejected value
+        let with_path = if attrs.redact {
+            quote! { |e| e.redact().with_path(#json_key) }
+        } else {
+            quote! { |e| e.with_path(#json_key) }
+        };
+
         // Generate from_json field extraction
         if is_optional {
             let inner_ty = get_inner_type(field_ty);
@@ -114,7 +147,10 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
                         if v.is_null() {
                             None
                         } else {
-                            Some(<#inner as mik_sdk::typed::FromJson>::from_json(&v)?)
+                            Some(
+                                <#inner as mik_sdk::typed::FromJson>::from_json(&v)
+                                    .map_err(#with_path)?,
+                            )
                         }
                     }
                 });
@@ -149,9 +185,17 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
                         .ok_or_else(|| mik_sdk::typed::ParseError::missing(#json_key))?
                 });
             } else {
-                // Complex type (Vec, custom struct, etc.) - use FromJson trait
+                // Complex type (Vec, custom struct, etc.) - use FromJson trait,
+                // prefixing nested errors with this field's path
                 from_json_fields.push(quote! {
-                    #field_name: <#field_ty as mik_sdk::typed::FromJson>::from_json(&__value.get(#json_key))?
+                    #field_name: {
+                        let v = __value.get(#json_key);
+                        if v.is_null() {
+                            return Err(mik_sdk::typed::ParseError::missing(#json_key));
+                        }
+                        <#field_ty as mik_sdk::typed::FromJson>::from_json(&v)
+                            .map_err(#with_path)?
+                    }
                 });
             }
         }
@@ -178,9 +222,11 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
             deprecated: attrs.deprecated,
         };
 
-        // Determine if this is a string type for constraint application
-        let is_string_type = base_schema_json.contains("\"type\":\"string\"");
-        let is_array_type = base_schema_json.contains("\"type\":\"array\"");
+        // Determine if this is a string type for constraint application.
+        // Map schemas nest their value type, which must not count here.
+        let is_map_type = base_schema_json.contains("\"additionalProperties\"");
+        let is_string_type = !is_map_type && base_schema_json.contains("\"type\":\"string\"");
+        let is_array_type = !is_map_type && base_schema_json.contains("\"type\":\"array\"");
 
         // Build the field schema with constraints applied
         let field_schema = if constraints.min.is_some()
@@ -211,8 +257,21 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
             field_name,
             is_optional,
             &base_schema_json,
-            &mut validation_checks,
+            &mut field_checks,
         );
+        let value_ty = if is_optional {
+            get_inner_type(field_ty).unwrap_or(field_ty)
+        } else {
+            field_ty
+        };
+        json_validation_checks.push(generate_json_validation(
+            &attrs,
+            &json_key,
+            value_ty,
+            is_optional,
+            &base_schema_json,
+            is_array_type,
+        ));
     }
 
     // Build OpenAPI schema using JSON-based helper (preserves nullable)
@@ -264,6 +323,10 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
         }
     };
 
+    let validate_impl = generate_validate_impl(name, &field_checks, &struct_rules);
+
+    let into_response_impl = super::generate_into_response_impl(name);
+
     let tokens = quote! {
         impl mik_sdk::typed::FromJson for #name {
             fn from_json(__value: &mik_sdk::json::JsonValue) -> Result<Self, mik_sdk::typed::ParseError> {
@@ -280,10 +343,28 @@ pub fn derive_struct_type_impl(input: &DeriveInput, data_struct: &syn::DataStruc
             }
         }
 
-        impl mik_sdk::typed::Validate for #name {
-            fn validate(&self) -> Result<(), mik_sdk::typed::ValidationError> {
-                #(#validation_checks)*
-                Ok(())
+        impl mik_sdk::typed::ToLogJson for #name {
+            fn to_log_json(&self) -> mik_sdk::json::JsonValue {
+                use mik_sdk::typed::{__ViaLogJson as _, __ViaToJson as _};
+                mik_sdk::json::obj()
+                    #(#log_json_fields)*
+            }
+        }
+
+        #into_response_impl
+
+        #validate_impl
+
+        impl #name {
+            /// Check a JSON value against this type's field constraints
+            /// without constructing it, collecting every violation.
+            #[allow(dead_code)]
+            pub fn validate(
+                __value: &mik_sdk::json::JsonValue,
+            ) -> Result<(), mik_sdk::typed::ValidationErrors> {
+                let mut __errors = mik_sdk::typed::ValidationErrors::new();
+                #(#json_validation_checks)*
+                __errors.into_result()
             }
         }
 
@@ -369,6 +450,17 @@ fn build_schema_with_constraints(
     // Check if this is a nullable (Option) type
     let is_nullable = base_json.contains("\"nullable\":true");
 
+    // Maps keep their value schema; only the description applies
+    if base_json.contains("\"additionalProperties\"") {
+        return match (&constraints.description, serde_json::from_str(&base_json)) {
+            (Some(description), Ok(serde_json::Value::Object(mut schema))) => {
+                schema.insert("description".to_string(), description.clone().into());
+                serde_json::Value::Object(schema).to_string()
+            },
+            _ => base_json,
+        };
+    }
+
     // For $ref types, we can't easily add constraints via utoipa
     if base_json.contains("\"$ref\"") {
         return base_json;
@@ -432,6 +524,11 @@ fn extract_custom_type_ident(ty: &syn::Type) -> Option<Ident> {
             return None;
         }
 
+        // Handle map types - extract the value type
+        if name == "HashMap" || name == "BTreeMap" {
+            return get_map_value_type(ty).and_then(extract_custom_type_ident);
+        }
+
         // Check if it's a known primitive type
         if lookup_type(&name).is_some() {
             return None;
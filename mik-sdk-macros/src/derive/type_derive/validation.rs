@@ -5,14 +5,31 @@ use quote::quote;
 
 use crate::derive::FieldAttrs;
 
+/// The checks for one field. `validate_all` runs them as a unit, reporting at
+/// most one violation per field.
+pub struct FieldChecks {
+    field_name: syn::Ident,
+    checks: Vec<TokenStream2>,
+}
+
+impl FieldChecks {
+    pub fn new(field_name: &syn::Ident, checks: Vec<TokenStream2>) -> Self {
+        Self {
+            field_name: field_name.clone(),
+            checks,
+        }
+    }
+}
+
 /// Generate validation check code for a field.
 pub fn generate_validation_checks(
     attrs: &FieldAttrs,
     field_name: &syn::Ident,
     is_optional: bool,
     base_schema: &str,
-    validation_checks: &mut Vec<TokenStream2>,
+    field_checks: &mut Vec<FieldChecks>,
 ) {
+    let mut validation_checks = Vec::new();
     if let Some(min) = attrs.min {
         let field_name_str = field_name.to_string();
         if is_optional {
@@ -87,4 +104,419 @@ pub fn generate_validation_checks(
             });
         }
     }
+    if base_schema.contains("string") {
+        validation_checks.extend(generate_string_checks(attrs, field_name, is_optional));
+    }
+    if !validation_checks.is_empty() {
+        field_checks.push(FieldChecks::new(field_name, validation_checks));
+    }
+}
+
+/// Generate the `Validate` impl: `validate` stops at the first failing
+/// check, `validate_all` collects one violation per field (with the rejected
+/// value) and one per failing struct-level rule.
+pub fn generate_validate_impl(
+    name: &syn::Ident,
+    field_checks: &[FieldChecks],
+    struct_rules: &[TokenStream2],
+) -> TokenStream2 {
+    let checks = field_checks.iter().map(|f| &f.checks);
+    let collected = field_checks
+        .iter()
+        .map(|FieldChecks { field_name, checks }| {
+            quote! {
+                let __check = || -> Result<(), mik_sdk::typed::ValidationError> {
+                    #(#checks)*
+                    Ok(())
+                };
+                if let Err(__e) = __check() {
+                    __errors.push_rejected(__e, mik_sdk::json::ToJson::to_json(&self.#field_name));
+                }
+            }
+        });
+    quote! {
+        impl mik_sdk::typed::Validate for #name {
+            fn validate(&self) -> Result<(), mik_sdk::typed::ValidationError> {
+                #(#(#checks)*)*
+                #(#struct_rules)*
+                Ok(())
+            }
+
+            fn validate_all(&self) -> Result<(), mik_sdk::typed::ValidationErrors> {
+                let mut __errors = mik_sdk::typed::ValidationErrors::new();
+                #(#collected)*
+                #(
+                    let __check = || -> Result<(), mik_sdk::typed::ValidationError> {
+                        #struct_rules
+                        Ok(())
+                    };
+                    if let Err(__e) = __check() {
+                        __errors.push(__e);
+                    }
+                )*
+                __errors.into_result()
+            }
+        }
+    }
+}
+
+/// Formats with a runtime check in `mik_sdk::typed::format`. Other formats
+/// are only emitted into the OpenAPI schema.
+const CHECKED_FORMATS: &[&str] = &["email", "uri", "uuid", "ipv4", "ipv6"];
+
+/// The `mik_sdk::typed::format` function checking the field's format, if any.
+fn format_checker(attrs: &FieldAttrs) -> Option<(&str, TokenStream2)> {
+    let format = attrs.format.as_deref()?;
+    CHECKED_FORMATS.contains(&format).then(|| {
+        let checker = quote::format_ident!("is_{}", format);
+        (format, quote! { mik_sdk::typed::format::#checker })
+    })
+}
+
+/// An expression testing the `&str` `__val` against the field's pattern,
+/// compiled at expansion time. Patterns are validated when the attribute is
+/// parsed, so compiling cannot fail here.
+fn pattern_matcher(attrs: &FieldAttrs) -> Option<(&str, TokenStream2)> {
+    let pattern = attrs.pattern.as_deref()?;
+    let program = crate::derive::pattern::compile(pattern).ok()?;
+    Some((
+        pattern,
+        quote! {
+            {
+                const __PATTERN: &[mik_sdk::typed::pattern::Inst] = #program;
+                mik_sdk::typed::pattern::is_match(__PATTERN, __val)
+            }
+        },
+    ))
+}
+
+/// Generate the `format` and `pattern` checks for a string field.
+pub fn generate_string_checks(
+    attrs: &FieldAttrs,
+    field_name: &syn::Ident,
+    is_optional: bool,
+) -> Option<TokenStream2> {
+    let field_name_str = field_name.to_string();
+    let format_check = format_checker(attrs).map(|(format, checker)| {
+        quote! {
+            if !#checker(__val) {
+                return Err(mik_sdk::typed::ValidationError::format(#field_name_str, #format));
+            }
+        }
+    });
+    let pattern_check = pattern_matcher(attrs).map(|(pattern, matcher)| {
+        quote! {
+            if !#matcher {
+                return Err(mik_sdk::typed::ValidationError::pattern(#field_name_str, #pattern));
+            }
+        }
+    });
+    if format_check.is_none() && pattern_check.is_none() {
+        return None;
+    }
+    Some(if is_optional {
+        quote! {
+            if let Some(ref __val) = self.#field_name {
+                #format_check
+                #pattern_check
+            }
+        }
+    } else {
+        quote! {
+            {
+                let __val = &self.#field_name;
+                #format_check
+                #pattern_check
+            }
+        }
+    })
+}
+
+/// Generate the check run by the derived `validate(&JsonValue)` for a field.
+///
+/// Reports a missing required field, a value of the wrong type, and `min`/
+/// `max`, `format` and `pattern` violations, recording each with the
+/// rejected value on the `ValidationErrors` in `__errors`.
+/// `value_ty` is the field type with any `Option` removed. Nested types are
+/// checked with their `FromJson` impl.
+pub fn generate_json_validation(
+    attrs: &FieldAttrs,
+    json_key: &str,
+    value_ty: &syn::Type,
+    is_optional: bool,
+    base_schema: &str,
+    is_array: bool,
+) -> TokenStream2 {
+    let on_missing = (!is_optional).then(|| {
+        quote! {
+            __errors.push(mik_sdk::typed::ValidationError::custom(
+                #json_key,
+                "required",
+                mik_sdk::typed::ParseError::missing(#json_key).to_string(),
+            ));
+        }
+    });
+
+    let on_present = crate::derive::rust_type_to_json_getter(value_ty).map_or_else(
+        || nested_value_check(attrs, json_key, value_ty, is_array),
+        |getter| simple_value_check(attrs, json_key, value_ty, &getter, base_schema),
+    );
+
+    quote! {
+        {
+            let __field = __value.get(#json_key);
+            if __field.is_null() {
+                #on_missing
+            } else {
+                #on_present
+            }
+        }
+    }
+}
+
+/// Check a value read with a JSON getter: its type, then string length,
+/// format and pattern or numeric bounds.
+fn simple_value_check(
+    attrs: &FieldAttrs,
+    json_key: &str,
+    value_ty: &syn::Type,
+    getter: &TokenStream2,
+    base_schema: &str,
+) -> TokenStream2 {
+    let type_name = crate::derive::rust_type_to_name(value_ty);
+    let format_check = format_checker(attrs).map(|(format, checker)| {
+        quote! {
+            if !#checker(&__val) {
+                __errors.push_rejected(
+                    mik_sdk::typed::ValidationError::format(#json_key, #format),
+                    __field.clone(),
+                );
+            }
+        }
+    });
+    let pattern_check = pattern_matcher(attrs).map(|(pattern, matcher)| {
+        quote! {
+            let __val: &str = &__val;
+            if !#matcher {
+                __errors.push_rejected(
+                    mik_sdk::typed::ValidationError::pattern(#json_key, #pattern),
+                    __field.clone(),
+                );
+            }
+        }
+    });
+    let has_string_checks = format_check.is_some() || pattern_check.is_some();
+    let on_value = if attrs.min.is_none() && attrs.max.is_none() && !has_string_checks {
+        quote! { Some(_) => {}, }
+    } else if base_schema.contains("string") {
+        let checks = bound_checks(attrs, json_key, &quote! { (__val.len() as i128) });
+        quote! { Some(__val) => { #checks #format_check #pattern_check }, }
+    } else {
+        let checks = bound_checks(attrs, json_key, &quote! { (__val as i128) });
+        quote! { Some(__val) => { #checks }, }
+    };
+
+    quote! {
+        let __typed: Option<#value_ty> = __field #getter;
+        match __typed {
+            #on_value
+            None => __errors.push_rejected(
+                mik_sdk::typed::ValidationError::custom(
+                    #json_key,
+                    "type",
+                    mik_sdk::typed::ParseError::type_mismatch(#json_key, #type_name).to_string(),
+                ),
+                __field.clone(),
+            ),
+        }
+    }
+}
+
+/// Check a nested value with its `FromJson` impl, then array length bounds.
+fn nested_value_check(
+    attrs: &FieldAttrs,
+    json_key: &str,
+    value_ty: &syn::Type,
+    is_array: bool,
+) -> TokenStream2 {
+    let length_checks = is_array.then(|| {
+        let checks = bound_checks(attrs, json_key, &quote! { (__len as i128) });
+        quote! {
+            if let Some(__len) = __field.len() {
+                #checks
+            }
+        }
+    });
+
+    quote! {
+        if let Err(__e) = <#value_ty as mik_sdk::typed::FromJson>::from_json(&__field) {
+            let __e = __e.with_path(#json_key);
+            __errors.push_rejected(
+                mik_sdk::typed::ValidationError::custom(__e.field(), "type", __e.to_string()),
+                __field.clone(),
+            );
+        }
+        #length_checks
+    }
+}
+
+/// Push `min`/`max` violations for `value`, an `i128` expression.
+fn bound_checks(attrs: &FieldAttrs, json_key: &str, value: &TokenStream2) -> TokenStream2 {
+    let min = attrs.min.map(|min| {
+        quote! {
+            if #value < (#min as i128) {
+                __errors.push_rejected(
+                    mik_sdk::typed::ValidationError::min(#json_key, #min),
+                    __field.clone(),
+                );
+            }
+        }
+    });
+    let max = attrs.max.map(|max| {
+        quote! {
+            if #value > (#max as i128) {
+                __errors.push_rejected(
+                    mik_sdk::typed::ValidationError::max(#json_key, #max),
+                    __field.clone(),
+                );
+            }
+        }
+    });
+    quote! { #min #max }
+}
+
+// ============================================================================
+// STRUCT-LEVEL RULES
+// ============================================================================
+
+/// A struct-level `#[validate(...)]` rule, run after the field checks.
+pub enum StructRule {
+    /// `#[validate(custom = "path")]`: calls `fn(&Self) -> Result<(), ValidationError>`
+    Custom(syn::Path),
+    /// `#[validate(start < end)]`: compares two fields
+    Compare {
+        left: syn::Ident,
+        op: syn::BinOp,
+        right: syn::Ident,
+    },
+}
+
+impl syn::parse::Parse for StructRule {
+    fn parse(input: syn::parse::ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(syn::Token![=]) && !input.peek2(syn::Token![==]) {
+            let key: syn::Ident = input.parse()?;
+            if key != "custom" {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "Unknown #[validate] option. Use `custom = \"fn_name\"` or a field \
+                     comparison like `start < end`",
+                ));
+            }
+            input.parse::<syn::Token![=]>()?;
+            let path: syn::LitStr = input.parse()?;
+            return Ok(Self::Custom(path.parse()?));
+        }
+
+        let expr: syn::Expr = input.parse()?;
+        let syn::Expr::Binary(binary) = &expr else {
+            return Err(comparison_error(&expr));
+        };
+        let is_comparison = matches!(
+            binary.op,
+            syn::BinOp::Lt(_)
+                | syn::BinOp::Le(_)
+                | syn::BinOp::Gt(_)
+                | syn::BinOp::Ge(_)
+                | syn::BinOp::Eq(_)
+                | syn::BinOp::Ne(_)
+        );
+        match (field_ident(&binary.left), field_ident(&binary.right)) {
+            (Some(left), Some(right)) if is_comparison => Ok(Self::Compare {
+                left,
+                op: binary.op,
+                right,
+            }),
+            _ => Err(comparison_error(&expr)),
+        }
+    }
+}
+
+fn comparison_error(expr: &syn::Expr) -> syn::Error {
+    syn::Error::new_spanned(
+        expr,
+        "#[validate] expects `custom = \"fn_name\"` or a comparison of two fields, \
+         e.g. #[validate(start < end)]",
+    )
+}
+
+/// The field named by a bare identifier expression.
+fn field_ident(expr: &syn::Expr) -> Option<syn::Ident> {
+    match expr {
+        syn::Expr::Path(path) if path.qself.is_none() => path.path.get_ident().cloned(),
+        _ => None,
+    }
+}
+
+/// Generate the checks for a struct's `#[validate(...)]` rules, appended to
+/// `Validate::validate` after the field checks.
+///
+/// Comparisons involving an `Option` field are skipped while it is `None`.
+pub fn generate_struct_rules(
+    attrs: &[syn::Attribute],
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+) -> syn::Result<Vec<TokenStream2>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("validate"))
+        .map(syn::Attribute::parse_args::<StructRule>)
+        .map(|rule| match rule? {
+            StructRule::Custom(path) => Ok(quote! { #path(self)?; }),
+            StructRule::Compare { left, op, right } => {
+                let left_value = field_ref(&left, fields)?;
+                let right_value = field_ref(&right, fields)?;
+                let relation = match op {
+                    syn::BinOp::Lt(_) => "less than",
+                    syn::BinOp::Le(_) => "at most",
+                    syn::BinOp::Gt(_) => "greater than",
+                    syn::BinOp::Ge(_) => "at least",
+                    syn::BinOp::Eq(_) => "equal to",
+                    _ => "different from",
+                };
+                let left_str = left.to_string();
+                let message = format!("`{left}` must be {relation} `{right}`");
+                Ok(quote! {
+                    if let (Some(__left), Some(__right)) = (#left_value, #right_value) {
+                        if !(__left #op __right) {
+                            return Err(mik_sdk::typed::ValidationError::custom(
+                                #left_str,
+                                "compare",
+                                #message,
+                            ));
+                        }
+                    }
+                })
+            },
+        })
+        .collect()
+}
+
+/// An `Option<&T>` expression for a compared field.
+fn field_ref(
+    ident: &syn::Ident,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+) -> syn::Result<TokenStream2> {
+    let field = fields
+        .iter()
+        .find(|f| f.ident.as_ref() == Some(ident))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                ident,
+                format!("#[validate] refers to unknown field `{ident}`"),
+            )
+        })?;
+    Ok(if crate::derive::is_option_type(&field.ty) {
+        quote! { self.#ident.as_ref() }
+    } else {
+        quote! { Some(&self.#ident) }
+    })
 }
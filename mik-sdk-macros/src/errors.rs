@@ -173,7 +173,7 @@ pub fn duplicate_field_error(span: Span, field: &str) -> Error {
 pub fn invalid_attr(span: Span, attr: &str, expected: &str, example: &str) -> Error {
     Error::new(
         span,
-        format!("'{attr}' expects {expected}.\n\n\u{2705} Correct: {example}",),
+        format!("'{attr}' expects {expected}.\n\n\u{2705} Correct: {example}"),
     )
 }
 
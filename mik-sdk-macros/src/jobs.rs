@@ -0,0 +1,155 @@
+//! JOBS! macro for scheduled (cron) jobs.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    LitStr, Path, Result, Token,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+};
+
+/// `@` shorthands accepted by `mik_sdk::schedule::Cron`.
+const SHORTHANDS: &[&str] = &[
+    "@yearly",
+    "@annually",
+    "@monthly",
+    "@weekly",
+    "@daily",
+    "@midnight",
+    "@hourly",
+];
+
+// ============================================================================
+// JOBS! MACRO - Export cron jobs through mik:core/schedule
+// ============================================================================
+
+/// Declare scheduled jobs, exported through `mik:core/schedule`.
+///
+/// # Example
+///
+/// ```ignore
+/// jobs! {
+///     "0 */15 * * * *" => purge_sessions,
+///     "@daily" => send_report,
+/// }
+/// ```
+///
+/// Must be used in the same module as `routes!`, which defines `Handler`.
+pub fn jobs_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as JobsInput);
+
+    let jobs = input.jobs.iter().map(|job| {
+        let schedule = &job.schedule;
+        let name = job.name();
+        let function = &job.function;
+        quote! {
+            mik_sdk::schedule::Job::new(#name, #schedule, |__mik_timestamp| {
+                mik_sdk::schedule::IntoJobResult::into_job_result(#function(__mik_timestamp))
+            })
+        }
+    });
+
+    let tokens = quote! {
+        #[doc(hidden)]
+        const __MIK_JOBS: &[mik_sdk::schedule::Job] = &[#(#jobs),*];
+
+        impl bindings::exports::mik::core::schedule::Guest for Handler {
+            fn jobs() -> Vec<bindings::exports::mik::core::schedule::Job> {
+                __MIK_JOBS
+                    .iter()
+                    .map(|__mik_job| bindings::exports::mik::core::schedule::Job {
+                        name: __mik_job.name().to_string(),
+                        schedule: __mik_job.schedule().to_string(),
+                    })
+                    .collect()
+            }
+
+            fn run(__mik_name: String, __mik_timestamp: u64) -> Result<(), String> {
+                mik_sdk::schedule::__run(__MIK_JOBS, &__mik_name, __mik_timestamp)
+            }
+
+            fn run_due(__mik_timestamp: u64) -> Vec<(String, String)> {
+                mik_sdk::schedule::__run_due(__MIK_JOBS, __mik_timestamp)
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
+
+/// One `"schedule" => function` entry.
+struct JobDef {
+    schedule: LitStr,
+    function: Path,
+}
+
+impl JobDef {
+    /// Job name: the function's last path segment.
+    fn name(&self) -> String {
+        self.function
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl Parse for JobDef {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let schedule: LitStr = input.parse()?;
+        check_schedule(&schedule)?;
+        input.parse::<Token![=>]>()?;
+        let function: Path = input.parse()?;
+        Ok(Self { schedule, function })
+    }
+}
+
+/// Catch malformed schedules at compile time. Field values are checked
+/// when the schedule is parsed at runtime.
+fn check_schedule(schedule: &LitStr) -> Result<()> {
+    let value = schedule.value();
+    let value = value.trim();
+    let fields = value.split_whitespace().count();
+    let valid = if value.starts_with('@') {
+        SHORTHANDS.contains(&value)
+    } else {
+        (fields == 5 || fields == 6)
+            && value.chars().all(|c| {
+                c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || "*?,-/".contains(c)
+            })
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            schedule.span(),
+            "Invalid cron schedule: expected 5 or 6 fields \
+             (`[sec] min hour day-of-month month day-of-week`) or one of \
+             @yearly, @monthly, @weekly, @daily, @hourly",
+        ))
+    }
+}
+
+/// Input for the jobs! macro.
+struct JobsInput {
+    jobs: Punctuated<JobDef, Token![,]>,
+}
+
+impl Parse for JobsInput {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let jobs = Punctuated::<JobDef, Token![,]>::parse_terminated(input)?;
+        let mut names = Vec::new();
+        for job in &jobs {
+            let name = job.name();
+            if names.contains(&name) {
+                return Err(syn::Error::new_spanned(
+                    &job.function,
+                    format!("Duplicate job '{name}': each function can be scheduled once"),
+                ));
+            }
+            names.push(name);
+        }
+        Ok(Self { jobs })
+    }
+}
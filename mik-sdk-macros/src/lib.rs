@@ -25,13 +25,16 @@
 
 use proc_macro::TokenStream;
 
+mod config;
 mod constants;
+mod consumers;
 mod debug;
 mod derive;
 mod dx;
 mod errors;
 mod http_client;
 mod ids;
+mod jobs;
 mod json;
 mod openapi;
 mod response;
@@ -115,6 +118,18 @@ pub fn accepted(input: TokenStream) -> TokenStream {
     response::accepted_impl(input)
 }
 
+/// Return a response with a streamed body.
+#[proc_macro]
+pub fn stream(input: TokenStream) -> TokenStream {
+    response::stream_impl(input)
+}
+
+/// Return a Server-Sent Events response.
+#[proc_macro]
+pub fn sse(input: TokenStream) -> TokenStream {
+    response::sse_impl(input)
+}
+
 // ============================================================================
 // DX Macros
 // ============================================================================
@@ -151,6 +166,41 @@ pub fn ids(input: TokenStream) -> TokenStream {
     ids::ids_impl(input)
 }
 
+/// Declare scheduled jobs, exported through `mik:core/schedule`.
+///
+/// ```ignore
+/// jobs! {
+///     "0 */15 * * * *" => purge_sessions, // fn(u64), Unix seconds
+///     "@daily" => send_report,            // fn(u64) -> Result<(), E>
+/// }
+/// ```
+///
+/// Use it in the same module as `routes!`. Schedules are cron expressions
+/// with 5 or 6 fields (seconds first) or `@` shorthands, in UTC; see
+/// `mik_sdk::schedule`.
+#[proc_macro]
+pub fn jobs(input: TokenStream) -> TokenStream {
+    jobs::jobs_impl(input)
+}
+
+/// Declare queue and topic message consumers, exported through
+/// `mik:core/consumer`.
+///
+/// ```ignore
+/// consumers! {
+///     "orders" => handle_order(body: OrderMsg), // fn(OrderMsg, &Message)
+///     "audit" => record_audit,                  // fn(&Message)
+/// }
+/// ```
+///
+/// Use it in the same module as `routes!`. Inputs come from `body:` (JSON,
+/// validated like route bodies) or `headers:` (message metadata); handlers
+/// return `()` or `Result<(), E>`. See `mik_sdk::consumer`.
+#[proc_macro]
+pub fn consumers(input: TokenStream) -> TokenStream {
+    consumers::consumers_impl(input)
+}
+
 /// Define routes with typed inputs and OpenAPI generation.
 ///
 /// ```ignore
@@ -160,8 +210,46 @@ pub fn ids(input: TokenStream) -> TokenStream {
 ///     GET "/users/{id}" => get_user(path: Id) -> User,
 ///     PUT "/users/{id}" => update_user(path: Id, body: UpdateUser) -> User,
 ///     DELETE "/users/{id}" => delete_user(path: Id),
+///     POST "/login" => login(form: LoginForm),
+/// }
+/// ```
+///
+/// Inputs come from `path:`, `query:`, `body:` (JSON), `form:`, `headers:`,
+/// or `extract:` for custom extractors implementing `FromRequest`.
+///
+/// `POST "/echo" (json) => echo` restricts a route's request `Content-Type`
+/// (`json`, `form`, `multipart`, `text`, `html`, `xml`, `binary` or a
+/// `"type/subtype"` string); other bodies get a 415.
+///
+/// `GET "/admin" [role = "admin" | "owner", permission = "users:read"] => admin`
+/// guards a route on the principal a before hook set with
+/// `mik_sdk::auth::set_principal`: each guard needs one of its values. No
+/// principal gets a 401, a missing role or permission a 403.
+///
+/// Middleware hooks wrap matched routes. `#![before(...)]` / `#![after(...)]`
+/// at the top apply to every route, `#[before(...)]` / `#[after(...)]` to one:
+///
+/// ```ignore
+/// routes! {
+///     #![after(add_security_headers)] // fn(&Request, Response) -> Response
+///     #[before(require_auth)]         // fn(&Request) -> Option<Response>
+///     DELETE "/users/{id}" => delete_user(path: Id),
 /// }
 /// ```
+///
+/// `fallback => handler` (`fn(&Request) -> Response`) replaces the 404 for
+/// unmatched paths, and `error => handler` (`fn(u16, &str, &Request) -> Response`)
+/// builds every error response the router generates itself.
+///
+/// `#![cors]` answers CORS preflights and adds CORS headers to every
+/// response, configured from `MIK_CORS_*` variables or with
+/// `#![cors(Cors::new()...)]`.
+///
+/// `#![config(trailing_slash = "ignore" | "redirect", case_insensitive)]`
+/// relaxes path matching. Matching is exact by default. `#![config(openapi)]`
+/// serves the OpenAPI 3.1 document at `GET /openapi.json` (or
+/// `openapi = "/path"`). `docs "/docs"` adds a Swagger UI page for it
+/// (`docs "/docs" => redoc` for Redoc) and serves the document too.
 #[proc_macro]
 pub fn routes(input: TokenStream) -> TokenStream {
     schema::routes_impl(input)
@@ -187,7 +275,13 @@ pub fn routes(input: TokenStream) -> TokenStream {
 ///     pub age: Option<i32>,
 /// }
 /// ```
-#[proc_macro_derive(Type, attributes(field))]
+///
+/// Struct-level `#[validate(...)]` rules run after the field constraints:
+/// `#[validate(start < end)]` compares two fields (skipped while either is
+/// `None`) and `#[validate(custom = "check_fn")]` calls a
+/// `fn(&Self) -> Result<(), ValidationError>`. They are also supported on
+/// `Query` and `Form`.
+#[proc_macro_derive(Type, attributes(field, validate))]
 pub fn derive_type(input: TokenStream) -> TokenStream {
     derive::derive_type_impl(input)
 }
@@ -208,11 +302,56 @@ pub fn derive_type(input: TokenStream) -> TokenStream {
 ///     pub search: Option<String>,
 /// }
 /// ```
-#[proc_macro_derive(Query, attributes(field))]
+#[proc_macro_derive(Query, attributes(field, validate))]
 pub fn derive_query(input: TokenStream) -> TokenStream {
     derive::derive_query_impl(input)
 }
 
+/// Derive macro for form-urlencoded body types.
+///
+/// Generates `FromForm`, `Validate`, and `OpenApiSchema` implementations.
+/// `#[field(min, max)]` constraints are checked while parsing. Empty values
+/// leave optional and defaulted fields unset, and `bool` fields follow
+/// checkbox semantics (`on` when checked, `false` when absent).
+///
+/// ```ignore
+/// #[derive(Form)]
+/// pub struct LoginForm {
+///     #[field(min = 3, max = 50)]
+///     pub username: String,
+///
+///     #[field(min = 8)]
+///     pub password: String,
+///
+///     pub remember_me: bool,
+/// }
+/// ```
+#[proc_macro_derive(Form, attributes(field, validate))]
+pub fn derive_form(input: TokenStream) -> TokenStream {
+    derive::derive_form_impl(input)
+}
+
+/// Derive macro for request header types.
+///
+/// Generates `FromHeaders` and `OpenApiSchema` implementations. Field names
+/// map to headers with `_` replaced by `-` (`x_api_key` reads `x-api-key`),
+/// matched case-insensitively; use `#[field(rename = "...")]` for anything else.
+///
+/// ```ignore
+/// #[derive(Headers)]
+/// pub struct ApiHeaders {
+///     #[field(docs = "Client API key")]
+///     pub x_api_key: String,
+///
+///     #[field(rename = "X-Request-ID")]
+///     pub request_id: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(Headers, attributes(field))]
+pub fn derive_headers(input: TokenStream) -> TokenStream {
+    derive::derive_headers_impl(input)
+}
+
 /// Derive macro for path parameter types.
 ///
 /// Generates `FromPath` implementation.
@@ -228,3 +367,26 @@ pub fn derive_query(input: TokenStream) -> TokenStream {
 pub fn derive_path(input: TokenStream) -> TokenStream {
     derive::derive_path_impl(input)
 }
+
+/// Derive macro for struct-based settings.
+///
+/// Generates `mik_sdk::config::Config`, loading each field from the
+/// uppercased field name once per instance.
+///
+/// ```ignore
+/// #[derive(Config)]
+/// #[config(prefix = "APP_")]
+/// pub struct Settings {
+///     pub database_url: Secret,   // APP_DATABASE_URL
+///
+///     #[config(default = "50")]
+///     pub max_items: u32,         // APP_MAX_ITEMS
+///
+///     #[config(rename = "REGION")]
+///     pub region: Option<String>, // REGION
+/// }
+/// ```
+#[proc_macro_derive(Config, attributes(config))]
+pub fn derive_config(input: TokenStream) -> TokenStream {
+    config::derive_config_impl(input)
+}
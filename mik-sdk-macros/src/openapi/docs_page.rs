@@ -0,0 +1,133 @@
+//! HTML pages rendering the served OpenAPI document.
+
+use std::fmt::Write;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::schema::types::DocsUi;
+
+/// Generate a `&'static str` expression holding the docs page for the
+/// document served at `spec_route`, titled after the handler crate.
+pub fn generate_docs_page(ui: DocsUi, spec_route: &str) -> TokenStream2 {
+    let (head, tail) = match ui {
+        DocsUi::Swagger => (
+            SWAGGER_HEAD.to_string(),
+            SWAGGER_TAIL.replace("{spec}", &escape_js(spec_route)),
+        ),
+        DocsUi::Redoc => (
+            REDOC_HEAD.to_string(),
+            REDOC_TAIL.replace("{spec}", &escape_html(spec_route)),
+        ),
+    };
+    quote! { ::core::concat!(#head, ::core::env!("CARGO_PKG_NAME"), #tail) }
+}
+
+const SWAGGER_HEAD: &str = "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n\
+<meta charset=\"utf-8\">\n\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+<title>";
+
+/// Swagger UI assets are pinned to the v5.17.14 release, with subresource
+/// integrity hashes of its `dist` files. Bump the version and hashes
+/// together.
+const SWAGGER_TAIL: &str = " - API docs</title>\n\
+<link rel=\"stylesheet\" \
+href=\"https://cdn.jsdelivr.net/gh/swagger-api/swagger-ui@v5.17.14/dist/swagger-ui.css\" \
+integrity=\"sha384-wxLW6kwyHktdDGr6Pv1zgm/VGJh99lfUbzSn6HNHBENZlCN7W602k9VkGdxuFvPn\" \
+crossorigin=\"anonymous\">\n\
+</head>\n<body>\n\
+<div id=\"swagger-ui\"></div>\n\
+<script \
+src=\"https://cdn.jsdelivr.net/gh/swagger-api/swagger-ui@v5.17.14/dist/swagger-ui-bundle.js\" \
+integrity=\"sha384-wmyclcVGX/WhUkdkATwhaK1X1JtiNrr2EoYJ+diV3vj4v6OC5yCeSu+yW13SYJep\" \
+crossorigin=\"anonymous\"></script>\n\
+<script>\n\
+window.onload = () => { window.ui = SwaggerUIBundle({ url: {spec}, dom_id: \"#swagger-ui\" }); };\n\
+</script>\n\
+</body>\n</html>\n";
+
+const REDOC_HEAD: &str = SWAGGER_HEAD;
+
+/// Redoc is pinned to an exact release rather than `latest`.
+const REDOC_TAIL: &str = " - API docs</title>\n\
+<style>body { margin: 0; padding: 0; }</style>\n\
+</head>\n<body>\n\
+<redoc spec-url=\"{spec}\"></redoc>\n\
+<script src=\"https://cdn.jsdelivr.net/npm/redoc@2.1.5/bundles/redoc.standalone.js\" \
+crossorigin=\"anonymous\"></script>\n\
+</body>\n</html>\n";
+
+/// Quote `value` as a JavaScript string literal safe inside `<script>`.
+fn escape_js(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '<' => out.push_str("\\u003c"),
+            '>' => out.push_str("\\u003e"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            },
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Escape `value` for a double-quoted HTML attribute.
+fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_js() {
+        assert_eq!(escape_js("/openapi.json"), r#""/openapi.json""#);
+        assert_eq!(escape_js(r#"/a"</script>"#), r#""/a\"\u003c/script\u003e""#);
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(escape_html(r#"/a"&<b>"#), "/a&quot;&amp;&lt;b&gt;");
+    }
+
+    #[test]
+    fn test_pages_reference_spec() {
+        let swagger = generate_docs_page(DocsUi::Swagger, "/spec.json").to_string();
+        assert!(swagger.contains("SwaggerUIBundle"));
+        assert!(swagger.contains(r#"url: \"/spec.json\""#));
+
+        let redoc = generate_docs_page(DocsUi::Redoc, "/spec.json").to_string();
+        assert!(redoc.contains(r#"spec-url=\"/spec.json\""#));
+    }
+
+    #[test]
+    fn test_assets_are_pinned() {
+        for page in [SWAGGER_TAIL, REDOC_TAIL] {
+            assert!(!page.contains("latest"));
+            assert!(!page.contains("@5/"));
+        }
+        // Every Swagger UI asset carries an integrity hash
+        assert_eq!(
+            SWAGGER_TAIL.matches("https://").count(),
+            SWAGGER_TAIL.matches("integrity=\"sha384-").count()
+        );
+    }
+}
@@ -3,12 +3,16 @@
 //! This module consolidates all OpenAPI-related functionality:
 //! - `utoipa`: Low-level schema builders using utoipa types
 //! - `routes`: OpenAPI specification generation for routes
+//! - `docs_page`: Swagger UI and Redoc pages for the served specification
 
+pub mod docs_page;
 pub mod routes;
 pub mod utoipa;
 
 // Re-export commonly used items
+pub use docs_page::generate_docs_page;
 pub use routes::generate_openapi_json;
 pub use utoipa::{
-    array_schema, make_nullable_json, ref_or_schema_to_json, rust_type_to_schema, schema_to_json,
+    array_schema, make_nullable_json, map_schema_json, ref_or_schema_to_json, rust_type_to_schema,
+    schema_to_json,
 };
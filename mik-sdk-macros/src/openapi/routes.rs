@@ -3,13 +3,15 @@
 //! Generates OpenAPI 3.0 specifications with full type schemas.
 //!
 //! Strategy: Everything is computed once at startup via LazyLock.
-//! Path/query parameters come from trait methods on the input types,
+//! Path/query/header parameters come from trait methods on the input types,
 //! allowing full type information to be included.
 
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::quote;
 
 use super::utoipa::problem_details_json;
+use crate::derive::escape_json_string;
+use crate::schema::codegen::split_param;
 use crate::schema::types::{InputSource, RouteDef, RoutesDef};
 
 // =============================================================================
@@ -44,7 +46,11 @@ const fn status_code_description(code: u16) -> &'static str {
 
 /// Generate code that builds an OpenAPI method entry at runtime.
 #[allow(clippy::too_many_lines)]
-fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> TokenStream2 {
+fn generate_method_entry_code(
+    route: &RouteDef,
+    default_tag: Option<&str>,
+    envelope: bool,
+) -> TokenStream2 {
     let method_name = route.method.as_str();
     let tag = route.effective_tag(default_tag);
     let handler_name = route.handler.to_string();
@@ -66,12 +72,19 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
         __parts.push(::std::format!("\"tags\":[\"{}\"]", #tag));
     });
 
-    // Add summary if present
+    // Add summary and description from the route's doc comment
     if let Some(ref summary) = route.summary {
+        let summary = escape_json_string(summary);
         parts.push(quote! {
             __parts.push(::std::format!("\"summary\":\"{}\"", #summary));
         });
     }
+    if let Some(ref description) = route.description {
+        let description = escape_json_string(description);
+        parts.push(quote! {
+            __parts.push(::std::format!("\"description\":\"{}\"", #description));
+        });
+    }
 
     // Add deprecated if true
     if is_deprecated {
@@ -93,54 +106,87 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
                 #type_name
             ));
         });
-    }
-
-    // Parameters (path + query) - collected from trait methods
-    let path_input = route
-        .inputs
-        .iter()
-        .find(|i| matches!(i.source, InputSource::Path));
-    let query_input = route
+    } else if let Some(form_input) = route
         .inputs
         .iter()
-        .find(|i| matches!(i.source, InputSource::Query));
-
-    if path_input.is_some() || query_input.is_some() {
-        let path_params_code = path_input.map_or_else(
-            || quote! { let __path_params: &str = "[]"; },
-            |input| {
-                let type_name = &input.type_name;
-                quote! {
-                    let __path_params: &str = <super::#type_name as mik_sdk::typed::OpenApiSchema>::openapi_path_params();
-                }
-            },
+        .find(|i| matches!(i.source, InputSource::Form))
+    {
+        let type_name = form_input.type_name.to_string();
+        parts.push(quote! {
+            __parts.push(::std::format!(
+                "\"requestBody\":{{\"required\":true,\"content\":{{\"application/x-www-form-urlencoded\":{{\"schema\":{{\"$ref\":\"#/components/schemas/{}\"}}}}}}}}",
+                #type_name
+            ));
+        });
+    } else if route.stream
+        || !route.consumes.is_empty()
+        || route.inputs.iter().any(|i| i.source == InputSource::Proto)
+    {
+        // Streamed, protobuf and undeclared bodies are opaque to the schema
+        let content = if route.consumes.is_empty() {
+            let mime = if route.stream {
+                "application/octet-stream"
+            } else {
+                "application/x-protobuf"
+            };
+            vec![mime.to_string()]
+        } else {
+            route.consumes.clone()
+        };
+        let body = format!(
+            "\"requestBody\":{{\"required\":true,\"content\":{{{}}}}}",
+            content
+                .iter()
+                .map(|mime| format!(
+                    "\"{mime}\":{{\"schema\":{{\"type\":\"string\",\"format\":\"binary\"}}}}"
+                ))
+                .collect::<Vec<_>>()
+                .join(",")
         );
+        parts.push(quote! {
+            __parts.push(#body.to_string());
+        });
+    }
 
-        let query_params_code = query_input.map_or_else(
-            || quote! { let __query_params: &str = "[]"; },
-            |input| {
-                let type_name = &input.type_name;
-                quote! {
-                    let __query_params: &str = <super::#type_name as mik_sdk::typed::OpenApiSchema>::openapi_query_params();
-                }
-            },
-        );
+    // Parameters (path + query + headers) - collected from trait methods
+    let param_sources = [
+        (InputSource::Path, quote! { openapi_path_params }),
+        (InputSource::Query, quote! { openapi_query_params }),
+        (InputSource::Headers, quote! { openapi_header_params }),
+    ];
+    let mut param_lists: Vec<TokenStream2> = param_sources
+        .iter()
+        .filter_map(|(source, method)| {
+            let input = route.inputs.iter().find(|i| i.source == *source)?;
+            let type_name = &input.type_name;
+            Some(quote! {
+                <super::#type_name as mik_sdk::typed::OpenApiSchema>::#method()
+            })
+        })
+        .collect();
+
+    // Constrained path parameters ({id:uuid}) take their schema from the constraint
+    let path_params_prelude = generate_constrained_path_params(route);
+    if !path_params_prelude.is_empty() {
+        if route.inputs.iter().any(|i| i.source == InputSource::Path) {
+            param_lists[0] = quote! { __mik_path_params.as_str() };
+        } else {
+            param_lists.insert(0, quote! { __mik_path_params.as_str() });
+        }
+    }
 
+    if !param_lists.is_empty() {
         parts.push(quote! {
             {
-                #path_params_code
-                #query_params_code
-                // Merge path and query params (strip brackets and combine)
-                let __path_inner = __path_params.trim_start_matches('[').trim_end_matches(']');
-                let __query_inner = __query_params.trim_start_matches('[').trim_end_matches(']');
-                let __all_params = match (__path_inner.is_empty(), __query_inner.is_empty()) {
-                    (true, true) => ::std::string::String::new(),
-                    (false, true) => __path_inner.to_string(),
-                    (true, false) => __query_inner.to_string(),
-                    (false, false) => ::std::format!("{},{}", __path_inner, __query_inner),
-                };
+                #path_params_prelude
+                // Merge parameter arrays (strip brackets and combine)
+                let __all_params: ::std::vec::Vec<&str> = [#(#param_lists),*]
+                    .iter()
+                    .map(|p| p.trim_start_matches('[').trim_end_matches(']'))
+                    .filter(|p| !p.is_empty())
+                    .collect();
                 if !__all_params.is_empty() {
-                    __parts.push(::std::format!("\"parameters\":[{}]", __all_params));
+                    __parts.push(::std::format!("\"parameters\":[{}]", __all_params.join(",")));
                 }
             }
         });
@@ -151,13 +197,21 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
     let status_description = status_code_description(status_code);
 
     if let Some(ref output_type) = route.output_type {
-        let output_str = output_type.to_string();
+        let reference = format!("{{\"$ref\":\"#/components/schemas/{output_type}\"}}");
+        // #![config(envelope)] wraps successful JSON bodies
+        let schema = if envelope {
+            format!(
+                "{{\"type\":\"object\",\"required\":[\"data\",\"meta\",\"trace_id\"],\"properties\":{{\"data\":{reference},\"meta\":{{\"type\":\"object\"}},\"trace_id\":{{\"type\":\"string\",\"nullable\":true}}}}}}"
+            )
+        } else {
+            reference
+        };
         parts.push(quote! {
             __parts.push(::std::format!(
-                "\"responses\":{{\"{}\":{{\"description\":\"{}\",\"content\":{{\"application/json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/{}\"}}}}}}}},\"4XX\":{{\"description\":\"Client Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}},\"5XX\":{{\"description\":\"Server Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}}}}",
+                "\"responses\":{{\"{}\":{{\"description\":\"{}\",\"content\":{{\"application/json\":{{\"schema\":{}}}}}}},\"4XX\":{{\"description\":\"Client Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}},\"5XX\":{{\"description\":\"Server Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}}}}",
                 #status_code,
                 #status_description,
-                #output_str
+                #schema
             ));
         });
     } else {
@@ -179,6 +233,72 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
     }
 }
 
+/// Generate code binding `__mik_path_params` to the route's path parameter
+/// list with constraint schemas applied, or nothing if no parameter is
+/// constrained.
+///
+/// Parameters documented by the `Path` input keep their name and
+/// description; parameters only named in the pattern are added.
+fn generate_constrained_path_params(route: &RouteDef) -> TokenStream2 {
+    let pattern = route.patterns.first().map_or("/", String::as_str);
+    let constraints: Vec<(&str, &str)> = pattern
+        .split('/')
+        .filter_map(split_param)
+        .filter_map(|(name, constraint)| Some((name, constraint?.schema_json())))
+        .collect();
+    if constraints.is_empty() {
+        return quote! {};
+    }
+
+    let names = constraints.iter().map(|(name, _)| name);
+    let schemas = constraints.iter().map(|(_, schema)| schema);
+    let derived = route
+        .inputs
+        .iter()
+        .find(|i| i.source == InputSource::Path)
+        .map_or_else(
+            || quote! { "[]" },
+            |input| {
+                let type_name = &input.type_name;
+                quote! { <super::#type_name as mik_sdk::typed::OpenApiSchema>::openapi_path_params() }
+            },
+        );
+
+    quote! {
+        let __mik_path_params = {
+            let __mik_constraints: &[(&str, &str)] = &[#((#names, #schemas)),*];
+            let __mik_schema = |__mik_s: &str| {
+                mik_sdk::json::try_parse_full(__mik_s.as_bytes()).unwrap_or_else(mik_sdk::json::obj)
+            };
+            let __mik_derived = mik_sdk::json::try_parse_full(#derived.as_bytes())
+                .unwrap_or_else(mik_sdk::json::arr);
+            let mut __mik_seen: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+            let mut __mik_list = mik_sdk::json::arr();
+            for __mik_i in 0..__mik_derived.len().unwrap_or(0) {
+                let mut __mik_param = __mik_derived.at(__mik_i);
+                let __mik_name = __mik_param.get("name").str_or("");
+                if let Some((_, __mik_s)) = __mik_constraints.iter().find(|(__mik_n, _)| *__mik_n == __mik_name) {
+                    __mik_param = __mik_param.set("schema", __mik_schema(__mik_s));
+                    __mik_seen.push(__mik_name);
+                }
+                __mik_list = __mik_list.push(__mik_param);
+            }
+            for (__mik_n, __mik_s) in __mik_constraints {
+                if !__mik_seen.iter().any(|__mik_name| __mik_name == __mik_n) {
+                    __mik_list = __mik_list.push(
+                        mik_sdk::json::obj()
+                            .set("name", mik_sdk::json::str(__mik_n))
+                            .set("in", mik_sdk::json::str("path"))
+                            .set("required", mik_sdk::json::bool(true))
+                            .set("schema", __mik_schema(__mik_s)),
+                    );
+                }
+            }
+            __mik_list.to_string()
+        };
+    }
+}
+
 /// Collect unique type names from routes for schema generation.
 fn collect_type_names(routes: &[RouteDef]) -> Vec<Ident> {
     use std::collections::HashSet;
@@ -187,7 +307,12 @@ fn collect_type_names(routes: &[RouteDef]) -> Vec<Ident> {
     let mut seen: HashSet<String> = HashSet::new();
 
     for route in routes {
-        for input in &route.inputs {
+        // Custom extractors and protobuf messages are opaque to the schema
+        for input in route
+            .inputs
+            .iter()
+            .filter(|i| !matches!(i.source, InputSource::Extract | InputSource::Proto))
+        {
             let name = input.type_name.to_string();
             if seen.insert(name) {
                 type_names.push(input.type_name.clone());
@@ -204,18 +329,34 @@ fn collect_type_names(routes: &[RouteDef]) -> Vec<Ident> {
     type_names
 }
 
+/// OpenAPI path key for a pattern: `/users/{id:uuid}` becomes `/users/{id}`.
+fn openapi_path(pattern: &str) -> String {
+    pattern
+        .split('/')
+        .map(|segment| match split_param(segment) {
+            Some((name, Some(_))) => format!("{{{name}}}"),
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// Generate code that builds paths JSON at runtime.
-fn generate_paths_code(routes: &[RouteDef], default_tag: Option<&str>) -> TokenStream2 {
+fn generate_paths_code(
+    routes: &[RouteDef],
+    default_tag: Option<&str>,
+    envelope: bool,
+) -> TokenStream2 {
     use std::collections::HashMap;
 
     // Group routes by path
     let mut paths: HashMap<String, Vec<&RouteDef>> = HashMap::new();
     for route in routes {
-        let path = route
+        let pattern = route
             .patterns
             .first()
             .map_or("/", std::string::String::as_str);
-        paths.entry(path.to_string()).or_default().push(route);
+        paths.entry(openapi_path(pattern)).or_default().push(route);
     }
 
     // Generate code for each path
@@ -224,7 +365,7 @@ fn generate_paths_code(routes: &[RouteDef], default_tag: Option<&str>) -> TokenS
         .map(|(path, methods)| {
             let method_codes: Vec<TokenStream2> = methods
                 .iter()
-                .map(|r| generate_method_entry_code(r, default_tag))
+                .map(|r| generate_method_entry_code(r, default_tag, envelope))
                 .collect();
             quote! {
                 {
@@ -253,7 +394,7 @@ fn generate_paths_code(routes: &[RouteDef], default_tag: Option<&str>) -> TokenS
 /// Path/query parameters come from trait methods, allowing full type information.
 pub fn generate_openapi_json(defs: &RoutesDef) -> TokenStream2 {
     let default_tag = defs.default_tag.as_deref();
-    let paths_code = generate_paths_code(&defs.routes, default_tag);
+    let paths_code = generate_paths_code(&defs.routes, default_tag, defs.config.envelope);
     let type_names = collect_type_names(&defs.routes);
 
     // Get RFC 7807 ProblemDetails schema JSON at compile time
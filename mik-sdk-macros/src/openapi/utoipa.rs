@@ -62,6 +62,12 @@ pub fn make_nullable_json(schema_json: &str) -> String {
     }
 }
 
+/// Build a map schema (for `HashMap<String, T>` and `BTreeMap<String, T>`).
+/// Returns JSON string with the value schema as `additionalProperties`.
+pub fn map_schema_json(value_schema_json: &str) -> String {
+    format!("{{\"type\":\"object\",\"additionalProperties\":{value_schema_json}}}")
+}
+
 /// Build an array schema (for `Vec<T>`).
 pub fn array_schema(items: RefOr<Schema>) -> Schema {
     ArrayBuilder::new().items(items).build().into()
@@ -773,7 +773,14 @@ impl Parse for RedirectInput {
 
         // Check if first token is a number (status code)
         if input.peek(LitInt) {
-            status = Some(input.parse()?);
+            let lit: LitInt = input.parse()?;
+            if !matches!(lit.base10_parse::<u16>(), Ok(300..=399)) {
+                return Err(syn::Error::new_spanned(
+                    &lit,
+                    "redirect! status must be a 3xx code, e.g. 301, 302, 303, 307 or 308",
+                ));
+            }
+            status = Some(lit);
             input.parse::<Token![,]>()?;
         }
 
@@ -1108,3 +1115,178 @@ pub fn accepted_impl(input: TokenStream) -> TokenStream {
 
     TokenStream::from(tokens)
 }
+
+/// Return a response whose body is produced chunk by chunk.
+///
+/// The body must be a `mik_sdk::stream::ChunkedBody`. Defaults to 200 OK
+/// with `application/octet-stream`.
+///
+/// # Examples
+///
+/// ```ignore
+/// // Binary stream
+/// stream!(body)
+///
+/// // CSV export with a download header
+/// stream!(ChunkedBody::from_iter(rows), content_type: "text/csv", headers: {
+///     "Content-Disposition": "attachment; filename=\"users.csv\""
+/// })
+///
+/// // Custom status
+/// stream!(body, status: 206)
+/// ```
+struct StreamInput {
+    body: Expr,
+    status: Option<LitInt>,
+    content_type: Option<Expr>,
+    headers: Option<HeadersBlock>,
+}
+
+/// Valid options for stream! macro.
+const VALID_STREAM_OPTIONS: &[&str] = &["status", "content_type", "headers"];
+
+impl Parse for StreamInput {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let body: Expr = input.parse()?;
+        let mut status = None;
+        let mut content_type = None;
+        let mut headers = None;
+
+        while input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            match ident.to_string().as_str() {
+                "status" => status = Some(input.parse()?),
+                "content_type" => content_type = Some(input.parse()?),
+                "headers" => headers = Some(input.parse()?),
+                other => {
+                    let suggestion = did_you_mean(other, VALID_STREAM_OPTIONS);
+                    return Err(syn::Error::new_spanned(
+                        &ident,
+                        format!(
+                            "Unknown stream! option '{other}'.{suggestion}\n\
+                             \n\
+                             Valid options: status, content_type, headers"
+                        ),
+                    ));
+                },
+            }
+        }
+
+        Ok(Self {
+            body,
+            status,
+            content_type,
+            headers,
+        })
+    }
+}
+
+pub fn stream_impl(input: TokenStream) -> TokenStream {
+    let StreamInput {
+        body,
+        status,
+        content_type,
+        headers,
+    } = parse_macro_input!(input as StreamInput);
+
+    let status = status.map_or_else(|| quote! { 200 }, |s| quote! { #s });
+    let content_type = content_type.map_or_else(
+        || quote! { "application/octet-stream" },
+        |ct| quote! { #ct },
+    );
+
+    let base_headers = vec![quote! {
+        (
+            ::mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+            (#content_type).to_string()
+        )
+    }];
+    let headers_code = generate_headers_code(base_headers, headers.as_ref());
+
+    let tokens = quote! {
+        {
+            ::mik_sdk::stream::__register(#body);
+            handler::Response {
+                status: #status,
+                headers: #headers_code,
+                body: None,
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
+
+/// Return a Server-Sent Events response from a `mik_sdk::sse::EventStream`.
+///
+/// Sets `Content-Type: text/event-stream` and `Cache-Control: no-cache`.
+/// Each event is flushed to the client as soon as it is produced.
+///
+/// # Examples
+///
+/// ```ignore
+/// sse!(events)
+///
+/// // With custom headers
+/// sse!(events, headers: {
+///     "X-Accel-Buffering": "no"
+/// })
+/// ```
+struct SseInput {
+    events: Expr,
+    headers: Option<HeadersBlock>,
+}
+
+impl Parse for SseInput {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let events: Expr = input.parse()?;
+
+        let headers = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let ident: syn::Ident = input.parse()?;
+            if ident == "headers" {
+                input.parse::<Token![:]>()?;
+                Some(input.parse()?)
+            } else {
+                return Err(syn::Error::new_spanned(ident, "Expected 'headers' keyword"));
+            }
+        } else {
+            None
+        };
+
+        Ok(Self { events, headers })
+    }
+}
+
+pub fn sse_impl(input: TokenStream) -> TokenStream {
+    let SseInput { events, headers } = parse_macro_input!(input as SseInput);
+
+    let base_headers = vec![
+        quote! {
+            (
+                ::mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+                ::mik_sdk::constants::MIME_EVENT_STREAM.to_string()
+            )
+        },
+        quote! { ("cache-control".to_string(), "no-cache".to_string()) },
+    ];
+    let headers_code = generate_headers_code(base_headers, headers.as_ref());
+
+    let tokens = quote! {
+        {
+            ::mik_sdk::stream::__register(::mik_sdk::sse::EventStream::into_body(#events));
+            handler::Response {
+                status: 200,
+                headers: #headers_code,
+                body: None,
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
@@ -1,9 +1,12 @@
 //! Route matching and handler wrapper code generation.
 
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 
-use super::types::{InputSource, RouteDef, TypedInput};
+use super::trie::generate_candidate_loop;
+use super::types::{
+    GuardKind, InputSource, ParamConstraint, RouteDef, RouterConfig, TrailingSlash, TypedInput,
+};
 
 // =============================================================================
 // CODE GENERATION - ROUTE MATCHING
@@ -22,6 +25,10 @@ pub fn extract_param_names(pattern: &str) -> Vec<String> {
                 }
                 name.push(c);
             }
+            // Drop the constraint from `{name:constraint}`
+            if let Some((param, _)) = name.split_once(':') {
+                name.truncate(param.len());
+            }
             if !name.is_empty() {
                 params.push(name);
             }
@@ -31,13 +38,82 @@ pub fn extract_param_names(pattern: &str) -> Vec<String> {
     params
 }
 
-pub fn generate_pattern_matcher(pattern: &str) -> TokenStream2 {
+/// Split a `{name}` or `{name:constraint}` pattern segment.
+pub fn split_param(segment: &str) -> Option<(&str, Option<ParamConstraint>)> {
+    let param = segment.strip_prefix('{')?.strip_suffix('}')?;
+    Some(match param.split_once(':') {
+        Some((name, constraint)) => (name, ParamConstraint::parse(constraint)),
+        None => (param, None),
+    })
+}
+
+/// Generate a check that `__mik_decoded_param` satisfies a constraint.
+fn generate_constraint_check(constraint: ParamConstraint) -> TokenStream2 {
+    match constraint {
+        ParamConstraint::Int => quote! { __mik_decoded_param.parse::<i64>().is_ok() },
+        ParamConstraint::Uint => quote! { __mik_decoded_param.parse::<u64>().is_ok() },
+        ParamConstraint::Uuid => quote! {
+            __mik_decoded_param.len() == 36
+                && __mik_decoded_param.bytes().enumerate().all(|(__mik_i, __mik_b)| {
+                    if matches!(__mik_i, 8 | 13 | 18 | 23) {
+                        __mik_b == b'-'
+                    } else {
+                        __mik_b.is_ascii_hexdigit()
+                    }
+                })
+        },
+        ParamConstraint::Alpha => quote! {
+            !__mik_decoded_param.is_empty()
+                && __mik_decoded_param.bytes().all(|__mik_b| __mik_b.is_ascii_alphabetic())
+        },
+        ParamConstraint::Alnum => quote! {
+            !__mik_decoded_param.is_empty()
+                && __mik_decoded_param.bytes().all(|__mik_b| __mik_b.is_ascii_alphanumeric())
+        },
+    }
+}
+
+/// Generate an expression matching `__mik_path` against a route pattern.
+///
+/// Evaluates to the extracted path parameters on a match.
+pub fn generate_pattern_matcher(pattern: &str, config: &RouterConfig) -> TokenStream2 {
     let params = extract_param_names(pattern);
 
+    // With trailing_slash = "ignore", both sides are compared without it
+    let (pattern, normalize) = if config.trailing_slash == TrailingSlash::Ignore {
+        let trimmed = if pattern.len() > 1 {
+            pattern.strip_suffix('/').unwrap_or(pattern)
+        } else {
+            pattern
+        };
+        (
+            trimmed,
+            quote! {
+                let __mik_path: &str = if __mik_path.len() > 1 {
+                    __mik_path.strip_suffix('/').unwrap_or(__mik_path)
+                } else {
+                    __mik_path
+                };
+            },
+        )
+    } else {
+        (pattern, quote! {})
+    };
+
+    let eq = |lhs: TokenStream2, rhs: &str| {
+        if config.case_insensitive {
+            quote! { #lhs.eq_ignore_ascii_case(#rhs) }
+        } else {
+            quote! { #lhs == #rhs }
+        }
+    };
+
     if params.is_empty() {
+        let check = eq(quote! { __mik_path }, pattern);
         quote! {
             (|| -> Option<::std::collections::HashMap<String, String>> {
-                if __mik_path == #pattern {
+                #normalize
+                if #check {
                     Some(::std::collections::HashMap::new())
                 } else {
                     None
@@ -52,9 +128,16 @@ pub fn generate_pattern_matcher(pattern: &str) -> TokenStream2 {
         let mut extractions = Vec::new();
 
         for (i, segment) in segments.iter().enumerate() {
-            if segment.starts_with('{') && segment.ends_with('}') {
+            if let Some((param_name, constraint)) = split_param(segment) {
                 // Parameter segment - URL decode the value
-                let param_name = &segment[1..segment.len() - 1];
+                let constraint_check = constraint.map(|c| {
+                    let check = generate_constraint_check(c);
+                    quote! {
+                        if !(#check) {
+                            return None;
+                        }
+                    }
+                });
                 extractions.push(quote! {
                     let __mik_raw_param = __mik_segments[#i];
                     // URL decode the path parameter. If decoding fails (malformed percent-encoding),
@@ -62,12 +145,11 @@ pub fn generate_pattern_matcher(pattern: &str) -> TokenStream2 {
                     // crash the handler, and the raw value will either match the route or not.
                     let __mik_decoded_param = mik_sdk::url_decode(__mik_raw_param)
                         .unwrap_or_else(|_| __mik_raw_param.to_string());
+                    #constraint_check
                     __mik_params.insert(#param_name.to_string(), __mik_decoded_param);
                 });
             } else if !segment.is_empty() {
-                checks.push(quote! {
-                    __mik_segments[#i] == #segment
-                });
+                checks.push(eq(quote! { __mik_segments[#i] }, segment));
             } else if i > 0 {
                 checks.push(quote! {
                     __mik_segments[#i].is_empty()
@@ -83,6 +165,7 @@ pub fn generate_pattern_matcher(pattern: &str) -> TokenStream2 {
 
         quote! {
             (|| -> Option<::std::collections::HashMap<String, String>> {
+                #normalize
                 let __mik_segments: Vec<&str> = __mik_path.split('/').collect();
                 if __mik_segments.len() == #segment_count && #all_checks {
                     let mut __mik_params = ::std::collections::HashMap::new();
@@ -96,6 +179,57 @@ pub fn generate_pattern_matcher(pattern: &str) -> TokenStream2 {
     }
 }
 
+/// Generate the 308 redirect for `trailing_slash = "redirect"`.
+///
+/// Runs after no route matched: if the path with its trailing slash added
+/// or removed matches a route for the same method, redirect there.
+pub fn generate_trailing_slash_redirect(
+    routes: &[RouteDef],
+    config: &RouterConfig,
+) -> TokenStream2 {
+    if config.trailing_slash != TrailingSlash::Redirect {
+        return quote! {};
+    }
+
+    let checks: Vec<TokenStream2> = routes
+        .iter()
+        .map(|route| {
+            let method_check = route.method.to_method_check();
+            let matchers = route
+                .patterns
+                .iter()
+                .map(|pattern| generate_pattern_matcher(pattern, config));
+            quote! {
+                (__mik_method == #method_check && (#(#matchers.is_some())||*))
+            }
+        })
+        .collect();
+
+    quote! {
+        if __mik_path.len() > 1 {
+            let __mik_target = match __mik_path.strip_suffix('/') {
+                Some(__mik_trimmed) => __mik_trimmed.to_string(),
+                None => ::std::format!("{}/", __mik_path),
+            };
+            let __mik_redirect = {
+                let __mik_path: &str = &__mik_target;
+                #(#checks)||*
+            };
+            if __mik_redirect {
+                let __mik_location = match __mik_raw.path.split_once('?') {
+                    Some((_, __mik_query)) => ::std::format!("{}?{}", __mik_target, __mik_query),
+                    None => __mik_target,
+                };
+                return handler::Response {
+                    status: 308,
+                    headers: vec![(mik_sdk::constants::HEADER_LOCATION.to_string(), __mik_location)],
+                    body: None,
+                };
+            }
+        }
+    }
+}
+
 // =============================================================================
 // CODE GENERATION - HANDLER WRAPPERS
 // =============================================================================
@@ -114,91 +248,34 @@ pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<
                 parsing.push(quote! {
                     let #var_name = match <#type_name as mik_sdk::typed::FromPath>::from_params(&__mik_params) {
                         Ok(v) => v,
-                        Err(e) => {
-                            return handler::Response {
-                                status: 400,
-                                headers: vec![
-                                    (
-                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                    )
-                                ],
-                                body: Some(mik_sdk::json::obj()
-                                    .set("type", mik_sdk::json::str("about:blank"))
-                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                    .set("status", mik_sdk::json::int(400))
-                                    .set("detail", mik_sdk::json::str(&e.to_string()))
-                                    .to_bytes()),
-                            };
-                        }
+                        Err(e) => return Handler::__mik_error(400, &e.to_string(), &__mik_req),
                     };
                 });
                 args.push(quote! { #var_name });
             },
             InputSource::Body => {
                 parsing.push(quote! {
+                    // Only JSON is parsed; an absent Content-Type is taken as JSON
+                    if !__mik_req.__content_type_allowed(&[mik_sdk::constants::MIME_JSON], false) {
+                        return Handler::__mik_error(415, "Expected a JSON body (Content-Type: application/json)", &__mik_req);
+                    }
                     let #var_name = match __mik_req.body() {
                         Some(bytes) => {
                             match mik_sdk::json::try_parse(bytes) {
                                 Some(json) => {
                                     match <#type_name as mik_sdk::typed::FromJson>::from_json(&json) {
                                         Ok(v) => v,
-                                        Err(e) => {
-                                            return handler::Response {
-                                                status: 400,
-                                                headers: vec![
-                                                    (
-                                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                                    )
-                                                ],
-                                                body: Some(mik_sdk::json::obj()
-                                                    .set("type", mik_sdk::json::str("about:blank"))
-                                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                                    .set("status", mik_sdk::json::int(400))
-                                                    .set("detail", mik_sdk::json::str(&e.to_string()))
-                                                    .to_bytes()),
-                                            };
-                                        }
+                                        Err(e) => return Handler::__mik_error(400, &e.to_string(), &__mik_req),
                                     }
                                 }
-                                None => {
-                                    return handler::Response {
-                                        status: 400,
-                                        headers: vec![
-                                            (
-                                                mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                                mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                            )
-                                        ],
-                                        body: Some(mik_sdk::json::obj()
-                                            .set("type", mik_sdk::json::str("about:blank"))
-                                            .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                            .set("status", mik_sdk::json::int(400))
-                                            .set("detail", mik_sdk::json::str("Invalid JSON body"))
-                                            .to_bytes()),
-                                    };
-                                }
+                                None => return Handler::__mik_error(400, "Invalid JSON body", &__mik_req),
                             }
                         }
-                        None => {
-                            return handler::Response {
-                                status: 400,
-                                headers: vec![
-                                    (
-                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                    )
-                                ],
-                                body: Some(mik_sdk::json::obj()
-                                    .set("type", mik_sdk::json::str("about:blank"))
-                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                    .set("status", mik_sdk::json::int(400))
-                                    .set("detail", mik_sdk::json::str("Request body required"))
-                                    .to_bytes()),
-                            };
-                        }
+                        None => return Handler::__mik_error(400, "Request body required", &__mik_req),
                     };
+                    if let Err(e) = mik_sdk::typed::Validate::validate_all(&#var_name) {
+                        return Handler::__mik_validation_error(&e, &__mik_req);
+                    }
                 });
                 args.push(quote! { #var_name });
             },
@@ -224,23 +301,54 @@ pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<
                         .unwrap_or_default();
                     let #var_name = match <#type_name as mik_sdk::typed::FromQuery>::from_query(&__mik_query_params) {
                         Ok(v) => v,
-                        Err(e) => {
-                            return handler::Response {
-                                status: 400,
-                                headers: vec![
-                                    (
-                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                    )
-                                ],
-                                body: Some(mik_sdk::json::obj()
-                                    .set("type", mik_sdk::json::str("about:blank"))
-                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                    .set("status", mik_sdk::json::int(400))
-                                    .set("detail", mik_sdk::json::str(&e.to_string()))
-                                    .to_bytes()),
-                            };
-                        }
+                        Err(e) => return Handler::__mik_error(400, &e.to_string(), &__mik_req),
+                    };
+                    if let Err(e) = mik_sdk::typed::Validate::validate_all(&#var_name) {
+                        return Handler::__mik_validation_error(&e, &__mik_req);
+                    }
+                });
+                args.push(quote! { #var_name });
+            },
+            InputSource::Form => {
+                parsing.push(quote! {
+                    if !__mik_req.__content_type_allowed(&[mik_sdk::constants::MIME_FORM_URLENCODED], false) {
+                        return Handler::__mik_error(415, "Expected a form body (Content-Type: application/x-www-form-urlencoded)", &__mik_req);
+                    }
+                    let __mik_form_params = __mik_req.form_params();
+                    let #var_name = match <#type_name as mik_sdk::typed::FromForm>::from_form(&__mik_form_params) {
+                        Ok(v) => v,
+                        Err(e) => return Handler::__mik_error(400, &e.to_string(), &__mik_req),
+                    };
+                });
+                args.push(quote! { #var_name });
+            },
+            InputSource::Proto => {
+                parsing.push(quote! {
+                    if !__mik_req.__content_type_allowed(&[mik_sdk::constants::MIME_PROTOBUF], false) {
+                        return Handler::__mik_error(415, "Expected a protobuf body (Content-Type: application/x-protobuf)", &__mik_req);
+                    }
+                    let #var_name = match mik_sdk::proto::from_request::<#type_name>(&__mik_req) {
+                        Ok(v) => v,
+                        Err(e) => return Handler::__mik_error(400, &e.to_string(), &__mik_req),
+                    };
+                });
+                args.push(quote! { #var_name });
+            },
+            InputSource::Headers => {
+                parsing.push(quote! {
+                    let #var_name = match <#type_name as mik_sdk::typed::FromHeaders>::from_headers(__mik_req.headers()) {
+                        Ok(v) => v,
+                        Err(e) => return Handler::__mik_error(400, &e.to_string(), &__mik_req),
+                    };
+                });
+                args.push(quote! { #var_name });
+            },
+            InputSource::Extract => {
+                parsing.push(quote! {
+                    // Extractors reject with their own response
+                    let #var_name = match <#type_name as mik_sdk::typed::FromRequest>::from_request::<handler::Response>(&__mik_req) {
+                        Ok(v) => v,
+                        Err(__mik_rejection) => return __mik_rejection,
                     };
                 });
                 args.push(quote! { #var_name });
@@ -251,7 +359,18 @@ pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<
     (parsing, args)
 }
 
-pub fn generate_route_block(route: &RouteDef) -> TokenStream2 {
+/// Generate the dispatch block for one route.
+///
+/// `streaming` selects the entry point the block is emitted into: `true` for
+/// `handle_streaming`, where `__mik_stream` holds the live body stream, and
+/// `false` for `handle`, where `#[stream]` routes get a stream over the
+/// buffered body instead.
+#[allow(clippy::too_many_lines)] // Hooks, input parsing and streaming variants
+pub fn generate_route_block(
+    route: &RouteDef,
+    config: &RouterConfig,
+    streaming: bool,
+) -> TokenStream2 {
     let handler = &route.handler;
     let method_check = route.method.to_method_check();
 
@@ -259,7 +378,7 @@ pub fn generate_route_block(route: &RouteDef) -> TokenStream2 {
         .patterns
         .iter()
         .map(|pattern_str| {
-            let matcher = generate_pattern_matcher(pattern_str);
+            let matcher = generate_pattern_matcher(pattern_str, config);
             quote! {
                 if let Some(__mik_params) = #matcher {
                     return Some(__mik_params);
@@ -268,14 +387,110 @@ pub fn generate_route_block(route: &RouteDef) -> TokenStream2 {
         })
         .collect();
 
-    let (input_parsing, input_args) = generate_input_parsing(&route.inputs);
+    let (mut input_parsing, mut input_args) = generate_input_parsing(&route.inputs);
+
+    // Declared content types are checked before any input is parsed
+    if !route.consumes.is_empty() {
+        let consumes = &route.consumes;
+        let detail = format!(
+            "Unsupported Content-Type. Expected {}",
+            route.consumes.join(" or ")
+        );
+        input_parsing.insert(
+            0,
+            quote! {
+                if !__mik_req.__content_type_allowed(&[#(#consumes),*], true) {
+                    return Handler::__mik_error(415, #detail, &__mik_req);
+                }
+            },
+        );
+    }
+
+    // Guards run after the before hooks, which set the principal, and
+    // before any input is parsed
+    let guard_checks: Vec<TokenStream2> = route
+        .guards
+        .iter()
+        .map(|guard| {
+            let values = &guard.values;
+            let check = match guard.kind {
+                GuardKind::Role => quote! { __require_role },
+                GuardKind::Permission => quote! { __require_permission },
+            };
+            quote! {
+                if let Some((__mik_status, __mik_detail)) = mik_sdk::auth::#check(&[#(#values),*]) {
+                    return Handler::__mik_error(__mik_status, __mik_detail, &__mik_req);
+                }
+            }
+        })
+        .collect();
+    input_parsing.splice(0..0, guard_checks);
+
+    // Streaming routes take the body stream right before &Request
+    let stream_setup = if route.stream {
+        input_args.push(quote! { __mik_stream });
+        if streaming {
+            quote! {}
+        } else {
+            quote! {
+                let __mik_stream = mik_sdk::BodyStream::from_bytes(
+                    __mik_raw.body.clone().unwrap_or_default()
+                );
+            }
+        }
+    } else {
+        quote! {}
+    };
 
-    // Build handler call with typed inputs + &Request
+    // Build handler call with typed inputs + &Request. Handlers may return
+    // the Response itself or a Result<Response, Error>.
     let handler_call = if input_args.is_empty() {
         quote! { #handler(&__mik_req) }
     } else {
         quote! { #handler(#(#input_args),*, &__mik_req) }
     };
+    // A declared `-> Type` must match what the handler returns
+    let handler_call = match &route.output_type {
+        Some(output) => quote_spanned! {output.span()=>
+            mik_sdk::response::__check_returns::<#output, _>(#handler_call)
+        },
+        None => handler_call,
+    };
+    let handler_call = quote! {
+        mik_sdk::IntoResponse::<handler::Response>::into_response(#handler_call)
+    };
+
+    let dispatch = if route.before.is_empty() && route.after.is_empty() {
+        quote! {
+            #(#input_parsing)*
+            #stream_setup
+
+            return #handler_call;
+        }
+    } else {
+        // Before hooks may short-circuit; after hooks see every response,
+        // including short-circuits and input parsing errors.
+        let before = &route.before;
+        let after = &route.after;
+        quote! {
+            let __mik_response = (|| -> handler::Response {
+                #(
+                    if let Some(__mik_early) = (#before)(&__mik_req) {
+                        return __mik_early;
+                    }
+                )*
+
+                #(#input_parsing)*
+                #stream_setup
+
+                #handler_call
+            })();
+            #(
+                let __mik_response = (#after)(&__mik_req, __mik_response);
+            )*
+            return __mik_response;
+        }
+    };
 
     quote! {
         if __mik_method == #method_check {
@@ -293,10 +508,198 @@ pub fn generate_route_block(route: &RouteDef) -> TokenStream2 {
                     __mik_params.clone(),
                 );
 
-                #(#input_parsing)*
+                #dispatch
+            }
+        }
+    }
+}
+
+/// Generate the 405 fallback for paths that match a route under another method.
+///
+/// Runs after no route matched: the methods of every candidate route whose
+/// pattern matches are merged into the `Allow` header.
+pub fn generate_method_not_allowed(routes: &[RouteDef], config: &RouterConfig) -> TokenStream2 {
+    // Candidate routes share the path's shape; those whose pattern matches
+    // contribute their method
+    let arms: Vec<TokenStream2> = routes
+        .iter()
+        .enumerate()
+        .map(|(i, route)| {
+            let method = route.method.as_str().to_ascii_uppercase();
+            let matchers = route
+                .patterns
+                .iter()
+                .map(|pattern| generate_pattern_matcher(pattern, config));
+            quote! {
+                #i => (#(#matchers.is_some())||*).then_some(#method),
+            }
+        })
+        .collect();
+    let checks = generate_candidate_loop(&quote! {
+        let __mik_route_method = match __mik_route {
+            #(#arms)*
+            _ => None,
+        };
+        if let Some(__mik_allowed_method) = __mik_route_method {
+            if !__mik_allowed.contains(&__mik_allowed_method) {
+                __mik_allowed.push(__mik_allowed_method);
+            }
+        }
+    });
+
+    quote! {
+        let mut __mik_allowed: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+        #checks
+
+        // Path exists under other methods - return 405 with the supported ones
+        if !__mik_allowed.is_empty() {
+            let __mik_allow = __mik_allowed.join(", ");
+            let mut __mik_response = Handler::__mik_error(
+                405,
+                &::std::format!("Method not allowed. Allowed: {}", __mik_allow),
+                &__mik_unmatched_req,
+            );
+            __mik_response
+                .headers
+                .push((mik_sdk::constants::HEADER_ALLOW.to_string(), __mik_allow));
+            return __mik_response;
+        }
+    }
+}
+
+/// Generate the body-mode check for a `#[stream]` route.
+///
+/// Used by `select_body_mode` so the bridge knows to hand over the raw body
+/// stream instead of buffering it.
+pub fn generate_stream_mode_check(route: &RouteDef, config: &RouterConfig) -> TokenStream2 {
+    let method_check = route.method.to_method_check();
+    let matches: Vec<TokenStream2> = route
+        .patterns
+        .iter()
+        .map(|pattern_str| {
+            let matcher = generate_pattern_matcher(pattern_str, config);
+            quote! { #matcher.is_some() }
+        })
+        .collect();
 
-                return #handler_call;
+    quote! {
+        if __mik_method == #method_check && (#(#matches)||*) {
+            return handler::BodyMode::Streaming;
+        }
+    }
+}
+
+// =============================================================================
+// CODE GENERATION - ROUTE TABLE
+// =============================================================================
+
+/// Generate the `mik_sdk::router::RouteInfo` entries for a route, one per pattern.
+pub fn generate_route_info(route: &RouteDef, default_tag: Option<&str>) -> Vec<TokenStream2> {
+    let method = route.method.to_method_check();
+    let handler = route.handler.to_string();
+    let tag = route.effective_tag(default_tag);
+    let summary = route.summary.as_ref().map(|s| quote! { .summary(#s) });
+    let description = route
+        .description
+        .as_ref()
+        .map(|d| quote! { .description(#d) });
+    let inputs: Vec<TokenStream2> = route
+        .inputs
+        .iter()
+        .map(|input| {
+            let source = match input.source {
+                InputSource::Path => "path",
+                InputSource::Body => "body",
+                InputSource::Query => "query",
+                InputSource::Form => "form",
+                InputSource::Proto => "proto",
+                InputSource::Headers => "headers",
+                InputSource::Extract => "extract",
+            };
+            let type_name = input.type_name.to_string();
+            quote! { (#source, #type_name) }
+        })
+        .collect();
+    let response = route.output_type.as_ref().map(|ty| {
+        let ty = ty.to_string();
+        quote! { .response(#ty) }
+    });
+    let status = route.status_code;
+    let deprecated = route.deprecated.then(|| quote! { .deprecated() });
+    let stream = route.stream.then(|| quote! { .stream() });
+    let consumes = &route.consumes;
+
+    route
+        .patterns
+        .iter()
+        .map(|pattern| {
+            quote! {
+                mik_sdk::router::RouteInfo::new(#method, #pattern, #handler)
+                    .tag(#tag)
+                    .inputs(&[#(#inputs),*])
+                    .status(#status)
+                    .consumes(&[#(#consumes),*])
+                    #summary
+                    #description
+                    #response
+                    #deprecated
+                    #stream
             }
+        })
+        .collect()
+}
+
+// =============================================================================
+// CODE GENERATION - REVERSE ROUTING
+// =============================================================================
+
+/// Generate the `url_for` module: one function per handler building the path
+/// of its first pattern from percent-encoded path parameters.
+pub fn generate_url_for(routes: &[RouteDef]) -> TokenStream2 {
+    let mut seen = std::collections::HashSet::new();
+    let functions = routes
+        .iter()
+        .filter(|route| seen.insert(route.handler.to_string()))
+        .filter_map(|route| {
+            let pattern = route.patterns.first()?;
+            let handler = &route.handler;
+            let doc = format!("`{pattern}`");
+            let mut format = String::new();
+            let mut args = Vec::new();
+            let mut values = Vec::new();
+            for (i, segment) in pattern.split('/').enumerate() {
+                if i > 0 {
+                    format.push('/');
+                }
+                match split_param(segment) {
+                    Some((name, _)) => {
+                        format.push_str("{}");
+                        // Keywords become raw identifiers, anything else positional
+                        let arg = syn::parse_str::<syn::Ident>(name)
+                            .or_else(|_| syn::parse_str::<syn::Ident>(&format!("r#{name}")))
+                            .unwrap_or_else(|_| format_ident!("param_{}", args.len() + 1));
+                        args.push(quote! { #arg: impl ::std::fmt::Display });
+                        values.push(quote! {
+                            mik_sdk::encoding::percent_encode(#arg.to_string().as_bytes())
+                        });
+                    },
+                    None => format.push_str(&segment.replace('{', "{{").replace('}', "}}")),
+                }
+            }
+            Some(quote! {
+                #[doc = #doc]
+                #[must_use]
+                pub fn #handler(#(#args),*) -> ::std::string::String {
+                    ::std::format!(#format, #(#values),*)
+                }
+            })
+        });
+
+    quote! {
+        /// Paths of the routes declared above, one function per handler.
+        #[allow(dead_code, clippy::useless_format)]
+        pub mod url_for {
+            #(#functions)*
         }
     }
 }
@@ -12,6 +12,7 @@
 //! ```
 
 pub mod codegen;
+pub mod trie;
 pub mod types;
 
 use proc_macro::TokenStream;
@@ -19,8 +20,12 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::parse_macro_input;
 
-use crate::openapi::generate_openapi_json;
-use codegen::generate_route_block;
+use crate::openapi::{generate_docs_page, generate_openapi_json};
+use codegen::{
+    generate_method_not_allowed, generate_route_block, generate_route_info,
+    generate_stream_mode_check, generate_trailing_slash_redirect, generate_url_for,
+};
+use trie::{generate_candidate_dispatch, generate_route_candidates};
 use types::RoutesDef;
 
 // =============================================================================
@@ -52,12 +57,465 @@ pub fn routes_impl(input: TokenStream) -> TokenStream {
                 }
             }
         }
+        if let Some(docs) = &defs.top_level.docs {
+            let spec_route = defs
+                .config
+                .openapi_route
+                .as_deref()
+                .unwrap_or("/openapi.json");
+            let route = docs.route.value();
+            if route == spec_route || seen.contains(&("get", route.as_str())) {
+                return syn::Error::new_spanned(
+                    &docs.route,
+                    format!(
+                        "The docs route \"{route}\" is already used by another GET route or the OpenAPI document."
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+        if let Some(health) = &defs.top_level.health {
+            let route = health.route.value();
+            let docs_route = defs.top_level.docs.as_ref().map(|docs| docs.route.value());
+            for path in [route, health.ready_route()] {
+                if seen.contains(&("get", path.as_str()))
+                    || docs_route.as_deref() == Some(path.as_str())
+                    || defs.config.metrics_route.as_deref() == Some(path.as_str())
+                {
+                    return syn::Error::new_spanned(
+                        &health.route,
+                        format!(
+                            "The health route \"{path}\" is already used by another GET route, the docs page or the metrics route."
+                        ),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            }
+        }
+        if let Some(route) = &defs.config.metrics_route {
+            let spec_route = defs
+                .config
+                .openapi_route
+                .as_deref()
+                .or_else(|| defs.top_level.docs.as_ref().map(|_| "/openapi.json"));
+            let docs_route = defs.top_level.docs.as_ref().map(|docs| docs.route.value());
+            if seen.contains(&("get", route.as_str()))
+                || spec_route == Some(route.as_str())
+                || docs_route.as_deref() == Some(route.as_str())
+            {
+                return syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    format!(
+                        "The metrics route \"{route}\" is already used by another GET route, the OpenAPI document or the docs page."
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
     }
 
-    let route_blocks: Vec<TokenStream2> = defs.routes.iter().map(generate_route_block).collect();
+    let route_blocks: Vec<TokenStream2> = defs
+        .routes
+        .iter()
+        .map(|route| generate_route_block(route, &defs.config, false))
+        .collect();
+    let route_candidates = generate_route_candidates(
+        defs.routes
+            .iter()
+            .enumerate()
+            .map(|(i, route)| (i, route.patterns.as_slice())),
+        &defs.config,
+    );
+    let route_dispatch = generate_candidate_dispatch(&route_blocks);
+
+    // Streaming routes are dispatched from handle_streaming with the live body stream
+    let stream_routes: Vec<&types::RouteDef> = defs.routes.iter().filter(|r| r.stream).collect();
+    let stream_mode_checks: Vec<TokenStream2> = stream_routes
+        .iter()
+        .map(|route| generate_stream_mode_check(route, &defs.config))
+        .collect();
+    let stream_route_blocks: Vec<TokenStream2> = stream_routes
+        .iter()
+        .map(|route| generate_route_block(route, &defs.config, true))
+        .collect();
+    let stream_route_dispatch = (!stream_routes.is_empty()).then(|| {
+        let candidates = generate_route_candidates(
+            stream_routes
+                .iter()
+                .enumerate()
+                .map(|(i, route)| (i, route.patterns.as_slice())),
+            &defs.config,
+        );
+        let dispatch = generate_candidate_dispatch(&stream_route_blocks);
+        quote! {
+            let __mik_candidates = #candidates;
+            #dispatch
+        }
+    });
+
+    let route_table: Vec<TokenStream2> = defs
+        .routes
+        .iter()
+        .flat_map(|route| generate_route_info(route, defs.default_tag.as_deref()))
+        .collect();
+    let url_for = generate_url_for(&defs.routes);
+
+    let convert_method = quote! {
+        match __mik_raw_method {
+            handler::Method::Get => mik_sdk::Method::Get,
+            handler::Method::Post => mik_sdk::Method::Post,
+            handler::Method::Put => mik_sdk::Method::Put,
+            handler::Method::Patch => mik_sdk::Method::Patch,
+            handler::Method::Delete => mik_sdk::Method::Delete,
+            handler::Method::Head => mik_sdk::Method::Head,
+            handler::Method::Options => mik_sdk::Method::Options,
+        }
+    };
+
+    // Streaming routes run here; anything else is buffered and dispatched
+    // like a regular request.
+    let streaming_dispatch = quote! {
+        (move || -> handler::Response {
+            let __mik_raw_method = __mik_raw.method;
+            #[allow(unused_variables)]
+            let __mik_method = #convert_method;
+            #[allow(unused_variables)]
+            let __mik_path = __mik_raw.path.split('?').next().unwrap_or(&__mik_raw.path);
+
+            #stream_route_dispatch
+
+            let __mik_body_bytes = match __mik_stream.read_to_end() {
+                Ok(__mik_bytes) => __mik_bytes,
+                Err(__mik_err) => {
+                    let __mik_status: u16 = match __mik_err {
+                        mik_sdk::BodyError::TooLarge { .. } => 413,
+                        _ => 400,
+                    };
+                    let __mik_req = mik_sdk::Request::new(
+                        __mik_method,
+                        __mik_raw.path.clone(),
+                        __mik_raw.headers.clone(),
+                        None,
+                        ::std::collections::HashMap::new(),
+                    );
+                    return Handler::__mik_error(__mik_status, &__mik_err.to_string(), &__mik_req);
+                }
+            };
+            Handler::__mik_dispatch(handler::RequestData {
+                body: if __mik_body_bytes.is_empty() { None } else { Some(__mik_body_bytes) },
+                ..__mik_raw
+            })
+        })()
+    };
+
+    // With #![cors], preflights are answered before routing and every
+    // response gets the CORS headers.
+    let cors_static = defs.cors.as_ref().map(|cors| {
+        quote! {
+            #[doc(hidden)]
+            static __MIK_CORS: ::std::sync::LazyLock<mik_sdk::cors::Cors> =
+                ::std::sync::LazyLock::new(|| #cors);
+        }
+    });
+    let with_cors = |dispatch: TokenStream2| {
+        if defs.cors.is_none() {
+            return dispatch;
+        }
+        quote! {
+            let __mik_raw_method = __mik_raw.method;
+            let __mik_cors_req = mik_sdk::Request::new(
+                #convert_method,
+                __mik_raw.path.clone(),
+                __mik_raw.headers.clone(),
+                None,
+                ::std::collections::HashMap::new(),
+            );
+            if let Some(__mik_preflight) = __MIK_CORS.preflight(&__mik_cors_req) {
+                return __mik_preflight;
+            }
+            let __mik_response = #dispatch;
+            __MIK_CORS.apply(&__mik_cors_req, __mik_response)
+        }
+    };
+    // With #![config(envelope)], every response is reshaped into the
+    // mik_sdk::envelope format once hooks and CORS are done with it.
+    let with_envelope = |dispatch: TokenStream2| {
+        if !defs.config.envelope {
+            return dispatch;
+        }
+        quote! {
+            mik_sdk::envelope::__begin();
+            let __mik_response = (move || -> handler::Response { #dispatch })();
+            mik_sdk::envelope::wrap(__mik_response)
+        }
+    };
+    // With #![trace], every request gets a traceparent that is current while
+    // it is handled and echoed in the response.
+    let with_trace = |dispatch: TokenStream2| {
+        if !defs.trace {
+            return dispatch;
+        }
+        quote! {
+            let mut __mik_raw = __mik_raw;
+            let __mik_traceparent = mik_sdk::trace::__begin(&mut __mik_raw.headers);
+            let mut __mik_response = (move || -> handler::Response { #dispatch })();
+            mik_sdk::trace::__end(__mik_traceparent, &mut __mik_response.headers);
+            __mik_response
+        }
+    };
+    // Every request logs panics with its method and path, is counted and
+    // timed in mik_sdk::metrics, and printed when MIK_ACCESS_LOG is set
+    let with_metrics = |dispatch: TokenStream2| {
+        quote! {
+            let __mik_started = mik_sdk::time::now_millis();
+            let __mik_raw_method = __mik_raw.method;
+            let __mik_metrics_method = #convert_method;
+            let __mik_access = mik_sdk::access_log::__start(
+                &__mik_raw.path,
+                __mik_raw.body.as_deref(),
+                __mik_started,
+            );
+            let __mik_guard_path = __mik_raw.path.clone();
+            let __mik_response = mik_sdk::recover::__guard(
+                __mik_metrics_method.as_str(),
+                &__mik_guard_path,
+                move || -> handler::Response { #dispatch },
+            );
+            mik_sdk::metrics::__record_request(
+                __mik_metrics_method.as_str(),
+                __mik_response.status,
+                __mik_started,
+            );
+            if let Some(__mik_access) = __mik_access {
+                __mik_access.finish(
+                    __mik_metrics_method.as_str(),
+                    __mik_response.status,
+                    &__mik_response.headers,
+                    __mik_response.body.as_deref(),
+                );
+            }
+            __mik_response
+        }
+    };
+    let handle_body = with_metrics(with_trace(with_envelope(with_cors(quote! {
+        Handler::__mik_dispatch(__mik_raw)
+    }))));
+    let streaming_body = with_metrics(with_trace(with_envelope(with_cors(streaming_dispatch))));
+
+    let trailing_slash_redirect = generate_trailing_slash_redirect(&defs.routes, &defs.config);
+    let method_not_allowed = generate_method_not_allowed(&defs.routes, &defs.config);
+    let not_found = defs.top_level.fallback.as_ref().map_or_else(
+        || quote! { Handler::__mik_error(404, "Route not found", &__mik_unmatched_req) },
+        |fallback| quote! { (#fallback)(&__mik_unmatched_req) },
+    );
+    let error_response = defs.top_level.error.as_ref().map_or_else(
+        || quote! { mik_sdk::middleware::problem(__mik_status, __mik_detail) },
+        |error| quote! { (#error)(__mik_status, __mik_detail, __mik_req) },
+    );
+    let validation_response = defs.top_level.error.as_ref().map_or_else(
+        || quote! { mik_sdk::middleware::validation_problem(__mik_errors) },
+        |error| quote! { (#error)(422, &__mik_errors.to_string(), __mik_req) },
+    );
 
     let openapi_static = generate_openapi_json(&defs);
 
+    // With #![config(openapi)] or a docs page, the document is served as OpenAPI 3.1
+    let spec_route = defs
+        .config
+        .openapi_route
+        .as_deref()
+        .or_else(|| defs.top_level.docs.as_ref().map(|_| "/openapi.json"));
+    let (openapi_module, openapi_route) = spec_route
+        .map(|route| {
+            let module = quote! {
+                #[doc(hidden)]
+                mod __mik_openapi {
+                    pub(super) static DOCUMENT: ::std::sync::LazyLock<::std::string::String> =
+                        ::std::sync::LazyLock::new(|| {
+                            let __mik_doc: ::std::string::String = #openapi_static;
+                            mik_sdk::typed::openapi_3_1(&__mik_doc).unwrap_or(__mik_doc)
+                        });
+                }
+            };
+            let dispatch = quote! {
+                if __mik_method == mik_sdk::Method::Get && __mik_path == #route {
+                    return handler::Response {
+                        status: 200,
+                        headers: vec![(
+                            mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+                            mik_sdk::constants::MIME_JSON.to_string(),
+                        )],
+                        body: Some(__mik_openapi::DOCUMENT.as_bytes().to_vec()),
+                    };
+                }
+            };
+            (module, dispatch)
+        })
+        .unzip();
+    // With #![config(metrics)], the registry is served for Prometheus scrapes
+    let metrics_route = defs.config.metrics_route.as_deref().map(|route| {
+        quote! {
+            if __mik_method == mik_sdk::Method::Get && __mik_path == #route {
+                return handler::Response {
+                    status: 200,
+                    headers: vec![(
+                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+                        mik_sdk::constants::MIME_PROMETHEUS.to_string(),
+                    )],
+                    body: Some(mik_sdk::metrics::render().into_bytes()),
+                };
+            }
+        }
+    });
+    // A health entry serves liveness and readiness, checks built once
+    let (health_static, health_route) = defs
+        .top_level
+        .health
+        .as_ref()
+        .map(|health| {
+            let checks = health.checks.as_ref().map_or_else(
+                || quote! { mik_sdk::health::HealthChecks::new() },
+                |checks| quote! { #checks },
+            );
+            let route = health.route.value();
+            let ready_route = health.ready_route();
+            let static_item = quote! {
+                #[doc(hidden)]
+                static __MIK_HEALTH: ::std::sync::LazyLock<mik_sdk::health::HealthChecks> =
+                    ::std::sync::LazyLock::new(|| #checks);
+            };
+            let dispatch = quote! {
+                if __mik_method == mik_sdk::Method::Get && __mik_path == #route {
+                    return mik_sdk::health::__liveness();
+                }
+                if __mik_method == mik_sdk::Method::Get && __mik_path == #ready_route {
+                    return mik_sdk::health::__readiness(&__MIK_HEALTH);
+                }
+            };
+            (static_item, dispatch)
+        })
+        .unzip();
+    // ws entries export mik:core/websocket, callbacks built once
+    let websocket_export = (!defs.top_level.websockets.is_empty()).then(|| {
+        let routes: Vec<String> = defs
+            .top_level
+            .websockets
+            .iter()
+            .map(|ws| ws.route.value())
+            .collect();
+        let sockets = defs.top_level.websockets.iter().map(|ws| &ws.socket);
+        quote! {
+            #[doc(hidden)]
+            static __MIK_WEBSOCKETS: ::std::sync::LazyLock<
+                ::std::vec::Vec<mik_sdk::websocket::WebSocket>,
+            > = ::std::sync::LazyLock::new(|| vec![#(#sockets),*]);
+
+            #[doc(hidden)]
+            const __MIK_WEBSOCKET_ROUTES: &[&str] = &[#(#routes),*];
+
+            /// Convert SDK actions to the `mik:core/websocket` actions.
+            #[doc(hidden)]
+            fn __mik_ws_actions(
+                __mik_actions: ::std::vec::Vec<mik_sdk::websocket::Action>,
+            ) -> ::std::vec::Vec<bindings::exports::mik::core::websocket::Action> {
+                use bindings::exports::mik::core::websocket as __mik_ws;
+                use mik_sdk::websocket::{Action, Message};
+                __mik_actions
+                    .into_iter()
+                    .filter_map(|__mik_action| match __mik_action {
+                        Action::Send(Message::Text(__mik_text)) => {
+                            Some(__mik_ws::Action::Send(__mik_ws::Message::Text(__mik_text)))
+                        }
+                        Action::Send(Message::Binary(__mik_bytes)) => {
+                            Some(__mik_ws::Action::Send(__mik_ws::Message::Binary(__mik_bytes)))
+                        }
+                        Action::Close(__mik_code, __mik_reason) => {
+                            Some(__mik_ws::Action::Close((__mik_code, __mik_reason)))
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            }
+
+            impl bindings::exports::mik::core::websocket::Guest for Handler {
+                fn accepts(__mik_path: String) -> bool {
+                    mik_sdk::websocket::__route(__MIK_WEBSOCKET_ROUTES, &__mik_path).is_some()
+                }
+
+                fn on_open(
+                    __mik_id: u64,
+                    __mik_raw: handler::RequestData,
+                ) -> Vec<bindings::exports::mik::core::websocket::Action> {
+                    let __mik_raw_method = __mik_raw.method;
+                    let __mik_req = mik_sdk::Request::new(
+                        #convert_method,
+                        __mik_raw.path,
+                        __mik_raw.headers,
+                        None,
+                        ::std::collections::HashMap::new(),
+                    );
+                    __mik_ws_actions(mik_sdk::websocket::__open(
+                        &__MIK_WEBSOCKETS,
+                        __MIK_WEBSOCKET_ROUTES,
+                        __mik_id,
+                        &__mik_req,
+                    ))
+                }
+
+                fn on_message(
+                    __mik_id: u64,
+                    __mik_message: bindings::exports::mik::core::websocket::Message,
+                ) -> Vec<bindings::exports::mik::core::websocket::Action> {
+                    use bindings::exports::mik::core::websocket::Message;
+                    let __mik_message = match __mik_message {
+                        Message::Text(__mik_text) => mik_sdk::websocket::Message::Text(__mik_text),
+                        Message::Binary(__mik_bytes) => {
+                            mik_sdk::websocket::Message::Binary(__mik_bytes)
+                        }
+                    };
+                    __mik_ws_actions(mik_sdk::websocket::__message(
+                        &__MIK_WEBSOCKETS,
+                        __mik_id,
+                        __mik_message,
+                    ))
+                }
+
+                fn on_close(__mik_id: u64, __mik_code: u16, __mik_reason: String) {
+                    mik_sdk::websocket::__close(
+                        &__MIK_WEBSOCKETS,
+                        __mik_id,
+                        __mik_code,
+                        &__mik_reason,
+                    );
+                }
+            }
+        }
+    });
+    let docs_route = defs
+        .top_level
+        .docs
+        .as_ref()
+        .zip(spec_route)
+        .map(|(docs, spec_route)| {
+            let route = &docs.route;
+            let page = generate_docs_page(docs.ui, spec_route);
+            quote! {
+                if __mik_method == mik_sdk::Method::Get && __mik_path == #route {
+                    return handler::Response {
+                        status: 200,
+                        headers: vec![(
+                            mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+                            mik_sdk::constants::MIME_HTML.to_string(),
+                        )],
+                        body: Some(#page.as_bytes().to_vec()),
+                    };
+                }
+            }
+        });
+
     let tokens = quote! {
         // Compile-time check: ensure bindings module is properly configured.
         // If you see an error here, make sure you have:
@@ -135,39 +593,196 @@ pub fn routes_impl(input: TokenStream) -> TokenStream {
 
         struct Handler;
 
+        // A raw Response can't be checked against a declared `-> Type`
+        impl<T> mik_sdk::response::Returns<T> for handler::Response {}
+
+        // Lets SDK middleware (auth, ...) build and inspect handler responses
+        impl mik_sdk::middleware::HttpResponse for handler::Response {
+            fn from_parts(
+                status: u16,
+                headers: ::std::vec::Vec<(::std::string::String, ::std::string::String)>,
+                body: ::std::option::Option<::std::vec::Vec<u8>>,
+            ) -> Self {
+                handler::Response { status, headers, body }
+            }
+
+            fn status(&self) -> u16 {
+                self.status
+            }
+
+            fn headers(&self) -> &[(::std::string::String, ::std::string::String)] {
+                &self.headers
+            }
+
+            fn headers_mut(&mut self) -> &mut ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                &mut self.headers
+            }
+
+            fn body(&self) -> ::std::option::Option<&[u8]> {
+                self.body.as_deref()
+            }
+        }
+
+        impl Handler {
+            /// Match the request against the routes and run the handler.
+            fn __mik_dispatch(__mik_raw: handler::RequestData) -> handler::Response {
+                let __mik_raw_method = __mik_raw.method;
+                let __mik_method = #convert_method;
+
+                let __mik_path = __mik_raw.path.split('?').next().unwrap_or(&__mik_raw.path);
+
+                #openapi_route
+                #docs_route
+                #metrics_route
+                #health_route
+
+                // Only routes sharing the path's shape are tried
+                let __mik_candidates = #route_candidates;
+                #route_dispatch
+
+                #trailing_slash_redirect
+
+                let __mik_unmatched_req = mik_sdk::Request::new(
+                    __mik_method,
+                    __mik_raw.path.clone(),
+                    __mik_raw.headers.clone(),
+                    __mik_raw.body.clone(),
+                    ::std::collections::HashMap::new(),
+                );
+
+                #method_not_allowed
+
+                // No route matched
+                #not_found
+            }
+
+            /// Build an error response generated by the router itself.
+            fn __mik_error(
+                __mik_status: u16,
+                __mik_detail: &str,
+                __mik_req: &mik_sdk::Request,
+            ) -> handler::Response {
+                #error_response
+            }
+
+            /// Build the `422` response for an input that failed validation.
+            #[allow(dead_code)]
+            fn __mik_validation_error(
+                __mik_errors: &mik_sdk::typed::ValidationErrors,
+                __mik_req: &mik_sdk::Request,
+            ) -> handler::Response {
+                #validation_response
+            }
+        }
+
+        #cors_static
+        #health_static
+        #websocket_export
+
+        #openapi_module
+
+        /// Response body registered with `stream!`, exported as `response-stream`.
+        #[doc(hidden)]
+        struct __MikResponseStream(::std::cell::RefCell<mik_sdk::stream::ChunkedBody>);
+
+        impl handler::GuestResponseStream for __MikResponseStream {
+            fn next_chunk(&self) -> Option<Vec<u8>> {
+                self.0.borrow_mut().next_chunk()
+            }
+        }
+
         impl Guest for Handler {
+            type ResponseStream = __MikResponseStream;
+
             fn handle(__mik_raw: handler::RequestData) -> handler::Response {
-                let __mik_method = match __mik_raw.method {
-                    handler::Method::Get => mik_sdk::Method::Get,
-                    handler::Method::Post => mik_sdk::Method::Post,
-                    handler::Method::Put => mik_sdk::Method::Put,
-                    handler::Method::Patch => mik_sdk::Method::Patch,
-                    handler::Method::Delete => mik_sdk::Method::Delete,
-                    handler::Method::Head => mik_sdk::Method::Head,
-                    handler::Method::Options => mik_sdk::Method::Options,
-                };
+                // Drop any streaming body left over from a previous request
+                let _ = mik_sdk::stream::__take_registered();
+                mik_sdk::tasks::__begin();
+                mik_sdk::auth::__begin();
 
-                let __mik_path = __mik_raw.path.split('?').next().unwrap_or(&__mik_raw.path);
+                #handle_body
+            }
 
-                #(#route_blocks)*
+            fn select_body_mode(__mik_raw_method: handler::Method, __mik_full_path: String) -> handler::BodyMode {
+                #[allow(unused_variables)]
+                let __mik_method = #convert_method;
+                #[allow(unused_variables)]
+                let __mik_path = __mik_full_path.split('?').next().unwrap_or(&__mik_full_path);
 
-                // No route matched - return 404
-                handler::Response {
-                    status: 404,
-                    headers: vec![
-                        (
-                            mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                            mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                        )
-                    ],
-                    body: Some(mik_sdk::json::obj()
-                        .set("type", mik_sdk::json::str("about:blank"))
-                        .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(404)))
-                        .set("status", mik_sdk::json::int(404))
-                        .set("detail", mik_sdk::json::str("Route not found"))
-                        .to_bytes()),
-                }
+                #(#stream_mode_checks)*
+
+                handler::BodyMode::Buffered
             }
+
+            fn take_response_stream() -> Option<handler::ResponseStream> {
+                mik_sdk::stream::__take_registered().map(|__mik_body| {
+                    handler::ResponseStream::new(__MikResponseStream(
+                        ::std::cell::RefCell::new(__mik_body),
+                    ))
+                })
+            }
+
+            fn handle_streaming(
+                __mik_raw: handler::RequestData,
+                __mik_body: handler::InputStream,
+            ) -> handler::Response {
+                let _ = mik_sdk::stream::__take_registered();
+                mik_sdk::tasks::__begin();
+                mik_sdk::auth::__begin();
+
+                let __mik_stream = mik_sdk::BodyStream::new(move |__mik_max| {
+                    match __mik_body.blocking_read(__mik_max) {
+                        Ok(__mik_chunk) => Ok(Some(__mik_chunk)),
+                        Err(handler::StreamError::Closed) => Ok(None),
+                        Err(_) => Err(mik_sdk::BodyError::Read),
+                    }
+                });
+
+                #streaming_body
+            }
+
+            fn run_tasks() {
+                mik_sdk::tasks::__run_pending();
+            }
+        }
+
+        /// The routes declared above, see [`mik_sdk::router`].
+        #[allow(dead_code)]
+        pub fn route_table() -> &'static [mik_sdk::router::RouteInfo] {
+            static ROUTES: &[mik_sdk::router::RouteInfo] = &[#(#route_table),*];
+            ROUTES
+        }
+
+        #url_for
+
+        /// Client that runs requests through this router natively, see
+        /// [`mik_sdk::testing`].
+        #[cfg(test)]
+        #[allow(dead_code)]
+        fn test_client() -> mik_sdk::testing::TestClient {
+            mik_sdk::testing::TestClient::new(|__mik_method, __mik_path, __mik_headers, __mik_body| {
+                let __mik_raw = handler::RequestData {
+                    method: match __mik_method {
+                        mik_sdk::Method::Get => handler::Method::Get,
+                        mik_sdk::Method::Post => handler::Method::Post,
+                        mik_sdk::Method::Put => handler::Method::Put,
+                        mik_sdk::Method::Patch => handler::Method::Patch,
+                        mik_sdk::Method::Delete => handler::Method::Delete,
+                        mik_sdk::Method::Head => handler::Method::Head,
+                        mik_sdk::Method::Options => handler::Method::Options,
+                        _ => unreachable!("{__mik_method} is not a handler method"),
+                    },
+                    path: __mik_path,
+                    headers: __mik_headers,
+                    body: __mik_body,
+                };
+                let __mik_res = <Handler as Guest>::handle(__mik_raw);
+                mik_sdk::middleware::HttpResponse::from_parts(
+                    __mik_res.status,
+                    __mik_res.headers,
+                    __mik_res.body,
+                )
+            })
         }
 
         // Allow unsafe_code for generated WIT bindings export macro
@@ -0,0 +1,268 @@
+//! Prefix tree over route path segments.
+//!
+//! Dispatch walks the request path through a tree built from the route
+//! patterns at compile time, so only routes sharing the path's shape are
+//! tried. Lookup cost grows with the path length, not the route count.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use super::codegen::split_param;
+use super::types::{RouterConfig, TrailingSlash};
+
+// =============================================================================
+// TREE CONSTRUCTION
+// =============================================================================
+
+/// A node of the segment tree.
+#[derive(Default)]
+struct Node {
+    /// Children for literal segments, in declaration order
+    statics: Vec<(String, Self)>,
+    /// Child for a `{param}` segment (matches any value)
+    param: Option<Box<Self>>,
+    /// Routes whose pattern ends at this node
+    routes: Vec<usize>,
+}
+
+impl Node {
+    fn insert(&mut self, segments: &[String], route: usize) {
+        let Some((segment, rest)) = segments.split_first() else {
+            if !self.routes.contains(&route) {
+                self.routes.push(route);
+            }
+            return;
+        };
+
+        if split_param(segment).is_some() {
+            self.param
+                .get_or_insert_with(Box::default)
+                .insert(rest, route);
+            return;
+        }
+
+        if !self.statics.iter().any(|(s, _)| s == segment) {
+            self.statics.push((segment.clone(), Self::default()));
+        }
+        if let Some((_, child)) = self.statics.iter_mut().find(|(s, _)| s == segment) {
+            child.insert(rest, route);
+        }
+    }
+}
+
+/// Split a pattern into the segments compared against the request path,
+/// normalized the same way the path is at runtime.
+fn pattern_segments(pattern: &str, config: &RouterConfig) -> Vec<String> {
+    let pattern = if config.trailing_slash == TrailingSlash::Ignore && pattern.len() > 1 {
+        pattern.strip_suffix('/').unwrap_or(pattern)
+    } else {
+        pattern
+    };
+
+    pattern
+        .split('/')
+        .map(|segment| {
+            if config.case_insensitive && split_param(segment).is_none() {
+                segment.to_ascii_lowercase()
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect()
+}
+
+// =============================================================================
+// CODE GENERATION
+// =============================================================================
+
+/// Generate an expression evaluating to the set of routes whose patterns
+/// could match `__mik_path`, as a bitset indexed by route.
+///
+/// `routes` pairs each route index with its patterns. Candidates are only a
+/// pre-filter: each route still runs its own matcher, which checks parameter
+/// constraints and extracts the values.
+pub fn generate_route_candidates<'a>(
+    routes: impl IntoIterator<Item = (usize, &'a [String])>,
+    config: &RouterConfig,
+) -> TokenStream2 {
+    let mut root = Node::default();
+    let mut route_count = 0;
+    let mut max_segments = 0;
+    for (index, patterns) in routes {
+        route_count = route_count.max(index + 1);
+        for pattern in patterns {
+            let segments = pattern_segments(pattern, config);
+            max_segments = max_segments.max(segments.len());
+            root.insert(&segments, index);
+        }
+    }
+    let words = route_count.div_ceil(64);
+
+    let normalize_slash = (config.trailing_slash == TrailingSlash::Ignore).then(|| {
+        quote! {
+            let __mik_key: &str = if __mik_key.len() > 1 {
+                __mik_key.strip_suffix('/').unwrap_or(__mik_key)
+            } else {
+                __mik_key
+            };
+        }
+    });
+    let normalize_case = config.case_insensitive.then(|| {
+        quote! {
+            let __mik_key = __mik_key.to_ascii_lowercase();
+            let __mik_key: &str = &__mik_key;
+        }
+    });
+    let walk = generate_node(&root, 0);
+
+    quote! {
+        {
+            let __mik_key: &str = __mik_path;
+            #normalize_slash
+            #normalize_case
+
+            // Paths with more segments than any pattern match nothing
+            let mut __mik_buf: [&str; #max_segments] = [""; #max_segments];
+            let mut __mik_len = 0usize;
+            let mut __mik_fits = true;
+            for __mik_part in __mik_key.split('/') {
+                match __mik_buf.get_mut(__mik_len) {
+                    Some(__mik_slot) => {
+                        *__mik_slot = __mik_part;
+                        __mik_len += 1;
+                    },
+                    None => {
+                        __mik_fits = false;
+                        break;
+                    },
+                }
+            }
+
+            let mut __mik_found = [0u64; #words];
+            if __mik_fits {
+                let __mik_segments = &__mik_buf[..__mik_len];
+                #walk
+            }
+            __mik_found
+        }
+    }
+}
+
+/// Generate the lookup for one tree node at segment `depth`.
+///
+/// Both the literal child and the parameter child are followed, so every
+/// route that could match is collected.
+fn generate_node(node: &Node, depth: usize) -> TokenStream2 {
+    let marks = node.routes.iter().map(|route| {
+        let word = route / 64;
+        let bit = 1u64 << (route % 64);
+        quote! { __mik_found[#word] |= #bit; }
+    });
+
+    let param = node
+        .param
+        .as_ref()
+        .map(|child| generate_node(child, depth + 1));
+
+    let on_segment = if node.statics.is_empty() {
+        quote! { Some(_) => { #param } }
+    } else {
+        let arms = node.statics.iter().map(|(segment, child)| {
+            let body = generate_node(child, depth + 1);
+            quote! { #segment => { #body }, }
+        });
+        quote! {
+            Some(&__mik_segment) => {
+                match __mik_segment {
+                    #(#arms)*
+                    _ => {},
+                }
+                #param
+            }
+        }
+    };
+
+    quote! {
+        match __mik_segments.get(#depth) {
+            None => { #(#marks)* },
+            #on_segment,
+        }
+    }
+}
+
+/// Generate a loop running `body` for each route in `__mik_candidates`,
+/// in declaration order, with the route index bound to `__mik_route`.
+pub fn generate_candidate_loop(body: &TokenStream2) -> TokenStream2 {
+    quote! {
+        for (__mik_word_index, &__mik_word) in __mik_candidates.iter().enumerate() {
+            let mut __mik_bits = __mik_word;
+            while __mik_bits != 0 {
+                let __mik_route = __mik_word_index * 64 + __mik_bits.trailing_zeros() as usize;
+                __mik_bits &= __mik_bits - 1;
+                #body
+            }
+        }
+    }
+}
+
+/// Generate a loop running the route blocks for `__mik_candidates` in order.
+///
+/// Each block returns when its route matches, so the first matching route
+/// in declaration order handles the request.
+pub fn generate_candidate_dispatch(blocks: &[TokenStream2]) -> TokenStream2 {
+    let indices = 0..blocks.len();
+    generate_candidate_loop(&quote! {
+        match __mik_route {
+            #(#indices => { #blocks },)*
+            _ => {},
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(patterns: &[&str], config: &RouterConfig) -> Node {
+        let mut root = Node::default();
+        for (i, pattern) in patterns.iter().enumerate() {
+            root.insert(&pattern_segments(pattern, config), i);
+        }
+        root
+    }
+
+    fn child<'a>(node: &'a Node, segment: &str) -> &'a Node {
+        &node.statics.iter().find(|(s, _)| s == segment).unwrap().1
+    }
+
+    #[test]
+    #[allow(clippy::literal_string_with_formatting_args)] // Route patterns, not format strings
+    fn test_shared_prefixes_share_nodes() {
+        let root = tree(
+            &["/users", "/users/{id}", "/users/{id:uuid}", "/users/me"],
+            &RouterConfig::default(),
+        );
+        let users = child(child(&root, ""), "users");
+        assert_eq!(users.routes, vec![0]);
+        assert_eq!(users.param.as_ref().unwrap().routes, vec![1, 2]);
+        assert_eq!(child(users, "me").routes, vec![3]);
+    }
+
+    #[test]
+    fn test_segments_follow_config() {
+        let config = RouterConfig {
+            trailing_slash: TrailingSlash::Ignore,
+            case_insensitive: true,
+            ..RouterConfig::default()
+        };
+        assert_eq!(
+            pattern_segments("/Users/{Id}/", &config),
+            ["", "users", "{Id}"]
+        );
+        assert_eq!(pattern_segments("/", &config), ["", ""]);
+        assert_eq!(
+            pattern_segments("/Users/", &RouterConfig::default()),
+            ["", "Users", ""]
+        );
+    }
+}
@@ -3,15 +3,33 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{
-    Attribute, Ident, LitStr, Result, Token,
-    parse::{Parse, ParseStream},
+    Attribute, Expr, Ident, LitStr, Result, Token,
+    parse::{Parse, ParseStream, discouraged::Speculative},
+    punctuated::Punctuated,
 };
 
 use crate::constants::VALID_HTTP_METHODS;
 use crate::errors::did_you_mean;
 
 /// Valid input sources for route handlers.
-const VALID_INPUT_SOURCES: &[&str] = &["path", "body", "query"];
+const VALID_INPUT_SOURCES: &[&str] = &[
+    "path", "body", "query", "form", "proto", "headers", "extract",
+];
+
+/// Guards for `GET "/admin" [role = "admin"] => ...`.
+const VALID_GUARDS: &[&str] = &["role", "permission"];
+
+/// Content type shorthands for `POST "/path" (json) => ...`.
+const CONTENT_TYPE_SHORTHANDS: &[(&str, &str)] = &[
+    ("json", "application/json"),
+    ("form", "application/x-www-form-urlencoded"),
+    ("multipart", "multipart/form-data"),
+    ("text", "text/plain"),
+    ("html", "text/html"),
+    ("xml", "application/xml"),
+    ("binary", "application/octet-stream"),
+    ("proto", "application/x-protobuf"),
+];
 
 // =============================================================================
 // TYPES
@@ -54,12 +72,71 @@ impl HttpMethod {
     }
 }
 
+/// Constraint on a path parameter: `{id:uuid}`, `{year:int}`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ParamConstraint {
+    Int,   // signed 64-bit integer
+    Uint,  // unsigned 64-bit integer
+    Uuid,  // hyphenated UUID
+    Alpha, // ASCII letters
+    Alnum, // ASCII letters and digits
+}
+
+impl ParamConstraint {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name {
+            "int" => Some(Self::Int),
+            "uint" => Some(Self::Uint),
+            "uuid" => Some(Self::Uuid),
+            "alpha" => Some(Self::Alpha),
+            "alnum" => Some(Self::Alnum),
+            _ => None,
+        }
+    }
+
+    /// OpenAPI schema for a parameter with this constraint.
+    pub(crate) const fn schema_json(self) -> &'static str {
+        match self {
+            Self::Int => r#"{"type":"integer","format":"int64"}"#,
+            Self::Uint => r#"{"type":"integer","format":"int64","minimum":0}"#,
+            Self::Uuid => r#"{"type":"string","format":"uuid"}"#,
+            Self::Alpha => r#"{"type":"string","pattern":"^[A-Za-z]+$"}"#,
+            Self::Alnum => r#"{"type":"string","pattern":"^[A-Za-z0-9]+$"}"#,
+        }
+    }
+}
+
 /// Input source for typed parameters
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum InputSource {
-    Path,  // from URL path params
-    Body,  // from JSON body
-    Query, // from query string
+    Path,    // from URL path params
+    Body,    // from JSON body
+    Query,   // from query string
+    Form,    // from form-urlencoded body
+    Proto,   // from protobuf body (prost feature)
+    Headers, // from request headers
+    Extract, // from a custom FromRequest extractor
+}
+
+impl InputSource {
+    /// Whether the input is parsed from the request body.
+    pub(crate) const fn reads_body(&self) -> bool {
+        matches!(self, Self::Body | Self::Form | Self::Proto)
+    }
+}
+
+/// What a route guard checks on the request's principal
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GuardKind {
+    Role,       // from [role = "..."]
+    Permission, // from [permission = "..."]
+}
+
+/// A route guard: the principal needs one of `values`
+#[derive(Clone)]
+pub struct Guard {
+    pub(crate) kind: GuardKind,
+    pub(crate) values: Vec<String>,
 }
 
 /// A typed input parameter for a handler
@@ -76,14 +153,26 @@ pub struct RouteDef {
     pub(crate) handler: Ident,
     pub(crate) inputs: Vec<TypedInput>,
     pub(crate) output_type: Option<Ident>,
-    /// Operation summary from doc comment
+    /// Operation summary from the first paragraph of the doc comment
     pub(crate) summary: Option<String>,
+    /// Operation description from the remaining doc comment paragraphs
+    pub(crate) description: Option<String>,
     /// Tag override from #[tag = "..."] attribute
     pub(crate) tag_override: Option<String>,
     /// Mark operation as deprecated in OpenAPI schema
     pub(crate) deprecated: bool,
     /// HTTP status code for success response (default: 200)
     pub(crate) status_code: u16,
+    /// Deliver the request body as a `BodyStream` (from `#[stream]`)
+    pub(crate) stream: bool,
+    /// Accepted request media types (from `"/path" (json, ...)`), empty for any
+    pub(crate) consumes: Vec<String>,
+    /// Guards checked after the before hooks (from `"/path" [role = "..."]`), all required
+    pub(crate) guards: Vec<Guard>,
+    /// Hooks run before the handler, in order (global first)
+    pub(crate) before: Vec<Expr>,
+    /// Hooks run on the response, in order (global last)
+    pub(crate) after: Vec<Expr>,
 }
 
 /// All routes in the macro
@@ -91,6 +180,89 @@ pub struct RoutesDef {
     pub(crate) routes: Vec<RouteDef>,
     /// Global tag for all routes (from #[tag = "..."] at top of block)
     pub(crate) default_tag: Option<String>,
+    /// CORS configuration (from #![cors] or #![cors(expr)])
+    pub(crate) cors: Option<Expr>,
+    /// W3C trace context propagation (from #![trace])
+    pub(crate) trace: bool,
+    /// Fallback, error handler and docs entries
+    pub(crate) top_level: TopLevel,
+    /// Router options (from #![config(...)])
+    pub(crate) config: RouterConfig,
+}
+
+/// How a trailing slash affects path matching.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// `/users` and `/users/` are different paths
+    #[default]
+    Strict,
+    /// `/users` and `/users/` match the same routes
+    Ignore,
+    /// The other form is redirected (308) to the one the route declares
+    Redirect,
+}
+
+/// Router-wide options.
+#[derive(Clone, Default)]
+pub struct RouterConfig {
+    pub(crate) trailing_slash: TrailingSlash,
+    /// Compare static path segments ignoring ASCII case
+    pub(crate) case_insensitive: bool,
+    /// Serve the OpenAPI 3.1 document at this path (from `openapi`)
+    pub(crate) openapi_route: Option<String>,
+    /// Serve Prometheus metrics at this path (from `metrics`)
+    pub(crate) metrics_route: Option<String>,
+    /// Wrap responses in the `mik_sdk::envelope` format (from `envelope`)
+    pub(crate) envelope: bool,
+}
+
+/// Top-level `fallback => ...`, `error => ...`, `docs "..."`, `health "..."` and `ws "..."` entries.
+#[derive(Default)]
+pub struct TopLevel {
+    /// Called when no route matches the path: `fn(&Request) -> Response`
+    pub(crate) fallback: Option<Expr>,
+    /// Builds every router error response: `fn(u16, &str, &Request) -> Response`
+    pub(crate) error: Option<Expr>,
+    /// Interactive API docs page
+    pub(crate) docs: Option<DocsPage>,
+    /// Liveness and readiness endpoints
+    pub(crate) health: Option<HealthEndpoint>,
+    /// WebSocket endpoints, in declaration order
+    pub(crate) websockets: Vec<WebSocketEndpoint>,
+}
+
+/// A `ws "/path" => socket` entry.
+pub struct WebSocketEndpoint {
+    pub(crate) route: LitStr,
+    /// Builds the `mik_sdk::websocket::WebSocket` callbacks
+    pub(crate) socket: Expr,
+}
+
+/// Liveness (`route`) and readiness (`route` + `/ready`) endpoints.
+pub struct HealthEndpoint {
+    pub(crate) route: LitStr,
+    /// Builds the `mik_sdk::health::HealthChecks` run for readiness
+    pub(crate) checks: Option<Expr>,
+}
+
+impl HealthEndpoint {
+    /// The readiness route, `route` + `/ready`.
+    pub(crate) fn ready_route(&self) -> String {
+        format!("{}/ready", self.route.value().trim_end_matches('/'))
+    }
+}
+
+/// An interactive API docs page served by the router.
+pub struct DocsPage {
+    pub(crate) route: LitStr,
+    pub(crate) ui: DocsUi,
+}
+
+/// Renderer for the API docs page.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DocsUi {
+    Swagger,
+    Redoc,
 }
 
 impl RouteDef {
@@ -136,39 +308,383 @@ impl Parse for RoutesDef {
         let mut routes = Vec::new();
         let mut default_tag = None;
 
-        // Check for global #[tag = "..."] at the start
-        while input.peek(Token![#]) {
-            let attrs: Vec<Attribute> = input.call(Attribute::parse_outer)?;
-            for attr in attrs {
-                if attr.path().is_ident("tag") {
+        // Global options: #![before(...)], #![after(...)], #![cors] and #![trace] at the top
+        let mut global_before = Vec::new();
+        let mut global_after = Vec::new();
+        let mut cors = None;
+        let mut trace = false;
+        let mut config = None;
+        for attr in input.call(Attribute::parse_inner)? {
+            if attr.path().is_ident("before") {
+                global_before.extend(parse_hooks(&attr)?);
+            } else if attr.path().is_ident("after") {
+                global_after.extend(parse_hooks(&attr)?);
+            } else if attr.path().is_ident("cors") {
+                if cors.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &attr,
+                        "Duplicate #![cors] attribute. Configure CORS once per routes! block.",
+                    ));
+                }
+                cors = Some(parse_cors(&attr)?);
+            } else if attr.path().is_ident("trace") {
+                if trace {
+                    return Err(syn::Error::new_spanned(
+                        &attr,
+                        "Duplicate #![trace] attribute. Enable tracing once per routes! block.",
+                    ));
+                }
+                attr.meta.require_path_only()?;
+                trace = true;
+            } else if attr.path().is_ident("config") {
+                if config.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &attr,
+                        "Duplicate #![config(...)] attribute. Put all router options in one attribute.",
+                    ));
+                }
+                config = Some(parse_config(&attr)?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &attr,
+                    "Unknown routes! attribute. Expected #![before(...)], #![after(...)], #![cors], #![trace] or #![config(...)].\n\
+                     \n\
+                     Example:\n\
+                     routes! {\n\
+                         #![cors]\n\
+                         #![before(require_auth)]\n\
+                         #![after(add_security_headers)]\n\
+                         GET \"/users\" => list_users,\n\
+                     }",
+                ));
+            }
+        }
+
+        // Check for global #[tag = "..."] at the start. A group containing any
+        // other attribute (doc comments, #[stream], ...) belongs to the first
+        // route, and one followed by `{` belongs to a route group.
+        if input.peek(Token![#]) {
+            let fork = input.fork();
+            let attrs: Vec<Attribute> = fork.call(Attribute::parse_outer)?;
+            if attrs.iter().all(|attr| attr.path().is_ident("tag")) && !fork.peek(syn::token::Brace)
+            {
+                input.advance_to(&fork);
+                for attr in attrs {
                     let value: LitStr = attr.parse_args()?;
                     default_tag = Some(value.value());
                 }
             }
         }
 
-        while !input.is_empty() {
-            let route = parse_route(input)?;
-            routes.push(route);
+        let global = GroupScope {
+            before: global_before,
+            after: global_after,
+            tag: None,
+        };
+        let mut top_level = TopLevel::default();
+        parse_entries(input, &global, &mut routes, Some(&mut top_level))?;
+
+        Ok(Self {
+            routes,
+            default_tag,
+            cors,
+            trace,
+            top_level,
+            config: config.unwrap_or_default(),
+        })
+    }
+}
+
+/// Hooks and tag inherited from the enclosing route groups.
+struct GroupScope {
+    before: Vec<Expr>,
+    after: Vec<Expr>,
+    tag: Option<String>,
+}
+
+impl GroupScope {
+    /// Scope for a nested group: outer before hooks run first, outer after
+    /// hooks run last.
+    fn nest(&self, attrs: &[Attribute]) -> Result<Self> {
+        let mut before = self.before.clone();
+        let mut after = Vec::new();
+        let mut tag = self.tag.clone();
+        for attr in attrs {
+            if attr.path().is_ident("before") {
+                before.extend(parse_hooks(attr)?);
+            } else if attr.path().is_ident("after") {
+                after.extend(parse_hooks(attr)?);
+            } else if attr.path().is_ident("tag") {
+                let value: LitStr = attr.parse_args()?;
+                tag = Some(value.value());
+            } else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "Route groups only accept #[before(...)], #[after(...)] and #[tag(\"...\")].\n\
+                     \n\
+                     Example:\n\
+                     #[before(auth::api_key)]\n\
+                     {\n\
+                         GET \"/admin/users\" => list_users,\n\
+                     }",
+                ));
+            }
+        }
+        after.extend(self.after.iter().cloned());
+        Ok(Self { before, after, tag })
+    }
+
+    /// Apply the inherited hooks and tag to a route.
+    fn apply(&self, route: &mut RouteDef) {
+        route.before.splice(0..0, self.before.iter().cloned());
+        route.after.extend(self.after.iter().cloned());
+        if route.tag_override.is_none() {
+            route.tag_override.clone_from(&self.tag);
+        }
+    }
+}
 
+/// Parse routes and `#[...] { ... }` route groups until the input ends.
+///
+/// `fallbacks` is `None` inside route groups, where `fallback =>` and
+/// `error =>` entries are not allowed.
+fn parse_entries(
+    input: ParseStream<'_>,
+    scope: &GroupScope,
+    routes: &mut Vec<RouteDef>,
+    mut top_level: Option<&mut TopLevel>,
+) -> Result<()> {
+    while !input.is_empty() {
+        if input.peek(Ident) && input.peek2(LitStr) && input.fork().parse::<Ident>()? == "docs" {
+            parse_docs(input, top_level.as_deref_mut())?;
             if input.peek(Token![,]) {
                 input.parse::<Token![,]>()?;
             }
+            continue;
         }
 
-        Ok(Self {
-            routes,
-            default_tag,
-        })
+        if input.peek(Ident) && input.peek2(LitStr) && input.fork().parse::<Ident>()? == "health" {
+            parse_health(input, top_level.as_deref_mut())?;
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+            continue;
+        }
+
+        if input.peek(Ident) && input.peek2(LitStr) && input.fork().parse::<Ident>()? == "ws" {
+            parse_websocket(input, top_level.as_deref_mut())?;
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+            continue;
+        }
+
+        if input.peek(Ident) && input.peek2(Token![=>]) {
+            parse_fallback(input, top_level.as_deref_mut())?;
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+            continue;
+        }
+
+        let fork = input.fork();
+        let attrs: Vec<Attribute> = fork.call(Attribute::parse_outer)?;
+        if fork.peek(syn::token::Brace) {
+            input.advance_to(&fork);
+            let content;
+            syn::braced!(content in input);
+            parse_entries(&content, &scope.nest(&attrs)?, routes, None)?;
+        } else {
+            let mut route = parse_route(input)?;
+            scope.apply(&mut route);
+            routes.push(route);
+        }
+
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
     }
+    Ok(())
+}
+
+/// Parse a `fallback => handler` or `error => handler` entry.
+fn parse_fallback(input: ParseStream<'_>, top_level: Option<&mut TopLevel>) -> Result<()> {
+    let kind: Ident = input.parse()?;
+    let slot = match (kind.to_string().as_str(), top_level) {
+        ("fallback", Some(top_level)) => &mut top_level.fallback,
+        ("error", Some(top_level)) => &mut top_level.error,
+        ("fallback" | "error", None) => {
+            return Err(syn::Error::new_spanned(
+                &kind,
+                format!(
+                    "`{kind} =>` must be at the top level of routes!, not inside a route group."
+                ),
+            ));
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &kind,
+                format!(
+                    "Unknown routes! entry '{kind}'.\n\
+                     \n\
+                     Routes start with an uppercase HTTP method:\n\
+                     GET \"/users\" => list_users,\n\
+                     \n\
+                     Error handlers use `fallback` or `error`:\n\
+                     fallback => not_found,   // fn(&Request) -> Response\n\
+                     error => render_error,   // fn(u16, &str, &Request) -> Response"
+                ),
+            ));
+        },
+    };
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(
+            &kind,
+            format!("Duplicate `{kind} =>` entry. Define it once per routes! block."),
+        ));
+    }
+    input.parse::<Token![=>]>()?;
+    *slot = Some(input.parse()?);
+    Ok(())
+}
+
+/// Parse a `docs "/path"` entry, optionally `=> swagger` or `=> redoc`.
+fn parse_docs(input: ParseStream<'_>, top_level: Option<&mut TopLevel>) -> Result<()> {
+    let kind: Ident = input.parse()?;
+    let Some(top_level) = top_level else {
+        return Err(syn::Error::new_spanned(
+            &kind,
+            "`docs` must be at the top level of routes!, not inside a route group.",
+        ));
+    };
+    if top_level.docs.is_some() {
+        return Err(syn::Error::new_spanned(
+            &kind,
+            "Duplicate `docs` entry. Define it once per routes! block.",
+        ));
+    }
+
+    let route: LitStr = input.parse()?;
+    if !route.value().starts_with('/') {
+        return Err(syn::Error::new_spanned(
+            &route,
+            "The docs route must start with '/', e.g. docs \"/docs\"",
+        ));
+    }
+
+    let ui = if input.peek(Token![=>]) {
+        input.parse::<Token![=>]>()?;
+        let ui: Ident = input.parse()?;
+        match ui.to_string().as_str() {
+            "swagger" => DocsUi::Swagger,
+            "redoc" => DocsUi::Redoc,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &ui,
+                    format!(
+                        "Unknown docs renderer '{ui}'. Expected `swagger` or `redoc`.\n\
+                         \n\
+                         Example: docs \"/docs\" => redoc"
+                    ),
+                ));
+            },
+        }
+    } else {
+        DocsUi::Swagger
+    };
+
+    top_level.docs = Some(DocsPage { route, ui });
+    Ok(())
+}
+
+/// Parse a `health "/path"` entry, optionally `=> checks`.
+fn parse_health(input: ParseStream<'_>, top_level: Option<&mut TopLevel>) -> Result<()> {
+    let kind: Ident = input.parse()?;
+    let Some(top_level) = top_level else {
+        return Err(syn::Error::new_spanned(
+            &kind,
+            "`health` must be at the top level of routes!, not inside a route group.",
+        ));
+    };
+    if top_level.health.is_some() {
+        return Err(syn::Error::new_spanned(
+            &kind,
+            "Duplicate `health` entry. Define it once per routes! block.",
+        ));
+    }
+
+    let route: LitStr = input.parse()?;
+    if !route.value().starts_with('/') {
+        return Err(syn::Error::new_spanned(
+            &route,
+            "The health route must start with '/', e.g. health \"/healthz\"",
+        ));
+    }
+
+    let checks = if input.peek(Token![=>]) {
+        input.parse::<Token![=>]>()?;
+        Some(input.parse()?)
+    } else {
+        None
+    };
+
+    top_level.health = Some(HealthEndpoint { route, checks });
+    Ok(())
+}
+
+/// Parse a `ws "/path" => socket` entry.
+fn parse_websocket(input: ParseStream<'_>, top_level: Option<&mut TopLevel>) -> Result<()> {
+    let kind: Ident = input.parse()?;
+    let Some(top_level) = top_level else {
+        return Err(syn::Error::new_spanned(
+            &kind,
+            "`ws` must be at the top level of routes!, not inside a route group.",
+        ));
+    };
+
+    let route: LitStr = input.parse()?;
+    let path = route.value();
+    if !path.starts_with('/') || path.contains(['{', '}', '?']) {
+        return Err(syn::Error::new_spanned(
+            &route,
+            "A ws route must be a fixed path starting with '/', e.g. ws \"/chat\" => chat()",
+        ));
+    }
+    if top_level
+        .websockets
+        .iter()
+        .any(|ws| ws.route.value() == path)
+    {
+        return Err(syn::Error::new_spanned(
+            &route,
+            format!("Duplicate ws route \"{path}\". Each WebSocket path must be unique."),
+        ));
+    }
+
+    if !input.peek(Token![=>]) {
+        return Err(syn::Error::new_spanned(
+            &route,
+            "Expected `=> socket` after the ws route, e.g. ws \"/chat\" => chat()",
+        ));
+    }
+    input.parse::<Token![=>]>()?;
+    let socket = input.parse()?;
+
+    top_level
+        .websockets
+        .push(WebSocketEndpoint { route, socket });
+    Ok(())
 }
 
 #[allow(clippy::too_many_lines)] // Complex route parsing with many input variants
 fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
-    // Parse doc comments (/// ...) and attributes (#[tag = "..."], #[deprecated], #[status(code)]) before the route
-    let mut summary = None;
+    // Parse doc comments (/// ...) and attributes (#[tag = "..."], #[deprecated], #[status(code)], #[stream], #[before(...)], #[after(...)]) before the route
+    let mut doc_lines = Vec::new();
     let mut tag_override = None;
     let mut deprecated = false;
+    let mut stream_attr: Option<Attribute> = None;
+    let mut before = Vec::new();
+    let mut after = Vec::new();
     let mut status_code: u16 = 200; // Default status code
 
     // Parse outer attributes (doc comments become #[doc = "..."])
@@ -182,20 +698,19 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
                     ..
                 }) = &meta.value
             {
-                let text = lit_str.value().trim().to_string();
-                if !text.is_empty() {
-                    // Append to summary (multiple /// lines get combined)
-                    summary = Some(match summary {
-                        Some(existing) => format!("{existing} {text}"),
-                        None => text,
-                    });
-                }
+                doc_lines.push(lit_str.value().trim().to_string());
             }
         } else if attr.path().is_ident("tag") {
             let value: LitStr = attr.parse_args()?;
             tag_override = Some(value.value());
         } else if attr.path().is_ident("deprecated") {
             deprecated = true;
+        } else if attr.path().is_ident("stream") {
+            stream_attr = Some(attr);
+        } else if attr.path().is_ident("before") {
+            before.extend(parse_hooks(&attr)?);
+        } else if attr.path().is_ident("after") {
+            after.extend(parse_hooks(&attr)?);
         } else if attr.path().is_ident("status") {
             let code: syn::LitInt = attr.parse_args()?;
             status_code = code.base10_parse().map_err(|_| {
@@ -283,6 +798,7 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
             ),
         )
     })?;
+    validate_pattern(&first_pattern)?;
     patterns.push(first_pattern.value());
 
     while input.peek(Token![|]) {
@@ -299,9 +815,28 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
                 ),
             )
         })?;
+        validate_pattern(&alt_pattern)?;
         patterns.push(alt_pattern.value());
     }
 
+    // Parse optional accepted content types: (json) or (json, "application/cbor")
+    let consumes = if input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in input);
+        parse_content_types(&content)?
+    } else {
+        Vec::new()
+    };
+
+    // Parse optional guards: [role = "admin"] or [role = "admin" | "editor", permission = "..."]
+    let guards = if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        parse_guards(&content)?
+    } else {
+        Vec::new()
+    };
+
     // Parse =>
     input.parse::<Token![=>]>().map_err(|e| {
         syn::Error::new(
@@ -408,6 +943,29 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
         None
     };
 
+    if let Some(attr) = &stream_attr
+        && inputs.iter().any(|input| input.source.reads_body())
+    {
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!(
+                "#[stream] routes cannot also take a `body:`, `form:` or `proto:` input.\n\
+                 \n\
+                 Streaming handlers receive the body as a BodyStream argument instead:\n\
+                 \n\
+                 #[stream]\n\
+                 {method_str} \"{}\" => {handler}\n\
+                 \n\
+                 fn {handler}(body: BodyStream, req: &Request) -> Response {{ ... }}",
+                patterns
+                    .first()
+                    .map_or("/path", std::string::String::as_str),
+            ),
+        ));
+    }
+
+    let (summary, description) = split_doc(&doc_lines);
+
     Ok(RouteDef {
         method,
         patterns,
@@ -415,12 +973,305 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
         inputs,
         output_type,
         summary,
+        description,
         tag_override,
         deprecated,
         status_code,
+        stream: stream_attr.is_some(),
+        consumes,
+        guards,
+        before,
+        after,
     })
 }
 
+/// Split doc comment lines into a summary (the first paragraph) and a
+/// description (the remaining paragraphs, separated by blank lines).
+fn split_doc(lines: &[String]) -> (Option<String>, Option<String>) {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in lines.iter().map(String::as_str).chain([""]) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join(" "));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+
+    let mut paragraphs = paragraphs.into_iter();
+    let summary = paragraphs.next();
+    let rest: Vec<String> = paragraphs.collect();
+    let description = (!rest.is_empty()).then(|| rest.join("\n\n"));
+    (summary, description)
+}
+
+/// Parse `#![cors]` (configured from the environment) or `#![cors(expr)]`.
+fn parse_cors(attr: &Attribute) -> Result<Expr> {
+    match &attr.meta {
+        syn::Meta::Path(_) => Ok(syn::parse_quote!(mik_sdk::cors::Cors::from_env())),
+        syn::Meta::List(_) => attr.parse_args::<Expr>().map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                "Expected a CORS configuration, e.g. #![cors(Cors::new().allow_origins([\"https://app.example.com\"]))]",
+            )
+        }),
+        syn::Meta::NameValue(_) => Err(syn::Error::new_spanned(
+            attr,
+            "Use #![cors] or #![cors(Cors::new()...)]",
+        )),
+    }
+}
+
+/// Parse `#![config(trailing_slash = "...", case_insensitive, openapi = "...", metrics = "...", envelope)]`.
+fn parse_config(attr: &Attribute) -> Result<RouterConfig> {
+    let mut config = RouterConfig::default();
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("trailing_slash") {
+            let value: LitStr = meta.value()?.parse()?;
+            config.trailing_slash = match value.value().as_str() {
+                "strict" => TrailingSlash::Strict,
+                "ignore" => TrailingSlash::Ignore,
+                "redirect" => TrailingSlash::Redirect,
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &value,
+                        format!(
+                            "Invalid trailing_slash mode '{other}'. Expected \"strict\", \"ignore\" or \"redirect\"."
+                        ),
+                    ));
+                },
+            };
+            Ok(())
+        } else if meta.path.is_ident("case_insensitive") {
+            config.case_insensitive = true;
+            Ok(())
+        } else if meta.path.is_ident("envelope") {
+            config.envelope = true;
+            Ok(())
+        } else if meta.path.is_ident("openapi") {
+            let route = if meta.input.peek(Token![=]) {
+                let value: LitStr = meta.value()?.parse()?;
+                if !value.value().starts_with('/') {
+                    return Err(syn::Error::new_spanned(
+                        &value,
+                        "The OpenAPI route must start with '/', e.g. openapi = \"/openapi.json\"",
+                    ));
+                }
+                value.value()
+            } else {
+                "/openapi.json".to_string()
+            };
+            config.openapi_route = Some(route);
+            Ok(())
+        } else if meta.path.is_ident("metrics") {
+            let route = if meta.input.peek(Token![=]) {
+                let value: LitStr = meta.value()?.parse()?;
+                if !value.value().starts_with('/') {
+                    return Err(syn::Error::new_spanned(
+                        &value,
+                        "The metrics route must start with '/', e.g. metrics = \"/metrics\"",
+                    ));
+                }
+                value.value()
+            } else {
+                "/metrics".to_string()
+            };
+            config.metrics_route = Some(route);
+            Ok(())
+        } else {
+            Err(meta.error(
+                "Unknown router option. Expected trailing_slash = \"strict\" | \"ignore\" | \"redirect\", \
+                 case_insensitive, openapi, metrics or envelope.\n\
+                 \n\
+                 Example: #![config(trailing_slash = \"redirect\", case_insensitive)]",
+            ))
+        }
+    })?;
+    Ok(config)
+}
+
+/// Parse the hook list of a `#[before(...)]` or `#[after(...)]` attribute.
+fn parse_hooks(attr: &Attribute) -> Result<Vec<Expr>> {
+    let hooks = attr
+        .parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        .map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                format!(
+                    "Expected a comma-separated list of middleware functions.\n\
+                     \n\
+                     Example: #[before(require_auth, log_request)]\n\
+                     \n\
+                     Original error: {e}"
+                ),
+            )
+        })?;
+    if hooks.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "Middleware attribute needs at least one function, e.g. #[before(require_auth)]",
+        ));
+    }
+    Ok(hooks.into_iter().collect())
+}
+
+/// Parse the accepted content types of a route: shorthands or `"type/subtype"`.
+fn parse_content_types(input: ParseStream<'_>) -> Result<Vec<String>> {
+    let mut consumes = Vec::new();
+    for item in Punctuated::<Expr, Token![,]>::parse_terminated(input)? {
+        let mime = match &item {
+            Expr::Path(path) if path.path.get_ident().is_some() => {
+                let name = path
+                    .path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                let Some((_, mime)) = CONTENT_TYPE_SHORTHANDS
+                    .iter()
+                    .find(|(shorthand, _)| *shorthand == name)
+                else {
+                    let names: Vec<&str> =
+                        CONTENT_TYPE_SHORTHANDS.iter().map(|(n, _)| *n).collect();
+                    let suggestion = did_you_mean(&name, &names);
+                    return Err(syn::Error::new_spanned(
+                        &item,
+                        format!(
+                            "Unknown content type '{name}'.{suggestion}\n\
+                             \n\
+                             Valid shorthands: {}\n\
+                             Or use a media type: POST \"/upload\" (\"image/png\") => upload",
+                            names.join(", ")
+                        ),
+                    ));
+                };
+                (*mime).to_string()
+            },
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => {
+                let mime = lit.value();
+                let valid = mime
+                    .split_once('/')
+                    .is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty());
+                if !valid || mime.contains(';') || mime.contains(char::is_whitespace) {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        format!(
+                            "Invalid media type \"{mime}\". Expected \"type/subtype\", \
+                             e.g. \"application/cbor\" or \"image/*\"."
+                        ),
+                    ));
+                }
+                mime.to_ascii_lowercase()
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &item,
+                    "Expected a content type shorthand (json, form, ...) or a media type string.\n\
+                     \n\
+                     Example: POST \"/echo\" (json) => echo",
+                ));
+            },
+        };
+        if !consumes.contains(&mime) {
+            consumes.push(mime);
+        }
+    }
+    if consumes.is_empty() {
+        return Err(
+            input.error("Expected at least one content type, e.g. POST \"/echo\" (json) => echo")
+        );
+    }
+    Ok(consumes)
+}
+
+/// Parse route guards: `role = "admin" | "editor", permission = "posts:write"`.
+fn parse_guards(input: ParseStream<'_>) -> Result<Vec<Guard>> {
+    let mut guards = Vec::new();
+    while !input.is_empty() {
+        let name: Ident = input.parse()?;
+        let kind = match name.to_string().as_str() {
+            "role" => GuardKind::Role,
+            "permission" => GuardKind::Permission,
+            other => {
+                let suggestion = did_you_mean(other, VALID_GUARDS);
+                return Err(syn::Error::new_spanned(
+                    &name,
+                    format!(
+                        "Unknown route guard '{other}'.{suggestion}\n\
+                         \n\
+                         Valid guards: {}\n\
+                         Example: GET \"/admin\" [role = \"admin\" | \"owner\"] => admin_panel",
+                        VALID_GUARDS.join(", ")
+                    ),
+                ));
+            },
+        };
+        input.parse::<Token![=]>()?;
+        let mut values = Vec::new();
+        loop {
+            let value: LitStr = input.parse()?;
+            if value.value().is_empty() {
+                return Err(syn::Error::new_spanned(
+                    &value,
+                    format!("Route guard '{name}' needs a non-empty value."),
+                ));
+            }
+            values.push(value.value());
+            if !input.peek(Token![|]) {
+                break;
+            }
+            input.parse::<Token![|]>()?;
+        }
+        guards.push(Guard { kind, values });
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+    if guards.is_empty() {
+        return Err(input.error(
+            "Expected at least one guard, e.g. GET \"/admin\" [role = \"admin\"] => admin_panel",
+        ));
+    }
+    Ok(guards)
+}
+
+/// Check `{name:constraint}` segments name a known constraint.
+fn validate_pattern(pattern: &LitStr) -> Result<()> {
+    for segment in pattern.value().split('/') {
+        let Some(param) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+            continue;
+        };
+        let Some((name, constraint)) = param.split_once(':') else {
+            continue;
+        };
+        if name.is_empty() {
+            return Err(syn::Error::new(
+                pattern.span(),
+                format!("Missing parameter name in '{{{param}}}'. Expected {{name:{constraint}}}."),
+            ));
+        }
+        if ParamConstraint::parse(constraint).is_none() {
+            return Err(syn::Error::new(
+                pattern.span(),
+                format!(
+                    "Unknown constraint '{constraint}' on path parameter '{name}'.\n\
+                     \n\
+                     Valid constraints: int, uint, uuid, alpha, alnum\n\
+                     \n\
+                     Example: GET \"/users/{{id:uuid}}\" => get_user(path: UserId)"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn parse_typed_inputs(
     input: ParseStream<'_>,
     method_str: &str,
@@ -444,6 +1295,10 @@ fn parse_typed_inputs(
                      - path: Type   - URL path parameters (e.g., /users/{{id}})\n\
                      - body: Type   - JSON request body\n\
                      - query: Type  - Query string parameters\n\
+                     - form: Type   - Form-urlencoded request body\n\
+                     - proto: Type  - Protobuf request body (prost feature)\n\
+                     - headers: Type - Request headers\n\
+                     - extract: Type - Custom extractor (FromRequest)\n\
                      \n\
                      Example:\n\
                      {method_str} \"{path}\" => {handler}(path: UserId, body: CreateUser, query: Pagination) -> User\n\
@@ -453,28 +1308,7 @@ fn parse_typed_inputs(
             )
         })?;
 
-        let source = match source_ident.to_string().as_str() {
-            "path" => InputSource::Path,
-            "body" => InputSource::Body,
-            "query" => InputSource::Query,
-            other => {
-                let suggestion = did_you_mean(other, VALID_INPUT_SOURCES);
-                return Err(syn::Error::new_spanned(
-                    &source_ident,
-                    format!(
-                        "Invalid input source '{other}'.{suggestion}\n\
-                         \n\
-                         Valid sources:\n\
-                         - path  - URL path parameters (e.g., /users/{{id}})\n\
-                         - body  - JSON request body\n\
-                         - query - Query string parameters\n\
-                         \n\
-                         Example:\n\
-                         {method_str} \"{path}\" => {handler}(path: Id, body: CreateUser) -> User"
-                    ),
-                ));
-            },
-        };
+        let source = parse_input_source(&source_ident, method_str, path, handler)?;
 
         // Parse colon
         input.parse::<Token![:]>().map_err(|e| {
@@ -504,6 +1338,10 @@ fn parse_typed_inputs(
                      - path: Type   - Type must derive Path\n\
                      - body: Type   - Type must derive Type (for JSON parsing)\n\
                      - query: Type  - Type must derive Query\n\
+                     - form: Type   - Type must derive Form\n\
+                     - proto: Type  - Type must implement prost::Message\n\
+                     - headers: Type - Type must derive Headers\n\
+                     - extract: Type - Type must implement FromRequest\n\
                      \n\
                      Example:\n\
                      #[derive(Path)]\n\
@@ -516,6 +1354,7 @@ fn parse_typed_inputs(
             )
         })?;
 
+        check_single_body_input(&inputs, &source, &source_ident, method_str, path, handler)?;
         inputs.push(TypedInput { source, type_name });
 
         // Optional comma
@@ -526,3 +1365,68 @@ fn parse_typed_inputs(
 
     Ok(inputs)
 }
+
+/// Parse the source of a typed input, e.g. `body` in `body: CreateUser`.
+fn parse_input_source(
+    source_ident: &Ident,
+    method_str: &str,
+    path: &str,
+    handler: &Ident,
+) -> Result<InputSource> {
+    match source_ident.to_string().as_str() {
+        "path" => Ok(InputSource::Path),
+        "body" => Ok(InputSource::Body),
+        "query" => Ok(InputSource::Query),
+        "form" => Ok(InputSource::Form),
+        "proto" => Ok(InputSource::Proto),
+        "headers" => Ok(InputSource::Headers),
+        "extract" => Ok(InputSource::Extract),
+        other => {
+            let suggestion = did_you_mean(other, VALID_INPUT_SOURCES);
+            Err(syn::Error::new_spanned(
+                source_ident,
+                format!(
+                    "Invalid input source '{other}'.{suggestion}\n\
+                     \n\
+                     Valid sources:\n\
+                     - path  - URL path parameters (e.g., /users/{{id}})\n\
+                     - body  - JSON request body\n\
+                     - query - Query string parameters\n\
+                     - form  - Form-urlencoded request body\n\
+                     - proto - Protobuf request body (prost feature)\n\
+                     - headers - Request headers\n\
+                     - extract - Custom extractor (FromRequest)\n\
+                     \n\
+                     Example:\n\
+                     {method_str} \"{path}\" => {handler}(path: Id, body: CreateUser) -> User"
+                ),
+            ))
+        },
+    }
+}
+
+/// Reject a second `body:`/`form:`/`proto:` input, since the body can only be parsed one way.
+fn check_single_body_input(
+    inputs: &[TypedInput],
+    source: &InputSource,
+    source_ident: &Ident,
+    method_str: &str,
+    path: &str,
+    handler: &Ident,
+) -> Result<()> {
+    if source.reads_body() && inputs.iter().any(|i| i.source.reads_body()) {
+        return Err(syn::Error::new_spanned(
+            source_ident,
+            format!(
+                "Handler '{handler}' takes more than one request body input.\n\
+                 \n\
+                 Use one of `body:` for JSON, `form:` for form-urlencoded or `proto:` for\n\
+                 protobuf bodies.\n\
+                 \n\
+                 Example:\n\
+                 {method_str} \"{path}\" => {handler}(form: LoginForm)"
+            ),
+        ));
+    }
+    Ok(())
+}
@@ -30,10 +30,15 @@ pub static TYPE_REGISTRY: &[TypeInfo] = &[
         display_name: "string",
     },
     TypeInfo {
-        rust_names: &["i8", "i16", "i32", "u8", "u16", "u32", "u64", "usize"],
+        rust_names: &["i8", "i16", "i32", "u8", "u16", "u32", "usize"],
         json_getter: "int_cast",
         display_name: "integer",
     },
+    TypeInfo {
+        rust_names: &["u64"],
+        json_getter: "uint",
+        display_name: "integer",
+    },
     TypeInfo {
         rust_names: &["i64"],
         json_getter: "int",
@@ -74,6 +79,7 @@ pub fn get_json_getter(ty: &Type) -> Option<TokenStream2> {
                 "str" => quote! { .str() },
                 "int" => quote! { .int() },
                 "int_cast" => quote! { .int().map(|n| n as _) },
+                "uint" => quote! { .uint() },
                 "float" => quote! { .float() },
                 "float_f32" => quote! { .float().map(|n| n as f32) },
                 "bool" => quote! { .bool() },
@@ -88,7 +94,8 @@ pub fn get_json_getter(ty: &Type) -> Option<TokenStream2> {
 /// Uses utoipa for type-safe schema generation.
 pub fn get_openapi_schema(ty: &Type) -> String {
     use crate::openapi::{
-        array_schema, make_nullable_json, ref_or_schema_to_json, rust_type_to_schema,
+        array_schema, make_nullable_json, map_schema_json, ref_or_schema_to_json,
+        rust_type_to_schema,
     };
 
     if let Type::Path(type_path) = ty
@@ -114,6 +121,13 @@ pub fn get_openapi_schema(ty: &Type) -> String {
             return r#"{"type":"array"}"#.to_string();
         }
 
+        if is_map_type(ty) {
+            if let Some(value) = get_map_value_type(ty) {
+                return map_schema_json(&get_openapi_schema(value));
+            }
+            return r#"{"type":"object"}"#.to_string();
+        }
+
         // Use utoipa for basic types
         let schema = rust_type_to_schema(&name);
         return ref_or_schema_to_json(&schema);
@@ -167,6 +181,28 @@ pub fn is_option_type(ty: &Type) -> bool {
     false
 }
 
+/// Check if a type is a string-keyed map: `HashMap<K, V>` or `BTreeMap<K, V>`.
+pub fn is_map_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        return segment.ident == "HashMap" || segment.ident == "BTreeMap";
+    }
+    false
+}
+
+/// Get the value type `V` from `HashMap<K, V>` or `BTreeMap<K, V>`.
+pub fn get_map_value_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(value)) = args.args.iter().nth(1)
+    {
+        return Some(value);
+    }
+    None
+}
+
 /// Get the inner type from Option<T> or Vec<T>.
 pub fn get_inner_type(ty: &Type) -> Option<&Type> {
     if let Type::Path(type_path) = ty
@@ -201,4 +237,20 @@ mod tests {
     fn test_lookup_unknown() {
         assert!(lookup_type("MyCustomType").is_none());
     }
+
+    #[test]
+    fn test_map_schema() {
+        let ty: Type = syn::parse_quote!(HashMap<String, i32>);
+        assert!(is_map_type(&ty));
+        assert_eq!(
+            get_openapi_schema(&ty),
+            r#"{"type":"object","additionalProperties":{"type":"integer"}}"#
+        );
+
+        let ty: Type = syn::parse_quote!(std::collections::BTreeMap<String, Vec<Tag>>);
+        assert_eq!(
+            get_openapi_schema(&ty),
+            r##"{"type":"object","additionalProperties":{"type":"array","items":{"$ref":"#/components/schemas/Tag"}}}"##
+        );
+    }
 }
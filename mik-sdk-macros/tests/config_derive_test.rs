@@ -0,0 +1,65 @@
+#![allow(clippy::unwrap_used)] // Test code
+//! Tests for #[derive(Config)].
+
+use mik_sdk::config::{Config, ConfigError, Secret};
+
+#[derive(Config, Debug)]
+#[config(prefix = "APP_")]
+struct Settings {
+    database_url: Secret,
+    #[config(default = "50")]
+    max_items: u32,
+    #[config(rename = "REGION")]
+    region: Option<String>,
+    hosts: Vec<String>,
+}
+
+fn lookup_from(pairs: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+    move |key| {
+        pairs
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| (*v).to_string())
+    }
+}
+
+#[test]
+fn test_config_derive_loads_fields() {
+    let settings = Settings::from_lookup(&lookup_from(&[
+        ("APP_DATABASE_URL", "postgres://db"),
+        ("APP_HOSTS", "a.example, b.example"),
+        ("REGION", "eu-west-1"),
+    ]))
+    .unwrap();
+
+    assert_eq!(settings.database_url.expose(), "postgres://db");
+    assert_eq!(settings.max_items, 50);
+    assert_eq!(settings.region.as_deref(), Some("eu-west-1"));
+    assert_eq!(settings.hosts, vec!["a.example", "b.example"]);
+    assert!(!format!("{settings:?}").contains("postgres://db"));
+}
+
+#[test]
+fn test_config_derive_errors() {
+    let missing = Settings::from_lookup(&lookup_from(&[("APP_HOSTS", "a")])).unwrap_err();
+    assert_eq!(
+        missing,
+        ConfigError::Missing {
+            key: "APP_DATABASE_URL".to_string()
+        }
+    );
+
+    let invalid = Settings::from_lookup(&lookup_from(&[
+        ("APP_DATABASE_URL", "postgres://db"),
+        ("APP_HOSTS", "a"),
+        ("APP_MAX_ITEMS", "many"),
+    ]))
+    .unwrap_err();
+    assert_eq!(
+        invalid,
+        ConfigError::Invalid {
+            key: "APP_MAX_ITEMS".to_string(),
+            expected: "integer"
+        }
+    );
+}
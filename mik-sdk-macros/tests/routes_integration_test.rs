@@ -17,9 +17,11 @@
 //! 1. Routes with typed body input (Type derive)
 //! 2. Routes with typed query input (Query derive)
 //! 3. Routes with typed path input (Path derive)
-//! 4. Routes with multiple input types combined
-//! 5. `OpenAPI` schema generation via test (`cargo test __mik_write_schema`)
-//! 6. HTTP method dispatch works correctly
+//! 4. Routes with typed form input (Form derive)
+//! 5. Routes with typed header input (Headers derive)
+//! 6. Routes with multiple input types combined
+//! 7. `OpenAPI` schema generation via test (`cargo test __mik_write_schema`)
+//! 8. HTTP method dispatch works correctly
 
 #![allow(dead_code)]
 
@@ -118,6 +120,37 @@ mod mik_sdk {
                     message: format!("Expected {expected} for field '{field}'"),
                 }
             }
+
+            pub fn custom(field: &str, message: impl Into<String>) -> Self {
+                Self {
+                    field: field.to_string(),
+                    message: message.into(),
+                }
+            }
+
+            pub fn redact(self) -> Self {
+                Self {
+                    field: self.field,
+                    message: "invalid value".to_string(),
+                }
+            }
+
+            pub fn with_path(self, parent: &str) -> Self {
+                Self {
+                    field: format!("{parent}.{}", self.field),
+                    message: self.message,
+                }
+            }
+
+            pub fn field(&self) -> &str {
+                &self.field
+            }
+        }
+
+        impl std::fmt::Display for ParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.message)
+            }
         }
 
         #[derive(Debug, Clone)]
@@ -143,6 +176,38 @@ mod mik_sdk {
                     message: format!("'{field}' must be at most {max}"),
                 }
             }
+
+            pub fn pattern(field: &str, pattern: &str) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: "pattern".to_string(),
+                    message: format!("'{field}' must match {pattern}"),
+                }
+            }
+
+            pub fn format(field: &str, expected: &str) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: "format".to_string(),
+                    message: format!("'{field}' must be a valid {expected}"),
+                }
+            }
+
+            pub fn custom(field: &str, constraint: &str, message: impl Into<String>) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: constraint.to_string(),
+                    message: message.into(),
+                }
+            }
+
+            pub fn field(&self) -> &str {
+                &self.field
+            }
+
+            pub fn message(&self) -> String {
+                self.message.clone()
+            }
         }
 
         pub trait FromJson: Sized {
@@ -153,12 +218,130 @@ mod mik_sdk {
             fn from_query(params: &[(String, String)]) -> Result<Self, ParseError>;
         }
 
+        pub trait FromForm: Sized {
+            fn from_form(params: &[(String, String)]) -> Result<Self, ParseError>;
+        }
+
+        pub trait FromHeaders: Sized {
+            fn from_headers(headers: &[(String, String)]) -> Result<Self, ParseError>;
+        }
+
         pub trait FromPath: Sized {
             fn from_params(params: &HashMap<String, String>) -> Result<Self, ParseError>;
         }
 
+        // Patterns run on the real matcher, the compiled program is the point
+        pub use ::mik_sdk::typed::pattern;
+
+        pub mod format {
+            pub fn is_email(value: &str) -> bool {
+                value.contains('@')
+            }
+
+            pub fn is_uri(value: &str) -> bool {
+                value.contains(':')
+            }
+
+            pub const fn is_uuid(value: &str) -> bool {
+                value.len() == 36
+            }
+
+            pub fn is_ipv4(value: &str) -> bool {
+                value.parse::<std::net::Ipv4Addr>().is_ok()
+            }
+
+            pub fn is_ipv6(value: &str) -> bool {
+                value.parse::<std::net::Ipv6Addr>().is_ok()
+            }
+        }
+
+        #[derive(Default)]
+        pub struct ValidationErrors {
+            pub errors: Vec<ValidationError>,
+            pub rejected: Vec<Option<crate::mik_sdk::json::JsonValue>>,
+        }
+
+        impl ValidationErrors {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            pub fn push(&mut self, error: ValidationError) {
+                self.errors.push(error);
+                self.rejected.push(None);
+            }
+
+            pub fn push_rejected(
+                &mut self,
+                error: ValidationError,
+                rejected: crate::mik_sdk::json::JsonValue,
+            ) {
+                self.errors.push(error);
+                self.rejected.push(Some(rejected));
+            }
+
+            pub fn iter(&self) -> std::slice::Iter<'_, ValidationError> {
+                self.errors.iter()
+            }
+
+            pub fn into_result(self) -> Result<(), Self> {
+                if self.errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(self)
+                }
+            }
+        }
+
         pub trait Validate {
             fn validate(&self) -> Result<(), ValidationError>;
+
+            fn validate_all(&self) -> Result<(), ValidationErrors> {
+                let mut errors = ValidationErrors::new();
+                if let Err(e) = self.validate() {
+                    errors.push(e);
+                }
+                errors.into_result()
+            }
+        }
+
+        pub const REDACTED: &str = "[REDACTED]";
+
+        pub trait ToLogJson: crate::mik_sdk::json::ToJson {
+            fn to_log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.to_json()
+            }
+        }
+
+        impl<T: ToLogJson> ToLogJson for Option<T> {
+            fn to_log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.as_ref().map_or_else(
+                    crate::mik_sdk::json::JsonValue::null,
+                    ToLogJson::to_log_json,
+                )
+            }
+        }
+
+        pub struct __LogJson<'a, T: ?Sized>(pub &'a T);
+
+        pub trait __ViaLogJson {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue;
+        }
+
+        impl<T: ToLogJson + ?Sized> __ViaLogJson for __LogJson<'_, T> {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.0.to_log_json()
+            }
+        }
+
+        pub trait __ViaToJson {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue;
+        }
+
+        impl<T: crate::mik_sdk::json::ToJson + ?Sized> __ViaToJson for &__LogJson<'_, T> {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.0.to_json()
+            }
         }
 
         pub trait OpenApiSchema {
@@ -170,6 +353,9 @@ mod mik_sdk {
             fn openapi_path_params() -> &'static str {
                 "[]"
             }
+            fn openapi_header_params() -> &'static str {
+                "[]"
+            }
             fn nested_schemas() -> &'static str {
                 ""
             }
@@ -333,6 +519,13 @@ mod mik_sdk {
                 }
             }
 
+            pub fn uint(&self) -> Option<u64> {
+                match &self.data {
+                    JsonData::Int(n) => u64::try_from(*n).ok(),
+                    _ => None,
+                }
+            }
+
             pub const fn float(&self) -> Option<f64> {
                 match &self.data {
                     JsonData::Float(n) => Some(*n),
@@ -401,6 +594,11 @@ mod mik_sdk {
             JsonValue::from_str(s)
         }
 
+        /// Create a JSON null value
+        pub const fn null() -> JsonValue {
+            JsonValue::null()
+        }
+
         /// Create a JSON object builder
         pub fn obj() -> JsonValue {
             JsonValue::from_object(HashMap::new())
@@ -482,9 +680,29 @@ mod mik_sdk {
             }
         }
     }
+
+    pub mod middleware {
+        pub trait HttpResponse: Sized {
+            fn from_json(status: u16, body: super::json::JsonValue) -> Self;
+        }
+    }
+
+    pub trait IntoResponse<R> {
+        fn into_response(self) -> R;
+    }
+
+    pub mod response {
+        pub trait Returns<T> {}
+    }
+
+    impl<R: middleware::HttpResponse> IntoResponse<R> for json::JsonValue {
+        fn into_response(self) -> R {
+            R::from_json(200, self)
+        }
+    }
 }
 
-use mik_sdk_macros::{Path, Query, Type};
+use mik_sdk_macros::{Form, Headers, Path, Query, Type};
 use std::collections::HashMap;
 
 // =============================================================================
@@ -558,6 +776,114 @@ fn test_type_derive_missing_required_field_returns_error() {
     assert_eq!(err.field, "name");
 }
 
+#[test]
+fn test_type_derive_nested_errors_include_path() {
+    #[derive(Type, Debug)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Type, Debug)]
+    struct Customer {
+        address: Address,
+    }
+
+    // Missing nested object
+    let json = mik_sdk::json::JsonValue::from_object(HashMap::new());
+    let err = <Customer as mik_sdk::typed::FromJson>::from_json(&json).unwrap_err();
+    assert_eq!(err.field, "address");
+
+    // Missing field inside the nested object
+    let mut obj = HashMap::new();
+    obj.insert(
+        "address".to_string(),
+        mik_sdk::json::JsonValue::from_object(HashMap::new()),
+    );
+    let json = mik_sdk::json::JsonValue::from_object(obj);
+    let err = <Customer as mik_sdk::typed::FromJson>::from_json(&json).unwrap_err();
+    assert_eq!(err.field, "address.city");
+}
+
+/// Test that #[field(redact)] masks values in log output and parse errors.
+#[test]
+fn test_type_derive_redact() {
+    use mik_sdk::json::ToJson;
+    use mik_sdk::typed::{FromJson, ToLogJson};
+
+    #[derive(Type, Debug)]
+    enum Plan {
+        Free,
+        Pro,
+    }
+
+    #[derive(Type, Debug)]
+    struct Credentials {
+        #[field(redact)]
+        token: String,
+    }
+
+    #[derive(Type, Debug)]
+    struct Signup {
+        email: String,
+        #[field(redact)]
+        password: String,
+        #[field(redact)]
+        recovery_code: Option<String>,
+        #[field(redact)]
+        plan: Plan,
+        credentials: Credentials,
+    }
+
+    let signup = Signup {
+        email: "ada@example.com".to_string(),
+        password: "hunter2".to_string(),
+        recovery_code: None,
+        plan: Plan::Pro,
+        credentials: Credentials {
+            token: "s3cr3t".to_string(),
+        },
+    };
+    let log = signup.to_log_json();
+    assert_eq!(log.get("email").str().as_deref(), Some("ada@example.com"));
+    assert_eq!(log.get("password").str().as_deref(), Some("[REDACTED]"));
+    assert!(log.get("recovery_code").is_null());
+    assert_eq!(log.get("plan").str().as_deref(), Some("[REDACTED]"));
+    assert_eq!(
+        log.get("credentials").get("token").str().as_deref(),
+        Some("[REDACTED]")
+    );
+    // Responses keep the values
+    assert_eq!(
+        signup.to_json().get("password").str().as_deref(),
+        Some("hunter2")
+    );
+    assert_eq!(Plan::Free.to_log_json().str().as_deref(), Some("free"));
+
+    // A rejected value of a redacted field is not echoed
+    let mut credentials = HashMap::new();
+    credentials.insert("token".to_string(), mik_sdk::json::JsonValue::from_str("t"));
+    let mut obj = HashMap::new();
+    obj.insert(
+        "email".to_string(),
+        mik_sdk::json::JsonValue::from_str("a@b.c"),
+    );
+    obj.insert(
+        "password".to_string(),
+        mik_sdk::json::JsonValue::from_str("p"),
+    );
+    obj.insert(
+        "plan".to_string(),
+        mik_sdk::json::JsonValue::from_str("s3cr3t-plan"),
+    );
+    obj.insert(
+        "credentials".to_string(),
+        mik_sdk::json::JsonValue::from_object(credentials),
+    );
+    let err = Signup::from_json(&mik_sdk::json::JsonValue::from_object(obj)).unwrap_err();
+    assert!(err.field.starts_with("plan"));
+    assert!(!err.message.contains("s3cr3t-plan"));
+}
+
 #[test]
 fn test_type_derive_with_constraints() {
     #[derive(Type)]
@@ -579,6 +905,283 @@ fn test_type_derive_with_constraints() {
     assert!(<ConstrainedInput as mik_sdk::typed::Validate>::validate(&input).is_err());
 }
 
+#[test]
+fn test_type_derive_validate_json_collects_violations() {
+    use mik_sdk::json::JsonValue;
+
+    #[derive(Type)]
+    struct SignupInput {
+        #[field(min = 3, max = 10)]
+        username: String,
+        #[field(min = 18)]
+        age: i32,
+        #[field(rename = "referrer", max = 4)]
+        referral_code: Option<String>,
+        #[field(min = 1)]
+        tags: Vec<String>,
+    }
+
+    let mut obj = HashMap::new();
+    obj.insert("username".to_string(), JsonValue::from_str("al"));
+    obj.insert("age".to_string(), JsonValue::from_str("old"));
+    obj.insert("referrer".to_string(), JsonValue::from_str("toolong"));
+    obj.insert("tags".to_string(), JsonValue::from_array(vec![]));
+    let errors = SignupInput::validate(&JsonValue::from_object(obj)).unwrap_err();
+    let violations: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|e| (e.field.as_str(), e.constraint.as_str()))
+        .collect();
+    assert_eq!(
+        violations,
+        [
+            ("username", "min"),
+            ("age", "type"),
+            ("referrer", "max"),
+            ("tags", "min")
+        ]
+    );
+
+    // Missing required fields are reported, missing optional ones are not
+    let errors = SignupInput::validate(&JsonValue::from_object(HashMap::new())).unwrap_err();
+    let missing: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(missing, ["username", "age", "tags"]);
+    assert!(errors.iter().all(|e| e.constraint == "required"));
+
+    let mut obj = HashMap::new();
+    obj.insert("username".to_string(), JsonValue::from_str("alice"));
+    obj.insert("age".to_string(), JsonValue::from_int(30));
+    obj.insert(
+        "tags".to_string(),
+        JsonValue::from_array(vec![JsonValue::from_str("new")]),
+    );
+    assert!(SignupInput::validate(&JsonValue::from_object(obj)).is_ok());
+}
+
+#[test]
+fn test_type_derive_format_checks() {
+    use mik_sdk::json::JsonValue;
+    use mik_sdk::typed::Validate;
+
+    #[derive(Type)]
+    struct Contact {
+        #[field(format = "email")]
+        email: String,
+        #[field(format = "ipv4")]
+        last_ip: Option<String>,
+        #[field(format = "date-time")]
+        seen_at: String,
+    }
+
+    let contact = |email: &str, last_ip: Option<&str>| Contact {
+        email: email.to_string(),
+        last_ip: last_ip.map(str::to_string),
+        seen_at: "not checked".to_string(),
+    };
+
+    assert!(contact("a@example.com", None).validate().is_ok());
+    assert!(
+        contact("a@example.com", Some("10.0.0.1"))
+            .validate()
+            .is_ok()
+    );
+
+    let err = contact("nope", None).validate().unwrap_err();
+    assert_eq!(
+        (err.field.as_str(), err.constraint.as_str()),
+        ("email", "format")
+    );
+    let err = contact("a@example.com", Some("10.0.0"))
+        .validate()
+        .unwrap_err();
+    assert_eq!(
+        (err.field.as_str(), err.constraint.as_str()),
+        ("last_ip", "format")
+    );
+
+    let mut obj = HashMap::new();
+    obj.insert("email".to_string(), JsonValue::from_str("nope"));
+    obj.insert("last_ip".to_string(), JsonValue::from_str("::1"));
+    obj.insert("seen_at".to_string(), JsonValue::from_str("whenever"));
+    let errors = Contact::validate(&JsonValue::from_object(obj)).unwrap_err();
+    let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, ["email", "last_ip"]);
+    assert!(errors.iter().all(|e| e.constraint == "format"));
+}
+
+#[test]
+fn test_type_derive_pattern_checks() {
+    use mik_sdk::json::JsonValue;
+    use mik_sdk::typed::Validate;
+
+    #[derive(Type)]
+    struct Article {
+        #[field(pattern = r"^[a-z0-9]+(-[a-z0-9]+)*$")]
+        slug: String,
+        #[field(pattern = r"(?i)^(draft|published)$")]
+        status: Option<String>,
+        #[field(pattern = r"^\p{Lu}\w{0,3}$")]
+        code: String,
+    }
+
+    let article = |slug: &str, status: Option<&str>, code: &str| Article {
+        slug: slug.to_string(),
+        status: status.map(str::to_string),
+        code: code.to_string(),
+    };
+
+    assert!(
+        article("hello-world-2", Some("Published"), "Été")
+            .validate()
+            .is_ok()
+    );
+    assert!(article("a", None, "X").validate().is_ok());
+
+    for (bad, field) in [
+        (article("Hello", None, "X"), "slug"),
+        (article("trailing-", None, "X"), "slug"),
+        (article("ok", Some("archived"), "X"), "status"),
+        (article("ok", Some("draft!"), "X"), "status"),
+        (article("ok", None, "x"), "code"),
+        (article("ok", None, "Xabcd"), "code"),
+    ] {
+        let err = bad.validate().unwrap_err();
+        assert_eq!(
+            (err.field.as_str(), err.constraint.as_str()),
+            (field, "pattern")
+        );
+    }
+
+    let mut obj = HashMap::new();
+    obj.insert("slug".to_string(), JsonValue::from_str("Not A Slug"));
+    obj.insert("status".to_string(), JsonValue::from_str("draft"));
+    obj.insert("code".to_string(), JsonValue::from_str("lower"));
+    let errors = Article::validate(&JsonValue::from_object(obj)).unwrap_err();
+    let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, ["slug", "code"]);
+}
+
+#[test]
+fn test_type_derive_struct_level_rules() {
+    use mik_sdk::typed::{Validate, ValidationError};
+
+    fn check_guests(booking: &Booking) -> Result<(), ValidationError> {
+        if booking.guests > 2 && booking.room == "single" {
+            return Err(ValidationError::custom(
+                "guests",
+                "capacity",
+                "single rooms hold two guests",
+            ));
+        }
+        Ok(())
+    }
+
+    #[derive(Type)]
+    #[validate(start < end)]
+    #[validate(min_nights <= max_nights)]
+    #[validate(custom = "check_guests")]
+    struct Booking {
+        start: i64,
+        end: i64,
+        min_nights: Option<i32>,
+        max_nights: Option<i32>,
+        #[field(min = 1)]
+        guests: i32,
+        room: String,
+    }
+
+    let booking = |start, end, min_nights, max_nights, guests, room: &str| Booking {
+        start,
+        end,
+        min_nights,
+        max_nights,
+        guests,
+        room: room.to_string(),
+    };
+
+    assert!(
+        booking(1, 2, Some(1), Some(3), 2, "single")
+            .validate()
+            .is_ok()
+    );
+
+    let err = booking(5, 5, None, None, 1, "double")
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.field, "start");
+    assert_eq!(err.constraint, "compare");
+    assert_eq!(err.message, "`start` must be less than `end`");
+
+    // Comparisons skip unset optional fields
+    assert!(booking(1, 2, Some(7), None, 1, "double").validate().is_ok());
+    let err = booking(1, 2, Some(7), Some(3), 1, "double")
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.message, "`min_nights` must be at most `max_nights`");
+
+    let err = booking(1, 2, None, None, 3, "single")
+        .validate()
+        .unwrap_err();
+    assert_eq!(
+        (err.field.as_str(), err.constraint.as_str()),
+        ("guests", "capacity")
+    );
+
+    // Field constraints run before struct-level rules
+    let err = booking(5, 1, None, None, 0, "single")
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.constraint, "min");
+}
+
+#[test]
+fn test_type_derive_validate_all_collects_per_field() {
+    use mik_sdk::typed::Validate;
+
+    #[derive(Type)]
+    #[validate(start < end)]
+    struct Window {
+        #[field(min = 3, format = "email")]
+        owner: String,
+        #[field(max = 10)]
+        slots: i32,
+        start: i64,
+        end: i64,
+    }
+
+    let window = Window {
+        owner: "x".to_string(),
+        slots: 12,
+        start: 9,
+        end: 1,
+    };
+    // validate() stops at the first failure
+    assert_eq!(window.validate().unwrap_err().field, "owner");
+
+    let errors = window.validate_all().unwrap_err();
+    let violations: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|e| (e.field.as_str(), e.constraint.as_str()))
+        .collect();
+    // One violation per field (min wins over format), then the struct rule
+    assert_eq!(
+        violations,
+        [("owner", "min"), ("slots", "max"), ("start", "compare")]
+    );
+    assert_eq!(
+        errors.rejected[0].as_ref().unwrap().str(),
+        Some("x".to_string())
+    );
+    assert!(errors.rejected[2].is_none());
+
+    let window = Window {
+        owner: "a@example.com".to_string(),
+        slots: 4,
+        start: 1,
+        end: 9,
+    };
+    assert!(window.validate_all().is_ok());
+}
+
 // =============================================================================
 // TYPED QUERY INPUT TESTS (Query derive)
 // =============================================================================
@@ -643,6 +1246,63 @@ fn test_query_derive_invalid_number_returns_error() {
     assert_eq!(err.field, "page");
 }
 
+#[test]
+fn test_query_derive_struct_level_rules() {
+    #[derive(Query)]
+    #[validate(from <= to)]
+    struct RangeQuery {
+        from: Option<u32>,
+        to: Option<u32>,
+    }
+
+    let parse = |pairs: &[(&str, &str)]| {
+        let params: Vec<(String, String)> = pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        <RangeQuery as mik_sdk::typed::FromQuery>::from_query(&params).unwrap()
+    };
+
+    let query = parse(&[("from", "3"), ("to", "1")]);
+    let err = <RangeQuery as mik_sdk::typed::Validate>::validate(&query).unwrap_err();
+    assert_eq!(err.field, "from");
+    assert_eq!(err.message, "`from` must be at most `to`");
+
+    let query = parse(&[("from", "3")]);
+    assert!(<RangeQuery as mik_sdk::typed::Validate>::validate(&query).is_ok());
+}
+
+#[test]
+fn test_query_derive_format_and_pattern_checks() {
+    #[derive(Query)]
+    struct LookupQuery {
+        #[field(format = "uuid")]
+        id: String,
+        #[field(pattern = "^(asc|desc)$")]
+        order: Option<String>,
+        #[field(format = "ipv6")]
+        addr: Option<String>,
+    }
+
+    let params = vec![
+        (
+            "id".to_string(),
+            "550e8400-e29b-41d4-a716-446655440000".to_string(),
+        ),
+        ("addr".to_string(), "::1".to_string()),
+    ];
+    let query = <LookupQuery as mik_sdk::typed::FromQuery>::from_query(&params).unwrap();
+    assert!(<LookupQuery as mik_sdk::typed::Validate>::validate(&query).is_ok());
+
+    let params = vec![("id".to_string(), "42".to_string())];
+    let query = <LookupQuery as mik_sdk::typed::FromQuery>::from_query(&params).unwrap();
+    let err = <LookupQuery as mik_sdk::typed::Validate>::validate(&query).unwrap_err();
+    assert_eq!(
+        (err.field.as_str(), err.constraint.as_str()),
+        ("id", "format")
+    );
+}
+
 #[test]
 fn test_query_derive_generates_openapi_schema() {
     #[derive(Query)]
@@ -659,6 +1319,192 @@ fn test_query_derive_generates_openapi_schema() {
     assert!(schema.contains("limit"));
 }
 
+// =============================================================================
+// TYPED FORM INPUT TESTS (Form derive)
+// =============================================================================
+
+fn form_params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+        .collect()
+}
+
+#[test]
+fn test_form_derive_required_and_defaults() {
+    #[derive(Form, Debug)]
+    struct LoginForm {
+        username: String,
+        #[field(default = 1)]
+        attempts: u32,
+        next: Option<String>,
+    }
+
+    let form = <LoginForm as mik_sdk::typed::FromForm>::from_form(&form_params(&[
+        ("username", "alice"),
+        ("attempts", "3"),
+        ("next", "/home"),
+    ]))
+    .unwrap();
+    assert_eq!(form.username, "alice");
+    assert_eq!(form.attempts, 3);
+    assert_eq!(form.next, Some("/home".to_string()));
+
+    // Empty inputs are treated like missing ones
+    let form = <LoginForm as mik_sdk::typed::FromForm>::from_form(&form_params(&[
+        ("username", "alice"),
+        ("attempts", ""),
+        ("next", ""),
+    ]))
+    .unwrap();
+    assert_eq!(form.attempts, 1);
+    assert_eq!(form.next, None);
+
+    let err = <LoginForm as mik_sdk::typed::FromForm>::from_form(&[]).unwrap_err();
+    assert_eq!(err.field, "username");
+}
+
+#[test]
+fn test_form_derive_checkbox_bool() {
+    #[derive(Form)]
+    struct PrefsForm {
+        subscribe: bool,
+        dark_mode: Option<bool>,
+    }
+
+    let form =
+        <PrefsForm as mik_sdk::typed::FromForm>::from_form(&form_params(&[("subscribe", "on")]))
+            .unwrap();
+    assert!(form.subscribe);
+    assert_eq!(form.dark_mode, None);
+
+    // Unchecked checkboxes are not submitted at all
+    let form = <PrefsForm as mik_sdk::typed::FromForm>::from_form(&[]).unwrap();
+    assert!(!form.subscribe);
+
+    let err =
+        <PrefsForm as mik_sdk::typed::FromForm>::from_form(&form_params(&[("subscribe", "maybe")]))
+            .err()
+            .unwrap();
+    assert_eq!(err.field, "subscribe");
+}
+
+#[test]
+fn test_form_derive_validates_constraints() {
+    #[derive(Form, Debug)]
+    struct SignupForm {
+        #[field(min = 3, max = 8)]
+        username: String,
+        #[field(default = 18, min = 13)]
+        age: u32,
+    }
+
+    let err =
+        <SignupForm as mik_sdk::typed::FromForm>::from_form(&form_params(&[("username", "al")]))
+            .unwrap_err();
+    assert_eq!(err.field, "username");
+
+    let err = <SignupForm as mik_sdk::typed::FromForm>::from_form(&form_params(&[
+        ("username", "alice"),
+        ("age", "10"),
+    ]))
+    .unwrap_err();
+    assert_eq!(err.field, "age");
+
+    assert!(
+        <SignupForm as mik_sdk::typed::FromForm>::from_form(&form_params(&[
+            ("username", "alice",)
+        ]))
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_form_derive_generates_openapi_schema() {
+    #[derive(Form)]
+    struct ContactForm {
+        #[field(min = 1, max = 100)]
+        name: String,
+        #[field(rename = "e-mail")]
+        email: Option<String>,
+    }
+
+    let schema = <ContactForm as mik_sdk::typed::OpenApiSchema>::openapi_schema();
+    assert!(schema.contains("\"type\":\"object\""));
+    assert!(schema.contains("\"required\":[\"name\"]"));
+    assert!(schema.contains("\"maxLength\":100"));
+    assert!(schema.contains("\"e-mail\""));
+    assert_eq!(
+        <ContactForm as mik_sdk::typed::OpenApiSchema>::schema_name(),
+        "ContactForm"
+    );
+}
+
+// =============================================================================
+// TYPED HEADER INPUT TESTS (Headers derive)
+// =============================================================================
+
+#[test]
+fn test_headers_derive_maps_field_names() {
+    #[derive(Headers, Debug)]
+    struct ApiHeaders {
+        x_api_key: String,
+        #[field(rename = "X-Request-ID")]
+        request_id: Option<String>,
+        #[field(default = 1)]
+        x_api_version: u32,
+    }
+
+    let headers = vec![
+        ("X-Api-Key".to_string(), "secret".to_string()),
+        ("x-request-id".to_string(), "req-1".to_string()),
+        ("x-api-version".to_string(), "2".to_string()),
+    ];
+    let parsed = <ApiHeaders as mik_sdk::typed::FromHeaders>::from_headers(&headers).unwrap();
+    assert_eq!(parsed.x_api_key, "secret");
+    assert_eq!(parsed.request_id, Some("req-1".to_string()));
+    assert_eq!(parsed.x_api_version, 2);
+
+    let headers = vec![("x-api-key".to_string(), "secret".to_string())];
+    let parsed = <ApiHeaders as mik_sdk::typed::FromHeaders>::from_headers(&headers).unwrap();
+    assert_eq!(parsed.request_id, None);
+    assert_eq!(parsed.x_api_version, 1);
+}
+
+#[test]
+fn test_headers_derive_errors() {
+    #[derive(Headers, Debug)]
+    struct LimitHeaders {
+        x_limit: u32,
+    }
+
+    let err = <LimitHeaders as mik_sdk::typed::FromHeaders>::from_headers(&[]).unwrap_err();
+    assert_eq!(err.field, "x-limit");
+
+    let headers = vec![("x-limit".to_string(), "lots".to_string())];
+    let err = <LimitHeaders as mik_sdk::typed::FromHeaders>::from_headers(&headers).unwrap_err();
+    assert_eq!(err.field, "x-limit");
+}
+
+#[test]
+fn test_headers_derive_generates_openapi_params() {
+    #[derive(Headers)]
+    struct DocumentedHeaders {
+        #[field(docs = "Client API key")]
+        x_api_key: String,
+        x_trace: Option<String>,
+    }
+
+    let params = <DocumentedHeaders as mik_sdk::typed::OpenApiSchema>::openapi_header_params();
+    assert!(params.contains("\"name\":\"x-api-key\""));
+    assert!(params.contains("\"in\":\"header\""));
+    assert!(params.contains("\"description\":\"Client API key\""));
+    assert!(params.contains("\"name\":\"x-trace\",\"required\":false"));
+
+    let schema = <DocumentedHeaders as mik_sdk::typed::OpenApiSchema>::openapi_schema();
+    assert!(schema.contains("\"required\":[\"x-api-key\"]"));
+}
+
 // =============================================================================
 // TYPED PATH INPUT TESTS (Path derive)
 // =============================================================================
@@ -66,6 +66,30 @@ mod mik_sdk {
                     message,
                 }
             }
+
+            pub fn redact(self) -> Self {
+                Self {
+                    field: self.field,
+                    message: "invalid value".to_string(),
+                }
+            }
+
+            pub fn with_path(self, parent: &str) -> Self {
+                Self {
+                    field: format!("{parent}.{}", self.field),
+                    message: self.message,
+                }
+            }
+
+            pub fn field(&self) -> &str {
+                &self.field
+            }
+        }
+
+        impl std::fmt::Display for ParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.message)
+            }
         }
 
         #[derive(Debug, Clone)]
@@ -91,6 +115,30 @@ mod mik_sdk {
                     message: format!("'{field}' must be at most {max}"),
                 }
             }
+
+            pub fn pattern(field: &str, pattern: &str) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: "pattern".to_string(),
+                    message: format!("'{field}' must match {pattern}"),
+                }
+            }
+
+            pub fn format(field: &str, expected: &str) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: "format".to_string(),
+                    message: format!("'{field}' must be a valid {expected}"),
+                }
+            }
+
+            pub fn custom(field: &str, constraint: &str, message: impl Into<String>) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: constraint.to_string(),
+                    message: message.into(),
+                }
+            }
         }
 
         pub trait FromJson: Sized {
@@ -105,8 +153,118 @@ mod mik_sdk {
             fn from_params(params: &HashMap<String, String>) -> Result<Self, ParseError>;
         }
 
+        // Patterns run on the real matcher, the compiled program is the point
+        pub use ::mik_sdk::typed::pattern;
+
+        pub mod format {
+            pub fn is_email(value: &str) -> bool {
+                value.contains('@')
+            }
+
+            pub fn is_uri(value: &str) -> bool {
+                value.contains(':')
+            }
+
+            pub const fn is_uuid(value: &str) -> bool {
+                value.len() == 36
+            }
+
+            pub fn is_ipv4(value: &str) -> bool {
+                value.parse::<std::net::Ipv4Addr>().is_ok()
+            }
+
+            pub fn is_ipv6(value: &str) -> bool {
+                value.parse::<std::net::Ipv6Addr>().is_ok()
+            }
+        }
+
+        #[derive(Default)]
+        pub struct ValidationErrors {
+            pub errors: Vec<ValidationError>,
+            pub rejected: Vec<Option<crate::mik_sdk::json::JsonValue>>,
+        }
+
+        impl ValidationErrors {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            pub fn push(&mut self, error: ValidationError) {
+                self.errors.push(error);
+                self.rejected.push(None);
+            }
+
+            pub fn push_rejected(
+                &mut self,
+                error: ValidationError,
+                rejected: crate::mik_sdk::json::JsonValue,
+            ) {
+                self.errors.push(error);
+                self.rejected.push(Some(rejected));
+            }
+
+            pub fn iter(&self) -> std::slice::Iter<'_, ValidationError> {
+                self.errors.iter()
+            }
+
+            pub fn into_result(self) -> Result<(), Self> {
+                if self.errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(self)
+                }
+            }
+        }
+
         pub trait Validate {
             fn validate(&self) -> Result<(), ValidationError>;
+
+            fn validate_all(&self) -> Result<(), ValidationErrors> {
+                let mut errors = ValidationErrors::new();
+                if let Err(e) = self.validate() {
+                    errors.push(e);
+                }
+                errors.into_result()
+            }
+        }
+
+        pub const REDACTED: &str = "[REDACTED]";
+
+        pub trait ToLogJson: crate::mik_sdk::json::ToJson {
+            fn to_log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.to_json()
+            }
+        }
+
+        impl<T: ToLogJson> ToLogJson for Option<T> {
+            fn to_log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.as_ref().map_or_else(
+                    crate::mik_sdk::json::JsonValue::null,
+                    ToLogJson::to_log_json,
+                )
+            }
+        }
+
+        pub struct __LogJson<'a, T: ?Sized>(pub &'a T);
+
+        pub trait __ViaLogJson {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue;
+        }
+
+        impl<T: ToLogJson + ?Sized> __ViaLogJson for __LogJson<'_, T> {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.0.to_log_json()
+            }
+        }
+
+        pub trait __ViaToJson {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue;
+        }
+
+        impl<T: crate::mik_sdk::json::ToJson + ?Sized> __ViaToJson for &__LogJson<'_, T> {
+            fn __log_json(&self) -> crate::mik_sdk::json::JsonValue {
+                self.0.to_json()
+            }
         }
 
         pub trait OpenApiSchema {
@@ -298,6 +456,13 @@ mod mik_sdk {
                 }
             }
 
+            pub fn uint(&self) -> Option<u64> {
+                match &self.data {
+                    JsonData::Int(n) => u64::try_from(*n).ok(),
+                    _ => None,
+                }
+            }
+
             pub const fn float(&self) -> Option<f64> {
                 match &self.data {
                     JsonData::Float(n) => Some(*n),
@@ -330,6 +495,11 @@ mod mik_sdk {
             JsonValue::from_str(s)
         }
 
+        /// Create a JSON null value
+        pub const fn null() -> JsonValue {
+            JsonValue::null()
+        }
+
         /// Create a JSON object builder
         pub fn obj() -> JsonValue {
             JsonValue::from_object(HashMap::new())
@@ -450,6 +620,26 @@ mod mik_sdk {
             }
         }
     }
+
+    pub mod middleware {
+        pub trait HttpResponse: Sized {
+            fn from_json(status: u16, body: super::json::JsonValue) -> Self;
+        }
+    }
+
+    pub trait IntoResponse<R> {
+        fn into_response(self) -> R;
+    }
+
+    pub mod response {
+        pub trait Returns<T> {}
+    }
+
+    impl<R: middleware::HttpResponse> IntoResponse<R> for json::JsonValue {
+        fn into_response(self) -> R {
+            R::from_json(200, self)
+        }
+    }
 }
 
 // =============================================================================
@@ -495,6 +685,32 @@ fn test_type_derive_basic() {
     assert!(json_str.contains("\"age\":25"));
 }
 
+#[test]
+fn test_type_derive_into_response() {
+    #[derive(Type)]
+    struct Greeting {
+        message: String,
+    }
+
+    struct MockResponse {
+        status: u16,
+        body: mik_sdk::json::JsonValue,
+    }
+
+    impl mik_sdk::middleware::HttpResponse for MockResponse {
+        fn from_json(status: u16, body: mik_sdk::json::JsonValue) -> Self {
+            Self { status, body }
+        }
+    }
+
+    let greeting = Greeting {
+        message: "Hello".to_string(),
+    };
+    let res: MockResponse = mik_sdk::IntoResponse::into_response(greeting);
+    assert_eq!(res.status, 200);
+    assert_eq!(res.body.get("message").str(), Some("Hello".to_string()));
+}
+
 #[test]
 fn test_type_derive_optional_fields() {
     #[derive(Type)]
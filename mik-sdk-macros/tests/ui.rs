@@ -32,4 +32,10 @@ fn ui() {
 
     // DX macro errors (guard!, ensure!)
     t.compile_fail("tests/ui/dx/*.rs");
+
+    // Jobs macro errors
+    t.compile_fail("tests/ui/jobs/*.rs");
+
+    // Consumers macro errors
+    t.compile_fail("tests/ui/consumers/*.rs");
 }
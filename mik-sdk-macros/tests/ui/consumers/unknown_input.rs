@@ -0,0 +1,12 @@
+use mik_sdk_macros::consumers;
+
+struct OrderQuery;
+
+fn handle_order(_query: OrderQuery) {}
+
+// Error: messages have no query string
+consumers! {
+    "orders" => handle_order(query: OrderQuery),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::Form;
+
+// Error: Form derive only supports structs
+#[derive(Form)]
+enum LoginForm {
+    Password,
+    Token,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::Headers;
+
+// Error: Headers derive only supports structs
+#[derive(Headers)]
+enum ApiHeaders {
+    Key,
+    Token,
+}
+
+fn main() {}
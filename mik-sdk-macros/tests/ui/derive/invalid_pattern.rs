@@ -0,0 +1,10 @@
+use mik_sdk_macros::Type;
+
+// Error: #[field(pattern)] regexes are compiled when the derive expands
+#[derive(Type)]
+struct Slug {
+    #[field(pattern = "^[a-z+$")]
+    value: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mik_sdk_macros::Type;
+
+// Error: #[validate] comparisons must name fields of the struct
+#[derive(Type)]
+#[validate(start < finish)]
+struct Range {
+    start: i64,
+    end: i64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::jobs;
+
+fn cleanup(_timestamp: u64) {}
+
+// Error: cron schedules have 5 or 6 fields
+jobs! {
+    "*/5 * * *" => cleanup,
+}
+
+fn main() {}
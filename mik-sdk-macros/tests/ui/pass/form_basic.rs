@@ -0,0 +1,15 @@
+// Pass: Basic derive Form usage
+use mik_sdk_macros::Form;
+
+#[derive(Form)]
+pub struct LoginForm {
+    #[field(min = 3, max = 32)]
+    pub username: String,
+    pub password: String,
+    pub remember_me: bool,
+    #[field(default = 1)]
+    pub attempts: u32,
+    pub next: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Pass: Basic derive Headers usage
+use mik_sdk_macros::Headers;
+
+#[derive(Headers)]
+pub struct ApiHeaders {
+    #[field(docs = "Client API key")]
+    pub x_api_key: String,
+    #[field(rename = "X-Request-ID")]
+    pub request_id: Option<String>,
+    #[field(default = 1)]
+    pub x_api_version: u32,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// Pass: derive Type with map fields
+use std::collections::{BTreeMap, HashMap};
+
+use mik_sdk_macros::Type;
+
+#[derive(Type)]
+pub struct Label {
+    pub color: String,
+}
+
+#[derive(Type)]
+pub struct Metadata {
+    pub attributes: HashMap<String, String>,
+    pub counts: BTreeMap<String, i64>,
+    pub labels: HashMap<String, Label>,
+    pub extra: Option<HashMap<String, Vec<String>>>,
+}
+
+fn main() {}
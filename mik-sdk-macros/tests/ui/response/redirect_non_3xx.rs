@@ -0,0 +1,6 @@
+use mik_sdk_macros::redirect;
+
+// Error: redirect! only accepts 3xx status codes
+fn main() {
+    let _resp = redirect!(200, "/login");
+}
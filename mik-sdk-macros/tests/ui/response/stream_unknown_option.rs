@@ -0,0 +1,6 @@
+use mik_sdk_macros::stream;
+
+fn main() {
+    // Error: unknown option (should be content_type)
+    let _ = stream!(body, content: "text/csv");
+}
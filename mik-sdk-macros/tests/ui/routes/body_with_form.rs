@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn login() -> String { String::new() }
+
+// Error: a route reads its body either as JSON or as a form, not both
+routes! {
+    POST "/login" => login(body: LoginJson, form: LoginForm),
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use mik_sdk_macros::routes;
+
+fn list() -> String { String::new() }
+
+// Error: the docs page applies to the whole router, not a group
+routes! {
+    #[before(require_auth)]
+    {
+        docs "/docs",
+        GET "/admin" => list,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn list() -> String { String::new() }
+
+// Error: the docs page would shadow an existing GET route
+routes! {
+    docs "/docs",
+    GET "/docs" => list,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: CORS is configured once per routes! block
+routes! {
+    #![cors]
+    #![cors(Cors::new())]
+    GET "/" => home,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mik_sdk_macros::routes;
+
+fn not_found() -> String { String::new() }
+fn list() -> String { String::new() }
+
+// Error: only one fallback handler per router
+routes! {
+    fallback => not_found,
+    GET "/users" => list,
+    fallback => not_found,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: tracing is enabled once per routes! block
+routes! {
+    #![trace]
+    #![trace]
+    GET "/" => home,
+}
+
+fn main() {}
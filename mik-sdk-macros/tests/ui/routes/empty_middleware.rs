@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: middleware attributes need at least one function
+routes! {
+    #[before()]
+    GET "/" => home,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use mik_sdk_macros::routes;
+
+fn not_found() -> String { String::new() }
+fn list() -> String { String::new() }
+
+// Error: fallback handlers apply to the whole router, not a group
+routes! {
+    #[before(require_auth)]
+    {
+        fallback => not_found,
+        GET "/admin" => list,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn ready() -> String { String::new() }
+
+// Error: the readiness endpoint would shadow an existing GET route
+routes! {
+    health "/healthz",
+    GET "/healthz/ready" => ready,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: route groups only take before/after hooks and a tag
+routes! {
+    #[stream]
+    {
+        GET "/" => home,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: the OpenAPI route is a path and must start with '/'
+routes! {
+    #![config(openapi = "openapi.json")]
+    GET "/" => home,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: trailing_slash must be "strict", "ignore" or "redirect"
+routes! {
+    #![config(trailing_slash = "append")]
+    GET "/" => home,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+fn stats() -> String { String::new() }
+
+// Error: the metrics route collides with a GET route
+routes! {
+    #![config(metrics)]
+    GET "/" => home,
+    GET "/metrics" => stats,
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use handler::Guest;
+use mik_sdk::Request;
+use mik_sdk_macros::{Type, routes};
+
+// Minimal stand-ins for the WIT bindings a handler crate generates
+mod bindings {
+    macro_rules! export { ($($t:tt)*) => {}; }
+    pub(crate) use export;
+}
+mod handler {
+    #[derive(Clone, Copy)]
+    pub enum Method { Get, Post, Put, Patch, Delete, Head, Options }
+    pub enum BodyMode { Buffered, Streaming }
+    pub struct Response { pub status: u16, pub headers: Vec<(String, String)>, pub body: Option<Vec<u8>> }
+    pub struct RequestData { pub method: Method, pub path: String, pub headers: Vec<(String, String)>, pub body: Option<Vec<u8>> }
+    pub enum StreamError { Closed, Other }
+    pub struct InputStream;
+    impl InputStream { pub fn blocking_read(&self, _n: u64) -> Result<Vec<u8>, StreamError> { Err(StreamError::Closed) } }
+    pub trait GuestResponseStream { fn next_chunk(&self) -> Option<Vec<u8>>; }
+    pub struct ResponseStream;
+    impl ResponseStream { pub fn new<T: GuestResponseStream>(_t: T) -> Self { Self } }
+    pub trait Guest {
+        type ResponseStream: GuestResponseStream;
+        fn handle(r: RequestData) -> Response;
+        fn select_body_mode(m: Method, p: String) -> BodyMode;
+        fn take_response_stream() -> Option<ResponseStream>;
+        fn handle_streaming(r: RequestData, b: InputStream) -> Response;
+    }
+}
+
+#[derive(Type)]
+pub struct User {
+    pub name: String,
+}
+
+#[derive(Type)]
+pub struct Post {
+    pub title: String,
+}
+
+fn get_user(_req: &Request) -> Result<Post, mik_sdk::Error> {
+    Ok(Post { title: String::new() })
+}
+
+// Error: the handler returns Post but the route declares -> User
+routes! {
+    GET "/users/{id}" => get_user -> User,
+}
+
+fn main() {}
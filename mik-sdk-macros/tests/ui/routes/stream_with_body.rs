@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn upload() -> String { String::new() }
+
+// Error: #[stream] routes receive the body as a BodyStream, not a typed body
+routes! {
+    #[stream]
+    POST "/files" => upload(body: FileMeta),
+}
+
+fn main() {}
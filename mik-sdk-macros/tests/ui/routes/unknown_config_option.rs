@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: #![config(...)] only accepts trailing_slash and case_insensitive
+routes! {
+    #![config(strict_query)]
+    GET "/" => home,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn echo() -> String { String::new() }
+
+// Error: content types are json, form, multipart, text, html, xml, binary or a "type/subtype" string
+routes! {
+    POST "/echo" (jsn) => echo,
+}
+
+fn main() {}
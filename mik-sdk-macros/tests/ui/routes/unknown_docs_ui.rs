@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn list() -> String { String::new() }
+
+// Error: only `swagger` and `redoc` pages are available
+routes! {
+    docs "/docs" => rapidoc,
+    GET "/users" => list,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn not_found() -> String { String::new() }
+
+// Error: only `fallback` and `error` entries use `name => handler`
+routes! {
+    missing => not_found,
+}
+
+fn main() {}
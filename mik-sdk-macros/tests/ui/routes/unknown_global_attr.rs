@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: only #![before(...)] and #![after(...)] are accepted as routes! inner attributes
+routes! {
+    #![around(log_request)]
+    GET "/" => home,
+}
+
+fn main() {}
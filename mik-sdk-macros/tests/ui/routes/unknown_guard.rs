@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn admin() -> String { String::new() }
+
+// Error: route guards are role or permission
+routes! {
+    GET "/admin" [rol = "admin"] => admin,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn get_user() -> String { String::new() }
+
+// Error: path constraints are int, uint, uuid, alpha or alnum
+routes! {
+    GET "/users/{id:number}" => get_user,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn chat() -> String { String::new() }
+
+// Error: ws routes are fixed paths; path parameters are not supported
+routes! {
+    ws "/rooms/{id}" => chat(),
+}
+
+fn main() {}
@@ -405,6 +405,41 @@ fn bench_json_path_extraction(c: &mut Criterion) {
     group.finish();
 }
 
+// =============================================================================
+// Lazy vs Eager Benchmarks
+// =============================================================================
+
+/// Parse and read two fields, the way a handler reads a request body.
+fn bench_json_lazy_vs_eager(c: &mut Criterion) {
+    let mut group = c.benchmark_group("json_lazy_vs_eager");
+
+    let medium = medium_json().to_vec();
+    let very_large = very_large_json();
+    for (size, data) in [("medium_500B", &medium), ("very_large_50KB", &very_large)] {
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("lazy", size), data, |b, data| {
+            b.iter(|| {
+                let value = json::try_parse(black_box(data)).unwrap();
+                (
+                    value.path_str(&["user", "name"]),
+                    value.path_int(&["pagination", "page"]),
+                )
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("eager", size), data, |b, data| {
+            b.iter(|| {
+                let value = json::try_parse_full(black_box(data)).unwrap();
+                (
+                    value.path_str(&["user", "name"]),
+                    value.path_int(&["pagination", "page"]),
+                )
+            });
+        });
+    }
+
+    group.finish();
+}
+
 // =============================================================================
 // JSON Serialization Benchmarks
 // =============================================================================
@@ -463,6 +498,7 @@ criterion_group!(
     bench_json_obj_builder,
     bench_json_arr_builder,
     bench_json_path_extraction,
+    bench_json_lazy_vs_eager,
     bench_json_serialization,
 );
 
@@ -0,0 +1,251 @@
+#![allow(
+    missing_docs,
+    dead_code,
+    clippy::unused_self, // Stand-in bindings mirror the generated signatures
+    clippy::missing_const_for_fn
+)]
+//! Benchmarks for `routes!` dispatch on a 100-route table.
+//!
+//! Run with: cargo bench -p mik-sdk -- routing
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use mik_sdk::prelude::*;
+use std::hint::black_box;
+
+// =============================================================================
+// Host Bindings Stand-in
+// =============================================================================
+
+// routes! expects the cargo-component generated `bindings` and `handler`
+// modules; these minimal stand-ins let the generated router run natively.
+mod bindings {
+    macro_rules! export {
+        ($($t:tt)*) => {};
+    }
+    pub(crate) use export;
+}
+
+mod handler {
+    #[derive(Clone, Copy)]
+    pub enum Method {
+        Get,
+        Post,
+        Put,
+        Patch,
+        Delete,
+        Head,
+        Options,
+    }
+
+    pub enum BodyMode {
+        Buffered,
+        Streaming,
+    }
+
+    pub struct Response {
+        pub status: u16,
+        pub headers: Vec<(String, String)>,
+        pub body: Option<Vec<u8>>,
+    }
+
+    pub struct RequestData {
+        pub method: Method,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: Option<Vec<u8>>,
+    }
+
+    pub enum StreamError {
+        Closed,
+    }
+
+    pub struct InputStream;
+
+    impl InputStream {
+        pub const fn blocking_read(&self, _len: u64) -> Result<Vec<u8>, StreamError> {
+            Err(StreamError::Closed)
+        }
+    }
+
+    pub trait GuestResponseStream {
+        fn next_chunk(&self) -> Option<Vec<u8>>;
+    }
+
+    pub struct ResponseStream;
+
+    impl ResponseStream {
+        pub fn new<T: GuestResponseStream>(_stream: T) -> Self {
+            Self
+        }
+    }
+
+    pub trait Guest {
+        type ResponseStream: GuestResponseStream;
+        fn handle(request: RequestData) -> Response;
+        fn select_body_mode(method: Method, path: String) -> BodyMode;
+        fn take_response_stream() -> Option<ResponseStream>;
+        fn handle_streaming(request: RequestData, body: InputStream) -> Response;
+    }
+}
+
+use handler::Guest;
+
+fn respond(_req: &Request) -> handler::Response {
+    handler::Response {
+        status: 204,
+        headers: vec![],
+        body: None,
+    }
+}
+
+// 25 resources x 4 routes
+routes! {
+    GET "/resource0" => respond,
+    POST "/resource0" => respond,
+    GET "/resource0/{id}" => respond,
+    DELETE "/resource0/{id}" => respond,
+    GET "/resource1" => respond,
+    POST "/resource1" => respond,
+    GET "/resource1/{id}" => respond,
+    DELETE "/resource1/{id}" => respond,
+    GET "/resource2" => respond,
+    POST "/resource2" => respond,
+    GET "/resource2/{id}" => respond,
+    DELETE "/resource2/{id}" => respond,
+    GET "/resource3" => respond,
+    POST "/resource3" => respond,
+    GET "/resource3/{id}" => respond,
+    DELETE "/resource3/{id}" => respond,
+    GET "/resource4" => respond,
+    POST "/resource4" => respond,
+    GET "/resource4/{id}" => respond,
+    DELETE "/resource4/{id}" => respond,
+    GET "/resource5" => respond,
+    POST "/resource5" => respond,
+    GET "/resource5/{id}" => respond,
+    DELETE "/resource5/{id}" => respond,
+    GET "/resource6" => respond,
+    POST "/resource6" => respond,
+    GET "/resource6/{id}" => respond,
+    DELETE "/resource6/{id}" => respond,
+    GET "/resource7" => respond,
+    POST "/resource7" => respond,
+    GET "/resource7/{id}" => respond,
+    DELETE "/resource7/{id}" => respond,
+    GET "/resource8" => respond,
+    POST "/resource8" => respond,
+    GET "/resource8/{id}" => respond,
+    DELETE "/resource8/{id}" => respond,
+    GET "/resource9" => respond,
+    POST "/resource9" => respond,
+    GET "/resource9/{id}" => respond,
+    DELETE "/resource9/{id}" => respond,
+    GET "/resource10" => respond,
+    POST "/resource10" => respond,
+    GET "/resource10/{id}" => respond,
+    DELETE "/resource10/{id}" => respond,
+    GET "/resource11" => respond,
+    POST "/resource11" => respond,
+    GET "/resource11/{id}" => respond,
+    DELETE "/resource11/{id}" => respond,
+    GET "/resource12" => respond,
+    POST "/resource12" => respond,
+    GET "/resource12/{id}" => respond,
+    DELETE "/resource12/{id}" => respond,
+    GET "/resource13" => respond,
+    POST "/resource13" => respond,
+    GET "/resource13/{id}" => respond,
+    DELETE "/resource13/{id}" => respond,
+    GET "/resource14" => respond,
+    POST "/resource14" => respond,
+    GET "/resource14/{id}" => respond,
+    DELETE "/resource14/{id}" => respond,
+    GET "/resource15" => respond,
+    POST "/resource15" => respond,
+    GET "/resource15/{id}" => respond,
+    DELETE "/resource15/{id}" => respond,
+    GET "/resource16" => respond,
+    POST "/resource16" => respond,
+    GET "/resource16/{id}" => respond,
+    DELETE "/resource16/{id}" => respond,
+    GET "/resource17" => respond,
+    POST "/resource17" => respond,
+    GET "/resource17/{id}" => respond,
+    DELETE "/resource17/{id}" => respond,
+    GET "/resource18" => respond,
+    POST "/resource18" => respond,
+    GET "/resource18/{id}" => respond,
+    DELETE "/resource18/{id}" => respond,
+    GET "/resource19" => respond,
+    POST "/resource19" => respond,
+    GET "/resource19/{id}" => respond,
+    DELETE "/resource19/{id}" => respond,
+    GET "/resource20" => respond,
+    POST "/resource20" => respond,
+    GET "/resource20/{id}" => respond,
+    DELETE "/resource20/{id}" => respond,
+    GET "/resource21" => respond,
+    POST "/resource21" => respond,
+    GET "/resource21/{id}" => respond,
+    DELETE "/resource21/{id}" => respond,
+    GET "/resource22" => respond,
+    POST "/resource22" => respond,
+    GET "/resource22/{id}" => respond,
+    DELETE "/resource22/{id}" => respond,
+    GET "/resource23" => respond,
+    POST "/resource23" => respond,
+    GET "/resource23/{id}" => respond,
+    DELETE "/resource23/{id}" => respond,
+    GET "/resource24" => respond,
+    POST "/resource24" => respond,
+    GET "/resource24/{id}" => respond,
+    DELETE "/resource24/{id}" => respond,
+}
+
+// =============================================================================
+// Dispatch Benchmarks
+// =============================================================================
+
+fn dispatch(method: handler::Method, path: &str) -> u16 {
+    Handler::handle(handler::RequestData {
+        method,
+        path: path.to_string(),
+        headers: vec![],
+        body: None,
+    })
+    .status
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("routing");
+
+    // First route in the table
+    group.bench_function("first_route", |b| {
+        b.iter(|| dispatch(handler::Method::Get, black_box("/resource0")));
+    });
+
+    // Route in the middle of the table
+    group.bench_function("middle_route", |b| {
+        b.iter(|| dispatch(handler::Method::Get, black_box("/resource12/42")));
+    });
+
+    // Last route in the table
+    group.bench_function("last_route", |b| {
+        b.iter(|| dispatch(handler::Method::Delete, black_box("/resource24/42")));
+    });
+
+    // No route matches (404)
+    group.bench_function("not_found", |b| {
+        b.iter(|| dispatch(handler::Method::Get, black_box("/missing/42")));
+    });
+
+    group.finish();
+}
+
+// =============================================================================
+// Main
+// =============================================================================
+
+criterion_group!(benches, bench_dispatch);
+
+criterion_main!(benches);
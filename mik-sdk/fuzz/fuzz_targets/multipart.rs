@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // First line is the Content-Type header, the rest is the body
+    mik_sdk::fuzzing::parse_multipart(data);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Path segment decoding and query parsing should never panic
+    mik_sdk::fuzzing::parse_request_path(data);
+});
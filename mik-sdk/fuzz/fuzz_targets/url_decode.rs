@@ -1,9 +1,8 @@
 #![no_main]
 
 use libfuzzer_sys::fuzz_target;
-use mik_sdk::url_decode;
 
-fuzz_target!(|data: &str| {
+fuzz_target!(|data: &[u8]| {
     // url_decode should never panic, regardless of input
-    let _ = url_decode(data);
+    mik_sdk::fuzzing::url_decode(data);
 });
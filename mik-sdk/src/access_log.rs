@@ -0,0 +1,230 @@
+//! Access logging for every request handled by `routes!`.
+//!
+//! Set `MIK_ACCESS_LOG` to print one line per request to stderr, after the
+//! response is built:
+//!
+//! | Value | Output |
+//! |-------|--------|
+//! | `json` (or `true`, `1`) | A `log!`-style JSON line |
+//! | `common` (or `clf`) | Common Log Format, with latency and trace ID appended |
+//! | unset, `off`, anything else | Nothing |
+//!
+//! ```text
+//! {"level":"info","msg":"access","method":"GET","path":"/users?page=2","status":"200","duration_ms":"3","request_bytes":"0","response_bytes":"512","trace_id":"4bf9...","ts":"2025-01-16T10:50:00.123Z"}
+//! - - - [16/Jan/2025:10:50:00 +0000] "GET /users?page=2 HTTP/1.1" 200 512 3ms 4bf9...
+//! ```
+//!
+//! The trace ID is read from the response `traceparent` header, so it is
+//! present with `#![trace]` (see [`trace`](crate::trace)) and `-` otherwise.
+//! Streamed bodies are not counted in the byte sizes.
+
+use crate::constants::HEADER_TRACE_ID;
+use std::sync::OnceLock;
+
+/// Environment variable selecting the access log format.
+pub const ACCESS_LOG_ENV: &str = "MIK_ACCESS_LOG";
+
+/// Access log line format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AccessLogFormat {
+    /// Common Log Format with latency and trace ID appended.
+    Common,
+    /// Structured JSON, matching `log!` output.
+    Json,
+}
+
+impl AccessLogFormat {
+    /// Parse a `MIK_ACCESS_LOG` value; `None` disables access logging.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" | "true" | "1" => Some(Self::Json),
+            "common" | "clf" => Some(Self::Common),
+            _ => None,
+        }
+    }
+}
+
+/// The access log format configured by `MIK_ACCESS_LOG`, read once.
+#[must_use]
+pub fn format() -> Option<AccessLogFormat> {
+    static FORMAT: OnceLock<Option<AccessLogFormat>> = OnceLock::new();
+    *FORMAT.get_or_init(|| {
+        std::env::var(ACCESS_LOG_ENV)
+            .ok()
+            .and_then(|value| AccessLogFormat::parse(&value))
+    })
+}
+
+/// A request being timed for the access log.
+///
+/// Created by code generated by `routes!` when access logging is enabled.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct __AccessStart {
+    format: AccessLogFormat,
+    path: String,
+    request_bytes: usize,
+    started_millis: u64,
+}
+
+/// Start an access log entry, or `None` when access logging is off.
+#[doc(hidden)]
+pub fn __start(path: &str, body: Option<&[u8]>, started_millis: u64) -> Option<__AccessStart> {
+    format().map(|format| __AccessStart {
+        format,
+        path: path.to_string(),
+        request_bytes: body.map_or(0, <[u8]>::len),
+        started_millis,
+    })
+}
+
+impl __AccessStart {
+    /// Print the entry for the finished response.
+    pub fn finish(
+        self,
+        method: &str,
+        status: u16,
+        headers: &[(String, String)],
+        body: Option<&[u8]>,
+    ) {
+        use std::io::Write;
+        let entry = Entry {
+            method,
+            path: &self.path,
+            status,
+            duration_ms: crate::time::now_millis().saturating_sub(self.started_millis),
+            request_bytes: self.request_bytes,
+            response_bytes: body.map_or(0, <[u8]>::len),
+            trace_id: headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(HEADER_TRACE_ID))
+                .and_then(|(_, value)| crate::trace::trace_id(value)),
+        };
+        let line = match self.format {
+            AccessLogFormat::Json => entry.to_json(),
+            AccessLogFormat::Common => entry.to_common(crate::time::now()),
+        };
+        let _ = writeln!(std::io::stderr(), "{line}");
+    }
+}
+
+/// One access log line.
+struct Entry<'a> {
+    method: &'a str,
+    path: &'a str,
+    status: u16,
+    duration_ms: u64,
+    request_bytes: usize,
+    response_bytes: usize,
+    trace_id: Option<&'a str>,
+}
+
+impl Entry<'_> {
+    fn to_json(&self) -> String {
+        crate::log::__build_structured_log(
+            "info",
+            "access",
+            &[
+                ("method", self.method),
+                ("path", self.path),
+                ("status", &self.status.to_string()),
+                ("duration_ms", &self.duration_ms.to_string()),
+                ("request_bytes", &self.request_bytes.to_string()),
+                ("response_bytes", &self.response_bytes.to_string()),
+                ("trace_id", self.trace_id.unwrap_or("-")),
+            ],
+        )
+    }
+
+    fn to_common(&self, now_secs: u64) -> String {
+        // "Thu, 16 Jan 2025 10:50:00 GMT" -> "16/Jan/2025:10:50:00 +0000"
+        let date = crate::time::to_http_date(now_secs);
+        let parts: Vec<&str> = date.split(' ').collect();
+        let date = format!("{}/{}/{}:{} +0000", parts[1], parts[2], parts[3], parts[4]);
+        let bytes = if self.response_bytes == 0 {
+            "-".to_string()
+        } else {
+            self.response_bytes.to_string()
+        };
+        // Quotes and control characters in the path would break the line
+        let path: String = self
+            .path
+            .chars()
+            .map(|c| if c == '"' || c.is_control() { '?' } else { c })
+            .collect();
+        format!(
+            "- - - [{date}] \"{} {path} HTTP/1.1\" {} {bytes} {}ms {}",
+            self.method,
+            self.status,
+            self.duration_ms,
+            self.trace_id.unwrap_or("-"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> Entry<'static> {
+        Entry {
+            method: "GET",
+            path: "/users?page=2",
+            status: 200,
+            duration_ms: 3,
+            request_bytes: 0,
+            response_bytes: 512,
+            trace_id: Some("4bf92f3577b34da6a3ce929d0e0e4736"),
+        }
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(AccessLogFormat::parse("json"), Some(AccessLogFormat::Json));
+        assert_eq!(
+            AccessLogFormat::parse(" TRUE "),
+            Some(AccessLogFormat::Json)
+        );
+        assert_eq!(AccessLogFormat::parse("clf"), Some(AccessLogFormat::Common));
+        assert_eq!(
+            AccessLogFormat::parse("Common"),
+            Some(AccessLogFormat::Common)
+        );
+        assert_eq!(AccessLogFormat::parse("off"), None);
+        assert_eq!(AccessLogFormat::parse(""), None);
+    }
+
+    #[test]
+    fn test_common_format() {
+        assert_eq!(
+            entry().to_common(1737024600),
+            "- - - [16/Jan/2025:10:50:00 +0000] \"GET /users?page=2 HTTP/1.1\" 200 512 3ms 4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+    }
+
+    #[test]
+    fn test_common_format_without_body_or_trace() {
+        let entry = Entry {
+            path: "/a\"b",
+            status: 204,
+            response_bytes: 0,
+            trace_id: None,
+            ..entry()
+        };
+        assert!(
+            entry
+                .to_common(0)
+                .ends_with("\"GET /a?b HTTP/1.1\" 204 - 3ms -")
+        );
+    }
+
+    #[test]
+    fn test_json_format() {
+        let line = entry().to_json();
+        assert!(line.starts_with(r#"{"level":"info","msg":"access","method":"GET","path":"/users?page=2","status":"200","duration_ms":"3""#));
+        assert!(line.contains(r#""request_bytes":"0","response_bytes":"512""#));
+        assert!(line.contains(r#""trace_id":"4bf92f3577b34da6a3ce929d0e0e4736","ts":""#));
+    }
+}
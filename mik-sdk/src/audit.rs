@@ -0,0 +1,438 @@
+//! Audit log of who did what.
+//!
+//! An [`AuditEvent`] records an actor, an action and the resource it acted
+//! on. The state before and after the change is stored as a hash, so the
+//! log proves what changed without copying personal data into it. An
+//! [`Auditor`] renders events as JSON records, runs redaction hooks on them
+//! and hands them to its sinks:
+//!
+//! ```text
+//! {"level":"info","msg":"audit","action":"user.update","actor":"u_42","resource":"users/7","before":"hmac-sha256:9f1c...","after":"hmac-sha256:03ab...","details":{"fields":["email"]},"trace_id":"4bf9...","ts":"2025-01-16T10:30:00.123Z"}
+//! ```
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::audit::{AuditEvent, Auditor};
+//! use std::sync::OnceLock;
+//!
+//! fn auditor() -> &'static Auditor {
+//!     static AUDITOR: OnceLock<Auditor> = OnceLock::new();
+//!     AUDITOR.get_or_init(|| {
+//!         Auditor::new()
+//!             // Keyed hashes: short values such as emails can't be guessed back
+//!             .hash_key(env::require("AUDIT_HASH_KEY").into_bytes())
+//!             .redact("ip")
+//!     })
+//! }
+//!
+//! fn update_user(path: Id, body: UpdateUser, req: &Request) -> Result<JsonValue, Error> {
+//!     let before = db::find_user(&path.0)?;
+//!     let after = db::update_user(&path.0, &body)?;
+//!     auditor().record(
+//!         AuditEvent::new("user.update")
+//!             .actor(req.header_or("x-user-id", "anonymous"))
+//!             .resource(format!("users/{}", path.0))
+//!             .before(&before)
+//!             .after(&after)
+//!             .detail("input", body.to_log_json())
+//!             .detail("ip", json::str(req.header_or("x-forwarded-for", ""))),
+//!     );
+//!     Ok(after.to_json())
+//! }
+//! ```
+//!
+//! [`record`] writes to stderr with the default settings, for handlers
+//! that don't need an [`Auditor`] of their own.
+//!
+//! # Sinks
+//!
+//! Records go to stderr, next to [`log`](crate::log) output, unless the
+//! auditor has sinks. Any `Fn(&JsonValue)` is a sink, so keeping records in
+//! a key-value store or publishing them to a queue is a closure over the
+//! host's `wasi:keyvalue` or messaging bindings:
+//!
+//! ```ignore
+//! Auditor::new().sink(|record: &JsonValue| {
+//!     let key = format!("audit/{}", record.get("ts").str_or(""));
+//!     let _ = keyvalue::set(&key, &record.to_bytes());
+//! })
+//! ```
+//!
+//! # Redaction
+//!
+//! Details marked with [`Auditor::redact`] show as `"[REDACTED]"`. Hooks
+//! added with [`Auditor::redact_with`] get the whole record and can drop
+//! or rewrite any field, e.g. to pseudonymize actors. Pass request types
+//! through [`ToLogJson`](crate::typed::ToLogJson) to mask their
+//! `#[field(redact)]` fields.
+
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use crate::crypto::{hmac_sha256, sha256};
+use crate::encoding::hex_encode;
+use crate::json::{self, JsonValue, ToJson};
+use crate::typed::REDACTED;
+
+/// The `msg` of every audit record, to tell them apart from other logs.
+pub const AUDIT_MSG: &str = "audit";
+
+/// One audited action.
+#[derive(Clone)]
+pub struct AuditEvent {
+    action: String,
+    actor: Option<String>,
+    resource: Option<String>,
+    before: Option<JsonValue>,
+    after: Option<JsonValue>,
+    details: Vec<(String, JsonValue)>,
+}
+
+impl AuditEvent {
+    /// An event for `action`, such as `"user.update"`.
+    #[must_use]
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            actor: None,
+            resource: None,
+            before: None,
+            after: None,
+            details: Vec::new(),
+        }
+    }
+
+    /// Who performed the action, e.g. a user or API key ID.
+    #[must_use]
+    pub fn actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// What the action applied to, e.g. `"users/7"`.
+    #[must_use]
+    pub fn resource(mut self, resource: impl Into<String>) -> Self {
+        self.resource = Some(resource.into());
+        self
+    }
+
+    /// The resource before the change. Only its hash is recorded.
+    #[must_use]
+    pub fn before<T: ToJson + ?Sized>(mut self, state: &T) -> Self {
+        self.before = Some(state.to_json());
+        self
+    }
+
+    /// The resource after the change. Only its hash is recorded.
+    #[must_use]
+    pub fn after<T: ToJson + ?Sized>(mut self, state: &T) -> Self {
+        self.after = Some(state.to_json());
+        self
+    }
+
+    /// Add a detail, recorded as is unless the auditor redacts `key`.
+    #[must_use]
+    pub fn detail(mut self, key: impl Into<String>, value: JsonValue) -> Self {
+        self.details.push((key.into(), value));
+        self
+    }
+}
+
+impl std::fmt::Debug for AuditEvent {
+    // Details and states may hold personal data
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditEvent")
+            .field("action", &self.action)
+            .field("actor", &self.actor)
+            .field("resource", &self.resource)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where audit records go.
+///
+/// Implemented for closures taking `&JsonValue`, so a sink writing to a
+/// key-value store or a queue needs no type of its own.
+pub trait AuditSink: Send + Sync {
+    /// Store or forward one record.
+    fn write(&self, record: &JsonValue);
+}
+
+impl<F: Fn(&JsonValue) + Send + Sync> AuditSink for F {
+    fn write(&self, record: &JsonValue) {
+        self(record);
+    }
+}
+
+/// Sink writing records to stderr as JSON lines, like [`log!`](crate::log!).
+pub fn stderr(record: &JsonValue) {
+    use std::io::Write;
+    let _ = writeln!(std::io::stderr(), "{record}");
+}
+
+/// Keeps records in memory, for tests. Clones share the same records.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+impl MemorySink {
+    /// An empty sink.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The records written so far, oldest first.
+    #[must_use]
+    pub fn records(&self) -> Vec<JsonValue> {
+        self.lines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter_map(|line| json::try_parse_full(line.as_bytes()))
+            .collect()
+    }
+}
+
+impl AuditSink for MemorySink {
+    fn write(&self, record: &JsonValue) {
+        self.lines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(record.to_string());
+    }
+}
+
+type RedactHook = dyn Fn(JsonValue) -> JsonValue + Send + Sync;
+
+/// Renders [`AuditEvent`]s, redacts them and writes them to its sinks.
+///
+/// Build once (e.g. in a `OnceLock`) and share across requests.
+#[derive(Clone, Default)]
+pub struct Auditor {
+    sinks: Vec<Arc<dyn AuditSink>>,
+    hash_key: Option<Vec<u8>>,
+    redacted: Vec<String>,
+    hooks: Vec<Arc<RedactHook>>,
+}
+
+impl Auditor {
+    /// An auditor writing to stderr, with plain SHA-256 state hashes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write records to `sink`. Once a sink is added, records no longer go
+    /// to stderr unless [`stderr`] is added too.
+    #[must_use]
+    pub fn sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Hash states with HMAC-SHA256 under `key`.
+    ///
+    /// Plain hashes of small states can be reversed by guessing the input;
+    /// keyed hashes can only be compared by whoever holds the key.
+    #[must_use]
+    pub fn hash_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.hash_key = Some(key.into());
+        self
+    }
+
+    /// Record the detail `key` as `"[REDACTED]"`.
+    #[must_use]
+    pub fn redact(mut self, key: impl Into<String>) -> Self {
+        self.redacted.push(key.into());
+        self
+    }
+
+    /// Pass every rendered record through `hook` before it is written.
+    ///
+    /// Hooks run in the order they were added, after [`Auditor::redact`].
+    #[must_use]
+    pub fn redact_with(
+        mut self,
+        hook: impl Fn(JsonValue) -> JsonValue + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Render, redact and write `event`.
+    pub fn record(&self, event: AuditEvent) {
+        let record = self.render(event);
+        if self.sinks.is_empty() {
+            stderr(&record);
+        }
+        for sink in &self.sinks {
+            sink.write(&record);
+        }
+    }
+
+    /// The record written for `event`, with redaction applied.
+    #[must_use]
+    pub fn render(&self, event: AuditEvent) -> JsonValue {
+        let mut record = json::obj()
+            .set("level", json::str("info"))
+            .set("msg", json::str(AUDIT_MSG))
+            .set("action", json::str(&event.action));
+        if let Some(actor) = &event.actor {
+            record = record.set("actor", json::str(actor));
+        }
+        if let Some(resource) = &event.resource {
+            record = record.set("resource", json::str(resource));
+        }
+        if let Some(before) = &event.before {
+            record = record.set("before", json::str(self.hash(before)));
+        }
+        if let Some(after) = &event.after {
+            record = record.set("after", json::str(self.hash(after)));
+        }
+        if !event.details.is_empty() {
+            let details = event
+                .details
+                .into_iter()
+                .fold(json::obj(), |details, (key, value)| {
+                    if self.redacted.contains(&key) {
+                        details.set(&key, json::str(REDACTED))
+                    } else {
+                        details.set(&key, value)
+                    }
+                });
+            record = record.set("details", details);
+        }
+        if let Some(trace_id) = crate::trace::current_trace_id() {
+            record = record.set("trace_id", json::str(trace_id));
+        }
+        record = record.set("ts", json::str(crate::time::now_iso()));
+        self.hooks.iter().fold(record, |record, hook| hook(record))
+    }
+
+    fn hash(&self, state: &JsonValue) -> String {
+        let bytes = state.to_bytes();
+        self.hash_key.as_deref().map_or_else(
+            || format!("sha256:{}", hex_encode(&sha256(&bytes))),
+            |key| format!("hmac-sha256:{}", hex_encode(&hmac_sha256(key, &bytes))),
+        )
+    }
+}
+
+impl std::fmt::Debug for Auditor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Auditor")
+            .field("sinks", &self.sinks.len())
+            .field("keyed", &self.hash_key.is_some())
+            .field("redacted", &self.redacted)
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+/// Write `event` to stderr with a default [`Auditor`].
+pub fn record(event: AuditEvent) {
+    static AUDITOR: OnceLock<Auditor> = OnceLock::new();
+    AUDITOR.get_or_init(Auditor::new).record(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(email: &str) -> JsonValue {
+        json::obj()
+            .set("id", json::int(7))
+            .set("email", json::str(email))
+    }
+
+    #[test]
+    fn test_record_fields() {
+        let sink = MemorySink::new();
+        let auditor = Auditor::new().sink(sink.clone());
+        auditor.record(
+            AuditEvent::new("user.update")
+                .actor("u_42")
+                .resource("users/7")
+                .before(&state("old@example.com"))
+                .after(&state("new@example.com"))
+                .detail("fields", json::arr().push(json::str("email"))),
+        );
+
+        let records = sink.records();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.get("msg").str().as_deref(), Some(AUDIT_MSG));
+        assert_eq!(record.get("action").str().as_deref(), Some("user.update"));
+        assert_eq!(record.get("actor").str().as_deref(), Some("u_42"));
+        assert_eq!(record.get("resource").str().as_deref(), Some("users/7"));
+        assert_eq!(record.get("details").get("fields").len(), Some(1));
+        assert!(record.get("ts").str().is_some());
+
+        let before = record.get("before").str().unwrap();
+        let after = record.get("after").str().unwrap();
+        assert!(before.starts_with("sha256:"));
+        assert_eq!(before.len(), "sha256:".len() + 64);
+        assert_ne!(before, after);
+        // States are only hashed
+        assert!(!record.to_string().contains("example.com"));
+    }
+
+    #[test]
+    fn test_keyed_hashes() {
+        let plain = Auditor::new().render(AuditEvent::new("a").after(&state("x@y.z")));
+        let keyed = Auditor::new()
+            .hash_key("k1")
+            .render(AuditEvent::new("a").after(&state("x@y.z")));
+        let other_key = Auditor::new()
+            .hash_key("k2")
+            .render(AuditEvent::new("a").after(&state("x@y.z")));
+
+        let keyed = keyed.get("after").str().unwrap();
+        assert!(keyed.starts_with("hmac-sha256:"));
+        assert_ne!(Some(keyed.clone()), other_key.get("after").str());
+        assert_ne!(
+            plain.get("after").str().unwrap()["sha256:".len()..],
+            keyed["hmac-sha256:".len()..]
+        );
+    }
+
+    #[test]
+    fn test_redaction() {
+        let auditor = Auditor::new()
+            .redact("ip")
+            .redact_with(|record| record.set("actor", json::str("pseudonym")));
+        let record = auditor.render(
+            AuditEvent::new("login")
+                .actor("ada@example.com")
+                .detail("ip", json::str("203.0.113.9"))
+                .detail("method", json::str("password")),
+        );
+        assert_eq!(
+            record.path_str(&["details", "ip"]).as_deref(),
+            Some(REDACTED)
+        );
+        assert_eq!(
+            record.path_str(&["details", "method"]).as_deref(),
+            Some("password")
+        );
+        assert_eq!(record.get("actor").str().as_deref(), Some("pseudonym"));
+        assert!(record.get("before").is_null());
+    }
+
+    #[test]
+    fn test_closure_sinks() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let collected = Arc::clone(&seen);
+        let auditor = Auditor::new().sink(move |record: &JsonValue| {
+            collected
+                .lock()
+                .unwrap()
+                .push(record.get("action").str().unwrap_or_default());
+        });
+        auditor.record(AuditEvent::new("order.cancel"));
+        auditor.record(AuditEvent::new("order.refund"));
+        assert_eq!(*seen.lock().unwrap(), ["order.cancel", "order.refund"]);
+    }
+}
@@ -0,0 +1,252 @@
+//! API key authentication.
+
+use std::sync::{Arc, LazyLock, OnceLock};
+
+use crate::crypto::{constant_time_eq, sha256};
+use crate::middleware::{HttpResponse, problem};
+use crate::request::Request;
+
+/// Header checked by default.
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// Environment variable holding the accepted keys, comma-separated.
+pub const API_KEYS_ENV: &str = "MIK_API_KEYS";
+
+/// Keys from `MIK_API_KEYS`, read once.
+fn env_keys() -> &'static [String] {
+    static KEYS: OnceLock<Vec<String>> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        crate::secrets::get(API_KEYS_ENV)
+            .map(|value| parse_keys(value.expose()))
+            .unwrap_or_default()
+    })
+}
+
+fn parse_keys(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Compare keys without leaking where, or whether their lengths, differ.
+fn key_matches(candidate: &str, expected: &str) -> bool {
+    constant_time_eq(&sha256(candidate.as_bytes()), &sha256(expected.as_bytes()))
+}
+
+/// How presented keys are validated.
+#[derive(Clone)]
+enum Validator {
+    Env,
+    Keys(Vec<String>),
+    Callback(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+/// API key check, usable as a `#[before(...)]` hook.
+///
+/// Responds with `401 Unauthorized` when the header is missing and
+/// `403 Forbidden` when the key is not accepted.
+///
+/// # Examples
+///
+/// ```ignore
+/// use mik_sdk::auth::ApiKey;
+///
+/// fn partner_auth(req: &Request) -> Option<Response> {
+///     ApiKey::keys(["k-123", "k-456"]).header("x-partner-key").check(req)
+/// }
+///
+/// fn tenant_auth(req: &Request) -> Option<Response> {
+///     ApiKey::validate(|key| key.starts_with("tenant_") && lookup(key).is_some()).check(req)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ApiKey {
+    header: String,
+    validator: Validator,
+}
+
+impl ApiKey {
+    /// Accept the keys listed in `MIK_API_KEYS` (comma-separated).
+    ///
+    /// With no keys configured, every request is rejected.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::with_validator(Validator::Env)
+    }
+
+    /// Accept a fixed set of keys.
+    #[must_use]
+    pub fn keys<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::with_validator(Validator::Keys(keys.into_iter().map(Into::into).collect()))
+    }
+
+    /// Accept keys for which `validate` returns `true`.
+    #[must_use]
+    pub fn validate(validate: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self::with_validator(Validator::Callback(Arc::new(validate)))
+    }
+
+    fn with_validator(validator: Validator) -> Self {
+        Self {
+            header: DEFAULT_API_KEY_HEADER.to_string(),
+            validator,
+        }
+    }
+
+    /// Read the key from another header (default `x-api-key`).
+    #[must_use]
+    pub fn header(mut self, name: impl Into<String>) -> Self {
+        self.header = name.into();
+        self
+    }
+
+    /// Check the request. Returns `None` if the key is accepted.
+    #[must_use]
+    pub fn check<R: HttpResponse>(&self, req: &Request) -> Option<R> {
+        let key = req.header_or(&self.header, "");
+        if key.is_empty() {
+            return Some(problem(401, &format!("Missing {} header", self.header)));
+        }
+        if self.accepts(key) {
+            None
+        } else {
+            Some(problem(403, "Invalid API key"))
+        }
+    }
+
+    fn accepts(&self, key: &str) -> bool {
+        match &self.validator {
+            Validator::Env => any_key_matches(key, env_keys()),
+            Validator::Keys(keys) => any_key_matches(key, keys),
+            Validator::Callback(validate) => validate(key),
+        }
+    }
+}
+
+/// Check every configured key so timing doesn't reveal which one matched.
+fn any_key_matches(key: &str, keys: &[String]) -> bool {
+    keys.iter()
+        .fold(false, |found, expected| key_matches(key, expected) | found)
+}
+
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match self.validator {
+            Validator::Env => "env",
+            Validator::Keys(_) => "keys",
+            Validator::Callback(_) => "callback",
+        };
+        f.debug_struct("ApiKey")
+            .field("header", &self.header)
+            .field("source", &source)
+            .finish()
+    }
+}
+
+/// Require an API key from `MIK_API_KEYS` in the `x-api-key` header.
+///
+/// Shorthand for `ApiKey::from_env().check(req)`, ready for
+/// `#[before(auth::api_key)]`.
+#[must_use]
+pub fn api_key<R: HttpResponse>(req: &Request) -> Option<R> {
+    static DEFAULT: LazyLock<ApiKey> = LazyLock::new(ApiKey::from_env);
+    DEFAULT.check(req)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use crate::middleware::TestResponse;
+    use std::collections::HashMap;
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        Request::new(
+            Method::Get,
+            "/admin".to_string(),
+            headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn status(auth: &ApiKey, req: &Request) -> Option<u16> {
+        auth.check::<TestResponse>(req).map(|res| res.status)
+    }
+
+    #[test]
+    fn test_missing_key_is_401() {
+        let auth = ApiKey::keys(["secret"]);
+        assert_eq!(status(&auth, &request(&[])), Some(401));
+    }
+
+    #[test]
+    fn test_wrong_key_is_403() {
+        let auth = ApiKey::keys(["secret"]);
+        let req = request(&[("x-api-key", "nope")]);
+        assert_eq!(status(&auth, &req), Some(403));
+    }
+
+    #[test]
+    fn test_valid_key_passes() {
+        let auth = ApiKey::keys(["one", "two"]);
+        let req = request(&[("X-API-Key", "two")]);
+        assert_eq!(status(&auth, &req), None);
+    }
+
+    #[test]
+    fn test_custom_header() {
+        let auth = ApiKey::keys(["secret"]).header("x-partner-key");
+        assert_eq!(
+            status(&auth, &request(&[("x-api-key", "secret")])),
+            Some(401)
+        );
+        assert_eq!(
+            status(&auth, &request(&[("x-partner-key", "secret")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_callback_validator() {
+        let auth = ApiKey::validate(|key| key.starts_with("tenant_"));
+        assert_eq!(status(&auth, &request(&[("x-api-key", "tenant_1")])), None);
+        assert_eq!(
+            status(&auth, &request(&[("x-api-key", "other")])),
+            Some(403)
+        );
+    }
+
+    #[test]
+    fn test_problem_body() {
+        let res: TestResponse = ApiKey::keys(["secret"]).check(&request(&[])).unwrap();
+        let body = crate::json::try_parse(res.body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body.path_str(&["detail"]),
+            Some("Missing x-api-key header".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(parse_keys(" a, b ,,c "), vec!["a", "b", "c"]);
+        assert!(parse_keys("").is_empty());
+    }
+
+    #[test]
+    fn test_key_matches() {
+        assert!(key_matches("abc", "abc"));
+        assert!(!key_matches("abc", "abcd"));
+        assert!(!any_key_matches("x", &[]));
+    }
+}
@@ -0,0 +1,341 @@
+//! HTTP Basic authentication (RFC 7617).
+
+use std::sync::{Arc, LazyLock, OnceLock};
+
+use crate::constants::{HEADER_AUTHORIZATION, HEADER_WWW_AUTHENTICATE};
+use crate::crypto::{constant_time_eq, sha256};
+use crate::encoding::base64_decode;
+use crate::middleware::{HttpResponse, problem};
+use crate::request::Request;
+
+/// Environment variable holding accepted credentials as comma-separated
+/// `user:password` pairs.
+pub const BASIC_AUTH_ENV: &str = "MIK_BASIC_AUTH";
+
+/// Realm sent in the `WWW-Authenticate` challenge by default.
+pub const DEFAULT_REALM: &str = "mik";
+
+/// Credentials from `MIK_BASIC_AUTH`, read once.
+fn env_credentials() -> &'static [(String, String)] {
+    static CREDENTIALS: OnceLock<Vec<(String, String)>> = OnceLock::new();
+    CREDENTIALS.get_or_init(|| {
+        crate::secrets::get(BASIC_AUTH_ENV)
+            .map(|value| parse_credentials(value.expose()))
+            .unwrap_or_default()
+    })
+}
+
+fn parse_credentials(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter_map(|pair| pair.split_once(':'))
+        .filter(|(user, _)| !user.is_empty())
+        .map(|(user, password)| (user.to_string(), password.to_string()))
+        .collect()
+}
+
+/// Username and password from a `Basic` authorization header.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Credentials {
+    /// User ID (the part before the first `:`).
+    pub username: String,
+    /// Password, which may itself contain `:`.
+    pub password: String,
+}
+
+impl Credentials {
+    /// Extract credentials from the request's `Authorization` header.
+    ///
+    /// Returns `None` if the header is missing, uses another scheme, or is
+    /// not valid base64-encoded UTF-8 `user:password`.
+    #[must_use]
+    pub fn from_request(req: &Request) -> Option<Self> {
+        Self::parse(req.header_or(HEADER_AUTHORIZATION, ""))
+    }
+
+    /// Parse an `Authorization` header value.
+    #[must_use]
+    pub fn parse(header: &str) -> Option<Self> {
+        let (scheme, encoded) = header.trim().split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("basic") {
+            return None;
+        }
+        let decoded = String::from_utf8(base64_decode(encoded.trim())?).ok()?;
+        let (username, password) = decoded.split_once(':')?;
+        Some(Self {
+            username: username.to_string(),
+            password: password.to_string(),
+        })
+    }
+
+    /// Compare against expected credentials in constant time.
+    ///
+    /// Both parts are always compared, so timing reveals neither which part
+    /// differed nor the expected lengths.
+    #[must_use]
+    pub fn matches(&self, username: &str, password: &str) -> bool {
+        let user_ok = constant_time_eq(
+            &sha256(self.username.as_bytes()),
+            &sha256(username.as_bytes()),
+        );
+        let password_ok = constant_time_eq(
+            &sha256(self.password.as_bytes()),
+            &sha256(password.as_bytes()),
+        );
+        user_ok & password_ok
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// How presented credentials are validated.
+#[derive(Clone)]
+enum Validator {
+    Env,
+    Users(Vec<(String, String)>),
+    Callback(Arc<dyn Fn(&Credentials) -> bool + Send + Sync>),
+}
+
+/// Basic authentication check, usable as a `#[before(...)]` hook.
+///
+/// Missing or rejected credentials get `401 Unauthorized` with a
+/// `WWW-Authenticate: Basic` challenge, so browsers show a login prompt.
+///
+/// # Examples
+///
+/// ```ignore
+/// use mik_sdk::auth::BasicAuth;
+///
+/// fn admin_auth(req: &Request) -> Option<Response> {
+///     BasicAuth::users([("admin", "s3cret")]).realm("Admin").check(req)
+/// }
+///
+/// fn db_auth(req: &Request) -> Option<Response> {
+///     BasicAuth::validate(|creds| verify_password(&creds.username, &creds.password)).check(req)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BasicAuth {
+    realm: String,
+    validator: Validator,
+}
+
+impl BasicAuth {
+    /// Accept the `user:password` pairs listed in `MIK_BASIC_AUTH`
+    /// (comma-separated).
+    ///
+    /// With no credentials configured, every request is rejected.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::with_validator(Validator::Env)
+    }
+
+    /// Accept a fixed set of `(username, password)` pairs.
+    #[must_use]
+    pub fn users<I, U, P>(users: I) -> Self
+    where
+        I: IntoIterator<Item = (U, P)>,
+        U: Into<String>,
+        P: Into<String>,
+    {
+        Self::with_validator(Validator::Users(
+            users
+                .into_iter()
+                .map(|(user, password)| (user.into(), password.into()))
+                .collect(),
+        ))
+    }
+
+    /// Accept credentials for which `validate` returns `true`.
+    ///
+    /// Use [`Credentials::matches`] inside the callback to keep comparisons
+    /// constant-time.
+    #[must_use]
+    pub fn validate(validate: impl Fn(&Credentials) -> bool + Send + Sync + 'static) -> Self {
+        Self::with_validator(Validator::Callback(Arc::new(validate)))
+    }
+
+    fn with_validator(validator: Validator) -> Self {
+        Self {
+            realm: DEFAULT_REALM.to_string(),
+            validator,
+        }
+    }
+
+    /// Set the realm sent in the challenge (default `mik`).
+    #[must_use]
+    pub fn realm(mut self, realm: impl Into<String>) -> Self {
+        self.realm = realm.into();
+        self
+    }
+
+    /// Check the request. Returns `None` if the credentials are accepted.
+    #[must_use]
+    pub fn check<R: HttpResponse>(&self, req: &Request) -> Option<R> {
+        let detail = match Credentials::from_request(req) {
+            Some(credentials) if self.accepts(&credentials) => return None,
+            Some(_) => "Invalid credentials",
+            None => "Missing or malformed Basic credentials",
+        };
+        let mut response: R = problem(401, detail);
+        response
+            .headers_mut()
+            .push((HEADER_WWW_AUTHENTICATE.to_string(), self.challenge()));
+        Some(response)
+    }
+
+    fn accepts(&self, credentials: &Credentials) -> bool {
+        match &self.validator {
+            Validator::Env => any_user_matches(credentials, env_credentials()),
+            Validator::Users(users) => any_user_matches(credentials, users),
+            Validator::Callback(validate) => validate(credentials),
+        }
+    }
+
+    /// `WWW-Authenticate` value, with the realm as an escaped quoted-string.
+    fn challenge(&self) -> String {
+        let realm = self.realm.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("Basic realm=\"{realm}\", charset=\"UTF-8\"")
+    }
+}
+
+/// Check every configured user so timing doesn't reveal which one matched.
+fn any_user_matches(credentials: &Credentials, users: &[(String, String)]) -> bool {
+    users.iter().fold(false, |found, (user, password)| {
+        credentials.matches(user, password) | found
+    })
+}
+
+impl std::fmt::Debug for BasicAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match self.validator {
+            Validator::Env => "env",
+            Validator::Users(_) => "users",
+            Validator::Callback(_) => "callback",
+        };
+        f.debug_struct("BasicAuth")
+            .field("realm", &self.realm)
+            .field("source", &source)
+            .finish()
+    }
+}
+
+/// Require Basic credentials listed in `MIK_BASIC_AUTH`.
+///
+/// Shorthand for `BasicAuth::from_env().check(req)`, ready for
+/// `#[before(auth::basic_auth)]`.
+#[must_use]
+pub fn basic_auth<R: HttpResponse>(req: &Request) -> Option<R> {
+    static DEFAULT: LazyLock<BasicAuth> = LazyLock::new(BasicAuth::from_env);
+    DEFAULT.check(req)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use crate::middleware::TestResponse;
+    use std::collections::HashMap;
+
+    // "admin:s3cret"
+    const ADMIN: &str = "Basic YWRtaW46czNjcmV0";
+
+    fn request(authorization: Option<&str>) -> Request {
+        Request::new(
+            Method::Get,
+            "/admin".to_string(),
+            authorization
+                .map(|value| vec![("Authorization".to_string(), value.to_string())])
+                .unwrap_or_default(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn check(auth: &BasicAuth, authorization: Option<&str>) -> Option<TestResponse> {
+        auth.check(&request(authorization))
+    }
+
+    #[test]
+    fn test_parse_credentials_header() {
+        let creds = Credentials::parse(ADMIN).unwrap();
+        assert_eq!(creds.username, "admin");
+        assert_eq!(creds.password, "s3cret");
+
+        // Scheme is case-insensitive; the password may contain ':'
+        let creds = Credentials::parse("basic dXNlcjpwYTpzcw==").unwrap();
+        assert_eq!(creds.username, "user");
+        assert_eq!(creds.password, "pa:ss");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        assert!(Credentials::parse("").is_none());
+        assert!(Credentials::parse("Bearer YWRtaW46czNjcmV0").is_none());
+        assert!(Credentials::parse("Basic !!!").is_none());
+        // "admin" without a colon
+        assert!(Credentials::parse("Basic YWRtaW4=").is_none());
+    }
+
+    #[test]
+    fn test_valid_credentials_pass() {
+        let auth = BasicAuth::users([("admin", "s3cret")]);
+        assert!(check(&auth, Some(ADMIN)).is_none());
+    }
+
+    #[test]
+    fn test_missing_credentials_challenge() {
+        let auth = BasicAuth::users([("admin", "s3cret")]).realm("Admin \"area\"");
+        let res = check(&auth, None).unwrap();
+        assert_eq!(res.status, 401);
+        assert!(res.headers.contains(&(
+            "WWW-Authenticate".to_string(),
+            "Basic realm=\"Admin \\\"area\\\"\", charset=\"UTF-8\"".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_wrong_credentials_rejected() {
+        let auth = BasicAuth::users([("admin", "other"), ("root", "s3cret")]);
+        let res = check(&auth, Some(ADMIN)).unwrap();
+        assert_eq!(res.status, 401);
+        let body = crate::json::try_parse(res.body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body.path_str(&["detail"]),
+            Some("Invalid credentials".to_string())
+        );
+    }
+
+    #[test]
+    fn test_callback_validator() {
+        let auth = BasicAuth::validate(|creds| creds.matches("admin", "s3cret"));
+        assert!(check(&auth, Some(ADMIN)).is_none());
+        assert!(check(&auth, Some("Basic YWRtaW46bm9wZQ==")).is_some());
+    }
+
+    #[test]
+    fn test_parse_env_credentials() {
+        assert_eq!(
+            parse_credentials(" admin:s3cret, ci:a:b ,broken,:x"),
+            vec![
+                ("admin".to_string(), "s3cret".to_string()),
+                ("ci".to_string(), "a:b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_debug_hides_password() {
+        let creds = Credentials::parse(ADMIN).unwrap();
+        assert!(!format!("{creds:?}").contains("s3cret"));
+    }
+}
@@ -0,0 +1,57 @@
+//! Authentication middleware.
+//!
+//! Ready-made `#[before(...)]` hooks for `routes!`. Each hook returns
+//! `None` to let the request through, or an RFC 7807 problem response.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::auth;
+//!
+//! routes! {
+//!     GET "/health" => health,
+//!
+//!     // Every route in the group requires a key from MIK_API_KEYS
+//!     #[before(auth::api_key)]
+//!     {
+//!         GET "/admin/users" => list_users,
+//!         DELETE "/admin/users/{id}" => delete_user(path: Id),
+//!     }
+//!
+//!     // Browser login prompt, credentials from MIK_BASIC_AUTH
+//!     #[before(auth::basic_auth)]
+//!     {
+//!         GET "/dashboard" => dashboard,
+//!     }
+//! }
+//! ```
+//!
+//! # Roles and Permissions
+//!
+//! Once a hook has authenticated the request, it records who made it with
+//! [`set_principal`]. Route guards then check the principal's roles or
+//! permissions, answering `401` without a principal and `403` when none of
+//! the listed values is granted:
+//!
+//! ```ignore
+//! routes! {
+//!     #![before(session_auth)]
+//!
+//!     GET "/admin/users" [role = "admin"] => list_users,
+//!     GET "/reports" [role = "admin" | "analyst"] => reports,
+//!     DELETE "/posts/{id}" [role = "editor", permission = "posts:delete"] => delete_post(path: Id),
+//! }
+//! ```
+//!
+//! For single sign-on through an OpenID Connect provider, see [`oidc`].
+
+mod api_key;
+mod basic;
+pub mod oidc;
+mod principal;
+
+pub use api_key::{API_KEYS_ENV, ApiKey, DEFAULT_API_KEY_HEADER, api_key};
+pub use basic::{BASIC_AUTH_ENV, BasicAuth, Credentials, DEFAULT_REALM, basic_auth};
+#[doc(hidden)]
+pub use principal::{__begin, __require_permission, __require_role};
+pub use principal::{Principal, principal, set_principal};
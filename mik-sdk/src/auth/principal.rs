@@ -0,0 +1,198 @@
+//! The authenticated principal of the request being handled.
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// Principal set by an authentication hook for the current request.
+    static CURRENT: RefCell<Option<Principal>> = const { RefCell::new(None) };
+}
+
+/// Who made the request: an ID with the roles and permissions granted to it.
+///
+/// Authentication hooks set it with [`set_principal`]; route guards such as
+/// `[role = "admin"]` and handlers read it back.
+///
+/// # Examples
+///
+/// ```ignore
+/// use mik_sdk::auth::{self, Principal};
+///
+/// fn session_auth<R: HttpResponse>(req: &Request) -> Option<R> {
+///     let Some(user) = sessions::user(req) else {
+///         return Some(problem(401, "Sign in required"));
+///     };
+///     auth::set_principal(Principal::new(user.id).role(user.role));
+///     None
+/// }
+///
+/// routes! {
+///     #![before(session_auth)]
+///
+///     GET "/admin/users" [role = "admin"] => list_users,
+///     DELETE "/posts/{id}" [permission = "posts:delete"] => delete_post(path: Id),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    id: String,
+    roles: Vec<String>,
+    permissions: Vec<String>,
+}
+
+impl Principal {
+    /// A principal with no roles or permissions.
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            roles: Vec::new(),
+            permissions: Vec::new(),
+        }
+    }
+
+    /// Grant a role.
+    #[must_use]
+    pub fn role(mut self, role: impl Into<String>) -> Self {
+        self.roles.push(role.into());
+        self
+    }
+
+    /// Grant a permission, e.g. `"posts:write"`.
+    #[must_use]
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.permissions.push(permission.into());
+        self
+    }
+
+    /// The user, client or key ID.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Granted roles.
+    #[must_use]
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    /// Granted permissions.
+    #[must_use]
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    /// Whether `role` is granted.
+    #[must_use]
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|granted| granted == role)
+    }
+
+    /// Whether `permission` is granted.
+    #[must_use]
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|granted| granted == permission)
+    }
+}
+
+/// Set the principal of the request being handled.
+///
+/// Call from an authentication hook once the request is authenticated.
+/// Cleared when the next request starts.
+pub fn set_principal(principal: Principal) {
+    CURRENT.with(|current| *current.borrow_mut() = Some(principal));
+}
+
+/// The principal of the request being handled, if it was authenticated.
+#[must_use]
+pub fn principal() -> Option<Principal> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Clear the principal of the previous request.
+///
+/// Called by code generated by `routes!`.
+#[doc(hidden)]
+pub fn __begin() {
+    CURRENT.with(|current| *current.borrow_mut() = None);
+}
+
+/// Check a `[role = "..."]` guard: the principal needs one of `roles`.
+///
+/// Returns the status and detail of the problem to send otherwise.
+#[doc(hidden)]
+#[must_use]
+pub fn __require_role(roles: &[&str]) -> Option<(u16, &'static str)> {
+    require(
+        |principal| roles.iter().any(|role| principal.has_role(role)),
+        "Missing required role",
+    )
+}
+
+/// Check a `[permission = "..."]` guard: the principal needs one of
+/// `permissions`.
+#[doc(hidden)]
+#[must_use]
+pub fn __require_permission(permissions: &[&str]) -> Option<(u16, &'static str)> {
+    require(
+        |principal| {
+            permissions
+                .iter()
+                .any(|permission| principal.has_permission(permission))
+        },
+        "Missing required permission",
+    )
+}
+
+fn require(
+    granted: impl FnOnce(&Principal) -> bool,
+    denied: &'static str,
+) -> Option<(u16, &'static str)> {
+    CURRENT.with(|current| match current.borrow().as_ref() {
+        None => Some((401, "Authentication required")),
+        Some(principal) if granted(principal) => None,
+        Some(_) => Some((403, denied)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guards() {
+        __begin();
+        assert_eq!(principal(), None);
+        assert_eq!(
+            __require_role(&["admin"]),
+            Some((401, "Authentication required"))
+        );
+
+        set_principal(
+            Principal::new("u_42")
+                .role("editor")
+                .permission("posts:write"),
+        );
+        assert_eq!(
+            principal().map(|p| p.id().to_string()).as_deref(),
+            Some("u_42")
+        );
+        assert_eq!(__require_role(&["admin", "editor"]), None);
+        assert_eq!(
+            __require_role(&["admin"]),
+            Some((403, "Missing required role"))
+        );
+        assert_eq!(__require_permission(&["posts:write"]), None);
+        assert_eq!(
+            __require_permission(&["posts:delete"]),
+            Some((403, "Missing required permission"))
+        );
+
+        // The next request starts unauthenticated
+        __begin();
+        assert_eq!(
+            __require_permission(&["posts:write"]).map(|(s, _)| s),
+            Some(401)
+        );
+    }
+}
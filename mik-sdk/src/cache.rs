@@ -0,0 +1,393 @@
+//! HTTP caching headers.
+//!
+//! [`Cache`] builds `Cache-Control`, `Vary` and `Expires` headers. Attach it
+//! to any handler response with [`CacheExt::cache`], or use it directly as a
+//! header value since it implements [`Display`](std::fmt::Display).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::prelude::*;
+//!
+//! fn list_products(_req: &Request) -> Response {
+//!     ok!({ "products": [] }).cache(
+//!         Cache::public()
+//!             .max_age(300)
+//!             .stale_while_revalidate(60)
+//!             .vary("accept-language"),
+//!     )
+//! }
+//!
+//! fn profile(_req: &Request) -> Response {
+//!     ok!({ "name": "Alice" }).cache(Cache::no_store())
+//! }
+//!
+//! // As a plain header value
+//! fn logo(_req: &Request) -> Response {
+//!     ok!({}, headers: { "cache-control": Cache::public().max_age(86400).immutable() })
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::middleware::HttpResponse;
+use crate::time;
+
+/// Response header carrying the cache directives.
+const CACHE_CONTROL: &str = "cache-control";
+/// Response header listing request headers the response depends on.
+const VARY: &str = "vary";
+/// Response header carrying the absolute expiry date.
+const EXPIRES: &str = "expires";
+
+/// Base cacheability of a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cacheability {
+    Public,
+    Private,
+    NoCache,
+    NoStore,
+}
+
+impl Cacheability {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Private => "private",
+            Self::NoCache => "no-cache",
+            Self::NoStore => "no-store",
+        }
+    }
+}
+
+/// Caching policy for a response.
+///
+/// Start from one of [`public`](Self::public), [`private`](Self::private),
+/// [`no_cache`](Self::no_cache) or [`no_store`](Self::no_store), then add
+/// directives with the builder methods.
+///
+/// # Examples
+///
+/// ```
+/// use mik_sdk::cache::Cache;
+///
+/// let cache = Cache::public().max_age(300).s_maxage(3600);
+/// assert_eq!(cache.to_string(), "public, max-age=300, s-maxage=3600");
+///
+/// assert_eq!(Cache::no_store().to_string(), "no-store");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct Cache {
+    cacheability: Cacheability,
+    max_age: Option<u32>,
+    s_maxage: Option<u32>,
+    stale_while_revalidate: Option<u32>,
+    stale_if_error: Option<u32>,
+    must_revalidate: bool,
+    immutable: bool,
+    no_transform: bool,
+    vary: Vec<String>,
+    expires: Option<u64>,
+}
+
+impl Cache {
+    const fn with(cacheability: Cacheability) -> Self {
+        Self {
+            cacheability,
+            max_age: None,
+            s_maxage: None,
+            stale_while_revalidate: None,
+            stale_if_error: None,
+            must_revalidate: false,
+            immutable: false,
+            no_transform: false,
+            vary: Vec::new(),
+            expires: None,
+        }
+    }
+
+    /// Cacheable by browsers and shared caches (CDNs, proxies).
+    pub const fn public() -> Self {
+        Self::with(Cacheability::Public)
+    }
+
+    /// Cacheable by the browser only, e.g. for per-user responses.
+    pub const fn private() -> Self {
+        Self::with(Cacheability::Private)
+    }
+
+    /// Cacheable, but must be revalidated with the server before each use.
+    pub const fn no_cache() -> Self {
+        Self::with(Cacheability::NoCache)
+    }
+
+    /// Never stored by any cache.
+    pub const fn no_store() -> Self {
+        Self::with(Cacheability::NoStore)
+    }
+
+    /// How long the response stays fresh, in seconds (`max-age`).
+    pub const fn max_age(mut self, seconds: u32) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// Freshness lifetime for shared caches only, in seconds (`s-maxage`).
+    pub const fn s_maxage(mut self, seconds: u32) -> Self {
+        self.s_maxage = Some(seconds);
+        self
+    }
+
+    /// How long a stale response may be served while it is revalidated in
+    /// the background, in seconds (`stale-while-revalidate`).
+    pub const fn stale_while_revalidate(mut self, seconds: u32) -> Self {
+        self.stale_while_revalidate = Some(seconds);
+        self
+    }
+
+    /// How long a stale response may be served when the origin errors, in
+    /// seconds (`stale-if-error`).
+    pub const fn stale_if_error(mut self, seconds: u32) -> Self {
+        self.stale_if_error = Some(seconds);
+        self
+    }
+
+    /// Forbid serving the response once stale without revalidating.
+    pub const fn must_revalidate(mut self) -> Self {
+        self.must_revalidate = true;
+        self
+    }
+
+    /// The response never changes while fresh, e.g. fingerprinted assets.
+    pub const fn immutable(mut self) -> Self {
+        self.immutable = true;
+        self
+    }
+
+    /// Forbid intermediaries from transforming the body (`no-transform`).
+    pub const fn no_transform(mut self) -> Self {
+        self.no_transform = true;
+        self
+    }
+
+    /// Add a request header the response varies on (`Vary`).
+    pub fn vary(mut self, header: impl Into<String>) -> Self {
+        let header = header.into();
+        if !self.vary.iter().any(|h| h.eq_ignore_ascii_case(&header)) {
+            self.vary.push(header);
+        }
+        self
+    }
+
+    /// Absolute expiry time as a Unix timestamp in seconds (`Expires`).
+    ///
+    /// `max-age` takes precedence in HTTP/1.1 caches; `Expires` is mainly
+    /// useful for older caches.
+    ///
+    /// ```
+    /// use mik_sdk::cache::Cache;
+    ///
+    /// let cache = Cache::public().expires(1737024600);
+    /// assert!(cache
+    ///     .headers()
+    ///     .contains(&("expires".to_string(), "Thu, 16 Jan 2025 10:50:00 GMT".to_string())));
+    /// ```
+    pub const fn expires(mut self, unix_seconds: u64) -> Self {
+        self.expires = Some(unix_seconds);
+        self
+    }
+
+    /// All headers for this policy: `cache-control`, plus `vary` and
+    /// `expires` when set.
+    #[must_use]
+    pub fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![(CACHE_CONTROL.to_string(), self.to_string())];
+        if !self.vary.is_empty() {
+            headers.push((VARY.to_string(), self.vary.join(", ")));
+        }
+        if let Some(expires) = self.expires {
+            headers.push((EXPIRES.to_string(), time::to_http_date(expires)));
+        }
+        headers
+    }
+}
+
+impl fmt::Display for Cache {
+    /// Formats the `Cache-Control` header value.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.cacheability.as_str())?;
+        let seconds = [
+            ("max-age", self.max_age),
+            ("s-maxage", self.s_maxage),
+            ("stale-while-revalidate", self.stale_while_revalidate),
+            ("stale-if-error", self.stale_if_error),
+        ];
+        for (name, value) in seconds {
+            if let Some(value) = value {
+                write!(f, ", {name}={value}")?;
+            }
+        }
+        let flags = [
+            ("must-revalidate", self.must_revalidate),
+            ("immutable", self.immutable),
+            ("no-transform", self.no_transform),
+        ];
+        for (name, set) in flags {
+            if set {
+                write!(f, ", {name}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adds [`cache`](Self::cache) to handler responses.
+///
+/// Implemented for every [`HttpResponse`], including the `Response` type
+/// generated by `routes!`. Included in the prelude.
+pub trait CacheExt: Sized {
+    /// Apply a caching policy to the response.
+    ///
+    /// Replaces any existing `cache-control` and `expires` headers, and
+    /// merges the policy's `vary` headers into an existing `vary` header.
+    #[must_use]
+    fn cache(self, cache: Cache) -> Self;
+}
+
+impl<R: HttpResponse> CacheExt for R {
+    fn cache(mut self, cache: Cache) -> Self {
+        let headers = self.headers_mut();
+        headers.retain(|(name, _)| {
+            !name.eq_ignore_ascii_case(CACHE_CONTROL) && !name.eq_ignore_ascii_case(EXPIRES)
+        });
+        headers.push((CACHE_CONTROL.to_string(), cache.to_string()));
+
+        if !cache.vary.is_empty() {
+            match headers
+                .iter_mut()
+                .find(|(name, _)| name.eq_ignore_ascii_case(VARY))
+            {
+                // `Vary: *` already covers every header
+                Some((_, value)) if value.trim() == "*" => {},
+                Some((_, value)) => {
+                    for header in &cache.vary {
+                        let present = value
+                            .split(',')
+                            .any(|h| h.trim().eq_ignore_ascii_case(header));
+                        if !present {
+                            value.push_str(", ");
+                            value.push_str(header);
+                        }
+                    }
+                },
+                None => headers.push((VARY.to_string(), cache.vary.join(", "))),
+            }
+        }
+
+        if let Some(expires) = cache.expires {
+            headers.push((EXPIRES.to_string(), time::to_http_date(expires)));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+
+    fn response(headers: &[(&str, &str)]) -> TestResponse {
+        TestResponse::from_parts(
+            200,
+            headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            None,
+        )
+    }
+
+    fn header<'a>(res: &'a TestResponse, name: &str) -> Vec<&'a str> {
+        res.headers
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_cache_control_value() {
+        assert_eq!(Cache::public().to_string(), "public");
+        assert_eq!(
+            Cache::private().max_age(60).to_string(),
+            "private, max-age=60"
+        );
+        assert_eq!(Cache::no_cache().to_string(), "no-cache");
+        assert_eq!(
+            Cache::public()
+                .max_age(300)
+                .s_maxage(600)
+                .stale_while_revalidate(30)
+                .stale_if_error(86400)
+                .must_revalidate()
+                .immutable()
+                .no_transform()
+                .to_string(),
+            "public, max-age=300, s-maxage=600, stale-while-revalidate=30, \
+             stale-if-error=86400, must-revalidate, immutable, no-transform"
+        );
+    }
+
+    #[test]
+    fn test_headers() {
+        let cache = Cache::public()
+            .max_age(300)
+            .vary("accept-language")
+            .vary("Accept-Language")
+            .vary("accept")
+            .expires(0);
+        assert_eq!(
+            cache.headers(),
+            vec![
+                (
+                    "cache-control".to_string(),
+                    "public, max-age=300".to_string()
+                ),
+                ("vary".to_string(), "accept-language, accept".to_string()),
+                (
+                    "expires".to_string(),
+                    "Thu, 01 Jan 1970 00:00:00 GMT".to_string()
+                ),
+            ]
+        );
+        assert_eq!(Cache::no_store().headers().len(), 1);
+    }
+
+    #[test]
+    fn test_cache_replaces_existing_headers() {
+        let res = response(&[
+            ("Cache-Control", "no-cache"),
+            ("Expires", "0"),
+            ("content-type", "application/json"),
+        ])
+        .cache(Cache::public().max_age(300).expires(1737024600));
+        assert_eq!(header(&res, "cache-control"), ["public, max-age=300"]);
+        assert_eq!(header(&res, "expires"), ["Thu, 16 Jan 2025 10:50:00 GMT"]);
+        assert_eq!(header(&res, "content-type"), ["application/json"]);
+        assert!(header(&res, "vary").is_empty());
+    }
+
+    #[test]
+    fn test_cache_merges_vary() {
+        let res = response(&[("Vary", "Origin")])
+            .cache(Cache::public().vary("origin").vary("accept-encoding"));
+        assert_eq!(header(&res, "vary"), ["Origin, accept-encoding"]);
+
+        let res = response(&[("vary", "*")]).cache(Cache::public().vary("accept"));
+        assert_eq!(header(&res, "vary"), ["*"]);
+
+        let res = response(&[]).cache(Cache::private().vary("cookie"));
+        assert_eq!(header(&res, "vary"), ["cookie"]);
+    }
+}
@@ -0,0 +1,367 @@
+//! Conditional updates with `If-Match` (RFC 9110 §13).
+//!
+//! Optimistic concurrency in two steps: reads send the resource's version
+//! as an `ETag`, and writes send it back in `If-Match`. [`check`] rejects a
+//! write made against a stale version with `412 Precondition Failed`, so two
+//! clients editing the same resource cannot silently overwrite each other.
+//!
+//! ```ignore
+//! use mik_sdk::conditional::{self, Versioned, VersionedResponse};
+//!
+//! impl Versioned for Document {
+//!     fn version(&self) -> String {
+//!         self.revision.to_string()
+//!     }
+//! }
+//!
+//! // ETag: "7"
+//! fn get_doc(path: Id, _req: &Request) -> Result<VersionedResponse<Document>, Error> {
+//!     Ok(VersionedResponse::ok(db::find(&path)?))
+//! }
+//!
+//! // If-Match: "7" succeeds once; a second write with "7" gets 412
+//! fn update_doc(path: Id, body: DocUpdate, req: &Request) -> Result<VersionedResponse<Document>, Error> {
+//!     let doc = db::find(&path)?;
+//!     conditional::check(req, &doc.etag())?;
+//!     Ok(VersionedResponse::ok(db::update(doc, body)?))
+//! }
+//! ```
+//!
+//! Requests without `If-Match` pass [`check`]; use [`require`] to answer them
+//! with `428 Precondition Required` instead. Comparison is strong, as
+//! `If-Match` requires: weak tags never match.
+
+use std::fmt;
+
+use crate::Request;
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_JSON};
+use crate::error::Error;
+use crate::json::ToJson;
+use crate::middleware::HttpResponse;
+use crate::response::{IntoResponse, Returns};
+
+/// Request header carrying the versions a write is allowed against.
+const IF_MATCH: &str = "if-match";
+/// Response header carrying the version of the representation.
+const ETAG: &str = "etag";
+
+/// An entity tag: an opaque version of a resource.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// A strong tag, e.g. from a revision counter.
+    ///
+    /// Characters not allowed in entity tags (quotes, spaces, control
+    /// characters) become `_`.
+    #[must_use]
+    pub fn strong(tag: impl AsRef<str>) -> Self {
+        Self {
+            tag: sanitize(tag.as_ref()),
+            weak: false,
+        }
+    }
+
+    /// A weak tag, for representations that are equivalent but not
+    /// byte-identical. Weak tags never satisfy `If-Match`.
+    #[must_use]
+    pub fn weak(tag: impl AsRef<str>) -> Self {
+        Self {
+            tag: sanitize(tag.as_ref()),
+            weak: true,
+        }
+    }
+
+    /// Parse an `ETag` header value such as `"v7"` or `W/"v7"`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match parse_list(value)?.as_slice() {
+            [Match::Tag(tag)] => Some(tag.clone()),
+            _ => None,
+        }
+    }
+
+    /// The opaque tag, without quotes.
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Whether the tag is weak.
+    #[must_use]
+    pub const fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Strong comparison: both tags are strong and equal.
+    #[must_use]
+    pub fn strong_eq(&self, other: &Self) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            f.write_str("W/")?;
+        }
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+/// A resource with a version, sent as its [`ETag`].
+pub trait Versioned {
+    /// The current version, e.g. a revision counter or update timestamp.
+    fn version(&self) -> String;
+
+    /// The strong tag of the current version.
+    fn etag(&self) -> ETag {
+        ETag::strong(self.version())
+    }
+}
+
+/// Whether the request's `If-Match` allows a write to a resource at `current`.
+///
+/// True without `If-Match`, for `*`, or when a listed tag strongly matches.
+/// An unparseable header matches nothing.
+#[must_use]
+pub fn matches(req: &Request, current: &ETag) -> bool {
+    let values = req.header_all(IF_MATCH);
+    if values.is_empty() {
+        return true;
+    }
+    values.iter().any(|value| {
+        parse_list(value).is_some_and(|list| {
+            list.iter().any(|m| match m {
+                Match::Any => true,
+                Match::Tag(tag) => tag.strong_eq(current),
+            })
+        })
+    })
+}
+
+/// Reject a write against a stale version with `412 Precondition Failed`.
+///
+/// Requests without `If-Match` pass.
+///
+/// # Errors
+///
+/// Returns a 412 error if `If-Match` does not match `current`.
+pub fn check(req: &Request, current: &ETag) -> Result<(), Error> {
+    if matches(req, current) {
+        Ok(())
+    } else {
+        Err(Error::status(
+            412,
+            format!("The resource has changed; its current version is {current}"),
+        ))
+    }
+}
+
+/// Like [`check`], but answer requests without `If-Match` with
+/// `428 Precondition Required`.
+///
+/// # Errors
+///
+/// Returns a 428 error without `If-Match`, or a 412 error if it does not
+/// match `current`.
+pub fn require(req: &Request, current: &ETag) -> Result<(), Error> {
+    if req.header_all(IF_MATCH).is_empty() {
+        return Err(Error::status(
+            428,
+            "This request requires an If-Match header with the resource's ETag",
+        ));
+    }
+    check(req, current)
+}
+
+/// A JSON response with the `ETag` of a [`Versioned`] body.
+///
+/// Returning it from a route declared `-> T` is allowed when the body is `T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionedResponse<T> {
+    status: u16,
+    body: T,
+}
+
+impl<T> VersionedResponse<T> {
+    /// A `200 OK` response.
+    #[must_use]
+    pub const fn ok(body: T) -> Self {
+        Self { status: 200, body }
+    }
+
+    /// A `201 Created` response.
+    #[must_use]
+    pub const fn created(body: T) -> Self {
+        Self { status: 201, body }
+    }
+
+    /// A response with any status.
+    #[must_use]
+    pub const fn new(status: u16, body: T) -> Self {
+        Self { status, body }
+    }
+}
+
+impl<R: HttpResponse, T: ToJson + Versioned> IntoResponse<R> for VersionedResponse<T> {
+    fn into_response(self) -> R {
+        R::from_parts(
+            self.status,
+            vec![
+                (HEADER_CONTENT_TYPE.to_string(), MIME_JSON.to_string()),
+                (ETAG.to_string(), self.body.etag().to_string()),
+            ],
+            Some(self.body.to_json().to_bytes()),
+        )
+    }
+}
+
+impl<T, U: Returns<T>> Returns<T> for VersionedResponse<U> {}
+
+/// One member of an `If-Match` list.
+enum Match {
+    Any,
+    Tag(ETag),
+}
+
+/// Parse `*` or a comma-separated list of entity tags.
+fn parse_list(value: &str) -> Option<Vec<Match>> {
+    let value = value.trim();
+    if value == "*" {
+        return Some(vec![Match::Any]);
+    }
+    let mut list = Vec::new();
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ',']);
+        if rest.is_empty() {
+            break;
+        }
+        let (weak, quoted) = rest
+            .strip_prefix("W/")
+            .map_or((false, rest), |quoted| (true, quoted));
+        let (tag, after) = quoted.strip_prefix('"')?.split_once('"')?;
+        if tag.chars().any(|c| !is_etagc(c)) {
+            return None;
+        }
+        list.push(Match::Tag(ETag {
+            tag: tag.to_string(),
+            weak,
+        }));
+        let after = after.trim_start_matches([' ', '\t']);
+        if !(after.is_empty() || after.starts_with(',')) {
+            return None;
+        }
+        rest = after;
+    }
+    (!list.is_empty()).then_some(list)
+}
+
+/// `etagc`: visible ASCII except `"`, or non-ASCII.
+fn is_etagc(c: char) -> bool {
+    c == '!' || ('#'..='~').contains(&c) || !c.is_ascii()
+}
+
+fn sanitize(tag: &str) -> String {
+    tag.chars()
+        .map(|c| if is_etagc(c) { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{self, JsonValue};
+    use crate::middleware::TestResponse;
+    use crate::request::Method;
+    use std::collections::HashMap;
+
+    struct Doc {
+        revision: u32,
+    }
+
+    impl Versioned for Doc {
+        fn version(&self) -> String {
+            self.revision.to_string()
+        }
+    }
+
+    impl ToJson for Doc {
+        fn to_json(&self) -> JsonValue {
+            json::obj().set("revision", json::int(i64::from(self.revision)))
+        }
+    }
+
+    fn request(if_match: &[&str]) -> Request {
+        Request::new(
+            Method::Put,
+            "/docs/1".to_string(),
+            if_match
+                .iter()
+                .map(|v| ("if-match".to_string(), (*v).to_string()))
+                .collect(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_etag_format_and_parse() {
+        assert_eq!(ETag::strong("v7").to_string(), "\"v7\"");
+        assert_eq!(ETag::weak("v7").to_string(), "W/\"v7\"");
+        assert_eq!(ETag::strong("a \"b\"").to_string(), "\"a__b_\"");
+        assert_eq!(ETag::parse("W/\"v7\""), Some(ETag::weak("v7")));
+        assert_eq!(ETag::parse(" \"v7\" "), Some(ETag::strong("v7")));
+        assert_eq!(ETag::parse("v7"), None);
+        assert_eq!(ETag::parse("\"a\", \"b\""), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        let current = ETag::strong("7");
+        assert!(matches(&request(&[]), &current));
+        assert!(matches(&request(&["\"7\""]), &current));
+        assert!(matches(&request(&["*"]), &current));
+        assert!(matches(&request(&["\"5\", \"7\""]), &current));
+        assert!(matches(&request(&["\"5\"", "\"7\""]), &current));
+        assert!(matches(&request(&["\"a,b\", \"7\""]), &current));
+        assert!(!matches(&request(&["\"6\""]), &current));
+        // If-Match uses strong comparison
+        assert!(!matches(&request(&["W/\"7\""]), &current));
+        assert!(!matches(&request(&["7"]), &current));
+        assert!(!matches(&request(&["\"7\" junk"]), &current));
+    }
+
+    #[test]
+    fn test_check_and_require() {
+        let current = ETag::strong("7");
+        assert!(check(&request(&[]), &current).is_ok());
+        let err = check(&request(&["\"6\""]), &current).unwrap_err();
+        assert_eq!(err.status_code(), 412);
+
+        let err = require(&request(&[]), &current).unwrap_err();
+        assert_eq!(err.status_code(), 428);
+        assert!(require(&request(&["\"7\""]), &current).is_ok());
+        assert_eq!(
+            require(&request(&["\"6\""]), &current)
+                .unwrap_err()
+                .status_code(),
+            412
+        );
+    }
+
+    #[test]
+    fn test_versioned_response() {
+        let res: TestResponse = VersionedResponse::created(Doc { revision: 3 }).into_response();
+        assert_eq!(res.status, 201);
+        assert!(
+            res.headers
+                .contains(&("etag".to_string(), "\"3\"".to_string()))
+        );
+        assert_eq!(res.body.as_deref(), Some(br#"{"revision":3}"#.as_slice()));
+    }
+}
@@ -0,0 +1,348 @@
+//! Typed application configuration.
+//!
+//! Values are read from the environment (`wasi:cli/environment`) and, with
+//! the `runtime-config` feature, from the host's `wasi:config/store` (Spin
+//! variables). Environment variables win; store keys are the lowercased
+//! name, so `MAX_ITEMS` falls back to the Spin variable `max_items`.
+//!
+//! ```ignore
+//! use mik_sdk::config;
+//!
+//! let max_items = config::get_or::<u32>("MAX_ITEMS", 50);
+//! let api_key: config::Secret = config::require("API_KEY")?;
+//! ```
+//!
+//! Invalid values are logged by key and expected type, never by value.
+//! Wrap sensitive values in [`Secret`], which prints as `[REDACTED]`.
+//!
+//! # Struct-Based Settings
+//!
+//! `#[derive(Config)]` loads a struct once per instance. Keys are the field
+//! names uppercased; `Option` fields may be missing and
+//! `#[config(default = "...")]` supplies a value in config syntax:
+//!
+//! ```ignore
+//! use mik_sdk::config::{Config, Secret};
+//!
+//! #[derive(Config)]
+//! #[config(prefix = "APP_")]
+//! struct Settings {
+//!     database_url: Secret,              // APP_DATABASE_URL
+//!     #[config(default = "50")]
+//!     max_items: u32,                    // APP_MAX_ITEMS
+//!     #[config(rename = "REGION")]
+//!     region: Option<String>,            // REGION
+//! }
+//!
+//! fn handler(_req: &Request) -> Response {
+//!     let Ok(settings) = Settings::load() else {  // &'static Settings
+//!         return error! { status: 500, title: "Misconfigured" };
+//!     };
+//!     // ...
+//! }
+//! ```
+
+use std::fmt;
+
+pub use mik_sdk_macros::Config;
+
+/// Error loading a configuration value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// A required key is not set.
+    Missing {
+        /// The key that was looked up.
+        key: String,
+    },
+    /// A value could not be parsed as the requested type.
+    Invalid {
+        /// The key that was looked up.
+        key: String,
+        /// Name of the expected type.
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { key } => write!(f, "missing config value `{key}`"),
+            Self::Invalid { key, expected } => {
+                write!(f, "config value `{key}` is not a valid {expected}")
+            },
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// A sensitive configuration value.
+///
+/// `Debug` and `Display` print `[REDACTED]`; use [`expose`](Self::expose)
+/// where the value is needed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Wrap a sensitive value.
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// The wrapped value.
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+/// Types that can be parsed from a configuration string.
+pub trait FromConfig: Sized {
+    /// Type name used in [`ConfigError::Invalid`].
+    const EXPECTED: &'static str;
+
+    /// Parse a raw value, or `None` if it is not valid.
+    fn from_config(value: &str) -> Option<Self>;
+}
+
+impl FromConfig for String {
+    const EXPECTED: &'static str = "string";
+
+    fn from_config(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+impl FromConfig for Secret {
+    const EXPECTED: &'static str = "string";
+
+    fn from_config(value: &str) -> Option<Self> {
+        Some(Self::new(value))
+    }
+}
+
+/// Accepts `true`/`false`, `1`/`0`, `yes`/`no` and `on`/`off`, ignoring case.
+impl FromConfig for bool {
+    const EXPECTED: &'static str = "boolean";
+
+    fn from_config(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Some(true),
+            "false" | "0" | "no" | "off" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_config_parse {
+    ($($ty:ty => $expected:literal),* $(,)?) => {
+        $(
+            impl FromConfig for $ty {
+                const EXPECTED: &'static str = $expected;
+
+                fn from_config(value: &str) -> Option<Self> {
+                    value.trim().parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_config_parse! {
+    u8 => "integer", u16 => "integer", u32 => "integer", u64 => "integer",
+    usize => "integer", i8 => "integer", i16 => "integer", i32 => "integer",
+    i64 => "integer", isize => "integer", f32 => "number", f64 => "number",
+    char => "character",
+}
+
+/// Human-readable durations such as `30s`, `250ms` or `1h30m`; see
+/// [`time::parse_duration`](crate::time::parse_duration).
+impl FromConfig for std::time::Duration {
+    const EXPECTED: &'static str = "duration";
+
+    fn from_config(value: &str) -> Option<Self> {
+        crate::time::parse_duration(value)
+    }
+}
+
+/// Comma-separated list; empty items are skipped.
+impl<T: FromConfig> FromConfig for Vec<T> {
+    const EXPECTED: &'static str = "comma-separated list";
+
+    fn from_config(value: &str) -> Option<Self> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(T::from_config)
+            .collect()
+    }
+}
+
+/// Struct-based settings, usually implemented with `#[derive(Config)]`.
+pub trait Config: Sized + Send + Sync + 'static {
+    /// Build the settings from a key lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` for a missing required key or an invalid value.
+    fn from_lookup(lookup: &dyn Fn(&str) -> Option<String>) -> Result<Self, ConfigError>;
+
+    /// Load the settings from [`lookup`] on first call and return the cached
+    /// instance afterwards. Errors are not cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` for a missing required key or an invalid value.
+    fn load() -> Result<&'static Self, ConfigError>;
+}
+
+/// Raw value for `key`: the environment variable, then the runtime config
+/// store when the `runtime-config` feature is enabled.
+#[must_use]
+pub fn lookup(key: &str) -> Option<String> {
+    std::env::var(key).ok().or_else(|| runtime_lookup(key))
+}
+
+/// Typed value for `key`, or `None` if it is unset or invalid.
+///
+/// Invalid values are logged with the key and expected type.
+#[must_use]
+pub fn get<T: FromConfig>(key: &str) -> Option<T> {
+    match require(key) {
+        Ok(value) => Some(value),
+        Err(ConfigError::Invalid { key, expected }) => {
+            crate::log!(warn, "invalid config value", key: key, expected: expected);
+            None
+        },
+        Err(_) => None,
+    }
+}
+
+/// Typed value for `key`, or `default` if it is unset or invalid.
+#[must_use]
+pub fn get_or<T: FromConfig>(key: &str, default: T) -> T {
+    get(key).unwrap_or(default)
+}
+
+/// Typed value for `key`.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Missing` if the key is unset and
+/// `ConfigError::Invalid` if the value does not parse.
+pub fn require<T: FromConfig>(key: &str) -> Result<T, ConfigError> {
+    match lookup(key) {
+        Some(value) => parse_value(key, &value),
+        None => Err(ConfigError::Missing {
+            key: key.to_string(),
+        }),
+    }
+}
+
+/// Parse a raw `value` read for `key`.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Invalid` if the value does not parse.
+pub fn parse_value<T: FromConfig>(key: &str, value: &str) -> Result<T, ConfigError> {
+    T::from_config(value).ok_or_else(|| ConfigError::Invalid {
+        key: key.to_string(),
+        expected: T::EXPECTED,
+    })
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "runtime-config"))]
+#[allow(warnings)]
+mod bindings {
+    wit_bindgen::generate!({
+        path: "wit",
+        world: "config",
+    });
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "runtime-config"))]
+fn runtime_lookup(key: &str) -> Option<String> {
+    bindings::wasi::config::store::get(&key.to_ascii_lowercase())
+        .ok()
+        .flatten()
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "runtime-config")))]
+const fn runtime_lookup(_key: &str) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(parse_value::<u32>("MAX_ITEMS", " 50 "), Ok(50));
+        assert_eq!(parse_value::<bool>("DEBUG", "On"), Ok(true));
+        assert_eq!(
+            parse_value::<Vec<String>>("HOSTS", "a, b,,c"),
+            Ok(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert_eq!(
+            parse_value::<u8>("RETRIES", "300"),
+            Err(ConfigError::Invalid {
+                key: "RETRIES".to_string(),
+                expected: "integer",
+            })
+        );
+        assert!(parse_value::<Vec<u16>>("PORTS", "80,http").is_err());
+        assert_eq!(
+            parse_value("TIMEOUT", "1m30s"),
+            Ok(std::time::Duration::from_secs(90))
+        );
+    }
+
+    #[test]
+    fn test_missing_key() {
+        let key = "MIK_CONFIG_TEST_UNSET_KEY";
+        assert_eq!(
+            require::<String>(key),
+            Err(ConfigError::Missing {
+                key: key.to_string()
+            })
+        );
+        assert_eq!(get::<u32>(key), None);
+        assert_eq!(get_or(key, 7_u32), 7);
+    }
+
+    #[test]
+    fn test_secret_redacted() {
+        let secret: Secret = parse_value("API_KEY", "hunter2").unwrap();
+        assert_eq!(secret.expose(), "hunter2");
+        assert_eq!(format!("{secret}"), "[REDACTED]");
+        assert!(!format!("{secret:?}").contains("hunter2"));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = ConfigError::Invalid {
+            key: "PORT".to_string(),
+            expected: "integer",
+        };
+        assert_eq!(
+            err.to_string(),
+            "config value `PORT` is not a valid integer"
+        );
+    }
+}
@@ -0,0 +1,854 @@
+//! Connect RPC and gRPC-web services.
+//!
+//! A [`Service`] serves the unary methods of a protobuf service to
+//! [Connect](https://connectrpc.com/docs/protocol) and gRPC-web clients from
+//! a plain `POST` route, next to the REST routes of the same crate. Methods
+//! are plain functions registered by name:
+//!
+//! ```ignore
+//! fn users() -> Service {
+//!     Service::new("users.v1.UserService")
+//!         .unary("GetUser", get_user)
+//!         .unary("DeleteUser", delete_user)
+//! }
+//!
+//! fn get_user(call: &Call) -> Result<Reply, ConnectError> {
+//!     let input: GetUserRequest = call.json()?;
+//!     let user = db::find(&input.id)
+//!         .ok_or_else(|| ConnectError::new(Code::NotFound, "no such user"))?;
+//!     Ok(Reply::json(&user))
+//! }
+//!
+//! routes! {
+//!     POST "/users.v1.UserService/{method}" => user_service,
+//! }
+//!
+//! fn user_service(req: &Request) -> Response {
+//!     users().serve(req)
+//! }
+//! ```
+//!
+//! # Protocols and codecs
+//!
+//! The `Content-Type` picks both:
+//!
+//! | Content-Type | Protocol | Codec |
+//! |--------------|----------|-------|
+//! | `application/json` | Connect unary | JSON |
+//! | `application/proto` | Connect unary | binary protobuf |
+//! | `application/grpc-web+json` | gRPC-web | JSON |
+//! | `application/grpc-web`, `application/grpc-web+proto` | gRPC-web | binary protobuf |
+//!
+//! Anything else gets `415`. JSON messages use [`FromJson`] and [`ToJson`],
+//! so `#[derive(Type)]` structs work as-is; binary messages use
+//! [`ProtoMessage`], implemented for `prost` messages by the `prost` feature.
+//! A method answers in the codec it was called with; [`Call::message`] and
+//! [`Call::reply`] pick it for types that have both encodings.
+//!
+//! # Errors
+//!
+//! A [`ConnectError`] becomes a Connect error body with the HTTP status of
+//! its [`Code`], or `grpc-status` / `grpc-message` trailers for gRPC-web.
+//! Unknown methods answer `unimplemented`, undecodable messages
+//! `invalid_argument`. Compressed messages are not supported and answer
+//! `unimplemented`, so clients must send them uncompressed (the default for
+//! Connect and gRPC-web clients without a compression option).
+//!
+//! Streaming methods and Connect `GET` requests are not supported.
+
+use crate::Request;
+use crate::constants::HEADER_CONTENT_TYPE;
+use crate::json::{self, ToJson};
+use crate::middleware::HttpResponse;
+use crate::request::Method;
+use crate::typed::{FromJson, ParseError};
+use std::fmt::Write;
+
+/// Flag of a gRPC-web frame carrying trailers instead of a message.
+const TRAILERS_FLAG: u8 = 0x80;
+
+/// Flag of a compressed frame.
+const COMPRESSED_FLAG: u8 = 0x01;
+
+/// Content types the service accepts, sent with `415` responses.
+const ACCEPTED: &str =
+    "application/json, application/proto, application/grpc-web+json, application/grpc-web+proto";
+
+/// A message encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// Protobuf JSON mapping.
+    Json,
+    /// Binary protobuf.
+    Proto,
+}
+
+/// The wire protocol of a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Connect unary: the message is the body, errors are JSON.
+    Connect,
+    /// gRPC-web: the message is framed, the status is in trailers.
+    GrpcWeb,
+}
+
+/// A message with a binary protobuf encoding.
+///
+/// The `prost` feature implements it for every `prost::Message`.
+pub trait ProtoMessage: Sized {
+    /// Decode a message, or describe why it is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the bytes are not a valid encoding.
+    fn decode_proto(bytes: &[u8]) -> Result<Self, String>;
+
+    /// Encode the message.
+    fn encode_proto(&self) -> Vec<u8>;
+}
+
+/// A Connect status code, shared with gRPC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Code {
+    /// The operation was cancelled, typically by the caller.
+    Canceled,
+    /// Unknown error.
+    Unknown,
+    /// The request is invalid, regardless of the system state.
+    InvalidArgument,
+    /// The deadline expired before the operation completed.
+    DeadlineExceeded,
+    /// A requested entity was not found.
+    NotFound,
+    /// The entity the caller tried to create already exists.
+    AlreadyExists,
+    /// The caller is not allowed to run the operation.
+    PermissionDenied,
+    /// A quota or rate limit was exhausted.
+    ResourceExhausted,
+    /// The system is not in a state the operation requires.
+    FailedPrecondition,
+    /// The operation was aborted, e.g. by a concurrency conflict.
+    Aborted,
+    /// The operation was attempted past the valid range.
+    OutOfRange,
+    /// The operation is not implemented or supported.
+    Unimplemented,
+    /// An invariant of the system is broken.
+    Internal,
+    /// The service is currently unavailable; retrying may succeed.
+    Unavailable,
+    /// Unrecoverable data loss or corruption.
+    DataLoss,
+    /// The caller has no valid credentials.
+    Unauthenticated,
+}
+
+impl Code {
+    /// The Connect name, e.g. `not_found`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Canceled => "canceled",
+            Self::Unknown => "unknown",
+            Self::InvalidArgument => "invalid_argument",
+            Self::DeadlineExceeded => "deadline_exceeded",
+            Self::NotFound => "not_found",
+            Self::AlreadyExists => "already_exists",
+            Self::PermissionDenied => "permission_denied",
+            Self::ResourceExhausted => "resource_exhausted",
+            Self::FailedPrecondition => "failed_precondition",
+            Self::Aborted => "aborted",
+            Self::OutOfRange => "out_of_range",
+            Self::Unimplemented => "unimplemented",
+            Self::Internal => "internal",
+            Self::Unavailable => "unavailable",
+            Self::DataLoss => "data_loss",
+            Self::Unauthenticated => "unauthenticated",
+        }
+    }
+
+    /// The numeric gRPC status, e.g. `5` for `not_found`.
+    #[must_use]
+    pub const fn grpc_status(self) -> u8 {
+        match self {
+            Self::Canceled => 1,
+            Self::Unknown => 2,
+            Self::InvalidArgument => 3,
+            Self::DeadlineExceeded => 4,
+            Self::NotFound => 5,
+            Self::AlreadyExists => 6,
+            Self::PermissionDenied => 7,
+            Self::ResourceExhausted => 8,
+            Self::FailedPrecondition => 9,
+            Self::Aborted => 10,
+            Self::OutOfRange => 11,
+            Self::Unimplemented => 12,
+            Self::Internal => 13,
+            Self::Unavailable => 14,
+            Self::DataLoss => 15,
+            Self::Unauthenticated => 16,
+        }
+    }
+
+    /// The HTTP status of a Connect unary error with this code.
+    #[must_use]
+    pub const fn http_status(self) -> u16 {
+        match self {
+            Self::Canceled => 499,
+            Self::InvalidArgument | Self::FailedPrecondition | Self::OutOfRange => 400,
+            Self::DeadlineExceeded => 504,
+            Self::NotFound => 404,
+            Self::AlreadyExists | Self::Aborted => 409,
+            Self::PermissionDenied => 403,
+            Self::ResourceExhausted => 429,
+            Self::Unimplemented => 501,
+            Self::Unknown | Self::Internal | Self::DataLoss => 500,
+            Self::Unavailable => 503,
+            Self::Unauthenticated => 401,
+        }
+    }
+}
+
+impl std::fmt::Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A failed call: a code and a message for the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectError {
+    code: Code,
+    message: String,
+}
+
+impl ConnectError {
+    /// An error with a code and a message.
+    #[must_use]
+    pub fn new(code: Code, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// The status code.
+    #[must_use]
+    pub const fn code(&self) -> Code {
+        self.code
+    }
+
+    /// The message sent to the client.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "{}", self.code)
+        } else {
+            write!(f, "{}: {}", self.code, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ConnectError {}
+
+impl From<ParseError> for ConnectError {
+    fn from(e: ParseError) -> Self {
+        Self::new(Code::InvalidArgument, e.to_string())
+    }
+}
+
+/// A method implementation.
+pub type Handler = fn(&Call<'_>) -> Result<Reply, ConnectError>;
+
+/// An incoming call: the decoded frame and the request it came with.
+#[derive(Debug)]
+pub struct Call<'a> {
+    req: &'a Request,
+    protocol: Protocol,
+    codec: Codec,
+    message: &'a [u8],
+}
+
+impl<'a> Call<'a> {
+    /// The HTTP request, e.g. for headers such as `authorization`.
+    #[must_use]
+    pub const fn request(&self) -> &'a Request {
+        self.req
+    }
+
+    /// The protocol the client used.
+    #[must_use]
+    pub const fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// The codec of the message, which the reply must use too.
+    #[must_use]
+    pub const fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// The encoded message.
+    #[must_use]
+    pub const fn bytes(&self) -> &'a [u8] {
+        self.message
+    }
+
+    /// Decode a JSON message. An empty body is the empty message `{}`.
+    ///
+    /// # Errors
+    ///
+    /// `invalid_argument` when the call is binary or the message does not
+    /// parse as `T`.
+    pub fn json<T: FromJson>(&self) -> Result<T, ConnectError> {
+        if self.codec != Codec::Json {
+            return Err(ConnectError::new(
+                Code::InvalidArgument,
+                "this method only accepts JSON messages",
+            ));
+        }
+        let message = if self.message.is_empty() {
+            b"{}".as_slice()
+        } else {
+            self.message
+        };
+        let value = json::try_parse_full(message)
+            .ok_or_else(|| ConnectError::new(Code::InvalidArgument, "invalid JSON message"))?;
+        Ok(T::from_json(&value)?)
+    }
+
+    /// Decode a binary protobuf message.
+    ///
+    /// # Errors
+    ///
+    /// `invalid_argument` when the call is JSON or the message does not
+    /// decode as `T`.
+    pub fn proto<T: ProtoMessage>(&self) -> Result<T, ConnectError> {
+        if self.codec != Codec::Proto {
+            return Err(ConnectError::new(
+                Code::InvalidArgument,
+                "this method only accepts binary protobuf messages",
+            ));
+        }
+        T::decode_proto(self.message).map_err(|e| ConnectError::new(Code::InvalidArgument, e))
+    }
+
+    /// Decode the message with the codec of the call.
+    ///
+    /// # Errors
+    ///
+    /// `invalid_argument` when the message does not decode as `T`.
+    pub fn message<T: FromJson + ProtoMessage>(&self) -> Result<T, ConnectError> {
+        match self.codec {
+            Codec::Json => self.json(),
+            Codec::Proto => self.proto(),
+        }
+    }
+
+    /// Encode a reply with the codec of the call.
+    #[must_use]
+    pub fn reply<T: ToJson + ProtoMessage>(&self, message: &T) -> Reply {
+        match self.codec {
+            Codec::Json => Reply::json(message),
+            Codec::Proto => Reply::proto(message),
+        }
+    }
+}
+
+/// An encoded reply message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    codec: Codec,
+    bytes: Vec<u8>,
+}
+
+impl Reply {
+    /// A JSON reply.
+    #[must_use]
+    pub fn json(message: &impl ToJson) -> Self {
+        Self {
+            codec: Codec::Json,
+            bytes: message.to_json().to_bytes(),
+        }
+    }
+
+    /// A binary protobuf reply.
+    #[must_use]
+    pub fn proto(message: &impl ProtoMessage) -> Self {
+        Self {
+            codec: Codec::Proto,
+            bytes: message.encode_proto(),
+        }
+    }
+}
+
+/// The unary methods of one protobuf service.
+#[derive(Debug, Clone)]
+pub struct Service {
+    name: String,
+    methods: Vec<(String, Handler)>,
+}
+
+impl Service {
+    /// A service by its fully-qualified name, e.g. `users.v1.UserService`.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            methods: Vec::new(),
+        }
+    }
+
+    /// Add a unary method, e.g. `GetUser`.
+    #[must_use]
+    pub fn unary(mut self, method: &str, handler: Handler) -> Self {
+        self.methods.push((method.to_string(), handler));
+        self
+    }
+
+    /// The fully-qualified service name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Answer a call to `/<service>/<method>`, with any path prefix.
+    #[must_use]
+    pub fn serve<R: HttpResponse>(&self, req: &Request) -> R {
+        let content_type = req.content_type_or("").to_ascii_lowercase();
+        let media = content_type.split(';').next().unwrap_or("").trim();
+        let Some((protocol, codec)) = negotiate(media) else {
+            return R::from_parts(
+                415,
+                vec![("accept-post".to_string(), ACCEPTED.to_string())],
+                None,
+            );
+        };
+        let result = self.call(req, protocol, codec);
+        match protocol {
+            Protocol::Connect => connect_response(codec, result),
+            Protocol::GrpcWeb => grpc_web_response(media, result),
+        }
+    }
+
+    fn call(
+        &self,
+        req: &Request,
+        protocol: Protocol,
+        codec: Codec,
+    ) -> Result<Vec<u8>, ConnectError> {
+        if req.method() != Method::Post {
+            return Err(ConnectError::new(
+                Code::Unimplemented,
+                "only POST is supported",
+            ));
+        }
+        let version = req.header_or("connect-protocol-version", "1");
+        if protocol == Protocol::Connect && version != "1" {
+            return Err(ConnectError::new(
+                Code::InvalidArgument,
+                format!("unsupported connect-protocol-version `{version}`"),
+            ));
+        }
+        let encoding_header = match protocol {
+            Protocol::Connect => "content-encoding",
+            Protocol::GrpcWeb => "grpc-encoding",
+        };
+        let encoding = req.header_or(encoding_header, "identity");
+        if !encoding.eq_ignore_ascii_case("identity") {
+            return Err(ConnectError::new(
+                Code::Unimplemented,
+                format!("compression `{encoding}` is not supported"),
+            ));
+        }
+
+        let path = req.path_without_query().trim_end_matches('/');
+        let handler = path
+            .rsplit_once('/')
+            .filter(|(service, _)| {
+                service
+                    .strip_suffix(self.name.as_str())
+                    .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with('/'))
+            })
+            .and_then(|(_, method)| {
+                self.methods
+                    .iter()
+                    .find(|(name, _)| name == method)
+                    .map(|(_, handler)| *handler)
+            })
+            .ok_or_else(|| {
+                ConnectError::new(Code::Unimplemented, format!("{path} is not implemented"))
+            })?;
+
+        let body = req.body().unwrap_or_default();
+        let message = match protocol {
+            Protocol::Connect => body,
+            Protocol::GrpcWeb => unframe(body)?,
+        };
+        let call = Call {
+            req,
+            protocol,
+            codec,
+            message,
+        };
+        let reply = handler(&call)?;
+        if reply.codec != codec {
+            return Err(ConnectError::new(
+                Code::Internal,
+                "the reply does not use the codec of the call",
+            ));
+        }
+        Ok(reply.bytes)
+    }
+}
+
+/// Protocol and codec of a media type, or `None` if unsupported.
+fn negotiate(media: &str) -> Option<(Protocol, Codec)> {
+    match media {
+        "application/json" => Some((Protocol::Connect, Codec::Json)),
+        "application/proto" => Some((Protocol::Connect, Codec::Proto)),
+        "application/grpc-web+json" => Some((Protocol::GrpcWeb, Codec::Json)),
+        "application/grpc-web" | "application/grpc-web+proto" => {
+            Some((Protocol::GrpcWeb, Codec::Proto))
+        },
+        _ => None,
+    }
+}
+
+/// The message of a single gRPC-web frame.
+fn unframe(body: &[u8]) -> Result<&[u8], ConnectError> {
+    let invalid = || ConnectError::new(Code::InvalidArgument, "invalid gRPC-web frame");
+    let (header, rest) = body.split_at_checked(5).ok_or_else(invalid)?;
+    let [flags, a, b, c, d] = *header else {
+        return Err(invalid());
+    };
+    if flags & COMPRESSED_FLAG != 0 {
+        return Err(ConnectError::new(
+            Code::Unimplemented,
+            "compressed messages are not supported",
+        ));
+    }
+    let len = usize::try_from(u32::from_be_bytes([a, b, c, d])).map_err(|_| invalid())?;
+    match rest.split_at_checked(len) {
+        // Unary: exactly one message
+        Some((message, [])) => Ok(message),
+        _ => Err(invalid()),
+    }
+}
+
+/// A gRPC-web frame.
+fn frame(flags: u8, payload: &[u8]) -> Vec<u8> {
+    let len = u32::try_from(payload.len()).unwrap_or(u32::MAX);
+    let mut out = Vec::with_capacity(payload.len() + 5);
+    out.push(flags);
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+fn connect_response<R: HttpResponse>(codec: Codec, result: Result<Vec<u8>, ConnectError>) -> R {
+    match result {
+        Ok(bytes) => {
+            let content_type = match codec {
+                Codec::Json => "application/json",
+                Codec::Proto => "application/proto",
+            };
+            R::from_parts(
+                200,
+                vec![(HEADER_CONTENT_TYPE.to_string(), content_type.to_string())],
+                Some(bytes),
+            )
+        },
+        Err(e) => {
+            let mut body = json::obj().set("code", json::str(e.code.as_str()));
+            if !e.message.is_empty() {
+                body = body.set("message", json::str(&e.message));
+            }
+            R::from_parts(
+                e.code.http_status(),
+                vec![(
+                    HEADER_CONTENT_TYPE.to_string(),
+                    "application/json".to_string(),
+                )],
+                Some(body.to_bytes()),
+            )
+        },
+    }
+}
+
+/// `200` with the message frame, if any, and the status in a trailers frame.
+fn grpc_web_response<R: HttpResponse>(media: &str, result: Result<Vec<u8>, ConnectError>) -> R {
+    let (mut body, trailers) = match result {
+        Ok(bytes) => (frame(0, &bytes), "grpc-status: 0\r\n".to_string()),
+        Err(e) => (
+            Vec::new(),
+            format!(
+                "grpc-status: {}\r\ngrpc-message: {}\r\n",
+                e.code.grpc_status(),
+                grpc_message(&e.message)
+            ),
+        ),
+    };
+    body.extend(frame(TRAILERS_FLAG, trailers.as_bytes()));
+    R::from_parts(
+        200,
+        vec![(HEADER_CONTENT_TYPE.to_string(), media.to_string())],
+        Some(body),
+    )
+}
+
+/// Percent-encode a `grpc-message`: `%` and bytes outside printable ASCII.
+fn grpc_message(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    for byte in message.bytes() {
+        if (0x20..=0x7e).contains(&byte) && byte != b'%' {
+            out.push(char::from(byte));
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+    use std::collections::HashMap;
+
+    /// A message with both encodings: the name as JSON or as raw bytes.
+    #[derive(Debug, PartialEq)]
+    struct Greeting {
+        name: String,
+    }
+
+    impl FromJson for Greeting {
+        fn from_json(value: &json::JsonValue) -> Result<Self, ParseError> {
+            let name = value
+                .get("name")
+                .str()
+                .ok_or_else(|| ParseError::missing("name"))?;
+            Ok(Self { name })
+        }
+    }
+
+    impl ToJson for Greeting {
+        fn to_json(&self) -> json::JsonValue {
+            json::obj().set("name", json::str(&self.name))
+        }
+    }
+
+    impl ProtoMessage for Greeting {
+        fn decode_proto(bytes: &[u8]) -> Result<Self, String> {
+            String::from_utf8(bytes.to_vec())
+                .map(|name| Self { name })
+                .map_err(|e| e.to_string())
+        }
+
+        fn encode_proto(&self) -> Vec<u8> {
+            self.name.as_bytes().to_vec()
+        }
+    }
+
+    fn greet(call: &Call) -> Result<Reply, ConnectError> {
+        let greeting: Greeting = call.message()?;
+        if greeting.name.is_empty() {
+            return Err(ConnectError::new(Code::InvalidArgument, "name is empty"));
+        }
+        Ok(call.reply(&Greeting {
+            name: format!("hello {}", greeting.name),
+        }))
+    }
+
+    fn service() -> Service {
+        Service::new("greet.v1.GreetService").unary("Greet", greet)
+    }
+
+    fn request(path: &str, content_type: &str, body: &[u8]) -> Request {
+        Request::new(
+            Method::Post,
+            path.to_string(),
+            vec![("content-type".to_string(), content_type.to_string())],
+            Some(body.to_vec()),
+            HashMap::new(),
+        )
+    }
+
+    fn header<'a>(res: &'a TestResponse, name: &str) -> Option<&'a str> {
+        res.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_connect_json() {
+        let req = request(
+            "/greet.v1.GreetService/Greet",
+            "application/json",
+            br#"{"name":"Ada"}"#,
+        );
+        let res: TestResponse = service().serve(&req);
+        assert_eq!(res.status, 200);
+        assert_eq!(header(&res, "content-type"), Some("application/json"));
+        assert_eq!(
+            res.body.as_deref(),
+            Some(br#"{"name":"hello Ada"}"#.as_slice())
+        );
+    }
+
+    #[test]
+    fn test_connect_proto() {
+        let req = request(
+            "/rpc/greet.v1.GreetService/Greet",
+            "application/proto",
+            b"Ada",
+        );
+        let res: TestResponse = service().serve(&req);
+        assert_eq!(res.status, 200);
+        assert_eq!(header(&res, "content-type"), Some("application/proto"));
+        assert_eq!(res.body.as_deref(), Some(b"hello Ada".as_slice()));
+    }
+
+    #[test]
+    fn test_connect_error() {
+        let req = request(
+            "/greet.v1.GreetService/Greet",
+            "application/json",
+            br#"{"name":""}"#,
+        );
+        let res: TestResponse = service().serve(&req);
+        assert_eq!(res.status, 400);
+        assert_eq!(
+            res.body.as_deref(),
+            Some(br#"{"code":"invalid_argument","message":"name is empty"}"#.as_slice())
+        );
+
+        let req = request("/greet.v1.GreetService/Greet", "application/json", b"{}");
+        let res: TestResponse = service().serve(&req);
+        let body = json::try_parse(res.body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body.path_str(&["code"]),
+            Some("invalid_argument".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unknown_method() {
+        for path in [
+            "/greet.v1.GreetService/Wave",
+            "/other.v1.GreetService/Greet",
+        ] {
+            let req = request(path, "application/json", b"{}");
+            let res: TestResponse = service().serve(&req);
+            assert_eq!(res.status, 501, "{path}");
+            let body = json::try_parse(res.body.as_deref().unwrap()).unwrap();
+            assert_eq!(body.path_str(&["code"]), Some("unimplemented".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let req = request("/greet.v1.GreetService/Greet", "text/plain", b"Ada");
+        let res: TestResponse = service().serve(&req);
+        assert_eq!(res.status, 415);
+        assert_eq!(header(&res, "accept-post"), Some(ACCEPTED));
+    }
+
+    #[test]
+    fn test_compression_rejected() {
+        let req = Request::new(
+            Method::Post,
+            "/greet.v1.GreetService/Greet".to_string(),
+            vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("content-encoding".to_string(), "gzip".to_string()),
+            ],
+            Some(b"{}".to_vec()),
+            HashMap::new(),
+        );
+        let res: TestResponse = service().serve(&req);
+        assert_eq!(res.status, 501);
+    }
+
+    #[test]
+    fn test_grpc_web() {
+        let req = request(
+            "/greet.v1.GreetService/Greet",
+            "application/grpc-web+proto",
+            &frame(0, b"Ada"),
+        );
+        let res: TestResponse = service().serve(&req);
+        assert_eq!(res.status, 200);
+        assert_eq!(
+            header(&res, "content-type"),
+            Some("application/grpc-web+proto")
+        );
+        let mut expected = frame(0, b"hello Ada");
+        expected.extend(frame(TRAILERS_FLAG, b"grpc-status: 0\r\n"));
+        assert_eq!(res.body, Some(expected));
+    }
+
+    #[test]
+    fn test_grpc_web_error() {
+        let req = request(
+            "/greet.v1.GreetService/Greet",
+            "application/grpc-web+json",
+            &frame(0, br#"{"name":""}"#),
+        );
+        let res: TestResponse = service().serve(&req);
+        assert_eq!(res.status, 200);
+        assert_eq!(
+            res.body,
+            Some(frame(
+                TRAILERS_FLAG,
+                b"grpc-status: 3\r\ngrpc-message: name is empty\r\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unframe() {
+        assert_eq!(unframe(&frame(0, b"abc")).ok(), Some(b"abc".as_slice()));
+        assert_eq!(unframe(&frame(0, b"")).ok(), Some(b"".as_slice()));
+        assert_eq!(
+            unframe(&frame(COMPRESSED_FLAG, b"abc")).map_err(|e| e.code()),
+            Err(Code::Unimplemented)
+        );
+        assert!(unframe(b"\0\0\0").is_err());
+        assert!(unframe(b"\0\0\0\0\x05abc").is_err());
+        let mut two = frame(0, b"a");
+        two.extend(frame(0, b"b"));
+        assert!(unframe(&two).is_err());
+    }
+
+    #[test]
+    fn test_codes() {
+        assert_eq!(Code::NotFound.as_str(), "not_found");
+        assert_eq!(Code::NotFound.grpc_status(), 5);
+        assert_eq!(Code::NotFound.http_status(), 404);
+        assert_eq!(Code::Unauthenticated.grpc_status(), 16);
+        assert_eq!(Code::ResourceExhausted.http_status(), 429);
+        assert_eq!(
+            ConnectError::new(Code::Internal, "boom").to_string(),
+            "internal: boom"
+        );
+    }
+
+    #[test]
+    fn test_grpc_message() {
+        assert_eq!(grpc_message("plain text"), "plain text");
+        assert_eq!(
+            grpc_message("100% ünïcode\n"),
+            "100%25 %C3%BCn%C3%AFcode%0A"
+        );
+    }
+}
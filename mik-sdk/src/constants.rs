@@ -7,10 +7,11 @@
 //!
 //! Some limits can be configured via environment variables:
 //!
-//! | Variable             | Default            | Description                        |
-//! |----------------------|--------------------|------------------------------------|
-//! | `MIK_MAX_JSON_SIZE`  | 1 MB (1,000,000)   | Maximum JSON input size            |
-//! | `MIK_MAX_BODY_SIZE`  | 10 MB (10,485,760) | Maximum request body size (bridge) |
+//! | Variable              | Default            | Description                        |
+//! |-----------------------|--------------------|------------------------------------|
+//! | `MIK_MAX_JSON_SIZE`   | 1 MB (1,000,000)   | Maximum JSON input size            |
+//! | `MIK_MAX_BODY_SIZE`   | 10 MB (10,485,760) | Maximum request body size (bridge) |
+//! | `MIK_TRUSTED_PROXIES` | 1                  | Proxy hops trusted for client IP   |
 //!
 //! ## Example
 //!
@@ -94,14 +95,68 @@ pub const MAX_URL_DECODED_LEN: usize = 65536;
 /// Prevents DoS via forms with thousands of tiny fields.
 pub const MAX_FORM_FIELDS: usize = 1000;
 
+/// Maximum number of ranges in a `Range` header.
+/// Prevents DoS via requests for thousands of tiny ranges.
+pub const MAX_RANGES: usize = 100;
+
 /// Maximum individual header value length (8KB).
 /// Prevents memory exhaustion from single large headers.
 pub const MAX_HEADER_VALUE_LEN: usize = 8192;
 
 /// Maximum total size of all headers combined (1MB).
 /// Prevents memory exhaustion from many headers.
+///
+/// Exceeding it only logs a warning; the bridge rejects requests over
+/// `MIK_MAX_HEADERS` / `MIK_MAX_HEADER_SIZE` with 431 before they get here.
 pub const MAX_TOTAL_HEADERS_SIZE: usize = 1024 * 1024;
 
+/// Default maximum request body size (10MB), matching the bridge default.
+const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Cached max body size from environment.
+static MAX_BODY_SIZE_CACHE: OnceLock<usize> = OnceLock::new();
+
+/// Returns the maximum allowed request body size in bytes.
+///
+/// Reads from `MIK_MAX_BODY_SIZE` on first call, falling back to 10MB.
+/// The bridge enforces this limit for buffered bodies; streamed bodies
+/// are read directly by the handler, so [`BodyStream`](crate::BodyStream)
+/// enforces it on the handler side.
+#[inline]
+pub fn get_max_body_size() -> usize {
+    *MAX_BODY_SIZE_CACHE.get_or_init(|| {
+        std::env::var("MIK_MAX_BODY_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_BODY_SIZE)
+    })
+}
+
+/// Chunk size requested per read when streaming request bodies (64KB).
+pub const BODY_STREAM_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Default number of trusted reverse proxies in front of the component.
+const DEFAULT_TRUSTED_PROXIES: usize = 1;
+
+/// Cached trusted proxy count from environment.
+static TRUSTED_PROXIES_CACHE: OnceLock<usize> = OnceLock::new();
+
+/// Returns how many reverse proxies in front of the component are trusted.
+///
+/// Reads from `MIK_TRUSTED_PROXIES` on first call, falling back to 1 (the
+/// runtime's own gateway). [`Request::client_ip`](crate::Request::client_ip)
+/// takes the address that many hops from the right of `Forwarded` /
+/// `X-Forwarded-For`; `0` ignores forwarded headers entirely.
+#[inline]
+pub fn get_trusted_proxies() -> usize {
+    *TRUSTED_PROXIES_CACHE.get_or_init(|| {
+        std::env::var("MIK_TRUSTED_PROXIES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_TRUSTED_PROXIES)
+    })
+}
+
 // ============================================================================
 // ENCODING
 // ============================================================================
@@ -109,6 +164,16 @@ pub const MAX_TOTAL_HEADERS_SIZE: usize = 1024 * 1024;
 /// Hex character lookup table for fast byte-to-hex conversion.
 pub const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
 
+/// Letters and digits used by [`random::alphanumeric`](crate::random::alphanumeric).
+pub const ALPHANUMERIC_CHARS: &[u8; 62] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Bitcoin base58 alphabet (no `0`, `O`, `I` or `l`).
+pub const BASE58_CHARS: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Crockford base32 alphabet in lowercase (no `i`, `l`, `o` or `u`).
+pub const BASE32_CHARS: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
 // ============================================================================
 // COMMON HEADER NAMES
 // ============================================================================
@@ -122,6 +187,15 @@ pub const HEADER_CONTENT_TYPE_TITLE: &str = "Content-Type";
 /// Authorization header name (lowercase for lookups).
 pub const HEADER_AUTHORIZATION: &str = "authorization";
 
+/// WWW-Authenticate header name (title-case for setting headers).
+pub const HEADER_WWW_AUTHENTICATE: &str = "WWW-Authenticate";
+
+/// Allow header name, listing the methods a path supports (sent with 405).
+pub const HEADER_ALLOW: &str = "allow";
+
+/// Location header name, the target of a redirect.
+pub const HEADER_LOCATION: &str = "location";
+
 /// W3C Trace Context header name (always lowercase per spec).
 pub const HEADER_TRACE_ID: &str = "traceparent";
 
@@ -141,12 +215,30 @@ pub const MIME_PROBLEM_JSON: &str = "application/problem+json";
 /// HTML MIME type.
 pub const MIME_HTML: &str = "text/html";
 
+/// Plain text MIME type (UTF-8).
+pub const MIME_TEXT: &str = "text/plain; charset=utf-8";
+
 /// Form URL-encoded MIME type.
 pub const MIME_FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
 
 /// Multipart form-data MIME type.
 pub const MIME_MULTIPART: &str = "multipart/form-data";
 
+/// Server-Sent Events MIME type.
+pub const MIME_EVENT_STREAM: &str = "text/event-stream";
+
+/// Newline-delimited JSON MIME type.
+pub const MIME_NDJSON: &str = "application/x-ndjson";
+
+/// Protobuf MIME type.
+pub const MIME_PROTOBUF: &str = "application/x-protobuf";
+
+/// CSV MIME type (UTF-8).
+pub const MIME_CSV: &str = "text/csv; charset=utf-8";
+
+/// Prometheus text exposition format MIME type.
+pub const MIME_PROMETHEUS: &str = "text/plain; version=0.0.4; charset=utf-8";
+
 // ============================================================================
 // COOKIE CONSTANTS
 // ============================================================================
@@ -192,6 +284,7 @@ pub const fn status_title(code: u16) -> &'static str {
         201 => "Created",
         202 => "Accepted",
         204 => "No Content",
+        206 => "Partial Content",
         // 3xx Redirection
         301 => "Moved Permanently",
         302 => "Found",
@@ -208,6 +301,7 @@ pub const fn status_title(code: u16) -> &'static str {
         409 => "Conflict",
         410 => "Gone",
         413 => "Payload Too Large",
+        416 => "Range Not Satisfiable",
         422 => "Unprocessable Entity",
         429 => "Too Many Requests",
         // 5xx Server Errors
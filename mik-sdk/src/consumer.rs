@@ -0,0 +1,248 @@
+//! Queue and topic message consumers.
+//!
+//! `consumers!` maps topics to handlers, with the same typed extraction and
+//! validation as `routes!`, and exports them through the
+//! `mik:core/consumer` interface:
+//!
+//! ```ignore
+//! #[derive(Type)]
+//! pub struct OrderMsg {
+//!     #[field(min = 1)]
+//!     pub order_id: String,
+//!     pub total: f64,
+//! }
+//!
+//! consumers! {
+//!     "orders" => handle_order(body: OrderMsg),
+//!     "audit" => record_audit,
+//! }
+//!
+//! fn handle_order(order: OrderMsg, _msg: &Message) -> Result<(), String> {
+//!     charge(&order.order_id, order.total)
+//! }
+//!
+//! fn record_audit(msg: &Message) {
+//!     log!(info, "audit event", topic: msg.topic(), bytes: msg.body().len());
+//! }
+//! ```
+//!
+//! Handlers take their typed inputs (`body:` for JSON, `headers:` for
+//! message metadata) followed by `&Message`, and return `()` or a `Result`
+//! with a displayable error. An error, an input that fails to parse or
+//! validate, or a panic is reported to the host, which decides whether to
+//! redeliver or dead-letter the message. Tasks spawned with
+//! [`tasks::spawn`](crate::tasks::spawn) run once the handler returns.
+//!
+//! # Requirements
+//!
+//! The handler world must export `mik:core/consumer@0.1.0` (as in the
+//! `user-handler-consumer` world), and `consumers!` goes in the same module
+//! as `routes!`. Built with the `messaging` feature, the bridge exports the
+//! `wasi:messaging` incoming handler and passes each message on; the host's
+//! subscriptions decide which topics are delivered.
+
+use crate::typed::{FromHeaders, FromJson, Validate};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// A message delivered from a topic or queue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    topic: String,
+    body: Vec<u8>,
+    metadata: Vec<(String, String)>,
+}
+
+impl Message {
+    /// A message, e.g. to call a consumer in tests.
+    #[must_use]
+    pub const fn new(topic: String, body: Vec<u8>, metadata: Vec<(String, String)>) -> Self {
+        Self {
+            topic,
+            body,
+            metadata,
+        }
+    }
+
+    /// Topic or queue the message was delivered from.
+    #[must_use]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Raw message body.
+    #[must_use]
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Body as UTF-8 text, if valid.
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.body).ok()
+    }
+
+    /// Body parsed as JSON, if valid.
+    #[must_use]
+    pub fn json(&self) -> Option<crate::json::JsonValue> {
+        crate::json::try_parse(&self.body)
+    }
+
+    /// First metadata value for `key` (case-insensitive).
+    #[must_use]
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All metadata entries, in delivery order.
+    #[must_use]
+    pub fn metadata_all(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    /// Content type from the metadata, if any.
+    #[must_use]
+    pub fn content_type(&self) -> Option<&str> {
+        self.metadata(crate::constants::HEADER_CONTENT_TYPE)
+    }
+}
+
+/// Outcome of a consumer: `()` or a `Result` with a displayable error.
+pub trait IntoConsumerResult {
+    /// Convert to the result reported to the host.
+    fn into_consumer_result(self) -> Result<(), String>;
+}
+
+impl IntoConsumerResult for () {
+    fn into_consumer_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: fmt::Display> IntoConsumerResult for Result<(), E> {
+    fn into_consumer_result(self) -> Result<(), String> {
+        self.map_err(|err| err.to_string())
+    }
+}
+
+// ============================================================================
+// DISPATCH (used by consumers!)
+// ============================================================================
+
+/// Parse and validate the JSON body of a message.
+///
+/// Called by code generated by `consumers!` for `body:` inputs.
+#[doc(hidden)]
+pub fn __body<T: FromJson + Validate>(msg: &Message) -> Result<T, String> {
+    let json = msg.json().ok_or_else(|| "Invalid JSON body".to_string())?;
+    let value = T::from_json(&json).map_err(|err| err.to_string())?;
+    value.validate_all().map_err(|err| err.to_string())?;
+    Ok(value)
+}
+
+/// Parse the metadata of a message.
+///
+/// Called by code generated by `consumers!` for `headers:` inputs.
+#[doc(hidden)]
+pub fn __headers<T: FromHeaders>(msg: &Message) -> Result<T, String> {
+    T::from_headers(&msg.metadata).map_err(|err| err.to_string())
+}
+
+/// Run a consumer for `msg`, catching panics and running spawned tasks.
+///
+/// Called by code generated by `consumers!`.
+#[doc(hidden)]
+pub fn __handle(
+    msg: &Message,
+    consumer: impl FnOnce(&Message) -> Result<(), String>,
+) -> Result<(), String> {
+    crate::tasks::__begin();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| consumer(msg)))
+        .unwrap_or_else(|_| Err("consumer panicked".to_string()));
+    if let Err(err) = &result {
+        crate::log_error!("Message on '{}' failed: {}", msg.topic, err);
+    }
+    crate::tasks::__run_pending();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::JsonValue;
+    use crate::typed::{ParseError, ValidationError};
+
+    #[derive(Debug)]
+    struct Order {
+        id: String,
+    }
+
+    impl FromJson for Order {
+        fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
+            let id = value
+                .get("id")
+                .str()
+                .ok_or_else(|| ParseError::missing("id"))?;
+            Ok(Self { id })
+        }
+    }
+
+    impl Validate for Order {
+        fn validate(&self) -> Result<(), ValidationError> {
+            if self.id.is_empty() {
+                return Err(ValidationError::min("id", 1));
+            }
+            Ok(())
+        }
+    }
+
+    fn message(body: &str) -> Message {
+        Message::new(
+            "orders".to_string(),
+            body.as_bytes().to_vec(),
+            vec![("Content-Type".to_string(), "application/json".to_string())],
+        )
+    }
+
+    #[test]
+    fn test_accessors() {
+        let msg = message(r#"{"id":"42"}"#);
+        assert_eq!(msg.topic(), "orders");
+        assert_eq!(msg.text(), Some(r#"{"id":"42"}"#));
+        assert_eq!(msg.content_type(), Some("application/json"));
+        assert_eq!(msg.metadata("missing"), None);
+    }
+
+    #[test]
+    fn test_body() {
+        let order: Order = __body(&message(r#"{"id":"42"}"#)).unwrap();
+        assert_eq!(order.id, "42");
+        assert!(__body::<Order>(&message("not json")).is_err());
+        assert!(__body::<Order>(&message("{}")).is_err());
+        assert!(__body::<Order>(&message(r#"{"id":""}"#)).is_err());
+    }
+
+    #[test]
+    fn test_handle() {
+        let msg = message("{}");
+        assert_eq!(__handle(&msg, |_| Ok(())), Ok(()));
+        assert_eq!(
+            __handle(&msg, |_| Err("declined".to_string())),
+            Err("declined".to_string())
+        );
+        assert_eq!(
+            __handle(&msg, |_| panic!("boom")),
+            Err("consumer panicked".to_string())
+        );
+    }
+
+    #[test]
+    fn test_into_consumer_result() {
+        assert_eq!(().into_consumer_result(), Ok(()));
+        let err: Result<(), &str> = Err("nope");
+        assert_eq!(err.into_consumer_result(), Err("nope".to_string()));
+    }
+}
@@ -0,0 +1,311 @@
+//! Cookies: building `Set-Cookie` headers, plus signed and encrypted values.
+//!
+//! Plain cookies are built with [`SetCookie`]. For values the client must not
+//! tamper with or read, use a [`CookieKey`]:
+//!
+//! - **Signed** cookies stay readable but carry an HMAC-SHA256 tag, so any
+//!   change is detected.
+//! - **Encrypted** cookies are sealed with AES-256-GCM, hiding the value
+//!   and detecting changes.
+//!
+//! Both bind the cookie name into the tag, so a value cannot be moved to a
+//! cookie with another name.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::cookies::CookieKey;
+//!
+//! // MIK_COOKIE_SECRET must hold at least 32 bytes; while rotating, cookies
+//! // made with MIK_COOKIE_SECRET_PREVIOUS are still accepted
+//! let key = CookieKey::from_env().expect("MIK_COOKIE_SECRET not set");
+//!
+//! // Setting
+//! let cookie = key.encrypted("session", &user_id).http_only().secure().build();
+//! ok!({ "ok": true }, headers: { "set-cookie": cookie })
+//!
+//! // Reading
+//! let user_id = req.encrypted_cookie(key, "session");
+//! ```
+
+use std::sync::OnceLock;
+
+use crate::crypto::{
+    GCM_NONCE_LEN, aes256_gcm_decrypt, aes256_gcm_encrypt, constant_time_eq, hmac_sha256,
+};
+use crate::encoding::{base64url_decode, base64url_encode};
+
+pub use crate::request::{SameSite, SetCookie};
+use crate::secrets::{self, Rotating};
+
+/// Environment variable holding the cookie secret.
+pub const COOKIE_SECRET_ENV: &str = "MIK_COOKIE_SECRET";
+
+/// Separator between a signed cookie's value and its tag.
+const SIGNATURE_SEPARATOR: char = '.';
+
+/// Key for signing and encrypting cookies.
+///
+/// Separate signing and encryption keys are derived from one secret, so the
+/// same secret can safely back both cookie kinds. Keys built from a
+/// [`Rotating`] secret set also accept cookies made with previous secrets.
+#[derive(Clone)]
+pub struct CookieKey {
+    current: KeyPair,
+    previous: Vec<KeyPair>,
+}
+
+/// Signing and encryption keys derived from one secret.
+#[derive(Clone)]
+struct KeyPair {
+    signing: [u8; 32],
+    encryption: [u8; 32],
+}
+
+impl KeyPair {
+    fn derive(secret: &[u8]) -> Option<Self> {
+        if secret.len() < CookieKey::MIN_SECRET_LEN {
+            return None;
+        }
+        Some(Self {
+            signing: hmac_sha256(secret, b"mik-cookie-signing"),
+            encryption: hmac_sha256(secret, b"mik-cookie-encryption"),
+        })
+    }
+
+    fn tag(&self, name: &str, value: &str) -> [u8; 32] {
+        // Length-prefix the name so "a" + "b=c" and "a=b" + "c" differ
+        let mut message = Vec::with_capacity(8 + name.len() + value.len());
+        message.extend_from_slice(&(name.len() as u64).to_be_bytes());
+        message.extend_from_slice(name.as_bytes());
+        message.extend_from_slice(value.as_bytes());
+        hmac_sha256(&self.signing, &message)
+    }
+}
+
+impl CookieKey {
+    /// Minimum secret length in bytes.
+    pub const MIN_SECRET_LEN: usize = 32;
+
+    /// Derive a key from a secret.
+    ///
+    /// Returns `None` if the secret is shorter than [`Self::MIN_SECRET_LEN`].
+    #[must_use]
+    pub fn new(secret: &[u8]) -> Option<Self> {
+        Some(Self {
+            current: KeyPair::derive(secret)?,
+            previous: Vec::new(),
+        })
+    }
+
+    /// Derive a key from a rotating secret set.
+    ///
+    /// New cookies use the current secret; cookies made with a previous
+    /// secret still verify and decrypt. Returns `None` if the current
+    /// secret is too short; too-short previous secrets are skipped.
+    #[must_use]
+    pub fn from_secrets(secrets: &Rotating) -> Option<Self> {
+        Some(Self {
+            current: KeyPair::derive(secrets.current().expose().as_bytes())?,
+            previous: secrets
+                .previous()
+                .iter()
+                .filter_map(|secret| KeyPair::derive(secret.expose().as_bytes()))
+                .collect(),
+        })
+    }
+
+    /// Key derived from `MIK_COOKIE_SECRET` (and `MIK_COOKIE_SECRET_PREVIOUS`
+    /// while rotating), read once and cached.
+    ///
+    /// Returns `None` if the secret is unset or too short.
+    #[must_use]
+    pub fn from_env() -> Option<&'static Self> {
+        static KEY: OnceLock<Option<CookieKey>> = OnceLock::new();
+        KEY.get_or_init(|| {
+            secrets::rotating(COOKIE_SECRET_ENV).and_then(|secrets| Self::from_secrets(&secrets))
+        })
+        .as_ref()
+    }
+
+    /// Build a signed cookie. The value stays readable by the client.
+    #[must_use]
+    pub fn signed(&self, name: &str, value: &str) -> SetCookie {
+        let tag = base64url_encode(&self.current.tag(name, value));
+        SetCookie::new(name, format!("{value}{SIGNATURE_SEPARATOR}{tag}"))
+    }
+
+    /// Verify a signed cookie value and return the original value.
+    ///
+    /// Returns `None` if the tag is missing or does not match.
+    #[must_use]
+    pub fn verify(&self, name: &str, raw: &str) -> Option<String> {
+        let (value, tag) = raw.rsplit_once(SIGNATURE_SEPARATOR)?;
+        let tag = base64url_decode(tag)?;
+        self.keys()
+            .any(|keys| constant_time_eq(&keys.tag(name, value), &tag))
+            .then(|| value.to_string())
+    }
+
+    /// Build an encrypted cookie. The value is hidden from the client.
+    ///
+    /// Uses a fresh random nonce each call, so the same value encrypts
+    /// differently every time.
+    #[must_use]
+    pub fn encrypted(&self, name: &str, value: &str) -> SetCookie {
+        let mut nonce = [0u8; GCM_NONCE_LEN];
+        nonce.copy_from_slice(&crate::random::bytes(GCM_NONCE_LEN));
+        SetCookie::new(name, self.seal(name, value, &nonce))
+    }
+
+    /// Decrypt an encrypted cookie value.
+    ///
+    /// Returns `None` if the value was not produced by this key for this
+    /// cookie name, or was modified.
+    #[must_use]
+    pub fn decrypt(&self, name: &str, raw: &str) -> Option<String> {
+        let data = base64url_decode(raw)?;
+        if data.len() < GCM_NONCE_LEN {
+            return None;
+        }
+        let (nonce, sealed) = data.split_at(GCM_NONCE_LEN);
+        let nonce: [u8; GCM_NONCE_LEN] = nonce.try_into().ok()?;
+        let plaintext = self.keys().find_map(|keys| {
+            aes256_gcm_decrypt(&keys.encryption, &nonce, name.as_bytes(), sealed)
+        })?;
+        String::from_utf8(plaintext).ok()
+    }
+
+    /// Current key first, then previous ones.
+    fn keys(&self) -> impl Iterator<Item = &KeyPair> {
+        std::iter::once(&self.current).chain(&self.previous)
+    }
+
+    fn seal(&self, name: &str, value: &str, nonce: &[u8; GCM_NONCE_LEN]) -> String {
+        let sealed = aes256_gcm_encrypt(
+            &self.current.encryption,
+            nonce,
+            name.as_bytes(),
+            value.as_bytes(),
+        );
+        let mut data = Vec::with_capacity(GCM_NONCE_LEN + sealed.len());
+        data.extend_from_slice(nonce);
+        data.extend_from_slice(&sealed);
+        base64url_encode(&data)
+    }
+}
+
+impl std::fmt::Debug for CookieKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CookieKey").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::Secret;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn key() -> CookieKey {
+        CookieKey::new(SECRET).unwrap()
+    }
+
+    /// Extract the cookie value from a built Set-Cookie string.
+    fn value_of(cookie: SetCookie) -> String {
+        let built = cookie.build();
+        let pair = built.split(';').next().unwrap();
+        pair.split_once('=').unwrap().1.to_string()
+    }
+
+    #[test]
+    fn test_short_secret_rejected() {
+        assert!(CookieKey::new(b"too short").is_none());
+    }
+
+    #[test]
+    fn test_signed_roundtrip() {
+        let raw = value_of(key().signed("user", "alice"));
+        assert!(raw.starts_with("alice."));
+        assert_eq!(key().verify("user", &raw), Some("alice".to_string()));
+    }
+
+    #[test]
+    fn test_signed_value_with_separator() {
+        let raw = value_of(key().signed("v", "a.b.c"));
+        assert_eq!(key().verify("v", &raw), Some("a.b.c".to_string()));
+    }
+
+    #[test]
+    fn test_signed_rejects_tampering() {
+        let raw = value_of(key().signed("user", "alice"));
+        let forged = raw.replacen("alice", "admin", 1);
+        assert!(key().verify("user", &forged).is_none());
+        assert!(key().verify("other", &raw).is_none());
+        assert!(key().verify("user", "alice").is_none());
+
+        let other = CookieKey::new(b"another secret that is long enough!").unwrap();
+        assert!(other.verify("user", &raw).is_none());
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip() {
+        let raw = value_of(key().encrypted("session", "user:42"));
+        assert!(!raw.contains("user"));
+        assert_eq!(key().decrypt("session", &raw), Some("user:42".to_string()));
+    }
+
+    #[test]
+    fn test_encrypted_uses_fresh_nonce() {
+        let a = value_of(key().encrypted("s", "same"));
+        let b = value_of(key().encrypted("s", "same"));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_encrypted_rejects_tampering() {
+        let raw = key().seal("session", "user:42", &[9u8; GCM_NONCE_LEN]);
+        assert!(key().decrypt("other", &raw).is_none());
+
+        let mut bytes = base64url_decode(&raw).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert!(
+            key()
+                .decrypt("session", &base64url_encode(&bytes))
+                .is_none()
+        );
+        assert!(key().decrypt("session", "not base64!").is_none());
+        assert!(key().decrypt("session", "AAAA").is_none());
+    }
+
+    #[test]
+    fn test_rotation_accepts_previous_secret() {
+        let old = key();
+        let signed = value_of(old.signed("user", "alice"));
+        let sealed = value_of(old.encrypted("session", "user:42"));
+
+        let rotated = CookieKey::from_secrets(
+            &Rotating::new(Secret::new("a brand new secret, 32+ bytes long")).with_previous([
+                Secret::new("short"),
+                Secret::new("0123456789abcdef0123456789abcdef"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(rotated.verify("user", &signed), Some("alice".to_string()));
+        assert_eq!(
+            rotated.decrypt("session", &sealed),
+            Some("user:42".to_string())
+        );
+
+        let fresh = value_of(rotated.signed("user", "alice"));
+        assert_ne!(fresh, signed);
+        assert!(old.verify("user", &fresh).is_none());
+    }
+
+    #[test]
+    fn test_debug_hides_key_material() {
+        assert_eq!(format!("{:?}", key()), "CookieKey { .. }");
+    }
+}
@@ -0,0 +1,504 @@
+//! Cross-Origin Resource Sharing (CORS).
+//!
+//! Enable CORS for a whole handler with `#![cors]` in `routes!`. Preflight
+//! `OPTIONS` requests are answered before routing, so no `OPTIONS` routes are
+//! needed, and every response (including errors and 404s) gets the CORS
+//! headers.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! routes! {
+//!     // Configure from MIK_CORS_* environment variables
+//!     #![cors]
+//!
+//!     GET "/users" => list_users,
+//! }
+//!
+//! routes! {
+//!     // Or configure in code
+//!     #![cors(Cors::new()
+//!         .allow_origins(["https://app.example.com"])
+//!         .allow_credentials(true)
+//!         .max_age(3600))]
+//!
+//!     GET "/users" => list_users,
+//! }
+//! ```
+//!
+//! # Environment Variables
+//!
+//! | Variable | Default | Description |
+//! |----------|---------|-------------|
+//! | `MIK_CORS_ALLOW_ORIGINS` | `*` | Comma-separated origins, or `*` for any |
+//! | `MIK_CORS_ALLOW_METHODS` | `GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS` | Comma-separated methods |
+//! | `MIK_CORS_ALLOW_HEADERS` | *(mirror request)* | Comma-separated request headers |
+//! | `MIK_CORS_EXPOSE_HEADERS` | *(none)* | Comma-separated response headers |
+//! | `MIK_CORS_MAX_AGE` | *(none)* | Preflight cache duration in seconds |
+//! | `MIK_CORS_ALLOW_CREDENTIALS` | `false` | Allow cookies and auth headers (needs an origin list) |
+
+use crate::env;
+use crate::middleware::HttpResponse;
+use crate::request::{Method, Request};
+
+/// Environment variable for allowed origins.
+pub const CORS_ALLOW_ORIGINS_ENV: &str = "MIK_CORS_ALLOW_ORIGINS";
+/// Environment variable for allowed methods.
+pub const CORS_ALLOW_METHODS_ENV: &str = "MIK_CORS_ALLOW_METHODS";
+/// Environment variable for allowed request headers.
+pub const CORS_ALLOW_HEADERS_ENV: &str = "MIK_CORS_ALLOW_HEADERS";
+/// Environment variable for exposed response headers.
+pub const CORS_EXPOSE_HEADERS_ENV: &str = "MIK_CORS_EXPOSE_HEADERS";
+/// Environment variable for the preflight max-age in seconds.
+pub const CORS_MAX_AGE_ENV: &str = "MIK_CORS_MAX_AGE";
+/// Environment variable enabling credentialed requests.
+pub const CORS_ALLOW_CREDENTIALS_ENV: &str = "MIK_CORS_ALLOW_CREDENTIALS";
+
+const DEFAULT_METHODS: &str = "GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS";
+
+/// Which origins may make cross-origin requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AllowOrigin {
+    Any,
+    List(Vec<String>),
+}
+
+/// CORS configuration.
+///
+/// Defaults allow any origin, the common methods, and whatever headers the
+/// preflight asks for, without credentials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cors {
+    origins: AllowOrigin,
+    methods: Vec<String>,
+    headers: Vec<String>,
+    expose_headers: Vec<String>,
+    max_age: Option<u32>,
+    credentials: bool,
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cors {
+    /// Permissive configuration: any origin, common methods, mirrored headers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            origins: AllowOrigin::Any,
+            methods: split_list(DEFAULT_METHODS),
+            headers: Vec::new(),
+            expose_headers: Vec::new(),
+            max_age: None,
+            credentials: false,
+        }
+    }
+
+    /// Configuration from the `MIK_CORS_*` environment variables.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_vars(&std::env::vars().collect::<Vec<_>>())
+    }
+
+    /// Configuration from a list of environment variables.
+    #[must_use]
+    pub fn from_vars(vars: &[(String, String)]) -> Self {
+        let mut cors = Self::new();
+        if let Some(origins) = env::get(vars, CORS_ALLOW_ORIGINS_ENV) {
+            cors = cors.allow_origins(split_list(&origins));
+        }
+        if let Some(methods) = env::get(vars, CORS_ALLOW_METHODS_ENV) {
+            cors = cors.allow_methods(split_list(&methods));
+        }
+        if let Some(headers) = env::get(vars, CORS_ALLOW_HEADERS_ENV) {
+            cors = cors.allow_headers(split_list(&headers));
+        }
+        if let Some(headers) = env::get(vars, CORS_EXPOSE_HEADERS_ENV) {
+            cors = cors.expose_headers(split_list(&headers));
+        }
+        if let Some(max_age) = env::get(vars, CORS_MAX_AGE_ENV).and_then(|v| v.trim().parse().ok())
+        {
+            cors = cors.max_age(max_age);
+        }
+        cors.allow_credentials(env::bool(vars, CORS_ALLOW_CREDENTIALS_ENV, false))
+    }
+
+    /// Allow only these origins, e.g. `https://app.example.com`.
+    ///
+    /// A `*` entry allows any origin.
+    #[must_use]
+    pub fn allow_origins<I, S>(mut self, origins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let origins: Vec<String> = origins.into_iter().map(Into::into).collect();
+        self.origins = if origins.iter().any(|origin| origin == "*") {
+            AllowOrigin::Any
+        } else {
+            AllowOrigin::List(origins)
+        };
+        self
+    }
+
+    /// Allowed methods, sent in preflight responses.
+    #[must_use]
+    pub fn allow_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.methods = methods.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Allowed request headers. If empty (the default), the headers
+    /// requested by the preflight are allowed.
+    #[must_use]
+    pub fn allow_headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Response headers readable by browser scripts.
+    #[must_use]
+    pub fn expose_headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.expose_headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// How long browsers may cache a preflight response, in seconds.
+    #[must_use]
+    pub const fn max_age(mut self, seconds: u32) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// Allow cookies and `Authorization` headers on cross-origin requests
+    /// from the origins listed with [`Cors::allow_origins`].
+    ///
+    /// Ignored while any origin is allowed: echoing every origin with
+    /// credentials would let any website read responses as the signed-in
+    /// user.
+    #[must_use]
+    pub const fn allow_credentials(mut self, allow: bool) -> Self {
+        self.credentials = allow;
+        self
+    }
+
+    /// Answer a preflight request. Returns `None` for any other request.
+    ///
+    /// Preflights from disallowed origins get a `204` without CORS headers,
+    /// which makes the browser block the actual request.
+    #[must_use]
+    pub fn preflight<R: HttpResponse>(&self, req: &Request) -> Option<R> {
+        if req.method() != Method::Options
+            || req
+                .header_or("access-control-request-method", "")
+                .is_empty()
+        {
+            return None;
+        }
+        let origin = req.header_or("origin", "");
+        let mut headers = Vec::new();
+        if let Some(allow_origin) = self.allow_origin(origin) {
+            headers.push(("access-control-allow-origin".to_string(), allow_origin));
+            headers.push((
+                "access-control-allow-methods".to_string(),
+                self.methods.join(", "),
+            ));
+            let allow_headers = if self.headers.is_empty() {
+                req.header_or("access-control-request-headers", "")
+                    .to_string()
+            } else {
+                self.headers.join(", ")
+            };
+            if !allow_headers.is_empty() {
+                headers.push(("access-control-allow-headers".to_string(), allow_headers));
+            }
+            if let Some(max_age) = self.max_age {
+                headers.push(("access-control-max-age".to_string(), max_age.to_string()));
+            }
+            if self.allows_credentials() {
+                headers.push((
+                    "access-control-allow-credentials".to_string(),
+                    "true".to_string(),
+                ));
+            }
+        }
+        headers.push(("vary".to_string(), self.vary().to_string()));
+        Some(R::from_parts(204, headers, None))
+    }
+
+    /// Add CORS headers to a response for an allowed origin.
+    #[must_use]
+    pub fn apply<R: HttpResponse>(&self, req: &Request, mut res: R) -> R {
+        let origin = req.header_or("origin", "");
+        if let Some(allow_origin) = self.allow_origin(origin) {
+            let headers = res.headers_mut();
+            headers.push(("access-control-allow-origin".to_string(), allow_origin));
+            if self.allows_credentials() {
+                headers.push((
+                    "access-control-allow-credentials".to_string(),
+                    "true".to_string(),
+                ));
+            }
+            if !self.expose_headers.is_empty() {
+                headers.push((
+                    "access-control-expose-headers".to_string(),
+                    self.expose_headers.join(", "),
+                ));
+            }
+        }
+        if self.echoes_origin() {
+            res.headers_mut()
+                .push(("vary".to_string(), "origin".to_string()));
+        }
+        res
+    }
+
+    /// `Access-Control-Allow-Origin` value for a request origin, if allowed.
+    fn allow_origin(&self, origin: &str) -> Option<String> {
+        if origin.is_empty() {
+            return None;
+        }
+        match &self.origins {
+            AllowOrigin::Any => Some("*".to_string()),
+            AllowOrigin::List(origins) => origins
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+                .then(|| origin.to_string()),
+        }
+    }
+
+    /// Whether credentialed requests are allowed: only for listed origins.
+    const fn allows_credentials(&self) -> bool {
+        self.credentials && matches!(self.origins, AllowOrigin::List(_))
+    }
+
+    /// Whether the allowed origin depends on the request, so caches must
+    /// key on it.
+    const fn echoes_origin(&self) -> bool {
+        matches!(self.origins, AllowOrigin::List(_))
+    }
+
+    const fn vary(&self) -> &'static str {
+        if self.echoes_origin() {
+            "origin, access-control-request-method, access-control-request-headers"
+        } else {
+            "access-control-request-method, access-control-request-headers"
+        }
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+    use std::collections::HashMap;
+
+    fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+        Request::new(
+            method,
+            "/users".to_string(),
+            headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn preflight_request(origin: &str) -> Request {
+        request(
+            Method::Options,
+            &[
+                ("origin", origin),
+                ("access-control-request-method", "POST"),
+                ("access-control-request-headers", "content-type, x-token"),
+            ],
+        )
+    }
+
+    fn header<'a>(res: &'a TestResponse, name: &str) -> Option<&'a str> {
+        res.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_preflight_defaults() {
+        let res: TestResponse = Cors::new()
+            .preflight(&preflight_request("https://a.example"))
+            .unwrap();
+        assert_eq!(res.status, 204);
+        assert_eq!(header(&res, "access-control-allow-origin"), Some("*"));
+        assert_eq!(
+            header(&res, "access-control-allow-methods"),
+            Some(DEFAULT_METHODS)
+        );
+        assert_eq!(
+            header(&res, "access-control-allow-headers"),
+            Some("content-type, x-token")
+        );
+        assert_eq!(header(&res, "access-control-max-age"), None);
+    }
+
+    #[test]
+    fn test_preflight_configured() {
+        let cors = Cors::new()
+            .allow_origins(["https://a.example"])
+            .allow_methods(["GET", "POST"])
+            .allow_headers(["content-type"])
+            .max_age(600)
+            .allow_credentials(true);
+        let res: TestResponse = cors
+            .preflight(&preflight_request("https://a.example"))
+            .unwrap();
+        assert_eq!(
+            header(&res, "access-control-allow-origin"),
+            Some("https://a.example")
+        );
+        assert_eq!(
+            header(&res, "access-control-allow-methods"),
+            Some("GET, POST")
+        );
+        assert_eq!(
+            header(&res, "access-control-allow-headers"),
+            Some("content-type")
+        );
+        assert_eq!(header(&res, "access-control-max-age"), Some("600"));
+        assert_eq!(
+            header(&res, "access-control-allow-credentials"),
+            Some("true")
+        );
+    }
+
+    #[test]
+    fn test_preflight_disallowed_origin() {
+        let cors = Cors::new().allow_origins(["https://a.example"]);
+        let res: TestResponse = cors
+            .preflight(&preflight_request("https://evil.example"))
+            .unwrap();
+        assert_eq!(res.status, 204);
+        assert_eq!(header(&res, "access-control-allow-origin"), None);
+    }
+
+    #[test]
+    fn test_preflight_ignores_plain_options() {
+        let cors = Cors::new();
+        let plain = request(Method::Options, &[("origin", "https://a.example")]);
+        assert!(cors.preflight::<TestResponse>(&plain).is_none());
+        let get = request(
+            Method::Get,
+            &[
+                ("origin", "https://a.example"),
+                ("access-control-request-method", "GET"),
+            ],
+        );
+        assert!(cors.preflight::<TestResponse>(&get).is_none());
+    }
+
+    #[test]
+    fn test_apply_headers() {
+        let cors = Cors::new().expose_headers(["x-total-count"]);
+        let req = request(Method::Get, &[("origin", "https://a.example")]);
+        let res = cors.apply(&req, TestResponse::from_parts(200, Vec::new(), None));
+        assert_eq!(header(&res, "access-control-allow-origin"), Some("*"));
+        assert_eq!(
+            header(&res, "access-control-expose-headers"),
+            Some("x-total-count")
+        );
+        assert_eq!(header(&res, "vary"), None);
+    }
+
+    #[test]
+    fn test_apply_echoes_origin_with_credentials() {
+        let cors = Cors::new()
+            .allow_origins(["https://a.example"])
+            .allow_credentials(true);
+        let req = request(Method::Get, &[("origin", "https://a.example")]);
+        let res = cors.apply(&req, TestResponse::from_parts(200, Vec::new(), None));
+        assert_eq!(
+            header(&res, "access-control-allow-origin"),
+            Some("https://a.example")
+        );
+        assert_eq!(
+            header(&res, "access-control-allow-credentials"),
+            Some("true")
+        );
+        assert_eq!(header(&res, "vary"), Some("origin"));
+    }
+
+    #[test]
+    fn test_credentials_need_origin_list() {
+        // MIK_CORS_ALLOW_CREDENTIALS alone leaves origins at `*`
+        let cors = Cors::from_vars(&[(CORS_ALLOW_CREDENTIALS_ENV.to_string(), "true".to_string())]);
+        let req = request(Method::Get, &[("origin", "https://evil.example")]);
+        let res = cors.apply(&req, TestResponse::from_parts(200, Vec::new(), None));
+        assert_eq!(header(&res, "access-control-allow-origin"), Some("*"));
+        assert_eq!(header(&res, "access-control-allow-credentials"), None);
+
+        let res: TestResponse = cors
+            .preflight(&preflight_request("https://evil.example"))
+            .unwrap();
+        assert_eq!(header(&res, "access-control-allow-origin"), Some("*"));
+        assert_eq!(header(&res, "access-control-allow-credentials"), None);
+    }
+
+    #[test]
+    fn test_apply_without_origin() {
+        let cors = Cors::new();
+        let req = request(Method::Get, &[]);
+        let res = cors.apply(&req, TestResponse::from_parts(200, Vec::new(), None));
+        assert!(res.headers.is_empty());
+    }
+
+    #[test]
+    fn test_from_vars() {
+        let vars: Vec<(String, String)> = [
+            (
+                CORS_ALLOW_ORIGINS_ENV,
+                "https://a.example, https://b.example",
+            ),
+            (CORS_ALLOW_METHODS_ENV, "GET,POST"),
+            (CORS_MAX_AGE_ENV, "120"),
+            (CORS_ALLOW_CREDENTIALS_ENV, "true"),
+        ]
+        .iter()
+        .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+        .collect();
+        let cors = Cors::from_vars(&vars);
+        assert_eq!(
+            cors,
+            Cors::new()
+                .allow_origins(["https://a.example", "https://b.example"])
+                .allow_methods(["GET", "POST"])
+                .max_age(120)
+                .allow_credentials(true)
+        );
+        assert_eq!(Cors::from_vars(&[]), Cors::new());
+    }
+}
@@ -0,0 +1,423 @@
+//! Hashing, HMAC and constant-time comparison.
+//!
+//! SHA-256, SHA-512, HMAC and AES-256-GCM come from the RustCrypto `sha2`,
+//! `hmac` and `aes-gcm` crates, whose AES is constant-time (bitsliced)
+//! where no hardware support exists, as on wasm32, and RSA signature checks
+//! from `rsa`. They back signed cookies, API key checks and OIDC ID
+//! tokens, and are public for things like webhook signatures. The hex and
+//! base64 helpers from [`encoding`](crate::encoding) are re-exported here:
+//!
+//! ```
+//! use mik_sdk::crypto;
+//!
+//! // X-Hub-Signature-256: sha256=<hex HMAC of the raw body>
+//! fn verify_webhook(secret: &[u8], body: &[u8], header: &str) -> bool {
+//!     header
+//!         .strip_prefix("sha256=")
+//!         .and_then(crypto::hex_decode)
+//!         .is_some_and(|tag| crypto::verify_hmac_sha256(secret, body, &tag))
+//! }
+//!
+//! let tag = crypto::hex_encode(&crypto::hmac_sha256(b"secret", b"{}"));
+//! assert!(verify_webhook(b"secret", b"{}", &format!("sha256={tag}")));
+//! assert!(!verify_webhook(b"secret", b"{\"x\":1}", &format!("sha256={tag}")));
+//! ```
+//!
+//! Compare secrets and tags with [`constant_time_eq`], never `==`.
+
+#![allow(clippy::redundant_pub_crate)]
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use hmac::{Hmac, Mac};
+use rsa::{BigUint, Pkcs1v15Sign, RsaPublicKey};
+use sha2::{Digest, Sha256, Sha512};
+
+pub use crate::encoding::{
+    base64_decode, base64_encode, base64url_decode, base64url_encode, hex_decode, hex_encode,
+};
+
+// ============================================================================
+// SHA-2
+// ============================================================================
+
+/// Compute the SHA-256 digest of `data`.
+#[must_use]
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// Compute the SHA-512 digest of `data`.
+#[must_use]
+pub fn sha512(data: &[u8]) -> [u8; 64] {
+    Sha512::digest(data).into()
+}
+
+// ============================================================================
+// HMAC
+// ============================================================================
+
+/// HMAC `M` keyed with `key` and fed `data`.
+fn hmac<M: Mac + KeyInit>(key: &[u8], data: &[u8]) -> M {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    Mac::update(&mut mac, data);
+    mac
+}
+
+/// Compute HMAC-SHA256 of `data` under `key` (RFC 2104).
+#[must_use]
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    hmac::<Hmac<Sha256>>(key, data)
+        .finalize()
+        .into_bytes()
+        .into()
+}
+
+/// Compute HMAC-SHA512 of `data` under `key` (RFC 2104).
+#[must_use]
+pub fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    hmac::<Hmac<Sha512>>(key, data)
+        .finalize()
+        .into_bytes()
+        .into()
+}
+
+/// Check an HMAC-SHA256 `tag` for `data` in constant time.
+#[must_use]
+pub fn verify_hmac_sha256(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    hmac::<Hmac<Sha256>>(key, data).verify_slice(tag).is_ok()
+}
+
+/// Check an HMAC-SHA512 `tag` for `data` in constant time.
+#[must_use]
+pub fn verify_hmac_sha512(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    hmac::<Hmac<Sha512>>(key, data).verify_slice(tag).is_ok()
+}
+
+/// Compare two byte slices in time independent of where they differ.
+///
+/// Slices of different lengths compare unequal immediately; lengths are
+/// not secret for digests and tags.
+#[must_use]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ============================================================================
+// AES-256-GCM
+// ============================================================================
+
+/// Nonce length for AES-GCM (96 bits, the recommended size).
+pub(crate) const GCM_NONCE_LEN: usize = 12;
+
+/// Encrypt `plaintext` with AES-256-GCM, returning `ciphertext || tag`.
+pub(crate) fn aes256_gcm_encrypt(
+    key: &[u8; 32],
+    nonce: &[u8; GCM_NONCE_LEN],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Vec<u8> {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+        .encrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        // Only inputs over 64 GiB are rejected
+        .expect("AES-GCM input too long")
+}
+
+/// Decrypt `ciphertext || tag` with AES-256-GCM.
+///
+/// Returns `None` if the input is too short or the tag does not verify.
+pub(crate) fn aes256_gcm_decrypt(
+    key: &[u8; 32],
+    nonce: &[u8; GCM_NONCE_LEN],
+    aad: &[u8],
+    sealed: &[u8],
+) -> Option<Vec<u8>> {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad })
+        .ok()
+}
+
+// ============================================================================
+// RSA
+// ============================================================================
+
+/// Accepted RSA modulus sizes in bits.
+const RSA_MODULUS_BITS: std::ops::RangeInclusive<usize> = 2048..=8192;
+
+/// Check an RSASSA-PKCS1-v1_5 signature with SHA-256 (RFC 8017), the
+/// `RS256` algorithm of JSON Web Tokens.
+///
+/// `modulus` and `exponent` are the big-endian public key, e.g. the decoded
+/// `n` and `e` of a JWK. Keys shorter than 2048 bits are rejected, and the
+/// signature must be exactly as long as the modulus.
+#[must_use]
+pub fn verify_rsa_sha256(
+    modulus: &[u8],
+    exponent: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let n = BigUint::from_bytes_be(modulus);
+    if !RSA_MODULUS_BITS.contains(&n.bits()) {
+        return false;
+    }
+    let Ok(key) = RsaPublicKey::new_with_max_size(
+        n,
+        BigUint::from_bytes_be(exponent),
+        *RSA_MODULUS_BITS.end(),
+    ) else {
+        return false;
+    };
+    key.verify(Pkcs1v15Sign::new::<Sha256>(), &sha256(message), signature)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        use std::fmt::Write;
+        bytes.iter().fold(String::new(), |mut s, b| {
+            let _ = write!(s, "{b:02x}");
+            s
+        })
+    }
+
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sha256_vectors() {
+        assert_eq!(
+            hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(&sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        // Test case 2
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6: key longer than the block size
+        assert_eq!(
+            hex(&hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_sha512_vectors() {
+        assert_eq!(
+            hex(&sha512(b"abc")),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            hex(&sha512(b"")),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+             47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+        // Two-block message
+        assert_eq!(
+            hex(&sha512(
+                b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                  hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
+            )),
+            "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018\
+             501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha512_rfc4231() {
+        // Test case 1
+        assert_eq!(
+            hex(&hmac_sha512(&[0x0b; 20], b"Hi There")),
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+             daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+        );
+        // Test case 6: key longer than the block size
+        assert_eq!(
+            hex(&hmac_sha512(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+             6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+        );
+    }
+
+    #[test]
+    fn test_verify_hmac() {
+        let tag = hmac_sha256(b"key", b"body");
+        assert!(verify_hmac_sha256(b"key", b"body", &tag));
+        assert!(!verify_hmac_sha256(b"key", b"body!", &tag));
+        assert!(!verify_hmac_sha256(b"key", b"body", &tag[..16]));
+
+        let tag = hmac_sha512(b"key", b"body");
+        assert!(verify_hmac_sha512(b"key", b"body", &tag));
+        assert!(!verify_hmac_sha512(b"other", b"body", &tag));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+    }
+
+    #[test]
+    fn test_gcm_zero_key_vectors() {
+        let key = [0u8; 32];
+        let nonce = [0u8; GCM_NONCE_LEN];
+        // Test case 13: empty plaintext
+        assert_eq!(
+            hex(&aes256_gcm_encrypt(&key, &nonce, b"", b"")),
+            "530f8afbc74536b9a963b4f1c4cb738b"
+        );
+        // Test case 14: one zero block
+        assert_eq!(
+            hex(&aes256_gcm_encrypt(&key, &nonce, b"", &[0u8; 16])),
+            "cea7403d4d606b6e074ec5d3baf39d18d0d1c8a799996bf0265b98b5d48ab919"
+        );
+    }
+
+    #[test]
+    fn test_gcm_with_aad_vector() {
+        // Test case 16
+        let key: [u8; 32] =
+            unhex("feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308")
+                .try_into()
+                .unwrap();
+        let nonce: [u8; 12] = unhex("cafebabefacedbaddecaf888").try_into().unwrap();
+        let aad = unhex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let plaintext = unhex(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        );
+        let sealed = aes256_gcm_encrypt(&key, &nonce, &aad, &plaintext);
+        assert_eq!(
+            hex(&sealed),
+            "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa\
+             8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662\
+             76fc6ece0f4e1768cddf8853bb2d551b"
+        );
+        assert_eq!(
+            aes256_gcm_decrypt(&key, &nonce, &aad, &sealed),
+            Some(plaintext)
+        );
+    }
+
+    #[test]
+    fn test_gcm_rejects_tampering() {
+        let key = [7u8; 32];
+        let nonce = [1u8; GCM_NONCE_LEN];
+        let mut sealed = aes256_gcm_encrypt(&key, &nonce, b"name", b"secret");
+        assert!(aes256_gcm_decrypt(&key, &nonce, b"other", &sealed).is_none());
+        sealed[0] ^= 1;
+        assert!(aes256_gcm_decrypt(&key, &nonce, b"name", &sealed).is_none());
+        assert!(aes256_gcm_decrypt(&key, &nonce, b"name", &[0u8; 4]).is_none());
+    }
+
+    /// RSA-2048 key with e = 65537 and its signature of `b"hello"`.
+    const RSA_N: &str = "b44e80ccc11b4760dc726a9919fd3fead792a3bba4ad0edf0898b09df50b7ef9\
+                         c2d67a8c3f74e8106d1013e921e46c9e77158b99c91a7b3278c5ce183bc0bef0\
+                         ab2fbb3611926792146e968cf4a2321a75e2667c643792ddb184f520d43fccba\
+                         81b46903ba028a7b484dfffd7d037f8cc28c88a39108cad5adf7f1f3f2133fff\
+                         97b68eecf8bcef3f5e70e5ffa3fc4b6e4eaca4bdd94b3850d2c2ecd31a529fe2\
+                         668c286fdad1aacae88acddcab065887ed232482895a39a56fc0238be703a187\
+                         663b3fb36632e47a7aff0067ccec9736fd9d1bf88f86f68e871a874e000d25b1\
+                         75971409f2e9ec9b9ca8076b444704d6658588b70fde3bbe73c0a26d9332bbc9";
+    const RSA_SIG: &str = "6b13548a6149be29726793ff65d77b8ac2c872d59c71902193f787880d5b2a08\
+                           a3c98353175e5efcdf8bac2c8790b23aa25555eab57612401b39a9e1577388c3\
+                           2b2106786c8705f571d56211c8d16b0f007ad2b202326f4a65f8d5bb69e0dc18\
+                           931008999d65ba94c73d7835adcc06829a2a8b238e17438c673863d1a9ff1529\
+                           e0b8b203ae2453b322496ecb6182d9795e0b6a7821653fa1b9ec566d437ff909\
+                           e3203dd67964f744d2496866181acad6b5ad3443110a4b36b114125be8659b48\
+                           0ff2bd92ef5280783c80d637e057be785d2cb71af8a0e62372e55a9f06876e11\
+                           e19c1e10a10a1a82ef6c1fe895b6d97915e71465d7255972c6a0cf1c9e85417c";
+
+    #[test]
+    fn test_verify_rsa_sha256() {
+        let n = unhex(RSA_N);
+        let e = [0x01, 0x00, 0x01];
+        let sig = unhex(RSA_SIG);
+        assert!(verify_rsa_sha256(&n, &e, b"hello", &sig));
+        // A leading zero byte on the modulus, as in some JWKs
+        assert!(verify_rsa_sha256(
+            &[&[0][..], &n].concat(),
+            &e,
+            b"hello",
+            &sig
+        ));
+
+        assert!(!verify_rsa_sha256(&n, &e, b"hellp", &sig));
+        let mut forged = sig.clone();
+        forged[100] ^= 1;
+        assert!(!verify_rsa_sha256(&n, &e, b"hello", &forged));
+        assert!(!verify_rsa_sha256(&n, &[0x03], b"hello", &sig));
+        assert!(!verify_rsa_sha256(&n, &e, b"hello", &sig[1..]));
+        // Short keys are refused
+        assert!(!verify_rsa_sha256(&n[..128], &e, b"hello", &sig[..128]));
+    }
+
+    #[test]
+    fn test_verify_rsa_sha256_rejects_malformed_input() {
+        let n = unhex(RSA_N);
+        let e = [0x01, 0x00, 0x01];
+        let sig = unhex(RSA_SIG);
+
+        // Signature padded to the wrong length, or not below the modulus
+        assert!(!verify_rsa_sha256(
+            &n,
+            &e,
+            b"hello",
+            &[&[0][..], &sig].concat()
+        ));
+        assert!(!verify_rsa_sha256(&n, &e, b"hello", &n));
+        assert!(!verify_rsa_sha256(&n, &e, b"hello", &[0xff; 256]));
+        assert!(!verify_rsa_sha256(&n, &e, b"hello", &[]));
+        // Degenerate or oversized exponents
+        assert!(!verify_rsa_sha256(&n, &[], b"hello", &sig));
+        assert!(!verify_rsa_sha256(&n, &[0x01], b"hello", &sig));
+        assert!(!verify_rsa_sha256(&n, &[0xff; 9], b"hello", &sig));
+        // Empty and oversized moduli
+        assert!(!verify_rsa_sha256(&[], &e, b"hello", &[]));
+        assert!(!verify_rsa_sha256(
+            &[0xff; 1025],
+            &e,
+            b"hello",
+            &[0x01; 1025]
+        ));
+    }
+}
@@ -0,0 +1,387 @@
+//! CSRF protection for form-based endpoints.
+//!
+//! Uses signed double-submit cookies: the token lives in a cookie signed
+//! with the [`CookieKey`], and every unsafe request (anything but `GET`,
+//! `HEAD` and `OPTIONS`) must send the same token back in the `_csrf` form
+//! field or the `x-csrf-token` header. Another site can make the browser
+//! send the cookie, but can neither read it nor forge a signed one, so it
+//! cannot supply the matching token. Mismatches get `403 Forbidden`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::csrf;
+//!
+//! routes! {
+//!     #[before(csrf::protect)]
+//!     #[after(csrf::set_cookie)]
+//!     {
+//!         GET "/signup" => signup_form,
+//!         POST "/signup" => signup(form: SignupForm),
+//!     }
+//! }
+//!
+//! fn signup_form(req: &Request) -> Response {
+//!     html(&format!(
+//!         r#"<form method="post">
+//!              <input type="hidden" name="_csrf" value="{}">
+//!              <input name="email"> <button>Sign up</button>
+//!            </form>"#,
+//!         csrf::csrf_token(req)
+//!     ))
+//! }
+//! ```
+//!
+//! Scripts send the token in the `x-csrf-token` header instead, e.g. read
+//! from a `<meta name="csrf-token">` tag rendered the same way.
+//!
+//! # Configuration
+//!
+//! [`protect`], [`csrf_token`] and [`set_cookie`] sign with the key from
+//! `MIK_COOKIE_SECRET` (see [`CookieKey::from_env`]). Without it, unsafe
+//! requests are all rejected. Build a [`Csrf`] for another key or other
+//! names.
+
+use std::cell::RefCell;
+use std::sync::OnceLock;
+
+use crate::Method;
+use crate::cookies::CookieKey;
+use crate::crypto::constant_time_eq;
+use crate::encoding::base64url_encode;
+use crate::middleware::{HttpResponse, problem};
+use crate::request::Request;
+
+/// Cookie holding the signed token by default.
+pub const DEFAULT_CSRF_COOKIE: &str = "csrf";
+
+/// Request header checked for the token by default.
+pub const DEFAULT_CSRF_HEADER: &str = "x-csrf-token";
+
+/// Form field checked for the token by default.
+pub const DEFAULT_CSRF_FIELD: &str = "_csrf";
+
+/// Random bytes in a token.
+const TOKEN_BYTES: usize = 32;
+
+thread_local! {
+    /// Token issued while handling the current request, not yet sent as a
+    /// cookie.
+    static ISSUED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// CSRF check and token issuer, usable as `#[before(...)]` and
+/// `#[after(...)]` hooks.
+///
+/// # Examples
+///
+/// ```ignore
+/// use mik_sdk::csrf::Csrf;
+///
+/// fn csrf() -> &'static Csrf {
+///     static CSRF: OnceLock<Csrf> = OnceLock::new();
+///     CSRF.get_or_init(|| Csrf::new(admin_key()).cookie("admin_csrf"))
+/// }
+///
+/// fn admin_csrf(req: &Request) -> Option<Response> {
+///     csrf().check(req)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Csrf {
+    key: Option<CookieKey>,
+    cookie: String,
+    header: String,
+    field: String,
+}
+
+impl Csrf {
+    /// Sign tokens with `key`, using the default cookie, header and field.
+    #[must_use]
+    pub fn new(key: CookieKey) -> Self {
+        Self::with_key(Some(key))
+    }
+
+    /// Sign tokens with the key from `MIK_COOKIE_SECRET`.
+    ///
+    /// Without a usable secret, every unsafe request is rejected and
+    /// [`Csrf::token`] returns an empty string.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::with_key(CookieKey::from_env().cloned())
+    }
+
+    fn with_key(key: Option<CookieKey>) -> Self {
+        Self {
+            key,
+            cookie: DEFAULT_CSRF_COOKIE.to_string(),
+            header: DEFAULT_CSRF_HEADER.to_string(),
+            field: DEFAULT_CSRF_FIELD.to_string(),
+        }
+    }
+
+    /// Keep the signed token in cookie `name`.
+    #[must_use]
+    pub fn cookie(mut self, name: impl Into<String>) -> Self {
+        self.cookie = name.into();
+        self
+    }
+
+    /// Read the token from request header `name`.
+    #[must_use]
+    pub fn header(mut self, name: impl Into<String>) -> Self {
+        self.header = name.into();
+        self
+    }
+
+    /// Read the token from form field `name`.
+    #[must_use]
+    pub fn field(mut self, name: impl Into<String>) -> Self {
+        self.field = name.into();
+        self
+    }
+
+    /// Check the request. Returns `None` for safe methods and for unsafe
+    /// requests sending the token from their cookie.
+    #[must_use]
+    pub fn check<R: HttpResponse>(&self, req: &Request) -> Option<R> {
+        // A new request: drop any token issued for the previous one
+        ISSUED.with(|issued| issued.borrow_mut().take());
+        if matches!(req.method(), Method::Get | Method::Head | Method::Options) {
+            return None;
+        }
+        let Some(expected) = self.cookie_token(req) else {
+            return Some(problem(403, "Missing or invalid CSRF cookie"));
+        };
+        let sent = match req.header_or(&self.header, "") {
+            "" if req.is_form() => req.form_or(&self.field, ""),
+            sent => sent,
+        };
+        if !sent.is_empty() && constant_time_eq(sent.as_bytes(), expected.as_bytes()) {
+            None
+        } else {
+            Some(problem(403, "Missing or invalid CSRF token"))
+        }
+    }
+
+    /// The token to embed in forms rendered for this request.
+    ///
+    /// Reuses the token from the request's cookie; otherwise issues a new
+    /// one, which [`Csrf::set_cookie`] then sends as the cookie.
+    #[must_use]
+    pub fn token(&self, req: &Request) -> String {
+        if self.key.is_none() {
+            return String::new();
+        }
+        if let Some(token) = self.cookie_token(req) {
+            return token;
+        }
+        ISSUED.with(|issued| {
+            issued
+                .borrow_mut()
+                .get_or_insert_with(|| base64url_encode(&crate::random::bytes(TOKEN_BYTES)))
+                .clone()
+        })
+    }
+
+    /// Add the cookie for a token issued by [`Csrf::token`] while handling
+    /// this request. Other responses pass unchanged.
+    #[must_use]
+    pub fn set_cookie<R: HttpResponse>(&self, _req: &Request, mut res: R) -> R {
+        let issued = ISSUED.with(|issued| issued.borrow_mut().take());
+        if let (Some(token), Some(key)) = (issued, &self.key) {
+            let cookie = key
+                .signed(&self.cookie, &token)
+                .http_only()
+                .secure()
+                .same_site_lax()
+                .path("/")
+                .build();
+            res.headers_mut().push(("set-cookie".to_string(), cookie));
+        }
+        res
+    }
+
+    fn cookie_token(&self, req: &Request) -> Option<String> {
+        req.signed_cookie(self.key.as_ref()?, &self.cookie)
+            .filter(|token| !token.is_empty())
+    }
+}
+
+impl std::fmt::Debug for Csrf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Csrf")
+            .field("cookie", &self.cookie)
+            .field("header", &self.header)
+            .field("field", &self.field)
+            .field("configured", &self.key.is_some())
+            .finish()
+    }
+}
+
+fn env_csrf() -> &'static Csrf {
+    static DEFAULT: OnceLock<Csrf> = OnceLock::new();
+    DEFAULT.get_or_init(Csrf::from_env)
+}
+
+/// Reject unsafe requests without a valid CSRF token.
+///
+/// Shorthand for `Csrf::from_env().check(req)`, ready for
+/// `#[before(csrf::protect)]`.
+#[must_use]
+pub fn protect<R: HttpResponse>(req: &Request) -> Option<R> {
+    env_csrf().check(req)
+}
+
+/// The CSRF token to embed in forms, signed with `MIK_COOKIE_SECRET`.
+///
+/// Pair with `#[after(csrf::set_cookie)]` so a newly issued token reaches
+/// the browser as a cookie.
+#[must_use]
+pub fn csrf_token(req: &Request) -> String {
+    env_csrf().token(req)
+}
+
+/// Send the cookie for a token issued by [`csrf_token`], ready for
+/// `#[after(csrf::set_cookie)]`.
+#[must_use]
+pub fn set_cookie<R: HttpResponse>(req: &Request, res: R) -> R {
+    env_csrf().set_cookie(req, res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+    use std::collections::HashMap;
+
+    fn csrf() -> Csrf {
+        Csrf::new(CookieKey::new(b"0123456789abcdef0123456789abcdef").unwrap())
+    }
+
+    fn request(method: Method, headers: &[(&str, &str)], body: Option<&str>) -> Request {
+        Request::new(
+            method,
+            "/signup".to_string(),
+            headers
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                .collect(),
+            body.map(|body| body.as_bytes().to_vec()),
+            HashMap::new(),
+        )
+    }
+
+    fn status(csrf: &Csrf, req: &Request) -> Option<u16> {
+        csrf.check::<TestResponse>(req).map(|res| res.status)
+    }
+
+    /// Render a form, returning its token and the cookie header to send back.
+    fn issue(csrf: &Csrf) -> (String, String) {
+        let get = request(Method::Get, &[], None);
+        assert_eq!(status(csrf, &get), None);
+        let token = csrf.token(&get);
+        let res = csrf.set_cookie(
+            &get,
+            TestResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: None,
+            },
+        );
+        let (_, cookie) = res
+            .headers
+            .iter()
+            .find(|(name, _)| name == "set-cookie")
+            .expect("cookie issued");
+        assert!(cookie.contains("HttpOnly"));
+        assert!(cookie.contains("SameSite=Lax"));
+        let pair = cookie.split(';').next().unwrap().to_string();
+        (token, pair)
+    }
+
+    #[test]
+    fn test_form_roundtrip() {
+        let csrf = csrf();
+        let (token, cookie) = issue(&csrf);
+        assert_eq!(token.len(), 43);
+
+        let body = format!("email=a%40b.c&_csrf={token}");
+        let form = [
+            ("cookie", cookie.as_str()),
+            ("content-type", "application/x-www-form-urlencoded"),
+        ];
+        assert_eq!(
+            status(&csrf, &request(Method::Post, &form, Some(&body))),
+            None
+        );
+
+        // The next page reuses the cookie's token and sets no new cookie
+        let get = request(Method::Get, &[("cookie", cookie.as_str())], None);
+        assert_eq!(csrf.token(&get), token);
+        let res = csrf.set_cookie(
+            &get,
+            TestResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: None,
+            },
+        );
+        assert!(res.headers.is_empty());
+    }
+
+    #[test]
+    fn test_header_token() {
+        let csrf = csrf();
+        let (token, cookie) = issue(&csrf);
+        let headers = [("cookie", cookie.as_str()), ("x-csrf-token", &token)];
+        assert_eq!(
+            status(&csrf, &request(Method::Delete, &headers, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rejects_missing_or_forged_tokens() {
+        let csrf = csrf();
+        let (token, cookie) = issue(&csrf);
+
+        // No cookie
+        let headers = [("x-csrf-token", token.as_str())];
+        assert_eq!(
+            status(&csrf, &request(Method::Post, &headers, None)),
+            Some(403)
+        );
+        // No token
+        let headers = [("cookie", cookie.as_str())];
+        assert_eq!(
+            status(&csrf, &request(Method::Put, &headers, None)),
+            Some(403)
+        );
+        // Wrong token
+        let headers = [("cookie", cookie.as_str()), ("x-csrf-token", "guess")];
+        assert_eq!(
+            status(&csrf, &request(Method::Patch, &headers, None)),
+            Some(403)
+        );
+        // Unsigned cookie chosen by the attacker
+        let headers = [("cookie", "csrf=evil"), ("x-csrf-token", "evil")];
+        assert_eq!(
+            status(&csrf, &request(Method::Post, &headers, None)),
+            Some(403)
+        );
+    }
+
+    #[test]
+    fn test_unconfigured_rejects_unsafe_methods() {
+        let csrf = Csrf::with_key(None);
+        let get = request(Method::Get, &[], None);
+        assert_eq!(status(&csrf, &get), None);
+        assert_eq!(csrf.token(&get), "");
+        let headers = [("x-csrf-token", "")];
+        assert_eq!(
+            status(&csrf, &request(Method::Post, &headers, None)),
+            Some(403)
+        );
+    }
+}
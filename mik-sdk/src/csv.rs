@@ -0,0 +1,409 @@
+//! CSV import and export.
+//!
+//! Uploaded CSV is read into typed rows with [`FromForm`]: each row is
+//! handed over as `(column, value)` pairs, so `#[derive(Form)]` structs
+//! parse rows as they parse HTML forms, with `#[field(rename, default, min,
+//! max)]` and empty cells leaving `Option` fields unset. Rows are written
+//! from [`ToJson`] values, so `#[derive(Type)]` structs export as-is:
+//!
+//! ```ignore
+//! #[derive(Form)]
+//! struct ContactRow {
+//!     #[field(min = 1)]
+//!     name: String,
+//!     email: String,
+//!     age: Option<u32>,
+//! }
+//!
+//! fn import(req: &Request) -> Response {
+//!     let rows: Vec<ContactRow> = match csv::from_request(req) {
+//!         Ok(rows) => rows,
+//!         Err(e) => return bad_request!(e.to_string()),
+//!     };
+//!     // ...
+//! }
+//!
+//! fn export(_req: &Request) -> Response {
+//!     let contacts: Vec<Contact> = db::contacts();
+//!     csv::attachment("contacts.csv", &["id", "name", "email"], &contacts)
+//! }
+//! ```
+//!
+//! The format is RFC 4180: comma-separated, fields optionally quoted with
+//! `"`, quotes doubled inside quoted fields, which may span lines. The first
+//! row names the columns. Errors name the failing row as `[n]`, counting
+//! rows like a spreadsheet does (the header is row 1), e.g.
+//! ``invalid format for `[3].age`: old``.
+
+use crate::Request;
+use crate::constants::MIME_CSV;
+use crate::json::{JsonValue, ToJson};
+use crate::middleware::HttpResponse;
+use crate::response;
+use crate::typed::{FromForm, ParseError};
+
+/// Parse a CSV request body into rows.
+///
+/// A UTF-8 byte order mark, as written by spreadsheet exports, is skipped.
+///
+/// # Errors
+///
+/// Returns an error if the body is missing or not UTF-8, the CSV is
+/// malformed, or a row does not parse as `T`.
+pub fn from_request<T: FromForm>(req: &Request) -> Result<Vec<T>, ParseError> {
+    let text = req
+        .text()
+        .ok_or_else(|| ParseError::custom("", "expected a UTF-8 CSV body"))?;
+    parse(text)
+}
+
+/// Parse CSV text with a header row into rows.
+///
+/// # Errors
+///
+/// Returns an error if the CSV is malformed or a row does not parse as `T`.
+pub fn parse<T: FromForm>(text: &str) -> Result<Vec<T>, ParseError> {
+    let mut records = records(text)?.into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<String> = header.into_iter().map(|h| h.trim().to_string()).collect();
+
+    records
+        .enumerate()
+        .map(|(i, record)| {
+            let path = format!("[{}]", i + 2);
+            if record.len() != header.len() {
+                return Err(ParseError::custom(
+                    &path,
+                    format!("expected {} fields, found {}", header.len(), record.len()),
+                ));
+            }
+            let params: Vec<(String, String)> = header.iter().cloned().zip(record).collect();
+            T::from_form(&params).map_err(|e| e.with_path(&path))
+        })
+        .collect()
+}
+
+/// Split CSV text into records of fields, without interpreting a header.
+///
+/// Blank lines are skipped; a final line break is optional.
+///
+/// # Errors
+///
+/// Returns an error for a quote inside an unquoted field, text after a
+/// closing quote, or an unterminated quoted field.
+pub fn records(text: &str) -> Result<Vec<Vec<String>>, ParseError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut state = State::FieldStart;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        // Line breaks inside quoted fields are kept as-is
+        let newline = matches!(c, '\n' | '\r') && !matches!(state, State::Quoted);
+        if newline && c == '\r' && chars.peek() == Some(&'\n') {
+            chars.next();
+        }
+        state = match (state, c) {
+            (State::Quoted, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+                State::Quoted
+            },
+            (State::Quoted, '"') => State::AfterQuote,
+            (State::Quoted, _) => {
+                field.push(c);
+                State::Quoted
+            },
+            (State::FieldStart, '"') => State::Quoted,
+            // A blank line
+            (State::FieldStart, _) if newline && record.is_empty() => State::FieldStart,
+            (_, ',') => {
+                record.push(std::mem::take(&mut field));
+                State::FieldStart
+            },
+            (_, _) if newline => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                State::FieldStart
+            },
+            (State::AfterQuote, _) => {
+                return Err(row_error(&records, "unexpected text after a closing quote"));
+            },
+            (_, '"') => return Err(row_error(&records, "unexpected quote in an unquoted field")),
+            (_, _) => {
+                field.push(c);
+                State::Unquoted
+            },
+        };
+    }
+
+    match state {
+        State::Quoted => return Err(row_error(&records, "unterminated quoted field")),
+        State::FieldStart if record.is_empty() => {},
+        _ => {
+            record.push(field);
+            records.push(record);
+        },
+    }
+    Ok(records)
+}
+
+/// Builds CSV text row by row.
+///
+/// # Example
+///
+/// ```
+/// use mik_sdk::csv::Writer;
+/// use mik_sdk::json;
+///
+/// let mut csv = Writer::new(&["id", "name"]);
+/// csv.row(&json::obj().set("id", json::int(1)).set("name", json::str("Smith, Jo")));
+/// csv.record(["2", "Lee"]);
+/// assert_eq!(csv.finish(), "id,name\r\n1,\"Smith, Jo\"\r\n2,Lee\r\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Writer {
+    columns: Vec<String>,
+    out: String,
+}
+
+impl Writer {
+    /// Start CSV text with a header row.
+    #[must_use]
+    pub fn new(columns: &[&str]) -> Self {
+        let mut writer = Self {
+            columns: columns.iter().map(ToString::to_string).collect(),
+            out: String::new(),
+        };
+        writer.record(columns);
+        writer
+    }
+
+    /// Append a row with the header columns of a JSON object.
+    ///
+    /// Strings are written as-is, `null` and missing keys as empty cells,
+    /// and other values as JSON.
+    pub fn row(&mut self, row: &impl ToJson) -> &mut Self {
+        let value = row.to_json();
+        let cells: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| cell(&value.get(column)))
+            .collect();
+        self.record(cells)
+    }
+
+    /// Append a row of raw fields.
+    pub fn record<S: AsRef<str>>(&mut self, fields: impl IntoIterator<Item = S>) -> &mut Self {
+        for (i, field) in fields.into_iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            let field = field.as_ref();
+            if field.contains([',', '"', '\r', '\n']) {
+                self.out.push('"');
+                self.out.push_str(&field.replace('"', "\"\""));
+                self.out.push('"');
+            } else {
+                self.out.push_str(field);
+            }
+        }
+        self.out.push_str("\r\n");
+        self
+    }
+
+    /// The CSV text.
+    #[must_use]
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Write rows as CSV text with the given columns.
+#[must_use]
+pub fn to_string<T: ToJson>(columns: &[&str], rows: &[T]) -> String {
+    let mut writer = Writer::new(columns);
+    for row in rows {
+        writer.row(row);
+    }
+    writer.finish()
+}
+
+/// Build a `200` CSV download of `rows` with the given columns.
+///
+/// See [`response::attachment`] for how the filename is sent.
+#[must_use]
+pub fn attachment<R: HttpResponse, T: ToJson>(filename: &str, columns: &[&str], rows: &[T]) -> R {
+    response::attachment(filename, to_string(columns, rows), MIME_CSV)
+}
+
+/// The text of a cell.
+fn cell(value: &JsonValue) -> String {
+    if value.is_null() {
+        String::new()
+    } else {
+        value.str().unwrap_or_else(|| value.to_string())
+    }
+}
+
+/// Where [`records`] is within a field.
+#[derive(Clone, Copy)]
+enum State {
+    /// Before the first character of a field.
+    FieldStart,
+    /// Inside a field without quotes.
+    Unquoted,
+    /// Inside a quoted field.
+    Quoted,
+    /// After the closing quote of a quoted field.
+    AfterQuote,
+}
+
+/// An error in the row after the complete `records`.
+fn row_error(records: &[Vec<String>], message: &str) -> ParseError {
+    ParseError::custom(&format!("[{}]", records.len() + 1), message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+    use crate::middleware::TestResponse;
+    use crate::request::Method;
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq)]
+    struct Contact {
+        name: String,
+        age: Option<u32>,
+    }
+
+    impl FromForm for Contact {
+        fn from_form(params: &[(String, String)]) -> Result<Self, ParseError> {
+            let value = |key: &str| {
+                params
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.as_str())
+                    .filter(|v| !v.is_empty())
+            };
+            let name = value("name").ok_or_else(|| ParseError::missing("name"))?;
+            let age = value("age")
+                .map(|v| v.parse().map_err(|_| ParseError::invalid_format("age", v)))
+                .transpose()?;
+            Ok(Self {
+                name: name.to_string(),
+                age,
+            })
+        }
+    }
+
+    impl ToJson for Contact {
+        fn to_json(&self) -> JsonValue {
+            let age = self
+                .age
+                .map_or_else(json::null, |age| json::int(i64::from(age)));
+            json::obj()
+                .set("name", json::str(&self.name))
+                .set("age", age)
+        }
+    }
+
+    fn contact(name: &str, age: Option<u32>) -> Contact {
+        Contact {
+            name: name.to_string(),
+            age,
+        }
+    }
+
+    #[test]
+    fn test_records() {
+        let text = "a,b,c\r\n1,\"two, \"\"2\"\"\",\n\n\"multi\nline\",,x";
+        assert_eq!(
+            records(text).unwrap(),
+            vec![
+                vec!["a", "b", "c"],
+                vec!["1", "two, \"2\"", ""],
+                vec!["multi\nline", "", "x"],
+            ]
+        );
+        assert_eq!(records("").unwrap(), Vec::<Vec<String>>::new());
+        assert_eq!(
+            records("\u{feff}a\n\"\"\n").unwrap(),
+            vec![vec!["a"], vec![""]]
+        );
+    }
+
+    #[test]
+    fn test_records_errors() {
+        let field = |text| records(text).unwrap_err().field().to_string();
+        assert_eq!(field("a\nb\"c\n"), "[2]");
+        assert_eq!(field("a\n\"b\"c\n"), "[2]");
+        assert_eq!(field("a\nb\n\"c\n"), "[3]");
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = " name ,age\nAda,36\nGrace,\n";
+        assert_eq!(
+            parse::<Contact>(text).unwrap(),
+            vec![contact("Ada", Some(36)), contact("Grace", None)]
+        );
+        assert!(parse::<Contact>("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_row_errors() {
+        let err = parse::<Contact>("name,age\nAda,36\nGrace,old\n").unwrap_err();
+        assert_eq!(err.field(), "[3].age");
+
+        let err = parse::<Contact>("name,age\nAda\n").unwrap_err();
+        assert_eq!(err.field(), "[2]");
+    }
+
+    #[test]
+    fn test_from_request() {
+        let req = Request::new(
+            Method::Post,
+            "/import".to_string(),
+            vec![("content-type".to_string(), "text/csv".to_string())],
+            Some(b"name\r\nAda\r\n".to_vec()),
+            HashMap::new(),
+        );
+        assert_eq!(
+            from_request::<Contact>(&req).unwrap(),
+            vec![contact("Ada", None)]
+        );
+    }
+
+    #[test]
+    fn test_writer() {
+        let rows = [contact("Smith, \"Jo\"", Some(40)), contact("Lee", None)];
+        assert_eq!(
+            to_string(&["name", "age", "email"], &rows),
+            "name,age,email\r\n\"Smith, \"\"Jo\"\"\",40,\r\nLee,,\r\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rows = vec![contact("multi\nline", Some(1)), contact("a,b", None)];
+        let text = to_string(&["name", "age"], &rows);
+        assert_eq!(parse::<Contact>(&text).unwrap(), rows);
+    }
+
+    #[test]
+    fn test_attachment() {
+        let res: TestResponse = attachment("contacts.csv", &["name"], &[contact("Ada", None)]);
+        assert_eq!(res.status, 200);
+        assert_eq!(
+            res.headers[0],
+            ("content-type".to_string(), MIME_CSV.to_string())
+        );
+        assert_eq!(res.body.as_deref(), Some(b"name\r\nAda\r\n".as_slice()));
+    }
+}
@@ -0,0 +1,274 @@
+//! Base64, hex and percent-encoding.
+//!
+//! Dependency-free encoders for the values handlers pass around: auth
+//! headers, signatures, cursors and URL components. Decoders return `None`
+//! on invalid input instead of guessing.
+//!
+//! ```
+//! use mik_sdk::encoding;
+//!
+//! // Authorization: Basic <base64 of user:pass>
+//! let header = format!("Basic {}", encoding::base64_encode(b"alice:s3cret"));
+//! assert_eq!(header, "Basic YWxpY2U6czNjcmV0");
+//!
+//! // Opaque ids in URLs
+//! let cursor = encoding::base64url_encode(b"{\"id\":42}");
+//! assert_eq!(encoding::base64url_decode(&cursor).unwrap(), b"{\"id\":42}");
+//!
+//! // Arbitrary bytes in a query string
+//! let q = encoding::percent_encode("café & co".as_bytes());
+//! assert_eq!(q, "caf%C3%A9%20%26%20co");
+//! assert_eq!(encoding::percent_decode(&q).unwrap(), "café & co".as_bytes());
+//! ```
+//!
+//! | Encoding   | Alphabet                  | Padding        |
+//! |------------|---------------------------|----------------|
+//! | `base64`   | `A-Z a-z 0-9 + /`         | `=` (optional when decoding) |
+//! | `base64url`| `A-Z a-z 0-9 - _`         | none           |
+//! | `hex`      | `0-9 a-f` (decodes `A-F`) | none           |
+//! | `percent`  | RFC 3986 unreserved kept  | none           |
+
+// Lookup tables are indexed with 4- and 6-bit values
+#![allow(clippy::indexing_slicing)]
+
+// ============================================================================
+// BASE64
+// ============================================================================
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode bytes as unpadded base64url (RFC 4648 §5).
+#[must_use]
+pub fn base64url_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..=chunk.len() {
+            out.push(char::from(BASE64URL[((n >> (18 - 6 * i)) & 0x3f) as usize]));
+        }
+    }
+    out
+}
+
+/// Decode unpadded base64url. Returns `None` on invalid input.
+#[must_use]
+pub fn base64url_decode(input: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' => 62,
+            b'_' => 63,
+            _ => return None,
+        };
+        Some(u32::from(v))
+    }
+
+    let bytes = input.as_bytes();
+    if bytes.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(bytes.len() * 3 / 4);
+    for chunk in bytes.chunks(4) {
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            n |= value(c)? << (18 - 6 * i);
+        }
+        let produced = chunk.len() - 1;
+        out.extend_from_slice(&n.to_be_bytes()[1..=produced]);
+    }
+    Some(out)
+}
+
+/// Decode standard base64 (RFC 4648 §4), with or without padding.
+#[must_use]
+pub fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    if input.contains(['-', '_']) {
+        return None;
+    }
+    let unpadded = input
+        .strip_suffix("==")
+        .or_else(|| input.strip_suffix('='))
+        .unwrap_or(input);
+    base64url_decode(&unpadded.replace('+', "-").replace('/', "_"))
+}
+
+/// Encode bytes as padded standard base64 (RFC 4648 §4).
+#[must_use]
+pub fn base64_encode(data: &[u8]) -> String {
+    let mut out = base64url_encode(data).replace('-', "+").replace('_', "/");
+    while !out.len().is_multiple_of(4) {
+        out.push('=');
+    }
+    out
+}
+
+// ============================================================================
+// HEX
+// ============================================================================
+
+/// Encode bytes as lowercase hex.
+#[must_use]
+pub fn hex_encode(data: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(data.len() * 2);
+    for byte in data {
+        out.push(char::from(DIGITS[usize::from(byte >> 4)]));
+        out.push(char::from(DIGITS[usize::from(byte & 0x0f)]));
+    }
+    out
+}
+
+/// Decode hex, upper or lower case. Returns `None` on invalid input.
+#[must_use]
+pub fn hex_decode(input: &str) -> Option<Vec<u8>> {
+    const fn value(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let bytes = input.as_bytes();
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    bytes
+        .chunks_exact(2)
+        .map(|pair| Some((value(pair[0])? << 4) | value(pair[1])?))
+        .collect()
+}
+
+// ============================================================================
+// PERCENT-ENCODING
+// ============================================================================
+
+/// Percent-encode bytes for use in a URL path segment or query value.
+///
+/// Keeps the RFC 3986 unreserved characters (`A-Z a-z 0-9 - . _ ~`) and
+/// encodes everything else as `%XX`, so the output is safe anywhere in a
+/// URL.
+#[must_use]
+pub fn percent_encode(data: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    let mut out = String::with_capacity(data.len());
+    for &byte in data {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            out.push('%');
+            out.push(char::from(DIGITS[usize::from(byte >> 4)]));
+            out.push(char::from(DIGITS[usize::from(byte & 0x0f)]));
+        }
+    }
+    out
+}
+
+/// Decode `%XX` escapes. Returns `None` on a truncated or non-hex escape.
+///
+/// `+` is kept as-is; use [`url_decode`](crate::url_decode) for
+/// form-encoded query strings where it means a space.
+#[must_use]
+pub fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while let Some(&byte) = bytes.get(i) {
+        if byte == b'%' {
+            let escape = input
+                .get(i + 1..i + 3)
+                .filter(|escape| escape.bytes().all(|b| b.is_ascii_hexdigit()))?;
+            out.push(u8::from_str_radix(escape, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(byte);
+            i += 1;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(hex_encode(&[0x00, 0xab, 0xff]), "00abff");
+        assert_eq!(hex_decode("00ABff"), Some(vec![0x00, 0xab, 0xff]));
+        assert_eq!(hex_decode(""), Some(vec![]));
+        assert!(hex_decode("abc").is_none());
+        assert!(hex_decode("zz").is_none());
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        // RFC 4648 §10
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+        assert_eq!(
+            base64_decode(&base64_encode(&[0xfb, 0xff])),
+            Some(vec![0xfb, 0xff])
+        );
+    }
+
+    #[test]
+    fn test_base64url_roundtrip() {
+        assert_eq!(base64url_encode(b""), "");
+        assert_eq!(base64url_encode(b"f"), "Zg");
+        assert_eq!(base64url_encode(b"fo"), "Zm8");
+        assert_eq!(base64url_encode(b"foo"), "Zm9v");
+        assert_eq!(base64url_encode(&[0xfb, 0xff]), "-_8");
+        for len in 0..20 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            assert_eq!(base64url_decode(&base64url_encode(&data)), Some(data));
+        }
+        assert!(base64url_decode("Zm9v!").is_none());
+        assert!(base64url_decode("Z").is_none());
+    }
+
+    #[test]
+    fn test_base64_decode() {
+        assert_eq!(base64_decode("Zm9vYg=="), Some(b"foob".to_vec()));
+        assert_eq!(base64_decode("Zm9vYmE="), Some(b"fooba".to_vec()));
+        assert_eq!(base64_decode("Zm9vYmE"), Some(b"fooba".to_vec()));
+        assert_eq!(base64_decode("+/8="), Some(vec![0xfb, 0xff]));
+        assert!(base64_decode("-_8").is_none());
+        assert!(base64_decode("Zm=9v").is_none());
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode(b""), "");
+        assert_eq!(percent_encode(b"AZaz09-._~"), "AZaz09-._~");
+        assert_eq!(percent_encode(b"a b/c?d=e&f"), "a%20b%2Fc%3Fd%3De%26f");
+        assert_eq!(percent_encode(&[0x00, 0xff]), "%00%FF");
+        assert_eq!(percent_encode("é".as_bytes()), "%C3%A9");
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b+c"), Some(b"a b+c".to_vec()));
+        assert_eq!(percent_decode("%c3%A9"), Some("é".as_bytes().to_vec()));
+        assert_eq!(percent_decode(""), Some(vec![]));
+        assert!(percent_decode("%").is_none());
+        assert!(percent_decode("%4").is_none());
+        assert!(percent_decode("%zz").is_none());
+        assert!(percent_decode("%+1").is_none());
+        for len in 0..64 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 53) as u8).collect();
+            assert_eq!(percent_decode(&percent_encode(&data)), Some(data));
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Uniform response envelope, enabled with `#![config(envelope)]`.
+//!
+//! With the option set, `routes!` reshapes every response after the handler
+//! and its hooks ran, so clients parse one shape whatever the handler
+//! returned:
+//!
+//! ```text
+//! 200 {"data":{"id":7,"name":"Ada"},"meta":{},"trace_id":"4bf92f3577b34da6a3ce929d0e0e4736"}
+//! 404 {"type":"about:blank","title":"Not Found","status":404,"detail":"User 7 not found","trace_id":"4bf9..."}
+//! ```
+//!
+//! - Successful (`2xx`) `application/json` responses are wrapped as
+//!   `{"data", "meta", "trace_id"}`. Text, binary and streamed bodies pass
+//!   unchanged.
+//! - Error (`4xx`/`5xx`) responses become RFC 7807 problems. A JSON body's
+//!   `detail`, `message` or `error` string, or a text body, is kept as the
+//!   `detail`.
+//!
+//! `trace_id` is the current trace with `#![trace]`, and `null` (or absent
+//! from problems) otherwise. Handlers add to `meta` with [`set_meta`]:
+//!
+//! ```ignore
+//! routes! {
+//!     #![config(envelope)]
+//!     #![trace]
+//!     GET "/users" => list_users,
+//! }
+//!
+//! // {"data":[...],"meta":{"total":42},"trace_id":"..."}
+//! fn list_users(_req: &Request) -> Result<JsonValue, Error> {
+//!     let (users, total) = db::users_page()?;
+//!     envelope::set_meta("total", json::uint(total));
+//!     Ok(users.to_json())
+//! }
+//! ```
+
+use std::cell::RefCell;
+
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_JSON, MIME_PROBLEM_JSON, status_title};
+use crate::json::{self, JsonValue};
+use crate::middleware::HttpResponse;
+
+thread_local! {
+    static META: RefCell<Option<JsonValue>> = const { RefCell::new(None) };
+}
+
+/// Add `key` to the `meta` object of the response being built.
+///
+/// Only used with `#![config(envelope)]`; a key set twice keeps the last
+/// value.
+pub fn set_meta(key: &str, value: JsonValue) {
+    META.with(|meta| {
+        let mut meta = meta.borrow_mut();
+        let current = meta.take().unwrap_or_else(json::obj);
+        *meta = Some(current.set(key, value));
+    });
+}
+
+/// Reset the `meta` of a new request.
+///
+/// Called by code generated by `routes!` with `#![config(envelope)]`.
+#[doc(hidden)]
+pub fn __begin() {
+    META.with(|meta| *meta.borrow_mut() = None);
+}
+
+/// Reshape a response into the envelope or problem format.
+///
+/// Applied to every response by `routes!` with `#![config(envelope)]`;
+/// takes the `meta` set while handling the request.
+#[must_use]
+pub fn wrap<R: HttpResponse>(res: R) -> R {
+    let meta = META.with(|meta| meta.borrow_mut().take());
+    let status = res.status();
+    let media_type = media_type(res.headers());
+    if (200..300).contains(&status) && status != 204 && media_type.as_deref() == Some(MIME_JSON) {
+        let Some(data) = res.body() else {
+            // Streamed bodies can't be wrapped
+            return res;
+        };
+        let data = if data.iter().all(u8::is_ascii_whitespace) {
+            b"null".as_slice()
+        } else {
+            data
+        };
+        let meta = meta.unwrap_or_else(json::obj).to_bytes();
+        let trace_id = trace_id().map_or_else(json::null, json::str).to_bytes();
+        let mut body = Vec::with_capacity(data.len() + meta.len() + trace_id.len() + 32);
+        body.extend_from_slice(b"{\"data\":");
+        body.extend_from_slice(data);
+        body.extend_from_slice(b",\"meta\":");
+        body.extend_from_slice(&meta);
+        body.extend_from_slice(b",\"trace_id\":");
+        body.extend_from_slice(&trace_id);
+        body.push(b'}');
+        return rebuild(res, MIME_JSON, body);
+    }
+    if status < 400 {
+        return res;
+    }
+    let problem = match media_type.as_deref() {
+        Some(MIME_PROBLEM_JSON) => res.body().and_then(json::try_parse_full),
+        Some(MIME_JSON) | None => Some(problem_body(
+            status,
+            &res.body()
+                .and_then(json::try_parse_full)
+                .and_then(|body| detail_of(&body))
+                .unwrap_or_else(|| status_title(status).to_string()),
+        )),
+        Some(media_type) if media_type.starts_with("text/") => {
+            let text = res.body().map(String::from_utf8_lossy).unwrap_or_default();
+            let text = text.trim();
+            Some(problem_body(
+                status,
+                if text.is_empty() {
+                    status_title(status)
+                } else {
+                    text
+                },
+            ))
+        },
+        Some(_) => None,
+    };
+    let Some(mut problem) = problem else {
+        return res;
+    };
+    if let Some(trace_id) = trace_id() {
+        problem = problem.set("trace_id", json::str(trace_id));
+    }
+    rebuild(res, MIME_PROBLEM_JSON, problem.to_bytes())
+}
+
+/// The media type of the response, lowercased and without parameters.
+fn media_type(headers: &[(String, String)]) -> Option<String> {
+    headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(HEADER_CONTENT_TYPE))
+        .map(|(_, value)| {
+            value
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+        })
+}
+
+fn trace_id() -> Option<String> {
+    crate::trace::current_trace_id()
+}
+
+/// The first of `detail`, `message` or `error` that is a string.
+fn detail_of(body: &JsonValue) -> Option<String> {
+    ["detail", "message", "error"]
+        .iter()
+        .find_map(|key| body.get(key).str())
+}
+
+fn problem_body(status: u16, detail: &str) -> JsonValue {
+    json::obj()
+        .set("type", json::str("about:blank"))
+        .set("title", json::str(status_title(status)))
+        .set("status", json::int(i64::from(status)))
+        .set("detail", json::str(detail))
+}
+
+/// Replace the body, its content type and any stale `Content-Length`.
+fn rebuild<R: HttpResponse>(res: R, content_type: &str, body: Vec<u8>) -> R {
+    let mut headers: Vec<(String, String)> = res
+        .headers()
+        .iter()
+        .filter(|(name, _)| {
+            !name.eq_ignore_ascii_case(HEADER_CONTENT_TYPE)
+                && !name.eq_ignore_ascii_case("content-length")
+        })
+        .cloned()
+        .collect();
+    headers.push((HEADER_CONTENT_TYPE.to_string(), content_type.to_string()));
+    R::from_parts(res.status(), headers, Some(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+
+    fn response(status: u16, content_type: &str, body: &str) -> TestResponse {
+        TestResponse {
+            status,
+            headers: vec![
+                (HEADER_CONTENT_TYPE.to_string(), content_type.to_string()),
+                ("x-request-id".to_string(), "r1".to_string()),
+            ],
+            body: Some(body.as_bytes().to_vec()),
+        }
+    }
+
+    fn body(res: &TestResponse) -> JsonValue {
+        json::try_parse_full(res.body.as_deref().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_wraps_json_success() {
+        __begin();
+        set_meta("total", json::int(2));
+        set_meta("page", json::int(1));
+        let res = wrap(response(200, "application/json; charset=utf-8", "[1,2]"));
+        assert_eq!(res.status, 200);
+        let value = body(&res);
+        assert_eq!(value.get("data").len(), Some(2));
+        assert_eq!(value.path_int(&["meta", "total"]), Some(2));
+        assert_eq!(value.path_int(&["meta", "page"]), Some(1));
+        assert!(value.get("trace_id").is_null());
+        assert!(
+            res.headers
+                .contains(&("x-request-id".to_string(), "r1".to_string()))
+        );
+        assert!(
+            res.headers
+                .contains(&(HEADER_CONTENT_TYPE.to_string(), MIME_JSON.to_string()))
+        );
+
+        // Meta does not leak into the next request
+        let value = body(&wrap(response(201, MIME_JSON, r#"{"id":7}"#)));
+        assert_eq!(value.path_int(&["data", "id"]), Some(7));
+        assert_eq!(value.get("meta").keys(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_leaves_other_success_responses() {
+        __begin();
+        let text = response(200, "text/plain", "hello");
+        assert_eq!(wrap(text.clone()), text);
+        let empty = TestResponse {
+            status: 204,
+            headers: vec![(HEADER_CONTENT_TYPE.to_string(), MIME_JSON.to_string())],
+            body: None,
+        };
+        assert_eq!(wrap(empty.clone()), empty);
+        let redirect = response(302, MIME_JSON, "{}");
+        assert_eq!(wrap(redirect.clone()), redirect);
+    }
+
+    #[test]
+    fn test_errors_become_problems() {
+        __begin();
+        let res = wrap(response(
+            404,
+            MIME_JSON,
+            r#"{"message":"User 7 not found"}"#,
+        ));
+        assert!(res.headers.contains(&(
+            HEADER_CONTENT_TYPE.to_string(),
+            MIME_PROBLEM_JSON.to_string()
+        )));
+        let value = body(&res);
+        assert_eq!(value.get("status").int(), Some(404));
+        assert_eq!(value.get("title").str().as_deref(), Some("Not Found"));
+        assert_eq!(
+            value.get("detail").str().as_deref(),
+            Some("User 7 not found")
+        );
+
+        let value = body(&wrap(response(
+            503,
+            "text/plain",
+            "  down for maintenance\n",
+        )));
+        assert_eq!(
+            value.get("detail").str().as_deref(),
+            Some("down for maintenance")
+        );
+
+        let value = body(&wrap(response(500, MIME_JSON, "[]")));
+        assert_eq!(
+            value.get("detail").str().as_deref(),
+            Some("Internal Server Error")
+        );
+
+        let problem = r#"{"type":"about:blank","title":"Conflict","status":409,"detail":"taken"}"#;
+        let value = body(&wrap(response(409, MIME_PROBLEM_JSON, problem)));
+        assert_eq!(value.get("detail").str().as_deref(), Some("taken"));
+
+        let binary = response(500, "application/octet-stream", "x");
+        assert_eq!(wrap(binary.clone()), binary);
+    }
+
+    #[test]
+    fn test_includes_trace_id() {
+        __begin();
+        let mut headers = Vec::new();
+        let traceparent = crate::trace::__begin(&mut headers);
+        let success = body(&wrap(response(200, MIME_JSON, "true")));
+        let error = body(&wrap(response(400, MIME_JSON, "{}")));
+        crate::trace::__end(traceparent.clone(), &mut headers);
+
+        let trace_id = crate::trace::trace_id(&traceparent).unwrap();
+        assert_eq!(success.get("trace_id").str().as_deref(), Some(trace_id));
+        assert_eq!(error.get("trace_id").str().as_deref(), Some(trace_id));
+        assert_eq!(success.get("data").bool(), Some(true));
+    }
+}
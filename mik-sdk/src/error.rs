@@ -0,0 +1,244 @@
+//! Handler error type.
+//!
+//! [`Error`] lets handlers return `Result<Response, Error>` and use `?` on
+//! fallible calls. `routes!` turns the error into an RFC 7807 problem
+//! response with the matching status code.
+//!
+//! # Example
+//!
+//! ```ignore
+//! fn get_user(path: Id, _req: &Request) -> Result<Response, mik_sdk::Error> {
+//!     let user = db::find(path.as_str())
+//!         .ok_or_else(|| mik_sdk::Error::not_found("User not found"))?;
+//!     Ok(ok!({ "id": user.id }))
+//! }
+//! ```
+
+use crate::http_client;
+use crate::middleware::{HttpResponse, problem, validation_problem};
+use crate::typed::{ParseError, ValidationError, ValidationErrors};
+
+/// An error a handler can return instead of a `Response`.
+///
+/// Each variant maps to an HTTP status. The `?` operator converts
+/// [`ParseError`] (400), [`ValidationError`] and [`ValidationErrors`] (422)
+/// and [`http_client::Error`] (502) automatically.
+///
+/// `Internal` and `Upstream` details are logged rather than sent to the
+/// client, which only sees the status title.
+///
+/// This enum is marked `#[non_exhaustive]` to allow adding new variants
+/// in future versions without breaking existing match expressions.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Error {
+    /// 400 Bad Request.
+    BadRequest(String),
+    /// 401 Unauthorized.
+    Unauthorized(String),
+    /// 403 Forbidden.
+    Forbidden(String),
+    /// 404 Not Found.
+    NotFound(String),
+    /// 409 Conflict.
+    Conflict(String),
+    /// 422 Unprocessable Entity, listing every violation.
+    Validation(ValidationErrors),
+    /// 500 Internal Server Error. The message is logged, not returned.
+    Internal(String),
+    /// 502 Bad Gateway from a failed outbound request. Logged, not returned.
+    Upstream(http_client::Error),
+    /// Any other status with a detail message.
+    Status {
+        /// HTTP status code
+        status: u16,
+        /// Problem detail sent to the client
+        detail: String,
+    },
+}
+
+impl Error {
+    /// Create a 400 Bad Request error.
+    #[must_use]
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::BadRequest(detail.into())
+    }
+
+    /// Create a 401 Unauthorized error.
+    #[must_use]
+    pub fn unauthorized(detail: impl Into<String>) -> Self {
+        Self::Unauthorized(detail.into())
+    }
+
+    /// Create a 403 Forbidden error.
+    #[must_use]
+    pub fn forbidden(detail: impl Into<String>) -> Self {
+        Self::Forbidden(detail.into())
+    }
+
+    /// Create a 404 Not Found error.
+    #[must_use]
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::NotFound(detail.into())
+    }
+
+    /// Create a 409 Conflict error.
+    #[must_use]
+    pub fn conflict(detail: impl Into<String>) -> Self {
+        Self::Conflict(detail.into())
+    }
+
+    /// Create a 500 Internal Server Error. The message is only logged.
+    #[must_use]
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal(message.into())
+    }
+
+    /// Create an error with any status code.
+    #[must_use]
+    pub fn status(status: u16, detail: impl Into<String>) -> Self {
+        Self::Status {
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    /// HTTP status code of the response this error becomes.
+    #[must_use]
+    pub const fn status_code(&self) -> u16 {
+        match self {
+            Self::BadRequest(_) => 400,
+            Self::Unauthorized(_) => 401,
+            Self::Forbidden(_) => 403,
+            Self::NotFound(_) => 404,
+            Self::Conflict(_) => 409,
+            Self::Validation(_) => 422,
+            Self::Internal(_) => 500,
+            Self::Upstream(_) => 502,
+            Self::Status { status, .. } => *status,
+        }
+    }
+
+    /// Build the RFC 7807 problem response for this error.
+    #[must_use]
+    pub fn to_response<R: HttpResponse>(&self) -> R {
+        let status = self.status_code();
+        match self {
+            Self::BadRequest(detail)
+            | Self::Unauthorized(detail)
+            | Self::Forbidden(detail)
+            | Self::NotFound(detail)
+            | Self::Conflict(detail)
+            | Self::Status { detail, .. } => problem(status, detail),
+            Self::Validation(errors) => validation_problem(errors),
+            Self::Internal(_) | Self::Upstream(_) => {
+                crate::log::__write_simple_log("error", &self.to_string());
+                problem(status, crate::constants::status_title(status))
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadRequest(detail)
+            | Self::Unauthorized(detail)
+            | Self::Forbidden(detail)
+            | Self::NotFound(detail)
+            | Self::Conflict(detail)
+            | Self::Internal(detail)
+            | Self::Status { detail, .. } => f.write_str(detail),
+            Self::Validation(errors) => write!(f, "{errors}"),
+            Self::Upstream(error) => write!(f, "upstream request failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Validation(errors) => Some(errors),
+            Self::Upstream(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::BadRequest(error.to_string())
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(error: ValidationError) -> Self {
+        Self::Validation(error.into())
+    }
+}
+
+impl From<ValidationErrors> for Error {
+    fn from(errors: ValidationErrors) -> Self {
+        Self::Validation(errors)
+    }
+}
+
+impl From<http_client::Error> for Error {
+    fn from(error: http_client::Error) -> Self {
+        Self::Upstream(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+    use crate::middleware::TestResponse;
+
+    fn detail(res: &TestResponse) -> Option<String> {
+        json::try_parse(res.body.as_deref().unwrap())
+            .unwrap()
+            .path_str(&["detail"])
+    }
+
+    #[test]
+    fn test_status_codes() {
+        assert_eq!(Error::bad_request("x").status_code(), 400);
+        assert_eq!(Error::unauthorized("x").status_code(), 401);
+        assert_eq!(Error::forbidden("x").status_code(), 403);
+        assert_eq!(Error::not_found("x").status_code(), 404);
+        assert_eq!(Error::conflict("x").status_code(), 409);
+        assert_eq!(Error::internal("x").status_code(), 500);
+        assert_eq!(Error::status(429, "slow down").status_code(), 429);
+    }
+
+    #[test]
+    fn test_to_response_uses_detail() {
+        let res: TestResponse = Error::not_found("User not found").to_response();
+        assert_eq!(res.status, 404);
+        assert_eq!(detail(&res), Some("User not found".to_string()));
+    }
+
+    #[test]
+    fn test_internal_details_are_hidden() {
+        let res: TestResponse = Error::internal("db password rejected").to_response();
+        assert_eq!(res.status, 500);
+        assert_eq!(detail(&res), Some("Internal Server Error".to_string()));
+
+        let res: TestResponse = Error::from(http_client::Error::dns("NXDOMAIN")).to_response();
+        assert_eq!(res.status, 502);
+        assert_eq!(detail(&res), Some("Bad Gateway".to_string()));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let err: Error = ParseError::missing("name").into();
+        assert_eq!(err.status_code(), 400);
+
+        let err: Error = ValidationError::min("name", 3).into();
+        assert_eq!(err.status_code(), 422);
+        let res: TestResponse = err.to_response();
+        let body = json::try_parse(res.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body.get("errors").len(), Some(1));
+    }
+}
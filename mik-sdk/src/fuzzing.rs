@@ -0,0 +1,115 @@
+//! Fuzz targets for the request parsers (`fuzzing` feature).
+//!
+//! Each target takes raw fuzzer input and runs it through the same code a
+//! component uses in production. None of them should ever panic; a crash
+//! found by the fuzzer is a bug.
+//!
+//! The `mik-sdk/fuzz` crate wraps each one for
+//! [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz). Downstream fuzz
+//! crates add `mik-sdk = { version = "0.1", features = ["fuzzing"] }` and
+//! do the same:
+//!
+//! ```ignore
+//! // fuzz/fuzz_targets/parse_json.rs
+//! #![no_main]
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| mik_sdk::fuzzing::parse_json(data));
+//! ```
+//!
+//! ```bash
+//! cargo +nightly fuzz run fuzz_json_parse
+//! ```
+
+use crate::json;
+use crate::request::{Method, Request};
+use std::collections::HashMap;
+
+/// Split a request path into decoded segments and parse its query string,
+/// the way `routes!` does before dispatching.
+pub fn parse_request_path(data: &[u8]) {
+    let path = String::from_utf8_lossy(data).into_owned();
+    let req = Request::new(Method::Get, path, Vec::new(), None, HashMap::new());
+    for segment in req.path_without_query().split('/') {
+        let _ = crate::url_decode(segment);
+    }
+    let _ = req.query_all("");
+}
+
+/// Parse JSON both lazily and eagerly, then read from the results.
+pub fn parse_json(data: &[u8]) {
+    if let Some(value) = json::try_parse(data) {
+        // Lazy scanning over the raw bytes
+        let _ = value.path_str(&["a"]);
+        let _ = value.path_int(&["b"]);
+        let _ = value.path_float(&["c"]);
+        let _ = value.path_bool(&["d"]);
+        let _ = value.path_exists(&["e"]);
+        let _ = value.path_is_null(&["f"]);
+        let _ = value.path_str(&["a", "b", "c"]);
+        let _ = value.path_int(&["x", "y", "z"]);
+
+        // Tree operations trigger the full parse
+        let _ = value.get("key");
+        let _ = value.at(0);
+        let _ = value.keys();
+        let _ = value.len();
+        let _ = value.is_empty();
+        let _ = value.to_string();
+    }
+    if let Some(value) = json::try_parse_full(data) {
+        let _ = value.get("test");
+        let _ = value.to_string();
+    }
+}
+
+/// Parse a `multipart/form-data` body.
+///
+/// The first line of the input is the `Content-Type` header (which carries
+/// the boundary), the rest is the body.
+pub fn parse_multipart(data: &[u8]) {
+    let (content_type, body) = data
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or((data, &[][..]), |i| data.split_at(i));
+    let content_type = String::from_utf8_lossy(content_type).into_owned();
+    let body = body.get(1..).unwrap_or_default().to_vec();
+    let req = Request::new(
+        Method::Post,
+        "/".to_string(),
+        vec![("content-type".to_string(), content_type)],
+        Some(body),
+        HashMap::new(),
+    );
+    if let Ok(parts) = req.multipart() {
+        for part in parts {
+            let _ = (part.name(), part.filename(), part.text());
+        }
+    }
+}
+
+/// Percent-decode a URL component.
+pub fn url_decode(data: &[u8]) {
+    let _ = crate::url_decode(&String::from_utf8_lossy(data));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_accept_any_input() {
+        let inputs: [&[u8]; 6] = [
+            b"",
+            b"/users/%ZZ/%E2%82?a=%&b==&&=",
+            b"{\"data\":{\"name\":\"x\"},\"id\":1}",
+            b"[[[[[[[[[[[[[[[[[[[[[[[[[[",
+            b"multipart/form-data; boundary=x\n--x\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n1\r\n--x--",
+            &[0xff, 0xfe, b'\n', 0x00, b'%'],
+        ];
+        for input in inputs {
+            parse_request_path(input);
+            parse_json(input);
+            parse_multipart(input);
+            url_decode(input);
+        }
+    }
+}
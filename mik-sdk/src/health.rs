@@ -0,0 +1,225 @@
+//! Liveness and readiness endpoints.
+//!
+//! A `health "/healthz"` entry in `routes!` serves two endpoints:
+//!
+//! - `GET /healthz` - liveness: always `200 {"status":"ok"}` while the
+//!   component can handle requests.
+//! - `GET /healthz/ready` - readiness: runs the registered checks and answers
+//!   `200` when all pass or `503` when any fails, listing each check.
+//!
+//! Checks are plain functions registered on [`HealthChecks`]:
+//!
+//! ```ignore
+//! fn checks() -> HealthChecks {
+//!     HealthChecks::new()
+//!         .check("config", || std::env::var("DATABASE_URL").map(|_| ()).map_err(|e| e.to_string()))
+//!         .check("kv", ping_kv)
+//! }
+//!
+//! routes! {
+//!     health "/healthz" => checks(),
+//!     GET "/users" => list_users,
+//! }
+//! ```
+//!
+//! ```json
+//! {"status":"unavailable","checks":[{"name":"config","status":"ok"},{"name":"kv","status":"error","error":"connection refused"}]}
+//! ```
+
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_JSON};
+use crate::json;
+use crate::middleware::HttpResponse;
+
+/// A readiness check: `Ok(())` when healthy, or an error message.
+pub type Check = fn() -> Result<(), String>;
+
+/// The readiness checks of a `health` endpoint, run in registration order.
+#[derive(Debug, Clone, Default)]
+pub struct HealthChecks {
+    checks: Vec<(String, Check)>,
+}
+
+impl HealthChecks {
+    /// No checks: readiness is the same as liveness.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { checks: Vec::new() }
+    }
+
+    /// Add a named check.
+    pub fn check(mut self, name: &str, check: Check) -> Self {
+        self.checks.push((name.to_string(), check));
+        self
+    }
+
+    /// Number of registered checks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    /// Whether no checks are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Run every check.
+    #[must_use]
+    pub fn run(&self) -> HealthReport {
+        HealthReport {
+            results: self
+                .checks
+                .iter()
+                .map(|(name, check)| (name.clone(), check()))
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of running [`HealthChecks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    results: Vec<(String, Result<(), String>)>,
+}
+
+impl HealthReport {
+    /// Whether every check passed.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
+
+    /// Each check's name and result, in registration order.
+    #[must_use]
+    pub fn results(&self) -> &[(String, Result<(), String>)] {
+        &self.results
+    }
+
+    /// `200` when healthy, `503` otherwise.
+    #[must_use]
+    pub fn status(&self) -> u16 {
+        if self.is_healthy() { 200 } else { 503 }
+    }
+
+    /// The readiness body, see the [module docs](self).
+    #[must_use]
+    pub fn to_json(&self) -> json::JsonValue {
+        let checks = self
+            .results
+            .iter()
+            .fold(json::arr(), |checks, (name, result)| {
+                let entry = json::obj().set("name", json::str(name));
+                checks.push(match result {
+                    Ok(()) => entry.set("status", json::str("ok")),
+                    Err(error) => entry
+                        .set("status", json::str("error"))
+                        .set("error", json::str(error)),
+                })
+            });
+        json::obj()
+            .set(
+                "status",
+                json::str(if self.is_healthy() {
+                    "ok"
+                } else {
+                    "unavailable"
+                }),
+            )
+            .set("checks", checks)
+    }
+}
+
+/// Build the liveness response.
+///
+/// Called by code generated by `routes!` for `health` entries.
+#[doc(hidden)]
+pub fn __liveness<R: HttpResponse>() -> R {
+    json_response(200, &json::obj().set("status", json::str("ok")))
+}
+
+/// Run the checks and build the readiness response.
+///
+/// Called by code generated by `routes!` for `health` entries.
+#[doc(hidden)]
+pub fn __readiness<R: HttpResponse>(checks: &HealthChecks) -> R {
+    let report = checks.run();
+    json_response(report.status(), &report.to_json())
+}
+
+fn json_response<R: HttpResponse>(status: u16, body: &json::JsonValue) -> R {
+    R::from_parts(
+        status,
+        vec![
+            (HEADER_CONTENT_TYPE.to_string(), MIME_JSON.to_string()),
+            ("cache-control".to_string(), "no-store".to_string()),
+        ],
+        Some(body.to_bytes()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+
+    fn passing() -> Result<(), String> {
+        Ok(())
+    }
+
+    fn failing() -> Result<(), String> {
+        Err("connection refused".to_string())
+    }
+
+    #[test]
+    fn test_liveness() {
+        let res: TestResponse = __liveness();
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body.as_deref(), Some(br#"{"status":"ok"}"#.as_slice()));
+    }
+
+    #[test]
+    fn test_readiness_without_checks() {
+        let res: TestResponse = __readiness(&HealthChecks::new());
+        assert_eq!(res.status, 200);
+        let body = json::try_parse(res.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body.path_str(&["status"]), Some("ok".to_string()));
+        assert_eq!(body.get("checks").len(), Some(0));
+    }
+
+    #[test]
+    fn test_readiness_all_passing() {
+        let checks = HealthChecks::new()
+            .check("config", passing)
+            .check("kv", || Ok(()));
+        assert_eq!(checks.len(), 2);
+        let res: TestResponse = __readiness(&checks);
+        assert_eq!(res.status, 200);
+    }
+
+    #[test]
+    fn test_readiness_failing_check() {
+        let checks = HealthChecks::new()
+            .check("config", passing)
+            .check("kv", failing);
+        let report = checks.run();
+        assert!(!report.is_healthy());
+        assert_eq!(report.results()[1].0, "kv");
+
+        let res: TestResponse = __readiness(&checks);
+        assert_eq!(res.status, 503);
+        let body = json::try_parse(res.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body.path_str(&["status"]), Some("unavailable".to_string()));
+        let checks = body.get("checks");
+        assert_eq!(checks.at(0).path_str(&["status"]), Some("ok".to_string()));
+        assert!(checks.at(0).get("error").is_null());
+        assert_eq!(
+            checks.at(1).path_str(&["status"]),
+            Some("error".to_string())
+        );
+        assert_eq!(
+            checks.at(1).path_str(&["error"]),
+            Some("connection refused".to_string())
+        );
+    }
+}
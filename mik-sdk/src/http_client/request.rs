@@ -89,7 +89,8 @@ impl ClientRequest {
     /// Forward trace ID header to outgoing request.
     ///
     /// If `trace_id` is `None`, no header is added.
-    /// Use with `Request::trace_id()` to propagate trace context.
+    /// Use with `Request::trace_id()` to propagate trace context. With
+    /// `#![trace]` in `routes!`, the current trace is added automatically.
     ///
     /// ```no_run
     /// # use mik_sdk::http_client::{self, Response, Error};
@@ -268,7 +269,22 @@ impl ClientRequest {
     {
         // Validate URL before sending
         let _ = self.parse_url()?;
-        sender(&self)
+        sender(&self.with_current_trace())
+    }
+
+    /// Add the current request's `traceparent` (see [`crate::trace`]) unless
+    /// one is already set.
+    pub(crate) fn with_current_trace(self) -> Self {
+        use crate::constants::HEADER_TRACE_ID;
+        let has_trace = self
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(HEADER_TRACE_ID));
+        if has_trace {
+            return self;
+        }
+        let traceparent = crate::trace::current();
+        self.with_trace_id(traceparent.as_deref())
     }
 
     /// Parse the URL into scheme, authority, and path components.
@@ -0,0 +1,465 @@
+//! Localized messages with `Accept-Language` negotiation.
+//!
+//! A [`Catalog`] holds one message table per locale, usually embedded at
+//! compile time with `include_str!` from JSON or Fluent-style (`.ftl`)
+//! files. Lookups fall back from the requested locale to its primary
+//! language, then to the default locale, so partial translations are fine.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::i18n::Catalog;
+//! use std::sync::OnceLock;
+//!
+//! fn messages() -> &'static Catalog {
+//!     static CATALOG: OnceLock<Catalog> = OnceLock::new();
+//!     CATALOG.get_or_init(|| {
+//!         Catalog::new("en")
+//!             .json("en", include_str!("../locales/en.json"))
+//!             .ftl("fr", include_str!("../locales/fr.ftl"))
+//!     })
+//! }
+//!
+//! fn get_user(req: &Request) -> Response {
+//!     let locale = messages().locale_for(req);
+//!     let id = path_str!(req, "id");
+//!     error! {
+//!         status: 404,
+//!         title: messages().t(locale, "user.not_found", &[("id", &id)]),
+//!     }
+//! }
+//! ```
+//!
+//! # Message Formats
+//!
+//! JSON catalogs are objects of strings; nested objects flatten to dotted
+//! keys (`{"user": {"not_found": "..."}}` defines `user.not_found`).
+//!
+//! FTL catalogs use `key = value` lines, `#` comments, indented
+//! continuation lines, and `.attr = value` attributes (stored as
+//! `key.attr`). Only message lookup and `{ $name }` placeables are
+//! supported, not Fluent selectors or functions.
+//!
+//! Placeholders are written `{name}`, `{$name}` or `{ $name }`; unknown
+//! placeholders are left as-is.
+//!
+//! # Validation Messages
+//!
+//! [`Catalog::validation`] localizes a [`ValidationError`] from the
+//! `validation.<constraint>` key with `field`, `min`, `max`, `pattern` and
+//! `expected` arguments:
+//!
+//! ```json
+//! {
+//!   "validation": {
+//!     "min": "{field} doit contenir au moins {min} caractères",
+//!     "format": "{field} doit être un {expected} valide"
+//!   }
+//! }
+//! ```
+
+use crate::json::{self, JsonValue};
+use crate::request::Request;
+use crate::typed::{ValidationError, ValidationErrors};
+use miniserde::json::Value;
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+
+/// Messages for one locale, keyed by dotted message id.
+type Messages = HashMap<String, String>;
+
+/// Message tables for a set of locales.
+///
+/// Build once (e.g. in a `OnceLock`) and share across requests.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    default_locale: String,
+    locales: Vec<(String, Messages)>,
+}
+
+impl Catalog {
+    /// Create an empty catalog that falls back to `default_locale`.
+    #[must_use]
+    pub fn new(default_locale: &str) -> Self {
+        Self {
+            default_locale: default_locale.to_string(),
+            locales: Vec::new(),
+        }
+    }
+
+    /// Add messages for `locale` from a JSON object.
+    ///
+    /// Nested objects flatten to dotted keys and non-string leaves are
+    /// ignored. Adding a locale twice merges the tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` is not a JSON object. Catalogs are embedded at
+    /// compile time, so this surfaces on first use rather than per request.
+    #[must_use]
+    pub fn json(mut self, locale: &str, source: &str) -> Self {
+        let Ok(Value::Object(root)) = miniserde::json::from_str::<Value>(source) else {
+            panic!("i18n catalog for `{locale}` is not a JSON object");
+        };
+        let mut flat = Vec::new();
+        flatten_json("", &Value::Object(root), &mut flat);
+        self.table_mut(locale).extend(flat);
+        self
+    }
+
+    /// Add messages for `locale` from Fluent-style `key = value` source.
+    ///
+    /// Malformed lines are skipped. Adding a locale twice merges the tables.
+    #[must_use]
+    pub fn ftl(mut self, locale: &str, source: &str) -> Self {
+        let parsed = parse_ftl(source);
+        self.table_mut(locale).extend(parsed);
+        self
+    }
+
+    /// Add a single message, e.g. for overrides in code.
+    #[must_use]
+    pub fn message(mut self, locale: &str, key: &str, text: &str) -> Self {
+        self.table_mut(locale)
+            .insert(key.to_string(), text.to_string());
+        self
+    }
+
+    /// The locale used when nothing better matches.
+    #[must_use]
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// Loaded locales, default first, for [`Request::language`].
+    #[must_use]
+    pub fn locales(&self) -> Vec<&str> {
+        let mut locales = vec![self.default_locale.as_str()];
+        locales.extend(
+            self.locales
+                .iter()
+                .map(|(locale, _)| locale.as_str())
+                .filter(|locale| !locale.eq_ignore_ascii_case(&self.default_locale)),
+        );
+        locales
+    }
+
+    /// Pick the request's locale from its `Accept-Language` header.
+    ///
+    /// Falls back to the default locale when no loaded locale matches.
+    #[must_use]
+    pub fn locale_for(&self, req: &Request) -> &str {
+        req.language(&self.locales())
+            .unwrap_or(&self.default_locale)
+    }
+
+    /// Look up the raw message for `key`.
+    ///
+    /// Tries `locale`, then its primary language (`fr-CA` → `fr`), then the
+    /// default locale.
+    #[must_use]
+    pub fn get(&self, locale: &str, key: &str) -> Option<&str> {
+        let primary = locale.split('-').next().unwrap_or_default();
+        [locale, primary, self.default_locale.as_str()]
+            .into_iter()
+            .filter_map(|candidate| self.table(candidate))
+            .find_map(|table| table.get(key))
+            .map(String::as_str)
+    }
+
+    /// Translate `key`, filling `{name}` placeholders from `args`.
+    ///
+    /// Returns the key itself when no locale defines it, so missing
+    /// translations stay visible without failing the request.
+    ///
+    /// ```ignore
+    /// let msg = catalog.t("fr", "cart.items", &[("count", &3)]);
+    /// ```
+    #[must_use]
+    pub fn t(&self, locale: &str, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        self.get(locale, key)
+            .map_or_else(|| key.to_string(), |template| interpolate(template, args))
+    }
+
+    /// Localize a validation error from its `validation.<constraint>` key.
+    ///
+    /// Falls back to the error's built-in English message.
+    #[must_use]
+    pub fn validation(&self, locale: &str, error: &ValidationError) -> String {
+        let key = format!("validation.{}", error.constraint());
+        let Some(template) = self.get(locale, &key) else {
+            return error.message();
+        };
+        let field = error.field();
+        let args: Vec<(&str, &dyn Display)> = match error {
+            ValidationError::Min { min, .. } => vec![("field", &field), ("min", min)],
+            ValidationError::Max { max, .. } => vec![("field", &field), ("max", max)],
+            ValidationError::Pattern { pattern, .. } => {
+                vec![("field", &field), ("pattern", pattern)]
+            },
+            ValidationError::Format { expected, .. } => {
+                vec![("field", &field), ("expected", expected)]
+            },
+            ValidationError::Custom { message, .. } => {
+                vec![("field", &field), ("message", message)]
+            },
+        };
+        interpolate(template, &args)
+    }
+
+    /// Serialize `errors` like their [`ToJson`](crate::json::ToJson) form,
+    /// with each `message` localized.
+    #[must_use]
+    pub fn validation_errors(&self, locale: &str, errors: &ValidationErrors) -> JsonValue {
+        errors.iter().fold(json::arr(), |arr, violation| {
+            let entry = json::obj()
+                .set("field", json::str(violation.error.field()))
+                .set("code", json::str(violation.error.constraint()))
+                .set(
+                    "message",
+                    json::str(self.validation(locale, &violation.error)),
+                );
+            let entry = match &violation.rejected {
+                Some(value) => entry.set("rejected_value", value.clone()),
+                None => entry,
+            };
+            arr.push(entry)
+        })
+    }
+
+    fn table(&self, locale: &str) -> Option<&Messages> {
+        self.locales
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(locale))
+            .map(|(_, table)| table)
+    }
+
+    fn table_mut(&mut self, locale: &str) -> &mut Messages {
+        let index = self
+            .locales
+            .iter()
+            .position(|(name, _)| name.eq_ignore_ascii_case(locale))
+            .unwrap_or_else(|| {
+                self.locales.push((locale.to_string(), Messages::new()));
+                self.locales.len() - 1
+            });
+        #[allow(clippy::indexing_slicing)] // index comes from position() or push
+        &mut self.locales[index].1
+    }
+}
+
+/// Collect string leaves of `value` as `(dotted.key, text)` pairs.
+fn flatten_json(prefix: &str, value: &Value, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::String(text) => out.push((prefix.to_string(), text.clone())),
+        Value::Object(object) => {
+            for (key, child) in object {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_json(&path, child, out);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Parse `key = value` messages with continuation lines and attributes.
+fn parse_ftl(source: &str) -> Vec<(String, String)> {
+    let mut messages: Vec<(String, String)> = Vec::new();
+    // Message id that continuation and attribute lines attach to
+    let mut current: Option<String> = None;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indented = line.starts_with([' ', '\t']);
+
+        if indented && let Some(id) = &current {
+            if let Some(attribute) = trimmed.strip_prefix('.') {
+                if let Some((name, value)) = attribute.split_once('=') {
+                    messages.push((format!("{id}.{}", name.trim()), value.trim().to_string()));
+                }
+            } else if let Some((key, text)) = messages.last_mut()
+                && key == id
+            {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(trimmed);
+            }
+            continue;
+        }
+
+        current = None;
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let key = key.trim();
+            if !key.is_empty() && !key.contains(char::is_whitespace) {
+                messages.push((key.to_string(), value.trim().to_string()));
+                current = Some(key.to_string());
+            }
+        }
+    }
+    messages
+}
+
+/// Replace `{name}`, `{$name}` and `{ $name }` with values from `args`.
+fn interpolate(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let (before, tail) = rest.split_at(open);
+        out.push_str(before);
+        let Some(close) = tail.find('}') else {
+            rest = tail;
+            break;
+        };
+        let (placeholder, after) = tail.split_at(close + 1);
+        let name = placeholder
+            .trim_start_matches('{')
+            .trim_end_matches('}')
+            .trim()
+            .trim_start_matches('$');
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => {
+                let _ = write!(out, "{value}");
+            },
+            None => out.push_str(placeholder),
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: &str = r#"{
+        "greeting": "Hello, {name}!",
+        "user": { "not_found": "User {id} not found", "count": 3 },
+        "validation": { "min": "{field} must have at least {min} characters" }
+    }"#;
+
+    const FR: &str = "
+# Messages en français
+greeting = Bonjour, { $name } !
+user.not_found = Utilisateur {$id} introuvable
+welcome =
+    Bienvenue
+    sur le site
+    .title = Accueil
+validation.min = {field} doit contenir au moins {min} caractères
+not a message
+";
+
+    fn catalog() -> Catalog {
+        Catalog::new("en").json("en", EN).ftl("fr", FR)
+    }
+
+    #[test]
+    fn test_json_flattens_nested_objects() {
+        let catalog = catalog();
+        assert_eq!(
+            catalog.get("en", "user.not_found"),
+            Some("User {id} not found")
+        );
+        // Non-string leaves are ignored
+        assert_eq!(catalog.get("en", "user.count"), None);
+    }
+
+    #[test]
+    fn test_ftl_parsing() {
+        let catalog = catalog();
+        assert_eq!(catalog.get("fr", "greeting"), Some("Bonjour, { $name } !"));
+        assert_eq!(catalog.get("fr", "welcome"), Some("Bienvenue\nsur le site"));
+        assert_eq!(catalog.get("fr", "welcome.title"), Some("Accueil"));
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let catalog = catalog();
+        assert_eq!(
+            catalog.t("en", "greeting", &[("name", &"Ada")]),
+            "Hello, Ada!"
+        );
+        assert_eq!(
+            catalog.t("fr", "greeting", &[("name", &"Ada")]),
+            "Bonjour, Ada !"
+        );
+        assert_eq!(
+            catalog.t("fr", "user.not_found", &[("id", &42)]),
+            "Utilisateur 42 introuvable"
+        );
+        // Unknown placeholders and stray braces are kept
+        assert_eq!(interpolate("{a} {b} {", &[("a", &1)]), "1 {b} {");
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        let catalog = Catalog::new("en").json("en", EN).ftl("fr", FR).message(
+            "fr-CA",
+            "greeting",
+            "Allô, {name}!",
+        );
+        assert_eq!(
+            catalog.t("fr-CA", "greeting", &[("name", &"Ada")]),
+            "Allô, Ada!"
+        );
+        // fr-CA -> fr
+        assert_eq!(catalog.get("fr-CA", "welcome.title"), Some("Accueil"));
+        // fr -> en
+        assert_eq!(catalog.get("FR", "user.count"), None);
+        assert_eq!(catalog.get("de", "greeting"), Some("Hello, {name}!"));
+        // Missing everywhere returns the key
+        assert_eq!(catalog.t("fr", "missing.key", &[]), "missing.key");
+    }
+
+    #[test]
+    fn test_locales_default_first() {
+        let catalog = Catalog::new("fr").json("en", EN).ftl("fr", FR);
+        assert_eq!(catalog.locales(), vec!["fr", "en"]);
+        assert_eq!(catalog.default_locale(), "fr");
+    }
+
+    #[test]
+    fn test_validation_messages() {
+        let catalog = catalog();
+        let error = ValidationError::min("name", 3);
+        assert_eq!(
+            catalog.validation("fr", &error),
+            "name doit contenir au moins 3 caractères"
+        );
+        assert_eq!(
+            catalog.validation("en", &error),
+            "name must have at least 3 characters"
+        );
+        // No translation: built-in message
+        let error = ValidationError::format("email", "email");
+        assert_eq!(
+            catalog.validation("fr", &error),
+            "`email` must be a valid email"
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_json() {
+        let mut errors = ValidationErrors::new();
+        errors.push_rejected(ValidationError::min("name", 3), json::str("al"));
+        assert_eq!(
+            catalog().validation_errors("fr", &errors).to_string(),
+            r#"[{"code":"min","field":"name","message":"name doit contenir au moins 3 caractères","rejected_value":"al"}]"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not a JSON object")]
+    fn test_invalid_json_panics() {
+        let _ = Catalog::new("en").json("en", "[1, 2]");
+    }
+}
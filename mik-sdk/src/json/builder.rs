@@ -1,5 +1,6 @@
 //! Constructor functions for building JSON values.
 
+use super::is_json_number;
 use super::value::JsonValue;
 use miniserde::json::{Array, Number, Object, Value};
 
@@ -27,6 +28,14 @@ pub fn int(value: i64) -> JsonValue {
     JsonValue::new(Value::Number(Number::I64(value)))
 }
 
+/// Create an unsigned integer value.
+///
+/// Unlike [`int`], values above `i64::MAX` are kept exact.
+#[must_use]
+pub fn uint(value: u64) -> JsonValue {
+    JsonValue::new(Value::Number(Number::U64(value)))
+}
+
 /// Create a float value.
 ///
 /// # Precision Note
@@ -39,6 +48,21 @@ pub fn float(value: f64) -> JsonValue {
     JsonValue::new(Value::Number(Number::F64(value)))
 }
 
+/// Create a decimal value from its text, e.g. `"19.99"`.
+///
+/// The number is written as a JSON string, so no digits are lost here or in
+/// clients that parse JSON numbers as f64. Returns null if `value` is not a
+/// valid JSON number. Read it back with [`JsonValue::decimal`].
+#[must_use]
+pub fn decimal<S: AsRef<str>>(value: S) -> JsonValue {
+    let value = value.as_ref();
+    if is_json_number(value) {
+        str(value)
+    } else {
+        JsonValue::null()
+    }
+}
+
 /// Create a boolean value.
 #[must_use]
 pub fn bool(value: bool) -> JsonValue {
@@ -3,6 +3,8 @@
 //! This module provides functions to scan JSON bytes and extract specific
 //! values by path without parsing the entire document.
 
+use std::borrow::Cow;
+
 /// Find a value at a path in JSON bytes and extract it as a string.
 #[inline]
 pub(super) fn path_str(bytes: &[u8], path: &[&str]) -> Option<String> {
@@ -10,6 +12,13 @@ pub(super) fn path_str(bytes: &[u8], path: &[&str]) -> Option<String> {
     parse_string_value(&bytes[start..end])
 }
 
+/// Find a string at a path in JSON bytes, borrowing it when it has no escapes.
+#[inline]
+pub(super) fn path_str_ref<'a>(bytes: &'a [u8], path: &[&str]) -> Option<Cow<'a, str>> {
+    let (start, end) = find_path_value(bytes, path)?;
+    parse_string_ref(&bytes[start..end])
+}
+
 /// Find a value at a path in JSON bytes and extract it as an integer.
 #[inline]
 pub(super) fn path_int(bytes: &[u8], path: &[&str]) -> Option<i64> {
@@ -17,6 +26,20 @@ pub(super) fn path_int(bytes: &[u8], path: &[&str]) -> Option<i64> {
     parse_int_value(&bytes[start..end])
 }
 
+/// Find a value at a path in JSON bytes and extract it as an unsigned integer.
+#[inline]
+pub(super) fn path_uint(bytes: &[u8], path: &[&str]) -> Option<u64> {
+    let (start, end) = find_path_value(bytes, path)?;
+    parse_uint_value(&bytes[start..end])
+}
+
+/// Find a value at a path in JSON bytes and extract its exact decimal text.
+#[inline]
+pub(super) fn path_decimal(bytes: &[u8], path: &[&str]) -> Option<String> {
+    let (start, end) = find_path_value(bytes, path)?;
+    parse_decimal_value(&bytes[start..end])
+}
+
 /// Find a value at a path in JSON bytes and extract it as a float.
 #[inline]
 pub(super) fn path_float(bytes: &[u8], path: &[&str]) -> Option<f64> {
@@ -31,6 +54,13 @@ pub(super) fn path_bool(bytes: &[u8], path: &[&str]) -> Option<bool> {
     parse_bool_value(&bytes[start..end])
 }
 
+/// Find the raw bytes of the value at a path.
+#[inline]
+pub(super) fn path_slice<'a>(bytes: &'a [u8], path: &[&str]) -> Option<&'a [u8]> {
+    let (start, end) = find_path_value(bytes, path)?;
+    Some(&bytes[start..end])
+}
+
 /// Check if a path exists in JSON bytes.
 #[inline]
 pub(super) fn path_exists(bytes: &[u8], path: &[&str]) -> bool {
@@ -51,49 +81,114 @@ pub(super) fn path_is_null(bytes: &[u8], path: &[&str]) -> bool {
 
 /// Find the byte range of a value at a given path.
 /// Returns (start, end) indices into the bytes slice.
+///
+/// Segments select object keys, or array elements when they are a decimal
+/// index (e.g. `["items", "0", "name"]`).
 fn find_path_value(bytes: &[u8], path: &[&str]) -> Option<(usize, usize)> {
-    if path.is_empty() {
-        // Return the whole value
-        let start = skip_whitespace(bytes, 0);
-        let end = find_value_end(bytes, start)?;
-        return Some((start, end));
-    }
-
     let mut pos = skip_whitespace(bytes, 0);
 
-    // Must start with object
-    if bytes.get(pos)? != &b'{' {
-        return None;
+    for segment in path {
+        pos = match bytes.get(pos)? {
+            b'{' => {
+                // Find the key in current object, then skip the colon
+                pos = find_object_key(bytes, pos + 1, segment)?;
+                pos = skip_whitespace(bytes, pos);
+                if bytes.get(pos)? != &b':' {
+                    return None;
+                }
+                skip_whitespace(bytes, pos + 1)
+            },
+            b'[' => find_array_element(bytes, pos + 1, parse_index(segment)?)?,
+            _ => return None,
+        };
     }
-    pos += 1;
 
-    for (depth, key) in path.iter().enumerate() {
-        pos = skip_whitespace(bytes, pos);
+    let end = find_value_end(bytes, pos)?;
+    Some((pos, end))
+}
 
-        // Find the key in current object
-        pos = find_object_key(bytes, pos, key)?;
+/// Iterator over the elements of a JSON array in raw bytes.
+///
+/// Created by [`JsonValue::iter_array_lazy`](super::JsonValue::iter_array_lazy).
+/// Each element is yielded as the raw bytes of its value, found by scanning
+/// past the previous one, so only the current element is ever materialized.
+/// Iteration stops at the end of the array, or early if the bytes are
+/// malformed.
+#[derive(Debug, Clone)]
+pub struct LazyArrayIter<'a> {
+    bytes: &'a [u8],
+    /// Position after the last yielded element, or `None` once finished
+    pos: Option<usize>,
+}
 
-        // Skip the colon
-        pos = skip_whitespace(bytes, pos);
-        if bytes.get(pos)? != &b':' {
-            return None;
-        }
-        pos += 1;
-        pos = skip_whitespace(bytes, pos);
+impl<'a> LazyArrayIter<'a> {
+    /// Start iterating the array at the root of `bytes`, or `None` if the
+    /// root is not an array.
+    pub(super) fn new(bytes: &'a [u8]) -> Option<Self> {
+        let pos = skip_whitespace(bytes, 0);
+        (bytes.get(pos)? == &b'[').then_some(Self {
+            bytes,
+            pos: Some(pos + 1),
+        })
+    }
 
-        if depth == path.len() - 1 {
-            // Last key - return the value range
-            let end = find_value_end(bytes, pos)?;
-            return Some((pos, end));
+    fn next_element(&mut self) -> Option<&'a [u8]> {
+        let mut pos = skip_whitespace(self.bytes, self.pos?);
+        if self.bytes.get(pos)? == &b',' {
+            pos = skip_whitespace(self.bytes, pos + 1);
         }
-        // Need to descend into nested object
-        if bytes.get(pos)? != &b'{' {
+        if self.bytes.get(pos)? == &b']' {
             return None;
         }
-        pos += 1;
+        let end = find_value_end(self.bytes, pos)?;
+        self.pos = Some(end);
+        Some(&self.bytes[pos..end])
     }
+}
 
-    None
+impl<'a> Iterator for LazyArrayIter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.next_element();
+        if element.is_none() {
+            self.pos = None;
+        }
+        element
+    }
+}
+
+impl std::iter::FusedIterator for LazyArrayIter<'_> {}
+
+/// Parse a path segment as an array index (digits only).
+fn parse_index(segment: &str) -> Option<usize> {
+    if segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    segment.parse().ok()
+}
+
+/// Find the element at `index` in an array starting at pos (after the `[`),
+/// return the position where the element's value starts.
+fn find_array_element(bytes: &[u8], mut pos: usize, index: usize) -> Option<usize> {
+    let mut current = 0;
+    loop {
+        pos = skip_whitespace(bytes, pos);
+
+        match bytes.get(pos)? {
+            b']' => return None, // End of array, index out of bounds
+            b',' => {
+                pos += 1;
+            },
+            _ => {
+                if current == index {
+                    return Some(pos);
+                }
+                pos = find_value_end(bytes, pos)?;
+                current += 1;
+            },
+        }
+    }
 }
 
 /// Find a key in an object starting at pos, return position after the closing quote.
@@ -277,6 +372,12 @@ fn trim_whitespace(bytes: &[u8]) -> &[u8] {
 
 /// Parse a JSON string value from bytes (including quotes).
 fn parse_string_value(bytes: &[u8]) -> Option<String> {
+    parse_string_ref(bytes).map(Cow::into_owned)
+}
+
+/// Parse a JSON string value from bytes (including quotes), borrowing the
+/// bytes when the string has no escapes.
+pub(super) fn parse_string_ref(bytes: &[u8]) -> Option<Cow<'_, str>> {
     let trimmed = trim_whitespace(bytes);
     if trimmed.len() < 2 || trimmed[0] != b'"' || trimmed[trimmed.len() - 1] != b'"' {
         return None;
@@ -285,54 +386,83 @@ fn parse_string_value(bytes: &[u8]) -> Option<String> {
 
     // Fast path: no escapes
     if !inner.contains(&b'\\') {
-        return std::str::from_utf8(inner).ok().map(String::from);
+        return std::str::from_utf8(inner).ok().map(Cow::Borrowed);
     }
 
     // Slow path: unescape
-    unescape_string(inner)
+    unescape_string(inner).map(Cow::Owned)
 }
 
 /// Unescape a JSON string (without surrounding quotes).
+///
+/// Raw bytes are copied as-is and the result must be valid UTF-8. `\uXXXX`
+/// escapes are decoded as UTF-16, so surrogate pairs combine into one
+/// character. Like the full parser, this rejects unknown escapes and
+/// unpaired surrogates.
 fn unescape_string(bytes: &[u8]) -> Option<String> {
-    let mut result = String::with_capacity(bytes.len());
+    let mut result = Vec::with_capacity(bytes.len());
     let mut i = 0;
 
     while i < bytes.len() {
-        if bytes[i] == b'\\' && i + 1 < bytes.len() {
-            match bytes[i + 1] {
-                b'"' => result.push('"'),
-                b'\\' => result.push('\\'),
-                b'/' => result.push('/'),
-                b'b' => result.push('\u{0008}'),
-                b'f' => result.push('\u{000C}'),
-                b'n' => result.push('\n'),
-                b'r' => result.push('\r'),
-                b't' => result.push('\t'),
-                b'u' => {
-                    // Unicode escape: \uXXXX
-                    if i + 5 < bytes.len() {
-                        let hex = std::str::from_utf8(&bytes[i + 2..i + 6]).ok()?;
-                        let code = u16::from_str_radix(hex, 16).ok()?;
-                        if let Some(c) = char::from_u32(u32::from(code)) {
-                            result.push(c);
-                        }
-                        i += 4; // Extra skip for \uXXXX
-                    }
-                },
-                _ => {
-                    result.push('\\');
-                    result.push(bytes[i + 1] as char);
-                },
-            }
-            i += 2;
-        } else {
-            // Regular UTF-8 byte
-            result.push(bytes[i] as char);
-            i += 1;
+        // Copy the run of bytes up to the next escape
+        let run = bytes[i..]
+            .iter()
+            .position(|&b| b == b'\\')
+            .map_or(bytes.len(), |p| i + p);
+        result.extend_from_slice(&bytes[i..run]);
+        i = run;
+        if i == bytes.len() {
+            break;
         }
+
+        let c = match *bytes.get(i + 1)? {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{0008}',
+            b'f' => '\u{000C}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = parse_hex4(bytes.get(i + 2..i + 6)?)?;
+                i += 4;
+                match high {
+                    0xD800..=0xDBFF => {
+                        // A high surrogate must be followed by `\u` + low surrogate
+                        if bytes.get(i + 2..i + 4)? != b"\\u" {
+                            return None;
+                        }
+                        let low = parse_hex4(bytes.get(i + 4..i + 8)?)?;
+                        if !(0xDC00..=0xDFFF).contains(&low) {
+                            return None;
+                        }
+                        i += 6;
+                        let code = 0x1_0000
+                            + ((u32::from(high) - 0xD800) << 10)
+                            + (u32::from(low) - 0xDC00);
+                        char::from_u32(code)?
+                    },
+                    0xDC00..=0xDFFF => return None,
+                    _ => char::from_u32(u32::from(high))?,
+                }
+            },
+            _ => return None,
+        };
+        let mut buf = [0u8; 4];
+        result.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        i += 2;
     }
 
-    Some(result)
+    String::from_utf8(result).ok()
+}
+
+/// Parse exactly four hex digits.
+fn parse_hex4(hex: &[u8]) -> Option<u16> {
+    if !hex.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    u16::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()
 }
 
 /// Parse a JSON number as i64.
@@ -356,6 +486,36 @@ fn parse_int_value(bytes: &[u8]) -> Option<i64> {
     None
 }
 
+/// Parse a JSON number as u64.
+fn parse_uint_value(bytes: &[u8]) -> Option<u64> {
+    let trimmed = trim_whitespace(bytes);
+    let s = std::str::from_utf8(trimmed).ok()?;
+
+    if let Ok(u) = s.parse::<u64>() {
+        return Some(u);
+    }
+
+    if let Ok(f) = s.parse::<f64>() {
+        const MAX_SAFE_INT: f64 = 9007199254740992.0; // 2^53
+        if f.is_finite() && (0.0..=MAX_SAFE_INT).contains(&f) && f.fract() == 0.0 {
+            return Some(f as u64);
+        }
+    }
+
+    None
+}
+
+/// Parse a JSON number, or a string holding one, keeping its text as-is.
+fn parse_decimal_value(bytes: &[u8]) -> Option<String> {
+    let trimmed = trim_whitespace(bytes);
+    let s = if trimmed.first() == Some(&b'"') {
+        parse_string_value(trimmed)?
+    } else {
+        std::str::from_utf8(trimmed).ok()?.to_string()
+    };
+    super::is_json_number(&s).then_some(s)
+}
+
 /// Parse a JSON number as f64.
 fn parse_float_value(bytes: &[u8]) -> Option<f64> {
     let trimmed = trim_whitespace(bytes);
@@ -446,6 +606,47 @@ mod tests {
         assert_eq!(path_str(json, &["name"]), Some("test".to_string()));
     }
 
+    #[test]
+    fn test_array_index() {
+        let json = br#"{"items": [{"name": "a"}, {"name": "b", "tags": ["x", "y"]}]}"#;
+        assert_eq!(
+            path_str(json, &["items", "0", "name"]),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            path_str(json, &["items", "1", "name"]),
+            Some("b".to_string())
+        );
+        assert_eq!(
+            path_str(json, &["items", "1", "tags", "1"]),
+            Some("y".to_string())
+        );
+        assert_eq!(path_str(json, &["items", "2", "name"]), None);
+        assert!(path_exists(json, &["items", "1"]));
+    }
+
+    #[test]
+    fn test_array_index_root_and_nested_arrays() {
+        let json = b" [[1, 2], [3, [4, 5]]] ";
+        assert_eq!(path_int(json, &["0", "1"]), Some(2));
+        assert_eq!(path_int(json, &["1", "1", "0"]), Some(4));
+        assert_eq!(path_int(json, &["1", "2"]), None);
+    }
+
+    #[test]
+    fn test_array_index_invalid_segments() {
+        let json = br#"{"items": [1, 2], "0": "zero"}"#;
+        // Non-numeric segments never match array elements
+        assert_eq!(path_int(json, &["items", "first"]), None);
+        assert_eq!(path_int(json, &["items", "-1"]), None);
+        assert_eq!(path_int(json, &["items", "+1"]), None);
+        assert_eq!(path_int(json, &["items", ""]), None);
+        // Numeric segments on objects are keys
+        assert_eq!(path_str(json, &["0"]), Some("zero".to_string()));
+        // Empty arrays have no elements
+        assert_eq!(path_int(b"[]", &["0"]), None);
+    }
+
     #[test]
     fn test_skip_nested_objects() {
         let json = br#"{"other": {"x": 1}, "target": "found"}"#;
@@ -50,6 +50,8 @@
 
 mod builder;
 mod lazy;
+pub mod ndjson;
+mod query;
 #[cfg(test)]
 mod tests;
 mod to_json;
@@ -59,13 +61,48 @@ use crate::constants::{MAX_JSON_DEPTH, get_max_json_size};
 use miniserde::json::{Number, Value};
 
 // Re-export public types and functions
-pub use builder::{arr, bool, float, int, null, obj, str};
+pub use builder::{arr, bool, decimal, float, int, null, obj, str, uint};
+pub use lazy::LazyArrayIter;
 pub use to_json::ToJson;
 pub use value::JsonValue;
 
 // Re-export Value for use with map_array/try_map_array
 pub use miniserde::json::Value as RawValue;
 
+/// Check if `s` is a number in JSON syntax, e.g. `-12.5e3`.
+pub(crate) fn is_json_number(s: &str) -> bool {
+    fn digits(bytes: &[u8]) -> usize {
+        bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    let bytes = s.as_bytes();
+    let mut pos = usize::from(bytes.first() == Some(&b'-'));
+
+    // Integer part: 0 or a digit sequence without leading zeros
+    match digits(&bytes[pos..]) {
+        0 => return false,
+        n if n > 1 && bytes[pos] == b'0' => return false,
+        n => pos += n,
+    }
+    if bytes.get(pos) == Some(&b'.') {
+        match digits(&bytes[pos + 1..]) {
+            0 => return false,
+            n => pos += 1 + n,
+        }
+    }
+    if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+        pos += 1;
+        if matches!(bytes.get(pos), Some(b'+' | b'-')) {
+            pos += 1;
+        }
+        match digits(&bytes[pos..]) {
+            0 => return false,
+            n => pos += n,
+        }
+    }
+    pos == bytes.len()
+}
+
 /// Check if JSON nesting depth exceeds limit.
 pub(crate) fn json_depth_exceeds_limit(data: &[u8]) -> bool {
     let mut depth: usize = 0;
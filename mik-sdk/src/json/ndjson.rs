@@ -0,0 +1,181 @@
+//! Newline-delimited JSON (NDJSON / JSON Lines).
+//!
+//! One JSON value per line, as used by bulk import and export endpoints.
+//! [`parse`] reads a request body line by line, and [`stream`] writes values
+//! as a streamed `application/x-ndjson` response.
+//!
+//! # Examples
+//!
+//! ```
+//! use mik_sdk::json::{self, ndjson};
+//!
+//! let body = b"{\"id\":1}\n{\"id\":2}\n";
+//! let ids: Vec<i64> = ndjson::parse(body)
+//!     .filter_map(Result::ok)
+//!     .filter_map(|row| row.path_int(&["id"]))
+//!     .collect();
+//! assert_eq!(ids, [1, 2]);
+//!
+//! let out = ndjson::to_bytes(&[json::obj().set("id", json::int(1)), json::int(2)]);
+//! assert_eq!(out, b"{\"id\":1}\n2\n");
+//! ```
+//!
+//! Streaming an export:
+//!
+//! ```ignore
+//! use mik_sdk::constants::MIME_NDJSON;
+//! use mik_sdk::json::ndjson;
+//!
+//! fn export(_req: &Request) -> Response {
+//!     let rows = load_rows().into_iter().map(|row| row.to_json());
+//!     stream!(ndjson::stream(rows), content_type: MIME_NDJSON)
+//! }
+//! ```
+
+use super::{JsonValue, try_parse};
+use crate::stream::ChunkedBody;
+
+/// A line of NDJSON input that is not a valid JSON value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LineError {
+    /// 1-based line number
+    pub line: usize,
+}
+
+impl std::fmt::Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid JSON on line {}", self.line)
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// Parse NDJSON input, one value per line.
+///
+/// Lines may end with `\n` or `\r\n`, and blank lines are skipped. Each line
+/// is parsed lazily with the same limits as [`try_parse`], so a bad line
+/// yields a [`LineError`] without stopping the iteration.
+#[must_use]
+pub const fn parse(bytes: &[u8]) -> Lines<'_> {
+    Lines {
+        rest: Some(bytes),
+        line: 0,
+    }
+}
+
+/// Iterator over the values of NDJSON input, created by [`parse`].
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    rest: Option<&'a [u8]>,
+    line: usize,
+}
+
+impl Iterator for Lines<'_> {
+    type Item = Result<JsonValue, LineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.rest?;
+            let (line, rest) = rest
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or((rest, None), |end| (&rest[..end], Some(&rest[end + 1..])));
+            self.rest = rest;
+            self.line += 1;
+
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            return Some(try_parse(line).ok_or(LineError { line: self.line }));
+        }
+    }
+}
+
+impl std::iter::FusedIterator for Lines<'_> {}
+
+/// Serialize values as NDJSON, each followed by `\n`.
+#[must_use]
+pub fn to_bytes<'a>(values: impl IntoIterator<Item = &'a JsonValue>) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in values {
+        out.extend_from_slice(&line(value));
+    }
+    out
+}
+
+/// Stream values as an NDJSON response body, one line per chunk.
+///
+/// Return it with `stream!(body, content_type: MIME_NDJSON)` so each line
+/// reaches the client as soon as it is produced.
+#[must_use]
+pub fn stream<I>(values: I) -> ChunkedBody
+where
+    I: IntoIterator<Item = JsonValue>,
+    I::IntoIter: 'static,
+{
+    let mut iter = values.into_iter();
+    ChunkedBody::new(move || iter.next().map(|value| line(&value)))
+}
+
+/// Serialize one value followed by a newline.
+fn line(value: &JsonValue) -> Vec<u8> {
+    let mut bytes = value.to_bytes();
+    bytes.push(b'\n');
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+
+    #[test]
+    fn test_parse_lines() {
+        let rows: Vec<_> = parse(b"{\"a\":1}\r\n\n  \n[2]\n\"three\"").collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].as_ref().unwrap().path_int(&["a"]), Some(1));
+        assert_eq!(rows[1].as_ref().unwrap().path_int(&["0"]), Some(2));
+        assert_eq!(rows[2].as_ref().unwrap().str(), Some("three".to_string()));
+    }
+
+    #[test]
+    fn test_parse_reports_bad_line_and_continues() {
+        let rows: Vec<_> = parse(b"1\n{\"a\":1} x\n\n3\n").collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].as_ref().unwrap_err(), &LineError { line: 2 });
+        assert_eq!(rows[2].as_ref().unwrap().int(), Some(3));
+        assert_eq!(LineError { line: 2 }.to_string(), "invalid JSON on line 2");
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(parse(b"").count(), 0);
+        assert_eq!(parse(b"\n\n").count(), 0);
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let values = [json::obj().set("a", json::str("x\ny")), json::null()];
+        assert_eq!(to_bytes(&values), b"{\"a\":\"x\\ny\"}\nnull\n");
+        assert_eq!(to_bytes(&[]), b"");
+    }
+
+    #[test]
+    fn test_stream_one_line_per_chunk() {
+        let mut body = stream(vec![json::int(1), json::obj()]);
+        assert_eq!(body.next_chunk(), Some(b"1\n".to_vec()));
+        assert_eq!(body.next_chunk(), Some(b"{}\n".to_vec()));
+        assert_eq!(body.next_chunk(), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = [json::int(1), json::arr().push(json::bool(true))];
+        let parsed: Vec<String> = parse(&to_bytes(&values))
+            .map(|row| row.unwrap().to_string())
+            .collect();
+        assert_eq!(parsed, ["1", "[true]"]);
+    }
+}
@@ -0,0 +1,454 @@
+//! JSON Pointer (RFC 6901) and a JSONPath subset for `JsonValue`.
+//!
+//! Supported JSONPath syntax:
+//! - `$` - the root value
+//! - `.name`, `['name']` - object member
+//! - `[0]`, `[-1]` - array element (negative counts from the end)
+//! - `.*`, `[*]` - every array element or object member
+//! - `[?(@.field)]` - elements having `field`
+//! - `[?(@.field op literal)]` - elements where `field` compares to a string,
+//!   number, `true`, `false` or `null` literal, with `==`, `!=`, `<`, `<=`,
+//!   `>` or `>=`
+
+use miniserde::json::{Number, Value};
+use std::cmp::Ordering;
+
+// =============================================================================
+// JSON POINTER
+// =============================================================================
+
+/// Split a JSON Pointer into its unescaped reference tokens.
+///
+/// Returns `None` if the pointer is neither empty nor starts with `/`.
+pub(super) fn parse_pointer(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    let rest = pointer.strip_prefix('/')?;
+    rest.split('/')
+        .map(|token| {
+            // Decoded in one pass, so `~01` becomes `~1` rather than `/`
+            if !token.contains('~') {
+                return Some(token.to_string());
+            }
+            let mut out = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    out.push(c);
+                    continue;
+                }
+                match chars.next()? {
+                    '0' => out.push('~'),
+                    '1' => out.push('/'),
+                    _ => return None,
+                }
+            }
+            Some(out)
+        })
+        .collect()
+}
+
+// =============================================================================
+// JSONPATH
+// =============================================================================
+
+/// One step of a JSONPath expression.
+#[derive(Debug, PartialEq)]
+enum Selector {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    Filter(Filter),
+}
+
+/// A `[?(@...)]` predicate.
+#[derive(Debug, PartialEq)]
+struct Filter {
+    /// Member path below the element (`@.a.b` is `["a", "b"]`)
+    path: Vec<String>,
+    /// Comparison, or `None` to test that the member exists
+    comparison: Option<(CmpOp, Literal)>,
+}
+
+/// The right-hand side of a filter comparison.
+#[derive(Debug, PartialEq)]
+enum Literal {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Evaluate a JSONPath expression against `root`.
+///
+/// Returns `None` if the expression is invalid or unsupported.
+pub(super) fn query<'a>(root: &'a Value, path: &str) -> Option<Vec<&'a Value>> {
+    let selectors = parse_path(path)?;
+    let mut current = vec![root];
+    for selector in &selectors {
+        let mut next = Vec::new();
+        for value in current {
+            select(value, selector, &mut next);
+        }
+        current = next;
+    }
+    Some(current)
+}
+
+/// Apply one selector to `value`, pushing the matches to `out`.
+fn select<'a>(value: &'a Value, selector: &Selector, out: &mut Vec<&'a Value>) {
+    match (selector, value) {
+        (Selector::Key(key), Value::Object(obj)) => out.extend(obj.get(key)),
+        (Selector::Index(index), Value::Array(arr)) => {
+            let index = if *index < 0 {
+                usize::try_from(index.unsigned_abs())
+                    .ok()
+                    .and_then(|back| arr.len().checked_sub(back))
+            } else {
+                usize::try_from(*index).ok()
+            };
+            out.extend(index.and_then(|i| arr.get(i)));
+        },
+        (Selector::Wildcard, Value::Array(arr)) => out.extend(arr.iter()),
+        (Selector::Wildcard, Value::Object(obj)) => out.extend(obj.values()),
+        (Selector::Filter(filter), Value::Array(arr)) => {
+            out.extend(arr.iter().filter(|item| filter.matches(item)));
+        },
+        (Selector::Filter(filter), Value::Object(obj)) => {
+            out.extend(obj.values().filter(|item| filter.matches(item)));
+        },
+        _ => {},
+    }
+}
+
+impl Filter {
+    fn matches(&self, item: &Value) -> bool {
+        let mut current = item;
+        for key in &self.path {
+            match current {
+                Value::Object(obj) => match obj.get(key) {
+                    Some(value) => current = value,
+                    None => return false,
+                },
+                _ => return false,
+            }
+        }
+        self.comparison
+            .as_ref()
+            .is_none_or(|(op, literal)| compare(current, *op, literal))
+    }
+}
+
+/// Compare a member value with a literal. Values of different types are
+/// only ever unequal.
+fn compare(value: &Value, op: CmpOp, literal: &Literal) -> bool {
+    let ordering = match (value, literal) {
+        (Value::Number(a), Literal::Number(b)) => number_as_f64(a).partial_cmp(b),
+        (Value::String(a), Literal::String(b)) => Some(a.as_str().cmp(b)),
+        (Value::Bool(a), Literal::Bool(b)) if a == b => Some(Ordering::Equal),
+        (Value::Null, Literal::Null) => Some(Ordering::Equal),
+        _ => None,
+    };
+    match op {
+        CmpOp::Eq => ordering == Some(Ordering::Equal),
+        CmpOp::Ne => ordering != Some(Ordering::Equal),
+        CmpOp::Lt => ordering == Some(Ordering::Less),
+        CmpOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        CmpOp::Gt => ordering == Some(Ordering::Greater),
+        CmpOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)] // Comparisons are approximate for huge integers
+const fn number_as_f64(n: &Number) -> f64 {
+    match *n {
+        Number::U64(u) => u as f64,
+        Number::I64(i) => i as f64,
+        Number::F64(f) => f,
+    }
+}
+
+// =============================================================================
+// PARSING
+// =============================================================================
+
+/// Cursor over a JSONPath expression.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, expected: &str) -> bool {
+        if self.input[self.pos..].starts_with(expected) {
+            self.pos += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Take characters while `f` holds.
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.input[start..self.pos]
+    }
+
+    /// A member name after `.`: letters, digits, `_` and `-`.
+    fn name(&mut self) -> Option<String> {
+        let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// A `'...'` or `"..."` string, with `\` escaping the next character.
+    fn quoted(&mut self) -> Option<String> {
+        let quote = self.bump().filter(|c| *c == '\'' || *c == '"')?;
+        let mut out = String::new();
+        loop {
+            match self.bump()? {
+                '\\' => out.push(self.bump()?),
+                c if c == quote => return Some(out),
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// A member step: `.name` or `['name']`.
+    fn member(&mut self) -> Option<String> {
+        if self.eat('.') {
+            self.name()
+        } else if self.eat('[') {
+            let key = self.quoted()?;
+            self.eat(']').then_some(key)
+        } else {
+            None
+        }
+    }
+
+    fn selector(&mut self) -> Option<Selector> {
+        if self.eat('.') {
+            if self.eat('*') {
+                return Some(Selector::Wildcard);
+            }
+            // Recursive descent (`..`) is not supported
+            return self.name().map(Selector::Key);
+        }
+        if !self.eat('[') {
+            return None;
+        }
+        self.skip_whitespace();
+        let selector = match self.peek()? {
+            '*' => {
+                self.bump();
+                Selector::Wildcard
+            },
+            '\'' | '"' => Selector::Key(self.quoted()?),
+            '?' => {
+                self.bump();
+                Selector::Filter(self.filter()?)
+            },
+            _ => {
+                let digits = self.take_while(|c| c == '-' || c.is_ascii_digit());
+                Selector::Index(digits.parse().ok()?)
+            },
+        };
+        self.skip_whitespace();
+        self.eat(']').then_some(selector)
+    }
+
+    /// `(@.path)` or `(@.path op literal)`, after the `?`.
+    fn filter(&mut self) -> Option<Filter> {
+        if !self.eat('(') {
+            return None;
+        }
+        self.skip_whitespace();
+        if !self.eat('@') {
+            return None;
+        }
+        let mut path = Vec::new();
+        while matches!(self.peek(), Some('.' | '[')) {
+            path.push(self.member()?);
+        }
+        self.skip_whitespace();
+
+        let comparison = if self.eat(')') {
+            return Some(Filter {
+                path,
+                comparison: None,
+            });
+        } else {
+            let op = self.operator()?;
+            self.skip_whitespace();
+            let literal = self.literal()?;
+            self.skip_whitespace();
+            (op, literal)
+        };
+        self.eat(')').then_some(Filter {
+            path,
+            comparison: Some(comparison),
+        })
+    }
+
+    fn operator(&mut self) -> Option<CmpOp> {
+        // Two-character operators first, so `<=` is not read as `<`
+        [
+            ("==", CmpOp::Eq),
+            ("!=", CmpOp::Ne),
+            ("<=", CmpOp::Le),
+            (">=", CmpOp::Ge),
+            ("<", CmpOp::Lt),
+            (">", CmpOp::Gt),
+        ]
+        .into_iter()
+        .find_map(|(token, op)| self.eat_str(token).then_some(op))
+    }
+
+    fn literal(&mut self) -> Option<Literal> {
+        match self.peek()? {
+            '\'' | '"' => self.quoted().map(Literal::String),
+            _ if self.eat_str("true") => Some(Literal::Bool(true)),
+            _ if self.eat_str("false") => Some(Literal::Bool(false)),
+            _ if self.eat_str("null") => Some(Literal::Null),
+            _ => {
+                let text = self.take_while(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+                if !super::is_json_number(text) {
+                    return None;
+                }
+                text.parse().ok().map(Literal::Number)
+            },
+        }
+    }
+}
+
+/// Parse a JSONPath expression into selectors.
+fn parse_path(path: &str) -> Option<Vec<Selector>> {
+    let mut parser = Parser {
+        input: path.trim(),
+        pos: 0,
+    };
+    if !parser.eat('$') {
+        return None;
+    }
+    let mut selectors = Vec::new();
+    while parser.peek().is_some() {
+        selectors.push(parser.selector()?);
+    }
+    Some(selectors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pointer() {
+        assert_eq!(parse_pointer(""), Some(vec![]));
+        assert_eq!(parse_pointer("/"), Some(vec![String::new()]));
+        assert_eq!(
+            parse_pointer("/a~1b/m~0n/~01"),
+            Some(vec!["a/b".to_string(), "m~n".to_string(), "~1".to_string()])
+        );
+        assert_eq!(parse_pointer("a/b"), None);
+        assert_eq!(parse_pointer("/a~2"), None);
+        assert_eq!(parse_pointer("/a~"), None);
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("$.items[0]['na.me'][*].*[-1]"),
+            Some(vec![
+                Selector::Key("items".to_string()),
+                Selector::Index(0),
+                Selector::Key("na.me".to_string()),
+                Selector::Wildcard,
+                Selector::Wildcard,
+                Selector::Index(-1),
+            ])
+        );
+        assert_eq!(
+            parse_path("$[?(@.a.b >= 2.5)]"),
+            Some(vec![Selector::Filter(Filter {
+                path: vec!["a".to_string(), "b".to_string()],
+                comparison: Some((CmpOp::Ge, Literal::Number(2.5))),
+            })])
+        );
+        assert_eq!(
+            parse_path("$[?(@['x'])]"),
+            Some(vec![Selector::Filter(Filter {
+                path: vec!["x".to_string()],
+                comparison: None,
+            })])
+        );
+    }
+
+    #[test]
+    fn test_parse_path_invalid() {
+        for invalid in [
+            "",
+            "items",
+            "$.",
+            "$..a",
+            "$[",
+            "$[0",
+            "$[x]",
+            "$['a]",
+            "$[?(@.a ~ 1)]",
+            "$[?(@.a == )]",
+            "$[?(a == 1)]",
+            "$[?(@.a == 01)]",
+        ] {
+            assert_eq!(parse_path(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_compare_mixed_types() {
+        let one = Value::Number(Number::I64(1));
+        let text = Literal::String("1".to_string());
+        assert!(!compare(&one, CmpOp::Eq, &text));
+        assert!(compare(&one, CmpOp::Ne, &text));
+        assert!(!compare(&one, CmpOp::Lt, &text));
+        assert!(compare(&one, CmpOp::Eq, &Literal::Number(1.0)));
+        assert!(!compare(&Value::Null, CmpOp::Eq, &Literal::Bool(false)));
+    }
+}
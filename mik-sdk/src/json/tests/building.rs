@@ -200,6 +200,35 @@ fn test_try_map_array_empty() {
     assert_eq!(result, Some(Ok(vec![])));
 }
 
+// === try_map_object tests ===
+
+#[test]
+fn test_try_map_object_success() {
+    let v = obj().set("a", int(1)).set("b", int(2));
+    let result: Option<Result<Vec<(String, i64)>, &str>> =
+        v.try_map_object(|_, v| raw_int(v).ok_or("not an int"));
+    assert_eq!(
+        result,
+        Some(Ok(vec![("a".to_string(), 1), ("b".to_string(), 2)]))
+    );
+}
+
+#[test]
+fn test_try_map_object_error_has_key() {
+    let v = obj().set("a", int(1)).set("b", str("oops"));
+    let result: Option<Result<Vec<(String, i64)>, String>> =
+        v.try_map_object(|key, v| raw_int(v).ok_or_else(|| key.to_string()));
+    assert_eq!(result, Some(Err("b".to_string())));
+}
+
+#[test]
+fn test_try_map_object_not_object() {
+    let v = arr().push(int(1));
+    let result: Option<Result<Vec<(String, i64)>, &str>> =
+        v.try_map_object(|_, v| raw_int(v).ok_or("not an int"));
+    assert!(result.is_none());
+}
+
 // === keys() tests ===
 
 #[test]
@@ -526,9 +555,9 @@ fn test_u32_to_json() {
 
 #[test]
 fn test_u64_to_json() {
-    // Note: u64::MAX > i64::MAX, so it will be truncated
-    // But values within i64 range work fine
     assert_eq!(1000000u64.to_json().to_string(), "1000000");
+    // Values above i64::MAX are kept exact
+    assert_eq!(u64::MAX.to_json().to_string(), "18446744073709551615");
 }
 
 #[test]
@@ -743,6 +772,24 @@ fn test_vec_of_options_to_json() {
     assert_eq!(v.to_json().to_string(), r#"["a",null,"c"]"#);
 }
 
+#[test]
+fn test_hashmap_to_json() {
+    let mut map = std::collections::HashMap::new();
+    map.insert("b".to_string(), 2);
+    map.insert("a".to_string(), 1);
+    assert_eq!(map.to_json().to_string(), r#"{"a":1,"b":2}"#);
+}
+
+#[test]
+fn test_btreemap_to_json() {
+    let mut map = std::collections::BTreeMap::new();
+    map.insert("tags", vec!["x", "y"]);
+    assert_eq!(map.to_json().to_string(), r#"{"tags":["x","y"]}"#);
+
+    let empty: std::collections::BTreeMap<String, i32> = std::collections::BTreeMap::new();
+    assert_eq!(empty.to_json().to_string(), "{}");
+}
+
 // === Building objects with ToJson ===
 
 #[test]
@@ -769,3 +816,62 @@ fn test_build_object_with_to_json() {
     assert!(output.contains(r#""tags":["admin","user"]"#));
     assert!(output.contains(r#""nickname":null"#));
 }
+
+// === Merge tests ===
+
+#[test]
+fn test_merge_rfc7396_examples() {
+    // Test cases from RFC 7396, Appendix A
+    let cases: &[(&str, &str, &str)] = &[
+        (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"a":null}"#, "{}"),
+        (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+        (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+        (
+            r#"{"a":{"b":"c"}}"#,
+            r#"{"a":{"b":"d","c":null}}"#,
+            r#"{"a":{"b":"d"}}"#,
+        ),
+        (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+        (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+        (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+        (r#"{"a":"foo"}"#, "null", "null"),
+        (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+        (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"a":1,"e":null}"#),
+        ("[1,2]", r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+        ("{}", r#"{"a":{"bb":{"ccc":null}}}"#, r#"{"a":{"bb":{}}}"#),
+    ];
+    for (target, patch, expected) in cases {
+        let target = try_parse(target.as_bytes()).unwrap();
+        let patch = try_parse(patch.as_bytes()).unwrap();
+        assert_eq!(target.merge(&patch).to_string(), *expected, "{patch}");
+    }
+}
+
+#[test]
+fn test_merge_does_not_affect_shared_value() {
+    let original = obj().set("a", int(1));
+    let patched = original.clone().merge(&obj().set("a", int(2)));
+    assert_eq!(original.to_string(), r#"{"a":1}"#);
+    assert_eq!(patched.to_string(), r#"{"a":2}"#);
+}
+
+#[test]
+fn test_deep_merge_keeps_nulls() {
+    let base = try_parse(br#"{"a":{"b":1,"c":2},"d":[1,2]}"#).unwrap();
+    let other = try_parse(br#"{"a":{"c":null,"e":3},"d":[3]}"#).unwrap();
+    assert_eq!(
+        base.deep_merge(&other).to_string(),
+        r#"{"a":{"b":1,"c":null,"e":3},"d":[3]}"#
+    );
+}
+
+#[test]
+fn test_deep_merge_replaces_non_objects() {
+    let merged = obj().set("a", int(1)).deep_merge(&str("x"));
+    assert_eq!(merged.to_string(), r#""x""#);
+    let merged = int(1).deep_merge(&obj().set("a", int(1)));
+    assert_eq!(merged.to_string(), r#"{"a":1}"#);
+}
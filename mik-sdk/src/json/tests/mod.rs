@@ -1,7 +1,9 @@
 //! All tests for the json module, organized into submodules.
 
 mod building;
+mod numbers;
 mod parsing;
 mod path;
 mod proptest;
+mod query;
 mod security;
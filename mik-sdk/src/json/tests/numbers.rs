@@ -0,0 +1,99 @@
+//! Tests for exact large-integer and decimal support.
+//!
+//! Tests uint(), decimal() builders, the uint()/decimal() readers and
+//! their path_* counterparts in lazy and parsed mode.
+
+use super::super::*;
+
+// =========================================================================
+// UNSIGNED INTEGERS
+// =========================================================================
+
+#[test]
+fn test_uint_round_trip() {
+    let v = obj().set("id", uint(u64::MAX));
+    let s = v.to_string();
+    assert_eq!(s, r#"{"id":18446744073709551615}"#);
+
+    let parsed = try_parse(s.as_bytes()).unwrap();
+    assert_eq!(parsed.path_uint(&["id"]), Some(u64::MAX));
+    assert_eq!(parsed.get("id").uint(), Some(u64::MAX));
+    assert_eq!(parsed.path_uint(&["id"]), Some(u64::MAX)); // parsed mode
+}
+
+#[test]
+fn test_uint_rejects_negative_and_fractions() {
+    assert_eq!(int(-1).uint(), None);
+    assert_eq!(float(1.5).uint(), None);
+    assert_eq!(float(2.0).uint(), Some(2));
+    assert_eq!(str("1").uint(), None);
+
+    let parsed = try_parse(br#"{"a": -1, "b": 1.5, "c": 7}"#).unwrap();
+    assert_eq!(parsed.path_uint(&["a"]), None);
+    assert_eq!(parsed.path_uint(&["b"]), None);
+    assert_eq!(parsed.path_uint(&["c"]), Some(7));
+}
+
+// =========================================================================
+// DECIMALS
+// =========================================================================
+
+#[test]
+fn test_decimal_builder_writes_string() {
+    let v = obj().set("amount", decimal("12345678901234567890.12"));
+    assert_eq!(v.to_string(), r#"{"amount":"12345678901234567890.12"}"#);
+    assert_eq!(
+        v.get("amount").decimal(),
+        Some("12345678901234567890.12".to_string())
+    );
+}
+
+#[test]
+fn test_decimal_builder_rejects_invalid() {
+    assert!(decimal("12.").is_null());
+    assert!(decimal("abc").is_null());
+    assert!(decimal("01").is_null());
+    assert!(decimal("").is_null());
+    assert!(!decimal("-0.5e-3").is_null());
+}
+
+#[test]
+fn test_path_decimal_keeps_raw_digits() {
+    let json = br#"{"price": 0.1000000000000000055511151231257827, "big": 98765432109876543210, "s": "19.99", "bad": "n/a"}"#;
+    let parsed = try_parse(json).unwrap();
+    assert_eq!(
+        parsed.path_decimal(&["price"]),
+        Some("0.1000000000000000055511151231257827".to_string())
+    );
+    assert_eq!(
+        parsed.path_decimal(&["big"]),
+        Some("98765432109876543210".to_string())
+    );
+    assert_eq!(parsed.path_decimal(&["s"]), Some("19.99".to_string()));
+    assert_eq!(parsed.path_decimal(&["bad"]), None);
+    assert_eq!(parsed.path_decimal(&["missing"]), None);
+}
+
+#[test]
+fn test_decimal_from_tree_numbers() {
+    assert_eq!(int(-42).decimal(), Some("-42".to_string()));
+    assert_eq!(uint(u64::MAX).decimal(), Some(u64::MAX.to_string()));
+    assert_eq!(float(0.25).decimal(), Some("0.25".to_string()));
+    assert_eq!(bool(true).decimal(), None);
+
+    let v = obj().set("n", float(1.5)).set("s", str("2.50"));
+    assert_eq!(v.path_decimal(&["n"]), Some("1.5".to_string()));
+    assert_eq!(v.path_decimal(&["s"]), Some("2.50".to_string()));
+}
+
+#[test]
+fn test_is_json_number() {
+    for valid in ["0", "-0", "10", "1.25", "1e10", "1E+2", "-3.5e-7"] {
+        assert!(is_json_number(valid), "{valid}");
+    }
+    for invalid in [
+        "", "-", "+1", "00", ".5", "1.", "1e", "1e+", "0x10", "1 ", "NaN",
+    ] {
+        assert!(!is_json_number(invalid), "{invalid}");
+    }
+}
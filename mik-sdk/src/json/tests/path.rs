@@ -1,9 +1,10 @@
 //! Tests for JSON path accessor methods.
 //!
-//! Tests path_str, path_int, path_float, path_bool, path_exists, path_is_null
+//! Tests path_str, path_str_ref, path_int, path_float, path_bool, path_exists, path_is_null
 //! and the lazy scanner functionality.
 
 use super::super::*;
+use std::borrow::Cow;
 
 // =========================================================================
 // BASIC PATH ACCESSOR TESTS
@@ -239,6 +240,48 @@ fn test_path_through_array_intermediate() {
     assert_eq!(v.path_str(&["user", "name"]), None);
 }
 
+// =========================================================================
+// BORROWED STRING ACCESS
+// =========================================================================
+
+#[test]
+fn test_path_str_ref_borrows_lazy_bytes() {
+    let v = try_parse(br#"{"user":{"name":"Alice","age":30}}"#).unwrap();
+    assert!(matches!(
+        v.path_str_ref(&["user", "name"]),
+        Some(Cow::Borrowed("Alice"))
+    ));
+    assert_eq!(v.path_str_ref(&["user", "age"]), None);
+    assert_eq!(v.path_str_ref(&["user", "missing"]), None);
+}
+
+#[test]
+fn test_path_str_ref_unescapes_into_owned() {
+    let v = try_parse(br#"{"quote":"say \"hi\"\u0021"}"#).unwrap();
+    let quote = v.path_str_ref(&["quote"]);
+    assert!(matches!(quote, Some(Cow::Owned(_))));
+    assert_eq!(quote.as_deref(), Some("say \"hi\"!"));
+    assert_eq!(quote.map(Cow::into_owned), v.path_str(&["quote"]));
+}
+
+#[test]
+fn test_path_str_ref_on_parsed_mode() {
+    let v = obj().set("user", obj().set("name", str("Alice")));
+    assert!(matches!(
+        v.path_str_ref(&["user", "name"]),
+        Some(Cow::Borrowed("Alice"))
+    ));
+}
+
+#[test]
+fn test_as_str() {
+    let lazy = try_parse(br#" "Alice" "#).unwrap();
+    assert!(matches!(lazy.as_str(), Some(Cow::Borrowed("Alice"))));
+    assert!(matches!(str("Bob").as_str(), Some(Cow::Borrowed("Bob"))));
+    assert_eq!(int(1).as_str(), None);
+    assert_eq!(try_parse(b"[1]").unwrap().as_str(), None);
+}
+
 // =========================================================================
 // LAZY SCANNER EDGE CASES
 // =========================================================================
@@ -264,10 +307,11 @@ fn test_lazy_empty_path_on_number() {
 
 #[test]
 fn test_lazy_path_on_array_root() {
-    // Root is array, not object - path lookup should return None
+    // Root is array - only index segments match
     let json = br"[1, 2, 3]";
     assert_eq!(lazy::path_str(json, &["key"]), None);
-    assert_eq!(lazy::path_int(json, &["0"]), None);
+    assert_eq!(lazy::path_int(json, &["0"]), Some(1));
+    assert_eq!(lazy::path_int(json, &["3"]), None);
 }
 
 #[test]
@@ -387,12 +431,41 @@ fn test_unescape_unicode_invalid_hex() {
 
 #[test]
 fn test_unescape_unicode_incomplete() {
-    // Incomplete unicode at end of string - parser behavior varies
     let json = br#"{"msg": "\u00"}"#;
-    let result = lazy::path_str(json, &["msg"]);
-    // Parser handles this gracefully (may succeed with partial)
-    // Just verify it doesn't panic
-    let _ = result;
+    assert_eq!(lazy::path_str(json, &["msg"]), None);
+}
+
+#[test]
+fn test_unescape_surrogate_pair() {
+    let json = br#"{"msg": "smile \ud83d\ude00!"}"#;
+    assert_eq!(
+        lazy::path_str(json, &["msg"]),
+        Some("smile \u{1F600}!".to_string())
+    );
+}
+
+#[test]
+fn test_unescape_keeps_raw_utf8() {
+    let json = "{\"msg\": \"caf\\u00e9 \\\"caf\u{e9}\\\" \u{65e5}\u{672c}\"}";
+    assert_eq!(
+        lazy::path_str(json.as_bytes(), &["msg"]),
+        Some("caf\u{e9} \"caf\u{e9}\" \u{65e5}\u{672c}".to_string())
+    );
+}
+
+#[test]
+fn test_unescape_rejects_what_the_parser_rejects() {
+    for json in [
+        br#"{"msg": "\ud83d"}"#.as_slice(),
+        br#"{"msg": "\ud83dx"}"#,
+        br#"{"msg": "\ud83d\u0041"}"#,
+        br#"{"msg": "\ude00"}"#,
+        br#"{"msg": "\q"}"#,
+        br#"{"msg": "\u+041"}"#,
+    ] {
+        assert_eq!(lazy::path_str(json, &["msg"]), None);
+        assert!(try_parse(json).unwrap().get("msg").str().is_none());
+    }
 }
 
 // =========================================================================
@@ -417,6 +490,28 @@ fn test_push_on_lazy_array_triggers_parse() {
     assert_eq!(v2.len(), Some(4));
 }
 
+// =========================================================================
+// ARRAY INDEX SEGMENTS
+// =========================================================================
+
+#[test]
+fn test_path_array_index_lazy_and_parsed_agree() {
+    let json = br#"{"items": [{"id": 7, "name": "first"}, {"id": 8}]}"#;
+    let lazy = try_parse(json).unwrap();
+    let parsed = try_parse_full(json).unwrap();
+
+    for v in [&lazy, &parsed] {
+        assert_eq!(
+            v.path_str(&["items", "0", "name"]),
+            Some("first".to_string())
+        );
+        assert_eq!(v.path_int(&["items", "1", "id"]), Some(8));
+        assert!(v.path_exists(&["items", "1"]));
+        assert!(!v.path_exists(&["items", "2"]));
+        assert_eq!(v.path_int(&["items", "x"]), None);
+    }
+}
+
 // =========================================================================
 // DISPLAY IMPLEMENTATION EDGE CASES
 // =========================================================================
@@ -437,3 +532,59 @@ fn test_display_parsed_mode_coverage() {
     assert!(display.contains("key"));
     assert!(display.contains("value"));
 }
+
+// =========================================================================
+// LAZY ARRAY ITERATION
+// =========================================================================
+
+#[test]
+fn test_iter_array_lazy_yields_raw_elements() {
+    let v = try_parse(br#" [ 1, "two,]" , {"a":[3]}, [4, 5], null ] "#).unwrap();
+    let elements: Vec<&[u8]> = v.iter_array_lazy().unwrap().collect();
+    assert_eq!(
+        elements,
+        [
+            &b"1"[..],
+            br#""two,]""#,
+            br#"{"a":[3]}"#,
+            b"[4, 5]",
+            b"null"
+        ]
+    );
+}
+
+#[test]
+fn test_iter_array_lazy_elements_parse() {
+    let v = try_parse(br#"[{"name":"a"},{"name":"b"}]"#).unwrap();
+    let names: Vec<String> = v
+        .iter_array_lazy()
+        .unwrap()
+        .filter_map(|item| try_parse(item)?.path_str(&["name"]))
+        .collect();
+    assert_eq!(names, ["a", "b"]);
+}
+
+#[test]
+fn test_iter_array_lazy_empty() {
+    let v = try_parse(b"[ ]").unwrap();
+    assert_eq!(v.iter_array_lazy().unwrap().count(), 0);
+}
+
+#[test]
+fn test_iter_array_lazy_requires_lazy_array() {
+    assert!(
+        try_parse(br#"{"a":1}"#)
+            .unwrap()
+            .iter_array_lazy()
+            .is_none()
+    );
+    assert!(arr().push(int(1)).iter_array_lazy().is_none());
+}
+
+#[test]
+fn test_iter_array_lazy_stops_on_malformed_bytes() {
+    let mut iter = lazy::LazyArrayIter::new(b"[1, tru, 3]").unwrap();
+    assert_eq!(iter.next(), Some(&b"1"[..]));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+}
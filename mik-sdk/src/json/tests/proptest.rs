@@ -195,6 +195,26 @@ proptest! {
         }
     }
 
+    /// The lazy scanner decodes escaped strings like the full parser.
+    #[test]
+    fn lazy_unescape_matches_parser(s in "\\PC*") {
+        let json = format!(r#"{{"s": {}}}"#, s.to_json());
+        let v = try_parse(json.as_bytes()).unwrap();
+        prop_assert_eq!(v.path_str(&["s"]), v.get("s").str());
+        prop_assert_eq!(v.path_str(&["s"]), Some(s));
+    }
+
+    /// Characters written as `\u` escapes (UTF-16, so surrogate pairs
+    /// outside the BMP) decode to themselves.
+    #[test]
+    fn lazy_unescape_utf16_escapes(s in "\\PC{0,16}") {
+        let escaped: String = s.encode_utf16().map(|unit| format!("\\u{unit:04x}")).collect();
+        let json = format!(r#"{{"s": "{escaped}"}}"#);
+        let v = try_parse(json.as_bytes()).unwrap();
+        prop_assert_eq!(v.path_str(&["s"]), v.get("s").str());
+        prop_assert_eq!(v.path_str(&["s"]), Some(s));
+    }
+
     /// Test mixed nested structures.
     #[test]
     fn parse_handles_mixed_nesting(depth in 1usize..20) {
@@ -0,0 +1,158 @@
+//! Tests for JSON Pointer and JSONPath queries.
+
+use super::super::*;
+
+const STORE: &[u8] = br#"{
+    "store": {
+        "name": "Corner",
+        "items": [
+            {"name": "pen", "price": 2, "tags": ["office"]},
+            {"name": "desk", "price": 120.5},
+            {"name": "lamp", "price": 35, "discontinued": true},
+            {"name": "mug", "price": null}
+        ],
+        "a/b": 1,
+        "m~n": 2
+    }
+}"#;
+
+fn names(matches: &[JsonValue]) -> Vec<String> {
+    matches
+        .iter()
+        .filter_map(|v| v.path_str(&["name"]))
+        .collect()
+}
+
+// =========================================================================
+// JSON POINTER
+// =========================================================================
+
+#[test]
+fn test_pointer_lazy_and_parsed_agree() {
+    let lazy = try_parse(STORE).unwrap();
+    let parsed = JsonValue::new(JsonValue::parse_bytes(STORE).unwrap());
+    for v in [&lazy, &parsed] {
+        assert_eq!(
+            v.pointer("/store/name").and_then(|v| v.str()),
+            Some("Corner".to_string())
+        );
+        assert_eq!(
+            v.pointer("/store/items/1/price").and_then(|v| v.float()),
+            Some(120.5)
+        );
+        assert_eq!(
+            v.pointer("/store/items/0/tags/0").and_then(|v| v.str()),
+            Some("office".to_string())
+        );
+        assert_eq!(v.pointer("/store/a~1b").and_then(|v| v.int()), Some(1));
+        assert_eq!(v.pointer("/store/m~0n").and_then(|v| v.int()), Some(2));
+        assert!(v.pointer("/store/items/3/price").unwrap().is_null());
+        assert_eq!(v.pointer("/store/items").and_then(|v| v.len()), Some(4));
+        assert_eq!(
+            v.pointer("").and_then(|v| v.keys().first().cloned()),
+            Some("store".to_string())
+        );
+        assert!(v.pointer("/store/items/4").is_none());
+        assert!(v.pointer("/store/missing").is_none());
+        assert!(v.pointer("store/name").is_none());
+        assert!(v.pointer("/store/a~2b").is_none());
+    }
+}
+
+#[test]
+fn test_pointer_result_supports_further_access() {
+    let v = try_parse(STORE).unwrap();
+    let item = v.pointer("/store/items/2").unwrap();
+    assert_eq!(item.path_str(&["name"]), Some("lamp".to_string()));
+    assert_eq!(
+        item.pointer("/discontinued").and_then(|v| v.bool()),
+        Some(true)
+    );
+}
+
+// =========================================================================
+// JSONPATH
+// =========================================================================
+
+#[test]
+fn test_query_members_and_indexes() {
+    let v = try_parse(STORE).unwrap();
+    assert_eq!(v.query("$").unwrap().len(), 1);
+    assert_eq!(
+        v.query("$.store.name").unwrap()[0].str(),
+        Some("Corner".to_string())
+    );
+    assert_eq!(v.query("$['store']['a/b']").unwrap()[0].int(), Some(1));
+    assert_eq!(names(&v.query("$.store.items[0]").unwrap()), ["pen"]);
+    assert_eq!(names(&v.query("$.store.items[-1]").unwrap()), ["mug"]);
+    assert!(v.query("$.store.items[9]").unwrap().is_empty());
+    assert!(v.query("$.store.items[-9]").unwrap().is_empty());
+    assert!(v.query("$.store.missing.name").unwrap().is_empty());
+}
+
+#[test]
+fn test_query_wildcards() {
+    let v = try_parse(STORE).unwrap();
+    let all: Vec<String> = v
+        .query("$.store.items[*].name")
+        .unwrap()
+        .iter()
+        .filter_map(JsonValue::str)
+        .collect();
+    assert_eq!(all, ["pen", "desk", "lamp", "mug"]);
+    assert_eq!(v.query("$.store.*").unwrap().len(), 4);
+}
+
+#[test]
+fn test_query_filters() {
+    let v = try_parse(STORE).unwrap();
+    assert_eq!(
+        names(&v.query("$.store.items[?(@.price < 50)]").unwrap()),
+        ["pen", "lamp"]
+    );
+    assert_eq!(
+        names(&v.query("$.store.items[?(@.price >= 35)]").unwrap()),
+        ["desk", "lamp"]
+    );
+    assert_eq!(
+        names(&v.query("$.store.items[?(@.name == 'desk')]").unwrap()),
+        ["desk"]
+    );
+    assert_eq!(
+        names(&v.query(r#"$.store.items[?(@.name != "desk")]"#).unwrap()),
+        ["pen", "lamp", "mug"]
+    );
+    assert_eq!(
+        names(&v.query("$.store.items[?(@.discontinued == true)]").unwrap()),
+        ["lamp"]
+    );
+    assert_eq!(
+        names(&v.query("$.store.items[?(@.price == null)]").unwrap()),
+        ["mug"]
+    );
+    assert_eq!(
+        names(&v.query("$.store.items[?(@.tags)]").unwrap()),
+        ["pen"]
+    );
+}
+
+#[test]
+fn test_query_on_built_value() {
+    let v = obj().set(
+        "users",
+        arr()
+            .push(obj().set("id", int(1)).set("admin", bool(true)))
+            .push(obj().set("id", int(2)).set("admin", bool(false))),
+    );
+    let admins = v.query("$.users[?(@.admin == true)].id").unwrap();
+    assert_eq!(admins.len(), 1);
+    assert_eq!(admins[0].int(), Some(1));
+}
+
+#[test]
+fn test_query_invalid_expression() {
+    let v = try_parse(STORE).unwrap();
+    assert!(v.query("store.name").is_none());
+    assert!(v.query("$..name").is_none());
+    assert!(v.query("$.store.items[?(@.price <> 1)]").is_none());
+}
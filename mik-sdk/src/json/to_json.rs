@@ -1,6 +1,6 @@
 //! ToJson trait and all implementations.
 
-use super::builder::{arr, float, int, null, str};
+use super::builder::{arr, float, int, null, obj, str, uint};
 use super::value::JsonValue;
 
 /// A trait for converting types to JSON values.
@@ -39,6 +39,7 @@ use super::value::JsonValue;
 /// - Boolean: `bool`
 /// - Optional: `Option<T>` where T: ToJson (None becomes null)
 /// - Arrays: `Vec<T>`, `&[T]` where T: ToJson
+/// - Maps: `HashMap<K, V>`, `BTreeMap<K, V>` with string keys, where V: ToJson
 /// - JSON: `JsonValue` (pass-through)
 ///
 /// # Example
@@ -153,15 +154,14 @@ impl ToJson for u32 {
 impl ToJson for u64 {
     #[inline]
     fn to_json(&self) -> JsonValue {
-        // Note: Values > i64::MAX will be truncated
-        int(*self as i64)
+        uint(*self)
     }
 }
 
 impl ToJson for usize {
     #[inline]
     fn to_json(&self) -> JsonValue {
-        int(*self as i64)
+        uint(*self as u64)
     }
 }
 
@@ -235,6 +235,30 @@ impl<T: ToJson, const N: usize> ToJson for [T; N] {
     }
 }
 
+// === Map implementations (keys become object fields) ===
+
+impl<K: AsRef<str>, V: ToJson, S> ToJson for std::collections::HashMap<K, V, S> {
+    #[inline]
+    fn to_json(&self) -> JsonValue {
+        let mut result = obj();
+        for (key, value) in self {
+            result = result.set(key.as_ref(), value.to_json());
+        }
+        result
+    }
+}
+
+impl<K: AsRef<str>, V: ToJson> ToJson for std::collections::BTreeMap<K, V> {
+    #[inline]
+    fn to_json(&self) -> JsonValue {
+        let mut result = obj();
+        for (key, value) in self {
+            result = result.set(key.as_ref(), value.to_json());
+        }
+        result
+    }
+}
+
 // === JsonValue pass-through ===
 
 impl ToJson for JsonValue {
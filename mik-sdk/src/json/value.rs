@@ -1,7 +1,8 @@
 //! JsonValue struct and all its methods.
 
-use super::lazy;
+use super::{lazy, query};
 use miniserde::json::{Array, Number, Object, Value};
+use std::borrow::Cow;
 use std::rc::Rc;
 
 /// Internal representation of a JSON value.
@@ -162,6 +163,36 @@ impl JsonValue {
         }
     }
 
+    /// Iterate a top-level array element by element, without parsing it.
+    ///
+    /// Yields the raw bytes of each element, which can be parsed on their own
+    /// with [`json::try_parse`](super::try_parse). Only the element being
+    /// processed is materialized, so memory stays bounded even for very large
+    /// arrays.
+    ///
+    /// Returns `None` if this value is not a lazily parsed array (values built
+    /// with `json::arr()` or returned by `get()` are already trees; use
+    /// [`map_array`](Self::map_array) for those). Iteration ends early if the
+    /// bytes are malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::json;
+    /// let body = br#"[{"id":1,"qty":2},{"id":2,"qty":5}]"#;
+    /// let parsed = json::try_parse(body).unwrap();
+    /// let total: i64 = parsed
+    ///     .iter_array_lazy()
+    ///     .unwrap()
+    ///     .filter_map(|item| json::try_parse(item)?.path_int(&["qty"]))
+    ///     .sum();
+    /// assert_eq!(total, 7);
+    /// ```
+    #[must_use]
+    pub fn iter_array_lazy(&self) -> Option<lazy::LazyArrayIter<'_>> {
+        lazy::LazyArrayIter::new(self.bytes()?)
+    }
+
     /// Process array elements with error handling, without per-element cloning.
     ///
     /// Like `map_array()`, but the function can return errors.
@@ -169,9 +200,9 @@ impl JsonValue {
     ///
     /// Note: This triggers a full parse if in lazy mode.
     #[must_use]
-    pub fn try_map_array<T, E, F>(&self, f: F) -> Option<Result<Vec<T>, E>>
+    pub fn try_map_array<T, E, F>(&self, mut f: F) -> Option<Result<Vec<T>, E>>
     where
-        F: Fn(&Value) -> Result<T, E>,
+        F: FnMut(&Value) -> Result<T, E>,
     {
         match self.get_value_for_tree() {
             Value::Array(arr) => {
@@ -188,6 +219,32 @@ impl JsonValue {
         }
     }
 
+    /// Process object entries with error handling, without per-value cloning.
+    ///
+    /// Like `try_map_array()`, but `f` receives each key and value of an
+    /// object. Returns `None` if not an object, `Some(Err(_))` if parsing fails.
+    ///
+    /// Note: This triggers a full parse if in lazy mode.
+    #[must_use]
+    pub fn try_map_object<T, E, F>(&self, mut f: F) -> Option<Result<Vec<(String, T)>, E>>
+    where
+        F: FnMut(&str, &Value) -> Result<T, E>,
+    {
+        match self.get_value_for_tree() {
+            Value::Object(obj) => {
+                let mut result = Vec::with_capacity(obj.len());
+                for (key, value) in obj {
+                    match f(&key, &value) {
+                        Ok(v) => result.push((key, v)),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Some(Ok(result))
+            },
+            _ => None,
+        }
+    }
+
     /// Wrap a raw Value reference in a temporary JsonValue for parsing.
     ///
     /// This is useful inside `map_array`/`try_map_array` callbacks when you
@@ -208,6 +265,33 @@ impl JsonValue {
         }
     }
 
+    /// As a borrowed string, None if not a string.
+    ///
+    /// Unlike [`str`](Self::str), this does not allocate unless the string
+    /// contains escapes that must be decoded. In lazy mode it reads the raw
+    /// bytes without parsing the tree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::json;
+    /// # use std::borrow::Cow;
+    /// let parsed = json::try_parse(br#"{"name":"Alice"}"#).unwrap();
+    /// let name = parsed.get("name");
+    /// assert_eq!(name.as_str().as_deref(), Some("Alice"));
+    /// assert!(matches!(name.as_str(), Some(Cow::Borrowed(_))));
+    /// ```
+    #[must_use]
+    pub fn as_str(&self) -> Option<Cow<'_, str>> {
+        match &self.inner {
+            JsonInner::Parsed(v) => match &**v {
+                Value::String(s) => Some(Cow::Borrowed(s)),
+                _ => None,
+            },
+            JsonInner::Lazy { bytes } => lazy::parse_string_ref(bytes),
+        }
+    }
+
     /// As string, or default if not a string.
     #[must_use]
     pub fn str_or(&self, default: &str) -> String {
@@ -240,6 +324,32 @@ impl JsonValue {
         self.int().unwrap_or(default)
     }
 
+    /// As unsigned integer, None if not a non-negative integer.
+    ///
+    /// Unlike [`int()`](Self::int), values above `i64::MAX` are returned exactly.
+    #[must_use]
+    pub fn uint(&self) -> Option<u64> {
+        match self.get_value_for_tree() {
+            Value::Number(n) => number_to_uint(&n),
+            _ => None,
+        }
+    }
+
+    /// As decimal text, None if not a number or a string holding one.
+    ///
+    /// Decimals written with [`json::decimal`](super::decimal) are strings and
+    /// come back exactly. Numbers in a parsed tree are already f64 or
+    /// integers; to read a JSON number with all its digits, use
+    /// [`path_decimal()`](Self::path_decimal) on a lazily parsed value.
+    #[must_use]
+    pub fn decimal(&self) -> Option<String> {
+        match self.get_value_for_tree() {
+            Value::String(s) => super::is_json_number(&s).then_some(s),
+            Value::Number(n) => number_to_decimal(&n),
+            _ => None,
+        }
+    }
+
     /// As float, None if not a number.
     ///
     /// # Precision Warning
@@ -327,7 +437,9 @@ impl JsonValue {
 
     /// Navigate to a nested value by path, returning a reference to the raw Value.
     ///
-    /// This requires a full parse. For lazy scanning, use `path_str`, `path_int`, etc.
+    /// Segments select object keys, or array elements when they are a
+    /// decimal index. This requires a full parse. For lazy scanning, use
+    /// `path_str`, `path_int`, etc.
     fn get_path(&self, path: &[&str]) -> Option<&Value> {
         let mut current = self.value();
         for key in path {
@@ -335,6 +447,9 @@ impl JsonValue {
                 Value::Object(obj) => {
                     current = obj.get(*key)?;
                 },
+                Value::Array(arr) if key.bytes().all(|b| b.is_ascii_digit()) => {
+                    current = arr.get(key.parse::<usize>().ok()?)?;
+                },
                 _ => return None,
             }
         }
@@ -354,6 +469,11 @@ impl JsonValue {
     /// let parsed = json::try_parse(body).unwrap();
     /// let name = parsed.path_str(&["user", "name"]);  // Lazy scan: ~500ns
     /// assert_eq!(name, Some("Alice".to_string()));
+    ///
+    /// // Numeric segments index into arrays
+    /// let body = br#"{"items":[{"name":"first"}]}"#;
+    /// let parsed = json::try_parse(body).unwrap();
+    /// assert_eq!(parsed.path_str(&["items", "0", "name"]), Some("first".to_string()));
     /// ```
     #[must_use]
     pub fn path_str(&self, path: &[&str]) -> Option<String> {
@@ -369,6 +489,38 @@ impl JsonValue {
         }
     }
 
+    /// Get string at path without allocating.
+    ///
+    /// Returns a [`Cow::Borrowed`] over the request bytes (or the parsed
+    /// tree) unless the string contains escapes that must be decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::json;
+    /// # use std::borrow::Cow;
+    /// let parsed = json::try_parse(br#"{"user":{"name":"Alice","bio":"a\nb"}}"#).unwrap();
+    /// let name = parsed.path_str_ref(&["user", "name"]);
+    /// assert!(matches!(name, Some(Cow::Borrowed("Alice"))));
+    ///
+    /// // Escaped strings are decoded into an owned value
+    /// let bio = parsed.path_str_ref(&["user", "bio"]);
+    /// assert!(matches!(bio.as_deref(), Some("a\nb")));
+    /// ```
+    #[must_use]
+    pub fn path_str_ref(&self, path: &[&str]) -> Option<Cow<'_, str>> {
+        // Fast path: lazy scanning
+        if let Some(bytes) = self.bytes() {
+            return lazy::path_str_ref(bytes, path);
+        }
+
+        // Fallback: tree traversal
+        match self.get_path(path)? {
+            Value::String(s) => Some(Cow::Borrowed(s)),
+            _ => None,
+        }
+    }
+
     /// Get string at path, or default.
     #[must_use]
     pub fn path_str_or(&self, path: &[&str], default: &str) -> String {
@@ -409,6 +561,42 @@ impl JsonValue {
         self.path_int(path).unwrap_or(default)
     }
 
+    /// Get unsigned integer at path.
+    ///
+    /// When in lazy mode, this scans the raw bytes without parsing the full tree.
+    #[must_use]
+    pub fn path_uint(&self, path: &[&str]) -> Option<u64> {
+        // Fast path: lazy scanning
+        if let Some(bytes) = self.bytes() {
+            return lazy::path_uint(bytes, path);
+        }
+
+        // Fallback: tree traversal
+        match self.get_path(path)? {
+            Value::Number(n) => number_to_uint(n),
+            _ => None,
+        }
+    }
+
+    /// Get decimal text at path, from a number or a string holding one.
+    ///
+    /// In lazy mode, numbers are returned exactly as written in the input,
+    /// so amounts like `12345678901234567890.12` keep every digit.
+    #[must_use]
+    pub fn path_decimal(&self, path: &[&str]) -> Option<String> {
+        // Fast path: lazy scanning
+        if let Some(bytes) = self.bytes() {
+            return lazy::path_decimal(bytes, path);
+        }
+
+        // Fallback: tree traversal
+        match self.get_path(path)? {
+            Value::String(s) => super::is_json_number(s).then(|| s.clone()),
+            Value::Number(n) => number_to_decimal(n),
+            _ => None,
+        }
+    }
+
     /// Get float at path.
     ///
     /// When in lazy mode, this scans the raw bytes without parsing the full tree.
@@ -489,6 +677,78 @@ impl JsonValue {
         self.get_path(path).is_some()
     }
 
+    // === Queries ===
+
+    /// Get the value at a JSON Pointer (RFC 6901), e.g. `/user/addresses/0/city`.
+    ///
+    /// `~1` and `~0` in a token stand for `/` and `~`. The empty pointer
+    /// selects the whole document. Returns `None` if the pointer is malformed
+    /// or nothing is there.
+    ///
+    /// When in lazy mode, this scans the raw bytes and only the selected
+    /// value is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::json;
+    /// let body = br#"{"user":{"addresses":[{"city":"Paris"}]}}"#;
+    /// let parsed = json::try_parse(body).unwrap();
+    /// let city = parsed.pointer("/user/addresses/0/city").and_then(|v| v.str());
+    /// assert_eq!(city, Some("Paris".to_string()));
+    /// assert!(parsed.pointer("user").is_none());
+    /// ```
+    #[must_use]
+    pub fn pointer(&self, pointer: &str) -> Option<Self> {
+        let tokens = query::parse_pointer(pointer)?;
+        let path: Vec<&str> = tokens.iter().map(String::as_str).collect();
+
+        // Fast path: lazy scanning
+        if let Some(bytes) = self.bytes() {
+            return lazy::path_slice(bytes, &path).map(Self::from_bytes);
+        }
+
+        // Fallback: tree traversal
+        self.get_path(&path).cloned().map(Self::new)
+    }
+
+    /// Select values with a JSONPath expression.
+    ///
+    /// Supports `$`, `.name`, `['name']`, `[index]` (negative counts from the
+    /// end), `.*` and `[*]`, and filters such as `[?(@.price < 10)]` or
+    /// `[?(@.tag)]`. Recursive descent (`..`) is not supported.
+    ///
+    /// Returns the matches in document order, or `None` if the expression is
+    /// invalid. This requires a full parse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::json;
+    /// let body = br#"{"items":[{"name":"pen","price":2},{"name":"desk","price":120}]}"#;
+    /// let parsed = json::try_parse(body).unwrap();
+    /// let cheap: Vec<String> = parsed
+    ///     .query("$.items[?(@.price < 10)].name")
+    ///     .unwrap()
+    ///     .iter()
+    ///     .filter_map(|v| v.str())
+    ///     .collect();
+    /// assert_eq!(cheap, ["pen"]);
+    /// ```
+    #[must_use]
+    pub fn query(&self, path: &str) -> Option<Vec<Self>> {
+        let parsed;
+        let root = match &self.inner {
+            JsonInner::Parsed(v) => &**v,
+            JsonInner::Lazy { bytes } => {
+                parsed = Self::parse_bytes(bytes).unwrap_or(Value::Null);
+                &parsed
+            },
+        };
+        let matches = query::query(root, path)?;
+        Some(matches.into_iter().cloned().map(Self::new).collect())
+    }
+
     // === Building (fluent) ===
 
     /// Get mutable access to the parsed value, converting from lazy if needed.
@@ -554,6 +814,62 @@ impl JsonValue {
         self
     }
 
+    /// Apply a JSON Merge Patch (RFC 7396).
+    ///
+    /// Members of `patch` replace those of `self`, objects are merged
+    /// recursively, and `null` members are removed. A `patch` that is not an
+    /// object replaces the whole value. This is the usual semantics for
+    /// `PATCH` requests with `application/merge-patch+json` bodies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::json;
+    /// let stored = json::obj()
+    ///     .set("name", json::str("Alice"))
+    ///     .set("phone", json::str("555-0100"))
+    ///     .set("address", json::obj().set("city", json::str("Paris")));
+    /// let patch = json::try_parse(br#"{"phone":null,"address":{"zip":"75001"}}"#).unwrap();
+    /// let updated = stored.merge(&patch);
+    /// assert_eq!(
+    ///     updated.to_string(),
+    ///     r#"{"address":{"city":"Paris","zip":"75001"},"name":"Alice"}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn merge(mut self, patch: &Self) -> Self {
+        let patch = patch.get_value_for_tree();
+        merge_patch(Rc::make_mut(self.get_parsed_mut()), patch);
+        self
+    }
+
+    /// Recursively merge `other` into this value.
+    ///
+    /// Like [`merge`](Self::merge), objects are merged member by member, but
+    /// `null` members of `other` are kept as `null` instead of removing the
+    /// member. Any other value, including arrays, replaces the existing one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::json;
+    /// let defaults = json::obj()
+    ///     .set("retries", json::int(3))
+    ///     .set("log", json::obj().set("level", json::str("info")).set("file", json::str("app.log")));
+    /// let overrides = json::obj()
+    ///     .set("log", json::obj().set("level", json::str("debug")).set("file", json::null()));
+    /// let config = defaults.deep_merge(&overrides);
+    /// assert_eq!(config.path_str(&["log", "level"]), Some("debug".to_string()));
+    /// assert!(config.path_is_null(&["log", "file"]));
+    /// assert_eq!(config.path_int(&["retries"]), Some(3));
+    /// ```
+    #[must_use]
+    pub fn deep_merge(mut self, other: &Self) -> Self {
+        let other = other.get_value_for_tree();
+        deep_merge_values(Rc::make_mut(self.get_parsed_mut()), other);
+        self
+    }
+
     // === Output ===
 
     /// Serialize to JSON bytes.
@@ -585,3 +901,67 @@ impl std::fmt::Debug for JsonValue {
         std::fmt::Display::fmt(self, f)
     }
 }
+
+/// Apply an RFC 7396 merge patch to `target`.
+fn merge_patch(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+    if !matches!(target, Value::Object(_)) {
+        *target = Value::Object(Object::new());
+    }
+    let Value::Object(target) = target else {
+        return;
+    };
+    for (key, value) in patch {
+        if matches!(value, Value::Null) {
+            target.remove(&key);
+        } else {
+            merge_patch(target.entry(key).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Merge `other` into `target`, recursing into objects present in both.
+fn deep_merge_values(target: &mut Value, other: Value) {
+    match (target, other) {
+        (Value::Object(target), Value::Object(other)) => {
+            for (key, value) in other {
+                match target.get_mut(&key) {
+                    Some(existing) => deep_merge_values(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    },
+                }
+            }
+        },
+        (target, other) => *target = other,
+    }
+}
+
+/// Convert a number to u64 if it is a non-negative integer.
+fn number_to_uint(n: &Number) -> Option<u64> {
+    match *n {
+        Number::U64(u) => Some(u),
+        Number::I64(i) => i.try_into().ok(),
+        Number::F64(f) => {
+            const MAX_SAFE_INT: f64 = 9007199254740992.0; // 2^53
+            if f.is_finite() && (0.0..=MAX_SAFE_INT).contains(&f) && f.fract() == 0.0 {
+                Some(f as u64)
+            } else {
+                None
+            }
+        },
+    }
+}
+
+/// Format a number as decimal text (f64 values use their shortest form).
+fn number_to_decimal(n: &Number) -> Option<String> {
+    match *n {
+        Number::U64(u) => Some(u.to_string()),
+        Number::I64(i) => Some(i.to_string()),
+        Number::F64(f) if f.is_finite() => Some(f.to_string()),
+        Number::F64(_) => None,
+    }
+}
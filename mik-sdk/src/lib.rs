@@ -99,6 +99,13 @@
 //! |----------------------|---------|--------------------------------------|
 //! | `MIK_MAX_JSON_SIZE`  | 1 MB    | Maximum JSON input size for parsing  |
 //! | `MIK_MAX_BODY_SIZE`  | 10 MB   | Maximum request body size (bridge)   |
+//! | `MIK_MAX_HEADERS`    | 100     | Maximum request header count (bridge) |
+//! | `MIK_MAX_HEADER_SIZE` | 64 KB  | Maximum combined header size (bridge) |
+//! | `MIK_RATE_LIMIT`     | off     | Requests per client IP, e.g. `100/min` (see [`rate_limit`]) |
+//! | `MIK_ACCESS_LOG`     | off     | Access log format: `json` or `common` (see [`access_log`]) |
+//! | `MIK_REQUEST_TIMEOUT` | none  | Request deadline in milliseconds, 504 past it (bridge) |
+//! | `MIK_TASKS`          | deferred | `inline` runs [`tasks`] before the response is sent |
+//! | `MIK_SLOW_QUERY_MS`  | off     | Log queries at least this slow (see `query_log`) |
 //!
 //! ```bash
 //! # Allow 5MB JSON payloads
@@ -108,6 +115,9 @@
 //! MIK_MAX_BODY_SIZE=52428800
 //! ```
 //!
+//! Application settings are read with [`config`], which parses typed values
+//! and supports `#[derive(Config)]` structs.
+//!
 //! # Core Macros
 //!
 //! - [`ok!`] - Return 200 OK with JSON body
@@ -120,6 +130,24 @@
 //! - [`created!`] - 201 Created response with Location header
 //! - [`no_content!`] - 204 No Content response
 //! - [`redirect!`] - Redirect responses (301, 302, 307, etc.)
+//! - [`stream!`] - Streamed response body (see [`mod@stream`])
+//! - [`sse!`] - Server-Sent Events response (see [`mod@sse`])
+//!
+//! A `ws "/chat" => chat()` entry in `routes!` serves a WebSocket endpoint
+//! on bridges built with WebSocket support (see [`websocket`]). [`jobs!`],
+//! next to `routes!`, exports cron-style jobs run by the host's scheduler
+//! (see [`schedule`]), and [`consumers!`] handles queue and topic messages
+//! with typed, validated bodies (see [`consumer`]).
+//!
+//! # Tracing
+//!
+//! `#![trace]` in `routes!` gives every request a W3C `traceparent`, echoed
+//! in the response and attached to logs and outbound calls (see [`trace`]).
+//!
+//! The router records request counts and latencies in [`metrics`];
+//! `#![config(metrics)]` serves them at `/metrics` for Prometheus. A
+//! `health "/healthz"` entry adds liveness and readiness endpoints (see
+//! [`health`]).
 //!
 //! # Request Helpers
 //!
@@ -197,12 +225,50 @@ pub mod constants;
 mod request;
 pub mod typed;
 
+pub mod access_log;
+pub mod audit;
+pub mod auth;
+pub mod cache;
+pub mod conditional;
+pub mod config;
+pub mod connect;
+pub mod consumer;
+pub mod cookies;
+pub mod cors;
+pub mod crypto;
+pub mod csrf;
+pub mod csv;
+pub mod encoding;
 pub mod env;
+pub mod envelope;
+pub mod error;
+pub mod health;
 pub mod http_client;
+pub mod i18n;
 pub mod json;
+pub mod links;
 pub mod log;
+pub mod metrics;
+pub mod middleware;
+pub mod pagination;
+pub mod password;
+pub mod proxy;
 pub mod random;
+pub mod range;
+pub mod rate_limit;
+pub mod recover;
+pub mod replay;
+pub mod response;
+pub mod router;
+pub mod schedule;
+pub mod secrets;
+pub mod sse;
+pub mod stream;
+pub mod tasks;
+pub mod testing;
 pub mod time;
+pub mod trace;
+pub mod websocket;
 
 // WASI bindings (HTTP, random, clocks)
 // Always included for WASM target, uses http-client feature for HTTP client on native
@@ -212,9 +278,21 @@ pub(crate) mod wasi_http;
 // Query module - re-export from mik-sql when the sql feature is enabled
 #[cfg(feature = "sql")]
 pub use mik_sql as query;
+#[cfg(feature = "sql")]
+pub mod query_log;
+
+// Protobuf bodies with prost messages
+#[cfg(feature = "prost")]
+pub mod proto;
+
+// Fuzz targets over the production parsers
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 
 pub use mik_sdk_macros::{
     // Derive macros for typed inputs
+    Form,
+    Headers,
     Path,
     Query,
     Type,
@@ -222,6 +300,8 @@ pub use mik_sdk_macros::{
     accepted,
     bad_request,
     conflict,
+    // Message consumers
+    consumers,
     created,
     // DX macros
     ensure,
@@ -233,6 +313,8 @@ pub use mik_sdk_macros::{
     guard,
     // Batched loading helper
     ids,
+    // Scheduled jobs
+    jobs,
     json,
     no_content,
     not_found,
@@ -240,6 +322,8 @@ pub use mik_sdk_macros::{
     redirect,
     // Routing macros
     routes,
+    sse,
+    stream,
 };
 
 // SQL CRUD macros - re-exported from mik-sql-macros when sql feature is enabled
@@ -275,8 +359,12 @@ pub fn __ensure_helper<T, H: EnsureHelper<T>>(value: H) -> Option<T> {
     value.into_option()
 }
 
+pub use error::Error;
+pub use response::IntoResponse;
+
 pub use request::{
-    DecodeError, Method, MultipartError, Part, Request, SameSite, SetCookie, url_decode,
+    BodyError, BodyStream, DecodeError, Method, MultipartError, MultipartStream, Part, Request,
+    SameSite, SetCookie, StreamedPart, url_decode,
 };
 
 /// HTTP status code constants.
@@ -295,6 +383,8 @@ pub mod status {
     pub const ACCEPTED: u16 = 202;
     /// 204 No Content - Success with no response body.
     pub const NO_CONTENT: u16 = 204;
+    /// 206 Partial Content - Range request served.
+    pub const PARTIAL_CONTENT: u16 = 206;
 
     // 3xx Redirection
     /// 301 Moved Permanently - Resource moved permanently.
@@ -325,6 +415,8 @@ pub mod status {
     pub const CONFLICT: u16 = 409;
     /// 410 Gone - Resource permanently removed.
     pub const GONE: u16 = 410;
+    /// 416 Range Not Satisfiable - Requested range outside the resource.
+    pub const RANGE_NOT_SATISFIABLE: u16 = 416;
     /// 422 Unprocessable Entity - Validation failed.
     pub const UNPROCESSABLE_ENTITY: u16 = 422;
     /// 429 Too Many Requests - Rate limit exceeded.
@@ -357,30 +449,58 @@ pub mod status {
 /// - [`status`] - HTTP status code constants
 /// - [`mod@env`] - Environment variable access helpers
 /// - [`http_client`] - HTTP client for outbound requests
+/// - [`CookieKey`](crate::cookies::CookieKey) - Signed and encrypted cookies
+/// - [`auth`] - Authentication middleware for `routes!`
+/// - [`Cors`](crate::cors::Cors) - CORS configuration for `#![cors(...)]`
+/// - [`range`] - Range requests (`206 Partial Content`) for binary bodies
+/// - [`Cache`](crate::cache::Cache), [`CacheExt`](crate::cache::CacheExt) - Caching headers via `.cache(...)`
+/// - [`HealthChecks`](crate::health::HealthChecks) - Readiness checks for `health "/healthz"`
+/// - [`WebSocket`](crate::websocket::WebSocket) - Callbacks for `ws "/path"` endpoints
+/// - [`tasks`] - Work deferred until after the response is sent
+/// - [`jobs!`] - Scheduled (cron) jobs, see [`schedule`](crate::schedule)
+/// - [`consumers!`], [`Message`](crate::consumer::Message) - Queue and topic consumers
 /// - Core macros: [`ok!`], [`error!`], [`json!`], [`routes!`], [`log!`]
 /// - DX macros: [`guard!`],
 ///   [`created!`], [`no_content!`], [`redirect!`], [`not_found!`],
 ///   [`conflict!`], [`forbidden!`], [`ensure!`], [`fetch!`]
+/// - Streaming: [`stream!`], [`sse!`], [`ChunkedBody`](crate::stream::ChunkedBody),
+///   [`BodyStream`], [`MultipartStream`]
 pub mod prelude {
+    pub use crate::auth;
+    pub use crate::cache::{Cache, CacheExt};
+    pub use crate::config;
+    pub use crate::consumer::Message;
+    pub use crate::cookies::CookieKey;
+    pub use crate::cors::Cors;
     pub use crate::env;
+    pub use crate::health::HealthChecks;
     pub use crate::http_client;
     pub use crate::json;
     pub use crate::json::ToJson;
     pub use crate::log;
     pub use crate::random;
+    pub use crate::range;
     pub use crate::request::{
-        DecodeError, Method, MultipartError, Part, Request, SameSite, SetCookie,
+        BodyError, BodyStream, DecodeError, Method, MultipartError, MultipartStream, Part, Request,
+        SameSite, SetCookie, StreamedPart,
     };
+    pub use crate::secrets;
+    pub use crate::sse;
     pub use crate::status;
+    pub use crate::stream;
+    pub use crate::stream::ChunkedBody;
+    pub use crate::tasks;
     pub use crate::time;
+    pub use crate::websocket::WebSocket;
     // Typed input types
     pub use crate::typed::{
-        FromJson, FromPath, FromQuery, Id, OpenApiSchema, ParseError, Validate, ValidationError,
+        FromForm, FromHeaders, FromJson, FromPath, FromQuery, FromRequest, Id, OpenApiSchema,
+        ParseError, Validate, ValidationError,
     };
     // Core macros (json module already exported above)
-    pub use crate::{error, ok, routes};
+    pub use crate::{consumers, error, jobs, ok, routes};
     // Derive macros for typed inputs
-    pub use crate::{Path, Query, Type};
+    pub use crate::{Form, Headers, Path, Query, Type};
     // DX macros
     pub use crate::{
         accepted, bad_request, conflict, created, ensure, fetch, forbidden, guard, no_content,
@@ -442,12 +562,33 @@ mod api_contracts {
     // MultipartError is Clone, Debug, PartialEq, Eq
     assert_impl_all!(crate::MultipartError: Clone, std::fmt::Debug, PartialEq, Eq);
 
+    // BodyError is Copy, Clone, Debug, PartialEq, Eq
+    assert_impl_all!(crate::BodyError: Copy, Clone, std::fmt::Debug, PartialEq, Eq);
+
+    // BodyStream is Debug and io::Read, but not Clone (it owns the stream)
+    assert_impl_all!(crate::BodyStream: std::fmt::Debug, std::io::Read);
+    assert_impl_all!(crate::MultipartStream: std::fmt::Debug);
+    assert_impl_all!(crate::StreamedPart: Clone, std::fmt::Debug, PartialEq, Eq);
+    assert_not_impl_any!(crate::BodyStream: Clone);
+
     // SameSite is Copy, Clone, Debug, PartialEq, Eq
     assert_impl_all!(crate::SameSite: Copy, Clone, std::fmt::Debug, PartialEq, Eq);
 
     // SetCookie is Clone, Debug
     assert_impl_all!(crate::SetCookie: Clone, std::fmt::Debug);
 
+    // CookieKey is Clone, Debug (redacted), and shareable from a static
+    assert_impl_all!(crate::cookies::CookieKey: Clone, std::fmt::Debug, Send, Sync);
+
+    // ApiKey can live in a static and be shared by route hooks
+    assert_impl_all!(crate::auth::ApiKey: Clone, std::fmt::Debug, Send, Sync);
+    assert_impl_all!(crate::auth::BasicAuth: Clone, std::fmt::Debug, Send, Sync);
+    // Cors lives in a static generated by routes!
+    assert_impl_all!(crate::cors::Cors: Clone, std::fmt::Debug, Send, Sync);
+    assert_impl_all!(crate::range::ByteRange: Copy, Clone, std::fmt::Debug, PartialEq, Eq);
+    assert_impl_all!(crate::range::RangeError: Copy, Clone, std::fmt::Debug, PartialEq, Eq, std::error::Error);
+    assert_impl_all!(crate::cache::Cache: Clone, std::fmt::Debug, std::fmt::Display, PartialEq, Eq, Send, Sync);
+
     // ========================================================================
     // HTTP Client types (when http-client feature is enabled)
     // ========================================================================
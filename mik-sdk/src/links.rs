@@ -0,0 +1,244 @@
+//! Hypermedia links in the HAL `_links` format.
+//!
+//! [`Links`] collects link relations and renders them as the `_links`
+//! object of a response body. Paths come from the `url_for` module that
+//! `routes!` generates, so links follow the route table instead of being
+//! assembled by hand:
+//!
+//! ```ignore
+//! use mik_sdk::links::{self, Links};
+//!
+//! routes! {
+//!     GET "/users/{id}" => get_user(path: Id),
+//!     GET "/users/{id}/posts" => list_posts(path: Id, query: PaginationQuery),
+//! }
+//!
+//! fn get_user(path: Id, _req: &Request) -> Result<JsonValue, Error> {
+//!     let user = db::find_user(&path.0)?;
+//!     let posts = url_for::list_posts(&user.id);
+//!     // {"id":"7","name":"Ada","_links":{
+//!     //   "self":{"href":"/users/7"},
+//!     //   "posts":{"href":"/users/7/posts?limit=20"}}}
+//!     let links = Links::new()
+//!         .self_link(url_for::get_user(&user.id))
+//!         .link("posts", links::with_query(posts, [("limit", 20)]));
+//!     Ok(links.attach(user.to_json()))
+//! }
+//! ```
+//!
+//! A relation added more than once renders as an array of links.
+
+use std::fmt::{Display, Write};
+
+use crate::encoding::percent_encode;
+use crate::json::{self, JsonValue, ToJson};
+
+/// Key of the links object in a response body.
+pub const LINKS_KEY: &str = "_links";
+
+/// A set of link relations, rendered as a HAL `_links` object.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Links {
+    links: Vec<(String, String)>,
+}
+
+impl Links {
+    /// An empty set of links.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Link to the resource itself (`self`).
+    #[must_use]
+    pub fn self_link(self, href: impl Into<String>) -> Self {
+        self.link("self", href)
+    }
+
+    /// Link to the next page (`next`).
+    #[must_use]
+    pub fn next(self, href: impl Into<String>) -> Self {
+        self.link("next", href)
+    }
+
+    /// Link to the previous page (`prev`).
+    #[must_use]
+    pub fn prev(self, href: impl Into<String>) -> Self {
+        self.link("prev", href)
+    }
+
+    /// Link to a related resource (`related`).
+    #[must_use]
+    pub fn related(self, href: impl Into<String>) -> Self {
+        self.link("related", href)
+    }
+
+    /// Add a link with any relation.
+    #[must_use]
+    pub fn link(mut self, rel: impl Into<String>, href: impl Into<String>) -> Self {
+        self.links.push((rel.into(), href.into()));
+        self
+    }
+
+    /// Add a link only when `href` is `Some`, e.g. a next page that may
+    /// not exist.
+    #[must_use]
+    pub fn link_opt(self, rel: impl Into<String>, href: Option<impl Into<String>>) -> Self {
+        match href {
+            Some(href) => self.link(rel, href),
+            None => self,
+        }
+    }
+
+    /// The first link with relation `rel`.
+    #[must_use]
+    pub fn get(&self, rel: &str) -> Option<&str> {
+        self.links
+            .iter()
+            .find(|(r, _)| r == rel)
+            .map(|(_, href)| href.as_str())
+    }
+
+    /// Whether there are no links.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Set these links as the `_links` member of `body`.
+    #[must_use]
+    pub fn attach(&self, body: JsonValue) -> JsonValue {
+        body.set(LINKS_KEY, self.to_json())
+    }
+}
+
+impl ToJson for Links {
+    fn to_json(&self) -> JsonValue {
+        let mut out = json::obj();
+        for (i, (rel, _)) in self.links.iter().enumerate() {
+            if self.links[..i].iter().any(|(r, _)| r == rel) {
+                continue;
+            }
+            let hrefs: Vec<&str> = self
+                .links
+                .iter()
+                .filter(|(r, _)| r == rel)
+                .map(|(_, href)| href.as_str())
+                .collect();
+            let value = if let [href] = hrefs.as_slice() {
+                link_object(href)
+            } else {
+                hrefs
+                    .iter()
+                    .fold(json::arr(), |arr, href| arr.push(link_object(href)))
+            };
+            out = out.set(rel, value);
+        }
+        out
+    }
+}
+
+fn link_object(href: &str) -> JsonValue {
+    json::obj().set("href", json::str(href))
+}
+
+/// Append percent-encoded query parameters to a path.
+///
+/// Uses `&` if the path already has a query string.
+///
+/// ```ignore
+/// let next = links::with_query(url_for::list_posts(7), [("after", cursor)]);
+/// ```
+#[must_use]
+pub fn with_query<K, V>(path: impl Into<String>, params: impl IntoIterator<Item = (K, V)>) -> String
+where
+    K: AsRef<str>,
+    V: Display,
+{
+    let mut url = path.into();
+    let mut separator = if url.contains('?') { '&' } else { '?' };
+    for (key, value) in params {
+        let _ = write!(
+            url,
+            "{separator}{}={}",
+            percent_encode(key.as_ref().as_bytes()),
+            percent_encode(value.to_string().as_bytes())
+        );
+        separator = '&';
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_links() {
+        let links = Links::new()
+            .self_link("/users/7")
+            .next("/users?offset=20")
+            .related("/teams/1");
+        let value = links.to_json();
+        assert_eq!(
+            value.path_str(&["self", "href"]).as_deref(),
+            Some("/users/7")
+        );
+        assert_eq!(
+            value.path_str(&["next", "href"]).as_deref(),
+            Some("/users?offset=20")
+        );
+        assert_eq!(
+            value.path_str(&["related", "href"]).as_deref(),
+            Some("/teams/1")
+        );
+        assert!(value.get("prev").is_null());
+        assert_eq!(links.get("next"), Some("/users?offset=20"));
+    }
+
+    #[test]
+    fn test_repeated_relation_is_array() {
+        let value = Links::new()
+            .link("item", "/posts/1")
+            .self_link("/posts")
+            .link("item", "/posts/2")
+            .to_json();
+        assert_eq!(value.get("item").len(), Some(2));
+        assert_eq!(
+            value.get("item").at(1).get("href").str().as_deref(),
+            Some("/posts/2")
+        );
+        assert_eq!(value.path_str(&["self", "href"]).as_deref(), Some("/posts"));
+    }
+
+    #[test]
+    fn test_link_opt_and_attach() {
+        let links = Links::new()
+            .self_link("/users")
+            .link_opt("next", None::<String>)
+            .link_opt("prev", Some("/users?offset=0"));
+        assert_eq!(links.get("next"), None);
+
+        let body = links.attach(json::obj().set("total", json::int(3)));
+        assert_eq!(body.get("total").int(), Some(3));
+        assert_eq!(
+            body.path_str(&["_links", "prev", "href"]).as_deref(),
+            Some("/users?offset=0")
+        );
+        assert!(Links::new().is_empty());
+    }
+
+    #[test]
+    fn test_with_query() {
+        assert_eq!(with_query("/posts", [("limit", 20)]), "/posts?limit=20");
+        assert_eq!(
+            with_query("/posts?limit=20", [("after", "a b&c")]),
+            "/posts?limit=20&after=a%20b%26c"
+        );
+        assert_eq!(
+            with_query("/posts", [("q", "x"), ("page", "2")]),
+            "/posts?q=x&page=2"
+        );
+        assert_eq!(with_query("/posts", Vec::<(&str, u8)>::new()), "/posts");
+    }
+}
@@ -9,6 +9,8 @@
 //! - `level`: Log level ("debug", "info", "warn", "error")
 //! - `msg`: The log message
 //! - `ts`: ISO 8601 UTC timestamp (e.g., "2025-01-16T10:30:00Z")
+//! - `trace_id`: The request's trace ID, when `routes!` uses `#![trace]`
+//!   (see [`trace`](crate::trace))
 //! - Additional key-value fields as specified
 //!
 //! ```json
@@ -44,6 +46,11 @@
 //! log::debug!("Debug message");  // Only in debug builds
 //! ```
 //!
+//! # Redacted Values
+//!
+//! Log request types with [`redacted`] so `#[field(redact)]` fields such as
+//! passwords and tokens show as `"[REDACTED]"`.
+//!
 //! # Debug Logging
 //!
 //! `debug!` messages are only emitted in debug builds (when `#[cfg(debug_assertions)]` is true).
@@ -64,6 +71,7 @@
 //! and builtin attributes, but are re-exported as `info`, `warn`, `error`, and `debug`
 //! for use as `log::info!()`, `log::warn!()`, etc.
 
+use crate::typed::ToLogJson;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Format timestamp as ISO 8601 with millisecond precision.
@@ -199,9 +207,12 @@ pub fn __write_simple_log(level: &str, msg: &str) {
     use std::io::Write;
     let timestamp = __format_timestamp();
     let escaped = __escape_json(msg);
+    let trace = crate::trace::current_trace_id()
+        .map(|id| format!(r#","trace_id":"{id}""#))
+        .unwrap_or_default();
     let _ = writeln!(
         std::io::stderr(),
-        r#"{{"level":"{level}","msg":"{escaped}","ts":"{timestamp}"}}"#,
+        r#"{{"level":"{level}","msg":"{escaped}"{trace},"ts":"{timestamp}"}}"#,
     );
 }
 
@@ -272,6 +283,48 @@ pub use log_error as error;
 pub use log_info as info;
 pub use log_warn as warn;
 
+// ============================================================================
+// REDACTED VALUES
+// ============================================================================
+
+/// Display a value as its redacted JSON, for `log!` fields.
+///
+/// Fields marked `#[field(redact)]` on a `#[derive(Type)]` struct show as
+/// `"[REDACTED]"`:
+///
+/// ```ignore
+/// #[derive(Type)]
+/// pub struct Signup {
+///     pub email: String,
+///     #[field(redact)]
+///     pub password: String,
+/// }
+///
+/// // "input":"{\"email\":\"ada@example.com\",\"password\":\"[REDACTED]\"}"
+/// log!(info, "signup", input: log::redacted(&body));
+/// ```
+#[must_use]
+pub const fn redacted<T: ToLogJson + ?Sized>(value: &T) -> Redacted<'_, T> {
+    Redacted(value)
+}
+
+/// A value displayed as its redacted JSON. Built by [`redacted`].
+pub struct Redacted<'a, T: ?Sized>(&'a T);
+
+impl<T: ToLogJson + ?Sized> std::fmt::Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_log_json())
+    }
+}
+
+impl<T: ToLogJson + ?Sized> std::fmt::Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Redacted")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
 // ============================================================================
 // STRUCTURED LOGGING MACRO
 // ============================================================================
@@ -303,6 +356,15 @@ pub fn __build_structured_log(level: &str, msg: &str, fields: &[(&str, &str)]) -
         output.push('"');
     }
 
+    // With #![trace], tie the line to the request unless a field already does
+    if let Some(trace_id) = crate::trace::current_trace_id()
+        && !fields.iter().any(|(key, _)| *key == "trace_id")
+    {
+        output.push_str(r#","trace_id":""#);
+        output.push_str(&trace_id);
+        output.push('"');
+    }
+
     output.push_str(r#","ts":""#);
     output.push_str(&now_iso());
     output.push_str(r#""}"#);
@@ -740,4 +802,18 @@ mod tests {
             "timestamp should be 20 or 24 chars"
         );
     }
+
+    #[test]
+    fn test_structured_log_includes_current_trace_id() {
+        let mut headers = vec![(
+            "traceparent".to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        )];
+        let traceparent = crate::trace::__begin(&mut headers);
+        let output = __build_structured_log("info", "test", &[]);
+        crate::trace::__end(traceparent, &mut Vec::new());
+
+        assert!(output.contains(r#","trace_id":"4bf92f3577b34da6a3ce929d0e0e4736","ts":""#));
+        assert!(!__build_structured_log("info", "test", &[]).contains("trace_id"));
+    }
 }
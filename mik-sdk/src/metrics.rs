@@ -0,0 +1,305 @@
+//! In-process metrics with Prometheus text exposition.
+//!
+//! Counters and histograms are kept in a registry local to the component
+//! instance. The `routes!` router records every request automatically:
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `mik_http_requests_total` | counter | `method`, `status` (`2xx`, `4xx`, ...) |
+//! | `mik_http_request_duration_seconds` | histogram | `method` |
+//!
+//! Add `#![config(metrics)]` to serve the registry at `GET /metrics`
+//! (or `metrics = "/path"` for another route) in the Prometheus text format.
+//!
+//! Runtimes that create a fresh instance per request start each one with an
+//! empty registry; scrape metrics from runtimes that reuse instances.
+//!
+//! # Examples
+//!
+//! ```
+//! use mik_sdk::metrics;
+//!
+//! metrics::increment("orders_created_total", &[("plan", "pro")]);
+//! metrics::observe("payment_duration_seconds", &[], 0.042);
+//!
+//! let text = metrics::render();
+//! assert!(text.contains(r#"orders_created_total{plan="pro"} 1"#));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Histogram bucket upper bounds in seconds, matching the Prometheus client defaults.
+pub const DEFAULT_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Request counter recorded by the router.
+pub const REQUESTS_TOTAL: &str = "mik_http_requests_total";
+
+/// Request latency histogram recorded by the router.
+pub const REQUEST_DURATION_SECONDS: &str = "mik_http_request_duration_seconds";
+
+/// Metric name plus sorted label pairs.
+type SeriesKey = (String, Vec<(String, String)>);
+
+#[derive(Debug)]
+enum Metric {
+    Counter(f64),
+    Histogram {
+        /// Cumulative counts, one per [`DEFAULT_BUCKETS`] bound.
+        buckets: [u64; DEFAULT_BUCKETS.len()],
+        sum: f64,
+        count: u64,
+    },
+}
+
+static REGISTRY: Mutex<BTreeMap<SeriesKey, Metric>> = Mutex::new(BTreeMap::new());
+
+fn series_key(name: &str, labels: &[(&str, &str)]) -> SeriesKey {
+    let mut labels: Vec<(String, String)> = labels
+        .iter()
+        .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+        .collect();
+    labels.sort();
+    (name.to_string(), labels)
+}
+
+fn with_registry<T>(f: impl FnOnce(&mut BTreeMap<SeriesKey, Metric>) -> T) -> T {
+    // A panic while recording leaves the map consistent; keep using it
+    let mut registry = REGISTRY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    f(&mut registry)
+}
+
+/// Increment a counter by one.
+pub fn increment(name: &str, labels: &[(&str, &str)]) {
+    add(name, labels, 1.0);
+}
+
+/// Add a non-negative value to a counter.
+///
+/// Negative or non-finite values are ignored, as are names already used
+/// by a histogram.
+pub fn add(name: &str, labels: &[(&str, &str)], value: f64) {
+    if !value.is_finite() || value < 0.0 {
+        return;
+    }
+    with_registry(|registry| {
+        if let Metric::Counter(total) = registry
+            .entry(series_key(name, labels))
+            .or_insert(Metric::Counter(0.0))
+        {
+            *total += value;
+        }
+    });
+}
+
+/// Record a histogram observation, in seconds for latencies.
+///
+/// Uses [`DEFAULT_BUCKETS`]. Non-finite values are ignored, as are names
+/// already used by a counter.
+pub fn observe(name: &str, labels: &[(&str, &str)], value: f64) {
+    if !value.is_finite() {
+        return;
+    }
+    with_registry(|registry| {
+        let metric = registry
+            .entry(series_key(name, labels))
+            .or_insert(Metric::Histogram {
+                buckets: [0; DEFAULT_BUCKETS.len()],
+                sum: 0.0,
+                count: 0,
+            });
+        if let Metric::Histogram {
+            buckets,
+            sum,
+            count,
+        } = metric
+        {
+            for (bucket, bound) in buckets.iter_mut().zip(DEFAULT_BUCKETS) {
+                if value <= bound {
+                    *bucket += 1;
+                }
+            }
+            *sum += value;
+            *count += 1;
+        }
+    });
+}
+
+/// Render every metric in the Prometheus text exposition format (0.0.4).
+#[must_use]
+pub fn render() -> String {
+    with_registry(|registry| {
+        let mut out = String::new();
+        let mut current: Option<&str> = None;
+        for ((name, labels), metric) in registry.iter() {
+            if current != Some(name.as_str()) {
+                let kind = match metric {
+                    Metric::Counter(_) => "counter",
+                    Metric::Histogram { .. } => "histogram",
+                };
+                let _ = writeln!(out, "# TYPE {name} {kind}");
+                current = Some(name);
+            }
+            match metric {
+                Metric::Counter(total) => {
+                    let _ = writeln!(out, "{name}{} {total}", format_labels(labels, None));
+                },
+                Metric::Histogram {
+                    buckets,
+                    sum,
+                    count,
+                } => {
+                    for (bucket, bound) in buckets.iter().zip(DEFAULT_BUCKETS) {
+                        let le = bound.to_string();
+                        let _ = writeln!(
+                            out,
+                            "{name}_bucket{} {bucket}",
+                            format_labels(labels, Some(&le))
+                        );
+                    }
+                    let _ = writeln!(
+                        out,
+                        "{name}_bucket{} {count}",
+                        format_labels(labels, Some("+Inf"))
+                    );
+                    let _ = writeln!(out, "{name}_sum{} {sum}", format_labels(labels, None));
+                    let _ = writeln!(out, "{name}_count{} {count}", format_labels(labels, None));
+                },
+            }
+        }
+        out
+    })
+}
+
+/// Format `{key="value",...}`, with an optional trailing `le` label.
+fn format_labels(labels: &[(String, String)], le: Option<&str>) -> String {
+    let pairs = labels
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .chain(le.map(|le| ("le", le)));
+    let mut out = String::new();
+    for (i, (key, value)) in pairs.enumerate() {
+        out.push(if i == 0 { '{' } else { ',' });
+        out.push_str(key);
+        out.push_str("=\"");
+        for c in value.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+    if !out.is_empty() {
+        out.push('}');
+    }
+    out
+}
+
+/// The status class label for a status code, e.g. `"4xx"` for 404.
+#[must_use]
+pub const fn status_class(status: u16) -> &'static str {
+    match status {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// Record one handled request.
+///
+/// Called by code generated by `routes!` with the start time from
+/// [`time::now_millis`](crate::time::now_millis).
+#[doc(hidden)]
+#[allow(clippy::cast_precision_loss)] // Latencies are far below 2^52 ms
+pub fn __record_request(method: &str, status: u16, started_millis: u64) {
+    let elapsed = crate::time::now_millis().saturating_sub(started_millis);
+    increment(
+        REQUESTS_TOTAL,
+        &[("method", method), ("status", status_class(status))],
+    );
+    observe(
+        REQUEST_DURATION_SECONDS,
+        &[("method", method)],
+        elapsed as f64 / 1000.0,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The registry is shared by all tests; each test uses its own names.
+
+    #[test]
+    fn test_counter_render() {
+        increment("test_counter_total", &[("b", "2"), ("a", "1")]);
+        add("test_counter_total", &[("a", "1"), ("b", "2")], 2.5);
+        add("test_counter_total", &[("a", "1"), ("b", "2")], -1.0);
+        let text = render();
+        assert!(text.contains("# TYPE test_counter_total counter\n"));
+        assert!(text.contains("test_counter_total{a=\"1\",b=\"2\"} 3.5\n"));
+    }
+
+    #[test]
+    fn test_unlabelled_counter() {
+        increment("test_plain_total", &[]);
+        assert!(render().contains("\ntest_plain_total 1\n"));
+    }
+
+    #[test]
+    fn test_histogram_render() {
+        observe("test_latency_seconds", &[("route", "/x")], 0.5);
+        observe("test_latency_seconds", &[("route", "/x")], 2.0);
+        observe("test_latency_seconds", &[("route", "/x")], 60.0);
+        let text = render();
+        assert!(text.contains("# TYPE test_latency_seconds histogram\n"));
+        assert!(text.contains("test_latency_seconds_bucket{route=\"/x\",le=\"0.25\"} 0\n"));
+        assert!(text.contains("test_latency_seconds_bucket{route=\"/x\",le=\"0.5\"} 1\n"));
+        assert!(text.contains("test_latency_seconds_bucket{route=\"/x\",le=\"5\"} 2\n"));
+        assert!(text.contains("test_latency_seconds_bucket{route=\"/x\",le=\"+Inf\"} 3\n"));
+        assert!(text.contains("test_latency_seconds_sum{route=\"/x\"} 62.5\n"));
+        assert!(text.contains("test_latency_seconds_count{route=\"/x\"} 3\n"));
+    }
+
+    #[test]
+    fn test_kind_mismatch_is_ignored() {
+        increment("test_mixed", &[]);
+        observe("test_mixed", &[], 1.0);
+        let text = render();
+        assert!(text.contains("\ntest_mixed 1\n"));
+        assert!(!text.contains("test_mixed_count"));
+    }
+
+    #[test]
+    fn test_label_escaping() {
+        increment("test_escaped_total", &[("path", "a\"b\\c\nd")]);
+        assert!(render().contains(r#"test_escaped_total{path="a\"b\\c\nd"} 1"#));
+    }
+
+    #[test]
+    fn test_status_class() {
+        assert_eq!(status_class(101), "1xx");
+        assert_eq!(status_class(204), "2xx");
+        assert_eq!(status_class(308), "3xx");
+        assert_eq!(status_class(429), "4xx");
+        assert_eq!(status_class(503), "5xx");
+    }
+
+    #[test]
+    fn test_record_request() {
+        __record_request("PATCH", 201, crate::time::now_millis());
+        let text = render();
+        assert!(text.contains("mik_http_requests_total{method=\"PATCH\",status=\"2xx\"} 1\n"));
+        assert!(text.contains("mik_http_request_duration_seconds_count{method=\"PATCH\"} 1\n"));
+    }
+}
@@ -0,0 +1,186 @@
+//! Building blocks for reusable middleware.
+//!
+//! Hooks registered with `#[before(...)]` and `#[after(...)]` in `routes!`
+//! return the handler's own `Response` type, which is generated from WIT in
+//! each handler crate. [`HttpResponse`] lets library code build and inspect
+//! that type anyway: `routes!` implements it for `handler::Response`, so
+//! middleware written against the trait plugs into any handler.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::middleware::{HttpResponse, problem};
+//!
+//! // Works as #[before(require_json)] in any routes! block
+//! pub fn require_json<R: HttpResponse>(req: &Request) -> Option<R> {
+//!     (!req.is_json()).then(|| problem(415, "Expected application/json"))
+//! }
+//!
+//! // Works as #[after(no_store)]
+//! pub fn no_store<R: HttpResponse>(_req: &Request, mut res: R) -> R {
+//!     res.headers_mut().push(("cache-control".into(), "no-store".into()));
+//!     res
+//! }
+//! ```
+
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_PROBLEM_JSON, status_title};
+use crate::json;
+
+/// A handler response that middleware can build and inspect.
+///
+/// Implemented by `routes!` for the generated `handler::Response`; there is
+/// no need to implement it by hand.
+pub trait HttpResponse: Sized {
+    /// Build a response from its parts.
+    fn from_parts(status: u16, headers: Vec<(String, String)>, body: Option<Vec<u8>>) -> Self;
+
+    /// HTTP status code.
+    fn status(&self) -> u16;
+
+    /// Response headers.
+    fn headers(&self) -> &[(String, String)];
+
+    /// Mutable response headers, e.g. to add headers in an after hook.
+    fn headers_mut(&mut self) -> &mut Vec<(String, String)>;
+
+    /// Response body, if any.
+    fn body(&self) -> Option<&[u8]>;
+}
+
+/// Build an RFC 7807 problem response, matching the `error!` macro output.
+#[must_use]
+pub fn problem<R: HttpResponse>(status: u16, detail: &str) -> R {
+    R::from_parts(
+        status,
+        vec![(
+            HEADER_CONTENT_TYPE.to_string(),
+            MIME_PROBLEM_JSON.to_string(),
+        )],
+        Some(
+            json::obj()
+                .set("type", json::str("about:blank"))
+                .set("title", json::str(status_title(status)))
+                .set("status", json::int(i64::from(status)))
+                .set("detail", json::str(detail))
+                .to_bytes(),
+        ),
+    )
+}
+
+/// Build the `422` problem response for failed validation.
+///
+/// Like [`problem`], with the violation messages joined as `detail` and an
+/// `errors` extension listing each violation (see
+/// [`ValidationErrors`](crate::typed::ValidationErrors)).
+#[must_use]
+pub fn validation_problem<R: HttpResponse>(errors: &crate::typed::ValidationErrors) -> R {
+    let status = 422;
+    R::from_parts(
+        status,
+        vec![(
+            HEADER_CONTENT_TYPE.to_string(),
+            MIME_PROBLEM_JSON.to_string(),
+        )],
+        Some(
+            json::obj()
+                .set("type", json::str("about:blank"))
+                .set("title", json::str(status_title(status)))
+                .set("status", json::int(i64::from(status)))
+                .set("detail", json::str(errors.to_string()))
+                .set("errors", json::ToJson::to_json(errors))
+                .to_bytes(),
+        ),
+    )
+}
+
+/// Minimal [`HttpResponse`] for unit tests of middleware.
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TestResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+#[cfg(test)]
+impl HttpResponse for TestResponse {
+    fn from_parts(status: u16, headers: Vec<(String, String)>, body: Option<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    fn headers_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.headers
+    }
+
+    fn body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_response() {
+        let res: TestResponse = problem(403, "Invalid API key");
+        assert_eq!(res.status(), 403);
+        assert_eq!(
+            res.headers(),
+            &[(
+                "content-type".to_string(),
+                "application/problem+json".to_string()
+            )]
+        );
+        let body = json::try_parse(res.body().unwrap()).unwrap();
+        assert_eq!(body.path_str(&["title"]), Some("Forbidden".to_string()));
+        assert_eq!(body.path_int(&["status"]), Some(403));
+        assert_eq!(
+            body.path_str(&["detail"]),
+            Some("Invalid API key".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validation_problem_response() {
+        use crate::typed::{ValidationError, ValidationErrors};
+
+        let mut errors = ValidationErrors::new();
+        errors.push_rejected(ValidationError::max("limit", 100), json::int(500));
+        errors.push(ValidationError::custom(
+            "sort",
+            "required",
+            "`sort` is required",
+        ));
+        let res: TestResponse = validation_problem(&errors);
+        assert_eq!(res.status(), 422);
+
+        let body = json::try_parse(res.body().unwrap()).unwrap();
+        assert_eq!(
+            body.path_str(&["title"]),
+            Some("Unprocessable Entity".to_string())
+        );
+        assert_eq!(
+            body.path_str(&["detail"]),
+            Some("`limit` must be at most 100; `sort` is required".to_string())
+        );
+        let errors = body.get("errors");
+        assert_eq!(errors.len(), Some(2));
+        assert_eq!(errors.at(0).path_str(&["code"]), Some("max".to_string()));
+        assert_eq!(errors.at(0).path_int(&["rejected_value"]), Some(500));
+        assert_eq!(errors.at(1).path_str(&["field"]), Some("sort".to_string()));
+        assert!(errors.at(1).get("rejected_value").is_null());
+    }
+}
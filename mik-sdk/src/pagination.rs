@@ -0,0 +1,517 @@
+//! Offset and cursor pagination for list endpoints.
+//!
+//! [`PaginationQuery`] reads `limit` plus either `offset` or an `after` /
+//! `before` cursor from the query string, ready to hand to mik-sql.
+//! [`Page`] wraps a page of items and renders both the JSON body and an
+//! RFC 5988 `Link` header pointing at the next and previous pages.
+//!
+//! Cursors use the same encoding as `mik_sql::Cursor` (unpadded base64url
+//! of a JSON object), so [`encode_cursor`] and [`decode_cursor`] interoperate
+//! with `sql_read!`'s `after:` / `before:`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use mik_sdk::pagination::{Page, PaginationQuery};
+//!
+//! routes! {
+//!     GET "/users" => list_users(query: PaginationQuery),
+//! }
+//!
+//! fn list_users(query: PaginationQuery, req: &Request) -> Response {
+//!     let (sql, params) = sql_read!(users {
+//!         select: [id, name],
+//!         order: [id],
+//!         limit: query.limit,
+//!         offset: query.offset,
+//!     });
+//!     let users: Vec<User> = run(sql, params);
+//!
+//!     // {"items":[...],"has_next":true,"has_prev":false}
+//!     // Link: </users?limit=20&offset=20>; rel="next"
+//!     Page::offset(users, &query).to_response(req)
+//! }
+//! ```
+
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_JSON};
+use crate::encoding::{base64url_decode, base64url_encode, percent_encode};
+use crate::json::{self, JsonValue, ToJson};
+use crate::middleware::HttpResponse;
+use crate::request::{Request, url_decode};
+use crate::response::IntoResponse;
+use crate::typed::{
+    FromQuery, OpenApiSchema, ParseError, Validate, ValidationError, ValidationErrors,
+};
+
+/// Page size when the request doesn't set `limit`.
+pub const DEFAULT_LIMIT: u32 = 20;
+
+/// Largest accepted `limit`.
+pub const MAX_LIMIT: u32 = 100;
+
+/// Longest accepted cursor, matching `mik_sql::Cursor`.
+const MAX_CURSOR_SIZE: usize = 4 * 1024;
+
+/// Encode a cursor position as an opaque, URL-safe string.
+///
+/// # Example
+///
+/// ```
+/// use mik_sdk::json;
+/// use mik_sdk::pagination::{decode_cursor, encode_cursor};
+///
+/// let cursor = encode_cursor(&json::obj().set("id", json::int(42)));
+/// assert_eq!(cursor, "eyJpZCI6NDJ9");
+/// assert_eq!(decode_cursor(&cursor).unwrap().path_int(&["id"]), Some(42));
+/// ```
+#[must_use]
+pub fn encode_cursor(position: &JsonValue) -> String {
+    base64url_encode(&position.to_bytes())
+}
+
+/// Decode a cursor from [`encode_cursor`] or `mik_sql::Cursor::encode`.
+///
+/// Returns `None` for oversized, malformed or non-object cursors.
+#[must_use]
+pub fn decode_cursor(cursor: &str) -> Option<JsonValue> {
+    if cursor.len() > MAX_CURSOR_SIZE {
+        return None;
+    }
+    let bytes = base64url_decode(cursor)?;
+    if bytes.trim_ascii_start().first() != Some(&b'{') {
+        return None;
+    }
+    json::try_parse(&bytes)
+}
+
+/// Pagination parameters from the query string.
+///
+/// | Parameter | Default | |
+/// |-----------|---------|---|
+/// | `limit`   | [`DEFAULT_LIMIT`] | at most [`MAX_LIMIT`] |
+/// | `offset`  | `0` | offset pagination |
+/// | `after`   | none | cursor pagination, forward |
+/// | `before`  | none | cursor pagination, backward |
+///
+/// `offset`, `after` and `before` are mutually exclusive. Use it as a
+/// `query:` input in `routes!`; it validates like a derived `Query`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PaginationQuery {
+    /// Maximum number of items to return.
+    pub limit: u32,
+    /// Number of items to skip (offset pagination).
+    pub offset: u32,
+    /// Return items after this cursor.
+    pub after: Option<String>,
+    /// Return items before this cursor.
+    pub before: Option<String>,
+}
+
+impl Default for PaginationQuery {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_LIMIT,
+            offset: 0,
+            after: None,
+            before: None,
+        }
+    }
+}
+
+impl PaginationQuery {
+    /// Set the page size.
+    #[must_use]
+    pub const fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the offset.
+    #[must_use]
+    pub const fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Whether the request uses cursor pagination.
+    #[must_use]
+    pub const fn is_cursor(&self) -> bool {
+        self.after.is_some() || self.before.is_some()
+    }
+
+    /// Every violated constraint.
+    fn violations(&self) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        if self.limit < 1 {
+            errors.push_rejected(ValidationError::min("limit", 1), json::int(0));
+        } else if self.limit > MAX_LIMIT {
+            errors.push_rejected(
+                ValidationError::max("limit", i64::from(MAX_LIMIT)),
+                json::int(i64::from(self.limit)),
+            );
+        }
+        if self.after.is_some() && self.before.is_some() {
+            errors.push(ValidationError::custom(
+                "before",
+                "exclusive",
+                "`after` and `before` cannot be combined",
+            ));
+        }
+        if self.offset > 0 && self.is_cursor() {
+            errors.push(ValidationError::custom(
+                "offset",
+                "exclusive",
+                "`offset` cannot be combined with a cursor",
+            ));
+        }
+        errors
+    }
+}
+
+impl FromQuery for PaginationQuery {
+    fn from_query(params: &[(String, String)]) -> Result<Self, ParseError> {
+        let get = |name: &str| params.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+        let number = |name: &str| {
+            get(name)
+                .map(|v| {
+                    v.parse::<u32>()
+                        .map_err(|_| ParseError::invalid_format(name, v))
+                })
+                .transpose()
+        };
+        let cursor = |name: &str| get(name).filter(|v| !v.is_empty()).cloned();
+
+        Ok(Self {
+            limit: number("limit")?.unwrap_or(DEFAULT_LIMIT),
+            offset: number("offset")?.unwrap_or(0),
+            after: cursor("after"),
+            before: cursor("before"),
+        })
+    }
+}
+
+impl Validate for PaginationQuery {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.violations()
+            .iter()
+            .next()
+            .map_or(Ok(()), |violation| Err(violation.error.clone()))
+    }
+
+    fn validate_all(&self) -> Result<(), ValidationErrors> {
+        self.violations().into_result()
+    }
+}
+
+impl OpenApiSchema for PaginationQuery {
+    fn openapi_schema() -> &'static str {
+        r#"{"type":"object","properties":{"limit":{"type":"integer","minimum":1,"maximum":100,"default":20},"offset":{"type":"integer","minimum":0,"default":0},"after":{"type":"string"},"before":{"type":"string"}}}"#
+    }
+
+    fn schema_name() -> &'static str {
+        "PaginationQuery"
+    }
+
+    fn openapi_query_params() -> &'static str {
+        r#"[{"in":"query","name":"limit","required":false,"schema":{"type":"integer","minimum":1,"maximum":100,"default":20}},{"in":"query","name":"offset","required":false,"schema":{"type":"integer","minimum":0,"default":0}},{"in":"query","name":"after","required":false,"schema":{"type":"string"},"description":"Cursor of the last item seen"},{"in":"query","name":"before","required":false,"schema":{"type":"string"},"description":"Cursor of the first item seen"}]"#
+    }
+}
+
+/// Where the neighbouring pages start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Position {
+    Offset(u32),
+    Cursor {
+        next: Option<String>,
+        prev: Option<String>,
+    },
+}
+
+/// One page of a list response.
+///
+/// Serializes to `{"items": [...], "has_next", "has_prev"}` plus
+/// `next_cursor`, `prev_cursor` and `total` when known. Return it from a
+/// handler for the body alone, or call [`to_response`](Self::to_response)
+/// to add the `Link` header.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Page<T> {
+    /// Items on this page.
+    pub items: Vec<T>,
+    /// Requested page size.
+    pub limit: u32,
+    /// Whether a next page exists.
+    pub has_next: bool,
+    /// Whether a previous page exists.
+    pub has_prev: bool,
+    /// Total number of items, if counted.
+    pub total: Option<u64>,
+    position: Position,
+}
+
+impl<T> Page<T> {
+    /// A page fetched with `limit` / `offset`.
+    ///
+    /// A full page is assumed to have a successor; call
+    /// [`with_total`](Self::with_total) to decide exactly.
+    #[must_use]
+    pub const fn offset(items: Vec<T>, query: &PaginationQuery) -> Self {
+        Self {
+            has_next: items.len() >= query.limit as usize,
+            has_prev: query.offset > 0,
+            items,
+            limit: query.limit,
+            total: None,
+            position: Position::Offset(query.offset),
+        }
+    }
+
+    /// A page fetched with a cursor, with the cursors of its last and first
+    /// items (typically from `mik_sql::Cursor::encode` or [`encode_cursor`]).
+    ///
+    /// Pass `None` for `next` on the last page and for `prev` on the first.
+    #[must_use]
+    pub const fn cursor(
+        items: Vec<T>,
+        query: &PaginationQuery,
+        next: Option<String>,
+        prev: Option<String>,
+    ) -> Self {
+        Self {
+            has_next: next.is_some(),
+            has_prev: prev.is_some(),
+            items,
+            limit: query.limit,
+            total: None,
+            position: Position::Cursor { next, prev },
+        }
+    }
+
+    /// Record the total item count. For offset pages this also decides
+    /// whether a next page exists.
+    #[must_use]
+    pub fn with_total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        if let Position::Offset(offset) = self.position {
+            self.has_next = u64::from(offset) + (self.items.len() as u64) < total;
+        }
+        self
+    }
+
+    /// The `Link` header value for the neighbouring pages of `req`, or
+    /// `None` if there are none.
+    ///
+    /// Links reuse the request path and keep its other query parameters.
+    #[must_use]
+    pub fn link_header(&self, req: &Request) -> Option<String> {
+        let (next, prev) = match &self.position {
+            Position::Offset(offset) => (
+                self.has_next
+                    .then(|| ("offset", offset.saturating_add(self.limit).to_string())),
+                self.has_prev
+                    .then(|| ("offset", offset.saturating_sub(self.limit).to_string())),
+            ),
+            Position::Cursor { next, prev } => (
+                next.clone().map(|cursor| ("after", cursor)),
+                prev.clone().map(|cursor| ("before", cursor)),
+            ),
+        };
+
+        let links: Vec<String> = [(next, "next"), (prev, "prev")]
+            .into_iter()
+            .filter_map(|(target, rel)| {
+                let (key, value) = target?;
+                Some(format!(
+                    "<{}>; rel=\"{rel}\"",
+                    self.page_url(req, key, &value)
+                ))
+            })
+            .collect();
+        (!links.is_empty()).then(|| links.join(", "))
+    }
+
+    /// `req`'s URL with the pagination parameters replaced.
+    fn page_url(&self, req: &Request, key: &str, value: &str) -> String {
+        let path = req.path_without_query();
+        let query = req.path().split_once('?').map_or("", |(_, query)| query);
+        let mut params: Vec<String> = query
+            .split('&')
+            .filter(|pair| {
+                let name = pair.split('=').next().unwrap_or_default();
+                let name = url_decode(name).unwrap_or_default();
+                !pair.is_empty()
+                    && !matches!(name.as_str(), "limit" | "offset" | "after" | "before")
+            })
+            .map(str::to_string)
+            .collect();
+        params.push(format!("limit={}", self.limit));
+        params.push(format!("{key}={}", percent_encode(value.as_bytes())));
+        format!("{path}?{}", params.join("&"))
+    }
+}
+
+impl<T: ToJson> Page<T> {
+    /// Build the `200` JSON response with its `Link` header.
+    #[must_use]
+    pub fn to_response<R: HttpResponse>(&self, req: &Request) -> R {
+        let mut headers = vec![(HEADER_CONTENT_TYPE.to_string(), MIME_JSON.to_string())];
+        if let Some(link) = self.link_header(req) {
+            headers.push(("link".to_string(), link));
+        }
+        R::from_parts(200, headers, Some(self.to_json().to_bytes()))
+    }
+}
+
+impl<T: ToJson> ToJson for Page<T> {
+    fn to_json(&self) -> JsonValue {
+        let mut value = json::obj()
+            .set("items", self.items.to_json())
+            .set("has_next", json::bool(self.has_next))
+            .set("has_prev", json::bool(self.has_prev));
+        if let Position::Cursor { next, prev } = &self.position {
+            if let Some(next) = next {
+                value = value.set("next_cursor", json::str(next));
+            }
+            if let Some(prev) = prev {
+                value = value.set("prev_cursor", json::str(prev));
+            }
+        }
+        if let Some(total) = self.total {
+            value = value.set("total", json::int(i64::try_from(total).unwrap_or(i64::MAX)));
+        }
+        value
+    }
+}
+
+/// Without the request, the response has no `Link` header.
+impl<R: HttpResponse, T: ToJson> IntoResponse<R> for Page<T> {
+    fn into_response(self) -> R {
+        crate::response::json(200, &self.to_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+    use crate::request::Method;
+    use std::collections::HashMap;
+
+    fn request(path: &str) -> Request {
+        Request::new(Method::Get, path.to_string(), vec![], None, HashMap::new())
+    }
+
+    fn query(pairs: &[(&str, &str)]) -> Result<PaginationQuery, ParseError> {
+        let params: Vec<(String, String)> = pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        PaginationQuery::from_query(&params)
+    }
+
+    #[test]
+    fn test_query_defaults_and_parsing() {
+        assert_eq!(query(&[]).unwrap(), PaginationQuery::default());
+
+        let q = query(&[("limit", "50"), ("offset", "100")]).unwrap();
+        assert_eq!((q.limit, q.offset), (50, 100));
+        assert!(!q.is_cursor());
+
+        let q = query(&[("after", "abc"), ("before", "")]).unwrap();
+        assert_eq!(q.after.as_deref(), Some("abc"));
+        assert_eq!(q.before, None);
+
+        assert!(query(&[("limit", "ten")]).is_err());
+        assert!(query(&[("offset", "-1")]).is_err());
+    }
+
+    #[test]
+    fn test_query_validation() {
+        assert!(query(&[("limit", "100")]).unwrap().validate_all().is_ok());
+
+        let errors = query(&[("limit", "0")])
+            .unwrap()
+            .validate_all()
+            .unwrap_err();
+        assert_eq!(errors.to_string(), "`limit` must be at least 1");
+
+        let errors = query(&[
+            ("limit", "500"),
+            ("offset", "5"),
+            ("after", "a"),
+            ("before", "b"),
+        ])
+        .unwrap()
+        .validate_all()
+        .unwrap_err();
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let position = json::obj()
+            .set("id", json::int(7))
+            .set("name", json::str("a b"));
+        let cursor = encode_cursor(&position);
+        assert!(!cursor.contains(['+', '/', '=']));
+        let decoded = decode_cursor(&cursor).unwrap();
+        assert_eq!(decoded.path_int(&["id"]), Some(7));
+        assert_eq!(decoded.path_str(&["name"]), Some("a b".to_string()));
+
+        assert!(decode_cursor("not base64!").is_none());
+        assert!(decode_cursor(&base64url_encode(b"[1,2]")).is_none());
+        assert!(decode_cursor(&"A".repeat(MAX_CURSOR_SIZE + 4)).is_none());
+    }
+
+    #[test]
+    fn test_offset_page_links() {
+        let q = query(&[("limit", "2"), ("offset", "4")]).unwrap();
+        let page = Page::offset(vec![1, 2], &q);
+        let req = request("/users?sort=name&limit=2&offset=4");
+        assert_eq!(
+            page.link_header(&req).unwrap(),
+            "</users?sort=name&limit=2&offset=6>; rel=\"next\", \
+             </users?sort=name&limit=2&offset=2>; rel=\"prev\""
+        );
+
+        // The total settles whether a next page exists
+        let page = Page::offset(vec![1, 2], &q).with_total(6);
+        assert!(!page.has_next);
+        assert_eq!(
+            page.link_header(&req).unwrap(),
+            "</users?sort=name&limit=2&offset=2>; rel=\"prev\""
+        );
+
+        let page = Page::offset(vec![1], &PaginationQuery::default());
+        assert_eq!(page.link_header(&request("/users")), None);
+    }
+
+    #[test]
+    fn test_cursor_page_response() {
+        let q = query(&[("after", "eyJpZCI6Mn0")]).unwrap();
+        let page = Page::cursor(
+            vec![json::str("c")],
+            &q,
+            Some("eyJpZCI6M30".to_string()),
+            Some("eyJpZCI6M30".to_string()),
+        );
+        let res: TestResponse = page.to_response(&request("/posts?after=eyJpZCI6Mn0"));
+        assert_eq!(res.status, 200);
+        assert_eq!(
+            res.headers[1],
+            (
+                "link".to_string(),
+                "</posts?limit=20&after=eyJpZCI6M30>; rel=\"next\", \
+                 </posts?limit=20&before=eyJpZCI6M30>; rel=\"prev\""
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            res.body.as_deref(),
+            Some(
+                &br#"{"has_next":true,"has_prev":true,"items":["c"],"next_cursor":"eyJpZCI6M30","prev_cursor":"eyJpZCI6M30"}"#[..]
+            )
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Password hashing with Argon2id (RFC 9106).
+//!
+//! Hashes are PHC strings (`$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`)
+//! that record their own parameters, so stored hashes keep verifying after
+//! the defaults change.
+//!
+//! ```ignore
+//! use mik_sdk::password;
+//!
+//! // Sign-up
+//! let stored = password::hash(&form.password);
+//!
+//! // Login
+//! guard!(password::verify(&form.password, &user.password_hash), 401, "Invalid credentials");
+//! if password::needs_rehash(&user.password_hash, password::Params::DEFAULT) {
+//!     save_hash(&user.id, &password::hash(&form.password));
+//! }
+//! ```
+//!
+//! Hashing uses the RustCrypto `argon2` crate, pure Rust and
+//! single-threaded, which is what a component gets anyway. [`Params::DEFAULT`] is the OWASP baseline (19 MiB,
+//! 2 passes) and takes tens of milliseconds in wasmtime; use
+//! [`Params::LOW_MEMORY`] when the instance memory limit is tight.
+
+use argon2::{Algorithm, Argon2, Version};
+
+use crate::crypto::constant_time_eq;
+use crate::encoding::{base64_decode, base64_encode};
+
+/// Argon2 version 1.3.
+const VERSION: u32 = 0x13;
+
+/// Salt length used by [`hash`], in bytes.
+const SALT_LEN: usize = 16;
+
+/// Hash length used by [`hash`], in bytes.
+const HASH_LEN: usize = 32;
+
+/// Most lanes Argon2 allows (`2^24 - 1`).
+const MAX_LANES: u32 = 0xFF_FFFF;
+
+/// Largest memory cost [`verify`] accepts, in KiB (256 MiB).
+const MAX_MEMORY_KIB: u32 = 256 * 1024;
+
+/// Largest iteration count [`verify`] accepts.
+const MAX_ITERATIONS: u32 = 64;
+
+/// Largest lane count [`verify`] accepts.
+const MAX_PARALLELISM: u32 = 16;
+
+/// Argon2id cost parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Params {
+    /// OWASP baseline: 19 MiB, 2 passes, 1 lane.
+    pub const DEFAULT: Self = Self::new(19 * 1024, 2, 1);
+
+    /// Same strength for tight memory limits: 7 MiB, 5 passes, 1 lane.
+    pub const LOW_MEMORY: Self = Self::new(7 * 1024, 5, 1);
+
+    /// Custom parameters.
+    ///
+    /// Values are raised to Argon2's minimums: one pass, one lane and
+    /// 8 KiB of memory per lane. Parallelism is capped at Argon2's maximum
+    /// of `2^24 - 1` lanes.
+    #[must_use]
+    pub const fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+        let parallelism = if parallelism == 0 {
+            1
+        } else if parallelism > MAX_LANES {
+            MAX_LANES
+        } else {
+            parallelism
+        };
+        let min_memory = parallelism.saturating_mul(8);
+        Self {
+            memory_kib: if memory_kib < min_memory {
+                min_memory
+            } else {
+                memory_kib
+            },
+            iterations: if iterations == 0 { 1 } else { iterations },
+            parallelism,
+        }
+    }
+
+    /// Memory cost in KiB.
+    #[must_use]
+    pub const fn memory_kib(&self) -> u32 {
+        self.memory_kib
+    }
+
+    /// Number of passes over memory.
+    #[must_use]
+    pub const fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Number of lanes.
+    #[must_use]
+    pub const fn parallelism(&self) -> u32 {
+        self.parallelism
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Hash a password with [`Params::DEFAULT`] and a random salt.
+#[must_use]
+pub fn hash(password: &str) -> String {
+    hash_with(password, Params::DEFAULT)
+}
+
+/// Hash a password with custom parameters and a random salt.
+#[must_use]
+pub fn hash_with(password: &str, params: Params) -> String {
+    let salt = crate::random::bytes(SALT_LEN);
+    // `Params` only holds values Argon2 accepts, and the salt and tag
+    // lengths are fixed
+    let tag = argon2id(password.as_bytes(), &salt, params, HASH_LEN)
+        .expect("Argon2 rejected valid parameters");
+    format!(
+        "$argon2id$v={VERSION}$m={},t={},p={}${}${}",
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        base64_unpadded(&salt),
+        base64_unpadded(&tag)
+    )
+}
+
+/// Check a password against a stored PHC hash.
+///
+/// Returns `false` for a wrong password and for hashes that are malformed,
+/// not Argon2id v1.3, or cost more than 256 MiB, 64 passes or 16 lanes to
+/// check.
+#[must_use]
+pub fn verify(password: &str, phc: &str) -> bool {
+    let Some(parsed) = Phc::parse(phc) else {
+        return false;
+    };
+    if parsed.params.memory_kib > MAX_MEMORY_KIB
+        || parsed.params.iterations > MAX_ITERATIONS
+        || parsed.params.parallelism > MAX_PARALLELISM
+    {
+        return false;
+    }
+    argon2id(
+        password.as_bytes(),
+        &parsed.salt,
+        parsed.params,
+        parsed.hash.len(),
+    )
+    .is_some_and(|tag| constant_time_eq(&tag, &parsed.hash))
+}
+
+/// Whether a stored hash should be recomputed with `params`, e.g. after
+/// raising the cost. Malformed hashes also need a rehash.
+#[must_use]
+pub fn needs_rehash(phc: &str, params: Params) -> bool {
+    Phc::parse(phc).is_none_or(|parsed| parsed.params != params)
+}
+
+/// A parsed `$argon2id$v=19$m=..,t=..,p=..$salt$hash` string.
+struct Phc {
+    params: Params,
+    salt: Vec<u8>,
+    hash: Vec<u8>,
+}
+
+impl Phc {
+    fn parse(phc: &str) -> Option<Self> {
+        let mut parts = phc.strip_prefix("$argon2id$")?.split('$');
+        if parts.next()? != format!("v={VERSION}") {
+            return None;
+        }
+        let (mut memory, mut iterations, mut parallelism) = (None, None, None);
+        for pair in parts.next()?.split(',') {
+            let (key, value) = pair.split_once('=')?;
+            let value: u32 = value.parse().ok()?;
+            match key {
+                "m" => memory = Some(value),
+                "t" => iterations = Some(value),
+                "p" => parallelism = Some(value),
+                _ => return None,
+            }
+        }
+        let params = Params::new(memory?, iterations?, parallelism?);
+        // Values `Params::new` would adjust are not valid Argon2 parameters
+        if params.memory_kib != memory?
+            || params.iterations != iterations?
+            || params.parallelism != parallelism?
+        {
+            return None;
+        }
+        let salt = base64_decode(parts.next()?)?;
+        let hash = base64_decode(parts.next()?)?;
+        if parts.next().is_some() || salt.len() < 8 || hash.len() < 4 {
+            return None;
+        }
+        Some(Self { params, salt, hash })
+    }
+}
+
+fn base64_unpadded(data: &[u8]) -> String {
+    base64_encode(data).trim_end_matches('=').to_string()
+}
+
+/// Argon2id v1.3 tag of `tag_len` bytes, or `None` if Argon2 rejects the
+/// salt or tag length.
+fn argon2id(password: &[u8], salt: &[u8], params: Params, tag_len: usize) -> Option<Vec<u8>> {
+    let params = argon2::Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(tag_len),
+    )
+    .ok()?;
+    let mut tag = vec![0; tag_len];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password, salt, &mut tag)
+        .ok()?;
+    Some(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_known_hashes() {
+        // Password "password", salt "somesalt", 32-byte tags
+        for stored in [
+            "$argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNGCd0BRW2kBt6fPZ2pPmyGwwChL8FGUhTOSSI",
+            "$argon2id$v=19$m=64,t=2,p=2$c29tZXNhbHQ$lDh0Fd+4TtGXdGWh6GJgc630K9Turh+qHdTiOh/2hZ8",
+        ] {
+            assert!(verify("password", stored), "{stored}");
+            assert!(!verify("passwore", stored), "{stored}");
+        }
+    }
+
+    #[test]
+    fn test_hash_and_verify() {
+        let params = Params::new(64, 1, 1);
+        let stored = hash_with("correct horse", params);
+        assert!(stored.starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+        assert!(verify("correct horse", &stored));
+        assert!(!verify("correct horsf", &stored));
+        assert_ne!(stored, hash_with("correct horse", params));
+    }
+
+    #[test]
+    fn test_needs_rehash() {
+        let stored = hash_with("pw", Params::new(64, 1, 1));
+        assert!(!needs_rehash(&stored, Params::new(64, 1, 1)));
+        assert!(needs_rehash(&stored, Params::new(64, 2, 1)));
+        assert!(needs_rehash("not a hash", Params::DEFAULT));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed() {
+        assert!(!verify("pw", ""));
+        assert!(!verify(
+            "pw",
+            "$argon2i$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$aGFzaA"
+        ));
+        assert!(!verify(
+            "pw",
+            "$argon2id$v=16$m=64,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g"
+        ));
+        assert!(!verify(
+            "pw",
+            "$argon2id$v=19$m=64,t=1$c2FsdHNhbHQ$aGFzaGhhc2g"
+        ));
+        assert!(!verify(
+            "pw",
+            "$argon2id$v=19$m=999999999,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g"
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_bad_parallelism() {
+        for p in ["0", "17", "16777216", "536870912", "4294967295"] {
+            let phc = format!("$argon2id$v=19$m=8,t=1,p={p}$c2FsdHNhbHQ$aGFzaGhhc2g");
+            assert!(!verify("pw", &phc), "p={p}");
+        }
+        // Lanes need 8 KiB each
+        assert!(!verify(
+            "pw",
+            "$argon2id$v=19$m=8,t=1,p=2$c2FsdHNhbHQ$aGFzaGhhc2g"
+        ));
+    }
+
+    #[test]
+    fn test_params_minimums() {
+        let params = Params::new(0, 0, 0);
+        assert_eq!(
+            (
+                params.memory_kib(),
+                params.iterations(),
+                params.parallelism()
+            ),
+            (8, 1, 1)
+        );
+        let params = Params::new(0, 1, u32::MAX);
+        assert_eq!(params.parallelism(), MAX_LANES);
+        assert_eq!(params.memory_kib(), MAX_LANES * 8);
+        assert_eq!(Params::default(), Params::DEFAULT);
+    }
+}
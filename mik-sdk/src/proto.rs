@@ -0,0 +1,151 @@
+//! Protobuf bodies with [`prost`] messages (requires the `prost` feature).
+//!
+//! `routes!` decodes `proto:` inputs from `application/x-protobuf` bodies,
+//! and handlers answer with [`Proto`] or [`response`]:
+//!
+//! ```ignore
+//! use mik_sdk::proto::Proto;
+//!
+//! routes! {
+//!     POST "/orders" => create_order(proto: CreateOrder),
+//! }
+//!
+//! fn create_order(input: CreateOrder, _req: &Request) -> Proto<Order> {
+//!     Proto(Order { id: 1, sku: input.sku })
+//! }
+//! ```
+//!
+//! Messages are the structs generated by `prost-build` (or written with
+//! `#[derive(prost::Message)]`). They carry no `OpenAPI` schema, so `proto:`
+//! bodies are documented as binary. The same messages also serve the binary
+//! codec of [`connect`](crate::connect) services.
+
+use crate::Request;
+use crate::connect::ProtoMessage;
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_PROTOBUF};
+use crate::middleware::HttpResponse;
+use crate::response::IntoResponse;
+use crate::typed::ParseError;
+
+pub use prost::Message;
+
+/// A protobuf response body: `200` with `Content-Type: application/x-protobuf`.
+///
+/// Use [`response`] for other statuses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Proto<M>(pub M);
+
+impl<R: HttpResponse, M: Message> IntoResponse<R> for Proto<M> {
+    fn into_response(self) -> R {
+        response(200, &self.0)
+    }
+}
+
+/// Decode a protobuf request body. An absent body is the empty message.
+///
+/// # Errors
+///
+/// Returns an error if the body is not a valid encoding of `M`.
+pub fn from_request<M: Message + Default>(req: &Request) -> Result<M, ParseError> {
+    decode(req.body().unwrap_or_default())
+}
+
+/// Decode a protobuf message.
+///
+/// # Errors
+///
+/// Returns an error if `bytes` is not a valid encoding of `M`.
+pub fn decode<M: Message + Default>(bytes: &[u8]) -> Result<M, ParseError> {
+    M::decode(bytes).map_err(|e| ParseError::custom("", format!("invalid protobuf body: {e}")))
+}
+
+/// Build a protobuf response with the given status.
+#[must_use]
+pub fn response<R: HttpResponse, M: Message>(status: u16, message: &M) -> R {
+    R::from_parts(
+        status,
+        vec![(HEADER_CONTENT_TYPE.to_string(), MIME_PROTOBUF.to_string())],
+        Some(message.encode_to_vec()),
+    )
+}
+
+impl<M: Message + Default> ProtoMessage for M {
+    fn decode_proto(bytes: &[u8]) -> Result<Self, String> {
+        M::decode(bytes).map_err(|e| e.to_string())
+    }
+
+    fn encode_proto(&self) -> Vec<u8> {
+        self.encode_to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+    use crate::request::Method;
+    use std::collections::HashMap;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Order {
+        #[prost(uint64, tag = "1")]
+        id: u64,
+        #[prost(string, tag = "2")]
+        sku: String,
+    }
+
+    fn order() -> Order {
+        Order {
+            id: 7,
+            sku: "mik-1".to_string(),
+        }
+    }
+
+    fn request(body: Option<Vec<u8>>) -> Request {
+        Request::new(
+            Method::Post,
+            "/orders".to_string(),
+            vec![(HEADER_CONTENT_TYPE.to_string(), MIME_PROTOBUF.to_string())],
+            body,
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let res: TestResponse = Proto(order()).into_response();
+        assert_eq!(res.status, 200);
+        assert_eq!(res.headers[0].1, MIME_PROTOBUF);
+
+        let req = request(res.body);
+        assert_eq!(from_request::<Order>(&req).unwrap(), order());
+    }
+
+    #[test]
+    fn test_empty_body() {
+        assert_eq!(
+            from_request::<Order>(&request(None)).unwrap(),
+            Order::default()
+        );
+    }
+
+    #[test]
+    fn test_invalid_body() {
+        let err = from_request::<Order>(&request(Some(vec![0x0a, 0xff]))).unwrap_err();
+        assert!(err.to_string().contains("invalid protobuf body"));
+    }
+
+    #[test]
+    fn test_response_status() {
+        let res: TestResponse = response(201, &order());
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body, Some(order().encode_to_vec()));
+    }
+
+    #[test]
+    fn test_connect_codec() {
+        let bytes = order().encode_proto();
+        assert_eq!(Order::decode_proto(&bytes).unwrap(), order());
+        assert!(Order::decode_proto(&[0x0a, 0xff]).is_err());
+    }
+}
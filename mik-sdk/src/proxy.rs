@@ -0,0 +1,350 @@
+//! Forwarding requests to an upstream service.
+//!
+//! A [`Proxy`] turns a handler into a lightweight gateway: it forwards the
+//! incoming method, path suffix, query, headers and body to an upstream URL
+//! with the outbound [`http_client`](crate::http_client) and answers with the
+//! upstream status, headers and body.
+//!
+//! ```ignore
+//! routes! {
+//!     GET "/users" => users_proxy,
+//!     GET "/users/{id}" => users_proxy,
+//!     POST "/users" => users_proxy,
+//! }
+//!
+//! fn users_proxy(req: &Request) -> Response {
+//!     // GET /users/42?full=1 -> GET http://users.internal/v2/42?full=1
+//!     Proxy::new("http://users.internal/v2")
+//!         .strip_prefix("/users")
+//!         .header("x-api-key", USERS_API_KEY)
+//!         .timeout_ms(5_000)
+//!         .forward(req)
+//! }
+//! ```
+//!
+//! # Headers
+//!
+//! Hop-by-hop headers (`Connection`, `Keep-Alive`, `Transfer-Encoding`,
+//! `Upgrade`, ... and any header listed in `Connection`) only apply to a
+//! single connection and are stripped in both directions, see
+//! [`strip_hop_by_hop`]. `Host` is replaced by the upstream authority and
+//! kept as `X-Forwarded-Host`.
+//!
+//! # Errors
+//!
+//! When the upstream cannot be reached the proxy answers with an RFC 7807
+//! problem: `504` for timeouts, `502` otherwise. Upstream error statuses are
+//! passed through unchanged.
+//!
+//! # Streaming
+//!
+//! [`Proxy::forward`] registers the upstream body as a streaming response
+//! (see [`stream`](crate::stream)), so large downloads are relayed chunk by
+//! chunk instead of being buffered. [`Proxy::forward_with`] takes a custom
+//! sender, like [`ClientRequest::send_with`], and returns a buffered response.
+
+use crate::Request;
+use crate::http_client::{ClientRequest, Error, Response};
+use crate::middleware::{HttpResponse, problem};
+
+/// Headers that only apply to a single connection (RFC 9110 Section 7.6.1).
+pub const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Remove hop-by-hop headers, including those named in `Connection`.
+#[must_use]
+pub fn strip_hop_by_hop(headers: &[(String, String)]) -> Vec<(String, String)> {
+    let listed: Vec<String> = headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("connection"))
+        .flat_map(|(_, value)| value.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+    headers
+        .iter()
+        .filter(|(name, _)| {
+            let name = name.to_ascii_lowercase();
+            !HOP_BY_HOP_HEADERS.contains(&name.as_str()) && !listed.contains(&name)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Forwards requests to an upstream base URL, see the [module docs](self).
+#[derive(Debug, Clone)]
+#[must_use = "call .forward() or .forward_with() to send the request"]
+pub struct Proxy {
+    upstream: String,
+    strip_prefix: Option<String>,
+    headers: Vec<(String, String)>,
+    timeout_ms: Option<u64>,
+}
+
+impl Proxy {
+    /// Forward to `upstream`, a base URL such as `http://users.internal/v2`.
+    ///
+    /// The forwarded path is appended to the base URL path.
+    pub fn new(upstream: &str) -> Self {
+        Self {
+            upstream: upstream.trim_end_matches('/').to_string(),
+            strip_prefix: None,
+            headers: Vec::new(),
+            timeout_ms: None,
+        }
+    }
+
+    /// Remove a leading path prefix before forwarding.
+    ///
+    /// The prefix only matches whole segments: `/api` strips `/api/users`
+    /// to `/users` and `/api` to `/`, but leaves `/apis` alone.
+    pub fn strip_prefix(mut self, prefix: &str) -> Self {
+        self.strip_prefix = Some(prefix.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Set a header on every forwarded request, replacing an incoming one
+    /// with the same name.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Upstream timeout, answered with `504` when exceeded.
+    pub const fn timeout_ms(mut self, ms: u64) -> Self {
+        self.timeout_ms = Some(ms);
+        self
+    }
+
+    /// The upstream path and query for an incoming path.
+    fn upstream_path<'a>(&self, path: &'a str) -> &'a str {
+        let Some(prefix) = self.strip_prefix.as_deref() else {
+            return path;
+        };
+        match path.strip_prefix(prefix) {
+            Some(rest) if rest.is_empty() => "/",
+            Some(rest) if rest.starts_with('?') && !prefix.is_empty() => rest,
+            Some(rest) if rest.starts_with('/') || prefix.is_empty() => rest,
+            _ => path,
+        }
+    }
+
+    /// Build the outbound request for `req` without sending it.
+    pub fn request(&self, req: &Request) -> ClientRequest {
+        let path = self.upstream_path(req.path());
+        let path = if path.starts_with('?') {
+            format!("/{path}")
+        } else {
+            path.to_string()
+        };
+        let mut out = ClientRequest::new(req.method(), &format!("{}{path}", self.upstream));
+
+        let host = req.header_or("host", "");
+        for (name, value) in strip_hop_by_hop(req.headers()) {
+            let replaced = self
+                .headers
+                .iter()
+                .any(|(own, _)| own.eq_ignore_ascii_case(&name));
+            // Host and framing are set by the outbound request itself
+            let skipped = ["host", "content-length", "x-forwarded-host"]
+                .iter()
+                .any(|skip| name.eq_ignore_ascii_case(skip));
+            if !replaced && !skipped && !value.contains(['\r', '\n']) {
+                out = out.header(&name, &value);
+            }
+        }
+        if !host.is_empty() && !host.contains(['\r', '\n']) {
+            out = out.header("x-forwarded-host", host);
+        }
+        for (name, value) in &self.headers {
+            out = out.header(name, value);
+        }
+        if let Some(body) = req.body() {
+            out = out.body(body);
+        }
+        if let Some(ms) = self.timeout_ms {
+            out = out.timeout_ms(ms);
+        }
+        out
+    }
+
+    /// Forward `req` with a custom sender and return the buffered response.
+    pub fn forward_with<R, F>(&self, req: &Request, sender: F) -> R
+    where
+        R: HttpResponse,
+        F: FnOnce(&ClientRequest) -> crate::http_client::Result<Response>,
+    {
+        match self.request(req).send_with(sender) {
+            Ok(response) => {
+                let headers = strip_hop_by_hop(response.headers());
+                let status = response.status;
+                let body = response.body();
+                R::from_parts(status, headers, (!body.is_empty()).then_some(body))
+            },
+            Err(err) => upstream_error(&err),
+        }
+    }
+
+    /// Forward `req` over `wasi:http/outgoing-handler`, streaming the
+    /// upstream body back to the client.
+    #[cfg(any(target_arch = "wasm32", feature = "http-client"))]
+    pub fn forward<R: HttpResponse>(&self, req: &Request) -> R {
+        match self.request(req).send_streaming() {
+            Ok((status, headers, body)) => {
+                crate::stream::__register(body);
+                R::from_parts(status, strip_hop_by_hop(&headers), None)
+            },
+            Err(err) => upstream_error(&err),
+        }
+    }
+}
+
+/// The problem response for a failed upstream call.
+fn upstream_error<R: HttpResponse>(err: &Error) -> R {
+    crate::log_warn!("Proxy upstream request failed: {}", err);
+    if err.is_timeout() {
+        problem(504, "The upstream service did not respond in time")
+    } else {
+        problem(502, "The upstream service could not be reached")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use crate::middleware::TestResponse;
+    use std::collections::HashMap;
+
+    fn request(
+        method: Method,
+        path: &str,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+    ) -> Request {
+        Request::new(
+            method,
+            path.to_string(),
+            headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            body.map(<[u8]>::to_vec),
+            HashMap::new(),
+        )
+    }
+
+    fn pairs(headers: &[(&str, &str)]) -> Vec<(String, String)> {
+        headers
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_strip_hop_by_hop() {
+        let headers = pairs(&[
+            ("Connection", "keep-alive, X-Session"),
+            ("Keep-Alive", "timeout=5"),
+            ("Transfer-Encoding", "chunked"),
+            ("x-session", "abc"),
+            ("Accept", "application/json"),
+        ]);
+        assert_eq!(
+            strip_hop_by_hop(&headers),
+            pairs(&[("Accept", "application/json")])
+        );
+    }
+
+    #[test]
+    fn test_upstream_path() {
+        let proxy = Proxy::new("http://up").strip_prefix("/api/");
+        assert_eq!(proxy.upstream_path("/api/users?page=2"), "/users?page=2");
+        assert_eq!(proxy.upstream_path("/api"), "/");
+        assert_eq!(proxy.upstream_path("/api?x=1"), "?x=1");
+        assert_eq!(proxy.upstream_path("/apis"), "/apis");
+        assert_eq!(Proxy::new("http://up").upstream_path("/a?b"), "/a?b");
+    }
+
+    #[test]
+    fn test_request_forwards_method_path_headers_body() {
+        let req = request(
+            Method::Post,
+            "/api/users?page=2",
+            &[
+                ("Host", "gateway.example.com"),
+                ("Content-Type", "application/json"),
+                ("Content-Length", "2"),
+                ("Connection", "close"),
+                ("X-Api-Key", "client"),
+            ],
+            Some(b"{}"),
+        );
+        let out = Proxy::new("http://users.internal/v2/")
+            .strip_prefix("/api")
+            .header("x-api-key", "secret")
+            .timeout_ms(500)
+            .request(&req);
+
+        assert_eq!(out.method(), Method::Post);
+        assert_eq!(out.url(), "http://users.internal/v2/users?page=2");
+        assert_eq!(
+            out.headers(),
+            pairs(&[
+                ("Content-Type", "application/json"),
+                ("x-forwarded-host", "gateway.example.com"),
+                ("x-api-key", "secret"),
+            ])
+            .as_slice()
+        );
+        assert_eq!(out.body_bytes(), Some(b"{}".as_slice()));
+        assert_eq!(out.timeout(), Some(500_000_000));
+    }
+
+    #[test]
+    fn test_request_query_only_suffix() {
+        let req = request(Method::Get, "/api?x=1", &[], None);
+        let out = Proxy::new("http://up").strip_prefix("/api").request(&req);
+        assert_eq!(out.url(), "http://up/?x=1");
+    }
+
+    #[test]
+    fn test_forward_with_passes_response_through() {
+        let req = request(Method::Get, "/files/a.txt", &[], None);
+        let res: TestResponse = Proxy::new("http://files.internal").forward_with(&req, |out| {
+            assert_eq!(out.url(), "http://files.internal/files/a.txt");
+            Ok(Response::new(
+                404,
+                pairs(&[
+                    ("content-type", "text/plain"),
+                    ("transfer-encoding", "chunked"),
+                ]),
+                b"missing".to_vec(),
+            ))
+        });
+        assert_eq!(res.status, 404);
+        assert_eq!(res.headers, pairs(&[("content-type", "text/plain")]));
+        assert_eq!(res.body.as_deref(), Some(b"missing".as_slice()));
+    }
+
+    #[test]
+    fn test_forward_with_upstream_errors() {
+        let req = request(Method::Get, "/", &[], None);
+        let proxy = Proxy::new("http://up");
+        let res: TestResponse = proxy.forward_with(&req, |_| Err(Error::timeout()));
+        assert_eq!(res.status, 504);
+        let res: TestResponse =
+            proxy.forward_with(&req, |_| Err(Error::ConnectionError("refused".into())));
+        assert_eq!(res.status, 502);
+        assert_eq!(res.headers[0].1, "application/problem+json");
+    }
+}
@@ -0,0 +1,126 @@
+//! Slow-query logging for SQL built with mik-sql.
+//!
+//! Wrap the call that sends a query to the database in [`timed`]. When
+//! `MIK_SLOW_QUERY_MS` is set and the call takes at least that long, a
+//! `log!`-style line with the SQL, the parameter types (never their values)
+//! and the duration goes to stderr:
+//!
+//! ```text
+//! {"level":"warn","msg":"slow query","sql":"SELECT id, name FROM users WHERE email = $1","params":"[string]","duration_ms":"412","threshold_ms":"250","ts":"..."}
+//! ```
+//!
+//! `MIK_SLOW_QUERY_MS=0` logs every query. Unset, [`timed`] just runs the
+//! closure.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use mik_sdk::query_log;
+//!
+//! fn find_user(email: &str) -> Response {
+//!     let (sql, params) = sql_read!(users {
+//!         select: [id, name],
+//!         filter: { email: email },
+//!     });
+//!     let rows = query_log::timed(&sql, &params, || db_query(&sql, &params));
+//!     // ...
+//! }
+//! ```
+//!
+//! Use [`QueryLog`] for a fixed threshold, and
+//! [`query::explain`](crate::query::explain) to see the plan of a slow query.
+
+use crate::query::{Value, describe_params};
+use std::sync::OnceLock;
+
+/// Environment variable with the slow-query threshold in milliseconds.
+pub const SLOW_QUERY_ENV: &str = "MIK_SLOW_QUERY_MS";
+
+/// Logs queries that take at least a threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLog {
+    threshold_ms: u64,
+}
+
+impl QueryLog {
+    /// Log queries taking at least `threshold_ms`; `0` logs every query.
+    #[must_use]
+    pub const fn new(threshold_ms: u64) -> Self {
+        Self { threshold_ms }
+    }
+
+    /// The logger configured by `MIK_SLOW_QUERY_MS`, read once.
+    ///
+    /// `None` when the variable is unset or not a number.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        static CONFIG: OnceLock<Option<QueryLog>> = OnceLock::new();
+        *CONFIG.get_or_init(|| {
+            std::env::var(SLOW_QUERY_ENV)
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .map(Self::new)
+        })
+    }
+
+    /// Threshold in milliseconds.
+    #[must_use]
+    pub const fn threshold_ms(&self) -> u64 {
+        self.threshold_ms
+    }
+
+    /// Whether a query that took `duration_ms` is logged.
+    #[must_use]
+    pub const fn is_slow(&self, duration_ms: u64) -> bool {
+        duration_ms >= self.threshold_ms
+    }
+
+    /// Run `execute`, logging `sql` if it is slow, and return its result.
+    pub fn run<T>(&self, sql: &str, params: &[Value], execute: impl FnOnce() -> T) -> T {
+        let started = crate::time::now_millis();
+        let result = execute();
+        let duration_ms = crate::time::now_millis().saturating_sub(started);
+        if self.is_slow(duration_ms) {
+            crate::log!(
+                warn,
+                "slow query",
+                sql: sql,
+                params: describe_params(params),
+                duration_ms: duration_ms,
+                threshold_ms: self.threshold_ms
+            );
+        }
+        result
+    }
+}
+
+/// Run `execute`, logging `sql` when it exceeds `MIK_SLOW_QUERY_MS`.
+///
+/// Without the variable this only calls `execute`.
+pub fn timed<T>(sql: &str, params: &[Value], execute: impl FnOnce() -> T) -> T {
+    match QueryLog::from_env() {
+        Some(log) => log.run(sql, params, execute),
+        None => execute(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_slow() {
+        let log = QueryLog::new(250);
+        assert!(!log.is_slow(249));
+        assert!(log.is_slow(250));
+        assert!(QueryLog::new(0).is_slow(0));
+    }
+
+    #[test]
+    fn test_run_returns_result() {
+        let params = [Value::Int(1)];
+        let rows = QueryLog::new(u64::MAX).run("SELECT 1", &params, || vec![1, 2]);
+        assert_eq!(rows, vec![1, 2]);
+        assert_eq!(timed("SELECT 1", &params, || 7), 7);
+    }
+}
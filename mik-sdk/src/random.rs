@@ -40,11 +40,26 @@
 //! let uuid = random::uuid();
 //! assert_eq!(uuid.len(), 36); // "550e8400-e29b-41d4-a716-446655440000"
 //!
+//! // Time-ordered ids for primary keys
+//! let order_id = random::uuid_v7(); // "017f22e2-79b0-7cc3-98c4-dc0c0c07398f"
+//! let event_id = random::ulid();    // "01ARZ3NDEKTSV4RRFFQ69G5FAV"
+//!
 //! // Generate random hex string
 //! let token = random::hex(16);
 //! assert_eq!(token.len(), 32); // 16 bytes = 32 hex characters
+//!
+//! // Tokens and ids for URLs
+//! let api_key = random::alphanumeric(32);
+//! let short_id = random::base58(8);
+//!
+//! // Ranges and collections
+//! let roll = random::range(1..=6);
+//! let winner = random::choice(&entries);
+//! random::shuffle(&mut playlist);
 //! ```
 
+use std::ops::{Bound, RangeBounds};
+
 // =============================================================================
 // WASM IMPLEMENTATION (Production)
 // Uses wasi:random/random for cryptographic randomness
@@ -190,26 +205,7 @@ pub fn uuid() -> String {
     // Set variant bits (2 bits at byte 8, high 2 bits = 10 for RFC 4122)
     buf[8] = (buf[8] & 0x3F) | 0x80;
 
-    // Format as UUID string
-    format!(
-        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
-        buf[0],
-        buf[1],
-        buf[2],
-        buf[3],
-        buf[4],
-        buf[5],
-        buf[6],
-        buf[7],
-        buf[8],
-        buf[9],
-        buf[10],
-        buf[11],
-        buf[12],
-        buf[13],
-        buf[14],
-        buf[15]
-    )
+    format_uuid(&buf)
 }
 
 /// Stub for native non-test builds. Panics with helpful error message.
@@ -279,6 +275,319 @@ pub fn hex(_byte_len: usize) -> String {
     )
 }
 
+// =============================================================================
+// DERIVED VALUES
+// Built on bytes() and u64(), so they work wherever those do
+// =============================================================================
+
+/// Generate a random integer in `range`, without modulo bias.
+///
+/// # Panics
+///
+/// Panics if `range` is empty.
+///
+/// # Examples
+///
+/// ```ignore
+/// let die = mik_sdk::random::range(1..=6);
+/// let index = mik_sdk::random::range(0..10);
+/// ```
+#[must_use]
+pub fn range(range: impl RangeBounds<u64>) -> u64 {
+    let start = match range.start_bound() {
+        Bound::Included(&n) => Some(n),
+        Bound::Excluded(&n) => n.checked_add(1),
+        Bound::Unbounded => Some(0),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => Some(n),
+        Bound::Excluded(&n) => n.checked_sub(1),
+        Bound::Unbounded => Some(u64::MAX),
+    };
+    let (Some(start), Some(end)) = (start, end) else {
+        panic!("random::range() called with an empty range");
+    };
+    assert!(start <= end, "random::range() called with an empty range");
+
+    // Only the full u64 range has no representable span
+    (end - start)
+        .checked_add(1)
+        .map_or_else(u64, |span| start + below(span))
+}
+
+/// Uniform value in `0..bound` (`bound` > 0).
+fn below(bound: u64) -> u64 {
+    // Values under the threshold would make the low results more likely
+    let threshold = bound.wrapping_neg() % bound;
+    loop {
+        let value = u64();
+        if value >= threshold {
+            return value % bound;
+        }
+    }
+}
+
+/// Uniform index in `0..len` (`len` > 0).
+#[allow(clippy::cast_possible_truncation)] // The result is below `len`
+fn index(len: usize) -> usize {
+    below(len as u64) as usize
+}
+
+/// Pick a random element, or `None` if `items` is empty.
+///
+/// # Examples
+///
+/// ```ignore
+/// let greeting = mik_sdk::random::choice(&["hi", "hello", "hey"]);
+/// ```
+#[must_use]
+pub fn choice<T>(items: &[T]) -> Option<&T> {
+    if items.is_empty() {
+        return None;
+    }
+    items.get(index(items.len()))
+}
+
+/// Shuffle `items` in place (Fisher-Yates).
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut deck: Vec<u32> = (1..=52).collect();
+/// mik_sdk::random::shuffle(&mut deck);
+/// ```
+pub fn shuffle<T>(items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, index(i + 1));
+    }
+}
+
+/// Generate a random string of `len` characters drawn from `alphabet`.
+///
+/// Every character is equally likely. Returns an empty string if
+/// `alphabet` is empty.
+///
+/// # Examples
+///
+/// ```ignore
+/// let pin = mik_sdk::random::string(6, "0123456789");
+/// ```
+#[must_use]
+pub fn string(len: usize, alphabet: &str) -> String {
+    let chars: Vec<char> = alphabet.chars().collect();
+    if chars.is_empty() {
+        return String::new();
+    }
+    (0..len)
+        .filter_map(|_| chars.get(index(chars.len())))
+        .collect()
+}
+
+/// Generate a random alphanumeric token (`A-Z`, `a-z`, `0-9`).
+///
+/// Each character carries about 5.95 bits, so 22 characters exceed
+/// 128 bits of entropy.
+///
+/// # Examples
+///
+/// ```ignore
+/// let token = mik_sdk::random::alphanumeric(32);
+/// assert_eq!(token.len(), 32);
+/// ```
+#[must_use]
+#[allow(clippy::indexing_slicing)] // Indices are reduced below the alphabet size
+pub fn alphanumeric(len: usize) -> String {
+    use crate::constants::ALPHANUMERIC_CHARS;
+    // 62 * 4 = 248: bytes below that map evenly onto the alphabet
+    const LIMIT: u8 = 248;
+
+    let mut result = String::with_capacity(len);
+    while result.len() < len {
+        for byte in bytes(len - result.len() + 8) {
+            if byte < LIMIT {
+                result.push(char::from(ALPHANUMERIC_CHARS[usize::from(byte % 62)]));
+                if result.len() == len {
+                    break;
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Generate `byte_len` random bytes encoded as base58 (Bitcoin alphabet).
+///
+/// Base58 has no look-alike characters (`0`/`O`, `I`/`l`) and no
+/// punctuation, so ids survive copy-paste and double-click selection.
+///
+/// # Examples
+///
+/// ```ignore
+/// let id = mik_sdk::random::base58(16); // ~22 chars
+/// ```
+#[must_use]
+pub fn base58(byte_len: usize) -> String {
+    base58_encode(&bytes(byte_len))
+}
+
+/// Generate `byte_len` random bytes encoded as lowercase Crockford base32.
+///
+/// Case-insensitive and free of `i`, `l`, `o` and `u`, so ids are safe in
+/// hostnames and easy to read aloud. The output has `ceil(8 * byte_len / 5)`
+/// characters.
+///
+/// # Examples
+///
+/// ```ignore
+/// let id = mik_sdk::random::base32(10);
+/// assert_eq!(id.len(), 16);
+/// ```
+#[must_use]
+pub fn base32(byte_len: usize) -> String {
+    base32_encode(&bytes(byte_len))
+}
+
+#[allow(clippy::indexing_slicing)] // Indices are reduced below the alphabet size
+fn base58_encode(data: &[u8]) -> String {
+    use crate::constants::BASE58_CHARS;
+
+    // Little-endian base58 digits of the big-endian input number
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in data {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // Each leading zero byte is written as the zero digit
+    let zeros = data.iter().take_while(|&&byte| byte == 0).count();
+    std::iter::repeat_n(BASE58_CHARS[0], zeros)
+        .chain(digits.iter().rev().map(|&d| BASE58_CHARS[usize::from(d)]))
+        .map(char::from)
+        .collect()
+}
+
+#[allow(clippy::indexing_slicing)] // Indices are reduced below the alphabet size
+fn base32_encode(data: &[u8]) -> String {
+    use crate::constants::BASE32_CHARS;
+
+    let mut result = String::with_capacity((data.len() * 8).div_ceil(5));
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for &byte in data {
+        buffer = (buffer << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            result.push(char::from(
+                BASE32_CHARS[usize::from((buffer >> bits) & 0x1f)],
+            ));
+        }
+    }
+    if bits > 0 {
+        result.push(char::from(
+            BASE32_CHARS[usize::from((buffer << (5 - bits)) & 0x1f)],
+        ));
+    }
+    result
+}
+
+// =============================================================================
+// TIME-ORDERED IDS
+// Millisecond timestamp followed by random bits, so ids sort by creation time
+// =============================================================================
+
+/// Generate a UUID v7 string (RFC 9562).
+///
+/// The first 48 bits are the Unix time in milliseconds, so ids sort by
+/// creation time as strings and as database `uuid` values, which keeps
+/// B-tree indexes compact. The remaining 74 bits are random; ids made in
+/// the same millisecond are unique but not ordered among themselves.
+///
+/// # Examples
+///
+/// ```ignore
+/// let id = mik_sdk::random::uuid_v7();
+/// assert_eq!(id.len(), 36);
+/// assert_eq!(id.chars().nth(14), Some('7')); // Version 7
+/// ```
+#[must_use]
+pub fn uuid_v7() -> String {
+    uuid_v7_at(crate::time::now_millis(), &bytes(10))
+}
+
+/// Generate a ULID string.
+///
+/// 26 uppercase Crockford base32 characters: a 48-bit millisecond
+/// timestamp followed by 80 random bits. ULIDs sort by creation time and
+/// are shorter than UUIDs in URLs.
+///
+/// # Examples
+///
+/// ```ignore
+/// let id = mik_sdk::random::ulid();
+/// assert_eq!(id.len(), 26); // "01ARZ3NDEKTSV4RRFFQ69G5FAV"
+/// ```
+#[must_use]
+pub fn ulid() -> String {
+    ulid_at(crate::time::now_millis(), &bytes(10))
+}
+
+/// UUID v7 from a millisecond timestamp and 10 random bytes.
+#[allow(clippy::indexing_slicing)] // Fixed-size buffers
+fn uuid_v7_at(millis: u64, random: &[u8]) -> String {
+    let mut buf = [0u8; 16];
+    buf[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+    buf[6..].copy_from_slice(&random[..10]);
+
+    // Version 7 in the high nibble of byte 6, RFC variant in byte 8
+    buf[6] = (buf[6] & 0x0F) | 0x70;
+    buf[8] = (buf[8] & 0x3F) | 0x80;
+    format_uuid(&buf)
+}
+
+/// ULID from a millisecond timestamp and 10 random bytes.
+#[allow(clippy::indexing_slicing)] // Indices are reduced below the alphabet size
+fn ulid_at(millis: u64, random: &[u8]) -> String {
+    use crate::constants::BASE32_CHARS;
+
+    let mut value = u128::from(millis & 0xFFFF_FFFF_FFFF) << 80;
+    for (i, &byte) in random[..10].iter().enumerate() {
+        value |= u128::from(byte) << (8 * (9 - i));
+    }
+
+    // 26 characters of 5 bits; the first one holds the top 3 bits
+    (0..26)
+        .rev()
+        .map(|i| {
+            let digit = ((value >> (5 * i)) & 0x1F) as usize;
+            char::from(BASE32_CHARS[digit].to_ascii_uppercase())
+        })
+        .collect()
+}
+
+/// Format 16 bytes as a lowercase hyphenated UUID.
+#[allow(clippy::indexing_slicing)] // Nibbles index the 16 hex digits
+fn format_uuid(buf: &[u8]) -> String {
+    use crate::constants::HEX_CHARS;
+    let mut result = String::with_capacity(36);
+    for (i, &b) in buf.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            result.push('-');
+        }
+        result.push(char::from(HEX_CHARS[usize::from(b >> 4)]));
+        result.push(char::from(HEX_CHARS[usize::from(b & 0x0f)]));
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -505,4 +814,127 @@ mod tests {
         let h2 = hex(16);
         assert_ne!(h1, h2, "Two hex strings should differ");
     }
+
+    #[test]
+    fn test_range_bounds() {
+        for _ in 0..200 {
+            assert!((1..=6).contains(&range(1..=6)));
+            assert!((10..20).contains(&range(10..20)));
+        }
+        assert_eq!(range(7..8), 7);
+        assert_eq!(range(u64::MAX..), u64::MAX);
+        let _ = range(..);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn test_range_empty_panics() {
+        let _ = range(5..5);
+    }
+
+    #[test]
+    fn test_range_covers_values() {
+        let mut seen = [false; 4];
+        for _ in 0..500 {
+            seen[range(0..4) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_choice() {
+        let empty: [u8; 0] = [];
+        assert_eq!(choice(&empty), None);
+        assert_eq!(choice(&[42]), Some(&42));
+        let items = ["a", "b", "c"];
+        assert!(items.contains(choice(&items).unwrap()));
+    }
+
+    #[test]
+    fn test_shuffle_is_permutation() {
+        let mut items: Vec<u32> = (0..50).collect();
+        shuffle(&mut items);
+        let mut sorted = items.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..50).collect::<Vec<_>>());
+        assert_ne!(items, sorted, "50 elements should not stay in order");
+
+        let mut one = [1];
+        shuffle(&mut one);
+        shuffle::<u8>(&mut []);
+    }
+
+    #[test]
+    fn test_string_and_alphanumeric() {
+        let pin = string(6, "0123456789");
+        assert_eq!(pin.len(), 6);
+        assert!(pin.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(string(4, ""), "");
+        assert_eq!(string(3, "é").chars().count(), 3);
+
+        let token = alphanumeric(64);
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(alphanumeric(0), "");
+        assert_ne!(alphanumeric(32), alphanumeric(32));
+    }
+
+    #[test]
+    fn test_base58_encode() {
+        assert_eq!(base58_encode(b""), "");
+        assert_eq!(base58_encode(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+        assert_eq!(base58_encode(&[0xff; 4]), "7YXq9G");
+        let id = base58(16);
+        assert!((21..=22).contains(&id.len()));
+    }
+
+    #[test]
+    fn test_base32_encode() {
+        assert_eq!(base32_encode(b""), "");
+        assert_eq!(base32_encode(b"f"), "cr");
+        assert_eq!(base32_encode(b"foobar"), "csqpyrk1e8");
+        assert_eq!(base32(10).len(), 16);
+        assert_eq!(base32(16).len(), 26);
+        assert!(base32(16).chars().all(|c| !"ilou".contains(c)));
+    }
+
+    #[test]
+    fn test_uuid_v7_rfc9562_example() {
+        // RFC 9562 Appendix A.6
+        let random = [0x0C, 0xC3, 0x18, 0xC4, 0xDC, 0x0C, 0x0C, 0x07, 0x39, 0x8F];
+        assert_eq!(
+            uuid_v7_at(0x017F_22E2_79B0, &random),
+            "017f22e2-79b0-7cc3-98c4-dc0c0c07398f"
+        );
+    }
+
+    #[test]
+    fn test_uuid_v7_format_and_order() {
+        let id = uuid_v7();
+        assert_eq!(id.len(), 36);
+        assert_eq!(id.chars().nth(14), Some('7'));
+        assert!(matches!(id.chars().nth(19), Some('8' | '9' | 'a' | 'b')));
+
+        let earlier = uuid_v7_at(1_000, &[0xFF; 10]);
+        let later = uuid_v7_at(1_001, &[0x00; 10]);
+        assert!(earlier < later);
+    }
+
+    #[test]
+    fn test_ulid() {
+        assert_eq!(
+            ulid_at(1_469_918_176_385, &[0; 10]),
+            "01ARYZ6S410000000000000000"
+        );
+        assert_eq!(ulid_at(0, &[0xFF; 10]), "0000000000ZZZZZZZZZZZZZZZZ");
+
+        let id = ulid();
+        assert_eq!(id.len(), 26);
+        assert!(
+            id.chars()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+        );
+        assert!(ulid_at(1_000, &[0xFF; 10]) < ulid_at(1_001, &[0; 10]));
+    }
 }
@@ -0,0 +1,420 @@
+//! Range requests (RFC 9110 §14).
+//!
+//! [`serve`] answers a `GET` for an in-memory blob, honouring the request's
+//! `Range` header: a single range becomes a `206 Partial Content` response
+//! with `Content-Range`, several ranges become a `multipart/byteranges`
+//! body, and ranges outside the blob become `416 Range Not Satisfiable`.
+//! Requests without a usable `Range` header get the full body.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::range;
+//!
+//! fn download(req: &Request) -> Response {
+//!     let blob = load_blob(req.param_or("id", ""));
+//!     range::serve(req, "application/pdf", &blob)
+//! }
+//! ```
+//!
+//! Use [`parse`] directly to build the response yourself:
+//!
+//! ```
+//! use mik_sdk::range::{self, RangeError};
+//!
+//! let ranges = range::parse("bytes=0-99, -50", 1000).unwrap();
+//! assert_eq!(ranges[0].content_range(1000), "bytes 0-99/1000");
+//! assert_eq!(ranges[1].content_range(1000), "bytes 950-999/1000");
+//!
+//! assert_eq!(range::parse("bytes=2000-", 1000), Err(RangeError::Unsatisfiable));
+//! ```
+
+use crate::constants::{HEADER_CONTENT_TYPE, MAX_RANGES};
+use crate::middleware::{HttpResponse, problem};
+use crate::random;
+use crate::request::{Method, Request};
+
+/// An inclusive byte range within a representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    /// Offset of the first byte.
+    #[inline]
+    #[must_use]
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Offset of the last byte (inclusive).
+    #[inline]
+    #[must_use]
+    pub const fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// `Content-Range` header value for this range.
+    #[must_use]
+    pub fn content_range(&self, total: u64) -> String {
+        format!("bytes {}-{}/{total}", self.start, self.end)
+    }
+
+    /// The bytes of `body` covered by this range.
+    ///
+    /// Returns an empty slice if the range lies outside `body`, which cannot
+    /// happen for ranges returned by [`parse`] with `body.len()`.
+    #[must_use]
+    pub fn slice<'a>(&self, body: &'a [u8]) -> &'a [u8] {
+        let start = usize::try_from(self.start).unwrap_or(usize::MAX);
+        let end = usize::try_from(self.end).unwrap_or(usize::MAX);
+        body.get(start..=end).unwrap_or_default()
+    }
+}
+
+/// Why a `Range` header could not be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RangeError {
+    /// The header is malformed, uses a unit other than `bytes`, or asks for
+    /// too many ranges. Servers ignore it and send the full body.
+    Invalid,
+    /// No range overlaps the representation. Servers answer `416`.
+    Unsatisfiable,
+}
+
+impl std::fmt::Display for RangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid => write!(f, "Invalid Range header"),
+            Self::Unsatisfiable => write!(f, "Range not satisfiable"),
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+/// Parse a `Range` header against a representation of `len` bytes.
+///
+/// Ranges are clamped to the representation, unsatisfiable ranges are
+/// dropped, and the rest are sorted with overlapping or adjacent ranges
+/// merged, so the result is never empty and never overlaps.
+///
+/// # Errors
+///
+/// - [`RangeError::Invalid`] if the header is malformed, not in `bytes`, or
+///   lists more than [`MAX_RANGES`] ranges.
+/// - [`RangeError::Unsatisfiable`] if no range overlaps the representation.
+pub fn parse(header: &str, len: u64) -> Result<Vec<ByteRange>, RangeError> {
+    let (unit, specs) = header.split_once('=').ok_or(RangeError::Invalid)?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return Err(RangeError::Invalid);
+    }
+
+    let mut ranges = Vec::new();
+    let mut count = 0;
+    for spec in specs.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        count += 1;
+        if count > MAX_RANGES {
+            return Err(RangeError::Invalid);
+        }
+        let (first, last) = spec.split_once('-').ok_or(RangeError::Invalid)?;
+        let (first, last) = (first.trim(), last.trim());
+
+        let range = if first.is_empty() {
+            // Suffix range: the last N bytes
+            let suffix = parse_offset(last)?;
+            (suffix > 0 && len > 0).then(|| ByteRange {
+                start: len.saturating_sub(suffix),
+                end: len - 1,
+            })
+        } else {
+            let start = parse_offset(first)?;
+            let end = if last.is_empty() {
+                u64::MAX
+            } else {
+                parse_offset(last)?
+            };
+            if end < start {
+                return Err(RangeError::Invalid);
+            }
+            (start < len).then(|| ByteRange {
+                start,
+                end: end.min(len - 1),
+            })
+        };
+        ranges.extend(range);
+    }
+    if count == 0 {
+        return Err(RangeError::Invalid);
+    }
+    if ranges.is_empty() {
+        return Err(RangeError::Unsatisfiable);
+    }
+
+    ranges.sort_unstable_by_key(|r| r.start);
+    let mut merged: Vec<ByteRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(1) => {
+                last.end = last.end.max(range.end);
+            },
+            _ => merged.push(range),
+        }
+    }
+    Ok(merged)
+}
+
+/// Parse a byte offset: ASCII digits only, no sign.
+fn parse_offset(s: &str) -> Result<u64, RangeError> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(RangeError::Invalid);
+    }
+    s.parse().map_err(|_| RangeError::Invalid)
+}
+
+/// Respond to `req` with `body`, honouring its `Range` header.
+///
+/// - No `Range` header, a non-`GET` request, an `If-Range` precondition
+///   (which can't be checked without a validator), or an invalid header:
+///   `200` with the full body.
+/// - One satisfiable range: `206` with `Content-Range`.
+/// - Several: `206` with a `multipart/byteranges` body.
+/// - None satisfiable: `416` with `Content-Range: bytes */len`.
+///
+/// Every response carries `Accept-Ranges: bytes`.
+#[must_use]
+pub fn serve<R: HttpResponse>(req: &Request, content_type: &str, body: &[u8]) -> R {
+    let len = body.len() as u64;
+    let full = || {
+        R::from_parts(
+            200,
+            vec![
+                (HEADER_CONTENT_TYPE.to_string(), content_type.to_string()),
+                ("accept-ranges".to_string(), "bytes".to_string()),
+            ],
+            Some(body.to_vec()),
+        )
+    };
+
+    if req.method() != Method::Get || !req.header_all("if-range").is_empty() {
+        return full();
+    }
+    let Some(header) = req.header_all("range").first().copied() else {
+        return full();
+    };
+
+    match parse(header, len) {
+        Err(RangeError::Unsatisfiable) => {
+            let mut res: R = problem(416, "Requested range is outside the resource");
+            let headers = res.headers_mut();
+            headers.push(("content-range".to_string(), format!("bytes */{len}")));
+            headers.push(("accept-ranges".to_string(), "bytes".to_string()));
+            res
+        },
+        Err(_) => full(),
+        Ok(ranges) => match ranges.as_slice() {
+            [range] => R::from_parts(
+                206,
+                vec![
+                    (HEADER_CONTENT_TYPE.to_string(), content_type.to_string()),
+                    ("content-range".to_string(), range.content_range(len)),
+                    ("accept-ranges".to_string(), "bytes".to_string()),
+                ],
+                Some(range.slice(body).to_vec()),
+            ),
+            ranges => {
+                let boundary = random::hex(16);
+                R::from_parts(
+                    206,
+                    vec![
+                        (
+                            HEADER_CONTENT_TYPE.to_string(),
+                            format!("multipart/byteranges; boundary={boundary}"),
+                        ),
+                        ("accept-ranges".to_string(), "bytes".to_string()),
+                    ],
+                    Some(multipart_body(&boundary, content_type, body, ranges)),
+                )
+            },
+        },
+    }
+}
+
+/// Build a `multipart/byteranges` body (RFC 9110 §14.6).
+fn multipart_body(
+    boundary: &str,
+    content_type: &str,
+    body: &[u8],
+    ranges: &[ByteRange],
+) -> Vec<u8> {
+    let len = body.len() as u64;
+    let mut out = Vec::new();
+    for range in ranges {
+        out.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Type: {content_type}\r\nContent-Range: {}\r\n\r\n",
+                range.content_range(len)
+            )
+            .as_bytes(),
+        );
+        out.extend_from_slice(range.slice(body));
+        out.extend_from_slice(b"\r\n");
+    }
+    out.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+    use std::collections::HashMap;
+
+    const BODY: &[u8] = b"0123456789";
+
+    fn range(start: u64, end: u64) -> ByteRange {
+        ByteRange { start, end }
+    }
+
+    fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+        Request::new(
+            method,
+            "/blob".to_string(),
+            headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn header<'a>(res: &'a TestResponse, name: &str) -> Option<&'a str> {
+        res.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_parse_forms() {
+        assert_eq!(parse("bytes=0-4", 10), Ok(vec![range(0, 4)]));
+        assert_eq!(parse("bytes=5-", 10), Ok(vec![range(5, 9)]));
+        assert_eq!(parse("bytes=-3", 10), Ok(vec![range(7, 9)]));
+        assert_eq!(parse("Bytes = 2-3", 10), Ok(vec![range(2, 3)]));
+    }
+
+    #[test]
+    fn test_parse_clamps_to_length() {
+        assert_eq!(parse("bytes=8-100", 10), Ok(vec![range(8, 9)]));
+        assert_eq!(parse("bytes=-100", 10), Ok(vec![range(0, 9)]));
+    }
+
+    #[test]
+    fn test_parse_sorts_and_merges() {
+        assert_eq!(
+            parse("bytes=6-7, 0-1, 1-2, 3-3", 10),
+            Ok(vec![range(0, 3), range(6, 7)])
+        );
+    }
+
+    #[test]
+    fn test_parse_drops_unsatisfiable_ranges() {
+        assert_eq!(parse("bytes=20-30, 0-0", 10), Ok(vec![range(0, 0)]));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for header in [
+            "0-4",
+            "items=0-4",
+            "bytes=",
+            "bytes=abc",
+            "bytes=4-2",
+            "bytes=+1-2",
+            "bytes=-",
+            "bytes=0-4;x",
+        ] {
+            assert_eq!(parse(header, 10), Err(RangeError::Invalid), "{header}");
+        }
+        let many = format!("bytes={}", vec!["0-0"; MAX_RANGES + 1].join(","));
+        assert_eq!(parse(&many, 10), Err(RangeError::Invalid));
+    }
+
+    #[test]
+    fn test_parse_unsatisfiable() {
+        assert_eq!(parse("bytes=10-", 10), Err(RangeError::Unsatisfiable));
+        assert_eq!(parse("bytes=-0", 10), Err(RangeError::Unsatisfiable));
+        assert_eq!(parse("bytes=0-", 0), Err(RangeError::Unsatisfiable));
+        assert_eq!(parse("bytes=-5", 0), Err(RangeError::Unsatisfiable));
+    }
+
+    #[test]
+    fn test_slice() {
+        assert_eq!(range(2, 4).slice(BODY), b"234");
+        assert_eq!(range(9, 9).slice(BODY), b"9");
+        assert!(range(5, 20).slice(BODY).is_empty());
+    }
+
+    #[test]
+    fn test_serve_full() {
+        for req in [
+            request(Method::Get, &[]),
+            request(Method::Post, &[("range", "bytes=0-1")]),
+            request(
+                Method::Get,
+                &[("range", "bytes=0-1"), ("if-range", "\"v1\"")],
+            ),
+            request(Method::Get, &[("range", "lines=0-1")]),
+        ] {
+            let res: TestResponse = serve(&req, "text/plain", BODY);
+            assert_eq!(res.status, 200);
+            assert_eq!(res.body.as_deref(), Some(BODY));
+            assert_eq!(header(&res, "accept-ranges"), Some("bytes"));
+        }
+    }
+
+    #[test]
+    fn test_serve_single_range() {
+        let req = request(Method::Get, &[("range", "bytes=-4")]);
+        let res: TestResponse = serve(&req, "text/plain", BODY);
+        assert_eq!(res.status, 206);
+        assert_eq!(res.body.as_deref(), Some(&b"6789"[..]));
+        assert_eq!(header(&res, "content-range"), Some("bytes 6-9/10"));
+        assert_eq!(header(&res, "content-type"), Some("text/plain"));
+    }
+
+    #[test]
+    fn test_serve_multiple_ranges() {
+        let req = request(Method::Get, &[("range", "bytes=0-1,5-6")]);
+        let res: TestResponse = serve(&req, "text/plain", BODY);
+        assert_eq!(res.status, 206);
+
+        let content_type = header(&res, "content-type").unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/byteranges; boundary=")
+            .unwrap();
+        let expected = format!(
+            "--{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 0-1/10\r\n\r\n01\r\n\
+             --{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 5-6/10\r\n\r\n56\r\n\
+             --{boundary}--\r\n"
+        );
+        assert_eq!(res.body.as_deref(), Some(expected.as_bytes()));
+        assert!(header(&res, "content-range").is_none());
+    }
+
+    #[test]
+    fn test_serve_unsatisfiable() {
+        let req = request(Method::Get, &[("range", "bytes=50-60")]);
+        let res: TestResponse = serve(&req, "text/plain", BODY);
+        assert_eq!(res.status, 416);
+        assert_eq!(header(&res, "content-range"), Some("bytes */10"));
+        assert_eq!(
+            header(&res, "content-type"),
+            Some("application/problem+json")
+        );
+    }
+}
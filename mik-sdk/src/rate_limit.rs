@@ -0,0 +1,492 @@
+//! Token-bucket rate limiting.
+//!
+//! Ready-made `#[before(...)]` hooks that answer `429 Too Many Requests`
+//! with a `Retry-After` header once a client has used up its bucket.
+//! Buckets are keyed by client IP (see [`Request::client_ip`]) or by a
+//! request header such as an API key.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::rate_limit::{self, RateLimit};
+//! use std::sync::LazyLock;
+//!
+//! // 10 requests per second per API key, with bursts of up to 20
+//! fn per_key(req: &Request) -> Option<Response> {
+//!     static LIMIT: LazyLock<RateLimit> =
+//!         LazyLock::new(|| RateLimit::per_second(10).burst(20).by_header("x-api-key"));
+//!     LIMIT.check(req)
+//! }
+//!
+//! routes! {
+//!     // MIK_RATE_LIMIT=100/min, per client IP
+//!     #![before(rate_limit::rate_limit)]
+//!
+//!     #[before(per_key)]
+//!     POST "/search" => search,
+//! }
+//! ```
+//!
+//! # Storage
+//!
+//! **The default store only limits requests within one component
+//! instance.** Buckets live in a [`MemoryStore`], which persists only while
+//! the runtime reuses the instance. Where the runtime creates a fresh
+//! instance per request (`wasmtime serve`, for one), every request starts
+//! with a full bucket and nothing is limited; with several instances side
+//! by side, each grants the full limit. The SDK does not ship a shared
+//! store: implement [`RateLimitStore`] over a key-value store such as
+//! `wasi:keyvalue` and pass it to [`RateLimit::store`].
+//! [`RateLimit::is_persistent`] tells which kind of store a limit uses.
+//!
+//! Client IP headers are set by proxies and can be forged when the component
+//! is reachable directly; set `MIK_TRUSTED_PROXIES` to the number of proxies
+//! in front of it, or key by a header you trust.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use crate::middleware::{HttpResponse, problem};
+use crate::request::Request;
+
+/// Environment variable holding the default limit, e.g. `100/min`.
+pub const RATE_LIMIT_ENV: &str = "MIK_RATE_LIMIT";
+
+/// Response header telling the client how many seconds to wait.
+const RETRY_AFTER: &str = "retry-after";
+
+/// Most buckets a [`MemoryStore`] keeps before evicting.
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// Bucket key for requests without a client IP or key header.
+const UNKNOWN_CLIENT: &str = "unknown";
+
+/// The state of one client's bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct Bucket {
+    /// Tokens left; one is spent per request.
+    pub tokens: f64,
+    /// When `tokens` was last updated, in milliseconds since the Unix epoch.
+    pub updated_millis: u64,
+}
+
+impl Bucket {
+    /// A bucket holding `tokens` as of `updated_millis`, e.g. when loading
+    /// one from a custom store.
+    #[must_use]
+    pub const fn new(tokens: f64, updated_millis: u64) -> Self {
+        Self {
+            tokens,
+            updated_millis,
+        }
+    }
+}
+
+/// Where buckets are kept between requests.
+///
+/// Implement this over a shared key-value store to rate limit across
+/// component instances. `ttl_millis` is how long until the bucket is full
+/// again; after that a missing bucket behaves the same, so stores may
+/// expire it.
+pub trait RateLimitStore: Send + Sync {
+    /// Load the bucket for `key`, or `None` if it is unknown or expired.
+    fn load(&self, key: &str) -> Option<Bucket>;
+
+    /// Save the bucket for `key`.
+    fn save(&self, key: &str, bucket: Bucket, ttl_millis: u64);
+
+    /// Whether buckets outlive the component instance and are shared
+    /// between instances. Only [`MemoryStore`] returns `false`.
+    fn is_persistent(&self) -> bool {
+        true
+    }
+}
+
+/// In-memory [`RateLimitStore`], local to the component instance.
+///
+/// Not persistent: see [Storage](self#storage) for when this limits
+/// nothing.
+///
+/// Holds up to 10,000 buckets; when full, expired buckets are dropped
+/// first, then the ones closest to expiry.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    buckets: Mutex<HashMap<String, (Bucket, u64)>>,
+}
+
+impl MemoryStore {
+    /// An empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RateLimitStore for MemoryStore {
+    fn load(&self, key: &str) -> Option<Bucket> {
+        let buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        buckets.get(key).map(|(bucket, _)| *bucket)
+    }
+
+    fn save(&self, key: &str, bucket: Bucket, ttl_millis: u64) {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        if buckets.len() >= MAX_TRACKED_KEYS && !buckets.contains_key(key) {
+            let now = bucket.updated_millis;
+            buckets.retain(|_, (_, expires)| *expires > now);
+            if buckets.len() >= MAX_TRACKED_KEYS {
+                let soonest = buckets
+                    .iter()
+                    .min_by_key(|(_, (_, expires))| *expires)
+                    .map(|(key, _)| key.clone());
+                if let Some(soonest) = soonest {
+                    buckets.remove(&soonest);
+                }
+            }
+        }
+        let expires = bucket.updated_millis.saturating_add(ttl_millis);
+        buckets.insert(key.to_string(), (bucket, expires));
+    }
+
+    fn is_persistent(&self) -> bool {
+        false
+    }
+}
+
+/// What identifies a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Key {
+    ClientIp,
+    Header(String),
+}
+
+/// Token-bucket rate limit, usable as a `#[before(...)]` hook.
+///
+/// Each client starts with a full bucket of [`burst`](Self::burst) tokens
+/// (the request count by default). Every request spends one, and tokens
+/// refill evenly over the period.
+#[derive(Clone)]
+pub struct RateLimit {
+    capacity: f64,
+    /// Tokens added per `period_millis`.
+    requests: f64,
+    period_millis: f64,
+    key: Key,
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl RateLimit {
+    /// Allow `requests` per `period_secs` seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests` or `period_secs` is zero.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Periods are far below 2^52 ms
+    pub fn new(requests: u32, period_secs: u64) -> Self {
+        assert!(
+            requests > 0 && period_secs > 0,
+            "rate limit needs at least one request per non-empty period"
+        );
+        Self {
+            capacity: f64::from(requests),
+            requests: f64::from(requests),
+            period_millis: period_secs.saturating_mul(1000) as f64,
+            key: Key::ClientIp,
+            store: Arc::new(MemoryStore::new()),
+        }
+    }
+
+    /// Allow `requests` per second.
+    #[must_use]
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, 1)
+    }
+
+    /// Allow `requests` per minute.
+    #[must_use]
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, 60)
+    }
+
+    /// Allow `requests` per hour.
+    #[must_use]
+    pub fn per_hour(requests: u32) -> Self {
+        Self::new(requests, 3600)
+    }
+
+    /// Bucket size: how many requests a rested client may send at once.
+    #[must_use]
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.capacity = f64::from(burst.max(1));
+        self
+    }
+
+    /// Key buckets by a request header instead of client IP.
+    ///
+    /// Requests without the header share one bucket.
+    #[must_use]
+    pub fn by_header(mut self, name: impl Into<String>) -> Self {
+        self.key = Key::Header(name.into());
+        self
+    }
+
+    /// Keep buckets in `store` instead of a per-instance [`MemoryStore`].
+    #[must_use]
+    pub fn store(mut self, store: impl RateLimitStore + 'static) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Whether the store keeps buckets across component instances. `false`
+    /// for the default [`MemoryStore`].
+    #[must_use]
+    pub fn is_persistent(&self) -> bool {
+        self.store.is_persistent()
+    }
+
+    /// Check the request. Returns `None` if it is within the limit.
+    #[must_use]
+    pub fn check<R: HttpResponse>(&self, req: &Request) -> Option<R> {
+        self.check_at(req, crate::time::now_millis())
+    }
+
+    fn check_at<R: HttpResponse>(&self, req: &Request, now: u64) -> Option<R> {
+        let key = self.key_for(req);
+        let bucket = self.store.load(&key).unwrap_or(Bucket {
+            tokens: self.capacity,
+            updated_millis: now,
+        });
+        match self.take(bucket, now) {
+            Ok(bucket) => {
+                self.store
+                    .save(&key, bucket, self.millis_until_full(&bucket));
+                None
+            },
+            Err(wait_millis) => {
+                let mut response: R = problem(429, "Rate limit exceeded");
+                response.headers_mut().push((
+                    RETRY_AFTER.to_string(),
+                    wait_millis.div_ceil(1000).to_string(),
+                ));
+                Some(response)
+            },
+        }
+    }
+
+    /// Refill `bucket` up to `now` and spend a token, or return how many
+    /// milliseconds until one is available.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn take(&self, bucket: Bucket, now: u64) -> Result<Bucket, u64> {
+        let elapsed = now.saturating_sub(bucket.updated_millis) as f64;
+        let tokens =
+            (bucket.tokens + elapsed * self.requests / self.period_millis).min(self.capacity);
+        if tokens >= 1.0 {
+            Ok(Bucket {
+                tokens: tokens - 1.0,
+                updated_millis: now,
+            })
+        } else {
+            Err(self.millis_for(1.0 - tokens))
+        }
+    }
+
+    fn millis_until_full(&self, bucket: &Bucket) -> u64 {
+        self.millis_for(self.capacity - bucket.tokens)
+    }
+
+    /// Milliseconds to refill `tokens`, rounded up.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn millis_for(&self, tokens: f64) -> u64 {
+        (tokens * self.period_millis / self.requests).ceil() as u64
+    }
+
+    fn key_for(&self, req: &Request) -> String {
+        let key = match &self.key {
+            Key::ClientIp => req.client_ip().map(|ip| ip.to_string()),
+            Key::Header(name) => Some(req.header_or(name, "").trim().to_string()),
+        };
+        key.filter(|key| !key.is_empty())
+            .unwrap_or_else(|| UNKNOWN_CLIENT.to_string())
+    }
+}
+
+impl std::fmt::Debug for RateLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimit")
+            .field("capacity", &self.capacity)
+            .field("requests", &self.requests)
+            .field("period_millis", &self.period_millis)
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parse a `MIK_RATE_LIMIT` value: `<requests>/<s|min|h>`, e.g. `100/min`.
+fn parse_limit(value: &str) -> Option<RateLimit> {
+    let (requests, period) = value.trim().split_once('/')?;
+    let requests: u32 = requests.trim().parse().ok().filter(|&n| n > 0)?;
+    let period_secs = match period.trim() {
+        "s" | "sec" | "second" => 1,
+        "m" | "min" | "minute" => 60,
+        "h" | "hour" => 3600,
+        _ => return None,
+    };
+    Some(RateLimit::new(requests, period_secs))
+}
+
+/// The limit from `MIK_RATE_LIMIT`, read once. `None` if unset or invalid.
+fn env_limit() -> Option<&'static RateLimit> {
+    static LIMIT: OnceLock<Option<RateLimit>> = OnceLock::new();
+    LIMIT
+        .get_or_init(|| {
+            std::env::var(RATE_LIMIT_ENV)
+                .ok()
+                .and_then(|value| parse_limit(&value))
+        })
+        .as_ref()
+}
+
+/// Rate limit by client IP using `MIK_RATE_LIMIT` (e.g. `100/min`).
+///
+/// Ready for `#[before(rate_limit::rate_limit)]`. Lets every request
+/// through when `MIK_RATE_LIMIT` is unset or invalid. Buckets are kept in a
+/// [`MemoryStore`], so the limit applies per component instance.
+#[must_use]
+pub fn rate_limit<R: HttpResponse>(req: &Request) -> Option<R> {
+    env_limit().and_then(|limit| limit.check(req))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use crate::middleware::TestResponse;
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        Request::new(
+            Method::Get,
+            "/search".to_string(),
+            headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn status(limit: &RateLimit, req: &Request, now: u64) -> Option<u16> {
+        limit
+            .check_at::<TestResponse>(req, now)
+            .map(|res| res.status)
+    }
+
+    #[test]
+    fn test_allows_up_to_capacity() {
+        let limit = RateLimit::per_second(3);
+        let req = request(&[("x-forwarded-for", "203.0.113.7")]);
+        for _ in 0..3 {
+            assert_eq!(status(&limit, &req, 1_000), None);
+        }
+        assert_eq!(status(&limit, &req, 1_000), Some(429));
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        let limit = RateLimit::per_second(2);
+        let req = request(&[("x-real-ip", "203.0.113.7")]);
+        assert_eq!(status(&limit, &req, 0), None);
+        assert_eq!(status(&limit, &req, 0), None);
+        assert_eq!(status(&limit, &req, 100), Some(429));
+        // One token every 500ms
+        assert_eq!(status(&limit, &req, 500), None);
+        assert_eq!(status(&limit, &req, 600), Some(429));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let limit = RateLimit::per_minute(1);
+        let req = request(&[]);
+        assert!(limit.check_at::<TestResponse>(&req, 0).is_none());
+        let res: TestResponse = limit.check_at(&req, 15_000).unwrap();
+        assert_eq!(res.status, 429);
+        assert!(
+            res.headers
+                .contains(&("retry-after".to_string(), "45".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_separate_buckets_per_client() {
+        let limit = RateLimit::per_hour(1);
+        let a = request(&[("x-forwarded-for", "203.0.113.7, 10.0.0.1")]);
+        let b = request(&[("x-forwarded-for", "198.51.100.2")]);
+        assert_eq!(status(&limit, &a, 0), None);
+        assert_eq!(status(&limit, &b, 0), None);
+        assert_eq!(status(&limit, &a, 0), Some(429));
+    }
+
+    #[test]
+    fn test_by_header_and_burst() {
+        let limit = RateLimit::per_minute(60).burst(1).by_header("x-api-key");
+        let a = request(&[("x-api-key", "k-1"), ("x-forwarded-for", "203.0.113.7")]);
+        let b = request(&[("x-api-key", "k-2"), ("x-forwarded-for", "203.0.113.7")]);
+        assert_eq!(status(&limit, &a, 0), None);
+        assert_eq!(status(&limit, &a, 0), Some(429));
+        assert_eq!(status(&limit, &b, 0), None);
+    }
+
+    #[test]
+    fn test_custom_store() {
+        #[derive(Default)]
+        struct Counting(MemoryStore, Mutex<u32>);
+        impl RateLimitStore for Arc<Counting> {
+            fn load(&self, key: &str) -> Option<Bucket> {
+                self.0.load(key)
+            }
+            fn save(&self, key: &str, bucket: Bucket, ttl_millis: u64) {
+                *self.1.lock().unwrap() += 1;
+                assert_eq!(ttl_millis, 1000);
+                self.0.save(key, bucket, ttl_millis);
+            }
+        }
+        let store = Arc::new(Counting::default());
+        assert!(!RateLimit::per_second(1).is_persistent());
+        let limit = RateLimit::per_second(1).store(Arc::clone(&store));
+        assert!(limit.is_persistent());
+        assert_eq!(status(&limit, &request(&[]), 0), None);
+        assert_eq!(status(&limit, &request(&[]), 0), Some(429));
+        assert_eq!(*store.1.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_memory_store_evicts() {
+        let store = MemoryStore::new();
+        let bucket = |updated_millis| Bucket {
+            tokens: 0.0,
+            updated_millis,
+        };
+        for i in 0..MAX_TRACKED_KEYS {
+            store.save(&i.to_string(), bucket(0), 10);
+        }
+        store.save("late", bucket(100), 10);
+        assert_eq!(store.buckets.lock().unwrap().len(), 1);
+        assert!(store.load("late").is_some());
+    }
+
+    #[test]
+    fn test_parse_limit() {
+        let limit = parse_limit("100/min").unwrap();
+        assert!((limit.capacity - 100.0).abs() < f64::EPSILON);
+        assert!(parse_limit(" 5 / s ").is_some());
+        assert!(parse_limit("1000/hour").is_some());
+        assert!(parse_limit("0/min").is_none());
+        assert!(parse_limit("100/day").is_none());
+        assert!(parse_limit("100").is_none());
+    }
+}
@@ -0,0 +1,135 @@
+//! Handler panic reporting.
+//!
+//! `routes!` runs every request through a guard that logs a panic as a
+//! structured `error` line with the panic message, source location, method,
+//! path and trace ID (see [`trace`](crate::trace)):
+//!
+//! ```json
+//! {"level":"error","msg":"handler panicked","panic":"index out of bounds","location":"src/lib.rs:42","method":"GET","path":"/users/7","ts":"..."}
+//! ```
+//!
+//! The log line is written by a panic hook, installed on the first request.
+//! A hook the application set earlier still runs after it.
+//!
+//! # Components Abort
+//!
+//! Components cannot recover from a panic. `wasm32` targets and the release
+//! profile (`panic = "abort"`) abort: the component instance traps right
+//! after the log line, before a response can be built, and the runtime
+//! answers with its own `500`. The trap cannot be caught from the bridge
+//! either, as it tears down the whole composed component.
+//!
+//! Only where panics unwind, such as native `cargo test` runs, does the
+//! guard turn a panic into a `500` RFC 7807 problem response.
+
+use crate::middleware::{HttpResponse, problem};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    /// Method and path of the request being handled, for the panic log.
+    static REQUEST: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+/// Log panics as a structured line, once, then run the previous hook.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            use std::io::Write;
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            let location = info
+                .location()
+                .map(|l| format!("{}:{}", l.file(), l.line()))
+                .unwrap_or_default();
+            let request = REQUEST
+                .try_with(|request| request.try_borrow().ok().and_then(|r| r.clone()))
+                .ok()
+                .flatten();
+
+            let mut fields = vec![("panic", message.as_str()), ("location", location.as_str())];
+            if let Some((method, path)) = &request {
+                fields.push(("method", method.as_str()));
+                fields.push(("path", path.as_str()));
+            }
+            let line = crate::log::__build_structured_log("error", "handler panicked", &fields);
+            let _ = writeln!(std::io::stderr(), "{line}");
+            previous(info);
+        }));
+    });
+}
+
+/// Run a request, logging a panic with its method and path.
+///
+/// An unwinding panic becomes a `500` problem response; with
+/// `panic = "abort"` the process aborts after the log line. Called by code
+/// generated by `routes!`.
+#[doc(hidden)]
+pub fn __guard<R: HttpResponse>(method: &str, path: &str, handle: impl FnOnce() -> R) -> R {
+    install_hook();
+    REQUEST.with(|request| *request.borrow_mut() = Some((method.to_string(), path.to_string())));
+    let result = panic::catch_unwind(AssertUnwindSafe(handle));
+    REQUEST.with(|request| *request.borrow_mut() = None);
+
+    result.unwrap_or_else(|_| {
+        let mut response: R = problem(500, "The request handler failed unexpectedly");
+        // The handler never reached trace::__end; echo the trace and reset it
+        if let Some(traceparent) = crate::trace::current() {
+            crate::trace::__end(traceparent, response.headers_mut());
+        }
+        response
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+
+    #[test]
+    fn test_guard_passes_response_through() {
+        let res: TestResponse = __guard("GET", "/ok", || TestResponse {
+            status: 204,
+            headers: Vec::new(),
+            body: None,
+        });
+        assert_eq!(res.status, 204);
+    }
+
+    #[test]
+    fn test_guard_recovers_from_panic() {
+        let res: TestResponse = __guard("POST", "/boom", || panic!("boom"));
+        assert_eq!(res.status, 500);
+        assert_eq!(res.headers[0].1, "application/problem+json");
+        let body = crate::json::try_parse(res.body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body.path_str(&["title"]),
+            Some("Internal Server Error".to_string())
+        );
+        assert!(REQUEST.with(|request| request.borrow().is_none()));
+    }
+
+    #[test]
+    fn test_guard_echoes_trace_after_panic() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let res: TestResponse = __guard("GET", "/traced", || {
+            crate::trace::__begin(&mut vec![(
+                "traceparent".to_string(),
+                traceparent.to_string(),
+            )]);
+            panic!("boom")
+        });
+        assert!(
+            res.headers
+                .contains(&("traceparent".to_string(), traceparent.to_string()))
+        );
+        assert_eq!(crate::trace::current(), None);
+    }
+}
@@ -0,0 +1,433 @@
+//! Replay protection with timestamp and nonce headers.
+//!
+//! Signed webhooks and service-to-service calls prove who sent a request,
+//! but not that it is sent only once: a captured request stays valid. A
+//! [`ReplayGuard`] closes that gap. Each request carries the Unix time it
+//! was sent and a unique nonce; the guard answers `401 Unauthorized` when
+//! the timestamp is outside the tolerance window or the nonce was already
+//! seen within it, and `503 Service Unavailable` when the nonce cannot be
+//! recorded, e.g. because the store is full.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::replay::{self, ReplayGuard};
+//! use std::sync::LazyLock;
+//!
+//! // Stripe-style names, one minute of clock skew
+//! fn webhook_replay(req: &Request) -> Option<Response> {
+//!     static GUARD: LazyLock<ReplayGuard> = LazyLock::new(|| {
+//!         ReplayGuard::new()
+//!             .timestamp_header("webhook-timestamp")
+//!             .nonce_header("webhook-id")
+//!             .tolerance_secs(60)
+//!     });
+//!     GUARD.check(req)
+//! }
+//!
+//! routes! {
+//!     // Verify the signature first, so unsigned requests cannot fill the store
+//!     #[before(verify_signature, webhook_replay)]
+//!     POST "/webhooks/billing" => billing_event,
+//!
+//!     // `x-timestamp` and `x-nonce`, five minutes of tolerance
+//!     #[before(auth::api_key, replay::replay_protection)]
+//!     POST "/internal/jobs" => enqueue_job,
+//! }
+//! ```
+//!
+//! The timestamp and nonce must be covered by the request signature, or an
+//! attacker can replay the request with fresh values.
+//!
+//! # Storage
+//!
+//! **With the default store, replays are only detected within one
+//! component instance.** Seen nonces live in a [`MemoryStore`], which
+//! persists only while the runtime reuses the instance. Runtimes that
+//! create a fresh instance per request (`wasmtime serve`, for one), or
+//! that run several instances side by side, accept a replayed request
+//! whenever it lands on an instance that has not seen the nonce. The SDK
+//! does not ship a shared store: implement [`NonceStore`] over a key-value
+//! store such as `wasi:keyvalue` and pass it to [`ReplayGuard::store`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use crate::middleware::{HttpResponse, problem};
+use crate::request::Request;
+
+/// Default request header carrying the Unix time in seconds.
+pub const DEFAULT_TIMESTAMP_HEADER: &str = "x-timestamp";
+
+/// Default request header carrying the nonce.
+pub const DEFAULT_NONCE_HEADER: &str = "x-nonce";
+
+/// Default tolerance between the request timestamp and the clock.
+pub const DEFAULT_TOLERANCE_SECS: u64 = 300;
+
+/// Most nonces a [`MemoryStore`] keeps.
+const MAX_TRACKED_NONCES: usize = 10_000;
+
+/// Inserts between two sweeps of expired nonces from a [`MemoryStore`].
+const SWEEP_INTERVAL: usize = 1_000;
+
+/// Longest accepted nonce.
+const MAX_NONCE_LEN: usize = 256;
+
+/// Where seen nonces are kept between requests.
+///
+/// Implement this over a shared key-value store to detect replays across
+/// component instances.
+pub trait NonceStore: Send + Sync {
+    /// Record `nonce` until `expires_millis` (Unix time in milliseconds).
+    ///
+    /// Returns `Ok(false)` if the nonce is already recorded and not
+    /// expired. `now_millis` is the current time, for stores that expire
+    /// entries themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreUnavailable`] if the nonce cannot be recorded. The
+    /// guard then rejects the request: forgetting a nonce that has not
+    /// expired would let its request be replayed.
+    fn insert(
+        &self,
+        nonce: &str,
+        expires_millis: u64,
+        now_millis: u64,
+    ) -> Result<bool, StoreUnavailable>;
+}
+
+/// A [`NonceStore`] could not record a nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)] // Built by custom stores
+pub struct StoreUnavailable;
+
+impl std::fmt::Display for StoreUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("nonce store unavailable")
+    }
+}
+
+impl std::error::Error for StoreUnavailable {}
+
+/// In-memory [`NonceStore`], local to the component instance.
+///
+/// Holds up to 10,000 nonces. Nonces are never dropped before they
+/// expire: once the store is full of live nonces, new ones are refused
+/// with [`StoreUnavailable`] until some expire. Expired nonces are swept
+/// every 1,000 inserts, or when the store is full.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    state: Mutex<Nonces>,
+}
+
+#[derive(Debug, Default)]
+struct Nonces {
+    expiry: HashMap<String, u64>,
+    /// Earliest expiry in `expiry`, so a sweep that would drop nothing is
+    /// skipped.
+    next_expiry: u64,
+    inserts_since_sweep: usize,
+}
+
+impl Nonces {
+    fn sweep(&mut self, now: u64) {
+        self.expiry.retain(|_, expires| *expires > now);
+        self.next_expiry = self.expiry.values().copied().min().unwrap_or(u64::MAX);
+        self.inserts_since_sweep = 0;
+    }
+}
+
+impl MemoryStore {
+    /// An empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NonceStore for MemoryStore {
+    fn insert(
+        &self,
+        nonce: &str,
+        expires_millis: u64,
+        now_millis: u64,
+    ) -> Result<bool, StoreUnavailable> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.expiry.get(nonce) {
+            Some(&expires) if expires > now_millis => return Ok(false),
+            Some(_) => {},
+            None => {
+                state.inserts_since_sweep += 1;
+                let full = state.expiry.len() >= MAX_TRACKED_NONCES;
+                if (full || state.inserts_since_sweep >= SWEEP_INTERVAL)
+                    && state.next_expiry <= now_millis
+                {
+                    state.sweep(now_millis);
+                }
+                if state.expiry.len() >= MAX_TRACKED_NONCES {
+                    return Err(StoreUnavailable);
+                }
+            },
+        }
+        state.next_expiry = state.next_expiry.min(expires_millis);
+        state.expiry.insert(nonce.to_string(), expires_millis);
+        drop(state);
+        Ok(true)
+    }
+}
+
+/// Timestamp and nonce check, usable as a `#[before(...)]` hook.
+#[derive(Clone)]
+pub struct ReplayGuard {
+    timestamp_header: String,
+    nonce_header: String,
+    tolerance_millis: u64,
+    store: Arc<dyn NonceStore>,
+}
+
+impl Default for ReplayGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplayGuard {
+    /// A guard reading `x-timestamp` and `x-nonce`, with five minutes of
+    /// tolerance and a per-instance [`MemoryStore`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            timestamp_header: DEFAULT_TIMESTAMP_HEADER.to_string(),
+            nonce_header: DEFAULT_NONCE_HEADER.to_string(),
+            tolerance_millis: DEFAULT_TOLERANCE_SECS * 1000,
+            store: Arc::new(MemoryStore::new()),
+        }
+    }
+
+    /// Read the Unix timestamp (seconds) from `name`.
+    #[must_use]
+    pub fn timestamp_header(mut self, name: impl Into<String>) -> Self {
+        self.timestamp_header = name.into();
+        self
+    }
+
+    /// Read the nonce from `name`.
+    #[must_use]
+    pub fn nonce_header(mut self, name: impl Into<String>) -> Self {
+        self.nonce_header = name.into();
+        self
+    }
+
+    /// Accept timestamps up to `secs` seconds away from the clock, in
+    /// either direction. Nonces are remembered for as long.
+    #[must_use]
+    pub const fn tolerance_secs(mut self, secs: u64) -> Self {
+        self.tolerance_millis = secs.saturating_mul(1000);
+        self
+    }
+
+    /// Keep nonces in `store` instead of a per-instance [`MemoryStore`].
+    #[must_use]
+    pub fn store(mut self, store: impl NonceStore + 'static) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Check the request. Returns `None` if it is fresh and not a replay.
+    #[must_use]
+    pub fn check<R: HttpResponse>(&self, req: &Request) -> Option<R> {
+        self.check_at(req, crate::time::now_millis())
+    }
+
+    fn check_at<R: HttpResponse>(&self, req: &Request, now: u64) -> Option<R> {
+        let timestamp = req.header_or(&self.timestamp_header, "").trim();
+        let nonce = req.header_or(&self.nonce_header, "").trim();
+        if timestamp.is_empty() || nonce.is_empty() {
+            return Some(problem(
+                401,
+                &format!(
+                    "Missing {} or {} header",
+                    self.timestamp_header, self.nonce_header
+                ),
+            ));
+        }
+        if nonce.len() > MAX_NONCE_LEN {
+            return Some(problem(401, "Invalid nonce"));
+        }
+        let Some(sent) = timestamp
+            .parse::<u64>()
+            .ok()
+            .map(|secs| secs.saturating_mul(1000))
+        else {
+            return Some(problem(401, "Invalid request timestamp"));
+        };
+        if sent.abs_diff(now) > self.tolerance_millis {
+            return Some(problem(401, "Request timestamp is too old or too new"));
+        }
+        // Past this, the timestamp check rejects the request anyway
+        let expires = sent.saturating_add(self.tolerance_millis);
+        match self.store.insert(nonce, expires, now) {
+            Ok(true) => None,
+            Ok(false) => Some(problem(401, "Request has already been received")),
+            Err(StoreUnavailable) => Some(problem(503, "Replay protection unavailable")),
+        }
+    }
+}
+
+impl std::fmt::Debug for ReplayGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplayGuard")
+            .field("timestamp_header", &self.timestamp_header)
+            .field("nonce_header", &self.nonce_header)
+            .field("tolerance_millis", &self.tolerance_millis)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reject replays with the default `x-timestamp` and `x-nonce` headers.
+///
+/// Ready for `#[before(replay::replay_protection)]`. Uses five minutes of
+/// tolerance and a per-instance [`MemoryStore`]; build a [`ReplayGuard`]
+/// for other settings.
+#[must_use]
+pub fn replay_protection<R: HttpResponse>(req: &Request) -> Option<R> {
+    static GUARD: OnceLock<ReplayGuard> = OnceLock::new();
+    GUARD.get_or_init(ReplayGuard::new).check(req)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use crate::middleware::TestResponse;
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn request(timestamp: &str, nonce: &str) -> Request {
+        Request::new(
+            Method::Post,
+            "/webhooks".to_string(),
+            vec![
+                ("x-timestamp".to_string(), timestamp.to_string()),
+                ("x-nonce".to_string(), nonce.to_string()),
+            ],
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn status(guard: &ReplayGuard, req: &Request, now: u64) -> Option<u16> {
+        guard
+            .check_at::<TestResponse>(req, now)
+            .map(|res| res.status)
+    }
+
+    #[test]
+    fn test_accepts_fresh_request_once() {
+        let guard = ReplayGuard::new();
+        let req = request("1700000000", "n-1");
+        assert_eq!(status(&guard, &req, NOW), None);
+        assert_eq!(status(&guard, &req, NOW + 1_000), Some(401));
+        assert_eq!(status(&guard, &request("1700000000", "n-2"), NOW), None);
+    }
+
+    #[test]
+    fn test_rejects_stale_and_future_timestamps() {
+        let guard = ReplayGuard::new().tolerance_secs(60);
+        assert_eq!(status(&guard, &request("1699999950", "a"), NOW), None);
+        assert_eq!(status(&guard, &request("1699999941", "b"), NOW), None);
+        assert_eq!(
+            status(&guard, &request("1699999941", "c"), NOW + 2_000),
+            Some(401)
+        );
+        assert_eq!(status(&guard, &request("1700000061", "d"), NOW), Some(401));
+    }
+
+    #[test]
+    fn test_rejects_missing_or_invalid_headers() {
+        let guard = ReplayGuard::new();
+        assert_eq!(status(&guard, &request("", "n"), NOW), Some(401));
+        assert_eq!(status(&guard, &request("1700000000", ""), NOW), Some(401));
+        assert_eq!(status(&guard, &request("yesterday", "n"), NOW), Some(401));
+        let long = "n".repeat(MAX_NONCE_LEN + 1);
+        assert_eq!(
+            status(&guard, &request("1700000000", &long), NOW),
+            Some(401)
+        );
+    }
+
+    #[test]
+    fn test_custom_header_names() {
+        let guard = ReplayGuard::new()
+            .timestamp_header("webhook-timestamp")
+            .nonce_header("webhook-id");
+        let req = Request::new(
+            Method::Post,
+            "/webhooks".to_string(),
+            vec![
+                ("webhook-timestamp".to_string(), "1700000000".to_string()),
+                ("webhook-id".to_string(), "msg_1".to_string()),
+            ],
+            None,
+            HashMap::new(),
+        );
+        assert_eq!(status(&guard, &req, NOW), None);
+        assert_eq!(status(&guard, &req, NOW), Some(401));
+        assert_eq!(status(&guard, &request("1700000000", "n"), NOW), Some(401));
+    }
+
+    #[test]
+    fn test_memory_store_expires() {
+        let store = MemoryStore::new();
+        assert_eq!(store.insert("a", 10, 0), Ok(true));
+        assert_eq!(store.insert("a", 10, 5), Ok(false));
+        // Expired nonces may be reused
+        assert_eq!(store.insert("a", 20, 10), Ok(true));
+    }
+
+    #[test]
+    fn test_memory_store_fails_closed_when_full() {
+        let store = MemoryStore::new();
+        for i in 0..MAX_TRACKED_NONCES {
+            assert_eq!(store.insert(&i.to_string(), 30, 0), Ok(true));
+        }
+        // Nothing has expired: no nonce is evicted to make room
+        assert_eq!(store.insert("flood", 30, 10), Err(StoreUnavailable));
+        assert_eq!(store.insert("0", 30, 10), Ok(false));
+
+        // Once they expire, a sweep frees the space
+        assert_eq!(store.insert("late", 100, 50), Ok(true));
+        assert_eq!(store.state.lock().unwrap().expiry.len(), 1);
+    }
+
+    #[test]
+    fn test_memory_store_sweeps_periodically() {
+        let store = MemoryStore::new();
+        assert_eq!(store.insert("old", 10, 0), Ok(true));
+        for i in 1..SWEEP_INTERVAL {
+            assert_eq!(store.insert(&i.to_string(), 1_000, 20), Ok(true));
+        }
+        let expiry = &store.state.lock().unwrap().expiry;
+        assert_eq!(expiry.len(), SWEEP_INTERVAL - 1);
+        assert!(!expiry.contains_key("old"));
+    }
+
+    #[test]
+    fn test_full_store_answers_503() {
+        let guard = ReplayGuard::new();
+        for i in 0..MAX_TRACKED_NONCES {
+            assert_eq!(
+                status(&guard, &request("1700000000", &i.to_string()), NOW),
+                None
+            );
+        }
+        assert_eq!(
+            status(&guard, &request("1700000000", "flood"), NOW),
+            Some(503)
+        );
+        // The first request still cannot be replayed
+        assert_eq!(status(&guard, &request("1700000000", "0"), NOW), Some(401));
+    }
+}
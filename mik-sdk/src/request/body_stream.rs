@@ -0,0 +1,284 @@
+//! Incremental request body reading.
+//!
+//! Routes marked with `#[stream]` in `routes!` receive a [`BodyStream`] instead
+//! of a buffered body. The bridge hands the raw `wasi:io` input stream to the
+//! handler, so uploads never have to fit in memory at once.
+//!
+//! # Example
+//!
+//! ```ignore
+//! routes! {
+//!     #[stream]
+//!     POST "/upload" => upload,
+//! }
+//!
+//! fn upload(mut body: BodyStream, _req: &Request) -> Response {
+//!     let mut total = 0;
+//!     while let Ok(Some(chunk)) = body.next_chunk() {
+//!         total += chunk.len();
+//!     }
+//!     ok!({ "received": total })
+//! }
+//! ```
+
+use crate::constants::{BODY_STREAM_CHUNK_SIZE, get_max_body_size};
+
+/// Boxed chunk reader backing a [`BodyStream`].
+type ChunkReader = Box<dyn FnMut(u64) -> Result<Option<Vec<u8>>, BodyError>>;
+
+/// Error type for streamed body reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BodyError {
+    /// The body exceeded the configured size limit.
+    TooLarge {
+        /// Limit in bytes.
+        limit: usize,
+    },
+    /// The underlying stream failed.
+    Read,
+}
+
+impl std::fmt::Display for BodyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLarge { limit } => write!(f, "Request body exceeds {limit} bytes"),
+            Self::Read => write!(f, "Failed to read request body"),
+        }
+    }
+}
+
+impl std::error::Error for BodyError {}
+
+/// A request body delivered in chunks.
+///
+/// Reads are capped at `MIK_MAX_BODY_SIZE` (10MB by default) unless a
+/// different limit is set with [`with_limit`](Self::with_limit). Also
+/// implements [`std::io::Read`] for use with streaming parsers.
+pub struct BodyStream {
+    reader: ChunkReader,
+    chunk_size: u64,
+    limit: usize,
+    read: usize,
+    done: bool,
+    /// Leftover bytes from a chunk partially consumed through `io::Read`.
+    pending: Vec<u8>,
+    pending_pos: usize,
+}
+
+impl BodyStream {
+    /// Create a stream from a chunk reader.
+    ///
+    /// The reader receives the maximum number of bytes wanted and returns
+    /// `Ok(None)` once the body is exhausted. Empty chunks are skipped.
+    /// Used by code generated by `routes!`.
+    #[doc(hidden)]
+    pub fn new(reader: impl FnMut(u64) -> Result<Option<Vec<u8>>, BodyError> + 'static) -> Self {
+        Self {
+            reader: Box::new(reader),
+            chunk_size: BODY_STREAM_CHUNK_SIZE,
+            limit: get_max_body_size(),
+            read: 0,
+            done: false,
+            pending: Vec::new(),
+            pending_pos: 0,
+        }
+    }
+
+    /// Create a stream over an already buffered body.
+    ///
+    /// Used when a streaming route is reached through the buffered entry
+    /// point, and handy for testing handlers.
+    #[must_use]
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        let mut bytes = Some(bytes);
+        Self::new(move |_| Ok(bytes.take().filter(|b| !b.is_empty())))
+    }
+
+    /// Set the maximum number of bytes this stream will yield.
+    #[must_use]
+    pub const fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the preferred chunk size for reads (default: 64KB).
+    #[must_use]
+    pub const fn with_chunk_size(mut self, size: u64) -> Self {
+        self.chunk_size = if size == 0 { 1 } else { size };
+        self
+    }
+
+    /// Number of bytes read so far.
+    #[inline]
+    #[must_use]
+    pub const fn bytes_read(&self) -> usize {
+        self.read
+    }
+
+    /// Whether the end of the body has been reached.
+    #[inline]
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Read the next chunk of the body.
+    ///
+    /// Returns `Ok(None)` at end of body. Once an error is returned the
+    /// stream is considered finished.
+    pub fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, BodyError> {
+        if self.pending_pos < self.pending.len() {
+            let rest = self.pending.split_off(self.pending_pos);
+            self.pending.clear();
+            self.pending_pos = 0;
+            return Ok(Some(rest));
+        }
+        while !self.done {
+            match (self.reader)(self.chunk_size) {
+                Ok(Some(chunk)) if chunk.is_empty() => {},
+                Ok(Some(chunk)) => {
+                    let total = self.read.saturating_add(chunk.len());
+                    if total > self.limit {
+                        self.done = true;
+                        return Err(BodyError::TooLarge { limit: self.limit });
+                    }
+                    self.read = total;
+                    return Ok(Some(chunk));
+                },
+                Ok(None) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Err(e);
+                },
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read the remaining body into memory.
+    pub fn read_to_end(mut self) -> Result<Vec<u8>, BodyError> {
+        let mut out = Vec::new();
+        while let Some(chunk) = self.next_chunk()? {
+            out.extend_from_slice(&chunk);
+        }
+        Ok(out)
+    }
+}
+
+impl std::io::Read for BodyStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.pending_pos >= self.pending.len() {
+            self.pending.clear();
+            self.pending_pos = 0;
+            self.pending = match self.next_chunk() {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => return Ok(0),
+                Err(e) => return Err(std::io::Error::other(e)),
+            };
+        }
+        let available = &self.pending[self.pending_pos..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pending_pos += n;
+        Ok(n)
+    }
+}
+
+impl std::fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BodyStream")
+            .field("bytes_read", &self.read)
+            .field("limit", &self.limit)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn chunked(chunks: Vec<&'static [u8]>) -> BodyStream {
+        let mut iter = chunks.into_iter();
+        BodyStream::new(move |_| Ok(iter.next().map(<[u8]>::to_vec)))
+    }
+
+    #[test]
+    fn test_next_chunk_yields_all_chunks() {
+        let mut stream = chunked(vec![b"hello ", b"", b"world"]);
+        assert_eq!(
+            stream.next_chunk().unwrap().as_deref(),
+            Some(&b"hello "[..])
+        );
+        assert_eq!(stream.next_chunk().unwrap().as_deref(), Some(&b"world"[..]));
+        assert_eq!(stream.next_chunk().unwrap(), None);
+        assert!(stream.is_done());
+        assert_eq!(stream.bytes_read(), 11);
+    }
+
+    #[test]
+    fn test_read_to_end() {
+        let stream = chunked(vec![b"a", b"bc", b"def"]);
+        assert_eq!(stream.read_to_end().unwrap(), b"abcdef");
+    }
+
+    #[test]
+    fn test_limit_exceeded() {
+        let mut stream = chunked(vec![b"1234", b"5678"]).with_limit(6);
+        assert!(stream.next_chunk().unwrap().is_some());
+        assert_eq!(stream.next_chunk(), Err(BodyError::TooLarge { limit: 6 }));
+        assert_eq!(stream.next_chunk().unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_error_ends_stream() {
+        let mut stream = BodyStream::new(|_| Err(BodyError::Read));
+        assert_eq!(stream.next_chunk(), Err(BodyError::Read));
+        assert_eq!(stream.next_chunk().unwrap(), None);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let stream = BodyStream::from_bytes(b"payload".to_vec());
+        assert_eq!(stream.read_to_end().unwrap(), b"payload");
+
+        let mut empty = BodyStream::from_bytes(Vec::new());
+        assert_eq!(empty.next_chunk().unwrap(), None);
+    }
+
+    #[test]
+    fn test_io_read_small_buffer() {
+        let mut stream = chunked(vec![b"hello", b" world"]);
+        let mut buf = [0u8; 3];
+        let mut out = Vec::new();
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(out, b"hello world");
+    }
+
+    #[test]
+    fn test_next_chunk_after_partial_io_read() {
+        let mut stream = chunked(vec![b"abcdef", b"gh"]);
+        let mut buf = [0u8; 2];
+        assert_eq!(stream.read(&mut buf).unwrap(), 2);
+        assert_eq!(stream.next_chunk().unwrap().as_deref(), Some(&b"cdef"[..]));
+        assert_eq!(stream.next_chunk().unwrap().as_deref(), Some(&b"gh"[..]));
+    }
+
+    #[test]
+    fn test_io_read_propagates_limit() {
+        let mut stream = chunked(vec![b"too long"]).with_limit(2);
+        let mut out = Vec::new();
+        assert!(Read::read_to_end(&mut stream, &mut out).is_err());
+    }
+}
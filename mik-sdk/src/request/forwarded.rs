@@ -0,0 +1,144 @@
+//! Proxy headers: `Forwarded` (RFC 7239) and the `X-Forwarded-*` family.
+//!
+//! Each proxy appends the hop it received the request from, so only the
+//! rightmost entries are trustworthy: with `n` trusted proxies the client
+//! is the `n`th entry from the right. Anything further left was supplied by
+//! the client and may be forged.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+/// One `Forwarded` element, e.g. `for=192.0.2.60;proto=https;host=example.com`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct Hop<'a> {
+    pub(super) node: Option<&'a str>,
+    pub(super) proto: Option<&'a str>,
+    pub(super) host: Option<&'a str>,
+}
+
+/// Parse `Forwarded` header lines into hops, in header order.
+pub(super) fn parse_forwarded<'a>(lines: &[&'a str]) -> Vec<Hop<'a>> {
+    lines
+        .iter()
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|element| !element.is_empty())
+        .map(|element| {
+            let mut hop = Hop::default();
+            for pair in element.split(';') {
+                let Some((name, value)) = pair.split_once('=') else {
+                    continue;
+                };
+                let value = Some(unquote(value.trim()));
+                match name.trim() {
+                    n if n.eq_ignore_ascii_case("for") => hop.node = value,
+                    n if n.eq_ignore_ascii_case("proto") => hop.proto = value,
+                    n if n.eq_ignore_ascii_case("host") => hop.host = value,
+                    _ => {},
+                }
+            }
+            hop
+        })
+        .collect()
+}
+
+/// Split comma-separated header lines (`X-Forwarded-For: a, b`) into values.
+pub(super) fn list<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    lines
+        .iter()
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// The entry `trusted` hops from the right, or the leftmost one when the
+/// request passed through fewer proxies. `None` when nothing is trusted.
+pub(super) fn trusted<T: Copy>(hops: &[T], trusted: usize) -> Option<T> {
+    if trusted == 0 {
+        return None;
+    }
+    hops.get(hops.len().saturating_sub(trusted)).copied()
+}
+
+/// Parse a node identifier into an IP, dropping quotes, brackets and port.
+///
+/// Obfuscated identifiers (`_hidden`) and `unknown` yield `None`.
+pub(super) fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = unquote(node.trim());
+    if let Some(rest) = node.strip_prefix('[') {
+        // [2001:db8::1]:4711
+        let (ip, _) = rest.split_once(']')?;
+        return ip.parse().ok();
+    }
+    if let Ok(ip) = node.parse() {
+        return Some(ip);
+    }
+    // 192.0.2.60:8080
+    let (ip, port) = node.rsplit_once(':')?;
+    port.parse::<u16>().ok()?;
+    ip.parse::<Ipv4Addr>().ok().map(IpAddr::V4)
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn test_parse_forwarded() {
+        let hops = parse_forwarded(&[
+            r#"for=192.0.2.60;proto=https;host="api.example.com", for="[2001:db8::1]:4711""#,
+            "for=unknown;by=10.0.0.1",
+        ]);
+        assert_eq!(hops.len(), 3);
+        assert_eq!(
+            hops[0],
+            Hop {
+                node: Some("192.0.2.60"),
+                proto: Some("https"),
+                host: Some("api.example.com"),
+            }
+        );
+        assert_eq!(hops[1].node, Some("[2001:db8::1]:4711"));
+        assert_eq!(hops[2].node, Some("unknown"));
+        assert_eq!(hops[2].proto, None);
+    }
+
+    #[test]
+    fn test_list() {
+        assert_eq!(
+            list(&["203.0.113.7, 10.0.0.1", " 10.0.0.2 ,"]),
+            vec!["203.0.113.7", "10.0.0.1", "10.0.0.2"]
+        );
+    }
+
+    #[test]
+    fn test_trusted_depth() {
+        let hops = ["spoofed", "client", "proxy"];
+        assert_eq!(trusted(&hops, 1), Some("proxy"));
+        assert_eq!(trusted(&hops, 2), Some("client"));
+        assert_eq!(trusted(&hops, 5), Some("spoofed"));
+        assert_eq!(trusted(&hops, 0), None);
+        assert_eq!(trusted::<&str>(&[], 1), None);
+    }
+
+    #[test]
+    fn test_parse_node() {
+        let v4 = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 60));
+        let v6 = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(parse_node("192.0.2.60"), Some(v4));
+        assert_eq!(parse_node("\"192.0.2.60:8080\""), Some(v4));
+        assert_eq!(parse_node("2001:db8::1"), Some(v6));
+        assert_eq!(parse_node("\"[2001:db8::1]:4711\""), Some(v6));
+        assert_eq!(parse_node("unknown"), None);
+        assert_eq!(parse_node("_hidden"), None);
+        assert_eq!(parse_node("192.0.2.60:x"), None);
+    }
+}
@@ -3,22 +3,30 @@
 //! This module provides the `Request` struct that wraps raw `request-data` from WIT
 //! and provides convenient accessors for path parameters, query strings, headers, and body.
 
+mod body_stream;
 mod cookie;
+mod forwarded;
 mod multipart;
+mod multipart_stream;
+mod negotiate;
 mod parsing;
 
+pub use body_stream::{BodyError, BodyStream};
 pub use cookie::{SameSite, SetCookie};
 pub use multipart::{MultipartError, Part};
-use parsing::contains_ignore_ascii_case;
+pub use multipart_stream::{MultipartStream, StreamedPart};
 pub use parsing::{DecodeError, url_decode};
+use parsing::{contains_ignore_ascii_case, media_type_matches};
 
 use crate::constants::{
     HEADER_AUTHORIZATION, HEADER_COOKIE, HEADER_TRACE_ID, MAX_FORM_FIELDS, MAX_HEADER_VALUE_LEN,
-    MAX_TOTAL_HEADERS_SIZE, MAX_URL_DECODED_LEN, MIME_MULTIPART,
+    MAX_TOTAL_HEADERS_SIZE, MAX_URL_DECODED_LEN, MIME_MULTIPART, get_trusted_proxies,
 };
+use crate::cookies::CookieKey;
 use crate::json::{self, JsonValue};
 use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::net::IpAddr;
 
 /// HTTP method enum matching the WIT definition.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -464,6 +472,39 @@ impl Request {
         self.header_opt(HEADER_CONTENT_TYPE)
     }
 
+    /// Check if the Content-Type is the given media type.
+    ///
+    /// Parameters like `charset` and case are ignored. `type/*` matches any
+    /// subtype, and `application/json` also matches `+json` types such as
+    /// `application/problem+json`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Content-Type: application/json; charset=utf-8
+    /// assert!(req.has_content_type("application/json"));
+    /// assert!(!req.has_content_type("text/*"));
+    /// ```
+    #[must_use]
+    pub fn has_content_type(&self, mime: &str) -> bool {
+        self.content_type_opt()
+            .is_some_and(|ct| media_type_matches(ct, mime))
+    }
+
+    /// Whether the Content-Type is one of `allowed`, for 415 checks.
+    ///
+    /// A missing Content-Type passes when there is no buffered body, or
+    /// when `require_header` is `false`. Used by code generated by `routes!`
+    /// for route content types and `body:` / `form:` inputs.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __content_type_allowed(&self, allowed: &[&str], require_header: bool) -> bool {
+        match self.content_type_opt() {
+            Some(ct) => allowed.iter().any(|mime| media_type_matches(ct, mime)),
+            None => !require_header || self.body().is_none(),
+        }
+    }
+
     /// Check if Content-Type is JSON (case-insensitive).
     #[inline]
     #[must_use]
@@ -547,10 +588,43 @@ impl Request {
         multipart::parse_multipart(body, boundary)
     }
 
+    /// Parse a streamed multipart/form-data body part by part.
+    ///
+    /// For `#[stream]` routes: unlike [`multipart()`](Self::multipart), parts
+    /// are parsed as body chunks arrive, so large uploads are never held in
+    /// memory.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn upload(body: BodyStream, req: &Request) -> Response {
+    ///     let mut form = ensure!(req.multipart_stream(body), 400, "Expected multipart");
+    ///     while let Ok(Some(part)) = form.next_part() {
+    ///         while let Ok(Some(chunk)) = form.next_chunk() {
+    ///             // write chunk to storage
+    ///         }
+    ///     }
+    ///     ok!({ "status": "uploaded" })
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MultipartError` if:
+    /// - Content-Type is not multipart/form-data
+    /// - No boundary parameter in Content-Type
+    pub fn multipart_stream(&self, body: BodyStream) -> Result<MultipartStream, MultipartError> {
+        let content_type = self
+            .content_type_opt()
+            .ok_or(MultipartError::NotMultipart)?;
+        MultipartStream::new(body, content_type)
+    }
+
     /// Check if client accepts a content type (via Accept header).
     ///
     /// Performs a simple case-insensitive substring match against the Accept header.
     /// Does not parse q-values; returns `true` if the MIME type is present at all.
+    /// Use [`negotiate()`](Self::negotiate) to choose between representations.
     ///
     /// ```ignore
     /// // Accept: text/html, application/json
@@ -563,6 +637,121 @@ impl Request {
             .is_some_and(|accept| contains_ignore_ascii_case(accept, mime))
     }
 
+    /// Choose the media type the client prefers among `offers`.
+    ///
+    /// Parses the Accept header with q-values and wildcards (RFC 9110
+    /// §12.5.1): each offer takes the quality of its most specific matching
+    /// range, the highest quality wins, and ties go to the more specific
+    /// match, then to the earlier offer. Returns `None` if no offer is
+    /// acceptable (respond with 406), and the first offer when there is no
+    /// Accept header.
+    ///
+    /// ```ignore
+    /// // Accept: text/html,application/xhtml+xml,*/*;q=0.8
+    /// match req.negotiate(&["application/json", "text/html"]) {
+    ///     Some("text/html") => html_page(&items),
+    ///     Some(_) => ok!({ "items": items }),
+    ///     None => error! { status: 406, title: "Not Acceptable" },
+    /// }
+    /// ```
+    #[must_use]
+    pub fn negotiate<'a>(&self, offers: &[&'a str]) -> Option<&'a str> {
+        negotiate::media_type(self.header_opt("accept"), offers)
+    }
+
+    /// Choose the locale the client prefers among `supported` language tags.
+    ///
+    /// Parses Accept-Language with q-values (RFC 4647 lookup): ranges are
+    /// tried by descending quality, matching a tag exactly, as a prefix
+    /// (`en` picks `en-US`), or by primary language (`de-CH` picks `de`).
+    /// Returns `None` if nothing matches, and the first tag when there is
+    /// no Accept-Language header, so list the default locale first.
+    ///
+    /// ```ignore
+    /// // Accept-Language: fr-CA, fr;q=0.9, en;q=0.5
+    /// let locale = req.language(&["en", "fr", "de"]).unwrap_or("en"); // "fr"
+    /// ```
+    #[must_use]
+    pub fn language<'a>(&self, supported: &[&'a str]) -> Option<&'a str> {
+        negotiate::language(self.header_opt("accept-language"), supported)
+    }
+
+    /// The client's IP address as reported by trusted reverse proxies.
+    ///
+    /// Reads `Forwarded` (RFC 7239), falling back to `X-Forwarded-For` and
+    /// then `X-Real-IP`. Proxies append to these headers, so the client is
+    /// taken `MIK_TRUSTED_PROXIES` hops (default 1) from the right; entries
+    /// further left are client-supplied and ignored. Returns `None` without
+    /// proxy headers, for obfuscated nodes like `unknown`, or when
+    /// `MIK_TRUSTED_PROXIES=0`.
+    ///
+    /// ```ignore
+    /// // X-Forwarded-For: 198.51.100.9, 203.0.113.7  (one trusted proxy)
+    /// req.client_ip()  // Some(203.0.113.7)
+    /// ```
+    #[must_use]
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip_with(get_trusted_proxies())
+    }
+
+    /// Like [`client_ip()`](Self::client_ip) with an explicit number of
+    /// trusted proxies, e.g. `2` behind a CDN and a load balancer.
+    #[must_use]
+    pub fn client_ip_with(&self, trusted_proxies: usize) -> Option<IpAddr> {
+        let forwarded = forwarded::parse_forwarded(&self.header_all("forwarded"));
+        let node = if forwarded.is_empty() {
+            let mut hops = forwarded::list(&self.header_all("x-forwarded-for"));
+            if hops.is_empty() {
+                hops.extend(self.header_opt("x-real-ip").map(str::trim));
+            }
+            forwarded::trusted(&hops, trusted_proxies)?
+        } else {
+            forwarded::trusted(&forwarded, trusted_proxies)?.node?
+        };
+        forwarded::parse_node(node)
+    }
+
+    /// The scheme the client used, e.g. `"https"` behind a TLS-terminating proxy.
+    ///
+    /// Reads `proto` from `Forwarded`, then `X-Forwarded-Proto`, using the
+    /// same trusted hop as [`client_ip()`](Self::client_ip). Defaults to `"http"`.
+    #[must_use]
+    pub fn scheme(&self) -> &str {
+        self.forwarded_value(|hop| hop.proto, "x-forwarded-proto")
+            .unwrap_or("http")
+    }
+
+    /// The host the client requested, including any port.
+    ///
+    /// Reads `host` from `Forwarded`, then `X-Forwarded-Host`, using the same
+    /// trusted hop as [`client_ip()`](Self::client_ip), then the `Host` header.
+    ///
+    /// ```ignore
+    /// let url = format!("{}://{}{}", req.scheme(), req.host().unwrap_or("localhost"), req.path());
+    /// ```
+    #[must_use]
+    pub fn host(&self) -> Option<&str> {
+        self.forwarded_value(|hop| hop.host, "x-forwarded-host")
+            .or_else(|| self.header_opt("host").map(str::trim))
+            .filter(|host| !host.is_empty())
+    }
+
+    /// A `Forwarded` parameter of the trusted hop, or the trusted entry of
+    /// its `X-Forwarded-*` counterpart.
+    fn forwarded_value<'a>(
+        &'a self,
+        param: fn(forwarded::Hop<'a>) -> Option<&'a str>,
+        legacy_header: &str,
+    ) -> Option<&'a str> {
+        let trusted = get_trusted_proxies();
+        let hops = forwarded::parse_forwarded(&self.header_all("forwarded"));
+        forwarded::trusted(&hops, trusted)
+            .and_then(param)
+            .or_else(|| {
+                forwarded::trusted(&forwarded::list(&self.header_all(legacy_header)), trusted)
+            })
+    }
+
     /// Get the first form field value from a form-urlencoded body, or a default.
     ///
     /// Parses `application/x-www-form-urlencoded` body data.
@@ -600,6 +789,19 @@ impl Request {
         self.form_cache().get(name).map_or(&[], Vec::as_slice)
     }
 
+    /// All fields of a form-urlencoded body as decoded key-value pairs.
+    ///
+    /// Values for the same key keep their order. Used by code generated by
+    /// `routes!` for `form:` inputs.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn form_params(&self) -> Vec<(String, String)> {
+        self.form_cache()
+            .iter()
+            .flat_map(|(key, values)| values.iter().map(|value| (key.clone(), value.clone())))
+            .collect()
+    }
+
     /// Get a cookie value by name from the Cookie header, or a default.
     ///
     /// Parses the `Cookie` header lazily on first access.
@@ -642,6 +844,30 @@ impl Request {
         self.cookie_cache()
     }
 
+    /// Get a signed cookie's value, verified with `key`.
+    ///
+    /// Returns `None` if the cookie is missing or its signature is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let key = CookieKey::from_env()?;
+    /// let user = req.signed_cookie(key, "user");
+    /// ```
+    #[must_use]
+    pub fn signed_cookie(&self, key: &CookieKey, name: &str) -> Option<String> {
+        key.verify(name, self.cookie_opt(name)?)
+    }
+
+    /// Get an encrypted cookie's value, decrypted with `key`.
+    ///
+    /// Returns `None` if the cookie is missing, was modified, or was not
+    /// encrypted with this key.
+    #[must_use]
+    pub fn encrypted_cookie(&self, key: &CookieKey, name: &str) -> Option<String> {
+        key.decrypt(name, self.cookie_opt(name)?)
+    }
+
     /// Parse request body as JSON using the provided parser.
     ///
     /// # Returns
@@ -22,6 +22,7 @@
 //! }
 //! ```
 
+use super::body_stream::BodyError;
 use crate::constants::MAX_MULTIPART_PARTS;
 
 /// A single part from a multipart form submission.
@@ -85,6 +86,8 @@ pub enum MultipartError {
     NoBody,
     /// Too many parts (exceeds MAX_MULTIPART_PARTS).
     TooManyParts,
+    /// Reading a streamed body failed.
+    Body(BodyError),
 }
 
 impl std::fmt::Display for MultipartError {
@@ -95,6 +98,7 @@ impl std::fmt::Display for MultipartError {
             Self::InvalidFormat => write!(f, "Invalid multipart format"),
             Self::NoBody => write!(f, "No body in request"),
             Self::TooManyParts => write!(f, "Too many parts (max: {MAX_MULTIPART_PARTS})"),
+            Self::Body(e) => write!(f, "{e}"),
         }
     }
 }
@@ -216,7 +220,7 @@ pub(super) fn parse_multipart<'a>(
 }
 
 /// Parse a single multipart part.
-fn parse_single_part(part_str: &str) -> Option<Part<'_>> {
+pub(super) fn parse_single_part(part_str: &str) -> Option<Part<'_>> {
     // Find header/body separator (CRLFCRLF or LFLF)
     let (headers_str, data_str) = if let Some(pos) = part_str.find("\r\n\r\n") {
         (&part_str[..pos], &part_str[pos + 4..])
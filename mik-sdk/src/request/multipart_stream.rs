@@ -0,0 +1,475 @@
+//! Streaming multipart/form-data parsing.
+//!
+//! [`MultipartStream`] parses a `#[stream]` route's [`BodyStream`] as parts
+//! arrive, so large uploads never have to be held in memory. Only the
+//! current part's headers and a chunk-sized window of its data are buffered.
+//!
+//! # Example
+//!
+//! ```ignore
+//! routes! {
+//!     #[stream]
+//!     POST "/upload" => upload,
+//! }
+//!
+//! fn upload(body: BodyStream, req: &Request) -> Response {
+//!     let mut form = match req.multipart_stream(body) {
+//!         Ok(form) => form,
+//!         Err(e) => return bad_request!("{e}"),
+//!     };
+//!     while let Ok(Some(part)) = form.next_part() {
+//!         let mut size = 0;
+//!         while let Ok(Some(chunk)) = form.next_chunk() {
+//!             size += chunk.len(); // write chunk to storage
+//!         }
+//!         log!(info, "part received", name: part.name(), bytes: size);
+//!     }
+//!     ok!({ "status": "uploaded" })
+//! }
+//! ```
+
+use super::body_stream::BodyStream;
+use super::multipart::{MultipartError, extract_boundary, parse_single_part};
+use crate::constants::{MAX_HEADER_VALUE_LEN, MAX_MULTIPART_PARTS};
+
+/// Headers of a part yielded by [`MultipartStream::next_part`].
+///
+/// The part's data is read with [`MultipartStream::next_chunk`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamedPart {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+}
+
+impl StreamedPart {
+    /// The field name from Content-Disposition.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The filename from Content-Disposition, if present (file upload).
+    #[inline]
+    #[must_use]
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// The Content-Type of this part, if specified.
+    #[inline]
+    #[must_use]
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+}
+
+/// Parser position within the body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Before the first boundary.
+    Preamble,
+    /// Just after a boundary delimiter.
+    Boundary,
+    /// Reading the current part's data.
+    Data,
+    /// After the closing boundary.
+    Done,
+}
+
+/// A multipart/form-data body parsed incrementally from a [`BodyStream`].
+///
+/// Call [`next_part`](Self::next_part) to advance to the next part, then
+/// [`next_chunk`](Self::next_chunk) until it returns `Ok(None)` to read that
+/// part's data. Unread data is skipped when advancing. Parts without a
+/// `name` are skipped, as with [`Request::multipart`](crate::Request::multipart).
+pub struct MultipartStream {
+    body: BodyStream,
+    /// `\n--boundary`; a preceding `\r` is stripped from part data.
+    delimiter: Vec<u8>,
+    buf: Vec<u8>,
+    state: State,
+    parts: usize,
+}
+
+impl MultipartStream {
+    /// Parse `body` using the boundary from a `multipart/form-data`
+    /// Content-Type header value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MultipartError::NoBoundary`] if the content type is not
+    /// `multipart/form-data` or has no boundary.
+    pub fn new(body: BodyStream, content_type: &str) -> Result<Self, MultipartError> {
+        let boundary = extract_boundary(content_type).ok_or(MultipartError::NoBoundary)?;
+        Ok(Self {
+            body,
+            delimiter: format!("\n--{boundary}").into_bytes(),
+            // The first boundary need not be preceded by a line break
+            buf: b"\n".to_vec(),
+            state: State::Preamble,
+            parts: 0,
+        })
+    }
+
+    /// Advance to the next part, skipping any unread data of the current one.
+    ///
+    /// Returns `Ok(None)` after the closing boundary.
+    ///
+    /// # Errors
+    ///
+    /// - [`MultipartError::InvalidFormat`] for malformed or truncated bodies
+    /// - [`MultipartError::TooManyParts`] past `MAX_MULTIPART_PARTS`
+    /// - [`MultipartError::Body`] if reading the body fails
+    pub fn next_part(&mut self) -> Result<Option<StreamedPart>, MultipartError> {
+        loop {
+            match self.state {
+                State::Done => return Ok(None),
+                State::Preamble => {
+                    if let Some(pos) = find(&self.buf, &self.delimiter) {
+                        self.buf.drain(..pos + self.delimiter.len());
+                        self.state = State::Boundary;
+                    } else {
+                        // Keep a tail that may hold the start of the delimiter
+                        let keep = self.delimiter.len() - 1;
+                        let discard = self.buf.len().saturating_sub(keep);
+                        self.buf.drain(..discard);
+                        self.fill()?;
+                    }
+                },
+                State::Data => while self.next_chunk()?.is_some() {},
+                State::Boundary => {
+                    if let Some(part) = self.read_headers()? {
+                        return Ok(Some(part));
+                    }
+                },
+            }
+        }
+    }
+
+    /// Read the next chunk of the current part's data.
+    ///
+    /// Returns `Ok(None)` at the end of the part (or before the first
+    /// [`next_part`](Self::next_part) call).
+    ///
+    /// # Errors
+    ///
+    /// - [`MultipartError::InvalidFormat`] if the body ends mid-part
+    /// - [`MultipartError::Body`] if reading the body fails
+    pub fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, MultipartError> {
+        while self.state == State::Data {
+            if let Some(pos) = find(&self.buf, &self.delimiter) {
+                let end = if pos > 0 && self.buf[pos - 1] == b'\r' {
+                    pos - 1
+                } else {
+                    pos
+                };
+                let data = self.buf[..end].to_vec();
+                self.buf.drain(..pos + self.delimiter.len());
+                self.state = State::Boundary;
+                return Ok((!data.is_empty()).then_some(data));
+            }
+            // Hold back enough bytes for a `\r` plus a partial delimiter
+            let safe = self.buf.len().saturating_sub(self.delimiter.len());
+            if safe > 0 {
+                return Ok(Some(self.buf.drain(..safe).collect()));
+            }
+            self.fill()?;
+        }
+        Ok(None)
+    }
+
+    /// Read the rest of the current part's data into memory.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`next_chunk`](Self::next_chunk).
+    pub fn read_part(&mut self) -> Result<Vec<u8>, MultipartError> {
+        let mut out = Vec::new();
+        while let Some(chunk) = self.next_chunk()? {
+            out.extend_from_slice(&chunk);
+        }
+        Ok(out)
+    }
+
+    /// Parse the line after a delimiter and the part headers that follow.
+    ///
+    /// Returns `Ok(None)` for the closing boundary and for unnamed parts
+    /// (which are left in the `Data` state to be skipped).
+    fn read_headers(&mut self) -> Result<Option<StreamedPart>, MultipartError> {
+        while self.buf.len() < 2 {
+            self.fill()?;
+        }
+        if self.buf.starts_with(b"--") {
+            self.state = State::Done;
+            self.buf.clear();
+            return Ok(None);
+        }
+
+        // Rest of the boundary line (transport padding is ignored)
+        let line_end = self.find_within_limit(b"\n")?;
+        self.buf.drain(..=line_end);
+
+        // Header block up to the blank line; may be empty
+        while self.buf.len() < 2 {
+            self.fill()?;
+        }
+        let (end, separator) = if self.buf.starts_with(b"\r\n") {
+            (0, 2)
+        } else if self.buf.starts_with(b"\n") {
+            (0, 1)
+        } else {
+            loop {
+                let crlf = find(&self.buf, b"\r\n\r\n").map(|pos| (pos, 4));
+                let lf = find(&self.buf, b"\n\n").map(|pos| (pos, 2));
+                match crlf.into_iter().chain(lf).min() {
+                    Some((end, _)) if end > MAX_HEADER_VALUE_LEN => {
+                        return Err(MultipartError::InvalidFormat);
+                    },
+                    Some(found) => break found,
+                    None if self.buf.len() > MAX_HEADER_VALUE_LEN => {
+                        return Err(MultipartError::InvalidFormat);
+                    },
+                    None => self.fill()?,
+                }
+            }
+        };
+
+        if self.parts >= MAX_MULTIPART_PARTS {
+            return Err(MultipartError::TooManyParts);
+        }
+        self.parts += 1;
+
+        let headers =
+            std::str::from_utf8(&self.buf[..end]).map_err(|_| MultipartError::InvalidFormat)?;
+        let part = parse_single_part(&format!("{headers}\r\n\r\n")).map(|part| StreamedPart {
+            name: part.name().to_string(),
+            filename: part.filename().map(String::from),
+            content_type: part.content_type().map(String::from),
+        });
+        self.buf.drain(..end + separator);
+        self.state = State::Data;
+        Ok(part)
+    }
+
+    /// Position of `needle` in the buffer, reading more of the body as
+    /// needed but never buffering more than `MAX_HEADER_VALUE_LEN` bytes.
+    fn find_within_limit(&mut self, needle: &[u8]) -> Result<usize, MultipartError> {
+        loop {
+            if let Some(pos) = find(&self.buf, needle) {
+                return Ok(pos);
+            }
+            if self.buf.len() > MAX_HEADER_VALUE_LEN {
+                return Err(MultipartError::InvalidFormat);
+            }
+            self.fill()?;
+        }
+    }
+
+    /// Append the next body chunk to the buffer.
+    fn fill(&mut self) -> Result<(), MultipartError> {
+        match self.body.next_chunk().map_err(MultipartError::Body)? {
+            Some(chunk) => {
+                self.buf.extend_from_slice(&chunk);
+                Ok(())
+            },
+            None => Err(MultipartError::InvalidFormat),
+        }
+    }
+}
+
+impl std::fmt::Debug for MultipartStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultipartStream")
+            .field("body", &self.body)
+            .field("state", &self.state)
+            .field("parts", &self.parts)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Position of the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::BodyError;
+
+    const CONTENT_TYPE: &str = "multipart/form-data; boundary=XyZ";
+
+    /// Feed `body` to a stream `chunk_size` bytes at a time.
+    fn stream(body: &[u8], chunk_size: usize) -> MultipartStream {
+        let mut chunks: Vec<Vec<u8>> = body.chunks(chunk_size).map(<[u8]>::to_vec).collect();
+        chunks.reverse();
+        let body = BodyStream::new(move |_| Ok(chunks.pop()));
+        MultipartStream::new(body, CONTENT_TYPE).unwrap()
+    }
+
+    fn collect(mut form: MultipartStream) -> Result<Vec<(StreamedPart, Vec<u8>)>, MultipartError> {
+        let mut parts = Vec::new();
+        while let Some(part) = form.next_part()? {
+            let data = form.read_part()?;
+            parts.push((part, data));
+        }
+        Ok(parts)
+    }
+
+    fn sample() -> Vec<u8> {
+        let mut body = b"preamble\r\n--XyZ\r\n\
+            Content-Disposition: form-data; name=\"title\"\r\n\
+            \r\n\
+            Hello\r\n\
+            --XyZ\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n\
+            Content-Type: application/octet-stream\r\n\
+            \r\n"
+            .to_vec();
+        // Binary data including CR/LF, dashes and a near-miss boundary
+        body.extend_from_slice(b"\x00\xff\r\n--XY\r\n-\r\n--Xy");
+        body.extend((0..=255u8).cycle().take(1000));
+        body.extend_from_slice(b"\r\n--XyZ--\r\nepilogue");
+        body
+    }
+
+    #[test]
+    fn test_parts_across_chunk_sizes() {
+        let body = sample();
+        let mut expected_file = b"\x00\xff\r\n--XY\r\n-\r\n--Xy".to_vec();
+        expected_file.extend((0..=255u8).cycle().take(1000));
+
+        for chunk_size in [1, 2, 3, 7, 16, 64, body.len()] {
+            let parts = collect(stream(&body, chunk_size)).unwrap();
+            assert_eq!(parts.len(), 2, "chunk size {chunk_size}");
+
+            assert_eq!(parts[0].0.name(), "title");
+            assert_eq!(parts[0].0.filename(), None);
+            assert_eq!(parts[0].1, b"Hello");
+
+            assert_eq!(parts[1].0.name(), "file");
+            assert_eq!(parts[1].0.filename(), Some("a.bin"));
+            assert_eq!(parts[1].0.content_type(), Some("application/octet-stream"));
+            assert_eq!(parts[1].1, expected_file, "chunk size {chunk_size}");
+        }
+    }
+
+    #[test]
+    fn test_next_part_skips_unread_data() {
+        let mut form = stream(&sample(), 5);
+        assert_eq!(form.next_part().unwrap().unwrap().name(), "title");
+        assert_eq!(form.next_part().unwrap().unwrap().name(), "file");
+        assert_eq!(form.next_part().unwrap(), None);
+        assert_eq!(form.next_part().unwrap(), None);
+    }
+
+    #[test]
+    fn test_data_is_streamed_in_bounded_chunks() {
+        let mut body = b"--XyZ\r\nContent-Disposition: form-data; name=\"big\"\r\n\r\n".to_vec();
+        body.extend(std::iter::repeat_n(b'a', 100_000));
+        body.extend_from_slice(b"\r\n--XyZ--");
+
+        let mut form = stream(&body, 4096);
+        form.next_part().unwrap().unwrap();
+        let mut total = 0;
+        while let Some(chunk) = form.next_chunk().unwrap() {
+            assert!(chunk.len() <= 4096);
+            total += chunk.len();
+        }
+        assert_eq!(total, 100_000);
+    }
+
+    #[test]
+    fn test_empty_and_unnamed_parts() {
+        let body = b"--XyZ\r\n\
+            Content-Disposition: form-data; name=\"empty\"\r\n\
+            \r\n\
+            \r\n\
+            --XyZ\r\n\
+            Content-Type: text/plain\r\n\
+            \r\n\
+            no name\r\n\
+            --XyZ\r\n\
+            \r\n\
+            no headers\r\n\
+            --XyZ--";
+        let parts = collect(stream(body, 4)).unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].0.name(), "empty");
+        assert!(parts[0].1.is_empty());
+    }
+
+    #[test]
+    fn test_lf_only_line_endings() {
+        let body = b"--XyZ\nContent-Disposition: form-data; name=\"a\"\n\nvalue\n--XyZ--\n";
+        let parts = collect(stream(body, 3)).unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].1, b"value");
+    }
+
+    #[test]
+    fn test_truncated_body() {
+        let body = b"--XyZ\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nunterminated";
+        assert_eq!(
+            collect(stream(body, 8)).unwrap_err(),
+            MultipartError::InvalidFormat
+        );
+        assert_eq!(
+            collect(stream(b"no boundary here", 8)).unwrap_err(),
+            MultipartError::InvalidFormat
+        );
+    }
+
+    #[test]
+    fn test_too_many_parts() {
+        let mut body = Vec::new();
+        for _ in 0..=MAX_MULTIPART_PARTS {
+            body.extend_from_slice(
+                b"--XyZ\r\nContent-Disposition: form-data; name=\"f\"\r\n\r\nx\r\n",
+            );
+        }
+        body.extend_from_slice(b"--XyZ--");
+        assert_eq!(
+            collect(stream(&body, 64)).unwrap_err(),
+            MultipartError::TooManyParts
+        );
+    }
+
+    #[test]
+    fn test_oversized_headers() {
+        let mut body = b"--XyZ\r\nContent-Disposition: form-data; name=\"".to_vec();
+        body.extend(std::iter::repeat_n(b'n', MAX_HEADER_VALUE_LEN + 1));
+        body.extend_from_slice(b"\"\r\n\r\nx\r\n--XyZ--");
+        assert_eq!(
+            collect(stream(&body, 1024)).unwrap_err(),
+            MultipartError::InvalidFormat
+        );
+    }
+
+    #[test]
+    fn test_body_errors_propagate() {
+        let body = BodyStream::from_bytes(sample()).with_limit(10);
+        let mut form = MultipartStream::new(body, CONTENT_TYPE).unwrap();
+        assert_eq!(
+            form.next_part().unwrap_err(),
+            MultipartError::Body(BodyError::TooLarge { limit: 10 })
+        );
+    }
+
+    #[test]
+    fn test_requires_boundary() {
+        let body = || BodyStream::from_bytes(Vec::new());
+        assert_eq!(
+            MultipartStream::new(body(), "multipart/form-data").unwrap_err(),
+            MultipartError::NoBoundary
+        );
+        assert_eq!(
+            MultipartStream::new(body(), "application/json").unwrap_err(),
+            MultipartError::NoBoundary
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Content negotiation over `Accept`-style headers (RFC 9110 §12).
+//!
+//! [`quality_list`] parses any weighted header (`Accept`, `Accept-Language`,
+//! `Accept-Encoding`); [`media_type`] picks the best offer for `Accept` and
+//! [`language`] the best locale for `Accept-Language`.
+
+/// Highest quality value, in thousandths.
+const Q_MAX: u16 = 1000;
+
+/// Parse a weighted header into `(value, q)` pairs in header order, with
+/// `q` in thousandths (`q=0.8` is 800).
+///
+/// Parameters other than `q` stay attached to the value. Elements with a
+/// malformed `q` are skipped, as RFC 9110 leaves them undefined.
+pub(super) fn quality_list(header: &str) -> Vec<(&str, u16)> {
+    header
+        .split(',')
+        .filter_map(|element| {
+            let element = element.trim();
+            if element.is_empty() {
+                return None;
+            }
+            let mut quality = Q_MAX;
+            let mut value_end = element.len();
+            for (offset, param) in param_offsets(element) {
+                let Some((name, raw)) = param.split_once('=') else {
+                    continue;
+                };
+                if name.trim().eq_ignore_ascii_case("q") {
+                    quality = parse_quality(raw.trim())?;
+                    // Accept extensions after q are not part of the value
+                    value_end = value_end.min(offset);
+                }
+            }
+            let value = element
+                .get(..value_end)?
+                .trim_end_matches([' ', ';'])
+                .trim();
+            Some((value, quality))
+        })
+        .collect()
+}
+
+/// `;`-separated parameters of an element with their byte offsets.
+fn param_offsets(element: &str) -> impl Iterator<Item = (usize, &str)> {
+    element
+        .match_indices(';')
+        .map(|(offset, _)| offset)
+        .map(move |offset| {
+            let rest = element.get(offset + 1..).unwrap_or_default();
+            (offset, rest.split(';').next().unwrap_or_default())
+        })
+}
+
+/// Parse a `qvalue`: `0`, `1`, or up to three decimals (`0.875`, `1.000`).
+fn parse_quality(raw: &str) -> Option<u16> {
+    let (whole, fraction) = raw.split_once('.').unwrap_or((raw, ""));
+    if fraction.len() > 3 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut thousandths: u16 = 0;
+    for digit in fraction.bytes().chain(std::iter::repeat(b'0')).take(3) {
+        thousandths = thousandths * 10 + u16::from(digit - b'0');
+    }
+    match whole {
+        "0" => Some(thousandths),
+        "1" if thousandths == 0 => Some(Q_MAX),
+        _ => None,
+    }
+}
+
+/// Pick the offer the `Accept` header prefers.
+///
+/// Each offer takes the quality of its most specific matching range
+/// (`text/html` over `text/*` over `*/*`). The highest quality wins; ties
+/// go to the more specific match, then to the earlier offer. Offers with
+/// `q=0` or no matching range are not acceptable. Without an `Accept`
+/// header, the first offer is returned.
+pub(super) fn media_type<'a>(accept: Option<&str>, offers: &[&'a str]) -> Option<&'a str> {
+    let Some(accept) = accept else {
+        return offers.first().copied();
+    };
+    let ranges = quality_list(accept);
+
+    let mut best: Option<(&'a str, u16, u8)> = None;
+    for &offer in offers {
+        let Some((quality, specificity)) = offer_quality(&ranges, offer) else {
+            continue;
+        };
+        if quality == 0 {
+            continue;
+        }
+        if best.is_none_or(|(_, q, s)| (quality, specificity) > (q, s)) {
+            best = Some((offer, quality, specificity));
+        }
+    }
+    best.map(|(offer, _, _)| offer)
+}
+
+/// Quality and specificity of the most specific range matching `offer`.
+fn offer_quality(ranges: &[(&str, u16)], offer: &str) -> Option<(u16, u8)> {
+    let (kind, subtype) = offer.split_once('/')?;
+    let mut matched: Option<(u16, u8)> = None;
+    for &(range, quality) in ranges {
+        // Media type parameters do not affect matching
+        let essence = range.split(';').next().unwrap_or_default().trim();
+        let Some((range_kind, range_subtype)) = essence.split_once('/') else {
+            continue;
+        };
+        let specificity = match (range_kind, range_subtype) {
+            ("*", "*") => 1,
+            (k, "*") if k.eq_ignore_ascii_case(kind) => 2,
+            (k, s) if k.eq_ignore_ascii_case(kind) && s.eq_ignore_ascii_case(subtype) => 3,
+            _ => continue,
+        };
+        if matched.is_none_or(|(_, s)| specificity > s) {
+            matched = Some((quality, specificity));
+        }
+    }
+    matched
+}
+
+/// Pick the supported locale the `Accept-Language` header prefers.
+///
+/// Ranges are tried from highest to lowest quality. A range matches a
+/// supported tag exactly, as a prefix (`en` matches `en-US`), or by its
+/// primary language (`de-CH` falls back to `de`); `*` matches the first
+/// supported tag. Tags are compared case-insensitively and ranges with
+/// `q=0` are skipped. Without a header, the first supported tag is returned.
+pub(super) fn language<'a>(header: Option<&str>, supported: &[&'a str]) -> Option<&'a str> {
+    let Some(header) = header else {
+        return supported.first().copied();
+    };
+    let mut ranges = quality_list(header);
+    // Stable sort keeps header order among equal qualities
+    ranges.sort_by_key(|&(_, quality)| std::cmp::Reverse(quality));
+
+    ranges
+        .iter()
+        .filter(|&&(_, quality)| quality > 0)
+        .find_map(|&(range, _)| {
+            if range == "*" {
+                return supported.first().copied();
+            }
+            let exact = || supported.iter().find(|tag| tag.eq_ignore_ascii_case(range));
+            let prefix = || supported.iter().find(|tag| is_subtag_prefix(range, tag));
+            let primary = || {
+                let base = range.split('-').next().unwrap_or_default();
+                supported.iter().find(|tag| tag.eq_ignore_ascii_case(base))
+            };
+            exact().or_else(prefix).or_else(primary).copied()
+        })
+}
+
+/// Whether `prefix` is `tag` truncated at a `-` boundary.
+fn is_subtag_prefix(prefix: &str, tag: &str) -> bool {
+    tag.get(..prefix.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+        && tag.as_bytes().get(prefix.len()) == Some(&b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOTH: &[&str] = &["application/json", "text/html"];
+
+    #[test]
+    fn test_quality_list() {
+        assert_eq!(
+            quality_list("text/html, application/json;q=0.9 , */*;q=0.1"),
+            vec![("text/html", 1000), ("application/json", 900), ("*/*", 100)]
+        );
+        assert_eq!(
+            quality_list("text/plain;format=flowed;q=0.5;ext=1"),
+            vec![("text/plain;format=flowed", 500)]
+        );
+        assert_eq!(
+            quality_list("en-US,en;q=0.5,,"),
+            vec![("en-US", 1000), ("en", 500)]
+        );
+        assert_eq!(
+            quality_list("a;q=2, b;q=0.1234, c;q=x, d;Q=0"),
+            vec![("d", 0)]
+        );
+    }
+
+    #[test]
+    fn test_parse_quality() {
+        assert_eq!(parse_quality("1"), Some(1000));
+        assert_eq!(parse_quality("1.000"), Some(1000));
+        assert_eq!(parse_quality("0"), Some(0));
+        assert_eq!(parse_quality("0.5"), Some(500));
+        assert_eq!(parse_quality("0.875"), Some(875));
+        assert_eq!(parse_quality("1.5"), None);
+        assert_eq!(parse_quality(".5"), None);
+        assert_eq!(parse_quality(""), None);
+    }
+
+    #[test]
+    fn test_missing_header_picks_first_offer() {
+        assert_eq!(media_type(None, BOTH), Some("application/json"));
+        assert_eq!(media_type(None, &[]), None);
+    }
+
+    #[test]
+    fn test_browser_accept_prefers_html() {
+        let accept = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+        assert_eq!(media_type(Some(accept), BOTH), Some("text/html"));
+    }
+
+    #[test]
+    fn test_quality_ordering() {
+        let accept = "text/html;q=0.5, application/json";
+        assert_eq!(media_type(Some(accept), BOTH), Some("application/json"));
+        let accept = "application/json;q=0.2, text/*;q=0.3";
+        assert_eq!(media_type(Some(accept), BOTH), Some("text/html"));
+    }
+
+    #[test]
+    fn test_specificity_breaks_ties() {
+        // Both offers get q=1; the exact match wins over */*
+        assert_eq!(media_type(Some("text/html, */*"), BOTH), Some("text/html"));
+        // Equal matches keep server order
+        assert_eq!(media_type(Some("*/*"), BOTH), Some("application/json"));
+    }
+
+    #[test]
+    fn test_most_specific_range_sets_quality() {
+        // text/html is excluded even though text/* allows it
+        let accept = "text/*, text/html;q=0";
+        assert_eq!(media_type(Some(accept), BOTH), None);
+        assert_eq!(
+            media_type(Some(accept), &["text/html", "text/plain"]),
+            Some("text/plain")
+        );
+    }
+
+    #[test]
+    fn test_not_acceptable() {
+        assert_eq!(media_type(Some("image/png"), BOTH), None);
+        assert_eq!(media_type(Some(""), BOTH), None);
+        assert_eq!(media_type(Some("*/*;q=0"), BOTH), None);
+    }
+
+    #[test]
+    fn test_case_and_params_ignored() {
+        let accept = "TEXT/HTML;charset=utf-8";
+        assert_eq!(media_type(Some(accept), BOTH), Some("text/html"));
+    }
+
+    const LOCALES: &[&str] = &["en-US", "fr", "de"];
+
+    #[test]
+    fn test_language_exact_and_quality() {
+        assert_eq!(language(Some("fr, en-US;q=0.8"), LOCALES), Some("fr"));
+        assert_eq!(language(Some("fr;q=0.5, en-us"), LOCALES), Some("en-US"));
+        assert_eq!(language(None, LOCALES), Some("en-US"));
+    }
+
+    #[test]
+    fn test_language_fallbacks() {
+        // Prefix match: en covers en-US
+        assert_eq!(language(Some("en"), LOCALES), Some("en-US"));
+        // Primary language: de-CH falls back to de
+        assert_eq!(language(Some("de-CH, fr;q=0.5"), LOCALES), Some("de"));
+        // Wildcard takes the default
+        assert_eq!(language(Some("ja, *;q=0.1"), LOCALES), Some("en-US"));
+        // "e" is not a subtag prefix of "en-US"
+        assert_eq!(language(Some("e"), LOCALES), None);
+    }
+
+    #[test]
+    fn test_language_not_acceptable() {
+        assert_eq!(language(Some("ja, zh-CN"), LOCALES), None);
+        assert_eq!(language(Some("fr;q=0"), LOCALES), None);
+        assert_eq!(language(Some(""), LOCALES), None);
+    }
+}
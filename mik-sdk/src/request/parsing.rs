@@ -44,6 +44,30 @@ pub(super) fn contains_ignore_ascii_case(haystack: &str, needle: &str) -> bool {
         .any(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
 }
 
+/// Check a `Content-Type` value against a media type (no allocation).
+///
+/// Parameters (`; charset=utf-8`) and case are ignored. `type/*` matches any
+/// subtype, and `application/json` also matches structured syntax suffixes
+/// such as `application/problem+json`.
+pub(super) fn media_type_matches(content_type: &str, expected: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    if essence.eq_ignore_ascii_case(expected) {
+        return true;
+    }
+    let Some((kind, subtype)) = essence.split_once('/') else {
+        return false;
+    };
+    match expected.split_once('/') {
+        Some((expected_kind, "*")) => kind.eq_ignore_ascii_case(expected_kind),
+        Some(("application", "json")) => {
+            kind.eq_ignore_ascii_case("application")
+                && subtype.len() > 5
+                && subtype.as_bytes()[subtype.len() - 5..].eq_ignore_ascii_case(b"+json")
+        },
+        _ => false,
+    }
+}
+
 /// Basic URL decoding (handles %XX sequences and + as space).
 ///
 /// # Errors
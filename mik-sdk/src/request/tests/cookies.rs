@@ -162,3 +162,41 @@ fn test_cookie_invalid_pairs_skipped() {
     assert_eq!(req.cookie_or("also_valid", ""), "123");
     assert_eq!(req.cookie_or("invalid", "default"), "default");
 }
+
+fn request_with_cookie(header: &str) -> Request {
+    Request::new(
+        Method::Get,
+        "/".to_string(),
+        vec![("cookie".to_string(), header.to_string())],
+        None,
+        HashMap::new(),
+    )
+}
+
+/// Cookie value as sent back by the browser (the `name=value` part only).
+fn echoed(cookie: SetCookie) -> String {
+    cookie.build().split(';').next().unwrap().to_string()
+}
+
+#[test]
+fn test_signed_cookie() {
+    let key = CookieKey::new(&[1u8; 32]).unwrap();
+    let req = request_with_cookie(&format!("{}; plain=x", echoed(key.signed("user", "alice"))));
+
+    assert_eq!(req.signed_cookie(&key, "user"), Some("alice".to_string()));
+    assert_eq!(req.signed_cookie(&key, "plain"), None);
+    assert_eq!(req.signed_cookie(&key, "missing"), None);
+}
+
+#[test]
+fn test_encrypted_cookie() {
+    let key = CookieKey::new(&[2u8; 32]).unwrap();
+    let req = request_with_cookie(&echoed(key.encrypted("session", "user:42")));
+
+    assert_eq!(
+        req.encrypted_cookie(&key, "session"),
+        Some("user:42".to_string())
+    );
+    let other = CookieKey::new(&[3u8; 32]).unwrap();
+    assert_eq!(req.encrypted_cookie(&other, "session"), None);
+}
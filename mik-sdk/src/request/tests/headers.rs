@@ -248,6 +248,135 @@ fn test_accepts_case_insensitive() {
     assert!(req.accepts("application/json"));
 }
 
+#[test]
+fn test_negotiate() {
+    let req = Request::new(
+        Method::Get,
+        "/".to_string(),
+        vec![(
+            "accept".to_string(),
+            "application/json;q=0.5, text/html".to_string(),
+        )],
+        None,
+        HashMap::new(),
+    );
+    assert_eq!(
+        req.negotiate(&["application/json", "text/html"]),
+        Some("text/html")
+    );
+    assert_eq!(
+        req.negotiate(&["application/json"]),
+        Some("application/json")
+    );
+    assert_eq!(req.negotiate(&["image/png"]), None);
+}
+
+#[test]
+fn test_negotiate_missing_header() {
+    let req = Request::new(Method::Get, "/".to_string(), vec![], None, HashMap::new());
+    assert_eq!(
+        req.negotiate(&["application/json", "text/html"]),
+        Some("application/json")
+    );
+}
+
+#[test]
+fn test_language() {
+    let req = Request::new(
+        Method::Get,
+        "/".to_string(),
+        vec![(
+            "Accept-Language".to_string(),
+            "fr-CA, fr;q=0.9, en;q=0.5".to_string(),
+        )],
+        None,
+        HashMap::new(),
+    );
+    assert_eq!(req.language(&["en", "fr", "de"]), Some("fr"));
+    assert_eq!(req.language(&["en-GB", "de"]), Some("en-GB"));
+    assert_eq!(req.language(&["de"]), None);
+
+    let req = Request::new(Method::Get, "/".to_string(), vec![], None, HashMap::new());
+    assert_eq!(req.language(&["en", "fr"]), Some("en"));
+}
+
+fn with_headers(headers: &[(&str, &str)]) -> Request {
+    Request::new(
+        Method::Get,
+        "/".to_string(),
+        headers
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+        None,
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn test_client_ip_trusted_depth() {
+    let req = with_headers(&[("X-Forwarded-For", "198.51.100.9, 203.0.113.7, 10.0.0.2")]);
+    assert_eq!(req.client_ip_with(1), "10.0.0.2".parse().ok());
+    assert_eq!(req.client_ip_with(2), "203.0.113.7".parse().ok());
+    // More trusted proxies than hops: the leftmost entry
+    assert_eq!(req.client_ip_with(9), "198.51.100.9".parse().ok());
+    assert_eq!(req.client_ip_with(0), None);
+}
+
+#[test]
+fn test_client_ip_forwarded_wins() {
+    let req = with_headers(&[
+        ("X-Forwarded-For", "198.51.100.9"),
+        ("Forwarded", "for=192.0.2.43"),
+        ("Forwarded", r#"for="[2001:db8:cafe::17]:4711";proto=https"#),
+    ]);
+    assert_eq!(req.client_ip_with(1), "2001:db8:cafe::17".parse().ok());
+    assert_eq!(req.client_ip_with(2), "192.0.2.43".parse().ok());
+
+    let req = with_headers(&[("Forwarded", "for=unknown")]);
+    assert_eq!(req.client_ip_with(1), None);
+}
+
+#[test]
+fn test_client_ip_real_ip_fallback() {
+    let req = with_headers(&[("X-Real-IP", " 203.0.113.7 ")]);
+    assert_eq!(req.client_ip_with(1), "203.0.113.7".parse().ok());
+    assert_eq!(with_headers(&[]).client_ip_with(1), None);
+    assert_eq!(
+        with_headers(&[("X-Real-IP", "bogus")]).client_ip_with(1),
+        None
+    );
+}
+
+#[test]
+fn test_scheme_and_host() {
+    // Assumes MIK_TRUSTED_PROXIES is unset (one trusted proxy)
+    let req = with_headers(&[("Host", "internal:8080")]);
+    assert_eq!(req.scheme(), "http");
+    assert_eq!(req.host(), Some("internal:8080"));
+
+    let req = with_headers(&[
+        ("Host", "internal:8080"),
+        ("X-Forwarded-Proto", "https"),
+        ("X-Forwarded-Host", "api.example.com"),
+    ]);
+    assert_eq!(req.scheme(), "https");
+    assert_eq!(req.host(), Some("api.example.com"));
+
+    let req = with_headers(&[
+        ("Host", "internal:8080"),
+        ("X-Forwarded-Proto", "http"),
+        (
+            "Forwarded",
+            r#"for=192.0.2.43;proto=https;host="shop.example.com""#,
+        ),
+    ]);
+    assert_eq!(req.scheme(), "https");
+    assert_eq!(req.host(), Some("shop.example.com"));
+
+    assert_eq!(with_headers(&[]).host(), None);
+}
+
 #[test]
 fn test_header_with_valid_utf8_special_chars() {
     // Headers with valid UTF-8 special characters
@@ -527,3 +656,55 @@ fn test_bearer_token_header_case_insensitive() {
 
     assert_eq!(req.bearer_token_or(""), "mytoken");
 }
+
+fn with_content_type(content_type: Option<&str>, body: Option<&[u8]>) -> Request {
+    Request::new(
+        Method::Post,
+        "/echo".to_string(),
+        content_type
+            .map(|ct| vec![("Content-Type".to_string(), ct.to_string())])
+            .unwrap_or_default(),
+        body.map(<[u8]>::to_vec),
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn test_has_content_type() {
+    let req = with_content_type(Some("Application/JSON; charset=utf-8"), None);
+    assert!(req.has_content_type("application/json"));
+    assert!(!req.has_content_type("application/xml"));
+
+    // +json suffix and wildcards
+    let req = with_content_type(Some("application/problem+json"), None);
+    assert!(req.has_content_type("application/json"));
+    assert!(req.has_content_type("application/*"));
+    assert!(!req.has_content_type("text/*"));
+
+    // Substrings are not matches
+    let req = with_content_type(Some("application/jsonx"), None);
+    assert!(!req.has_content_type("application/json"));
+    let req = with_content_type(Some("text/plain+json"), None);
+    assert!(!req.has_content_type("application/json"));
+
+    assert!(!with_content_type(None, None).has_content_type("application/json"));
+}
+
+#[test]
+fn test_content_type_allowed() {
+    let json = ["application/json"];
+    let body = Some(b"{}".as_slice());
+
+    assert!(with_content_type(Some("application/json"), body).__content_type_allowed(&json, true));
+    assert!(!with_content_type(Some("text/plain"), body).__content_type_allowed(&json, false));
+
+    // Missing header: fine without a body, or when not required
+    assert!(with_content_type(None, None).__content_type_allowed(&json, true));
+    assert!(with_content_type(None, body).__content_type_allowed(&json, false));
+    assert!(!with_content_type(None, body).__content_type_allowed(&json, true));
+
+    // Any of several types
+    let types = ["application/json", "application/x-www-form-urlencoded"];
+    let req = with_content_type(Some("application/x-www-form-urlencoded"), body);
+    assert!(req.__content_type_allowed(&types, true));
+}
@@ -365,3 +365,53 @@ fn test_multipart_exactly_max_parts() {
     let parts = req.multipart().unwrap();
     assert_eq!(parts.len(), MAX_MULTIPART_PARTS);
 }
+
+#[test]
+fn test_multipart_stream_matches_buffered() {
+    let boundary = "----Boundary";
+    let body = multipart_body(
+        boundary,
+        &[
+            ("title", None, None, b"Report"),
+            ("file", Some("report.csv"), Some("text/csv"), b"a,b\r\n1,2"),
+        ],
+    );
+    let req = Request::new(
+        Method::Post,
+        "/upload".to_string(),
+        vec![(
+            "content-type".to_string(),
+            format!("multipart/form-data; boundary={boundary}"),
+        )],
+        Some(body.clone()),
+        HashMap::new(),
+    );
+
+    let mut form = req
+        .multipart_stream(BodyStream::from_bytes(body).with_chunk_size(5))
+        .unwrap();
+    for part in req.multipart().unwrap() {
+        let streamed = form.next_part().unwrap().unwrap();
+        assert_eq!(streamed.name(), part.name());
+        assert_eq!(streamed.filename(), part.filename());
+        assert_eq!(streamed.content_type(), part.content_type());
+        assert_eq!(form.read_part().unwrap(), part.data());
+    }
+    assert_eq!(form.next_part().unwrap(), None);
+}
+
+#[test]
+fn test_multipart_stream_not_multipart() {
+    let req = Request::new(
+        Method::Post,
+        "/upload".to_string(),
+        vec![],
+        None,
+        HashMap::new(),
+    );
+    assert_eq!(
+        req.multipart_stream(BodyStream::from_bytes(Vec::new()))
+            .unwrap_err(),
+        MultipartError::NotMultipart
+    );
+}
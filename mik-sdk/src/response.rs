@@ -0,0 +1,314 @@
+//! Converting handler return values into responses.
+//!
+//! `routes!` passes whatever a handler returns through [`IntoResponse`], so
+//! a handler may return its `Response` directly, a plain value such as a
+//! `String`, a [`JsonValue`] or a `#[derive(Type)]` struct, or a
+//! `Result<_, Error>` and use `?` inside.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[derive(Type)]
+//! pub struct HelloResponse {
+//!     pub greeting: String,
+//! }
+//!
+//! // 200 with the JSON body
+//! fn hello(path: HelloPath, _req: &Request) -> HelloResponse {
+//!     HelloResponse { greeting: format!("Hello, {}!", path.name) }
+//! }
+//!
+//! // 201 with the JSON body, or a problem response
+//! fn create(body: CreateUser, _req: &Request) -> Result<(u16, User), Error> {
+//!     Ok((201, db::insert(body)?))
+//! }
+//! ```
+
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_JSON, MIME_TEXT};
+use crate::error::Error;
+use crate::json::{JsonValue, ToJson};
+use crate::middleware::HttpResponse;
+
+/// A value `routes!` can turn into the handler's response type `R`.
+///
+/// Implemented for:
+///
+/// | Type | Response |
+/// |------|----------|
+/// | `R` | unchanged |
+/// | `String`, `&'static str` | `200`, `text/plain` |
+/// | [`JsonValue`] | `200`, `application/json` |
+/// | `(u16, T)` where `T: ToJson` | the status, `application/json` |
+/// | `#[derive(Type)]` types | `200`, `application/json` |
+/// | [`Error`] | RFC 7807 problem with the error's status |
+/// | `Result<T, E>` | `T`'s response, or `E.into()` as an [`Error`] |
+///
+/// The derive implements it for each `Type`; there is no need to implement
+/// it by hand.
+pub trait IntoResponse<R: HttpResponse> {
+    /// Convert into a response.
+    fn into_response(self) -> R;
+}
+
+impl<R: HttpResponse> IntoResponse<R> for R {
+    #[inline]
+    fn into_response(self) -> R {
+        self
+    }
+}
+
+/// Build a JSON response with the given status.
+#[must_use]
+pub fn json<R: HttpResponse>(status: u16, value: &JsonValue) -> R {
+    R::from_parts(
+        status,
+        vec![(HEADER_CONTENT_TYPE.to_string(), MIME_JSON.to_string())],
+        Some(value.to_bytes()),
+    )
+}
+
+/// Build a plain text response with the given status.
+#[must_use]
+pub fn text<R: HttpResponse>(status: u16, body: &str) -> R {
+    R::from_parts(
+        status,
+        vec![(HEADER_CONTENT_TYPE.to_string(), MIME_TEXT.to_string())],
+        Some(body.as_bytes().to_vec()),
+    )
+}
+
+/// Build a `200` file download response, e.g. for CSV or PDF exports.
+///
+/// Sets `Content-Type`, `Content-Length` and `Content-Disposition:
+/// attachment`. Following RFC 6266, the filename is sent as an ASCII
+/// fallback in `filename` and, when it isn't plain ASCII, percent-encoded
+/// as UTF-8 in `filename*`. Path separators, quotes and control characters
+/// become `_`.
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::middleware::HttpResponse;
+/// # struct Res(Vec<(String, String)>);
+/// # impl HttpResponse for Res {
+/// #     fn from_parts(_: u16, h: Vec<(String, String)>, _: Option<Vec<u8>>) -> Self { Self(h) }
+/// #     fn status(&self) -> u16 { 200 }
+/// #     fn headers(&self) -> &[(String, String)] { &self.0 }
+/// #     fn headers_mut(&mut self) -> &mut Vec<(String, String)> { &mut self.0 }
+/// #     fn body(&self) -> Option<&[u8]> { None }
+/// # }
+/// use mik_sdk::response;
+///
+/// let res: Res = response::attachment("résumé.pdf", b"%PDF".to_vec(), "application/pdf");
+/// assert_eq!(
+///     res.headers()[2].1,
+///     "attachment; filename=\"r_sum_.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf"
+/// );
+/// ```
+#[must_use]
+pub fn attachment<R: HttpResponse>(
+    filename: &str,
+    body: impl Into<Vec<u8>>,
+    content_type: &str,
+) -> R {
+    let body = body.into();
+    R::from_parts(
+        200,
+        vec![
+            (HEADER_CONTENT_TYPE.to_string(), content_type.to_string()),
+            ("content-length".to_string(), body.len().to_string()),
+            (
+                "content-disposition".to_string(),
+                content_disposition(filename),
+            ),
+        ],
+        Some(body),
+    )
+}
+
+/// `attachment; filename="..."[; filename*=UTF-8''...]` for `filename`.
+fn content_disposition(filename: &str) -> String {
+    let safe = |c: char| !(c.is_control() || matches!(c, '/' | '\\' | '"'));
+    let fallback: String = filename
+        .chars()
+        .map(|c| if c.is_ascii() && safe(c) { c } else { '_' })
+        .collect();
+    if filename.is_ascii() {
+        return format!("attachment; filename=\"{fallback}\"");
+    }
+
+    // RFC 5987 ext-value: attr-chars as-is, every other UTF-8 byte as %XX
+    let mut encoded = String::with_capacity(filename.len() * 3);
+    for c in filename.chars() {
+        let c = if safe(c) { c } else { '_' };
+        let mut buf = [0; 4];
+        for &b in c.encode_utf8(&mut buf).as_bytes() {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                encoded.push(char::from(b));
+            } else {
+                let _ = std::fmt::Write::write_fmt(&mut encoded, format_args!("%{b:02X}"));
+            }
+        }
+    }
+    format!("attachment; filename=\"{fallback}\"; filename*=UTF-8''{encoded}")
+}
+
+impl<R: HttpResponse> IntoResponse<R> for JsonValue {
+    fn into_response(self) -> R {
+        json(200, &self)
+    }
+}
+
+impl<R: HttpResponse, T: ToJson> IntoResponse<R> for (u16, T) {
+    fn into_response(self) -> R {
+        json(self.0, &self.1.to_json())
+    }
+}
+
+impl<R: HttpResponse> IntoResponse<R> for String {
+    fn into_response(self) -> R {
+        text(200, &self)
+    }
+}
+
+impl<R: HttpResponse> IntoResponse<R> for &'static str {
+    fn into_response(self) -> R {
+        text(200, self)
+    }
+}
+
+/// A handler return type that produces a `T` body.
+///
+/// When a route declares a response type (`GET "/users/{id}" => get_user ->
+/// User`), `routes!` requires the handler's return type to implement
+/// `Returns<User>`, so the `OpenAPI` schema cannot drift from what the handler
+/// serializes. Implemented for:
+///
+/// - `T` itself, for `#[derive(Type)]` types
+/// - `(u16, U)` where `U: Returns<T>`
+/// - `Result<U, E>` where `U: Returns<T>` and `E: Into<Error>`
+/// - the handler's raw `Response`, which cannot be inspected and is
+///   accepted as-is
+#[diagnostic::on_unimplemented(
+    message = "handler returns `{Self}`, but its route declares `-> {T}`",
+    label = "expected `{T}`, `(u16, {T})` or `Result<{T}, Error>`",
+    note = "return the declared type, or change the `-> {T}` annotation in routes!"
+)]
+pub trait Returns<T> {}
+
+impl<T, U: Returns<T>> Returns<T> for (u16, U) {}
+
+impl<T, U: Returns<T>, E: Into<Error>> Returns<T> for Result<U, E> {}
+
+/// Check that a handler's return value satisfies its declared response type.
+/// This is an implementation detail of `routes!`.
+#[doc(hidden)]
+#[inline]
+pub const fn __check_returns<T, O: Returns<T>>(value: O) -> O {
+    value
+}
+
+impl<R: HttpResponse> IntoResponse<R> for Error {
+    fn into_response(self) -> R {
+        self.to_response()
+    }
+}
+
+impl<R, T, E> IntoResponse<R> for Result<T, E>
+where
+    R: HttpResponse,
+    T: IntoResponse<R>,
+    E: Into<Error>,
+{
+    fn into_response(self) -> R {
+        match self {
+            Ok(value) => value.into_response(),
+            Err(error) => error.into().to_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::TestResponse;
+    use crate::typed::ParseError;
+
+    fn handler(fail: bool) -> Result<TestResponse, Error> {
+        if fail {
+            Err(ParseError::missing("name"))?;
+        }
+        Ok(TestResponse::from_parts(204, vec![], None))
+    }
+
+    #[test]
+    fn test_response_passes_through() {
+        let res: TestResponse = TestResponse::from_parts(201, vec![], None).into_response();
+        assert_eq!(res.status, 201);
+    }
+
+    #[test]
+    fn test_result_into_response() {
+        let ok: TestResponse = handler(false).into_response();
+        assert_eq!(ok.status, 204);
+        let err: TestResponse = handler(true).into_response();
+        assert_eq!(err.status, 400);
+    }
+
+    #[test]
+    fn test_plain_values() {
+        let res: TestResponse = String::from("hi").into_response();
+        assert_eq!(res.status, 200);
+        assert_eq!(res.headers[0].1, "text/plain; charset=utf-8");
+        assert_eq!(res.body.as_deref(), Some(&b"hi"[..]));
+
+        let res: TestResponse = crate::json::obj()
+            .set("a", crate::json::int(1))
+            .into_response();
+        assert_eq!(res.status, 200);
+        assert_eq!(res.headers[0].1, "application/json");
+        assert_eq!(res.body.as_deref(), Some(&br#"{"a":1}"#[..]));
+
+        let res: TestResponse = (201, String::from("x")).into_response();
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body.as_deref(), Some(&br#""x""#[..]));
+    }
+
+    #[test]
+    fn test_attachment_headers() {
+        let res: TestResponse = attachment("report.csv", "a,b\n1,2\n", "text/csv");
+        assert_eq!(res.status, 200);
+        assert_eq!(
+            res.headers,
+            vec![
+                ("content-type".to_string(), "text/csv".to_string()),
+                ("content-length".to_string(), "8".to_string()),
+                (
+                    "content-disposition".to_string(),
+                    "attachment; filename=\"report.csv\"".to_string()
+                ),
+            ]
+        );
+        assert_eq!(res.body.as_deref(), Some(&b"a,b\n1,2\n"[..]));
+    }
+
+    #[test]
+    fn test_content_disposition_escaping() {
+        assert_eq!(
+            content_disposition("../\"evil\".txt"),
+            "attachment; filename=\"..__evil_.txt\""
+        );
+        assert_eq!(
+            content_disposition("отчёт 2024.csv"),
+            "attachment; filename=\"_____ 2024.csv\"; \
+             filename*=UTF-8''%D0%BE%D1%82%D1%87%D1%91%D1%82%202024.csv"
+        );
+    }
+
+    #[test]
+    fn test_result_with_convertible_error() {
+        let res: Result<TestResponse, ParseError> = Err(ParseError::missing("id"));
+        let res: TestResponse = res.into_response();
+        assert_eq!(res.status, 400);
+    }
+}
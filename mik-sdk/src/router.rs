@@ -0,0 +1,324 @@
+//! Route table introspection.
+//!
+//! `routes!` generates a `route_table()` function describing every route it
+//! dispatches: method, pattern, handler name, doc comment and the
+//! request/response types. Tests use it to check coverage and conventions,
+//! tools use it to generate clients or docs without parsing `OpenAPI`.
+//!
+//! ```ignore
+//! routes! {
+//!     /// List users
+//!     GET "/users" => list_users(query: ListQuery) -> UserList,
+//!     POST "/users" => create_user(body: CreateUser) -> User,
+//! }
+//!
+//! #[test]
+//! fn every_route_is_documented() {
+//!     for route in route_table() {
+//!         assert!(route.summary.is_some(), "{} {} has no doc comment", route.method, route.pattern);
+//!     }
+//! }
+//!
+//! #[test]
+//! fn lookup() {
+//!     let route = router::find(route_table(), Method::Post, "/users").unwrap();
+//!     assert_eq!(route.handler, "create_user");
+//!     assert_eq!(route.input("body"), Some("CreateUser"));
+//! }
+//! ```
+//!
+//! A route with several patterns (`GET "/" | "/index"`) has one entry per
+//! pattern. The built-in health, metrics and docs endpoints are not listed.
+//!
+//! # Reverse routing
+//!
+//! `routes!` also generates a `url_for` module with one function per
+//! handler, taking its path parameters in order and returning the path of
+//! its first pattern. Parameters are percent-encoded, and renaming a
+//! parameter or removing a route breaks the build instead of a link:
+//!
+//! ```ignore
+//! routes! {
+//!     GET "/users/{id}/posts/{post_id}" => get_post,
+//! }
+//!
+//! assert_eq!(url_for::get_post(7, "a b"), "/users/7/posts/a%20b");
+//! ```
+//!
+//! [`links`](crate::links) builds hypermedia `_links` objects from them.
+
+use crate::Method;
+
+/// One route declared in `routes!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RouteInfo {
+    /// HTTP method.
+    pub method: Method,
+    /// Path pattern, e.g. `/users/{id}` or `/posts/{id:uuid}`.
+    pub pattern: &'static str,
+    /// Name of the handler function.
+    pub handler: &'static str,
+    /// First paragraph of the doc comment.
+    pub summary: Option<&'static str>,
+    /// Remaining doc comment paragraphs.
+    pub description: Option<&'static str>,
+    /// `OpenAPI` tag.
+    pub tag: &'static str,
+    /// Typed inputs as `(source, type)`, e.g. `("body", "CreateUser")`.
+    ///
+    /// Sources are `path`, `body`, `query`, `form`, `proto`, `headers` and
+    /// `extract`.
+    pub inputs: &'static [(&'static str, &'static str)],
+    /// Response type from `-> Type`.
+    pub response: Option<&'static str>,
+    /// Success status code.
+    pub status: u16,
+    /// Marked `#[deprecated]`.
+    pub deprecated: bool,
+    /// Receives the body as a `BodyStream` (`#[stream]`).
+    pub stream: bool,
+    /// Accepted media types from `"/path" (json, form)`, empty for any.
+    pub consumes: &'static [&'static str],
+}
+
+impl RouteInfo {
+    /// A route with no docs, inputs or response type, answering `200`.
+    #[must_use]
+    pub const fn new(method: Method, pattern: &'static str, handler: &'static str) -> Self {
+        Self {
+            method,
+            pattern,
+            handler,
+            summary: None,
+            description: None,
+            tag: "Default",
+            inputs: &[],
+            response: None,
+            status: 200,
+            deprecated: false,
+            stream: false,
+            consumes: &[],
+        }
+    }
+
+    /// Set the summary.
+    #[must_use]
+    pub const fn summary(mut self, summary: &'static str) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Set the description.
+    #[must_use]
+    pub const fn description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Set the tag.
+    #[must_use]
+    pub const fn tag(mut self, tag: &'static str) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Set the typed inputs.
+    #[must_use]
+    pub const fn inputs(mut self, inputs: &'static [(&'static str, &'static str)]) -> Self {
+        self.inputs = inputs;
+        self
+    }
+
+    /// Set the response type.
+    #[must_use]
+    pub const fn response(mut self, response: &'static str) -> Self {
+        self.response = Some(response);
+        self
+    }
+
+    /// Set the success status code.
+    #[must_use]
+    pub const fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Mark as deprecated.
+    #[must_use]
+    pub const fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+
+    /// Mark as streaming.
+    #[must_use]
+    pub const fn stream(mut self) -> Self {
+        self.stream = true;
+        self
+    }
+
+    /// Set the accepted media types.
+    #[must_use]
+    pub const fn consumes(mut self, consumes: &'static [&'static str]) -> Self {
+        self.consumes = consumes;
+        self
+    }
+
+    /// The type of the input read from `source` (`"body"`, `"query"`, ...).
+    #[must_use]
+    pub fn input(&self, source: &str) -> Option<&'static str> {
+        self.inputs
+            .iter()
+            .find(|(s, _)| *s == source)
+            .map(|(_, ty)| *ty)
+    }
+
+    /// Names of the path parameters, in pattern order.
+    #[must_use]
+    pub fn params(&self) -> Vec<&'static str> {
+        self.pattern
+            .split('/')
+            .filter_map(split_param)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Whether a request for `method` and `path` would be routed here.
+    ///
+    /// Uses the default matching rules (exact, case-sensitive, trailing
+    /// slash significant); any query string is ignored.
+    #[must_use]
+    pub fn matches(&self, method: Method, path: &str) -> bool {
+        if method != self.method {
+            return false;
+        }
+        let path = path.split_once('?').map_or(path, |(path, _)| path);
+        let mut expected = self.pattern.split('/');
+        let mut actual = path.split('/');
+        loop {
+            match (expected.next(), actual.next()) {
+                (None, None) => return true,
+                (Some(expected), Some(actual)) => match split_param(expected) {
+                    Some((_, constraint)) => {
+                        if actual.is_empty() || !constraint.is_none_or(|c| satisfies(c, actual)) {
+                            return false;
+                        }
+                    },
+                    None if expected == actual => {},
+                    None => return false,
+                },
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// The first route in `routes` matching `method` and `path`.
+#[must_use]
+pub fn find<'a>(routes: &'a [RouteInfo], method: Method, path: &str) -> Option<&'a RouteInfo> {
+    routes.iter().find(|route| route.matches(method, path))
+}
+
+/// Split a `{name}` or `{name:constraint}` pattern segment.
+fn split_param(segment: &str) -> Option<(&str, Option<&str>)> {
+    let param = segment.strip_prefix('{')?.strip_suffix('}')?;
+    Some(match param.split_once(':') {
+        Some((name, constraint)) => (name, Some(constraint)),
+        None => (param, None),
+    })
+}
+
+/// Whether a path segment satisfies a parameter constraint.
+fn satisfies(constraint: &str, value: &str) -> bool {
+    match constraint {
+        "int" => value.parse::<i64>().is_ok(),
+        "uint" => value.parse::<u64>().is_ok(),
+        "uuid" => {
+            value.len() == 36
+                && value.bytes().enumerate().all(|(i, b)| {
+                    if matches!(i, 8 | 13 | 18 | 23) {
+                        b == b'-'
+                    } else {
+                        b.is_ascii_hexdigit()
+                    }
+                })
+        },
+        "alpha" => value.bytes().all(|b| b.is_ascii_alphabetic()),
+        "alnum" => value.bytes().all(|b| b.is_ascii_alphanumeric()),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static ROUTES: &[RouteInfo] = &[
+        RouteInfo::new(Method::Get, "/users", "list_users")
+            .summary("List users")
+            .tag("Users")
+            .inputs(&[("query", "ListQuery")])
+            .response("User"),
+        RouteInfo::new(Method::Post, "/users", "create_user")
+            .inputs(&[("body", "CreateUser")])
+            .response("User")
+            .status(201)
+            .consumes(&["json"]),
+        RouteInfo::new(Method::Get, "/users/{id:uint}", "get_user").inputs(&[("path", "UserId")]),
+        RouteInfo::new(Method::Get, "/users/{id:uint}/posts/{slug}", "user_post").deprecated(),
+    ];
+
+    #[test]
+    fn test_defaults() {
+        let route = RouteInfo::new(Method::Delete, "/", "root");
+        assert_eq!(route.status, 200);
+        assert_eq!(route.tag, "Default");
+        assert_eq!(route.summary, None);
+        assert!(route.inputs.is_empty());
+        assert!(!route.deprecated && !route.stream);
+    }
+
+    #[test]
+    fn test_input() {
+        assert_eq!(ROUTES[1].input("body"), Some("CreateUser"));
+        assert_eq!(ROUTES[1].input("query"), None);
+        assert_eq!(ROUTES[1].status, 201);
+    }
+
+    #[test]
+    fn test_params() {
+        assert_eq!(ROUTES[3].params(), vec!["id", "slug"]);
+        assert!(ROUTES[0].params().is_empty());
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(ROUTES[0].matches(Method::Get, "/users"));
+        assert!(ROUTES[0].matches(Method::Get, "/users?page=2"));
+        assert!(!ROUTES[0].matches(Method::Post, "/users"));
+        assert!(!ROUTES[0].matches(Method::Get, "/users/"));
+        assert!(ROUTES[2].matches(Method::Get, "/users/42"));
+        assert!(!ROUTES[2].matches(Method::Get, "/users/abc"));
+        assert!(!ROUTES[2].matches(Method::Get, "/users/"));
+        assert!(ROUTES[3].matches(Method::Get, "/users/1/posts/hello"));
+        assert!(!ROUTES[3].matches(Method::Get, "/users/1/posts"));
+    }
+
+    #[test]
+    fn test_find() {
+        let route = find(ROUTES, Method::Post, "/users");
+        assert_eq!(route.map(|r| r.handler), Some("create_user"));
+        assert_eq!(find(ROUTES, Method::Delete, "/users"), None);
+    }
+
+    #[test]
+    fn test_satisfies() {
+        assert!(satisfies("int", "-3"));
+        assert!(!satisfies("uint", "-3"));
+        assert!(satisfies("uuid", "67e55044-10b1-426f-9247-bb680e5fe0c8"));
+        assert!(!satisfies("uuid", "67e55044"));
+        assert!(satisfies("alpha", "abc"));
+        assert!(!satisfies("alnum", "a-b"));
+    }
+}
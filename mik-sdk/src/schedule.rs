@@ -0,0 +1,404 @@
+//! Scheduled jobs (cron).
+//!
+//! `jobs!` declares cron-style jobs next to `routes!`, in the same module,
+//! and exports them through the `mik:core/schedule` interface:
+//!
+//! ```ignore
+//! routes! {
+//!     GET "/reports" => list_reports,
+//! }
+//!
+//! jobs! {
+//!     "0 */15 * * * *" => purge_sessions, // every 15 minutes
+//!     "@daily" => send_report,
+//! }
+//!
+//! fn purge_sessions(_timestamp: u64) {
+//!     sessions::purge_expired();
+//! }
+//!
+//! fn send_report(timestamp: u64) -> Result<(), String> {
+//!     let report = build_report(timestamp);
+//!     http_client::post(REPORT_URL).json(report.as_bytes()).send().map_err(|e| e.to_string())?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! A job is a `fn(u64)` taking the scheduled time in Unix seconds and
+//! returning `()` or a `Result` whose error is reported to the host. Panics
+//! are caught and reported as errors. Tasks spawned with
+//! [`tasks::spawn`](crate::tasks::spawn) run once the job returns.
+//!
+//! # Schedules
+//!
+//! Six fields, `sec min hour day-of-month month day-of-week`, or the usual
+//! five without seconds (seconds are then `0`). Fields accept `*`, values,
+//! ranges (`1-5`), steps (`*/10`, `0-30/5`), lists (`1,15`) and English
+//! names (`jan`, `mon`). Days match when either day field matches, unless
+//! one of them is `*`. `@yearly`, `@monthly`, `@weekly`, `@daily` and
+//! `@hourly` are shorthands. Times are UTC.
+//!
+//! # Requirements
+//!
+//! The handler world must export `mik:core/schedule@0.1.0` (as in the
+//! `user-handler-jobs` world). The bridge adapts it to the host's
+//! scheduler: built with the `spin-cron` feature, it handles Spin cron
+//! trigger events by running the jobs due at the event's time. Hosts that
+//! call component exports directly, such as wasmCloud, can call
+//! `run-due` or `run` on the composed component themselves.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Error from parsing a cron schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronError(String);
+
+impl fmt::Display for CronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron schedule: {}", self.0)
+    }
+}
+
+impl std::error::Error for CronError {}
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed cron schedule. Each field is a bitset of the values it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cron {
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    /// Parse a 5- or 6-field cron expression or an `@` shorthand.
+    pub fn parse(expr: &str) -> Result<Self, CronError> {
+        let expr = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 0 1 1 *",
+            "@monthly" => "0 0 0 1 * *",
+            "@weekly" => "0 0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 0 * * *",
+            "@hourly" => "0 0 * * * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let (second, minute, hour, day, month, weekday) = match *fields.as_slice() {
+            [minute, hour, day, month, weekday] => ("0", minute, hour, day, month, weekday),
+            [second, minute, hour, day, month, weekday] => {
+                (second, minute, hour, day, month, weekday)
+            },
+            _ => {
+                let count = fields.len();
+                return Err(CronError(format!("expected 5 or 6 fields, got {count}")));
+            },
+        };
+
+        let weekdays = parse_field(weekday, 0, 7, &WEEKDAY_NAMES)?;
+        Ok(Self {
+            seconds: parse_field(second, 0, 59, &[])?,
+            minutes: parse_field(minute, 0, 59, &[])?,
+            hours: parse_field(hour, 0, 23, &[])?,
+            days: parse_field(day, 1, 31, &[])?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES)?,
+            // 7 is Sunday too
+            weekdays: (weekdays | (weekdays >> 7)) & 0x7f,
+            any_day: matches!(day, "*" | "?"),
+            any_weekday: matches!(weekday, "*" | "?"),
+        })
+    }
+
+    /// Whether the schedule fires at `timestamp` (Unix seconds, UTC).
+    #[must_use]
+    pub fn matches(&self, timestamp: u64) -> bool {
+        let days_since_epoch = timestamp / 86400;
+        let (_, month, day) = crate::time::civil_from_days(days_since_epoch);
+        // 1970-01-01 was a Thursday
+        let weekday = (days_since_epoch + 4) % 7;
+
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => bit(self.weekdays, weekday),
+            (false, true) => bit(self.days, day),
+            (false, false) => bit(self.days, day) || bit(self.weekdays, weekday),
+        };
+        day_matches
+            && bit(self.seconds, timestamp % 60)
+            && bit(self.minutes, timestamp / 60 % 60)
+            && bit(self.hours, timestamp / 3600 % 24)
+            && bit(self.months, month)
+    }
+}
+
+const fn bit(set: u64, value: u64) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parse one field into a bitset of the values in `min..=max`.
+fn parse_field(field: &str, min: u64, max: u64, names: &[&str]) -> Result<u64, CronError> {
+    let value = |text: &str| -> Result<u64, CronError> {
+        let lower = text.to_ascii_lowercase();
+        let parsed = names
+            .iter()
+            .position(|name| *name == lower)
+            .map(|index| index as u64 + min)
+            .or_else(|| text.parse().ok());
+        match parsed {
+            Some(v) if (min..=max).contains(&v) => Ok(v),
+            _ => Err(CronError(format!("'{text}' is not in {min}-{max}"))),
+        }
+    };
+
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<usize>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(CronError(format!("invalid step in '{part}'"))),
+            },
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" | "?" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `5/10` runs from 5 to the end of the range
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let v = value(range)?;
+                    (v, v)
+                },
+            },
+        };
+        if start > end {
+            return Err(CronError(format!("empty range '{range}'")));
+        }
+        for v in (start..=end).step_by(step) {
+            set |= 1 << v;
+        }
+    }
+    Ok(set)
+}
+
+/// Outcome of a job: `()` or a `Result` with a displayable error.
+pub trait IntoJobResult {
+    /// Convert to the result reported to the host.
+    fn into_job_result(self) -> Result<(), String>;
+}
+
+impl IntoJobResult for () {
+    fn into_job_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: fmt::Display> IntoJobResult for Result<(), E> {
+    fn into_job_result(self) -> Result<(), String> {
+        self.map_err(|err| err.to_string())
+    }
+}
+
+/// A job function, called with the scheduled time in Unix seconds.
+pub type JobFn = fn(u64) -> Result<(), String>;
+
+/// A job declared with `jobs!`.
+#[derive(Debug, Clone, Copy)]
+pub struct Job {
+    name: &'static str,
+    schedule: &'static str,
+    run: JobFn,
+}
+
+impl Job {
+    /// A job named `name` running `run` on `schedule`.
+    #[must_use]
+    pub const fn new(name: &'static str, schedule: &'static str, run: JobFn) -> Self {
+        Self {
+            name,
+            schedule,
+            run,
+        }
+    }
+
+    /// Job name: the function name in `jobs!`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Cron schedule, as written.
+    #[must_use]
+    pub const fn schedule(&self) -> &'static str {
+        self.schedule
+    }
+
+    /// Run the job for `timestamp`, turning a panic into an error.
+    pub fn run(&self, timestamp: u64) -> Result<(), String> {
+        crate::tasks::__begin();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| (self.run)(timestamp)))
+            .unwrap_or_else(|_| Err("job panicked".to_string()));
+        if let Err(err) = &result {
+            crate::log_error!("Job '{}' failed: {}", self.name, err);
+        }
+        crate::tasks::__run_pending();
+        result
+    }
+}
+
+// ============================================================================
+// DISPATCH (used by jobs!)
+// ============================================================================
+
+/// Run the job named `name`.
+///
+/// Called by code generated by `jobs!` for `run`.
+#[doc(hidden)]
+pub fn __run(jobs: &[Job], name: &str, timestamp: u64) -> Result<(), String> {
+    jobs.iter()
+        .find(|job| job.name == name)
+        .ok_or_else(|| format!("unknown job '{name}'"))?
+        .run(timestamp)
+}
+
+/// Run every job due at `timestamp`; returns the failed jobs and errors.
+///
+/// Called by code generated by `jobs!` for `run-due`.
+#[doc(hidden)]
+#[must_use]
+pub fn __run_due(jobs: &[Job], timestamp: u64) -> Vec<(String, String)> {
+    let mut failed = Vec::new();
+    for job in jobs {
+        let due = match Cron::parse(job.schedule) {
+            Ok(cron) => cron.matches(timestamp),
+            Err(err) => {
+                crate::log_warn!("Job '{}' skipped: {}", job.name, err);
+                false
+            },
+        };
+        if due && let Err(err) = job.run(timestamp) {
+            failed.push((job.name.to_string(), err));
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    // 2025-01-06T09:30:00Z, a Monday
+    const MONDAY_0930: u64 = 1_736_155_800;
+
+    thread_local! {
+        static RAN: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn every_minute(_: u64) -> Result<(), String> {
+        RAN.with(|ran| ran.borrow_mut().push("every_minute"));
+        Ok(())
+    }
+
+    fn failing(_: u64) -> Result<(), String> {
+        Err("boom".to_string())
+    }
+
+    fn panicking(_: u64) -> Result<(), String> {
+        panic!("boom")
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let cron = Cron::parse("*/15 9-17 * jan,jun mon-fri").unwrap();
+        assert_eq!(cron.seconds, 1);
+        assert_eq!(cron.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+        assert_eq!(cron.hours, (9..=17).fold(0_u64, |set, h| set | 1 << h));
+        assert_eq!(cron.months, 1 << 1 | 1 << 6);
+        assert_eq!(cron.weekdays, 0b011_1110);
+        assert!(cron.any_day && !cron.any_weekday);
+    }
+
+    #[test]
+    fn test_parse_sunday_as_seven() {
+        assert_eq!(Cron::parse("0 0 * * 7").unwrap().weekdays, 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Cron::parse("* * * *").is_err());
+        assert!(Cron::parse("60 * * * *").is_err());
+        assert!(Cron::parse("* * 0 * *").is_err());
+        assert!(Cron::parse("*/0 * * * *").is_err());
+        assert!(Cron::parse("5-1 * * * *").is_err());
+        assert!(Cron::parse("@sometimes").is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(Cron::parse("30 9 * * mon").unwrap().matches(MONDAY_0930));
+        assert!(Cron::parse("*/10 * * * * *").unwrap().matches(MONDAY_0930));
+        assert!(
+            Cron::parse("@daily")
+                .unwrap()
+                .matches(MONDAY_0930 - 9 * 3600 - 1800)
+        );
+        assert!(!Cron::parse("@hourly").unwrap().matches(MONDAY_0930));
+        assert!(!Cron::parse("30 9 * * tue").unwrap().matches(MONDAY_0930));
+        // 5-field schedules fire on second 0 only
+        assert!(!Cron::parse("* * * * *").unwrap().matches(MONDAY_0930 + 1));
+    }
+
+    #[test]
+    fn test_matches_either_day_field() {
+        // The 6th (a Monday) matches through the weekday field
+        assert!(Cron::parse("30 9 1 * mon").unwrap().matches(MONDAY_0930));
+        assert!(!Cron::parse("30 9 1 * tue").unwrap().matches(MONDAY_0930));
+        assert!(Cron::parse("30 9 6 * *").unwrap().matches(MONDAY_0930));
+    }
+
+    #[test]
+    fn test_run_due() {
+        let jobs = [
+            Job::new("every_minute", "* * * * *", every_minute),
+            Job::new("hourly", "@hourly", every_minute),
+            Job::new("failing", "30 9 * * *", failing),
+            Job::new("panicking", "30 9 * * *", panicking),
+            Job::new("invalid", "not a schedule", every_minute),
+        ];
+        let failed = __run_due(&jobs, MONDAY_0930);
+        assert_eq!(
+            failed,
+            vec![
+                ("failing".to_string(), "boom".to_string()),
+                ("panicking".to_string(), "job panicked".to_string()),
+            ]
+        );
+        assert_eq!(RAN.with(|ran| ran.take()), ["every_minute"]);
+    }
+
+    #[test]
+    fn test_run_by_name() {
+        let jobs = [Job::new("every_minute", "* * * * *", every_minute)];
+        assert!(__run(&jobs, "every_minute", 0).is_ok());
+        assert_eq!(
+            __run(&jobs, "other", 0),
+            Err("unknown job 'other'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_into_job_result() {
+        assert_eq!(().into_job_result(), Ok(()));
+        let err: Result<(), &str> = Err("nope");
+        assert_eq!(err.into_job_result(), Err("nope".to_string()));
+    }
+}
@@ -0,0 +1,144 @@
+//! Secrets: keys, tokens and passwords read without exposing them.
+//!
+//! Secrets come from the same sources as [`config`](crate::config): the
+//! environment and, with the `runtime-config` feature, Spin variables
+//! (declare them with `secret = true`) through `wasi:config/store`. Values
+//! are wrapped in [`Secret`], which prints as `[REDACTED]`, so they cannot
+//! end up in logs by accident.
+//!
+//! ```ignore
+//! use mik_sdk::secrets;
+//!
+//! let token = secrets::require("UPSTREAM_TOKEN")?;
+//! let res = fetch!(GET "https://api.example.com/items",
+//!     headers: { "authorization": format!("Bearer {}", token.expose()) }).send()?;
+//! ```
+//!
+//! # Rotation
+//!
+//! [`rotating`] reads `NAME` and `NAME_PREVIOUS`. Sign with the current
+//! secret and accept anything the previous one produced until it expires:
+//!
+//! ```text
+//! MIK_COOKIE_SECRET=<new secret>
+//! MIK_COOKIE_SECRET_PREVIOUS=<old secret>
+//! ```
+//!
+//! [`CookieKey::from_env`](crate::cookies::CookieKey::from_env) uses this,
+//! and [`CookieKey::from_secrets`](crate::cookies::CookieKey::from_secrets)
+//! takes a [`Rotating`] set from anywhere else.
+
+use crate::config::{self, ConfigError};
+
+pub use crate::config::Secret;
+
+/// Suffix of the variable holding the previous secret during rotation.
+pub const PREVIOUS_SUFFIX: &str = "_PREVIOUS";
+
+/// Secret named `name`, or `None` if it is unset or empty.
+#[must_use]
+pub fn get(name: &str) -> Option<Secret> {
+    config::lookup(name)
+        .filter(|value| !value.is_empty())
+        .map(Secret::new)
+}
+
+/// Secret named `name`.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Missing` if it is unset or empty.
+pub fn require(name: &str) -> Result<Secret, ConfigError> {
+    get(name).ok_or_else(|| ConfigError::Missing {
+        key: name.to_string(),
+    })
+}
+
+/// Current secret `name` plus `NAME_PREVIOUS` when set.
+///
+/// Returns `None` if the current secret is unset.
+#[must_use]
+pub fn rotating(name: &str) -> Option<Rotating> {
+    let current = get(name)?;
+    let previous = get(&format!("{name}{PREVIOUS_SUFFIX}"));
+    Some(Rotating::new(current).with_previous(previous))
+}
+
+/// A current secret and the previous ones still accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotating {
+    current: Secret,
+    previous: Vec<Secret>,
+}
+
+impl Rotating {
+    /// A set with only a current secret.
+    #[must_use]
+    pub const fn new(current: Secret) -> Self {
+        Self {
+            current,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Also accept these previous secrets, newest first.
+    #[must_use]
+    pub fn with_previous(mut self, previous: impl IntoIterator<Item = Secret>) -> Self {
+        self.previous.extend(previous);
+        self
+    }
+
+    /// The secret used to sign or encrypt.
+    #[must_use]
+    pub const fn current(&self) -> &Secret {
+        &self.current
+    }
+
+    /// Previous secrets, newest first.
+    #[must_use]
+    pub fn previous(&self) -> &[Secret] {
+        &self.previous
+    }
+
+    /// All secrets, current first, for verifying or decrypting.
+    pub fn all(&self) -> impl Iterator<Item = &Secret> {
+        std::iter::once(&self.current).chain(&self.previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing() {
+        let name = "MIK_SECRETS_TEST_UNSET";
+        assert!(get(name).is_none());
+        assert!(rotating(name).is_none());
+        assert_eq!(
+            require(name),
+            Err(ConfigError::Missing {
+                key: name.to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_rotating_order() {
+        let set = Rotating::new(Secret::new("new"))
+            .with_previous([Secret::new("old"), Secret::new("older")]);
+        assert_eq!(set.current().expose(), "new");
+        assert_eq!(set.previous().len(), 2);
+        let all: Vec<&str> = set.all().map(Secret::expose).collect();
+        assert_eq!(all, ["new", "old", "older"]);
+    }
+
+    #[test]
+    fn test_debug_redacted() {
+        let set = Rotating::new(Secret::new("hunter2")).with_previous(Some(Secret::new("old")));
+        assert_eq!(
+            format!("{set:?}"),
+            "Rotating { current: Secret([REDACTED]), previous: [Secret([REDACTED])] }"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Server-Sent Events (SSE).
+//!
+//! Build [`Event`]s, collect them into an [`EventStream`] and return it with
+//! the `sse!` macro. Each event becomes one chunk of a streamed response, so
+//! the bridge flushes it to the client as soon as it is produced.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::sse::{Event, EventStream};
+//!
+//! fn ticks(_req: &Request) -> Response {
+//!     let mut n = 0;
+//!     let events = EventStream::new(move || {
+//!         n += 1;
+//!         (n <= 3).then(|| Event::new(n.to_string()).event("tick").id(n.to_string()))
+//!     })
+//!     .retry(5000);
+//!     sse!(events)
+//! }
+//! ```
+//!
+//! Produces:
+//!
+//! ```text
+//! retry: 5000
+//!
+//! event: tick
+//! id: 1
+//! data: 1
+//!
+//! ...
+//! ```
+
+use crate::json::JsonValue;
+use crate::stream::ChunkedBody;
+
+/// A single Server-Sent Event.
+///
+/// Line breaks in `data` are split across multiple `data:` lines. Line
+/// breaks in the event name, id and comment are removed so a value can
+/// never inject extra fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Event {
+    name: Option<String>,
+    data: Option<String>,
+    id: Option<String>,
+    retry: Option<u64>,
+    comment: Option<String>,
+}
+
+impl Event {
+    /// Create an event with the given data.
+    #[must_use]
+    pub fn new(data: impl Into<String>) -> Self {
+        Self {
+            data: Some(data.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Create an event whose data is a JSON value.
+    #[must_use]
+    pub fn json(value: &JsonValue) -> Self {
+        Self::new(value.to_string())
+    }
+
+    /// Create a comment-only event, often used as a keep-alive.
+    #[must_use]
+    pub fn comment(text: impl Into<String>) -> Self {
+        Self {
+            comment: Some(text.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the event name (`event:` field).
+    #[must_use]
+    pub fn event(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the event id (`id:` field), used by clients for `Last-Event-ID`.
+    #[must_use]
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the client reconnection delay in milliseconds (`retry:` field).
+    #[must_use]
+    pub const fn retry(mut self, millis: u64) -> Self {
+        self.retry = Some(millis);
+        self
+    }
+
+    /// Encode the event in `text/event-stream` format, including the
+    /// terminating blank line.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(comment) = &self.comment {
+            writeln!(f, ": {}", single_line(comment))?;
+        }
+        if let Some(name) = &self.name {
+            writeln!(f, "event: {}", single_line(name))?;
+        }
+        if let Some(id) = &self.id {
+            // NUL in an id makes clients ignore the field entirely
+            writeln!(f, "id: {}", single_line(id).replace('\0', ""))?;
+        }
+        if let Some(retry) = self.retry {
+            writeln!(f, "retry: {retry}")?;
+        }
+        if let Some(data) = &self.data {
+            for line in data.split("\r\n").flat_map(|l| l.split(['\n', '\r'])) {
+                writeln!(f, "data: {line}")?;
+            }
+        }
+        writeln!(f)
+    }
+}
+
+/// Strip line breaks from a single-line field value.
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], "")
+}
+
+/// Boxed event producer backing an [`EventStream`].
+type EventSource = Box<dyn FnMut() -> Option<Event>>;
+
+/// A stream of events sent as a `text/event-stream` response.
+pub struct EventStream {
+    source: EventSource,
+    retry: Option<u64>,
+}
+
+impl EventStream {
+    /// Create a stream from an event producer.
+    ///
+    /// The producer is called until it returns `None`, which closes the
+    /// connection.
+    pub fn new(source: impl FnMut() -> Option<Event> + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            retry: None,
+        }
+    }
+
+    /// Create a stream from a list of events.
+    #[must_use]
+    pub fn from_events<I>(events: I) -> Self
+    where
+        I: IntoIterator<Item = Event>,
+        I::IntoIter: 'static,
+    {
+        let mut iter = events.into_iter();
+        Self::new(move || iter.next())
+    }
+
+    /// Send a `retry:` field before the first event.
+    #[must_use]
+    pub const fn retry(mut self, millis: u64) -> Self {
+        self.retry = Some(millis);
+        self
+    }
+
+    /// Convert into a chunked response body, one event per chunk.
+    #[must_use]
+    pub fn into_body(self) -> ChunkedBody {
+        let Self { mut source, retry } = self;
+        let mut preamble = retry.map(|ms| format!("retry: {ms}\n\n").into_bytes());
+        ChunkedBody::new(move || preamble.take().or_else(|| source().map(|e| e.to_bytes())))
+    }
+}
+
+impl std::fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream")
+            .field("retry", &self.retry)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+
+    fn text(event: &Event) -> String {
+        event.to_string()
+    }
+
+    #[test]
+    fn test_data_only() {
+        assert_eq!(text(&Event::new("hello")), "data: hello\n\n");
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let event = Event::new("payload").event("update").id("42").retry(3000);
+        assert_eq!(
+            text(&event),
+            "event: update\nid: 42\nretry: 3000\ndata: payload\n\n"
+        );
+    }
+
+    #[test]
+    fn test_multiline_data() {
+        let event = Event::new("line1\nline2\r\nline3\rline4");
+        assert_eq!(
+            text(&event),
+            "data: line1\ndata: line2\ndata: line3\ndata: line4\n\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_data_still_sends_field() {
+        assert_eq!(text(&Event::new("")), "data: \n\n");
+    }
+
+    #[test]
+    fn test_field_injection_stripped() {
+        let event = Event::new("x").event("a\ndata: evil").id("1\r\n2\0");
+        assert_eq!(text(&event), "event: adata: evil\nid: 12\ndata: x\n\n");
+    }
+
+    #[test]
+    fn test_comment() {
+        assert_eq!(text(&Event::comment("keep-alive")), ": keep-alive\n\n");
+    }
+
+    #[test]
+    fn test_json_event() {
+        let event = Event::json(&json::obj().set("n", json::int(1)));
+        assert_eq!(text(&event), "data: {\"n\":1}\n\n");
+    }
+
+    #[test]
+    fn test_stream_one_event_per_chunk() {
+        let mut body = EventStream::from_events(vec![Event::new("a"), Event::new("b")])
+            .retry(1000)
+            .into_body();
+        assert_eq!(body.next_chunk(), Some(b"retry: 1000\n\n".to_vec()));
+        assert_eq!(body.next_chunk(), Some(b"data: a\n\n".to_vec()));
+        assert_eq!(body.next_chunk(), Some(b"data: b\n\n".to_vec()));
+        assert_eq!(body.next_chunk(), None);
+    }
+
+    #[test]
+    fn test_stream_from_producer() {
+        let mut n = 0;
+        let body = EventStream::new(move || {
+            n += 1;
+            (n <= 2).then(|| Event::new(n.to_string()))
+        })
+        .into_body();
+        assert_eq!(body.collect_bytes(), b"data: 1\n\ndata: 2\n\n");
+    }
+}
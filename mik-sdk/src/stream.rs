@@ -0,0 +1,166 @@
+//! Streaming response bodies.
+//!
+//! A [`ChunkedBody`] produces the response body piece by piece. Return one
+//! with the `stream!` macro and the bridge writes each chunk to the client
+//! as soon as it is produced, so large exports never sit in memory whole.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use mik_sdk::stream::ChunkedBody;
+//!
+//! fn export(_req: &Request) -> Response {
+//!     let mut page = 0;
+//!     let body = ChunkedBody::new(move || {
+//!         page += 1;
+//!         load_rows_as_csv(page) // Option<Vec<u8>>, None when done
+//!     });
+//!     stream!(body, content_type: "text/csv")
+//! }
+//!
+//! // Or from any iterator of chunks
+//! stream!(ChunkedBody::from_iter(vec!["a\n", "b\n"]))
+//! ```
+//!
+//! # How It Works
+//!
+//! `stream!` registers the body for the current request and returns the
+//! status and headers as a regular response. After the handler returns, the
+//! bridge calls `take-response-stream` and pulls chunks until the body ends.
+//! Handler components are single-threaded, so the registration is a simple
+//! thread-local slot that `routes!` clears at the start of every request.
+
+use std::cell::RefCell;
+
+/// Boxed chunk producer backing a [`ChunkedBody`].
+type ChunkProducer = Box<dyn FnMut() -> Option<Vec<u8>>>;
+
+/// A response body produced incrementally.
+pub struct ChunkedBody {
+    producer: ChunkProducer,
+    done: bool,
+}
+
+impl ChunkedBody {
+    /// Create a body from a chunk producer.
+    ///
+    /// The producer is called until it returns `None`. Empty chunks are
+    /// skipped.
+    pub fn new(producer: impl FnMut() -> Option<Vec<u8>> + 'static) -> Self {
+        Self {
+            producer: Box::new(producer),
+            done: false,
+        }
+    }
+
+    /// Create a body from an iterator of chunks.
+    #[allow(clippy::should_implement_trait)] // Boxed 'static iterator, not FromIterator
+    pub fn from_iter<I>(chunks: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Vec<u8>>,
+        I::IntoIter: 'static,
+    {
+        let mut iter = chunks.into_iter();
+        Self::new(move || iter.next().map(Into::into))
+    }
+
+    /// Produce the next non-empty chunk, or `None` once the body is complete.
+    pub fn next_chunk(&mut self) -> Option<Vec<u8>> {
+        while !self.done {
+            match (self.producer)() {
+                Some(chunk) if chunk.is_empty() => {},
+                Some(chunk) => return Some(chunk),
+                None => self.done = true,
+            }
+        }
+        None
+    }
+
+    /// Collect the remaining chunks into a single buffer.
+    ///
+    /// Useful in tests, or when a streaming handler is called directly.
+    #[must_use]
+    pub fn collect_bytes(mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        while let Some(chunk) = self.next_chunk() {
+            out.extend_from_slice(&chunk);
+        }
+        out
+    }
+}
+
+impl std::fmt::Debug for ChunkedBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkedBody")
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// PENDING BODY (used by stream! and routes!)
+// ============================================================================
+
+thread_local! {
+    static PENDING: RefCell<Option<ChunkedBody>> = const { RefCell::new(None) };
+}
+
+/// Register the streaming body for the current response.
+///
+/// Called by the `stream!` macro. Replaces any previously registered body.
+#[doc(hidden)]
+pub fn __register(body: ChunkedBody) {
+    PENDING.with(|pending| *pending.borrow_mut() = Some(body));
+}
+
+/// Take the registered streaming body, if any.
+///
+/// Called by code generated by `routes!`.
+#[doc(hidden)]
+#[must_use]
+pub fn __take_registered() -> Option<ChunkedBody> {
+    PENDING.with(|pending| pending.borrow_mut().take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_skips_empty_chunks() {
+        let mut chunks = vec![b"b".to_vec(), Vec::new(), b"a".to_vec()];
+        let mut body = ChunkedBody::new(move || chunks.pop());
+        assert_eq!(body.next_chunk(), Some(b"a".to_vec()));
+        assert_eq!(body.next_chunk(), Some(b"b".to_vec()));
+        assert_eq!(body.next_chunk(), None);
+        assert_eq!(body.next_chunk(), None);
+    }
+
+    #[test]
+    fn test_from_iter_strings() {
+        let body = ChunkedBody::from_iter(vec!["id,name\n", "1,alice\n"]);
+        assert_eq!(body.collect_bytes(), b"id,name\n1,alice\n");
+    }
+
+    #[test]
+    fn test_producer_not_called_after_end() {
+        let mut calls = 0;
+        let mut body = ChunkedBody::new(move || {
+            calls += 1;
+            assert!(calls <= 1, "producer called after returning None");
+            None
+        });
+        assert_eq!(body.next_chunk(), None);
+        assert_eq!(body.next_chunk(), None);
+    }
+
+    #[test]
+    fn test_register_and_take() {
+        assert!(__take_registered().is_none());
+        __register(ChunkedBody::from_iter(vec!["x"]));
+        let body = __take_registered().expect("registered body");
+        assert_eq!(body.collect_bytes(), b"x");
+        assert!(__take_registered().is_none());
+    }
+}
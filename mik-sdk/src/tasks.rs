@@ -0,0 +1,173 @@
+//! Work deferred until after the response is sent.
+//!
+//! [`spawn`] queues a closure while a request is handled. Once the bridge
+//! has sent the response, it calls the handler's `run-tasks` export and the
+//! queue runs in order, on the same component instance, so the client never
+//! waits for it:
+//!
+//! ```ignore
+//! fn create_order(body: OrderInput, _req: &Request) -> Response {
+//!     let order = save(&body);
+//!     let payload = order.to_json().to_string();
+//!     tasks::spawn(move || {
+//!         for url in webhook_urls() {
+//!             let _ = http_client::post(&url).json(payload.as_bytes()).send();
+//!         }
+//!     });
+//!     created!(format!("/orders/{}", order.id), order.to_json())
+//! }
+//! ```
+//!
+//! Deferred tasks still run inside the request's instance, so they count
+//! toward the runtime's execution limits, and are lost if the instance traps.
+//! They are for best-effort side effects, not durable jobs.
+//!
+//! # Inline Fallback
+//!
+//! Tasks run immediately, inside [`spawn`], when:
+//!
+//! - `MIK_TASKS=inline` is set, e.g. for runtimes that stop the instance as
+//!   soon as the response is complete,
+//! - no request is being handled, e.g. a handler called from a unit test,
+//! - a task spawns another task.
+//!
+//! A task left queued because the bridge never called `run-tasks` runs at
+//! the start of the next request on the same instance.
+
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::OnceLock;
+
+/// Environment variable selecting when tasks run: `deferred` or `inline`.
+pub const TASKS_ENV: &str = "MIK_TASKS";
+
+type Task = Box<dyn FnOnce()>;
+
+thread_local! {
+    static QUEUE: RefCell<Vec<Task>> = RefCell::new(Vec::new());
+    static DEFERRING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether `MIK_TASKS=inline` is set, read once.
+fn inline_configured() -> bool {
+    static INLINE: OnceLock<bool> = OnceLock::new();
+    *INLINE.get_or_init(|| {
+        std::env::var(TASKS_ENV).is_ok_and(|value| value.trim().eq_ignore_ascii_case("inline"))
+    })
+}
+
+/// Run `task` after the response is sent, or right away when deferring is
+/// not possible (see the [module docs](self)).
+pub fn spawn(task: impl FnOnce() + 'static) {
+    if DEFERRING.with(Cell::get) {
+        QUEUE.with(|queue| queue.borrow_mut().push(Box::new(task)));
+    } else {
+        run(Box::new(task));
+    }
+}
+
+/// Number of tasks waiting for the response to be sent.
+#[must_use]
+pub fn pending() -> usize {
+    QUEUE.with(|queue| queue.borrow().len())
+}
+
+/// Run one task; a panic is contained so the remaining tasks still run.
+fn run(task: Task) {
+    if panic::catch_unwind(AssertUnwindSafe(task)).is_err() {
+        crate::log_warn!("Deferred task panicked");
+    }
+}
+
+/// Start deferring tasks for a new request.
+///
+/// Runs tasks left over from a previous request first. Called by code
+/// generated by `routes!`.
+#[doc(hidden)]
+pub fn __begin() {
+    __run_pending();
+    DEFERRING.with(|deferring| deferring.set(!inline_configured()));
+}
+
+/// Run the queued tasks, in order.
+///
+/// Called by code generated by `routes!` when the bridge calls `run-tasks`.
+#[doc(hidden)]
+pub fn __run_pending() {
+    DEFERRING.with(|deferring| deferring.set(false));
+    loop {
+        let tasks = QUEUE.with(|queue| std::mem::take(&mut *queue.borrow_mut()));
+        if tasks.is_empty() {
+            break;
+        }
+        tasks.into_iter().for_each(run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn recorder() -> (Log, impl Fn(&'static str) + Clone) {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let push = {
+            let log = Rc::clone(&log);
+            move |entry| log.borrow_mut().push(entry)
+        };
+        (log, push)
+    }
+
+    #[test]
+    fn test_spawn_outside_request_runs_inline() {
+        let (log, push) = recorder();
+        spawn(move || push("task"));
+        assert_eq!(*log.borrow(), ["task"]);
+        assert_eq!(pending(), 0);
+    }
+
+    #[test]
+    fn test_deferred_until_run_pending() {
+        let (log, push) = recorder();
+        DEFERRING.with(|deferring| deferring.set(true));
+        let first = push.clone();
+        spawn(move || first("first"));
+        let second = push.clone();
+        spawn(move || {
+            second("second");
+            // Spawned from a task: runs right away
+            let nested = second.clone();
+            spawn(move || nested("nested"));
+        });
+        push("response");
+        assert_eq!(pending(), 2);
+
+        __run_pending();
+        assert_eq!(*log.borrow(), ["response", "first", "second", "nested"]);
+        assert_eq!(pending(), 0);
+        assert!(!DEFERRING.with(Cell::get));
+    }
+
+    #[test]
+    fn test_begin_runs_leftovers() {
+        let (log, push) = recorder();
+        DEFERRING.with(|deferring| deferring.set(true));
+        spawn(move || push("leftover"));
+        __begin();
+        assert_eq!(*log.borrow(), ["leftover"]);
+        assert!(DEFERRING.with(Cell::get));
+        __run_pending();
+    }
+
+    #[test]
+    fn test_panicking_task_does_not_stop_queue() {
+        let (log, push) = recorder();
+        DEFERRING.with(|deferring| deferring.set(true));
+        spawn(|| panic!("boom"));
+        spawn(move || push("after"));
+        __run_pending();
+        assert_eq!(*log.borrow(), ["after"]);
+    }
+}
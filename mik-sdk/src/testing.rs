@@ -0,0 +1,810 @@
+//! Test harness that drives a `routes!` router natively.
+//!
+//! Under `cargo test`, `routes!` generates a `test_client()` function that
+//! feeds requests straight into the handler, so unit tests run without
+//! composing the component or launching a WASI runtime.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! routes! {
+//!     GET "/hello/{name}" => hello,
+//! }
+//!
+//! #[cfg(test)]
+//! mod tests {
+//!     use super::*;
+//!
+//!     #[test]
+//!     fn greets_by_name() {
+//!         test_client()
+//!             .get("/hello/Bob")
+//!             .send()
+//!             .assert_status(200)
+//!             .assert_header("content-type", "application/json")
+//!             .assert_json("/greeting", "Hello, Bob!");
+//!     }
+//!
+//!     #[test]
+//!     fn creates_user() {
+//!         let client = test_client().header("authorization", "Bearer test-token");
+//!         let res = client
+//!             .post("/users")
+//!             .json(&json!({ "name": "Alice" }))
+//!             .send();
+//!         res.assert_status(201).assert_snapshot("create_user");
+//!     }
+//! }
+//! ```
+//!
+//! # Snapshots
+//!
+//! [`TestResponse::assert_snapshot`] compares the status and body with
+//! `snapshots/<name>.snap` next to `Cargo.toml`. JSON bodies are stored
+//! pretty-printed with sorted keys. Missing snapshots are written on first
+//! run; set `MIK_UPDATE_SNAPSHOTS=1` to overwrite changed ones.
+//!
+//! # Examples in schemas
+//!
+//! [`assert_example`] builds a body from a type's `x_example` field values
+//! and checks it still parses and validates, so documented examples cannot
+//! drift from the rules.
+//!
+//! # Limitations
+//!
+//! Everything runs in-process on the native target: `time` and `random`
+//! use their std fallbacks, streamed responses are collected into the body,
+//! and background tasks run before `send()` returns. Routes taking a
+//! `BodyStream` and outbound `http_client` calls need the WASI runtime.
+
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_FORM_URLENCODED, MIME_JSON};
+use crate::encoding::percent_encode;
+use crate::json::{self, JsonValue, RawValue, ToJson};
+use crate::middleware::HttpResponse;
+use crate::request::Method;
+use crate::typed::{FromJson, OpenApiSchema, Validate};
+use miniserde::json::{Array, Object};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Environment variable that makes [`TestResponse::assert_snapshot`]
+/// overwrite existing snapshots.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "MIK_UPDATE_SNAPSHOTS";
+
+/// Router entry point generated by `routes!`.
+#[doc(hidden)]
+pub type Dispatch = fn(Method, String, Vec<(String, String)>, Option<Vec<u8>>) -> TestResponse;
+
+/// Sends requests through a router without a WASI runtime.
+///
+/// Get one from the `test_client()` function generated by `routes!`.
+#[derive(Debug, Clone)]
+pub struct TestClient {
+    dispatch: Dispatch,
+    headers: Vec<(String, String)>,
+}
+
+impl TestClient {
+    /// Create a client for a router entry point. Called by `routes!`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn new(dispatch: Dispatch) -> Self {
+        Self {
+            dispatch,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Send `name: value` with every request from this client.
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Start a request with any method.
+    #[must_use]
+    pub fn request(&self, method: Method, path: &str) -> TestRequest<'_> {
+        TestRequest {
+            client: self,
+            method,
+            path: path.to_string(),
+            headers: self.headers.clone(),
+            body: None,
+        }
+    }
+
+    /// Start a `GET` request.
+    #[must_use]
+    pub fn get(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::Get, path)
+    }
+
+    /// Start a `POST` request.
+    #[must_use]
+    pub fn post(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::Post, path)
+    }
+
+    /// Start a `PUT` request.
+    #[must_use]
+    pub fn put(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::Put, path)
+    }
+
+    /// Start a `PATCH` request.
+    #[must_use]
+    pub fn patch(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::Patch, path)
+    }
+
+    /// Start a `DELETE` request.
+    #[must_use]
+    pub fn delete(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::Delete, path)
+    }
+
+    /// Start a `HEAD` request.
+    #[must_use]
+    pub fn head(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::Head, path)
+    }
+
+    /// Start an `OPTIONS` request.
+    #[must_use]
+    pub fn options(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::Options, path)
+    }
+}
+
+/// A request being built by a [`TestClient`].
+#[derive(Debug)]
+#[must_use = "call send() to run the request"]
+pub struct TestRequest<'a> {
+    client: &'a TestClient,
+    method: Method,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl TestRequest<'_> {
+    /// Add a header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add `Authorization: Bearer <token>`.
+    pub fn bearer(self, token: &str) -> Self {
+        self.header("authorization", &format!("Bearer {token}"))
+    }
+
+    /// Add a cookie, joining the `Cookie` header if one is already set.
+    pub fn cookie(mut self, name: &str, value: &str) -> Self {
+        let pair = format!("{name}={value}");
+        match self
+            .headers
+            .iter_mut()
+            .find(|(header, _)| header.eq_ignore_ascii_case("cookie"))
+        {
+            Some((_, cookies)) => {
+                cookies.push_str("; ");
+                cookies.push_str(&pair);
+            },
+            None => self.headers.push(("cookie".to_string(), pair)),
+        }
+        self
+    }
+
+    /// Append a percent-encoded query parameter to the path.
+    pub fn query(mut self, name: &str, value: &str) -> Self {
+        let separator = if self.path.contains('?') { '&' } else { '?' };
+        let _ = write!(
+            self.path,
+            "{separator}{}={}",
+            percent_encode(name.as_bytes()),
+            percent_encode(value.as_bytes())
+        );
+        self
+    }
+
+    /// Set a raw body.
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Set a JSON body and `Content-Type: application/json`.
+    pub fn json(self, value: &impl ToJson) -> Self {
+        self.header(HEADER_CONTENT_TYPE, MIME_JSON)
+            .body(value.to_json().to_bytes())
+    }
+
+    /// Set a form-urlencoded body and its `Content-Type`.
+    pub fn form(self, fields: &[(&str, &str)]) -> Self {
+        let body = fields
+            .iter()
+            .map(|(name, value)| {
+                format!(
+                    "{}={}",
+                    percent_encode(name.as_bytes()),
+                    percent_encode(value.as_bytes())
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        self.header(HEADER_CONTENT_TYPE, MIME_FORM_URLENCODED)
+            .body(body)
+    }
+
+    /// Run the request through the router.
+    ///
+    /// Streamed bodies are collected into the response and background
+    /// tasks run before this returns.
+    #[must_use]
+    pub fn send(self) -> TestResponse {
+        let mut response = (self.client.dispatch)(self.method, self.path, self.headers, self.body);
+        if let Some(mut stream) = crate::stream::__take_registered() {
+            while let Some(chunk) = stream.next_chunk() {
+                response.body.extend(chunk);
+            }
+        }
+        crate::tasks::__run_pending();
+        response
+    }
+}
+
+/// A response returned by [`TestRequest::send`], with chainable assertions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl TestResponse {
+    /// HTTP status code.
+    #[must_use]
+    pub const fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// First value of a header (case-insensitive).
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All response headers.
+    #[must_use]
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Raw body bytes (empty when there is no body).
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Body as UTF-8, or `""` if it is not valid UTF-8.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        std::str::from_utf8(&self.body).unwrap_or_default()
+    }
+
+    /// Body parsed as JSON.
+    #[must_use]
+    pub fn json(&self) -> Option<JsonValue> {
+        json::try_parse_full(&self.body)
+    }
+
+    /// Assert the status code.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the body when the status differs.
+    #[track_caller]
+    pub fn assert_status(&self, expected: u16) -> &Self {
+        assert!(
+            self.status == expected,
+            "expected status {expected}, got {}\nbody: {}",
+            self.status,
+            self.text()
+        );
+        self
+    }
+
+    /// Assert a header's first value.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the header is missing or has another value.
+    #[track_caller]
+    pub fn assert_header(&self, name: &str, expected: &str) -> &Self {
+        assert_eq!(self.header(name), Some(expected), "header `{name}`");
+        self
+    }
+
+    /// Assert the JSON value at a JSON Pointer, e.g. `/items/0/id`.
+    ///
+    /// Values are compared in their serialized form, so `1` and `1.0` differ.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the body is not JSON, the pointer selects nothing, or
+    /// the value differs.
+    #[track_caller]
+    pub fn assert_json(&self, pointer: &str, expected: impl ToJson) -> &Self {
+        let Some(body) = self.json() else {
+            panic!("response body is not JSON: {}", self.text());
+        };
+        let actual = body.pointer(pointer).map(|value| value.to_string());
+        let expected = expected.to_json().to_string();
+        assert_eq!(
+            actual.as_deref(),
+            Some(expected.as_str()),
+            "JSON at `{pointer}`"
+        );
+        self
+    }
+
+    /// Compare the status and body with `snapshots/<name>.snap`.
+    ///
+    /// The snapshot is written when missing, or when `MIK_UPDATE_SNAPSHOTS`
+    /// is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the snapshot differs, or cannot be read or written.
+    #[track_caller]
+    pub fn assert_snapshot(&self, name: &str) -> &Self {
+        let path = snapshot_dir().join(format!("{name}.snap"));
+        let actual = self.snapshot();
+        let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some();
+
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if !update => {
+                assert!(
+                    expected == actual,
+                    "snapshot `{name}` differs (set {UPDATE_SNAPSHOTS_ENV}=1 to update)\n--- expected\n{expected}\n--- actual\n{actual}"
+                );
+            },
+            _ => {
+                let written = path
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|()| std::fs::write(&path, &actual));
+                if let Err(err) = written {
+                    panic!("cannot write snapshot {}: {err}", path.display());
+                }
+            },
+        }
+        self
+    }
+
+    /// Status line plus body, with JSON pretty-printed for stable diffs.
+    fn snapshot(&self) -> String {
+        let body = self
+            .json()
+            .map_or_else(|| self.text().to_string(), |value| pretty(value.value(), 0));
+        format!("status: {}\n\n{body}\n", self.status)
+    }
+}
+
+impl HttpResponse for TestResponse {
+    fn from_parts(status: u16, headers: Vec<(String, String)>, body: Option<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers,
+            body: body.unwrap_or_default(),
+        }
+    }
+
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    fn headers_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.headers
+    }
+
+    fn body(&self) -> Option<&[u8]> {
+        (!self.body.is_empty()).then_some(self.body.as_slice())
+    }
+}
+
+/// Example JSON for `T`, built from the `#[field(x_example = ...)]` values
+/// in its schema.
+///
+/// Optional fields without an example are left out. Nested types use their
+/// own examples, enums their first variant, and a scalar example on a list
+/// field becomes a one-element array.
+///
+/// # Errors
+///
+/// Lists the required fields that have no example, e.g. `address.city`.
+pub fn example<T: OpenApiSchema>() -> Result<JsonValue, String> {
+    let schema = miniserde::json::from_str::<RawValue>(T::openapi_schema())
+        .map_err(|_| format!("{} has no JSON schema", T::schema_name()))?;
+    let nested = miniserde::json::from_str::<RawValue>(&format!("{{{}}}", T::nested_schemas()))
+        .unwrap_or(RawValue::Null);
+    let mut missing = Vec::new();
+    let value = example_of(&schema, &nested, "", 0, &mut missing);
+    match value {
+        Some(value) if missing.is_empty() => Ok(JsonValue::from_raw(&value)),
+        _ if missing.is_empty() => Err(format!("{} has no example", T::schema_name())),
+        _ => Err(format!(
+            "{} has no example for required field(s): {}",
+            T::schema_name(),
+            missing.join(", ")
+        )),
+    }
+}
+
+/// Assert that `T`'s [`example`] parses and passes validation, so the
+/// documented examples stay in sync with the rules.
+///
+/// ```ignore
+/// #[test]
+/// fn examples_are_valid() {
+///     mik_sdk::testing::assert_example::<CreateUser>();
+///     mik_sdk::testing::assert_example::<UpdateUser>();
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics when a required field has no example, or when the example is
+/// rejected by `FromJson` or `Validate`.
+#[track_caller]
+pub fn assert_example<T: FromJson + Validate + OpenApiSchema>() -> T {
+    let name = T::schema_name();
+    let json = example::<T>().unwrap_or_else(|err| panic!("{err}"));
+    let value = T::from_json(&json)
+        .unwrap_or_else(|err| panic!("example for {name} does not parse: {err}\n{json}"));
+    if let Err(errors) = value.validate_all() {
+        panic!("example for {name} fails validation: {errors}\n{json}");
+    }
+    value
+}
+
+/// Build an example for one schema, recording required fields without one.
+fn example_of(
+    schema: &RawValue,
+    nested: &RawValue,
+    path: &str,
+    depth: usize,
+    missing: &mut Vec<String>,
+) -> Option<RawValue> {
+    // Recursive types stop here instead of overflowing
+    const MAX_DEPTH: usize = 16;
+
+    let RawValue::Object(schema) = schema else {
+        return None;
+    };
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let is_array = matches!(schema.get("type"), Some(RawValue::String(t)) if t == "array");
+
+    if let Some(example) = schema.get("x-example") {
+        return Some(match example {
+            RawValue::Array(_) => example.clone(),
+            scalar if is_array => {
+                let mut items = Array::new();
+                items.push(scalar.clone());
+                RawValue::Array(items)
+            },
+            scalar => scalar.clone(),
+        });
+    }
+    if let Some(RawValue::String(reference)) = schema.get("$ref") {
+        let name = reference.rsplit('/').next().unwrap_or(reference);
+        let RawValue::Object(schemas) = nested else {
+            return None;
+        };
+        return example_of(schemas.get(name)?, nested, path, depth + 1, missing);
+    }
+    for key in ["allOf", "oneOf", "anyOf"] {
+        if let Some(RawValue::Array(variants)) = schema.get(key) {
+            return variants
+                .iter()
+                .find_map(|variant| example_of(variant, nested, path, depth + 1, missing));
+        }
+    }
+    if let Some(RawValue::Array(values)) = schema.get("enum") {
+        return values.first().cloned();
+    }
+    if is_array {
+        let item = example_of(schema.get("items")?, nested, path, depth + 1, missing)?;
+        let mut items = Array::new();
+        items.push(item);
+        return Some(RawValue::Array(items));
+    }
+
+    let RawValue::Object(properties) = schema.get("properties")? else {
+        return None;
+    };
+    let required = |name: &str| match schema.get("required") {
+        Some(RawValue::Array(required)) => required
+            .iter()
+            .any(|r| matches!(r, RawValue::String(r) if r == name)),
+        _ => false,
+    };
+    let mut object = Object::new();
+    for (name, property) in properties.iter() {
+        let field = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{path}.{name}")
+        };
+        match example_of(property, nested, &field, depth + 1, missing) {
+            Some(value) => {
+                object.insert(name.clone(), value);
+            },
+            None if required(name) => missing.push(field),
+            None => {},
+        }
+    }
+    Some(RawValue::Object(object))
+}
+
+/// Directory holding snapshots: `snapshots/` in the crate under test.
+fn snapshot_dir() -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map_or_else(PathBuf::new, PathBuf::from)
+        .join("snapshots")
+}
+
+/// Pretty-print JSON with two-space indentation.
+fn pretty(value: &RawValue, indent: usize) -> String {
+    let pad = "  ".repeat(indent + 1);
+    let close = "  ".repeat(indent);
+    match value {
+        RawValue::Array(items) if !items.is_empty() => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| format!("{pad}{}", pretty(item, indent + 1)))
+                .collect();
+            format!("[\n{}\n{close}]", items.join(",\n"))
+        },
+        RawValue::Object(fields) if !fields.is_empty() => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(key, item)| {
+                    let key = miniserde::json::to_string(key);
+                    format!("{pad}{key}: {}", pretty(item, indent + 1))
+                })
+                .collect();
+            format!("{{\n{}\n{close}}}", fields.join(",\n"))
+        },
+        scalar => miniserde::json::to_string(scalar),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(
+        method: Method,
+        path: String,
+        headers: Vec<(String, String)>,
+        body: Option<Vec<u8>>,
+    ) -> TestResponse {
+        let headers_json = headers.iter().fold(json::obj(), |obj, (name, value)| {
+            obj.set(name, json::str(value))
+        });
+        let echoed = json::obj()
+            .set("method", json::str(method.as_str()))
+            .set("path", json::str(&path))
+            .set("headers", headers_json)
+            .set(
+                "body",
+                json::str(String::from_utf8(body.unwrap_or_default()).unwrap_or_default()),
+            );
+        TestResponse::from_parts(
+            200,
+            vec![("Content-Type".to_string(), MIME_JSON.to_string())],
+            Some(echoed.to_bytes()),
+        )
+    }
+
+    #[test]
+    fn test_request_building() {
+        let client = TestClient::new(echo).header("x-tenant", "acme");
+        let res = client
+            .post("/search")
+            .query("q", "a b")
+            .query("page", "2")
+            .bearer("t0k")
+            .cookie("a", "1")
+            .cookie("b", "2")
+            .json(&json::obj().set("n", json::int(1)))
+            .send();
+
+        res.assert_status(200)
+            .assert_header("content-type", MIME_JSON)
+            .assert_json("/method", "POST")
+            .assert_json("/path", "/search?q=a%20b&page=2")
+            .assert_json("/headers/x-tenant", "acme")
+            .assert_json("/headers/authorization", "Bearer t0k")
+            .assert_json("/headers/cookie", "a=1; b=2")
+            .assert_json("/headers/content-type", MIME_JSON)
+            .assert_json("/body", r#"{"n":1}"#);
+    }
+
+    #[test]
+    fn test_form_body() {
+        let res = TestClient::new(echo)
+            .put("/profile")
+            .form(&[("name", "Ada L"), ("lang", "en&fr")])
+            .send();
+        res.assert_json("/body", "name=Ada%20L&lang=en%26fr")
+            .assert_json("/headers/content-type", MIME_FORM_URLENCODED);
+    }
+
+    #[test]
+    fn test_response_accessors() {
+        let res = TestResponse::from_parts(204, vec![("ETag".into(), "\"v1\"".into())], None);
+        assert_eq!(res.status(), 204);
+        assert_eq!(res.header("etag"), Some("\"v1\""));
+        assert_eq!(res.text(), "");
+        assert!(res.json().is_none());
+        assert_eq!(HttpResponse::body(&res), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected status 404, got 200")]
+    fn test_assert_status_fails() {
+        TestClient::new(echo).get("/").send().assert_status(404);
+    }
+
+    #[test]
+    #[should_panic(expected = "JSON at `/method`")]
+    fn test_assert_json_fails() {
+        TestClient::new(echo)
+            .get("/")
+            .send()
+            .assert_json("/method", "POST");
+    }
+
+    #[test]
+    fn test_snapshot_format() {
+        let body = json::obj()
+            .set("b", json::arr().push(json::int(1)).push(json::obj()))
+            .set("a", json::str("x\"y"));
+        let res = TestResponse::from_parts(201, vec![], Some(body.to_bytes()));
+        assert_eq!(
+            res.snapshot(),
+            "status: 201\n\n{\n  \"a\": \"x\\\"y\",\n  \"b\": [\n    1,\n    {}\n  ]\n}\n"
+        );
+        let res = TestResponse::from_parts(200, vec![], Some(b"plain".to_vec()));
+        assert_eq!(res.snapshot(), "status: 200\n\nplain\n");
+    }
+
+    struct Signup {
+        email: String,
+        age: i64,
+    }
+
+    impl FromJson for Signup {
+        fn from_json(value: &JsonValue) -> Result<Self, crate::typed::ParseError> {
+            Ok(Self {
+                email: value
+                    .path_str(&["email"])
+                    .ok_or_else(|| crate::typed::ParseError::missing("email"))?,
+                age: value
+                    .path_int(&["age"])
+                    .ok_or_else(|| crate::typed::ParseError::missing("age"))?,
+            })
+        }
+    }
+
+    impl Validate for Signup {
+        fn validate(&self) -> Result<(), crate::typed::ValidationError> {
+            if self.age < 18 {
+                return Err(crate::typed::ValidationError::min("age", 18));
+            }
+            Ok(())
+        }
+    }
+
+    impl OpenApiSchema for Signup {
+        fn openapi_schema() -> &'static str {
+            r##"{"type":"object","required":["email","age","address"],"properties":{
+                "email":{"type":"string","x-example":"ada@example.com"},
+                "age":{"type":"integer","minimum":18,"x-example":36},
+                "nickname":{"type":"string"},
+                "plan":{"type":"string","enum":["free","pro"]},
+                "address":{"$ref":"#/components/schemas/Address"}}}"##
+        }
+
+        fn schema_name() -> &'static str {
+            "Signup"
+        }
+
+        fn nested_schemas() -> &'static str {
+            r#""Address":{"type":"object","required":["city"],"properties":{
+                "city":{"type":"string","x-example":"London"},
+                "tags":{"type":"array","items":{"type":"string"},"x-example":"home"}}}"#
+        }
+    }
+
+    struct Underage(Signup);
+
+    impl FromJson for Underage {
+        fn from_json(value: &JsonValue) -> Result<Self, crate::typed::ParseError> {
+            Signup::from_json(value).map(Self)
+        }
+    }
+
+    impl Validate for Underage {
+        fn validate(&self) -> Result<(), crate::typed::ValidationError> {
+            self.0.validate()
+        }
+    }
+
+    impl OpenApiSchema for Underage {
+        fn openapi_schema() -> &'static str {
+            r#"{"type":"object","required":["email","age"],"properties":{
+                "email":{"type":"string","x-example":"kid@example.com"},
+                "age":{"type":"integer","x-example":12},
+                "city":{"type":"string"}}}"#
+        }
+
+        fn schema_name() -> &'static str {
+            "Signup"
+        }
+    }
+
+    struct Undocumented;
+
+    impl OpenApiSchema for Undocumented {
+        fn openapi_schema() -> &'static str {
+            r#"{"type":"object","required":["id","name"],"properties":{
+                "id":{"type":"integer"},"name":{"type":"string","x-example":"x"}}}"#
+        }
+
+        fn schema_name() -> &'static str {
+            "Undocumented"
+        }
+    }
+
+    #[test]
+    fn test_example() {
+        let example = example::<Signup>().unwrap();
+        assert_eq!(
+            example.to_string(),
+            r#"{"address":{"city":"London","tags":["home"]},"age":36,"email":"ada@example.com","plan":"free"}"#
+        );
+    }
+
+    #[test]
+    fn test_example_missing_required() {
+        let err = example::<Undocumented>().unwrap_err();
+        assert_eq!(err, "Undocumented has no example for required field(s): id");
+    }
+
+    #[test]
+    fn test_assert_example() {
+        let signup = assert_example::<Signup>();
+        assert_eq!(signup.email, "ada@example.com");
+        assert_eq!(signup.age, 36);
+    }
+
+    #[test]
+    #[should_panic(expected = "example for Signup fails validation")]
+    fn test_assert_example_invalid() {
+        let _ = assert_example::<Underage>();
+    }
+}
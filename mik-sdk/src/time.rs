@@ -1,9 +1,12 @@
 //! Time utilities for WASI HTTP handlers.
 //!
-//! Provides convenient functions for getting the current time and formatting timestamps.
+//! Provides convenient functions for getting the current time, formatting and
+//! parsing timestamps, measuring elapsed time with [`Instant`], and reading
+//! durations such as `"30s"`.
 //!
-//! - **WASI (wasi-http feature):** Uses `wasi:clocks/wall-clock` directly
-//! - **Native:** Uses `std::time::SystemTime`
+//! - **WASI (wasi-http feature):** Uses `wasi:clocks/wall-clock` and
+//!   `wasi:clocks/monotonic-clock` directly
+//! - **Native:** Uses `std::time::SystemTime` and `std::time::Instant`
 //!
 //! # Usage
 //!
@@ -33,8 +36,17 @@
 //! // ISO 8601 string
 //! let iso = time::now_iso();
 //! assert!(iso.contains('T')); // "2025-01-16T10:30:00Z"
+//!
+//! // Parse RFC 3339 input back to seconds and nanoseconds
+//! let (secs, nanos) = time::parse_iso("2025-01-16T10:50:00.500Z").unwrap();
+//! assert_eq!(time::to_iso(secs, nanos), "2025-01-16T10:50:00.500Z");
+//!
+//! // Handler latency on the monotonic clock
+//! let started = time::Instant::now();
+//! let took = time::format_duration(started.elapsed()); // "3ms"
 //! ```
 
+use std::time::Duration;
 // Native target: use std::time for testing
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -65,8 +77,7 @@ pub fn now() -> u64 {
 pub fn now() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0)
+        .map_or(0, |d| d.as_secs())
 }
 
 /// Get current Unix timestamp in milliseconds.
@@ -96,8 +107,7 @@ pub fn now_millis() -> u64 {
 pub fn now_millis() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_millis() as u64)
-        .unwrap_or(0)
+        .map_or(0, |d| d.as_millis() as u64)
 }
 
 /// Get current time as ISO 8601 string.
@@ -132,6 +142,77 @@ pub fn now_iso() -> String {
     to_iso(duration.as_secs(), duration.subsec_nanos())
 }
 
+// ============================================================================
+// MONOTONIC CLOCK
+// ============================================================================
+
+/// A reading of the monotonic clock, for measuring elapsed time.
+///
+/// Unlike [`now_millis`], the monotonic clock never jumps when the wall
+/// clock is adjusted, so use it for latencies and timeouts. Readings are
+/// only comparable within one component instance.
+///
+/// - **WASI:** `wasi:clocks/monotonic-clock`
+/// - **Native:** `std::time::Instant`
+///
+/// # Examples
+///
+/// ```
+/// use mik_sdk::time::Instant;
+///
+/// let started = Instant::now();
+/// // ... handle the request
+/// let took = started.elapsed();
+/// assert!(took.as_secs() < 60);
+/// assert!(started.elapsed_millis() < 60_000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(u64);
+
+impl Instant {
+    /// Read the monotonic clock.
+    #[must_use]
+    pub fn now() -> Self {
+        Self(monotonic_nanos())
+    }
+
+    /// Time elapsed since this reading.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        Self::now().duration_since(*self)
+    }
+
+    /// Whole milliseconds elapsed since this reading.
+    #[must_use]
+    pub fn elapsed_millis(&self) -> u64 {
+        u64::try_from(self.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    /// Time from `earlier` to this reading, or zero if `earlier` is later.
+    #[must_use]
+    pub const fn duration_since(&self, earlier: Self) -> Duration {
+        Duration::from_nanos(self.0.saturating_sub(earlier.0))
+    }
+}
+
+/// Nanoseconds on the host's monotonic clock.
+#[cfg(target_arch = "wasm32")]
+fn monotonic_nanos() -> u64 {
+    crate::wasi_http::wasi::clocks::monotonic_clock::now()
+}
+
+/// Nanoseconds since the first reading in this process (native implementation).
+#[cfg(not(target_arch = "wasm32"))]
+fn monotonic_nanos() -> u64 {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    let elapsed = START.get_or_init(std::time::Instant::now).elapsed();
+    u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX)
+}
+
+// ============================================================================
+// CONVERSIONS
+// ============================================================================
+
 /// Convert to milliseconds since epoch.
 ///
 /// Uses saturating arithmetic to prevent overflow on extreme values.
@@ -170,7 +251,6 @@ pub fn to_millis(seconds: u64, nanoseconds: u32) -> u64 {
 /// assert_eq!(iso, "2025-01-16T10:50:00.500Z");
 /// ```
 #[must_use]
-#[allow(clippy::similar_names)] // doe/doy are standard date algorithm abbreviations
 pub fn to_iso(seconds: u64, nanoseconds: u32) -> String {
     use crate::constants::{SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE};
 
@@ -182,7 +262,294 @@ pub fn to_iso(seconds: u64, nanoseconds: u32) -> String {
     let minutes = remaining / SECONDS_PER_MINUTE;
     let secs = remaining % SECONDS_PER_MINUTE;
 
-    // Howard Hinnant's algorithm: https://howardhinnant.github.io/date_algorithms.html
+    let (year, m, d) = civil_from_days(days);
+
+    if nanoseconds == 0 {
+        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}Z")
+    } else {
+        let millis = nanoseconds / 1_000_000;
+        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}.{millis:03}Z")
+    }
+}
+
+/// Parse an RFC 3339 timestamp into seconds and nanoseconds since the
+/// Unix epoch, the inverse of [`to_iso`].
+///
+/// Accepts any fraction length and numeric offsets (`+02:00`), which are
+/// converted to UTC. Returns `None` for malformed input, impossible dates
+/// and times before 1970.
+///
+/// # Examples
+///
+/// ```
+/// use mik_sdk::time;
+///
+/// assert_eq!(time::parse_iso("2025-01-16T10:50:00.500Z"), Some((1737024600, 500_000_000)));
+/// assert_eq!(time::parse_iso("2025-01-16T12:50:00+02:00"), Some((1737024600, 0)));
+/// assert_eq!(time::parse_iso("2025-02-30T00:00:00Z"), None);
+/// ```
+#[must_use]
+pub fn parse_iso(input: &str) -> Option<(u64, u32)> {
+    let field = |start: usize, len: usize| input.get(start..start + len).and_then(digits);
+    let separator = |index: usize, allowed: &[u8]| {
+        input
+            .as_bytes()
+            .get(index)
+            .is_some_and(|b| allowed.contains(b))
+    };
+    if !(separator(4, b"-")
+        && separator(7, b"-")
+        && separator(10, b"Tt ")
+        && separator(13, b":")
+        && separator(16, b":"))
+    {
+        return None;
+    }
+
+    let (year, month, day) = (field(0, 4)?, field(5, 2)?, field(8, 2)?);
+    let (hour, minute, second) = (field(11, 2)?, field(14, 2)?, field(17, 2)?);
+    // Second 60 is a leap second, counted as the first second of the next minute
+    if !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    let mut rest = input.get(19..)?;
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        // Digits past nanosecond precision are truncated
+        let kept = fraction.get(..len.min(9))?;
+        nanos = u32::try_from(digits(kept)?).ok()?;
+        for _ in kept.len()..9 {
+            nanos *= 10;
+        }
+        rest = fraction.get(len..)?;
+    }
+
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes().first()? {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            if rest.len() != 6 || rest.as_bytes().get(3) != Some(&b':') {
+                return None;
+            }
+            let (hours, minutes) = (
+                rest.get(1..3).and_then(digits)?,
+                rest.get(4..6).and_then(digits)?,
+            );
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            sign * i64::try_from(hours * 3600 + minutes * 60).ok()?
+        },
+    };
+
+    let days = days_from_civil(
+        i64::try_from(year).ok()?,
+        i64::try_from(month).ok()?,
+        i64::try_from(day).ok()?,
+    );
+    let seconds = days * 86_400 + i64::try_from(hour * 3600 + minute * 60 + second).ok()? - offset;
+    Some((u64::try_from(seconds).ok()?, nanos))
+}
+
+/// Parse a non-empty run of ASCII digits.
+fn digits(input: &str) -> Option<u64> {
+    if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    input.parse().ok()
+}
+
+const fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        },
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Format as an HTTP date (RFC 9110 IMF-fixdate), as used by `Expires`,
+/// `Last-Modified` and cookie `Expires` attributes.
+///
+/// # Examples
+///
+/// ```
+/// let date = mik_sdk::time::to_http_date(1737024600);
+/// assert_eq!(date, "Thu, 16 Jan 2025 10:50:00 GMT");
+/// ```
+#[must_use]
+pub fn to_http_date(seconds: u64) -> String {
+    use crate::constants::{SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE};
+
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let days = seconds / SECONDS_PER_DAY;
+    let remaining = seconds % SECONDS_PER_DAY;
+
+    let hours = remaining / SECONDS_PER_HOUR;
+    let remaining = remaining % SECONDS_PER_HOUR;
+    let minutes = remaining / SECONDS_PER_MINUTE;
+    let secs = remaining % SECONDS_PER_MINUTE;
+
+    let (year, m, d) = civil_from_days(days);
+    // 1970-01-01 was a Thursday
+    let weekday = WEEKDAYS[(days % 7) as usize];
+    let month = MONTHS[(m - 1) as usize];
+
+    format!("{weekday}, {d:02} {month} {year:04} {hours:02}:{minutes:02}:{secs:02} GMT")
+}
+
+// ============================================================================
+// DURATIONS
+// ============================================================================
+
+/// Parse a human-readable duration such as `"250ms"`, `"30s"`, `"1.5h"` or
+/// `"1h30m"`.
+///
+/// Units are `us`, `ms`, `s`, `m`, `h` and `d`; a bare number is seconds.
+/// Returns `None` for unknown units, empty input or overflow.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use mik_sdk::time::parse_duration;
+///
+/// assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+/// assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+/// assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+/// ```
+#[must_use]
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    if let Some(secs) = digits(input) {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let mut rest = input;
+    let mut total: u128 = 0;
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let number_len = rest
+            .bytes()
+            .take_while(|b| b.is_ascii_digit() || *b == b'.')
+            .count();
+        let (number, after) = rest.split_at(number_len);
+        let unit_len = after
+            .char_indices()
+            .find(|(_, c)| c.is_ascii_digit())
+            .map_or(after.len(), |(i, _)| i);
+        let (unit, after) = after.split_at(unit_len);
+
+        let unit_nanos: u128 = match unit {
+            "us" | "µs" => 1_000,
+            "ms" => 1_000_000,
+            "s" => 1_000_000_000,
+            "m" => 60_000_000_000,
+            "h" => 3_600_000_000_000,
+            "d" => 86_400_000_000_000,
+            _ => return None,
+        };
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        let mut nanos = u128::from(digits(whole)?).checked_mul(unit_nanos)?;
+        if !fraction.is_empty() {
+            let scale = 10_u128.checked_pow(u32::try_from(fraction.len()).ok()?)?;
+            nanos += u128::from(digits(fraction)?).checked_mul(unit_nanos)? / scale;
+        }
+        total = total.checked_add(nanos)?;
+        rest = after;
+    }
+
+    let secs = u64::try_from(total / 1_000_000_000).ok()?;
+    Some(Duration::new(secs, (total % 1_000_000_000) as u32))
+}
+
+/// Format a duration compactly, for logs and `Retry-After`-style messages.
+///
+/// Sub-second values use `us` or `ms`, values under a minute use seconds
+/// with up to two decimals, and longer values show their two largest units.
+/// The output is accepted by [`parse_duration`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use mik_sdk::time::format_duration;
+///
+/// assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+/// assert_eq!(format_duration(Duration::from_millis(1500)), "1.5s");
+/// assert_eq!(format_duration(Duration::from_secs(5400)), "1h30m");
+/// ```
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    use crate::constants::{SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE};
+
+    let secs = duration.as_secs();
+    if secs == 0 {
+        let micros = duration.subsec_micros();
+        return if micros < 1000 {
+            format!("{micros}us")
+        } else {
+            format!("{}ms", duration.subsec_millis())
+        };
+    }
+    if secs < SECONDS_PER_MINUTE {
+        let hundredths = duration.subsec_millis() / 10;
+        return match hundredths {
+            0 => format!("{secs}s"),
+            _ if hundredths.is_multiple_of(10) => format!("{secs}.{}s", hundredths / 10),
+            _ => format!("{secs}.{hundredths:02}s"),
+        };
+    }
+
+    let units = [
+        (SECONDS_PER_DAY, "d"),
+        (SECONDS_PER_HOUR, "h"),
+        (SECONDS_PER_MINUTE, "m"),
+        (1, "s"),
+    ];
+    let mut remaining = secs;
+    let mut parts = Vec::with_capacity(2);
+    for (size, suffix) in units {
+        let count = remaining / size;
+        remaining %= size;
+        if count > 0 {
+            parts.push(format!("{count}{suffix}"));
+        } else if !parts.is_empty() {
+            // Stop at a zero unit: "1h" rather than "1h0m"
+            break;
+        }
+        if parts.len() == 2 {
+            break;
+        }
+    }
+    parts.concat()
+}
+
+/// Convert days since the Unix epoch to a `(year, month, day)` civil date.
+///
+/// Howard Hinnant's algorithm: <https://howardhinnant.github.io/date_algorithms.html>
+#[allow(clippy::similar_names)] // doe/doy are standard date algorithm abbreviations
+pub(crate) const fn civil_from_days(days: u64) -> (u64, u64, u64) {
     let z = days + 719468;
     let era = z / 146097;
     let doe = z - era * 146097;
@@ -193,13 +560,20 @@ pub fn to_iso(seconds: u64, nanoseconds: u32) -> String {
     let d = doy - (153 * mp + 2) / 5 + 1;
     let m = if mp < 10 { mp + 3 } else { mp - 9 };
     let year = if m <= 2 { y + 1 } else { y };
+    (year, m, d)
+}
 
-    if nanoseconds == 0 {
-        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}Z")
-    } else {
-        let millis = nanoseconds / 1_000_000;
-        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}.{millis:03}Z")
-    }
+/// Convert a civil date to days since the Unix epoch, the inverse of
+/// [`civil_from_days`].
+#[allow(clippy::similar_names)] // doe/doy are standard date algorithm abbreviations
+pub(crate) const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
 }
 
 #[cfg(test)]
@@ -258,6 +632,15 @@ mod tests {
         assert_eq!(to_iso(1730419200, 0), "2024-11-01T00:00:00Z");
     }
 
+    #[test]
+    fn test_to_http_date() {
+        assert_eq!(to_http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(to_http_date(1737024600), "Thu, 16 Jan 2025 10:50:00 GMT");
+        assert_eq!(to_http_date(1709208000), "Thu, 29 Feb 2024 12:00:00 GMT");
+        assert_eq!(to_http_date(1735689599), "Tue, 31 Dec 2024 23:59:59 GMT");
+        assert_eq!(to_http_date(784111777), "Sun, 06 Nov 1994 08:49:37 GMT");
+    }
+
     #[test]
     fn test_to_iso_day_boundary() {
         assert_eq!(to_iso(1719791999, 0), "2024-06-30T23:59:59Z");
@@ -421,4 +804,106 @@ mod tests {
         let t2 = now_millis();
         assert!(t2 > t1, "Time should advance: {t2} should be > {t1}");
     }
+
+    #[test]
+    fn test_instant_elapsed() {
+        let started = Instant::now();
+        std::thread::sleep(Duration::from_millis(5));
+        let later = Instant::now();
+        assert!(later > started);
+        assert!(started.elapsed() >= Duration::from_millis(5));
+        assert!(started.elapsed_millis() >= 5);
+        assert_eq!(started.duration_since(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_iso_roundtrip() {
+        for (secs, nanos) in [(0, 0), (1_737_024_600, 0), (1_737_024_600, 500_000_000)] {
+            assert_eq!(parse_iso(&to_iso(secs, nanos)), Some((secs, nanos)));
+        }
+        assert_eq!(parse_iso("2024-02-29T23:59:59Z"), Some((1_709_251_199, 0)));
+    }
+
+    #[test]
+    fn test_parse_iso_variants() {
+        let expected = Some((1_737_024_600, 0));
+        assert_eq!(parse_iso("2025-01-16t10:50:00z"), expected);
+        assert_eq!(parse_iso("2025-01-16 10:50:00Z"), expected);
+        assert_eq!(parse_iso("2025-01-16T05:20:00-05:30"), expected);
+        assert_eq!(
+            parse_iso("2025-01-16T10:50:00.123456789123Z"),
+            Some((1_737_024_600, 123_456_789))
+        );
+        assert_eq!(
+            parse_iso("2025-01-16T10:50:00.1Z"),
+            Some((1_737_024_600, 100_000_000))
+        );
+    }
+
+    #[test]
+    fn test_parse_iso_invalid() {
+        for input in [
+            "",
+            "2025-01-16",
+            "2025-01-16T10:50:00",
+            "2025-13-01T00:00:00Z",
+            "2023-02-29T00:00:00Z",
+            "2025-01-16T24:00:00Z",
+            "2025-01-16T10:50:00.Z",
+            "2025-01-16T10:50:00+0200",
+            "2025-01-16T10:50:00+24:00",
+            "1969-12-31T23:59:59Z",
+            "+025-01-16T10:50:00Z",
+            "2025-01-16T10:50:00Zjunk",
+        ] {
+            assert_eq!(parse_iso(input), None, "{input}");
+        }
+        assert_eq!(parse_iso("1970-01-01T01:00:00+01:00"), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_days_from_civil_inverse() {
+        for days in [0, 59, 365, 11_016, 20_104, 2_932_896] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y as i64, m as i64, d as i64), days as i64);
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration(" 5m "), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2d"), Some(Duration::from_secs(172_800)));
+        assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("40us"), Some(Duration::from_micros(40)));
+        assert_eq!(parse_duration("0"), Some(Duration::ZERO));
+        for input in ["", "s", "10 s", "5y", "1.2.3s", "m5", "-1s"] {
+            assert_eq!(parse_duration(input), None, "{input}");
+        }
+        assert_eq!(parse_duration("999999999999999999999d"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::ZERO), "0us");
+        assert_eq!(format_duration(Duration::from_micros(40)), "40us");
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(Duration::from_secs(12)), "12s");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format_duration(Duration::from_millis(1234)), "1.23s");
+        assert_eq!(format_duration(Duration::from_secs(150)), "2m30s");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1h");
+        assert_eq!(format_duration(Duration::from_secs(3661)), "1h1m");
+        assert_eq!(format_duration(Duration::from_secs(90_000)), "1d1h");
+    }
+
+    #[test]
+    fn test_format_duration_parses_back() {
+        for millis in [1, 250, 1500, 12_000, 150_000, 3_600_000] {
+            let duration = Duration::from_millis(millis);
+            assert_eq!(parse_duration(&format_duration(duration)), Some(duration));
+        }
+    }
 }
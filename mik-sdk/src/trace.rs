@@ -0,0 +1,214 @@
+//! W3C Trace Context propagation.
+//!
+//! With `#![trace]` in `routes!`, every request carries a `traceparent`:
+//! an incoming valid header is kept, otherwise a new one is generated and
+//! added to the [`Request`](crate::Request) headers. While the handler runs,
+//! the value is the *current* trace, and it is:
+//!
+//! - echoed in the `traceparent` response header,
+//! - added as a `trace_id` field to `log!` and `log::info!` output,
+//! - sent with outbound `http_client` requests that don't set one.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! routes! {
+//!     #![trace]
+//!     GET "/users" => list_users,
+//! }
+//!
+//! fn list_users(req: &Request) -> Response {
+//!     // Always set with #![trace]
+//!     let traceparent = req.trace_id_or("");
+//!     log!(info, "listing users"); // {"level":"info",...,"trace_id":"4bf9..."}
+//!     ok!({ "traceparent": str(traceparent) })
+//! }
+//! ```
+//!
+//! Without `#![trace]`, [`current`] is always `None` and nothing is added.
+
+use crate::constants::HEADER_TRACE_ID;
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// The `traceparent` of the request being handled, if tracing is enabled.
+#[must_use]
+pub fn current() -> Option<String> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// The trace ID (32 hex digits) of the request being handled, if any.
+#[must_use]
+pub fn current_trace_id() -> Option<String> {
+    current().and_then(|traceparent| trace_id(&traceparent).map(str::to_string))
+}
+
+/// Generate a new sampled `traceparent` with random trace and parent IDs.
+///
+/// Format: `00-<32 hex trace-id>-<16 hex parent-id>-01`.
+#[must_use]
+pub fn generate() -> String {
+    // All-zero IDs are invalid; 16 + 8 random bytes make them vanishingly rare
+    let mut trace_id = crate::random::hex(16);
+    while trace_id.bytes().all(|b| b == b'0') {
+        trace_id = crate::random::hex(16);
+    }
+    let mut parent_id = crate::random::hex(8);
+    while parent_id.bytes().all(|b| b == b'0') {
+        parent_id = crate::random::hex(8);
+    }
+    format!("00-{trace_id}-{parent_id}-01")
+}
+
+/// Check a `traceparent` value against the W3C Trace Context format.
+///
+/// Accepts version `00` only, with lowercase hex IDs that are not all zero.
+#[must_use]
+pub fn is_valid(traceparent: &str) -> bool {
+    let mut parts = traceparent.split('-');
+    let (Some(version), Some(trace_id), Some(parent_id), Some(flags), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let is_hex = |s: &str, len: usize| {
+        s.len() == len
+            && s.bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    let is_zero = |s: &str| s.bytes().all(|b| b == b'0');
+    version == "00"
+        && is_hex(trace_id, 32)
+        && !is_zero(trace_id)
+        && is_hex(parent_id, 16)
+        && !is_zero(parent_id)
+        && is_hex(flags, 2)
+}
+
+/// Extract the trace ID from a valid `traceparent`.
+#[must_use]
+pub fn trace_id(traceparent: &str) -> Option<&str> {
+    is_valid(traceparent).then(|| &traceparent[3..35])
+}
+
+/// Start tracing a request.
+///
+/// Keeps a valid incoming `traceparent` header or replaces it with a
+/// generated one, and makes it the current trace. Returns the value.
+///
+/// Called by code generated by `routes!` with `#![trace]`.
+#[doc(hidden)]
+pub fn __begin(headers: &mut Vec<(String, String)>) -> String {
+    let incoming = headers
+        .iter()
+        .position(|(name, _)| name.eq_ignore_ascii_case(HEADER_TRACE_ID));
+    let traceparent = match incoming {
+        Some(i) if is_valid(&headers[i].1) => headers[i].1.clone(),
+        _ => {
+            let traceparent = generate();
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case(HEADER_TRACE_ID));
+            headers.push((HEADER_TRACE_ID.to_string(), traceparent.clone()));
+            traceparent
+        },
+    };
+    CURRENT.with(|current| *current.borrow_mut() = Some(traceparent.clone()));
+    traceparent
+}
+
+/// Finish tracing a request, echoing the trace in the response headers.
+///
+/// Called by code generated by `routes!` with `#![trace]`.
+#[doc(hidden)]
+pub fn __end(traceparent: String, headers: &mut Vec<(String, String)>) {
+    CURRENT.with(|current| *current.borrow_mut() = None);
+    if !headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(HEADER_TRACE_ID))
+    {
+        headers.push((HEADER_TRACE_ID.to_string(), traceparent));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid(VALID));
+        assert!(is_valid(&generate()));
+        assert!(!is_valid(""));
+        assert!(!is_valid("abc123"));
+        // Unknown version, uppercase hex, zero IDs, extra fields
+        assert!(!is_valid(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        ));
+        assert!(!is_valid(
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01"
+        ));
+        assert!(!is_valid(
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+        ));
+        assert!(!is_valid(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01"
+        ));
+        assert!(!is_valid(&format!("{VALID}-00")));
+    }
+
+    #[test]
+    fn test_trace_id() {
+        assert_eq!(trace_id(VALID), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+        assert_eq!(trace_id("garbage"), None);
+    }
+
+    #[test]
+    fn test_begin_keeps_valid_incoming() {
+        let mut headers = vec![("Traceparent".to_string(), VALID.to_string())];
+        assert_eq!(__begin(&mut headers), VALID);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(current().as_deref(), Some(VALID));
+        assert_eq!(
+            current_trace_id().as_deref(),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+
+        let mut response = Vec::new();
+        __end(VALID.to_string(), &mut response);
+        assert_eq!(
+            response,
+            vec![("traceparent".to_string(), VALID.to_string())]
+        );
+        assert_eq!(current(), None);
+    }
+
+    #[test]
+    fn test_begin_replaces_missing_or_invalid() {
+        let mut headers = vec![("traceparent".to_string(), "bogus".to_string())];
+        let traceparent = __begin(&mut headers);
+        assert!(is_valid(&traceparent));
+        assert_eq!(
+            headers,
+            vec![("traceparent".to_string(), traceparent.clone())]
+        );
+
+        let mut headers = Vec::new();
+        let generated = __begin(&mut headers);
+        assert_ne!(generated, traceparent);
+        assert_eq!(headers.len(), 1);
+
+        // A handler-set traceparent is left alone
+        let mut response = vec![("traceparent".to_string(), VALID.to_string())];
+        __end(generated, &mut response);
+        assert_eq!(response.len(), 1);
+        assert_eq!(response[0].1, VALID);
+    }
+}
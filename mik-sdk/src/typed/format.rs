@@ -0,0 +1,189 @@
+//! String format checks behind `#[field(format = "...")]`.
+//!
+//! Derived `Validate` impls call these for the `email`, `uri`, `uuid`,
+//! `ipv4` and `ipv6` formats; any other format is only an OpenAPI hint.
+//! The checks are structural, not exhaustive: `is_email` does not resolve
+//! domains and `is_uri` does not validate scheme-specific syntax.
+//!
+//! # Example
+//!
+//! ```
+//! use mik_sdk::typed::format;
+//!
+//! assert!(format::is_email("alice@example.com"));
+//! assert!(format::is_uuid("550e8400-e29b-41d4-a716-446655440000"));
+//! assert!(!format::is_ipv4("256.0.0.1"));
+//! ```
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Check a value against a named format.
+///
+/// Returns `None` for formats without a runtime check.
+#[must_use]
+pub fn check(format: &str, value: &str) -> Option<bool> {
+    match format {
+        "email" => Some(is_email(value)),
+        "uri" => Some(is_uri(value)),
+        "uuid" => Some(is_uuid(value)),
+        "ipv4" => Some(is_ipv4(value)),
+        "ipv6" => Some(is_ipv6(value)),
+        _ => None,
+    }
+}
+
+/// Whether `value` is an email address (`local@domain`).
+///
+/// The local part accepts the RFC 5322 dot-atom characters, and the domain
+/// must have at least two dot-separated labels of letters, digits and
+/// inner hyphens. Quoted local parts and IP literals are rejected.
+#[must_use]
+pub fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+    if local.is_empty() || local.len() > 64 || value.len() > 254 {
+        return false;
+    }
+    let local_ok = local.split('.').all(|atom| {
+        !atom.is_empty()
+            && atom
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+/=?^_`{|}~-".contains(&b))
+    });
+    local_ok && domain.contains('.') && is_hostname(domain)
+}
+
+/// Whether `value` is an absolute URI (`scheme:rest`, RFC 3986).
+///
+/// The scheme must start with a letter followed by letters, digits, `+`,
+/// `-` or `.`, and the rest must be non-empty with no whitespace or
+/// control characters.
+#[must_use]
+pub fn is_uri(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return false;
+    };
+    let mut scheme_bytes = scheme.bytes();
+    let scheme_ok = scheme_bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && scheme_bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'));
+    scheme_ok && !rest.is_empty() && !rest.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+/// Whether `value` is a hyphenated UUID (`8-4-4-4-12` hex digits, any case).
+#[must_use]
+pub fn is_uuid(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+/// Whether `value` is an IPv4 address in dotted-decimal form.
+#[must_use]
+pub fn is_ipv4(value: &str) -> bool {
+    value.parse::<Ipv4Addr>().is_ok()
+}
+
+/// Whether `value` is an IPv6 address.
+#[must_use]
+pub fn is_ipv6(value: &str) -> bool {
+    value.parse::<Ipv6Addr>().is_ok()
+}
+
+/// Whether `value` is a DNS hostname (RFC 1123 labels).
+fn is_hostname(value: &str) -> bool {
+    value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email() {
+        for ok in [
+            "alice@example.com",
+            "first.last+tag@sub.example.co.uk",
+            "o'brien@example.org",
+            "x@a-b.io",
+        ] {
+            assert!(is_email(ok), "{ok}");
+        }
+        for bad in [
+            "",
+            "alice",
+            "@example.com",
+            "alice@",
+            "alice@localhost",
+            "alice@@example.com",
+            "al ice@example.com",
+            ".alice@example.com",
+            "alice..b@example.com",
+            "alice@-example.com",
+            "alice@example..com",
+        ] {
+            assert!(!is_email(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_uri() {
+        for ok in [
+            "https://example.com/path?q=1#frag",
+            "mailto:alice@example.com",
+            "urn:isbn:0451450523",
+            "git+ssh://host/repo",
+        ] {
+            assert!(is_uri(ok), "{ok}");
+        }
+        for bad in [
+            "",
+            "example.com",
+            "/relative/path",
+            "1http://x",
+            "http:",
+            "http://a b",
+        ] {
+            assert!(!is_uri(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_uuid() {
+        assert!(is_uuid("550e8400-e29b-41d4-a716-446655440000"));
+        assert!(is_uuid("550E8400-E29B-41D4-A716-446655440000"));
+        assert!(!is_uuid("550e8400e29b41d4a716446655440000"));
+        assert!(!is_uuid("550e8400-e29b-41d4-a716-44665544000g"));
+        assert!(!is_uuid("{550e8400-e29b-41d4-a716-446655440000}"));
+    }
+
+    #[test]
+    fn test_ip() {
+        assert!(is_ipv4("192.168.0.1"));
+        assert!(!is_ipv4("192.168.0"));
+        assert!(!is_ipv4("::1"));
+        assert!(is_ipv6("::1"));
+        assert!(is_ipv6("2001:db8::8a2e:370:7334"));
+        assert!(!is_ipv6("192.168.0.1"));
+        assert!(!is_ipv6("2001:db8:::1"));
+    }
+
+    #[test]
+    fn test_check_dispatch() {
+        assert_eq!(check("email", "a@b.co"), Some(true));
+        assert_eq!(check("ipv4", "nope"), Some(false));
+        assert_eq!(check("date-time", "anything"), None);
+    }
+}
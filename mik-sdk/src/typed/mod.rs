@@ -4,7 +4,12 @@
 //! - [`Id`] - Built-in path parameter for single ID routes
 //! - [`ParseError`] - Error type for parsing failures
 //! - [`ValidationError`] - Error type for constraint validation
+//! - [`ValidationErrors`] - Every violation found, rendered as a `422` problem
 //! - Traits for parsing JSON, query strings, and path parameters
+//! - [`FromRequest`] - Custom extractors taken as `routes!` handler inputs
+//! - [`format`] - String format checks behind `#[field(format = "...")]`
+//! - [`ToLogJson`] - Redacted JSON for logs, behind `#[field(redact)]`
+//! - [`openapi_3_1`] - Converts generated OpenAPI documents to 3.1
 //!
 //! # Newtypes and Validation
 //!
@@ -31,13 +36,26 @@
 //! assert_eq!(too_short.constraint(), "min");
 //! ```
 
+pub mod format;
+mod openapi;
 mod parse_error;
+#[doc(hidden)]
+pub mod pattern;
+mod redact;
 mod validation_error;
+mod validation_errors;
 
+pub use openapi::openapi_3_1;
 pub use parse_error::ParseError;
+#[doc(hidden)]
+pub use redact::{__LogJson, __ViaLogJson, __ViaToJson};
+pub use redact::{REDACTED, ToLogJson};
 pub use validation_error::ValidationError;
+pub use validation_errors::{ValidationErrors, Violation};
 
 use crate::json::JsonValue;
+use crate::middleware::HttpResponse;
+use crate::request::Request;
 use std::collections::HashMap;
 
 // ============================================================================
@@ -190,6 +208,23 @@ impl std::fmt::Display for Id {
 /// let result = String::from_json(&value);
 /// assert!(result.is_err());
 /// ```
+///
+/// Derived types parse nested types and `Vec`s of them recursively. Errors
+/// carry the full path to the failing field:
+///
+/// ```
+/// # use mik_sdk::Type;
+/// # use mik_sdk::typed::FromJson;
+/// #[derive(Type)]
+/// struct Item { name: String }
+///
+/// #[derive(Type)]
+/// struct Order { items: Vec<Item> }
+///
+/// let body = mik_sdk::json::try_parse_full(br#"{"items":[{"name":"a"},{}]}"#).unwrap();
+/// let err = Order::from_json(&body).err().unwrap();
+/// assert_eq!(err.field(), "items[1].name");
+/// ```
 pub trait FromJson: Sized {
     /// Parse this type from a JSON value.
     fn from_json(value: &JsonValue) -> Result<Self, ParseError>;
@@ -239,6 +274,105 @@ pub trait FromQuery: Sized {
     fn from_query(params: &[(String, String)]) -> Result<Self, ParseError>;
 }
 
+/// Trait for types that can be parsed from a form-urlencoded body.
+///
+/// Implement this for HTML form types. Usually derived with `#[derive(Form)]`,
+/// which also enforces `#[field(min, max)]` constraints while parsing.
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::typed::{FromForm, ParseError};
+/// // Example of manually implementing FromForm
+/// struct LoginForm { username: String }
+///
+/// impl FromForm for LoginForm {
+///     fn from_form(params: &[(String, String)]) -> Result<Self, ParseError> {
+///         let username = params.iter()
+///             .find(|(k, _)| k == "username")
+///             .map(|(_, v)| v.clone())
+///             .ok_or_else(|| ParseError::missing("username"))?;
+///         Ok(Self { username })
+///     }
+/// }
+///
+/// let params = vec![("username".to_string(), "alice".to_string())];
+/// let form = LoginForm::from_form(&params).unwrap();
+/// assert_eq!(form.username, "alice");
+/// ```
+pub trait FromForm: Sized {
+    /// Parse this type from decoded form fields.
+    fn from_form(params: &[(String, String)]) -> Result<Self, ParseError>;
+}
+
+/// Trait for types that can be parsed from request headers.
+///
+/// Implement this for header types. Usually derived with `#[derive(Headers)]`.
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::typed::{FromHeaders, ParseError};
+/// // Example of manually implementing FromHeaders
+/// struct ApiHeaders { api_key: String }
+///
+/// impl FromHeaders for ApiHeaders {
+///     fn from_headers(headers: &[(String, String)]) -> Result<Self, ParseError> {
+///         let api_key = headers.iter()
+///             .find(|(k, _)| k.eq_ignore_ascii_case("x-api-key"))
+///             .map(|(_, v)| v.clone())
+///             .ok_or_else(|| ParseError::missing("x-api-key"))?;
+///         Ok(Self { api_key })
+///     }
+/// }
+///
+/// let headers = vec![("X-Api-Key".to_string(), "secret".to_string())];
+/// let parsed = ApiHeaders::from_headers(&headers).unwrap();
+/// assert_eq!(parsed.api_key, "secret");
+/// ```
+pub trait FromHeaders: Sized {
+    /// Parse this type from request headers. Header names match case-insensitively.
+    fn from_headers(headers: &[(String, String)]) -> Result<Self, ParseError>;
+}
+
+/// Trait for custom extractors built from the whole request.
+///
+/// Implement this for values derived from the request, such as a tenant
+/// context or an authenticated principal, and take them in `routes!` with
+/// `extract: Type`. Returning `Err` short-circuits the route with that
+/// response, so extractors pick their own status (401, 403, ...).
+///
+/// # Example
+///
+/// ```
+/// use mik_sdk::middleware::{HttpResponse, problem};
+/// use mik_sdk::typed::FromRequest;
+/// use mik_sdk::Request;
+///
+/// struct Tenant { id: String }
+///
+/// impl FromRequest for Tenant {
+///     fn from_request<R: HttpResponse>(req: &Request) -> Result<Self, R> {
+///         req.header_all("x-tenant-id")
+///             .first()
+///             .map(|id| Tenant { id: (*id).to_string() })
+///             .ok_or_else(|| problem(401, "Missing x-tenant-id header"))
+///     }
+/// }
+///
+/// // routes! {
+/// //     GET "/projects" => list_projects(extract: Tenant),
+/// // }
+/// ```
+pub trait FromRequest: Sized {
+    /// Extract this value from the request, or reject it with a response.
+    ///
+    /// # Errors
+    ///
+    /// Returns the response to send instead of calling the handler.
+    fn from_request<R: HttpResponse>(req: &Request) -> Result<Self, R>;
+}
+
 /// Trait for types that can be parsed from path parameters.
 ///
 /// Implement this for path parameter types. Usually derived with `#[derive(Path)]`.
@@ -303,6 +437,19 @@ pub trait FromPath: Sized {
 pub trait Validate {
     /// Validate this value against its constraints.
     fn validate(&self) -> Result<(), ValidationError>;
+
+    /// Validate this value, collecting every violation instead of stopping
+    /// at the first.
+    ///
+    /// Derived impls report at most one violation per field, with the
+    /// rejected value. The default wraps [`validate`](Self::validate).
+    ///
+    /// # Errors
+    ///
+    /// Returns every violation found.
+    fn validate_all(&self) -> Result<(), ValidationErrors> {
+        self.validate().map_err(ValidationErrors::from)
+    }
 }
 
 /// Trait for types that can generate their OpenAPI schema.
@@ -344,6 +491,14 @@ pub trait OpenApiSchema {
         "[]"
     }
 
+    /// Get OpenAPI header parameters array for Headers types.
+    ///
+    /// Returns a JSON array of parameter objects for use in OpenAPI path items.
+    /// Only meaningful for types derived with `#[derive(Headers)]`.
+    fn openapi_header_params() -> &'static str {
+        "[]"
+    }
+
     /// Get schemas for nested types referenced by this type via `$ref`.
     ///
     /// Returns a comma-separated list of `"TypeName":schema` entries (no outer braces).
@@ -364,7 +519,7 @@ impl FromJson for String {
     fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
         value
             .str()
-            .ok_or_else(|| ParseError::type_mismatch("value", "string"))
+            .ok_or_else(|| ParseError::type_mismatch("", "string"))
     }
 }
 
@@ -373,7 +528,7 @@ impl FromJson for i32 {
         value
             .int()
             .map(|n| n as Self)
-            .ok_or_else(|| ParseError::type_mismatch("value", "integer"))
+            .ok_or_else(|| ParseError::type_mismatch("", "integer"))
     }
 }
 
@@ -381,7 +536,7 @@ impl FromJson for i64 {
     fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
         value
             .int()
-            .ok_or_else(|| ParseError::type_mismatch("value", "integer"))
+            .ok_or_else(|| ParseError::type_mismatch("", "integer"))
     }
 }
 
@@ -389,7 +544,7 @@ impl FromJson for f64 {
     fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
         value
             .float()
-            .ok_or_else(|| ParseError::type_mismatch("value", "number"))
+            .ok_or_else(|| ParseError::type_mismatch("", "number"))
     }
 }
 
@@ -397,7 +552,7 @@ impl FromJson for bool {
     fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
         value
             .bool()
-            .ok_or_else(|| ParseError::type_mismatch("value", "boolean"))
+            .ok_or_else(|| ParseError::type_mismatch("", "boolean"))
     }
 }
 
@@ -415,17 +570,46 @@ impl<T: FromJson> FromJson for Vec<T> {
     fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
         // Use try_map_array for direct iteration without index-based access.
         // This avoids the overhead of len() check + at(i) bounds checking per element.
+        let mut index = 0;
         value
-            .try_map_array(|elem| T::from_json(&crate::json::JsonValue::from_raw(elem)))
-            .ok_or_else(|| ParseError::type_mismatch("value", "array"))?
+            .try_map_array(|elem| {
+                let item = T::from_json(&crate::json::JsonValue::from_raw(elem))
+                    .map_err(|e| e.with_index(index));
+                index += 1;
+                item
+            })
+            .ok_or_else(|| ParseError::type_mismatch("", "array"))?
+    }
+}
+
+impl<T: FromJson, S: std::hash::BuildHasher + Default> FromJson for HashMap<String, T, S> {
+    fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
+        parse_object_entries(value).map(|entries| entries.into_iter().collect())
+    }
+}
+
+impl<T: FromJson> FromJson for std::collections::BTreeMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, ParseError> {
+        parse_object_entries(value).map(|entries| entries.into_iter().collect())
     }
 }
 
+/// Parse every value of a JSON object, keeping the key as the error path.
+fn parse_object_entries<T: FromJson>(value: &JsonValue) -> Result<Vec<(String, T)>, ParseError> {
+    value
+        .try_map_object(|key, elem| {
+            T::from_json(&crate::json::JsonValue::from_raw(elem)).map_err(|e| e.with_path(key))
+        })
+        .ok_or_else(|| ParseError::type_mismatch("", "object"))?
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
     use crate::json;
+    use crate::middleware::{TestResponse, problem};
+    use crate::request::Method;
 
     // ============================================================================
     // ID STRUCT TESTS
@@ -570,6 +754,21 @@ mod tests {
         assert!(matches!(err, ParseError::InvalidFormat { .. }));
     }
 
+    #[test]
+    fn test_parse_error_redact() {
+        let err = ParseError::invalid_format("token", "s3cr3t")
+            .redact()
+            .with_path("auth");
+        assert_eq!(err.field(), "auth.token");
+        assert!(!err.message().contains("s3cr3t"));
+
+        let err = ParseError::custom("", "unknown enum variant \"s3cr3t\"").redact();
+        assert!(!err.message().contains("s3cr3t"));
+
+        let err = ParseError::missing("token");
+        assert_eq!(err.clone().redact(), err);
+    }
+
     #[test]
     fn test_parse_error_type_mismatch() {
         let err = ParseError::type_mismatch("age", "integer");
@@ -664,6 +863,69 @@ mod tests {
         assert_eq!(err.field(), "data.value");
     }
 
+    #[test]
+    fn test_parse_error_with_index() {
+        let err = ParseError::missing("name").with_index(2).with_path("items");
+        assert_eq!(err.field(), "items[2].name");
+        assert!(err.message().contains("`items[2].name`"));
+
+        // Errors about the value itself take the index as their field
+        let err = ParseError::type_mismatch("", "string").with_index(0);
+        assert_eq!(err.field(), "[0]");
+        assert_eq!(err.with_path("tags").field(), "tags[0]");
+
+        // Nested arrays
+        let err = ParseError::missing("x").with_index(1).with_index(3);
+        assert_eq!(err.field(), "[3][1].x");
+    }
+
+    #[test]
+    fn test_parse_error_empty_field_message() {
+        assert_eq!(
+            ParseError::type_mismatch("", "integer").to_string(),
+            "expected integer"
+        );
+        assert_eq!(
+            ParseError::missing("").to_string(),
+            "missing required value"
+        );
+    }
+
+    #[test]
+    fn test_from_json_vec_reports_element_index() {
+        let v = json::arr().push(json::int(1)).push(json::str("two"));
+        let err = Vec::<i32>::from_json(&v).unwrap_err();
+        assert_eq!(err.field(), "[1]");
+        assert!(err.message().contains("integer"));
+    }
+
+    #[test]
+    fn test_from_json_hashmap() {
+        let v = json::obj().set("a", json::int(1)).set("b", json::int(2));
+        let map = HashMap::<String, i64>::from_json(&v).unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["b"], 2);
+
+        let map = std::collections::BTreeMap::<String, i64>::from_json(&v).unwrap();
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_from_json_map_errors() {
+        let v = json::obj()
+            .set("a", json::int(1))
+            .set("b", json::str("two"));
+        let err = HashMap::<String, i64>::from_json(&v).unwrap_err();
+        assert_eq!(err.field(), "b");
+
+        let v = json::obj().set("tags", json::arr().push(json::int(1)));
+        let err = HashMap::<String, Vec<String>>::from_json(&v).unwrap_err();
+        assert_eq!(err.field(), "tags[0]");
+
+        let err = HashMap::<String, i64>::from_json(&json::arr()).unwrap_err();
+        assert!(err.message().contains("object"));
+    }
+
     #[test]
     fn test_validation_error_to_parse_error() {
         let validation_err = ValidationError::min("count", 1);
@@ -820,7 +1082,7 @@ mod tests {
         let result = String::from_json(&v);
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.field(), "value");
+        assert_eq!(err.field(), "");
         assert!(err.message().contains("string"));
     }
 
@@ -1226,4 +1488,48 @@ mod tests {
         let parsed = result.unwrap();
         assert!((parsed - (-42.5)).abs() < 0.001);
     }
+
+    // ============================================================================
+    // FROM REQUEST TESTS
+    // ============================================================================
+
+    struct Tenant {
+        id: String,
+    }
+
+    impl FromRequest for Tenant {
+        fn from_request<R: HttpResponse>(req: &Request) -> Result<Self, R> {
+            req.header_all("x-tenant-id")
+                .first()
+                .map(|id| Self {
+                    id: (*id).to_string(),
+                })
+                .ok_or_else(|| problem(401, "Missing tenant"))
+        }
+    }
+
+    #[test]
+    fn test_from_request_extracts_and_rejects() {
+        let req = Request::new(
+            Method::Get,
+            "/projects".to_string(),
+            vec![("X-Tenant-Id".to_string(), "acme".to_string())],
+            None,
+            HashMap::new(),
+        );
+        let tenant = Tenant::from_request::<TestResponse>(&req).unwrap();
+        assert_eq!(tenant.id, "acme");
+
+        let req = Request::new(
+            Method::Get,
+            "/projects".to_string(),
+            vec![],
+            None,
+            HashMap::new(),
+        );
+        let Err(rejection) = Tenant::from_request::<TestResponse>(&req) else {
+            panic!("expected a rejection");
+        };
+        assert_eq!(rejection.status, 401);
+    }
 }
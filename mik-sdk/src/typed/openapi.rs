@@ -0,0 +1,118 @@
+//! OpenAPI 3.1 conversion for generated documents.
+
+use miniserde::json::{self, Array, Object, Value};
+
+/// Convert an OpenAPI 3.0 document, as generated by `routes!`, to OpenAPI 3.1.
+///
+/// Schemas marked `"nullable": true` get `"null"` added to their `type`
+/// (or are wrapped in `anyOf` when they have no `type`, like `$ref`s), and
+/// the version becomes `3.1.0`. Returns `None` if `document` is not JSON.
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::typed::openapi_3_1;
+/// let doc = r#"{"openapi":"3.0.0","components":{"schemas":{"Name":{"nullable":true,"type":"string"}}}}"#;
+/// let upgraded = openapi_3_1(doc).unwrap();
+/// assert!(upgraded.contains(r#""openapi":"3.1.0""#));
+/// assert!(upgraded.contains(r#""type":["string","null"]"#));
+/// ```
+#[must_use]
+pub fn openapi_3_1(document: &str) -> Option<String> {
+    let mut doc: Value = json::from_str(document).ok()?;
+    upgrade(&mut doc);
+    if let Value::Object(root) = &mut doc {
+        root.insert("openapi".to_string(), Value::String("3.1.0".to_string()));
+    }
+    Some(json::to_string(&doc))
+}
+
+/// Rewrite `nullable` schemas below `value`.
+fn upgrade(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for child in object.values_mut() {
+                upgrade(child);
+            }
+            // A property named "nullable" holds a schema, not `true`
+            if matches!(object.get("nullable"), Some(Value::Bool(true))) {
+                object.remove("nullable");
+                make_nullable(object);
+            }
+        },
+        Value::Array(items) => items.iter_mut().for_each(upgrade),
+        _ => {},
+    }
+}
+
+/// Let `schema` also accept `null`, the 3.1 way.
+fn make_nullable(schema: &mut Object) {
+    if let Some(Value::Array(values)) = schema.get_mut("enum") {
+        values.push(Value::Null);
+    }
+    match schema.get_mut("type") {
+        Some(Value::String(ty)) => {
+            let mut types = Array::new();
+            types.push(Value::String(std::mem::take(ty)));
+            types.push(Value::String("null".to_string()));
+            schema.insert("type".to_string(), Value::Array(types));
+        },
+        Some(_) => {},
+        None => {
+            let mut null = Object::new();
+            null.insert("type".to_string(), Value::String("null".to_string()));
+            let mut any_of = Array::new();
+            any_of.push(Value::Object(std::mem::take(schema)));
+            any_of.push(Value::Object(null));
+            schema.insert("anyOf".to_string(), Value::Array(any_of));
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sets_version() {
+        let doc = openapi_3_1(r#"{"openapi":"3.0.0","paths":{}}"#).unwrap();
+        assert_eq!(doc, r#"{"openapi":"3.1.0","paths":{}}"#);
+    }
+
+    #[test]
+    fn test_nullable_type_becomes_type_array() {
+        let doc = openapi_3_1(r#"{"s":{"nullable":true,"type":"integer","minimum":0}}"#).unwrap();
+        assert_eq!(
+            doc,
+            r#"{"openapi":"3.1.0","s":{"minimum":0,"type":["integer","null"]}}"#
+        );
+    }
+
+    #[test]
+    fn test_nullable_enum_accepts_null() {
+        let doc = openapi_3_1(r#"{"s":{"nullable":true,"type":"string","enum":["a"]}}"#).unwrap();
+        assert!(doc.contains(r#""enum":["a",null]"#));
+    }
+
+    #[test]
+    fn test_nullable_ref_becomes_any_of() {
+        let doc =
+            openapi_3_1(r##"{"s":{"nullable":true,"$ref":"#/components/schemas/User"}}"##).unwrap();
+        assert!(
+            doc.contains(
+                r##""s":{"anyOf":[{"$ref":"#/components/schemas/User"},{"type":"null"}]}"##
+            )
+        );
+    }
+
+    #[test]
+    fn test_property_named_nullable_is_kept() {
+        let doc = openapi_3_1(r#"{"properties":{"nullable":{"type":"boolean"}}}"#).unwrap();
+        assert!(doc.contains(r#""nullable":{"type":"boolean"}"#));
+    }
+
+    #[test]
+    fn test_invalid_document() {
+        assert!(openapi_3_1("not json").is_none());
+    }
+}
@@ -1,6 +1,6 @@
 //! ParseError enum and implementations.
 
-use super::ValidationError;
+use super::{REDACTED, ValidationError};
 
 /// Error type for parsing failures.
 ///
@@ -132,20 +132,82 @@ impl ParseError {
     /// ```
     #[must_use]
     pub fn with_path(self, parent: &str) -> Self {
+        self.map_field(|field| {
+            if field.is_empty() {
+                parent.to_string()
+            } else if field.starts_with('[') {
+                format!("{parent}{field}")
+            } else {
+                format!("{parent}.{field}")
+            }
+        })
+    }
+
+    /// Add the position of the failing element within an array.
+    ///
+    /// Combined with [`with_path`](Self::with_path), this builds paths like
+    /// `items[2].name`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::typed::ParseError;
+    /// let err = ParseError::missing("name").with_index(2).with_path("items");
+    /// assert_eq!(err.field(), "items[2].name");
+    /// ```
+    #[must_use]
+    pub fn with_index(self, index: usize) -> Self {
+        self.map_field(|field| {
+            if field.is_empty() || field.starts_with('[') {
+                format!("[{index}]{field}")
+            } else {
+                format!("[{index}].{field}")
+            }
+        })
+    }
+
+    /// Hide the rejected value, for fields marked `#[field(redact)]`.
+    ///
+    /// The value of an [`InvalidFormat`](Self::InvalidFormat) error becomes
+    /// `[REDACTED]`, and custom messages, which may quote the value, become
+    /// `invalid value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mik_sdk::typed::ParseError;
+    /// let err = ParseError::invalid_format("pin", "1234").redact();
+    /// assert_eq!(err.to_string(), "invalid format for `pin`: [REDACTED]");
+    /// ```
+    #[must_use]
+    pub fn redact(self) -> Self {
         match self {
-            Self::MissingField { field } => Self::MissingField {
-                field: format!("{parent}.{field}"),
+            Self::InvalidFormat { field, .. } => Self::InvalidFormat {
+                field,
+                value: REDACTED.to_string(),
+            },
+            Self::Custom { field, .. } => Self::Custom {
+                field,
+                message: "invalid value".to_string(),
             },
+            other => other,
+        }
+    }
+
+    /// Rewrite the field name, keeping the rest of the error.
+    fn map_field(self, f: impl FnOnce(&str) -> String) -> Self {
+        match self {
+            Self::MissingField { field } => Self::MissingField { field: f(&field) },
             Self::InvalidFormat { field, value } => Self::InvalidFormat {
-                field: format!("{parent}.{field}"),
+                field: f(&field),
                 value,
             },
             Self::TypeMismatch { field, expected } => Self::TypeMismatch {
-                field: format!("{parent}.{field}"),
+                field: f(&field),
                 expected,
             },
             Self::Custom { field, message } => Self::Custom {
-                field: format!("{parent}.{field}"),
+                field: f(&field),
                 message,
             },
         }
@@ -154,13 +216,23 @@ impl ParseError {
 
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // An empty field means the error is about the parsed value itself
         match self {
+            Self::MissingField { field } if field.is_empty() => {
+                write!(f, "missing required value")
+            },
             Self::MissingField { field } => {
                 write!(f, "missing required field `{field}`")
             },
+            Self::InvalidFormat { field, value } if field.is_empty() => {
+                write!(f, "invalid format: {value}")
+            },
             Self::InvalidFormat { field, value } => {
                 write!(f, "invalid format for `{field}`: {value}")
             },
+            Self::TypeMismatch { field, expected } if field.is_empty() => {
+                write!(f, "expected {expected}")
+            },
             Self::TypeMismatch { field, expected } => {
                 write!(f, "expected {expected} for field `{field}`")
             },
@@ -0,0 +1,226 @@
+//! Matcher for `#[field(pattern = "...")]` regexes.
+//!
+//! The derive macros parse and compile each pattern when they expand, so an
+//! invalid regex is a compile error and no regex engine ships in the WASM
+//! component. What remains at runtime is this small Pike VM, which runs the
+//! compiled [`Inst`] program in a single pass over the input without
+//! backtracking.
+//!
+//! Matching follows `regex::Regex::is_match` semantics: the pattern may match
+//! anywhere in the value unless it is anchored with `^`/`$`.
+
+/// A compiled pattern instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Inst {
+    /// Consume one character inside any of the sorted, non-overlapping ranges.
+    Ranges(&'static [(char, char)]),
+    /// Continue at both targets.
+    Split(usize, usize),
+    /// Continue at the target.
+    Jump(usize),
+    /// Continue at the next instruction if the assertion holds.
+    Assert(Look),
+    /// The pattern matched.
+    Match,
+}
+
+/// A zero-width assertion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Look {
+    /// `^` (or `\A`): start of the value.
+    Start,
+    /// `$` (or `\z`): end of the value.
+    End,
+    /// `(?m:^)`: start of the value or after `\n`.
+    StartLine,
+    /// `(?m:$)`: end of the value or before `\n`.
+    EndLine,
+    /// `\b`: between a word and a non-word character.
+    WordBoundary,
+    /// `\B`: not at a word boundary.
+    NotWordBoundary,
+    /// `(?-u:\b)`: word boundary with ASCII word characters.
+    AsciiWordBoundary,
+    /// `(?-u:\B)`: not at an ASCII word boundary.
+    NotAsciiWordBoundary,
+}
+
+impl Look {
+    fn holds(self, prev: Option<char>, next: Option<char>) -> bool {
+        let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let ascii_word = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+        match self {
+            Self::Start => prev.is_none(),
+            Self::End => next.is_none(),
+            Self::StartLine => prev.is_none_or(|c| c == '\n'),
+            Self::EndLine => next.is_none_or(|c| c == '\n'),
+            Self::WordBoundary => word(prev) != word(next),
+            Self::NotWordBoundary => word(prev) == word(next),
+            Self::AsciiWordBoundary => ascii_word(prev) != ascii_word(next),
+            Self::NotAsciiWordBoundary => ascii_word(prev) == ascii_word(next),
+        }
+    }
+}
+
+/// Whether the compiled `program` matches anywhere in `haystack`.
+#[must_use]
+pub fn is_match(program: &[Inst], haystack: &str) -> bool {
+    let mut vm = Vm {
+        program,
+        seen: vec![false; program.len()],
+        stack: Vec::new(),
+    };
+    let mut current = Vec::new();
+    let mut next = Vec::new();
+    let mut prev = None;
+    let mut rest = haystack.chars();
+
+    loop {
+        let ahead = rest.clone().next();
+        // Start a new attempt at every position (unanchored search)
+        if vm.add(&mut current, 0, prev, ahead) {
+            return true;
+        }
+        let Some(c) = rest.next() else {
+            return false;
+        };
+        let ahead = rest.clone().next();
+        vm.seen.fill(false);
+        for &pc in &current {
+            if let Inst::Ranges(ranges) = program[pc]
+                && in_ranges(ranges, c)
+                && vm.add(&mut next, pc + 1, Some(c), ahead)
+            {
+                return true;
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+        next.clear();
+        prev = Some(c);
+    }
+}
+
+fn in_ranges(ranges: &[(char, char)], c: char) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+struct Vm<'a> {
+    program: &'a [Inst],
+    /// Instructions already queued at the current position.
+    seen: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl Vm<'_> {
+    /// Queue the character-consuming threads reachable from `pc`, returning
+    /// `true` if a `Match` is reachable.
+    fn add(
+        &mut self,
+        list: &mut Vec<usize>,
+        pc: usize,
+        prev: Option<char>,
+        next: Option<char>,
+    ) -> bool {
+        self.stack.clear();
+        self.stack.push(pc);
+        while let Some(pc) = self.stack.pop() {
+            if std::mem::replace(&mut self.seen[pc], true) {
+                continue;
+            }
+            match self.program[pc] {
+                Inst::Match => return true,
+                Inst::Ranges(_) => list.push(pc),
+                Inst::Jump(to) => self.stack.push(to),
+                Inst::Split(first, second) => {
+                    self.stack.push(second);
+                    self.stack.push(first);
+                },
+                Inst::Assert(look) => {
+                    if look.holds(prev, next) {
+                        self.stack.push(pc + 1);
+                    }
+                },
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOWER: &[(char, char)] = &[('a', 'z')];
+    const DIGIT: &[(char, char)] = &[('0', '9')];
+
+    /// `^[a-z]+$`
+    const SLUG: &[Inst] = &[
+        Inst::Assert(Look::Start),
+        Inst::Ranges(LOWER),
+        Inst::Split(1, 3),
+        Inst::Assert(Look::End),
+        Inst::Match,
+    ];
+
+    /// `\d*` (matches the empty string anywhere)
+    const DIGITS: &[Inst] = &[
+        Inst::Split(1, 3),
+        Inst::Ranges(DIGIT),
+        Inst::Jump(0),
+        Inst::Match,
+    ];
+
+    /// `\bid\b`
+    const WORD: &[Inst] = &[
+        Inst::Assert(Look::WordBoundary),
+        Inst::Ranges(&[('i', 'i')]),
+        Inst::Ranges(&[('d', 'd')]),
+        Inst::Assert(Look::WordBoundary),
+        Inst::Match,
+    ];
+
+    #[test]
+    fn test_anchored_match() {
+        assert!(is_match(SLUG, "hello"));
+        assert!(!is_match(SLUG, "Hello"));
+        assert!(!is_match(SLUG, "hello world"));
+        assert!(!is_match(SLUG, ""));
+    }
+
+    #[test]
+    fn test_empty_loop_terminates() {
+        assert!(is_match(DIGITS, ""));
+        assert!(is_match(DIGITS, "abc"));
+    }
+
+    #[test]
+    fn test_unanchored_search_and_word_boundary() {
+        assert!(is_match(WORD, "user id 7"));
+        assert!(is_match(WORD, "id"));
+        assert!(!is_match(WORD, "userid"));
+        assert!(!is_match(WORD, "ids"));
+    }
+
+    #[test]
+    fn test_range_lookup() {
+        let ranges = &[('0', '9'), ('A', 'Z'), ('a', 'z'), ('é', 'é')];
+        for c in ['0', '5', 'Z', 'q', 'é'] {
+            assert!(in_ranges(ranges, c), "{c}");
+        }
+        for c in ['/', ':', '_', 'è'] {
+            assert!(!in_ranges(ranges, c), "{c}");
+        }
+    }
+}
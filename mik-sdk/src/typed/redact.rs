@@ -0,0 +1,108 @@
+//! Redacted JSON for logs, behind `#[field(redact)]`.
+
+use crate::json::{JsonValue, ToJson};
+
+/// Placeholder for the value of a redacted field.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// JSON safe to write to logs.
+///
+/// `#[derive(Type)]` implements it with `#[field(redact)]` fields replaced
+/// by `"[REDACTED]"` (an absent `Option` stays `null`) and nested types
+/// redacted the same way. Log it with [`log::redacted`](crate::log::redacted).
+pub trait ToLogJson: ToJson {
+    /// The value as JSON, without secrets.
+    fn to_log_json(&self) -> JsonValue {
+        self.to_json()
+    }
+}
+
+impl<T: ToLogJson> ToLogJson for Option<T> {
+    fn to_log_json(&self) -> JsonValue {
+        self.as_ref()
+            .map_or_else(crate::json::null, ToLogJson::to_log_json)
+    }
+}
+
+impl<T: ToLogJson> ToLogJson for Vec<T> {
+    fn to_log_json(&self) -> JsonValue {
+        self.iter()
+            .fold(crate::json::arr(), |arr, item| arr.push(item.to_log_json()))
+    }
+}
+
+// Lets derived code call `to_log_json` on fields that implement it and fall
+// back to `to_json` on the others: method lookup tries `__ViaLogJson` on
+// `__LogJson<T>` before auto-referencing to `__ViaToJson` on `&__LogJson<T>`.
+
+#[doc(hidden)]
+#[derive(Debug)]
+#[allow(clippy::exhaustive_structs)] // Built by derived code
+pub struct __LogJson<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __ViaLogJson {
+    fn __log_json(&self) -> JsonValue;
+}
+
+impl<T: ToLogJson + ?Sized> __ViaLogJson for __LogJson<'_, T> {
+    fn __log_json(&self) -> JsonValue {
+        self.0.to_log_json()
+    }
+}
+
+#[doc(hidden)]
+pub trait __ViaToJson {
+    fn __log_json(&self) -> JsonValue;
+}
+
+impl<T: ToJson + ?Sized> __ViaToJson for &__LogJson<'_, T> {
+    fn __log_json(&self) -> JsonValue {
+        self.0.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+
+    struct Secret(String);
+
+    impl ToJson for Secret {
+        fn to_json(&self) -> JsonValue {
+            json::str(&self.0)
+        }
+    }
+
+    impl ToLogJson for Secret {
+        fn to_log_json(&self) -> JsonValue {
+            json::str(REDACTED)
+        }
+    }
+
+    #[test]
+    fn test_containers() {
+        let secrets = vec![Secret("a".into()), Secret("b".into())];
+        assert_eq!(
+            secrets.to_log_json().to_string(),
+            r#"["[REDACTED]","[REDACTED]"]"#
+        );
+        assert!(None::<Secret>.to_log_json().is_null());
+    }
+
+    #[test]
+    fn test_falls_back_to_json() {
+        let secret = Secret("a".into());
+        let count = 3;
+        assert_eq!(
+            __LogJson(&secret).__log_json().str().as_deref(),
+            Some(REDACTED)
+        );
+        assert_eq!((&__LogJson(&count)).__log_json().int(), Some(3));
+        assert_eq!(
+            __LogJson(&Some(secret)).__log_json().str().as_deref(),
+            Some(REDACTED)
+        );
+    }
+}
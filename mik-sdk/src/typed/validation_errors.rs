@@ -0,0 +1,147 @@
+//! ValidationErrors collection for reporting every violation at once.
+
+use super::ValidationError;
+use crate::json::{self, JsonValue, ToJson};
+
+/// One entry in [`ValidationErrors`]: a violation and the value that caused it.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Violation {
+    /// The violated constraint, carrying the field path and message
+    pub error: ValidationError,
+    /// The rejected value, when known
+    pub rejected: Option<JsonValue>,
+}
+
+/// Every constraint violation found while validating a value.
+///
+/// Derived `Validate` impls fill this from [`Validate::validate_all`], one
+/// violation per field with the rejected value. `routes!` turns it into a
+/// `422` RFC 7807 response whose `errors` extension lists each violation.
+///
+/// [`Validate::validate_all`]: super::Validate::validate_all
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::typed::{ValidationError, ValidationErrors};
+/// # use mik_sdk::json::{self, ToJson};
+/// let mut errors = ValidationErrors::new();
+/// errors.push_rejected(ValidationError::min("name", 3), json::str("al"));
+/// errors.push(ValidationError::format("email", "email"));
+///
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(
+///     errors.to_string(),
+///     "`name` must be at least 3; `email` must be a valid email"
+/// );
+/// assert_eq!(
+///     errors.to_json().to_string(),
+///     r#"[{"code":"min","field":"name","message":"`name` must be at least 3","rejected_value":"al"},{"code":"format","field":"email","message":"`email` must be a valid email"}]"#
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors {
+    violations: Vec<Violation>,
+}
+
+impl ValidationErrors {
+    /// Create an empty collection.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            violations: Vec::new(),
+        }
+    }
+
+    /// Record a violation without a rejected value.
+    pub fn push(&mut self, error: ValidationError) {
+        self.violations.push(Violation {
+            error,
+            rejected: None,
+        });
+    }
+
+    /// Record a violation along with the value that was rejected.
+    pub fn push_rejected(&mut self, error: ValidationError, rejected: JsonValue) {
+        self.violations.push(Violation {
+            error,
+            rejected: Some(rejected),
+        });
+    }
+
+    /// Whether no violations were recorded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Number of recorded violations.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    /// Iterate over the recorded violations in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Violation> {
+        self.violations.iter()
+    }
+
+    /// `Ok(())` if empty, otherwise `Err(self)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` when at least one violation was recorded.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl From<ValidationError> for ValidationErrors {
+    fn from(error: ValidationError) -> Self {
+        let mut errors = Self::new();
+        errors.push(error);
+        errors
+    }
+}
+
+impl<'a> IntoIterator for &'a ValidationErrors {
+    type Item = &'a Violation;
+    type IntoIter = std::slice::Iter<'a, Violation>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Serializes to the `errors` array of the problem response: one object per
+/// violation with `field`, `code`, `message` and, when known, `rejected_value`.
+impl ToJson for ValidationErrors {
+    fn to_json(&self) -> JsonValue {
+        self.violations.iter().fold(json::arr(), |arr, violation| {
+            let entry = json::obj()
+                .set("field", json::str(violation.error.field()))
+                .set("code", json::str(violation.error.constraint()))
+                .set("message", json::str(violation.error.message()));
+            let entry = match &violation.rejected {
+                Some(value) => entry.set("rejected_value", value.clone()),
+                None => entry,
+            };
+            arr.push(entry)
+        })
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", violation.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
@@ -4,6 +4,7 @@
 //! - HTTP client with `.send()` method
 //! - Random number generation via `wasi:random/random`
 //! - Wall clock access via `wasi:clocks/wall-clock`
+//! - Monotonic clock access via `wasi:clocks/monotonic-clock`
 //!
 //! Only available when the `wasi-http` feature is enabled.
 
@@ -22,6 +23,7 @@ use wasi::http::types as http_types;
 use wasi::io::streams::StreamError;
 
 use crate::http_client::{ClientRequest, Error, Method, Response, Result, Scheme};
+use crate::stream::ChunkedBody;
 
 impl ClientRequest {
     /// Send the HTTP request using WASI HTTP.
@@ -55,6 +57,80 @@ impl ClientRequest {
     /// - TLS handshake fails
     /// - SSRF protection blocks a private IP address
     pub fn send(self) -> Result<Response> {
+        // Propagate the current trace (see crate::trace)
+        self.with_current_trace().send_wasi()
+    }
+
+    /// Send the request as is over `wasi:http/outgoing-handler`.
+    fn send_wasi(self) -> Result<Response> {
+        let incoming_response = self.start_wasi()?;
+        let status = incoming_response.status();
+        let header_entries = response_headers(&incoming_response);
+
+        // Read response body
+        let body = incoming_response
+            .consume()
+            .map_err(|()| Error::ResponseError("Failed to consume body".into()))?;
+        let body_stream = body
+            .stream()
+            .map_err(|()| Error::ResponseError("Failed to get body stream".into()))?;
+
+        // Read response body using blocking_read.
+        // Non-blocking read() can return empty immediately if data isn't ready,
+        // which causes empty bodies on some runtimes (e.g., wasmCloud http-client provider).
+        let mut body_bytes = Vec::new();
+        loop {
+            match body_stream.blocking_read(64 * 1024) {
+                Ok(chunk) => {
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    body_bytes.extend_from_slice(&chunk);
+                },
+                Err(StreamError::Closed) => break,
+                Err(e) => {
+                    return Err(Error::ResponseError(format!(
+                        "Failed to read body: {:?}",
+                        e
+                    )));
+                },
+            }
+        }
+
+        Ok(Response::new(status, header_entries, body_bytes))
+    }
+
+    /// Send the request and return the response body as a [`ChunkedBody`]
+    /// that reads from the upstream as it is pulled.
+    ///
+    /// Used by [`crate::proxy`] so proxied responses are never buffered whole.
+    /// A read error ends the body early; the client sees a truncated response.
+    pub(crate) fn send_streaming(self) -> Result<(u16, Vec<(String, String)>, ChunkedBody)> {
+        let incoming_response = self.with_current_trace().start_wasi()?;
+        let status = incoming_response.status();
+        let header_entries = response_headers(&incoming_response);
+
+        let body = incoming_response
+            .consume()
+            .map_err(|()| Error::ResponseError("Failed to consume body".into()))?;
+        let body_stream = body
+            .stream()
+            .map_err(|()| Error::ResponseError("Failed to get body stream".into()))?;
+
+        // Child resources must be dropped before their parents: stream, body, response
+        let resources = (body_stream, body, incoming_response);
+        let chunks = ChunkedBody::new(move || {
+            let (body_stream, _, _) = &resources;
+            body_stream
+                .blocking_read(64 * 1024)
+                .ok()
+                .filter(|chunk| !chunk.is_empty())
+        });
+        Ok((status, header_entries, chunks))
+    }
+
+    /// Send the request and wait for the response head.
+    fn start_wasi(self) -> Result<http_types::IncomingResponse> {
         // Validate URL and check for private IPs if configured
         let (scheme, authority, path) = self.parse_url()?;
 
@@ -164,47 +240,16 @@ impl ClientRequest {
             }
         };
 
-        // Read response status
-        let status = incoming_response.status();
-
-        // Read response headers
-        let response_headers = incoming_response.headers();
-        let header_entries: Vec<(String, String)> = response_headers
-            .entries()
-            .into_iter()
-            .filter_map(|(k, v)| String::from_utf8(v).ok().map(|v| (k, v)))
-            .collect();
-
-        // Read response body
-        let body = incoming_response
-            .consume()
-            .map_err(|()| Error::ResponseError("Failed to consume body".into()))?;
-        let body_stream = body
-            .stream()
-            .map_err(|()| Error::ResponseError("Failed to get body stream".into()))?;
-
-        // Read response body using blocking_read.
-        // Non-blocking read() can return empty immediately if data isn't ready,
-        // which causes empty bodies on some runtimes (e.g., wasmCloud http-client provider).
-        let mut body_bytes = Vec::new();
-        loop {
-            match body_stream.blocking_read(64 * 1024) {
-                Ok(chunk) => {
-                    if chunk.is_empty() {
-                        break;
-                    }
-                    body_bytes.extend_from_slice(&chunk);
-                },
-                Err(StreamError::Closed) => break,
-                Err(e) => {
-                    return Err(Error::ResponseError(format!(
-                        "Failed to read body: {:?}",
-                        e
-                    )));
-                },
-            }
-        }
-
-        Ok(Response::new(status, header_entries, body_bytes))
+        Ok(incoming_response)
     }
 }
+
+/// Response headers with UTF-8 values; other values are dropped.
+fn response_headers(response: &http_types::IncomingResponse) -> Vec<(String, String)> {
+    response
+        .headers()
+        .entries()
+        .into_iter()
+        .filter_map(|(k, v)| String::from_utf8(v).ok().map(|v| (k, v)))
+        .collect()
+}
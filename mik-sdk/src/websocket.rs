@@ -0,0 +1,338 @@
+//! WebSocket endpoints.
+//!
+//! A `ws "/path" => socket` entry in `routes!` serves a WebSocket endpoint.
+//! The bridge answers the upgrade handshake and runs the connection; the
+//! handler only sees events, registered as plain functions on [`WebSocket`]:
+//!
+//! ```ignore
+//! fn chat() -> WebSocket {
+//!     WebSocket::new()
+//!         .on_open(|socket, req| {
+//!             let user = req.query_or("user", "anonymous");
+//!             socket.send_text(&format!("welcome, {user}"));
+//!         })
+//!         .on_message(|socket, message| match message {
+//!             Message::Text(text) if text == "bye" => socket.close(1000, "bye"),
+//!             message => socket.send(message), // echo
+//!         })
+//!         .on_close(|id, code, reason| log!(info, "closed", id: id, code: code, reason: reason))
+//! }
+//!
+//! routes! {
+//!     ws "/chat" => chat(),
+//!     GET "/users" => list_users,
+//! }
+//! ```
+//!
+//! # Requirements
+//!
+//! `ws` entries export the `mik:core/websocket` interface, so the handler
+//! world must include it (`export mik:core/websocket@0.1.0;`, as in the
+//! `user-handler-ws` world) and the component must be composed with the
+//! bridge built with its `websocket` feature. The runtime has to keep the
+//! connection open after `101 Switching Protocols`; on runtimes that don't,
+//! `on_close` is called with `1006` right after `on_open`.
+//!
+//! Handler components are single-threaded and one instance serves the whole
+//! connection, so per-connection state can live in a `thread_local!` keyed
+//! by [`Socket::id`].
+
+use crate::Request;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// A complete WebSocket message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Message {
+    /// UTF-8 text message.
+    Text(String),
+    /// Binary message.
+    Binary(Vec<u8>),
+}
+
+/// Something the bridge does after a callback returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Action {
+    /// Send a message to the client.
+    Send(Message),
+    /// Close the connection with a status code and reason.
+    Close(u16, String),
+}
+
+/// The connection a callback runs for; queues messages to send.
+#[derive(Debug)]
+pub struct Socket {
+    id: u64,
+    actions: Vec<Action>,
+}
+
+impl Socket {
+    /// A socket with no queued actions, e.g. to call callbacks in tests.
+    #[must_use]
+    pub const fn new(id: u64) -> Self {
+        Self {
+            id,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Connection ID, unique within the component instance.
+    #[must_use]
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Send a message.
+    pub fn send(&mut self, message: Message) {
+        self.actions.push(Action::Send(message));
+    }
+
+    /// Send a text message.
+    pub fn send_text(&mut self, text: &str) {
+        self.send(Message::Text(text.to_string()));
+    }
+
+    /// Send a binary message.
+    pub fn send_binary(&mut self, bytes: &[u8]) {
+        self.send(Message::Binary(bytes.to_vec()));
+    }
+
+    /// Send a JSON value as a text message.
+    pub fn send_json(&mut self, value: &crate::json::JsonValue) {
+        self.send(Message::Text(value.to_string()));
+    }
+
+    /// Close the connection, e.g. `close(1000, "done")` or
+    /// `close(1008, "unauthorized")`. Actions queued after this are dropped.
+    pub fn close(&mut self, code: u16, reason: &str) {
+        self.actions.push(Action::Close(code, reason.to_string()));
+    }
+
+    /// The queued actions, in order.
+    #[must_use]
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Take the queued actions, up to and including the first close.
+    #[must_use]
+    pub fn into_actions(self) -> Vec<Action> {
+        let mut actions = self.actions;
+        if let Some(close) = actions
+            .iter()
+            .position(|action| matches!(action, Action::Close(..)))
+        {
+            actions.truncate(close + 1);
+        }
+        actions
+    }
+}
+
+/// Called when a connection opens, with the upgrade request.
+pub type OnOpen = fn(&mut Socket, &Request);
+
+/// Called for every message from the client.
+pub type OnMessage = fn(&mut Socket, Message);
+
+/// Called once the connection is closed: connection ID, code and reason.
+pub type OnClose = fn(u64, u16, &str);
+
+/// The callbacks of a `ws` endpoint. Missing callbacks do nothing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebSocket {
+    on_open: Option<OnOpen>,
+    on_message: Option<OnMessage>,
+    on_close: Option<OnClose>,
+}
+
+impl WebSocket {
+    /// An endpoint without callbacks: accepts connections and ignores messages.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            on_open: None,
+            on_message: None,
+            on_close: None,
+        }
+    }
+
+    /// Set the callback for new connections.
+    #[must_use]
+    pub const fn on_open(mut self, callback: OnOpen) -> Self {
+        self.on_open = Some(callback);
+        self
+    }
+
+    /// Set the callback for incoming messages.
+    #[must_use]
+    pub const fn on_message(mut self, callback: OnMessage) -> Self {
+        self.on_message = Some(callback);
+        self
+    }
+
+    /// Set the callback for closed connections.
+    #[must_use]
+    pub const fn on_close(mut self, callback: OnClose) -> Self {
+        self.on_close = Some(callback);
+        self
+    }
+
+    /// Run `on_open` for a connection and return its actions.
+    #[must_use]
+    pub fn open(&self, id: u64, req: &Request) -> Vec<Action> {
+        let mut socket = Socket::new(id);
+        if let Some(on_open) = self.on_open {
+            on_open(&mut socket, req);
+        }
+        socket.into_actions()
+    }
+
+    /// Run `on_message` for a connection and return its actions.
+    #[must_use]
+    pub fn message(&self, id: u64, message: Message) -> Vec<Action> {
+        let mut socket = Socket::new(id);
+        if let Some(on_message) = self.on_message {
+            on_message(&mut socket, message);
+        }
+        socket.into_actions()
+    }
+
+    /// Run `on_close` for a connection.
+    pub fn close(&self, id: u64, code: u16, reason: &str) {
+        if let Some(on_close) = self.on_close {
+            on_close(id, code, reason);
+        }
+    }
+}
+
+// ============================================================================
+// DISPATCH (used by routes!)
+// ============================================================================
+
+thread_local! {
+    /// Endpoint index of each open connection.
+    static CONNECTIONS: RefCell<HashMap<u64, usize>> = RefCell::new(HashMap::new());
+}
+
+/// Index of the endpoint serving `path` (query ignored), if any.
+#[doc(hidden)]
+#[must_use]
+pub fn __route(routes: &[&str], path: &str) -> Option<usize> {
+    let path = path.split('?').next().unwrap_or(path);
+    routes.iter().position(|route| *route == path)
+}
+
+/// Open a connection on the endpoint serving the request path.
+///
+/// Called by code generated by `routes!` for `ws` entries.
+#[doc(hidden)]
+pub fn __open(sockets: &[WebSocket], routes: &[&str], id: u64, req: &Request) -> Vec<Action> {
+    let Some((index, socket)) =
+        __route(routes, req.path()).and_then(|i| sockets.get(i).map(|s| (i, s)))
+    else {
+        return vec![Action::Close(1011, "No WebSocket endpoint".to_string())];
+    };
+    CONNECTIONS.with(|connections| connections.borrow_mut().insert(id, index));
+    socket.open(id, req)
+}
+
+/// Dispatch a message to the connection's endpoint.
+///
+/// Called by code generated by `routes!` for `ws` entries.
+#[doc(hidden)]
+pub fn __message(sockets: &[WebSocket], id: u64, message: Message) -> Vec<Action> {
+    let index = CONNECTIONS.with(|connections| connections.borrow().get(&id).copied());
+    index
+        .and_then(|i| sockets.get(i))
+        .map_or_else(Vec::new, |socket| socket.message(id, message))
+}
+
+/// Close the connection and forget it.
+///
+/// Called by code generated by `routes!` for `ws` entries.
+#[doc(hidden)]
+pub fn __close(sockets: &[WebSocket], id: u64, code: u16, reason: &str) {
+    let index = CONNECTIONS.with(|connections| connections.borrow_mut().remove(&id));
+    if let Some(socket) = index.and_then(|i| sockets.get(i)) {
+        socket.close(id, code, reason);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use std::cell::Cell;
+
+    thread_local! {
+        static CLOSED: Cell<Option<(u64, u16)>> = const { Cell::new(None) };
+    }
+
+    fn request(path: &str) -> Request {
+        Request::new(
+            Method::Get,
+            path.to_string(),
+            Vec::new(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn echo() -> WebSocket {
+        WebSocket::new()
+            .on_open(|socket, req| socket.send_text(req.query_or("greeting", "hi")))
+            .on_message(|socket, message| match message {
+                Message::Text(text) if text == "bye" => {
+                    socket.close(1000, "bye");
+                    socket.send_text("dropped");
+                },
+                message => socket.send(message),
+            })
+            .on_close(|id, code, _| CLOSED.with(|closed| closed.set(Some((id, code)))))
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(__route(&["/a", "/chat"], "/chat?x=1"), Some(1));
+        assert_eq!(__route(&["/a"], "/chat"), None);
+    }
+
+    #[test]
+    fn test_session() {
+        let sockets = [WebSocket::new(), echo()];
+        let routes = ["/quiet", "/chat"];
+
+        let opened = __open(&sockets, &routes, 7, &request("/chat?greeting=hello"));
+        assert_eq!(
+            opened,
+            vec![Action::Send(Message::Text("hello".to_string()))]
+        );
+
+        let echoed = __message(&sockets, 7, Message::Binary(vec![1, 2]));
+        assert_eq!(echoed, vec![Action::Send(Message::Binary(vec![1, 2]))]);
+
+        // Actions after a close are dropped
+        let closing = __message(&sockets, 7, Message::Text("bye".to_string()));
+        assert_eq!(closing, vec![Action::Close(1000, "bye".to_string())]);
+
+        __close(&sockets, 7, 1000, "bye");
+        assert_eq!(CLOSED.with(Cell::get), Some((7, 1000)));
+        assert!(__message(&sockets, 7, Message::Text("late".to_string())).is_empty());
+    }
+
+    #[test]
+    fn test_default_callbacks() {
+        let sockets = [WebSocket::new()];
+        assert!(__open(&sockets, &["/quiet"], 1, &request("/quiet")).is_empty());
+        assert!(__message(&sockets, 1, Message::Text("x".to_string())).is_empty());
+        __close(&sockets, 1, 1006, "");
+    }
+
+    #[test]
+    fn test_unknown_endpoint_closes() {
+        let actions = __open(&[], &[], 1, &request("/missing"));
+        assert!(matches!(actions.as_slice(), [Action::Close(1011, _)]));
+    }
+}
@@ -28,6 +28,7 @@ mod delete;
 mod errors;
 mod parse;
 mod read;
+mod row;
 mod trace;
 mod types;
 mod update;
@@ -94,5 +95,28 @@ pub fn sql_delete(input: TokenStream) -> TokenStream {
     delete::sql_delete_impl(input)
 }
 
+// ============================================================================
+// ROW MAPPING
+// ============================================================================
+
+/// Derive `FromRow`, mapping result columns to fields by name.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Row)]
+/// struct User {
+///     id: i64,
+///     #[row(rename = "full_name")]
+///     name: String,
+///     email: Option<String>, // missing or NULL -> None
+///     #[row(default)]
+///     login_count: i32,      // missing or NULL -> 0
+/// }
+/// ```
+#[proc_macro_derive(Row, attributes(row))]
+pub fn derive_row(input: TokenStream) -> TokenStream {
+    row::derive_row_impl(input)
+}
+
 // Note: ids! macro has been consolidated into mik-sdk-macros
 // Users get ids! via mik-sdk or mik-sql (which re-exports from mik-sdk-macros)
@@ -190,20 +190,21 @@ pub fn generate_sql_tokens(input: SqlInput) -> TokenStream2 {
             let max_depth_val = max_depth.map_or_else(|| quote! { 5 }, |d| quote! { #d as usize });
 
             let setup = quote! {
+                use ::mik_sql::MergeFilters as _;
+
                 let __validator = ::mik_sql::FilterValidator::new()
                     .allow_fields(&[#(#allow_strs),*])
                     .deny_operators(&[#(#deny_op_tokens),*])
                     .max_depth(#max_depth_val);
 
-                for __user_filter in &#merge_expr {
-                    __validator.validate(&__user_filter).map_err(|e| e.to_string())?;
-                }
+                (#merge_expr)
+                    .validate_merge(&__validator)
+                    .map_err(|e| e.to_string())?;
             };
 
+            // Lists are ANDed; a FilterExpr keeps its AND/OR/NOT structure
             let chain = quote! {
-                for __f in &#merge_expr {
-                    __builder = __builder.filter(__f.field.clone(), __f.op, __f.value.clone());
-                }
+                __builder = (#merge_expr).apply_merge(__builder);
             };
 
             (setup, chain)
@@ -0,0 +1,102 @@
+//! #[derive(Row)] implementation for typed result rows.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input};
+
+/// Per-field `#[row(...)]` options.
+#[derive(Default)]
+struct RowAttrs {
+    rename: Option<String>,
+    default: bool,
+}
+
+fn parse_row_attrs(attrs: &[syn::Attribute]) -> syn::Result<RowAttrs> {
+    let mut parsed = RowAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("row")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                parsed.rename = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                parsed.default = true;
+                Ok(())
+            } else {
+                Err(meta
+                    .error("Unknown row attribute. Valid options: rename = \"column\", default"))
+            }
+        })?;
+    }
+    Ok(parsed)
+}
+
+// ============================================================================
+// DERIVE ROW
+// ============================================================================
+
+/// Generates a `FromRow` implementation mapping columns to fields by name.
+///
+/// Columns default to the field name. `Option` fields accept missing and
+/// NULL columns; `#[row(default)]` fields fall back to `Default::default()`.
+pub fn derive_row_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    name,
+                    "#[derive(Row)] requires a struct with named fields",
+                )
+                .to_compile_error()
+                .into();
+            },
+        },
+        _ => {
+            return syn::Error::new_spanned(name, "#[derive(Row)] only supports structs")
+                .to_compile_error()
+                .into();
+        },
+    };
+
+    let mut extractions = Vec::new();
+    for field in fields {
+        let Some(field_name) = field.ident.as_ref() else {
+            continue;
+        };
+        let attrs = match parse_row_attrs(&field.attrs) {
+            Ok(attrs) => attrs,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let column = attrs.rename.unwrap_or_else(|| field_name.to_string());
+
+        extractions.push(if attrs.default {
+            quote! {
+                #field_name: match __row.value(#column) {
+                    None | Some(::mik_sql::Value::Null) => ::std::default::Default::default(),
+                    Some(_) => __row.get(#column)?,
+                }
+            }
+        } else {
+            quote! { #field_name: __row.get(#column)? }
+        });
+    }
+
+    let tokens = quote! {
+        impl #impl_generics ::mik_sql::FromRow for #name #ty_generics #where_clause {
+            fn from_row(
+                __row: &::mik_sql::Row,
+            ) -> ::std::result::Result<Self, ::mik_sql::RowError> {
+                ::std::result::Result::Ok(Self {
+                    #(#extractions),*
+                })
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
@@ -1,6 +1,6 @@
 //! Shared filter building functions.
 
-use super::types::{CompoundFilter, Filter, FilterExpr, LogicalOp, Operator, Value};
+use super::types::{CompoundFilter, Filter, FilterExpr, LogicalOp, Operator, Value, escape_like};
 use crate::dialect::Dialect;
 
 /// Build a filter expression (simple or compound).
@@ -32,25 +32,16 @@ pub(super) fn build_compound_filter_impl<D: Dialect>(
         idx = new_idx;
     }
 
-    let sql = match compound.op {
-        LogicalOp::And => {
-            if conditions.len() == 1 {
-                conditions.into_iter().next().unwrap()
-            } else {
-                format!("({})", conditions.join(" AND "))
-            }
-        },
-        LogicalOp::Or => {
-            if conditions.len() == 1 {
-                conditions.into_iter().next().unwrap()
-            } else {
-                format!("({})", conditions.join(" OR "))
-            }
-        },
-        LogicalOp::Not => {
-            let inner = conditions.into_iter().next().unwrap_or_default();
-            format!("NOT ({inner})")
-        },
+    let sql = match (compound.op, conditions.len()) {
+        // Empty groups: AND of nothing is true, OR of nothing is false,
+        // and NOT negates the (true) empty AND
+        (LogicalOp::And, 0) => "1=1".to_string(),
+        (LogicalOp::Or | LogicalOp::Not, 0) => "1=0".to_string(),
+        (LogicalOp::And | LogicalOp::Or, 1) => conditions.remove(0),
+        (LogicalOp::And, _) => format!("({})", conditions.join(" AND ")),
+        (LogicalOp::Or, _) => format!("({})", conditions.join(" OR ")),
+        // NOT over several conditions negates their conjunction
+        (LogicalOp::Not, _) => format!("NOT ({})", conditions.join(" AND ")),
     };
 
     (sql, all_params, idx)
@@ -102,25 +93,29 @@ pub(super) fn build_condition_impl<D: Dialect>(
         // ILIKE (falls back to LIKE on SQLite)
         (Operator::ILike, value) => {
             let sql = if dialect.supports_ilike() {
-                format!("{} ILIKE {}", field, dialect.param(idx))
+                format!("{} ILIKE {} ESCAPE '\\'", field, dialect.param(idx))
             } else {
-                format!("{} LIKE {}", field, dialect.param(idx))
+                format!("{} LIKE {} ESCAPE '\\'", field, dialect.param(idx))
             };
             (sql, vec![value.clone()], idx + 1)
         },
+        (Operator::Like, value) => {
+            let sql = format!("{} LIKE {} ESCAPE '\\'", field, dialect.param(idx));
+            (sql, vec![value.clone()], idx + 1)
+        },
 
-        // String pattern operators
+        // String pattern operators (the value is matched literally)
         (Operator::StartsWith, value) => {
             let sql = dialect.starts_with_clause(field, idx);
-            (sql, vec![value.clone()], idx + 1)
+            (sql, vec![literal_pattern(value)], idx + 1)
         },
         (Operator::EndsWith, value) => {
             let sql = dialect.ends_with_clause(field, idx);
-            (sql, vec![value.clone()], idx + 1)
+            (sql, vec![literal_pattern(value)], idx + 1)
         },
         (Operator::Contains, value) => {
             let sql = dialect.contains_clause(field, idx);
-            (sql, vec![value.clone()], idx + 1)
+            (sql, vec![literal_pattern(value)], idx + 1)
         },
 
         // BETWEEN operator - takes an array with exactly 2 values
@@ -151,7 +146,6 @@ pub(super) fn build_condition_impl<D: Dialect>(
                 Operator::Gte => ">=",
                 Operator::Lt => "<",
                 Operator::Lte => "<=",
-                Operator::Like => "LIKE",
                 _ => "=", // fallback for unhandled cases
             };
             let sql = format!("{} {} {}", field, op_str, dialect.param(idx));
@@ -160,11 +154,66 @@ pub(super) fn build_condition_impl<D: Dialect>(
     }
 }
 
+/// Escape a string operand of a starts/ends/contains filter so `%` and `_`
+/// match literally.
+fn literal_pattern(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(escape_like(s)),
+        other => other.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dialect::{Postgres, Sqlite};
 
+    fn eq(field: &str, value: i64) -> FilterExpr {
+        FilterExpr::Simple(Filter {
+            field: field.to_string(),
+            op: Operator::Eq,
+            value: Value::Int(value),
+        })
+    }
+
+    #[test]
+    fn test_compound_nested_rendering() {
+        // a = 1 AND (b = 2 OR NOT (c = 3))
+        let expr = FilterExpr::Compound(CompoundFilter::and(vec![
+            eq("a", 1),
+            FilterExpr::Compound(CompoundFilter::or(vec![
+                eq("b", 2),
+                FilterExpr::Compound(CompoundFilter::not(eq("c", 3))),
+            ])),
+        ]));
+        let (sql, params, idx) = build_filter_expr_impl(&Postgres, &expr, 1);
+        assert_eq!(sql, "(a = $1 AND (b = $2 OR NOT (c = $3)))");
+        assert_eq!(params, vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
+        assert_eq!(idx, 4);
+
+        let (sql, _, _) = build_filter_expr_impl(&Sqlite, &expr, 5);
+        assert_eq!(sql, "(a = ?5 AND (b = ?6 OR NOT (c = ?7)))");
+    }
+
+    #[test]
+    fn test_compound_empty_and_multi_not() {
+        let empty_and = CompoundFilter::and(vec![]);
+        let empty_or = CompoundFilter::or(vec![]);
+        assert_eq!(
+            build_compound_filter_impl(&Postgres, &empty_and, 1).0,
+            "1=1"
+        );
+        assert_eq!(build_compound_filter_impl(&Postgres, &empty_or, 1).0, "1=0");
+
+        let multi_not = CompoundFilter {
+            op: LogicalOp::Not,
+            filters: vec![eq("a", 1), eq("b", 2)],
+        };
+        let (sql, params, _) = build_compound_filter_impl(&Postgres, &multi_not, 1);
+        assert_eq!(sql, "NOT (a = $1 AND b = $2)");
+        assert_eq!(params.len(), 2);
+    }
+
     #[test]
     fn test_eq_null_postgres() {
         let pg = Postgres;
@@ -323,7 +372,7 @@ mod tests {
             value: Value::String("%john%".to_string()),
         };
         let (sql, params, idx) = build_condition_impl(&pg, &filter, 1);
-        assert_eq!(sql, "name ILIKE $1");
+        assert_eq!(sql, "name ILIKE $1 ESCAPE '\\'");
         assert_eq!(params, vec![Value::String("%john%".to_string())]);
         assert_eq!(idx, 2);
     }
@@ -337,7 +386,7 @@ mod tests {
             value: Value::String("%john%".to_string()),
         };
         let (sql, params, idx) = build_condition_impl(&sqlite, &filter, 1);
-        assert_eq!(sql, "name LIKE ?1");
+        assert_eq!(sql, "name LIKE ?1 ESCAPE '\\'");
         assert_eq!(params, vec![Value::String("%john%".to_string())]);
         assert_eq!(idx, 2);
     }
@@ -519,11 +568,41 @@ mod tests {
             value: Value::String("%smith%".to_string()),
         };
         let (sql, params, idx) = build_condition_impl(&pg, &filter, 1);
-        assert_eq!(sql, "name LIKE $1");
+        assert_eq!(sql, "name LIKE $1 ESCAPE '\\'");
         assert_eq!(params, vec![Value::String("%smith%".to_string())]);
         assert_eq!(idx, 2);
     }
 
+    #[test]
+    fn test_like_escaped_patterns() {
+        let filter = Filter {
+            field: "code".to_string(),
+            op: Operator::Like,
+            value: Value::like_starts_with("A_1%"),
+        };
+        let (sql, params, _) = build_condition_impl(&Sqlite, &filter, 1);
+        assert_eq!(sql, "code LIKE ?1 ESCAPE '\\'");
+        assert_eq!(params, vec![Value::String(r"A\_1\%%".to_string())]);
+
+        assert_eq!(
+            Value::like_ends_with(r"C:\tmp"),
+            Value::String(r"%C:\\tmp".to_string())
+        );
+        assert_eq!(escape_like("plain"), "plain");
+    }
+
+    #[test]
+    fn test_contains_matches_wildcards_literally() {
+        let filter = Filter {
+            field: "title".to_string(),
+            op: Operator::Contains,
+            value: Value::String("50%_off".to_string()),
+        };
+        let (sql, params, _) = build_condition_impl(&Postgres, &filter, 1);
+        assert_eq!(sql, "title LIKE '%' || $1 || '%' ESCAPE '\\'");
+        assert_eq!(params, vec![Value::String(r"50\%\_off".to_string())]);
+    }
+
     #[test]
     fn test_compound_and_single() {
         let pg = Postgres;
@@ -4,6 +4,7 @@ mod delete;
 mod filter;
 mod insert;
 mod parse;
+mod query;
 mod select;
 mod types;
 mod update;
@@ -12,9 +13,10 @@ mod update;
 pub use delete::{DeleteBuilder, delete, delete_sqlite};
 pub use insert::{InsertBuilder, insert, insert_sqlite};
 pub use parse::{ParseError, parse_filter};
+pub use query::{parse_query_filters, parse_rsql};
 pub use select::QueryBuilder;
 pub use types::{
     Aggregate, AggregateFunc, CompoundFilter, ComputedField, CursorDirection, Filter, FilterExpr,
-    LogicalOp, Operator, QueryResult, SortDir, SortField, Value, and, not, or, simple,
+    LogicalOp, Operator, QueryResult, SortDir, SortField, Value, and, escape_like, not, or, simple,
 };
 pub use update::{UpdateBuilder, update, update_sqlite};
@@ -57,6 +57,12 @@ use super::types::{CompoundFilter, Filter, FilterExpr, Operator, Value};
 use miniserde::json::{Number, Value as JsonValue};
 use std::fmt;
 
+/// Deepest `$and` / `$or` / `$not` nesting accepted while parsing.
+///
+/// Bounds recursion on untrusted input; `FilterValidator::max_depth`
+/// applies the (usually tighter) policy limit afterwards.
+const MAX_PARSE_DEPTH: usize = 32;
+
 /// Error type for JSON filter parsing.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -84,6 +90,13 @@ pub enum ParseError {
     },
     /// $not requires exactly one condition.
     NotRequiresOneCondition,
+    /// Malformed query-string or RSQL filter.
+    InvalidSyntax(String),
+    /// Logical operators are nested too deeply.
+    NestingTooDeep {
+        /// The maximum nesting depth.
+        max: usize,
+    },
 }
 
 /// Parse a Mongo-style filter from a JSON string.
@@ -136,6 +149,10 @@ impl fmt::Display for ParseError {
             Self::NotRequiresOneCondition => {
                 write!(f, "$not requires exactly one condition")
             },
+            Self::InvalidSyntax(msg) => write!(f, "Invalid filter syntax: {msg}"),
+            Self::NestingTooDeep { max } => {
+                write!(f, "Logical operators nested deeper than {max} levels")
+            },
         }
     }
 }
@@ -269,6 +286,17 @@ impl FilterExpr {
     ///
     /// Returns `ParseError` if the JSON structure is invalid.
     pub fn from_json(json: &JsonValue) -> Result<Self, ParseError> {
+        Self::from_json_at(json, 0)
+    }
+
+    /// Parse at a logical nesting depth, failing past [`MAX_PARSE_DEPTH`].
+    fn from_json_at(json: &JsonValue, depth: usize) -> Result<Self, ParseError> {
+        if depth > MAX_PARSE_DEPTH {
+            return Err(ParseError::NestingTooDeep {
+                max: MAX_PARSE_DEPTH,
+            });
+        }
+
         let obj = match json {
             JsonValue::Object(o) => o,
             _ => return Err(ParseError::ExpectedObject),
@@ -289,15 +317,15 @@ impl FilterExpr {
             if key.starts_with('$') {
                 match key.as_str() {
                     "$and" => {
-                        let exprs = parse_filter_array(value)?;
+                        let exprs = parse_filter_array(value, depth + 1)?;
                         filters.push(Self::Compound(CompoundFilter::and(exprs)));
                     },
                     "$or" => {
-                        let exprs = parse_filter_array(value)?;
+                        let exprs = parse_filter_array(value, depth + 1)?;
                         filters.push(Self::Compound(CompoundFilter::or(exprs)));
                     },
                     "$not" => {
-                        let inner = Self::from_json(value)?;
+                        let inner = Self::from_json_at(value, depth + 1)?;
                         filters.push(Self::Compound(CompoundFilter::not(inner)));
                     },
                     _ => return Err(ParseError::UnknownOperator(key.clone())),
@@ -319,13 +347,15 @@ impl FilterExpr {
 }
 
 /// Parse an array of filter expressions (for $and/$or).
-fn parse_filter_array(json: &JsonValue) -> Result<Vec<FilterExpr>, ParseError> {
+fn parse_filter_array(json: &JsonValue, depth: usize) -> Result<Vec<FilterExpr>, ParseError> {
     let arr = match json {
         JsonValue::Array(a) => a,
         _ => return Err(ParseError::ExpectedArray),
     };
 
-    arr.iter().map(FilterExpr::from_json).collect()
+    arr.iter()
+        .map(|item| FilterExpr::from_json_at(item, depth))
+        .collect()
 }
 
 /// Parse a field filter: `{"$op": value}` or just `value` (implicit $eq).
@@ -676,6 +706,28 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_error_nesting_too_deep() {
+        let deep = format!(
+            "{}{}{}",
+            r#"{"$not": "#.repeat(40),
+            r#"{"a": 1}"#,
+            "}".repeat(40)
+        );
+        assert!(matches!(
+            FilterExpr::parse(&deep),
+            Err(ParseError::NestingTooDeep { max: 32 })
+        ));
+
+        let ok = format!(
+            "{}{}{}",
+            r#"{"$not": "#.repeat(8),
+            r#"{"a": 1}"#,
+            "}".repeat(8)
+        );
+        assert!(FilterExpr::parse(&ok).is_ok());
+    }
+
     #[test]
     fn test_error_in_not_array() {
         let json: JsonValue = json::from_str(r#"{"status": {"$in": "not-array"}}"#).unwrap();
@@ -0,0 +1,554 @@
+//! Filter parsing from URL query strings.
+//!
+//! Two formats are supported, both producing a flat list of [`Filter`]s
+//! (combined with AND) that can go straight to `merge_filters` or the
+//! `merge:` option of `sql_read!`:
+//!
+//! | Format | Example |
+//! |--------|---------|
+//! | Bracket params | `filter[status]=eq:active&filter[age]=gte:18` |
+//! | RSQL/FIQL | `status==active;age=ge=18` |
+//!
+//! Values are typed the way a JSON filter would be: `true`/`false` become
+//! booleans, `null` is NULL, numbers become integers or floats, and
+//! everything else is a string. Quote a value (`'42'` or `"42"`) to keep it
+//! a string.
+//!
+//! # Example
+//!
+//! ```
+//! use mik_sql::prelude::*;
+//!
+//! let filters = parse_query_filters("filter[status]=in:active,pending&filter[age]=gte:18&page=2")
+//!     .unwrap();
+//! assert_eq!(filters.len(), 2);
+//!
+//! let validator = FilterValidator::new().allow_fields(&["status", "age"]);
+//! let trusted = vec![Filter::new("org_id", Operator::Eq, Value::Int(7))];
+//! let all = merge_filters(trusted, filters, &validator).unwrap();
+//! assert_eq!(all.len(), 3);
+//! ```
+
+use super::parse::ParseError;
+use super::types::{Filter, Operator, Value};
+
+/// Parse `filter[field]=op:value` parameters from a URL query string.
+///
+/// The leading `?` is optional, other parameters are ignored, and keys and
+/// values are percent-decoded. The operator prefix uses the JSON operator
+/// names without `$` (`eq`, `ne`, `gt`, `gte`, `lt`, `lte`, `in`, `nin`,
+/// `like`, `ilike`, `regex`, `starts_with`, `ends_with`, `contains`,
+/// `between`); without a known prefix the whole value is matched with `eq`.
+/// `in`, `nin` and `between` take comma-separated lists.
+///
+/// # Example
+///
+/// ```
+/// use mik_sql::{Filter, Operator, Value, parse_query_filters};
+///
+/// let filters = parse_query_filters("?filter[status]=eq:active&filter[age]=gte:18").unwrap();
+/// assert_eq!(filters, vec![
+///     Filter::new("status", Operator::Eq, Value::String("active".into())),
+///     Filter::new("age", Operator::Gte, Value::Int(18)),
+/// ]);
+/// ```
+///
+/// # Errors
+///
+/// Returns `ParseError` for an empty field name, a `between` without exactly
+/// two values, or malformed percent-encoding.
+pub fn parse_query_filters(query: &str) -> Result<Vec<Filter>, ParseError> {
+    let query = query.strip_prefix('?').unwrap_or(query);
+    let mut filters = Vec::new();
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, raw) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = percent_decode(key)?;
+        let Some(field) = key
+            .strip_prefix("filter[")
+            .and_then(|rest| rest.strip_suffix(']'))
+        else {
+            continue;
+        };
+        if field.is_empty() {
+            return Err(ParseError::EmptyFieldName);
+        }
+
+        let raw = percent_decode(raw)?;
+        let (op, value) = raw
+            .split_once(':')
+            .and_then(|(prefix, rest)| Operator::from_mongo(prefix).map(|op| (op, rest)))
+            .unwrap_or((Operator::Eq, raw.as_str()));
+
+        let value = match op {
+            Operator::In | Operator::NotIn | Operator::Between => {
+                list_value(op, &split_list(value))?
+            },
+            _ => parse_scalar(value),
+        };
+        filters.push(Filter::new(field, op, value));
+    }
+
+    Ok(filters)
+}
+
+/// Parse an RSQL/FIQL filter into filters combined with AND.
+///
+/// Comparisons are joined with `;` or ` and `. Supported operators are
+/// `==`, `!=`, `=gt=`/`>`, `=ge=`/`>=`, `=lt=`/`<`, `=le=`/`<=`,
+/// `=in=(..)`, `=out=(..)`, `=like=`, `=ilike=` and `=between=(a,b)`.
+/// A `==` value with a leading or trailing `*` becomes a `starts_with`,
+/// `ends_with` or `contains` match.
+///
+/// OR (`,`) and grouping are not supported, since the result is a flat
+/// filter list; use [`parse_filter`](super::parse_filter) with JSON for those.
+///
+/// # Example
+///
+/// ```
+/// use mik_sql::{Filter, Operator, Value, parse_rsql};
+///
+/// let filters = parse_rsql("status=in=(active,pending);name==Al*").unwrap();
+/// assert_eq!(filters[1], Filter::new("name", Operator::StartsWith, Value::String("Al".into())));
+/// ```
+///
+/// # Errors
+///
+/// Returns `ParseError` for unknown operators, OR expressions, unbalanced
+/// quotes or parentheses, or an empty field name.
+pub fn parse_rsql(input: &str) -> Result<Vec<Filter>, ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    split_top_level(input)?
+        .into_iter()
+        .map(|comparison| parse_comparison(comparison.trim()))
+        .collect()
+}
+
+/// Split an RSQL expression at top-level `;` and ` and `, rejecting `,`.
+fn split_top_level(input: &str) -> Result<Vec<&str>, ParseError> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {},
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| syntax("unbalanced parentheses"))?;
+            },
+            (None, ';') if depth == 0 => {
+                parts.push(input.get(start..i).unwrap_or_default());
+                start = i + 1;
+            },
+            (None, ',') if depth == 0 => {
+                return Err(syntax("OR (',') is not supported in query filters"));
+            },
+            _ => {},
+        }
+    }
+    if quote.is_some() {
+        return Err(syntax("unterminated quote"));
+    }
+    if depth != 0 {
+        return Err(syntax("unbalanced parentheses"));
+    }
+    parts.push(input.get(start..).unwrap_or_default());
+
+    // ` and ` is an alias for `;` outside of values
+    let mut split = Vec::new();
+    for part in parts {
+        let mut rest = part;
+        while let Some(pos) = find_keyword(rest, " and ") {
+            split.push(rest.get(..pos).unwrap_or_default());
+            rest = rest.get(pos + 5..).unwrap_or_default();
+        }
+        if find_keyword(rest, " or ").is_some() {
+            return Err(syntax("OR is not supported in query filters"));
+        }
+        split.push(rest);
+    }
+
+    if split.iter().any(|part| part.trim().is_empty()) {
+        return Err(syntax("empty comparison"));
+    }
+    Ok(split)
+}
+
+/// Position of `keyword` outside quotes and parentheses.
+fn find_keyword(input: &str, keyword: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {},
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            _ if depth == 0
+                && input
+                    .get(i..i + keyword.len())
+                    .is_some_and(|s| s.eq_ignore_ascii_case(keyword)) =>
+            {
+                return Some(i);
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// RSQL operators, longest first so `>=` wins over `>`.
+const RSQL_OPERATORS: &[(&str, Operator)] = &[
+    ("=between=", Operator::Between),
+    ("=ilike=", Operator::ILike),
+    ("=like=", Operator::Like),
+    ("=out=", Operator::NotIn),
+    ("=in=", Operator::In),
+    ("=gt=", Operator::Gt),
+    ("=ge=", Operator::Gte),
+    ("=lt=", Operator::Lt),
+    ("=le=", Operator::Lte),
+    ("==", Operator::Eq),
+    ("!=", Operator::Ne),
+    (">=", Operator::Gte),
+    ("<=", Operator::Lte),
+    (">", Operator::Gt),
+    ("<", Operator::Lt),
+];
+
+/// Parse one `field<op>value` comparison.
+fn parse_comparison(comparison: &str) -> Result<Filter, ParseError> {
+    let op_start = comparison
+        .find(['=', '!', '<', '>'])
+        .ok_or_else(|| syntax("expected a comparison operator"))?;
+    let field = comparison.get(..op_start).unwrap_or_default().trim();
+    if field.is_empty() {
+        return Err(ParseError::EmptyFieldName);
+    }
+
+    let rest = comparison.get(op_start..).unwrap_or_default();
+    let (token, op) = RSQL_OPERATORS
+        .iter()
+        .find(|(token, _)| rest.starts_with(token))
+        .copied()
+        .ok_or_else(|| {
+            // Report `=foo=` operators up to their closing `=`
+            let end = rest
+                .get(1..)
+                .and_then(|tail| tail.find('='))
+                .map_or(1, |end| end + 2);
+            ParseError::UnknownOperator(rest.get(..end).unwrap_or(rest).to_string())
+        })?;
+    let raw = rest.get(token.len()..).unwrap_or_default().trim();
+
+    let value = match op {
+        Operator::In | Operator::NotIn | Operator::Between => {
+            let inner = raw
+                .strip_prefix('(')
+                .and_then(|s| s.strip_suffix(')'))
+                .unwrap_or(raw);
+            list_value(op, &split_list(inner))?
+        },
+        Operator::Eq if !is_quoted(raw) && (raw.starts_with('*') || raw.ends_with('*')) => {
+            return Ok(wildcard_filter(field, raw));
+        },
+        _ => parse_scalar(raw),
+    };
+    Ok(Filter::new(field, op, value))
+}
+
+/// `==` with `*` wildcards: `Al*`, `*son` or `*li*`.
+fn wildcard_filter(field: &str, raw: &str) -> Filter {
+    let (op, text) = match (raw.strip_prefix('*'), raw.strip_suffix('*')) {
+        (Some(tail), Some(_)) => (Operator::Contains, tail.strip_suffix('*').unwrap_or(tail)),
+        (Some(tail), None) => (Operator::EndsWith, tail),
+        (None, Some(head)) => (Operator::StartsWith, head),
+        (None, None) => (Operator::Eq, raw),
+    };
+    Filter::new(field, op, Value::String(text.to_string()))
+}
+
+/// Build the array value for `in`, `nin` and `between`.
+fn list_value(op: Operator, items: &[&str]) -> Result<Value, ParseError> {
+    let values: Vec<Value> = items.iter().copied().map(parse_scalar).collect();
+    if op == Operator::Between && values.len() != 2 {
+        return Err(ParseError::InvalidOperatorValue {
+            op: "between".to_string(),
+            expected: "exactly 2 comma-separated values",
+        });
+    }
+    if values.is_empty() {
+        return Err(ParseError::InvalidOperatorValue {
+            op: "in/nin".to_string(),
+            expected: "comma-separated values",
+        });
+    }
+    Ok(Value::Array(values))
+}
+
+/// Split a comma-separated list, keeping commas inside quotes.
+fn split_list(input: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {},
+            (None, '\'' | '"') => quote = Some(c),
+            (None, ',') => {
+                items.push(input.get(start..i).unwrap_or_default().trim());
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    let last = input.get(start..).unwrap_or_default().trim();
+    if !(items.is_empty() && last.is_empty()) {
+        items.push(last);
+    }
+    items
+}
+
+/// Whether `raw` is wrapped in matching single or double quotes.
+fn is_quoted(raw: &str) -> bool {
+    raw.len() >= 2
+        && ((raw.starts_with('\'') && raw.ends_with('\''))
+            || (raw.starts_with('"') && raw.ends_with('"')))
+}
+
+/// Type a value the way JSON would: bool, null, integer, float or string.
+fn parse_scalar(raw: &str) -> Value {
+    if is_quoted(raw) {
+        return Value::String(raw.get(1..raw.len() - 1).unwrap_or_default().to_string());
+    }
+    match raw {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        // Leading zeros (zip codes, account numbers) stay strings
+        _ if has_leading_zero(raw) => Value::String(raw.to_string()),
+        _ => raw.parse::<i64>().map(Value::Int).unwrap_or_else(|_| {
+            raw.parse::<f64>()
+                .ok()
+                .filter(|f| f.is_finite())
+                .map_or_else(|| Value::String(raw.to_string()), Value::Float)
+        }),
+    }
+}
+
+/// Whether `raw` looks like a number with a leading zero, e.g. `007`.
+fn has_leading_zero(raw: &str) -> bool {
+    let digits = raw.strip_prefix('-').unwrap_or(raw);
+    digits.starts_with('0') && digits.bytes().nth(1).is_some_and(|b| b.is_ascii_digit())
+}
+
+/// Decode `%XX` escapes and `+` as space.
+fn percent_decode(input: &str) -> Result<String, ParseError> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut iter = input.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'%' => {
+                let hex = [iter.next(), iter.next()];
+                let decoded = match hex {
+                    [Some(hi), Some(lo)] => std::str::from_utf8(&[hi, lo])
+                        .ok()
+                        .and_then(|s| u8::from_str_radix(s, 16).ok()),
+                    _ => None,
+                };
+                bytes.push(decoded.ok_or_else(|| syntax("invalid percent-encoding"))?);
+            },
+            b'+' => bytes.push(b' '),
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| syntax("invalid percent-encoding"))
+}
+
+fn syntax(message: &str) -> ParseError {
+    ParseError::InvalidSyntax(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(v: &str) -> Value {
+        Value::String(v.to_string())
+    }
+
+    // =========================================================================
+    // Bracket query parameters
+    // =========================================================================
+
+    #[test]
+    fn test_query_filters_basic() {
+        let filters =
+            parse_query_filters("?filter[status]=eq:active&filter[age]=gte:18&page=2").unwrap();
+        assert_eq!(
+            filters,
+            vec![
+                Filter::new("status", Operator::Eq, s("active")),
+                Filter::new("age", Operator::Gte, Value::Int(18)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_filters_implicit_eq() {
+        let filters = parse_query_filters("filter[name]=Alice&filter[at]=10:30").unwrap();
+        assert_eq!(filters[0], Filter::new("name", Operator::Eq, s("Alice")));
+        assert_eq!(filters[1], Filter::new("at", Operator::Eq, s("10:30")));
+    }
+
+    #[test]
+    fn test_query_filters_lists() {
+        let filters =
+            parse_query_filters("filter[status]=in:active,pending&filter[age]=between:18,65")
+                .unwrap();
+        assert_eq!(
+            filters[0].value,
+            Value::Array(vec![s("active"), s("pending")])
+        );
+        assert_eq!(
+            filters[1].value,
+            Value::Array(vec![Value::Int(18), Value::Int(65)])
+        );
+
+        assert!(matches!(
+            parse_query_filters("filter[age]=between:18"),
+            Err(ParseError::InvalidOperatorValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_query_filters_percent_decoding() {
+        let filters =
+            parse_query_filters("filter%5Bname%5D=starts_with:John+Q&filter[email]=%40example.com")
+                .unwrap();
+        assert_eq!(
+            filters[0],
+            Filter::new("name", Operator::StartsWith, s("John Q"))
+        );
+        assert_eq!(filters[1].value, s("@example.com"));
+
+        assert!(matches!(
+            parse_query_filters("filter[name]=%ZZ"),
+            Err(ParseError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_query_filters_empty_field() {
+        assert_eq!(
+            parse_query_filters("filter[]=eq:1"),
+            Err(ParseError::EmptyFieldName)
+        );
+    }
+
+    #[test]
+    fn test_scalar_typing() {
+        assert_eq!(parse_scalar("42"), Value::Int(42));
+        assert_eq!(parse_scalar("-1.5"), Value::Float(-1.5));
+        assert_eq!(parse_scalar("0.5"), Value::Float(0.5));
+        assert_eq!(parse_scalar("true"), Value::Bool(true));
+        assert_eq!(parse_scalar("null"), Value::Null);
+        assert_eq!(parse_scalar("'42'"), s("42"));
+        assert_eq!(parse_scalar("007"), s("007"));
+        assert_eq!(parse_scalar("inf"), s("inf"));
+    }
+
+    // =========================================================================
+    // RSQL / FIQL
+    // =========================================================================
+
+    #[test]
+    fn test_rsql_comparisons() {
+        let filters = parse_rsql("status==active;age=ge=18;score>2.5;role!='admin'").unwrap();
+        assert_eq!(
+            filters,
+            vec![
+                Filter::new("status", Operator::Eq, s("active")),
+                Filter::new("age", Operator::Gte, Value::Int(18)),
+                Filter::new("score", Operator::Gt, Value::Float(2.5)),
+                Filter::new("role", Operator::Ne, s("admin")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rsql_lists_and_keywords() {
+        let filters =
+            parse_rsql("status=out=(deleted, 'on,hold') and age=between=(18,65)").unwrap();
+        assert_eq!(
+            filters[0],
+            Filter::new(
+                "status",
+                Operator::NotIn,
+                Value::Array(vec![s("deleted"), s("on,hold")])
+            )
+        );
+        assert_eq!(filters[1].op, Operator::Between);
+    }
+
+    #[test]
+    fn test_rsql_wildcards() {
+        let filters = parse_rsql("name==Al*;email==*@example.com;bio==*rust*;code=='A*'").unwrap();
+        assert_eq!(filters[0].op, Operator::StartsWith);
+        assert_eq!(
+            filters[1],
+            Filter::new("email", Operator::EndsWith, s("@example.com"))
+        );
+        assert_eq!(
+            filters[2],
+            Filter::new("bio", Operator::Contains, s("rust"))
+        );
+        assert_eq!(filters[3], Filter::new("code", Operator::Eq, s("A*")));
+    }
+
+    #[test]
+    fn test_rsql_errors() {
+        assert!(matches!(
+            parse_rsql("a==1,b==2"),
+            Err(ParseError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            parse_rsql("a==1 or b==2"),
+            Err(ParseError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            parse_rsql("a=foo=1"),
+            Err(ParseError::UnknownOperator(_))
+        ));
+        assert!(matches!(
+            parse_rsql("name=='open"),
+            Err(ParseError::InvalidSyntax(_))
+        ));
+        assert_eq!(parse_rsql("==1"), Err(ParseError::EmptyFieldName));
+        assert_eq!(parse_rsql(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_merge_with_validator() {
+        use crate::{FilterValidator, merge_filters};
+
+        let validator = FilterValidator::new().allow_fields(&["status"]);
+        let user = parse_rsql("status==active").unwrap();
+        assert!(merge_filters(Vec::new(), user, &validator).is_ok());
+
+        let user = parse_query_filters("filter[password]=eq:x").unwrap();
+        assert!(merge_filters(Vec::new(), user, &validator).is_err());
+    }
+}
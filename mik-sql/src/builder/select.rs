@@ -1,7 +1,7 @@
 //! SELECT query builder.
 
 use crate::dialect::Dialect;
-use crate::pagination::{Cursor, IntoCursor};
+use crate::pagination::{Cursor, IntoCursor, Pagination};
 use crate::validate::{assert_valid_sql_expression, assert_valid_sql_identifier};
 
 use super::filter::{build_condition_impl, build_filter_expr_impl};
@@ -153,6 +153,18 @@ impl<D: Dialect> QueryBuilder<D> {
         self
     }
 
+    /// AND an expression with the current filter expression.
+    ///
+    /// Unlike [`filter_expr`](Self::filter_expr), this keeps an expression
+    /// that is already set, e.g. to add validated user filters to trusted ones.
+    pub fn and_filter_expr(mut self, expr: FilterExpr) -> Self {
+        self.filter_expr = Some(match self.filter_expr.take() {
+            Some(existing) => FilterExpr::Compound(CompoundFilter::and(vec![existing, expr])),
+            None => expr,
+        });
+        self
+    }
+
     /// Add an AND compound filter.
     pub fn and(mut self, filters: Vec<FilterExpr>) -> Self {
         self.filter_expr = Some(FilterExpr::Compound(CompoundFilter::and(filters)));
@@ -222,6 +234,21 @@ impl<D: Dialect> QueryBuilder<D> {
         self
     }
 
+    /// Apply validated pagination: the capped limit plus the offset or cursor.
+    ///
+    /// Cursor pagination needs the sort fields, so add them first.
+    pub fn paginate(mut self, pagination: &Pagination) -> Self {
+        self.limit = Some(pagination.effective_limit());
+        if let Some(after) = &pagination.after {
+            self = self.after_cursor(after.as_str());
+        } else if let Some(before) = &pagination.before {
+            self = self.before_cursor(before.as_str());
+        } else if pagination.offset > 0 {
+            self.offset = Some(pagination.offset);
+        }
+        self
+    }
+
     /// Paginate after this cursor (forward pagination).
     ///
     /// This method accepts flexible input types for great DX:
@@ -342,6 +342,58 @@ pub enum Value {
     Array(Vec<Self>),
 }
 
+impl Value {
+    /// LIKE pattern matching values that contain `text`.
+    ///
+    /// `%`, `_` and `\` in `text` are escaped, so it matches literally.
+    ///
+    /// ```
+    /// use mik_sql::Value;
+    ///
+    /// assert_eq!(Value::like_contains("50%_off"), Value::String(r"%50\%\_off%".into()));
+    /// ```
+    #[must_use]
+    pub fn like_contains(text: &str) -> Self {
+        Self::String(format!("%{}%", escape_like(text)))
+    }
+
+    /// LIKE pattern matching values that start with `text`, matched literally.
+    #[must_use]
+    pub fn like_starts_with(text: &str) -> Self {
+        Self::String(format!("{}%", escape_like(text)))
+    }
+
+    /// LIKE pattern matching values that end with `text`, matched literally.
+    #[must_use]
+    pub fn like_ends_with(text: &str) -> Self {
+        Self::String(format!("%{}", escape_like(text)))
+    }
+}
+
+/// Escape LIKE wildcards (`%`, `_`) and the escape character `\` in `text`.
+///
+/// The builder renders every LIKE and ILIKE condition with `ESCAPE '\'`, so
+/// an escaped string matches literally on both Postgres and SQLite. Use it
+/// to build custom patterns around user input:
+///
+/// ```
+/// use mik_sql::escape_like;
+///
+/// assert_eq!(escape_like("100%"), r"100\%");
+/// assert_eq!(format!("{}-%", escape_like("a_b")), r"a\_b-%");
+/// ```
+#[must_use]
+pub fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 /// Sort direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
@@ -32,6 +32,9 @@ pub trait Dialect: Clone + Copy {
     fn supports_ilike(&self) -> bool;
 
     /// Format a STARTS WITH clause (e.g., `LIKE $1 || '%'` or `LIKE ?1 || '%'`).
+    ///
+    /// LIKE clauses end with `ESCAPE '\'` so escaped wildcards in the
+    /// parameter (see [`escape_like`](crate::escape_like)) match literally.
     fn starts_with_clause(&self, field: &str, idx: usize) -> String;
 
     /// Format an ENDS WITH clause (e.g., `LIKE '%' || $1` or `LIKE '%' || ?1`).
@@ -39,6 +42,12 @@ pub trait Dialect: Clone + Copy {
 
     /// Format a CONTAINS clause (e.g., `LIKE '%' || $1 || '%'` or `LIKE '%' || ?1 || '%'`).
     fn contains_clause(&self, field: &str, idx: usize) -> String;
+
+    /// Wrap a query to show its plan; `analyze` also runs it where supported.
+    fn explain(&self, sql: &str, analyze: bool) -> String {
+        let _ = analyze;
+        format!("EXPLAIN {sql}")
+    }
 }
 
 /// Postgres dialect.
@@ -85,17 +94,25 @@ impl Dialect for Postgres {
 
     #[inline]
     fn starts_with_clause(&self, field: &str, idx: usize) -> String {
-        format!("{field} LIKE ${idx} || '%'")
+        format!("{field} LIKE ${idx} || '%' ESCAPE '\\'")
     }
 
     #[inline]
     fn ends_with_clause(&self, field: &str, idx: usize) -> String {
-        format!("{field} LIKE '%' || ${idx}")
+        format!("{field} LIKE '%' || ${idx} ESCAPE '\\'")
     }
 
     #[inline]
     fn contains_clause(&self, field: &str, idx: usize) -> String {
-        format!("{field} LIKE '%' || ${idx} || '%'")
+        format!("{field} LIKE '%' || ${idx} || '%' ESCAPE '\\'")
+    }
+
+    fn explain(&self, sql: &str, analyze: bool) -> String {
+        if analyze {
+            format!("EXPLAIN (ANALYZE, BUFFERS) {sql}")
+        } else {
+            format!("EXPLAIN {sql}")
+        }
     }
 }
 
@@ -151,17 +168,22 @@ impl Dialect for Sqlite {
 
     #[inline]
     fn starts_with_clause(&self, field: &str, idx: usize) -> String {
-        format!("{field} LIKE ?{idx} || '%'")
+        format!("{field} LIKE ?{idx} || '%' ESCAPE '\\'")
     }
 
     #[inline]
     fn ends_with_clause(&self, field: &str, idx: usize) -> String {
-        format!("{field} LIKE '%' || ?{idx}")
+        format!("{field} LIKE '%' || ?{idx} ESCAPE '\\'")
     }
 
     #[inline]
     fn contains_clause(&self, field: &str, idx: usize) -> String {
-        format!("{field} LIKE '%' || ?{idx} || '%'")
+        format!("{field} LIKE '%' || ?{idx} || '%' ESCAPE '\\'")
+    }
+
+    fn explain(&self, sql: &str, _analyze: bool) -> String {
+        // SQLite has no EXPLAIN ANALYZE; the query plan is the closest match
+        format!("EXPLAIN QUERY PLAN {sql}")
     }
 }
 
@@ -286,17 +308,26 @@ mod tests {
     #[test]
     fn test_postgres_starts_with_clause() {
         let pg = Postgres;
-        assert_eq!(pg.starts_with_clause("name", 1), "name LIKE $1 || '%'");
-        assert_eq!(pg.starts_with_clause("title", 5), "title LIKE $5 || '%'");
+        assert_eq!(
+            pg.starts_with_clause("name", 1),
+            "name LIKE $1 || '%' ESCAPE '\\'"
+        );
+        assert_eq!(
+            pg.starts_with_clause("title", 5),
+            "title LIKE $5 || '%' ESCAPE '\\'"
+        );
     }
 
     #[test]
     fn test_sqlite_starts_with_clause() {
         let sqlite = Sqlite;
-        assert_eq!(sqlite.starts_with_clause("name", 1), "name LIKE ?1 || '%'");
+        assert_eq!(
+            sqlite.starts_with_clause("name", 1),
+            "name LIKE ?1 || '%' ESCAPE '\\'"
+        );
         assert_eq!(
             sqlite.starts_with_clause("title", 5),
-            "title LIKE ?5 || '%'"
+            "title LIKE ?5 || '%' ESCAPE '\\'"
         );
     }
 
@@ -305,15 +336,27 @@ mod tests {
     #[test]
     fn test_postgres_ends_with_clause() {
         let pg = Postgres;
-        assert_eq!(pg.ends_with_clause("name", 1), "name LIKE '%' || $1");
-        assert_eq!(pg.ends_with_clause("email", 3), "email LIKE '%' || $3");
+        assert_eq!(
+            pg.ends_with_clause("name", 1),
+            "name LIKE '%' || $1 ESCAPE '\\'"
+        );
+        assert_eq!(
+            pg.ends_with_clause("email", 3),
+            "email LIKE '%' || $3 ESCAPE '\\'"
+        );
     }
 
     #[test]
     fn test_sqlite_ends_with_clause() {
         let sqlite = Sqlite;
-        assert_eq!(sqlite.ends_with_clause("name", 1), "name LIKE '%' || ?1");
-        assert_eq!(sqlite.ends_with_clause("email", 3), "email LIKE '%' || ?3");
+        assert_eq!(
+            sqlite.ends_with_clause("name", 1),
+            "name LIKE '%' || ?1 ESCAPE '\\'"
+        );
+        assert_eq!(
+            sqlite.ends_with_clause("email", 3),
+            "email LIKE '%' || ?3 ESCAPE '\\'"
+        );
     }
 
     // --- Tests for contains_clause ---
@@ -321,10 +364,13 @@ mod tests {
     #[test]
     fn test_postgres_contains_clause() {
         let pg = Postgres;
-        assert_eq!(pg.contains_clause("name", 1), "name LIKE '%' || $1 || '%'");
+        assert_eq!(
+            pg.contains_clause("name", 1),
+            "name LIKE '%' || $1 || '%' ESCAPE '\\'"
+        );
         assert_eq!(
             pg.contains_clause("description", 2),
-            "description LIKE '%' || $2 || '%'"
+            "description LIKE '%' || $2 || '%' ESCAPE '\\'"
         );
     }
 
@@ -333,11 +379,11 @@ mod tests {
         let sqlite = Sqlite;
         assert_eq!(
             sqlite.contains_clause("name", 1),
-            "name LIKE '%' || ?1 || '%'"
+            "name LIKE '%' || ?1 || '%' ESCAPE '\\'"
         );
         assert_eq!(
             sqlite.contains_clause("description", 2),
-            "description LIKE '%' || ?2 || '%'"
+            "description LIKE '%' || ?2 || '%' ESCAPE '\\'"
         );
     }
 
@@ -0,0 +1,91 @@
+//! Query debugging: `EXPLAIN` wrappers and parameter shapes.
+//!
+//! ```
+//! use mik_sql::{Postgres, Sqlite, Value, describe_params, explain, explain_analyze};
+//!
+//! let sql = "SELECT id FROM users WHERE email = $1";
+//! let params = vec![Value::String("alice@example.com".into())];
+//!
+//! assert_eq!(explain(Postgres, sql), "EXPLAIN SELECT id FROM users WHERE email = $1");
+//! assert!(explain_analyze(Postgres, sql).starts_with("EXPLAIN (ANALYZE, BUFFERS)"));
+//! assert!(explain(Sqlite, "SELECT 1").starts_with("EXPLAIN QUERY PLAN"));
+//!
+//! // Shapes, never values - safe to log
+//! assert_eq!(describe_params(&params), "[string]");
+//! ```
+//!
+//! The wrapped query takes the same parameters as the original.
+
+use crate::{Dialect, Value};
+
+/// Wrap `sql` in the dialect's `EXPLAIN` to show the query plan.
+#[must_use]
+pub fn explain<D: Dialect>(dialect: D, sql: &str) -> String {
+    dialect.explain(sql, false)
+}
+
+/// Wrap `sql` in `EXPLAIN ANALYZE` (Postgres) to show the plan with real timings.
+///
+/// **This executes the query**, including writes: run it in a transaction
+/// you roll back when explaining `INSERT`, `UPDATE` or `DELETE`. `SQLite`
+/// has no analyze mode and gets `EXPLAIN QUERY PLAN`.
+#[must_use]
+pub fn explain_analyze<D: Dialect>(dialect: D, sql: &str) -> String {
+    dialect.explain(sql, true)
+}
+
+/// Parameter types without their values, e.g. `[int, string, array(3)]`.
+///
+/// Safe to log: user data stays out of the output.
+#[must_use]
+pub fn describe_params(params: &[Value]) -> String {
+    let shapes: Vec<String> = params.iter().map(shape).collect();
+    format!("[{}]", shapes.join(", "))
+}
+
+fn shape(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) => "bool".to_string(),
+        Value::Int(_) => "int".to_string(),
+        Value::Float(_) => "float".to_string(),
+        Value::String(_) => "string".to_string(),
+        Value::Array(items) => format!("array({})", items.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Postgres, Sqlite};
+
+    #[test]
+    fn test_explain_dialects() {
+        assert_eq!(explain(Postgres, "SELECT 1"), "EXPLAIN SELECT 1");
+        assert_eq!(
+            explain_analyze(Postgres, "SELECT 1"),
+            "EXPLAIN (ANALYZE, BUFFERS) SELECT 1"
+        );
+        assert_eq!(explain(Sqlite, "SELECT 1"), "EXPLAIN QUERY PLAN SELECT 1");
+        assert_eq!(
+            explain_analyze(Sqlite, "SELECT 1"),
+            "EXPLAIN QUERY PLAN SELECT 1"
+        );
+    }
+
+    #[test]
+    fn test_describe_params_hides_values() {
+        let params = vec![
+            Value::Int(42),
+            Value::String("secret@example.com".into()),
+            Value::Array(vec![Value::Int(1), Value::Int(2)]),
+            Value::Null,
+            Value::Bool(true),
+            Value::Float(1.5),
+        ];
+        let described = describe_params(&params);
+        assert_eq!(described, "[int, string, array(2), null, bool, float]");
+        assert!(!described.contains("secret"));
+        assert_eq!(describe_params(&[]), "[]");
+    }
+}
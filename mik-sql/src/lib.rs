@@ -84,6 +84,33 @@
 //! | `$contains` | `LIKE '%' \|\| $1 \|\| '%'` | `"bio": { "$contains": "developer" }` |
 //! | `$between` | `BETWEEN $1 AND $2` | `"age": { "$between": [18, 65] }` |
 //!
+//! LIKE conditions end with `ESCAPE '\'`. `$starts_with`, `$ends_with` and
+//! `$contains` escape `%` and `_` in their value; for `$like` and `$ilike`,
+//! build patterns with [`Value::like_contains`] and friends or [`escape_like`].
+//!
+//! ## Query-String Filters
+//!
+//! [`parse_query_filters`] reads `?filter[age]=gte:18` parameters and
+//! [`parse_rsql`] reads RSQL (`age=ge=18;status==active`); both return
+//! `Vec<Filter>` for [`merge_filters`] or the `merge:` option.
+//!
+//! ## Sorting and Pagination
+//!
+//! [`SortValidator`] whitelists sortable fields and caps the number of sort
+//! keys; [`Pagination`] caps the page size and holds an offset or cursor.
+//! Apply them with [`QueryBuilder::sorts`] and [`QueryBuilder::paginate`].
+//!
+//! ## Debugging Queries
+//!
+//! [`explain`] and [`explain_analyze`] wrap a generated query in the
+//! dialect's `EXPLAIN`; [`describe_params`] lists parameter types without
+//! their values, for logs.
+//!
+//! ## Typed Rows
+//!
+//! `#[derive(Row)]` maps result columns to struct fields by name; see
+//! [`rows_from_json`] and [`FromRow`].
+//!
 //! ## Cursor Pagination
 //!
 //! ```
@@ -107,14 +134,16 @@
 
 mod builder;
 mod dialect;
+mod explain;
 mod pagination;
+mod row;
 mod validate;
 
 pub use builder::{
     Aggregate, AggregateFunc, CompoundFilter, ComputedField, CursorDirection, DeleteBuilder,
     Filter, FilterExpr, InsertBuilder, LogicalOp, Operator, ParseError, QueryBuilder, QueryResult,
-    SortDir, SortField, UpdateBuilder, Value, and, delete, insert, not, or, parse_filter, simple,
-    update,
+    SortDir, SortField, UpdateBuilder, Value, and, delete, escape_like, insert, not, or,
+    parse_filter, parse_query_filters, parse_rsql, simple, update,
 };
 
 /// Re-export miniserde's json module for runtime filter parsing.
@@ -136,14 +165,18 @@ pub use miniserde::json;
 #[doc(hidden)]
 pub use builder::{delete_sqlite, insert_sqlite, update_sqlite};
 pub use dialect::{Dialect, Postgres, Sqlite};
-pub use pagination::{Cursor, CursorError, IntoCursor, KeysetCondition, PageInfo};
+pub use explain::{describe_params, explain, explain_analyze};
+pub use pagination::{Cursor, CursorError, IntoCursor, KeysetCondition, PageInfo, Pagination};
+pub use row::{FromRow, FromValue, Row, RowError, map_rows, rows_from_json};
+#[doc(hidden)]
+pub use validate::MergeFilters;
 pub use validate::{
-    FilterValidator, ValidationError, assert_valid_sql_expression, assert_valid_sql_identifier,
-    is_valid_sql_expression, is_valid_sql_identifier, merge_filters,
+    FilterValidator, SortValidator, ValidationError, assert_valid_sql_expression,
+    assert_valid_sql_identifier, is_valid_sql_expression, is_valid_sql_identifier, merge_filters,
 };
 
 // Re-export SQL macros from mik-sql-macros
-pub use mik_sql_macros::{sql_create, sql_delete, sql_read, sql_update};
+pub use mik_sql_macros::{Row, sql_create, sql_delete, sql_read, sql_update};
 
 // Re-export ids! from mik-sdk-macros (consolidated location)
 pub use mik_sdk_macros::ids;
@@ -175,11 +208,12 @@ pub fn sqlite(table: &str) -> QueryBuilder<Sqlite> {
 pub mod prelude {
     pub use crate::{
         Aggregate, AggregateFunc, CompoundFilter, ComputedField, Cursor, CursorDirection,
-        CursorError, DeleteBuilder, Dialect, Filter, FilterExpr, FilterValidator, InsertBuilder,
-        IntoCursor, KeysetCondition, LogicalOp, Operator, PageInfo, ParseError, Postgres,
-        QueryBuilder, QueryResult, SortDir, SortField, Sqlite, UpdateBuilder, ValidationError,
-        Value, and, delete, insert, json, merge_filters, not, or, parse_filter, postgres, simple,
-        sqlite, update,
+        CursorError, DeleteBuilder, Dialect, Filter, FilterExpr, FilterValidator, FromRow,
+        InsertBuilder, IntoCursor, KeysetCondition, LogicalOp, Operator, PageInfo, Pagination,
+        ParseError, Postgres, QueryBuilder, QueryResult, Row, RowError, SortDir, SortField,
+        SortValidator, Sqlite, UpdateBuilder, ValidationError, Value, and, delete, escape_like,
+        insert, json, map_rows, merge_filters, not, or, parse_filter, parse_query_filters,
+        parse_rsql, postgres, rows_from_json, simple, sqlite, update,
     };
 
     // Re-export macros
@@ -50,12 +50,14 @@ mod cursor;
 mod encoding;
 mod keyset;
 mod page_info;
+mod params;
 mod value_conv;
 
 // Re-export all public items
 pub use cursor::{Cursor, CursorError, IntoCursor};
 pub use keyset::KeysetCondition;
 pub use page_info::PageInfo;
+pub use params::Pagination;
 
 #[cfg(test)]
 mod tests {
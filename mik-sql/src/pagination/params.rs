@@ -0,0 +1,257 @@
+//! Pagination request parameters with a capped limit.
+
+use crate::ValidationError;
+
+/// Page size when none is requested.
+const DEFAULT_LIMIT: u32 = 20;
+
+/// Default cap on the page size.
+const DEFAULT_MAX_LIMIT: u32 = 100;
+
+/// Validated pagination parameters for a list query.
+///
+/// Holds a page size, capped at [`max_limit`](Self::max_limit), and either
+/// an offset or an `after` / `before` cursor. Apply it with
+/// [`QueryBuilder::paginate`](crate::QueryBuilder::paginate), which renders
+/// the `LIMIT` / `OFFSET` clauses or the cursor condition.
+///
+/// # Example
+///
+/// ```
+/// use mik_sql::{Pagination, SortDir, postgres};
+///
+/// let page = Pagination::new().max_limit(50).limit(500).page(3);
+/// assert_eq!(page.effective_limit(), 50);
+///
+/// let result = postgres("users")
+///     .fields(&["id"])
+///     .sort("id", SortDir::Asc)
+///     .paginate(&page)
+///     .build();
+/// assert!(result.sql.ends_with("LIMIT 50 OFFSET 100"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Pagination {
+    /// Requested page size.
+    pub limit: u32,
+    /// Number of rows to skip (offset pagination).
+    pub offset: u32,
+    /// Return rows after this encoded cursor.
+    pub after: Option<String>,
+    /// Return rows before this encoded cursor.
+    pub before: Option<String>,
+    /// Largest page size that will be rendered.
+    pub max_limit: u32,
+}
+
+impl Pagination {
+    /// First page with the default limit (20, capped at 100).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            limit: DEFAULT_LIMIT,
+            offset: 0,
+            after: None,
+            before: None,
+            max_limit: DEFAULT_MAX_LIMIT,
+        }
+    }
+
+    /// Set the page size.
+    #[must_use]
+    pub const fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the offset.
+    #[must_use]
+    pub const fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the offset from a 1-indexed page number and the current limit.
+    #[must_use]
+    pub const fn page(mut self, page: u32) -> Self {
+        self.offset = page
+            .saturating_sub(1)
+            .saturating_mul(self.effective_limit());
+        self
+    }
+
+    /// Paginate forward from an encoded cursor.
+    #[must_use]
+    pub fn after(mut self, cursor: impl Into<String>) -> Self {
+        self.after = Some(cursor.into());
+        self
+    }
+
+    /// Paginate backward from an encoded cursor.
+    #[must_use]
+    pub fn before(mut self, cursor: impl Into<String>) -> Self {
+        self.before = Some(cursor.into());
+        self
+    }
+
+    /// Cap the page size. Default is 100.
+    ///
+    /// Set it before [`page`](Self::page), which uses the capped limit.
+    #[must_use]
+    pub const fn max_limit(mut self, max: u32) -> Self {
+        self.max_limit = max;
+        self
+    }
+
+    /// The page size that is rendered: `limit` clamped to `1..=max_limit`.
+    #[must_use]
+    pub const fn effective_limit(&self) -> u32 {
+        let max = if self.max_limit == 0 {
+            1
+        } else {
+            self.max_limit
+        };
+        if self.limit == 0 {
+            1
+        } else if self.limit > max {
+            max
+        } else {
+            self.limit
+        }
+    }
+
+    /// Whether the request uses cursor pagination.
+    #[must_use]
+    pub const fn is_cursor(&self) -> bool {
+        self.after.is_some() || self.before.is_some()
+    }
+
+    /// Read `limit`, `offset`, `page`, `after` and `before` from a query string.
+    ///
+    /// The leading `?` is optional and other parameters are ignored. An
+    /// oversized `limit` is capped rather than rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for non-numeric values, or when `offset`,
+    /// `page`, `after` and `before` are combined.
+    pub fn from_query(query: &str) -> Result<Self, ValidationError> {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        let mut pagination = Self::new();
+        let mut page = None;
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "limit" => pagination.limit = number(key, value)?,
+                "offset" => pagination.offset = number(key, value)?,
+                "page" => page = Some(number(key, value)?),
+                "after" if !value.is_empty() => pagination.after = Some(value.to_string()),
+                "before" if !value.is_empty() => pagination.before = Some(value.to_string()),
+                _ => {},
+            }
+        }
+
+        if let Some(page) = page {
+            if pagination.offset > 0 {
+                return Err(invalid("`page` cannot be combined with `offset`"));
+            }
+            pagination = pagination.page(page);
+        }
+        pagination.validate()?;
+        Ok(pagination)
+    }
+
+    /// Check that offset and cursors are not combined.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError::InvalidPagination` describing the conflict.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.after.is_some() && self.before.is_some() {
+            return Err(invalid("`after` and `before` cannot be combined"));
+        }
+        if self.offset > 0 && self.is_cursor() {
+            return Err(invalid("`offset` cannot be combined with a cursor"));
+        }
+        Ok(())
+    }
+
+    /// Render `LIMIT n` plus `OFFSET n` when set, for hand-written SQL.
+    ///
+    /// Cursors need the sort columns, so they are only applied through
+    /// [`QueryBuilder::paginate`](crate::QueryBuilder::paginate).
+    #[must_use]
+    pub fn to_sql(&self) -> String {
+        if self.offset > 0 {
+            format!("LIMIT {} OFFSET {}", self.effective_limit(), self.offset)
+        } else {
+            format!("LIMIT {}", self.effective_limit())
+        }
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn number(name: &str, value: &str) -> Result<u32, ValidationError> {
+    value
+        .parse()
+        .map_err(|_| invalid(&format!("`{name}` must be a non-negative integer")))
+}
+
+fn invalid(message: &str) -> ValidationError {
+    ValidationError::InvalidPagination(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let page = Pagination::from_query("").unwrap();
+        assert_eq!(page, Pagination::new());
+        assert_eq!(page.to_sql(), "LIMIT 20");
+    }
+
+    #[test]
+    fn test_limit_cap() {
+        assert_eq!(Pagination::new().limit(1000).effective_limit(), 100);
+        assert_eq!(Pagination::new().limit(0).effective_limit(), 1);
+        assert_eq!(
+            Pagination::new().limit(80).max_limit(50).effective_limit(),
+            50
+        );
+    }
+
+    #[test]
+    fn test_page_uses_capped_limit() {
+        let page = Pagination::from_query("?limit=500&page=3").unwrap();
+        assert_eq!(page.offset, 200);
+        assert_eq!(page.to_sql(), "LIMIT 100 OFFSET 200");
+    }
+
+    #[test]
+    fn test_cursor() {
+        let page = Pagination::from_query("limit=10&after=eyJpZCI6NDJ9").unwrap();
+        assert!(page.is_cursor());
+        assert_eq!(page.after.as_deref(), Some("eyJpZCI6NDJ9"));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            Pagination::from_query("limit=abc"),
+            Err(ValidationError::InvalidPagination(_))
+        ));
+        assert!(Pagination::from_query("offset=-1").is_err());
+        assert!(Pagination::from_query("offset=10&after=abc").is_err());
+        assert!(Pagination::from_query("after=a&before=b").is_err());
+        assert!(Pagination::from_query("page=2&offset=10").is_err());
+    }
+}
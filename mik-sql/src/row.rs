@@ -0,0 +1,367 @@
+//! Typed row mapping for query results.
+//!
+//! Results come back from the database sidecar as JSON. [`rows_from_json`]
+//! turns them into [`Row`]s, and [`FromRow`] (usually derived with
+//! `#[derive(Row)]`) maps each row to a struct by column name:
+//!
+//! ```
+//! use mik_sql::{Row, rows_from_json};
+//!
+//! #[derive(Row)]
+//! struct User {
+//!     id: i64,
+//!     #[row(rename = "full_name")]
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! let json = mik_sql::json::from_str(r#"[{"id": 1, "full_name": "Alice", "email": null}]"#).unwrap();
+//! let users: Vec<User> = mik_sql::map_rows(&rows_from_json(&json).unwrap()).unwrap();
+//! assert_eq!(users[0].name, "Alice");
+//! assert_eq!(users[0].email, None);
+//! ```
+
+use crate::Value;
+use miniserde::json::{self, Number};
+use std::fmt;
+
+/// Error mapping a row to a type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RowError {
+    /// A required column is not in the row.
+    MissingColumn(String),
+    /// A column holds a value of the wrong type, or NULL for a non-`Option` field.
+    InvalidType {
+        /// Column name.
+        column: String,
+        /// Expected type, e.g. `integer`.
+        expected: &'static str,
+    },
+    /// The JSON result set is not an array of objects.
+    InvalidResultSet,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingColumn(column) => write!(f, "missing column '{column}'"),
+            Self::InvalidType { column, expected } => {
+                write!(f, "column '{column}' is not a valid {expected}")
+            },
+            Self::InvalidResultSet => write!(f, "result set must be an array of row objects"),
+        }
+    }
+}
+
+impl std::error::Error for RowError {}
+
+/// One result row: column names and their values.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Row {
+    columns: Vec<(String, Value)>,
+}
+
+impl Row {
+    /// An empty row.
+    pub const fn new() -> Self {
+        Self {
+            columns: Vec::new(),
+        }
+    }
+
+    /// Add a column.
+    pub fn with(mut self, column: impl Into<String>, value: Value) -> Self {
+        self.columns.push((column.into(), value));
+        self
+    }
+
+    /// Raw value of `column`, if present.
+    pub fn value(&self, column: &str) -> Option<&Value> {
+        self.columns
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value)
+    }
+
+    /// Typed value of `column`. `Option` types accept missing and NULL columns.
+    pub fn get<T: FromValue>(&self, column: &str) -> Result<T, RowError> {
+        match self.value(column) {
+            Some(value) => T::from_value(value).ok_or_else(|| RowError::InvalidType {
+                column: column.to_string(),
+                expected: T::EXPECTED,
+            }),
+            None => T::from_missing().ok_or_else(|| RowError::MissingColumn(column.to_string())),
+        }
+    }
+
+    /// Column names.
+    pub fn columns(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Number of columns.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Whether the row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+}
+
+/// Types that can be built from a [`Row`]. Derive with `#[derive(Row)]`.
+pub trait FromRow: Sized {
+    /// Map a row to `Self`.
+    fn from_row(row: &Row) -> Result<Self, RowError>;
+}
+
+/// Map every row to `T`, stopping at the first error.
+pub fn map_rows<T: FromRow>(rows: &[Row]) -> Result<Vec<T>, RowError> {
+    rows.iter().map(T::from_row).collect()
+}
+
+/// Column value types.
+pub trait FromValue: Sized {
+    /// Type name used in errors.
+    const EXPECTED: &'static str;
+
+    /// Convert a column value; `None` if it has the wrong type.
+    fn from_value(value: &Value) -> Option<Self>;
+
+    /// Value for a missing column; `None` makes the column required.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+impl FromValue for Value {
+    const EXPECTED: &'static str = "value";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+impl FromValue for String {
+    const EXPECTED: &'static str = "string";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for i64 {
+    const EXPECTED: &'static str = "integer";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Int(n) => Some(*n),
+            // Integral floats, e.g. from COUNT(*) through some drivers
+            Value::Float(f) if f.fract() == 0.0 && f.abs() < 9.0e15 => Some(*f as Self),
+            // Postgres BIGINT and NUMERIC are often sent as strings
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! from_value_int {
+    ($($ty:ty),*) => {$(
+        impl FromValue for $ty {
+            const EXPECTED: &'static str = "integer";
+
+            fn from_value(value: &Value) -> Option<Self> {
+                i64::from_value(value).and_then(|n| Self::try_from(n).ok())
+            }
+        }
+    )*};
+}
+
+from_value_int!(i8, i16, i32, u8, u16, u32, u64, usize);
+
+impl FromValue for f64 {
+    const EXPECTED: &'static str = "number";
+
+    #[allow(clippy::cast_precision_loss)] // Large integers round, like in SQL
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Float(f) => Some(*f),
+            Value::Int(n) => Some(*n as Self),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for f32 {
+    const EXPECTED: &'static str = "number";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        f64::from_value(value).map(|f| f as Self)
+    }
+}
+
+impl FromValue for bool {
+    const EXPECTED: &'static str = "boolean";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(b) => Some(*b),
+            // SQLite stores booleans as 0/1
+            Value::Int(0) => Some(false),
+            Value::Int(1) => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    const EXPECTED: &'static str = T::EXPECTED;
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => Some(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    const EXPECTED: &'static str = "array";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Array(items) => items.iter().map(T::from_value).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a JSON result set: an array of row objects, or an object with a
+/// `rows` array of them (the shape most database proxies return).
+pub fn rows_from_json(value: &json::Value) -> Result<Vec<Row>, RowError> {
+    let rows = match value {
+        json::Value::Array(rows) => rows,
+        json::Value::Object(object) => match object.get("rows") {
+            Some(json::Value::Array(rows)) => rows,
+            _ => return Err(RowError::InvalidResultSet),
+        },
+        _ => return Err(RowError::InvalidResultSet),
+    };
+
+    rows.iter()
+        .map(|row| match row {
+            json::Value::Object(object) => Ok(Row {
+                columns: object
+                    .iter()
+                    .map(|(name, value)| (name.clone(), json_to_value(value)))
+                    .collect(),
+            }),
+            _ => Err(RowError::InvalidResultSet),
+        })
+        .collect()
+}
+
+/// Convert a JSON column value. Objects (JSON columns) are kept as JSON text.
+#[allow(clippy::cast_precision_loss)] // Only for integers above i64::MAX
+fn json_to_value(value: &json::Value) -> Value {
+    match value {
+        json::Value::Null => Value::Null,
+        json::Value::Bool(b) => Value::Bool(*b),
+        json::Value::Number(Number::I64(n)) => Value::Int(*n),
+        json::Value::Number(Number::U64(n)) => {
+            i64::try_from(*n).map_or(Value::Float(*n as f64), Value::Int)
+        },
+        json::Value::Number(Number::F64(f)) => Value::Float(*f),
+        json::Value::String(s) => Value::String(s.clone()),
+        json::Value::Array(items) => Value::Array(items.iter().map(json_to_value).collect()),
+        json::Value::Object(_) => Value::String(json::to_string(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vec<Row> {
+        rows_from_json(&json::from_str(text).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_rows_from_json() {
+        let rows = parse(r#"[{"id": 1, "name": "Alice"}, {"id": 2, "name": "Bob"}]"#);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].value("name"), Some(&Value::String("Bob".into())));
+
+        let wrapped = parse(r#"{"rows": [{"id": 1}], "rowCount": 1}"#);
+        assert_eq!(wrapped[0].get::<i64>("id"), Ok(1));
+    }
+
+    #[test]
+    fn test_rows_from_json_invalid() {
+        let invalid = json::from_str(r#"{"id": 1}"#).unwrap();
+        assert_eq!(rows_from_json(&invalid), Err(RowError::InvalidResultSet));
+        let invalid = json::from_str("[1, 2]").unwrap();
+        assert_eq!(rows_from_json(&invalid), Err(RowError::InvalidResultSet));
+    }
+
+    #[test]
+    fn test_get_conversions() {
+        let row = Row::new()
+            .with("count", Value::String("42".into()))
+            .with("ratio", Value::Int(2))
+            .with("active", Value::Int(1))
+            .with("tags", Value::Array(vec![Value::String("a".into())]))
+            .with("note", Value::Null);
+
+        assert_eq!(row.get::<i32>("count"), Ok(42));
+        assert_eq!(row.get::<f64>("ratio"), Ok(2.0));
+        assert_eq!(row.get::<bool>("active"), Ok(true));
+        assert_eq!(row.get::<Vec<String>>("tags"), Ok(vec!["a".to_string()]));
+        assert_eq!(row.get::<Option<String>>("note"), Ok(None));
+        assert_eq!(row.get::<Option<String>>("missing"), Ok(None));
+    }
+
+    #[test]
+    fn test_get_errors() {
+        let row = Row::new()
+            .with("id", Value::String("abc".into()))
+            .with("note", Value::Null)
+            .with("big", Value::Int(300));
+
+        assert_eq!(
+            row.get::<i64>("id"),
+            Err(RowError::InvalidType {
+                column: "id".into(),
+                expected: "integer",
+            })
+        );
+        assert!(matches!(
+            row.get::<String>("note"),
+            Err(RowError::InvalidType { .. })
+        ));
+        assert!(matches!(
+            row.get::<u8>("big"),
+            Err(RowError::InvalidType { .. })
+        ));
+        assert_eq!(
+            row.get::<String>("missing"),
+            Err(RowError::MissingColumn("missing".into()))
+        );
+    }
+
+    #[test]
+    fn test_json_column_kept_as_text() {
+        let rows = parse(r#"[{"meta": {"a": 1}}]"#);
+        assert_eq!(rows[0].get::<String>("meta"), Ok(r#"{"a":1}"#.to_string()));
+    }
+}
@@ -1,6 +1,7 @@
 //! Filter validation logic for user-provided filters.
 
-use crate::{Filter, Operator, Value};
+use super::column::is_valid_sql_identifier;
+use crate::{Dialect, Filter, FilterExpr, Operator, QueryBuilder, Value};
 use std::fmt;
 
 /// Maximum number of value nodes to validate (defense-in-depth).
@@ -108,13 +109,71 @@ impl FilterValidator {
     /// Validate a filter against the configured rules.
     ///
     /// Returns an error if:
-    /// - Field is not in the allowed list (when list is not empty)
+    /// - Field is not in the allowed list (when list is not empty), or is
+    ///   not a valid SQL identifier (when it is)
     /// - Operator is in the denied list
     /// - Array nesting depth exceeds maximum
     pub fn validate(&self, filter: &Filter) -> Result<(), ValidationError> {
         self.validate_with_depth(filter, 0)
     }
 
+    /// Validate a filter expression, walking nested AND/OR/NOT groups.
+    ///
+    /// Every simple filter is checked like [`validate`](Self::validate).
+    /// Each logical group adds one level of depth, counted together with
+    /// array nesting in the values below it, and the whole tree is subject
+    /// to the node count limit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mik_sql::{FilterValidator, parse_filter};
+    ///
+    /// let validator = FilterValidator::new().allow_fields(&["role", "status"]);
+    ///
+    /// let ok = parse_filter(r#"{"$or": [{"role": "admin"}, {"status": "active"}]}"#).unwrap();
+    /// assert!(validator.validate_expr(&ok).is_ok());
+    ///
+    /// // Fields inside logical groups are checked too
+    /// let hidden = parse_filter(r#"{"$not": {"password": "x"}}"#).unwrap();
+    /// assert!(validator.validate_expr(&hidden).is_err());
+    /// ```
+    pub fn validate_expr(&self, expr: &FilterExpr) -> Result<(), ValidationError> {
+        let mut node_count = 0;
+        self.validate_expr_with_depth(expr, 0, &mut node_count)
+    }
+
+    /// Internal expression validation with depth and node count tracking.
+    fn validate_expr_with_depth(
+        &self,
+        expr: &FilterExpr,
+        depth: usize,
+        count: &mut usize,
+    ) -> Result<(), ValidationError> {
+        *count += 1;
+        if *count > MAX_VALUE_NODES {
+            return Err(ValidationError::TooManyNodes {
+                max: MAX_VALUE_NODES,
+            });
+        }
+
+        match expr {
+            FilterExpr::Simple(filter) => self.validate_with_depth(filter, depth),
+            FilterExpr::Compound(compound) => {
+                if depth + 1 > self.max_depth {
+                    return Err(ValidationError::NestingTooDeep {
+                        max: self.max_depth,
+                        actual: depth + 1,
+                    });
+                }
+                for inner in &compound.filters {
+                    self.validate_expr_with_depth(inner, depth + 1, count)?;
+                }
+                Ok(())
+            },
+        }
+    }
+
     /// Internal validation with depth tracking.
     fn validate_with_depth(&self, filter: &Filter, depth: usize) -> Result<(), ValidationError> {
         // Check nesting depth
@@ -125,8 +184,13 @@ impl FilterValidator {
             });
         }
 
-        // Check field whitelist (only if not empty)
-        if !self.allowed_fields.is_empty() && !self.allowed_fields.contains(&filter.field) {
+        // Check field whitelist, or identifier syntax when there is none
+        let field_ok = if self.allowed_fields.is_empty() {
+            is_valid_sql_identifier(&filter.field)
+        } else {
+            self.allowed_fields.contains(&filter.field)
+        };
+        if !field_ok {
             return Err(ValidationError::FieldNotAllowed {
                 field: filter.field.clone(),
                 allowed: self.allowed_fields.clone(),
@@ -219,6 +283,15 @@ pub enum ValidationError {
         /// The maximum allowed node count.
         max: usize,
     },
+    /// Too many sort keys.
+    TooManySortKeys {
+        /// The maximum allowed number of sort keys.
+        max: usize,
+        /// The number of sort keys requested.
+        actual: usize,
+    },
+    /// Malformed or conflicting pagination parameters.
+    InvalidPagination(String),
 }
 
 impl fmt::Display for ValidationError {
@@ -257,6 +330,10 @@ impl fmt::Display for ValidationError {
                     "filter contains too many value nodes (max {max}, DoS prevention)"
                 )
             },
+            Self::TooManySortKeys { max, actual } => {
+                write!(f, "{actual} sort keys exceed maximum {max}")
+            },
+            Self::InvalidPagination(msg) => write!(f, "invalid pagination: {msg}"),
         }
     }
 }
@@ -317,6 +394,56 @@ pub fn merge_filters(
     Ok(result)
 }
 
+/// User filters accepted by the `merge:` option of `sql_read!`.
+///
+/// A list of filters is ANDed with the query's filters; a [`FilterExpr`]
+/// keeps its AND/OR/NOT structure. Called by generated code.
+#[doc(hidden)]
+pub trait MergeFilters {
+    /// Validate every filter, including those inside logical groups.
+    fn validate_merge(&self, validator: &FilterValidator) -> Result<(), ValidationError>;
+
+    /// Add the filters to a query.
+    fn apply_merge<D: Dialect>(&self, builder: QueryBuilder<D>) -> QueryBuilder<D>;
+}
+
+impl MergeFilters for [Filter] {
+    fn validate_merge(&self, validator: &FilterValidator) -> Result<(), ValidationError> {
+        self.iter()
+            .try_for_each(|filter| validator.validate(filter))
+    }
+
+    fn apply_merge<D: Dialect>(&self, builder: QueryBuilder<D>) -> QueryBuilder<D> {
+        self.iter().fold(builder, |builder, f| {
+            builder.filter(f.field.clone(), f.op, f.value.clone())
+        })
+    }
+}
+
+impl MergeFilters for FilterExpr {
+    fn validate_merge(&self, validator: &FilterValidator) -> Result<(), ValidationError> {
+        validator.validate_expr(self)
+    }
+
+    fn apply_merge<D: Dialect>(&self, builder: QueryBuilder<D>) -> QueryBuilder<D> {
+        builder.and_filter_expr(self.clone())
+    }
+}
+
+impl<T: MergeFilters> MergeFilters for Option<T> {
+    fn validate_merge(&self, validator: &FilterValidator) -> Result<(), ValidationError> {
+        self.as_ref()
+            .map_or(Ok(()), |filters| filters.validate_merge(validator))
+    }
+
+    fn apply_merge<D: Dialect>(&self, builder: QueryBuilder<D>) -> QueryBuilder<D> {
+        match self {
+            Some(filters) => filters.apply_merge(builder),
+            None => builder,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -850,8 +977,8 @@ mod tests {
         // Should fail - field not in whitelist
         assert!(validator.validate(&filter).is_err());
 
-        // Even without whitelist, the field goes through identifier validation
-        // when the query is built
+        // Without a whitelist, the field must still be a valid identifier
+        assert!(FilterValidator::new().validate(&filter).is_err());
     }
 
     #[test]
@@ -877,4 +1004,79 @@ mod tests {
         };
         assert!(validator.validate(&filter).is_ok());
     }
+
+    #[test]
+    fn test_validate_expr_walks_compound_tree() {
+        use crate::builder::{and, not, or, simple};
+
+        let validator = FilterValidator::new().allow_fields(&["role", "status"]);
+        let ok = and(vec![
+            simple("status", Operator::Eq, Value::String("active".into())),
+            or(vec![
+                simple("role", Operator::Eq, Value::String("admin".into())),
+                not(simple("role", Operator::Eq, Value::String("guest".into()))),
+            ]),
+        ]);
+        assert!(validator.validate_expr(&ok).is_ok());
+
+        // Disallowed field hidden inside NOT inside OR
+        let hidden = or(vec![
+            simple("role", Operator::Eq, Value::String("admin".into())),
+            not(simple("password", Operator::Eq, Value::String("x".into()))),
+        ]);
+        assert!(matches!(
+            validator.validate_expr(&hidden),
+            Err(ValidationError::FieldNotAllowed { field, .. }) if field == "password"
+        ));
+
+        // Denied operator deep in the tree
+        let regex = and(vec![or(vec![simple(
+            "role",
+            Operator::Regex,
+            Value::String(".*".into()),
+        )])]);
+        assert!(matches!(
+            validator.validate_expr(&regex),
+            Err(ValidationError::OperatorDenied { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_expr_depth_limit() {
+        use crate::builder::{not, simple};
+
+        let mut expr = simple("status", Operator::Eq, Value::Int(1));
+        for _ in 0..3 {
+            expr = not(expr);
+        }
+        assert!(
+            FilterValidator::new()
+                .max_depth(3)
+                .validate_expr(&expr)
+                .is_ok()
+        );
+        assert_eq!(
+            FilterValidator::new().max_depth(2).validate_expr(&expr),
+            Err(ValidationError::NestingTooDeep { max: 2, actual: 3 })
+        );
+
+        // Group depth and value depth add up
+        let nested_value = not(simple(
+            "tags",
+            Operator::In,
+            Value::Array(vec![Value::Array(vec![Value::Int(1)])]),
+        ));
+        assert!(
+            FilterValidator::new()
+                .max_depth(3)
+                .validate_expr(&nested_value)
+                .is_ok()
+        );
+        assert!(
+            FilterValidator::new()
+                .max_depth(2)
+                .validate_expr(&nested_value)
+                .is_err()
+        );
+    }
 }
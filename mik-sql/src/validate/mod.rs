@@ -3,6 +3,7 @@
 //! This module provides validation for:
 //! - User-provided filters (field whitelisting, operator blacklisting)
 //! - SQL identifiers (table names, column names) to prevent injection
+//! - User-provided sort fields (field whitelisting, key count limits)
 //! - Nesting depth limits for complex queries
 //!
 //! # Example
@@ -35,8 +36,10 @@
 mod column;
 mod expression;
 mod filter;
+mod sort;
 
 // Re-export all public items
 pub use column::{assert_valid_sql_identifier, is_valid_sql_identifier};
 pub use expression::{assert_valid_sql_expression, is_valid_sql_expression};
-pub use filter::{FilterValidator, ValidationError, merge_filters};
+pub use filter::{FilterValidator, MergeFilters, ValidationError, merge_filters};
+pub use sort::SortValidator;
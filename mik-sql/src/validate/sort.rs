@@ -0,0 +1,177 @@
+//! Sort validation for user-provided `ORDER BY` fields.
+
+use super::column::is_valid_sql_identifier;
+use super::filter::ValidationError;
+use crate::{SortDir, SortField};
+
+/// Validation rules for user-provided sort fields.
+///
+/// The sort counterpart to [`FilterValidator`](crate::FilterValidator):
+/// a whitelist of sortable fields and a cap on the number of sort keys,
+/// since every key adds work for the database.
+///
+/// # Example
+///
+/// ```
+/// use mik_sql::{SortDir, SortValidator, postgres};
+///
+/// let validator = SortValidator::new().allow_fields(&["name", "created_at"]);
+/// let sorts = validator.parse("-created_at,name").unwrap();
+/// assert_eq!(sorts[0].dir, SortDir::Desc);
+///
+/// let result = postgres("users").fields(&["id"]).sorts(&sorts).build();
+/// assert!(result.sql.contains("ORDER BY created_at DESC, name ASC"));
+///
+/// assert!(validator.parse("password").is_err());
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SortValidator {
+    /// Allowed field names (whitelist). Empty = allow all valid identifiers.
+    pub allowed_fields: Vec<String>,
+    /// Maximum number of sort keys.
+    pub max_keys: usize,
+}
+
+impl SortValidator {
+    /// Create a new validator with defaults.
+    ///
+    /// Defaults:
+    /// - No field restrictions (any valid SQL identifier)
+    /// - Max sort keys: 3
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            allowed_fields: Vec::new(),
+            max_keys: 3,
+        }
+    }
+
+    /// Set allowed fields (whitelist).
+    ///
+    /// Only fields in this list can be sorted on.
+    /// If empty, all valid SQL identifiers are allowed.
+    #[must_use]
+    pub fn allow_fields(mut self, fields: &[&str]) -> Self {
+        self.allowed_fields = fields.iter().map(|s| (*s).to_string()).collect();
+        self
+    }
+
+    /// Set the maximum number of sort keys. Default is 3.
+    #[must_use]
+    pub const fn max_keys(mut self, max: usize) -> Self {
+        self.max_keys = max;
+        self
+    }
+
+    /// Parse and validate a sort string like `name,-created_at`.
+    ///
+    /// Fields prefixed with `-` sort descending; `+` or no prefix sorts
+    /// ascending. Empty segments are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if a field is not allowed or there are
+    /// more than [`max_keys`](Self::max_keys) keys.
+    pub fn parse(&self, sort: &str) -> Result<Vec<SortField>, ValidationError> {
+        let sorts: Vec<SortField> = sort
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| match part.strip_prefix('-') {
+                Some(field) => SortField::new(field, SortDir::Desc),
+                None => SortField::new(part.strip_prefix('+').unwrap_or(part), SortDir::Asc),
+            })
+            .collect();
+        self.validate(&sorts)?;
+        Ok(sorts)
+    }
+
+    /// Validate sort fields against the configured rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if a field is not allowed or not a valid
+    /// identifier, or there are too many sort keys.
+    pub fn validate(&self, sorts: &[SortField]) -> Result<(), ValidationError> {
+        if sorts.len() > self.max_keys {
+            return Err(ValidationError::TooManySortKeys {
+                max: self.max_keys,
+                actual: sorts.len(),
+            });
+        }
+
+        for sort in sorts {
+            let allowed = if self.allowed_fields.is_empty() {
+                is_valid_sql_identifier(&sort.field)
+            } else {
+                self.allowed_fields.contains(&sort.field)
+            };
+            if !allowed {
+                return Err(ValidationError::FieldNotAllowed {
+                    field: sort.field.clone(),
+                    allowed: self.allowed_fields.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SortValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_directions() {
+        let sorts = SortValidator::new()
+            .parse("-created_at, +name,,id")
+            .unwrap();
+        assert_eq!(
+            sorts,
+            vec![
+                SortField::new("created_at", SortDir::Desc),
+                SortField::new("name", SortDir::Asc),
+                SortField::new("id", SortDir::Asc),
+            ]
+        );
+        assert_eq!(SortValidator::new().parse(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_whitelist() {
+        let validator = SortValidator::new().allow_fields(&["name"]);
+        assert!(validator.parse("-name").is_ok());
+        assert_eq!(
+            validator.parse("password"),
+            Err(ValidationError::FieldNotAllowed {
+                field: "password".into(),
+                allowed: vec!["name".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_rejects_injection_without_whitelist() {
+        let validator = SortValidator::new();
+        assert!(validator.parse("name; DROP TABLE users").is_err());
+        assert!(validator.parse("1=1").is_err());
+    }
+
+    #[test]
+    fn test_max_keys() {
+        let validator = SortValidator::new().max_keys(2);
+        assert!(validator.parse("a,b").is_ok());
+        assert_eq!(
+            validator.parse("a,b,c"),
+            Err(ValidationError::TooManySortKeys { max: 2, actual: 3 })
+        );
+    }
+}
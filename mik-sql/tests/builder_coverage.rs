@@ -57,14 +57,24 @@ mod filter_tests {
 
     #[test]
     fn test_not_with_empty_filters() {
-        // NOT with empty filters should produce NOT () with empty inner
+        // An empty AND is true, so NOT of it matches nothing
         let result = postgres("users")
             .fields(&["id"])
             .filter_expr(not(and(vec![])))
             .build();
 
-        // NOT with empty inner should produce NOT ()
-        assert!(result.sql.contains("NOT"), "SQL: {}", result.sql);
+        assert!(result.sql.contains("NOT (1=1)"), "SQL: {}", result.sql);
+    }
+
+    #[test]
+    fn test_empty_or_matches_nothing() {
+        let result = postgres("users")
+            .fields(&["id"])
+            .filter_expr(or(vec![]))
+            .build();
+
+        assert!(result.sql.ends_with("WHERE 1=0"), "SQL: {}", result.sql);
+        assert!(result.params.is_empty());
     }
 
     #[test]
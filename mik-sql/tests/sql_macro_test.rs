@@ -241,7 +241,10 @@ fn test_sql_like_operator() {
         },
     });
 
-    assert_eq!(sql, "SELECT id, name FROM users WHERE name LIKE $1");
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM users WHERE name LIKE $1 ESCAPE '\\'"
+    );
     assert_eq!(params.len(), 1);
 }
 
@@ -272,7 +275,10 @@ fn test_sql_starts_with() {
         },
     });
 
-    assert_eq!(sql, "SELECT id, name FROM users WHERE name LIKE $1 || '%'");
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM users WHERE name LIKE $1 || '%' ESCAPE '\\'"
+    );
     assert_eq!(params.len(), 1);
     match &params[0] {
         Value::String(v) => assert_eq!(v, "A"),
@@ -289,7 +295,10 @@ fn test_sql_ends_with() {
         },
     });
 
-    assert_eq!(sql, "SELECT id, name FROM users WHERE email LIKE '%' || $1");
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM users WHERE email LIKE '%' || $1 ESCAPE '\\'"
+    );
     assert_eq!(params.len(), 1);
     match &params[0] {
         Value::String(v) => assert_eq!(v, "@example.com"),
@@ -308,7 +317,7 @@ fn test_sql_contains() {
 
     assert_eq!(
         sql,
-        "SELECT id, name FROM users WHERE description LIKE '%' || $1 || '%'"
+        "SELECT id, name FROM users WHERE description LIKE '%' || $1 || '%' ESCAPE '\\'"
     );
     assert_eq!(params.len(), 1);
     match &params[0] {
@@ -350,7 +359,10 @@ fn test_sql_starts_with_snake_case() {
         },
     });
 
-    assert_eq!(sql, "SELECT id, name FROM users WHERE name LIKE $1 || '%'");
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM users WHERE name LIKE $1 || '%' ESCAPE '\\'"
+    );
     assert_eq!(params.len(), 1);
 }
 
@@ -363,7 +375,10 @@ fn test_sql_ends_with_snake_case() {
         },
     });
 
-    assert_eq!(sql, "SELECT id, name FROM users WHERE name LIKE '%' || $1");
+    assert_eq!(
+        sql,
+        "SELECT id, name FROM users WHERE name LIKE '%' || $1 ESCAPE '\\'"
+    );
     assert_eq!(params.len(), 1);
 }
 
@@ -1121,6 +1136,41 @@ fn test_sql_merge_empty_filters() {
     assert_eq!(params.len(), 1);
 }
 
+#[test]
+fn test_sql_merge_filter_expr_keeps_or() {
+    let user_filter =
+        mik_sql::parse_filter(r#"{"$or": [{"name": "Alice"}, {"status": "vip"}]}"#).unwrap();
+
+    let result = sql_read!(users {
+        select: [id, name],
+        filter: { active: true },
+        merge: user_filter,
+        allow: [name, status],
+    });
+
+    let (sql, params) = result.unwrap();
+    assert!(
+        sql.contains("WHERE (active = $1 AND (name = $2 OR status = $3))"),
+        "SQL: {sql}"
+    );
+    assert_eq!(params.len(), 3);
+}
+
+#[test]
+fn test_sql_merge_filter_expr_nested_field_denied() {
+    let user_filter =
+        mik_sql::parse_filter(r#"{"$or": [{"name": "Alice"}, {"$not": {"password": "x"}}]}"#)
+            .unwrap();
+
+    let result = sql_read!(users {
+        select: [id, name],
+        merge: user_filter,
+        allow: [name],
+    });
+
+    assert!(result.is_err());
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // sql_insert! MACRO TESTS
 // ═══════════════════════════════════════════════════════════════════════════
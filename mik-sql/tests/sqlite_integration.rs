@@ -876,3 +876,63 @@ fn test_sqlite_special_characters_in_string() -> Result<()> {
     assert_eq!(users[0].1, "O'Brien");
     Ok(())
 }
+
+// =============================================================================
+// ROW MAPPING
+// =============================================================================
+
+#[derive(mik_sql::Row)]
+struct UserRow {
+    id: i64,
+    #[row(rename = "name")]
+    display_name: String,
+    active: bool,
+    deleted_at: Option<String>,
+    #[row(default)]
+    age: i64,
+}
+
+// Convert a rusqlite row to a mik_sql::Row, the way a database sidecar would.
+fn to_row(row: &rusqlite::Row<'_>) -> Result<mik_sql::Row> {
+    use rusqlite::types::ValueRef;
+
+    let mut mapped = mik_sql::Row::new();
+    for (i, column) in row.as_ref().column_names().into_iter().enumerate() {
+        let value = match row.get_ref(i)? {
+            ValueRef::Integer(n) => Value::Int(n),
+            ValueRef::Real(f) => Value::Float(f),
+            ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+            ValueRef::Null | ValueRef::Blob(_) => Value::Null,
+        };
+        mapped = mapped.with(column, value);
+    }
+    Ok(mapped)
+}
+
+#[test]
+fn test_sqlite_derive_row() -> Result<()> {
+    let conn = create_test_db()?;
+    seed_test_data(&conn)?;
+    conn.execute("UPDATE users SET age = NULL WHERE name = 'Eve'", [])?;
+
+    let (sql, params) = sql_read!(
+        sqlite,
+        users {
+            select: [id, name, active, deleted_at, age],
+            order: id,
+        }
+    );
+
+    let rows = query_with_values(&conn, &sql, &params, to_row)?;
+    let users: Vec<UserRow> = mik_sql::map_rows(&rows).expect("rows map to UserRow");
+
+    assert_eq!(users.len(), 5);
+    assert_eq!(users[0].id, 1);
+    assert_eq!(users[0].display_name, "Alice");
+    assert!(users[0].active);
+    assert!(!users[2].active);
+    assert_eq!(users[0].deleted_at, None);
+    assert_eq!(users[0].age, 30);
+    assert_eq!(users[4].age, 0); // NULL falls back to the default
+    Ok(())
+}
@@ -0,0 +1,42 @@
+#!/bin/bash
+# Hot-path benchmark regression check for mik-sdk
+#
+# Usage:
+#   scripts/bench-compare.sh save [baseline]   # record a baseline (default: main)
+#   scripts/bench-compare.sh [baseline]        # compare against it, fail on regression
+#
+# Typical release flow: save a baseline on the previous release tag, check out
+# the release candidate, then compare.
+
+set -e
+
+# Routing dispatch, lazy vs eager JSON, URL decoding and header lookup
+FILTER="routing|json_lazy_vs_eager|url_decoding|header_lookup"
+
+MODE="compare"
+if [ "$1" = "save" ]; then
+    MODE="save"
+    shift
+fi
+BASELINE="${1:-main}"
+
+if [ "$MODE" = "save" ]; then
+    echo "=== Saving baseline '$BASELINE' ==="
+    cargo bench -p mik-sdk -- "$FILTER" --save-baseline "$BASELINE"
+    exit 0
+fi
+
+echo "=== Comparing against baseline '$BASELINE' ==="
+OUTPUT="target/bench-compare.txt"
+mkdir -p target
+cargo bench -p mik-sdk -- "$FILTER" --baseline "$BASELINE" --noise-threshold 0.05 | tee "$OUTPUT"
+
+if grep -q "Performance has regressed" "$OUTPUT"; then
+    echo ""
+    echo "=== Regressions ==="
+    grep -B 3 "Performance has regressed" "$OUTPUT"
+    exit 1
+fi
+
+echo ""
+echo "=== No regressions ==="